@@ -7,7 +7,7 @@ use anyhow::{Context as AnyhowContext, Result};
 use clap::{ArgAction, Parser, Subcommand};
 use ferrum_codegen::generators::GeneratorConfig;
 use serde_json::{Map, Value};
-use ferrum_context::{DefaultFhirContext, FhirContext};
+use ferrum_context::{ConflictPolicy, DefaultFhirContext, FhirContext};
 use ferrum_models::{Snapshot, StructureDefinition};
 use ferrum_registry_client::RegistryClient;
 use ferrum_snapshot::{
@@ -99,6 +99,15 @@ enum Commands {
         /// Generate serde derive/attributes.
         #[arg(long, default_value_t = true, action = ArgAction::Set)]
         serde: bool,
+        /// Generate conversions between models and serde_json::Value (requires --serde).
+        #[arg(long, default_value_t = false, action = ArgAction::Set)]
+        value_conversions: bool,
+        /// Additional derive for generated structs (e.g. Default, Hash). Repeatable.
+        #[arg(long = "derive", value_name = "TRAIT")]
+        derives: Vec<String>,
+        /// Generate a builder per struct that checks required fields.
+        #[arg(long, default_value_t = false, action = ArgAction::Set)]
+        builders: bool,
         /// Optional module path prefix for generated modules.
         #[arg(long)]
         module_prefix: Option<String>,
@@ -273,17 +282,20 @@ async fn main() -> Result<()> {
             packages,
             docs,
             serde,
+            value_conversions,
+            derives,
+            builders,
             module_prefix,
         } => {
-            run_codegen(
-                &output,
-                &fhir_version,
-                &packages,
-                docs,
-                serde,
+            let config = GeneratorConfig {
+                generate_docs: docs,
+                generate_serde: serde,
+                generate_value_conversions: value_conversions,
+                extra_derives: derives,
+                generate_builders: builders,
                 module_prefix,
-            )
-            .await?;
+            };
+            run_codegen(&output, &fhir_version, &packages, config).await?;
         }
     }
 
@@ -577,18 +589,10 @@ async fn run_codegen(
     output: &Path,
     fhir_version: &str,
     packages: &[String],
-    docs: bool,
-    serde: bool,
-    module_prefix: Option<String>,
+    config: GeneratorConfig,
 ) -> Result<()> {
     let context = create_context(fhir_version, packages).await?;
 
-    let config = GeneratorConfig {
-        generate_docs: docs,
-        generate_serde: serde,
-        module_prefix,
-    };
-
     let generated = ferrum_codegen::generate_rust_from_context(&context, output, config)
         .with_context(|| "Failed to generate Rust models from context".to_string())?;
 
@@ -635,7 +639,10 @@ async fn create_context(
     combined_packages
         .retain(|pkg| seen.insert(format!("{}#{}", pkg.manifest.name, pkg.manifest.version)));
 
-    Ok(DefaultFhirContext::from_packages(combined_packages))
+    Ok(DefaultFhirContext::from_packages(
+        combined_packages,
+        ConflictPolicy::default(),
+    ))
 }
 
 fn parse_name_version(s: &str) -> Result<(String, String)> {
@@ -0,0 +1,12 @@
+-- ============================================================================
+-- SEARCH PARAMETER INDEX WATERMARK
+-- Tracks search parameters whose index may not cover every stored resource
+-- ============================================================================
+
+-- Resources last written before `indexed_from` were indexed without this parameter,
+-- so searches on it can miss them until a reindex runs. NULL means the index is
+-- complete. Existing rows predate the watermark and are treated as complete.
+ALTER TABLE search_parameters ADD COLUMN indexed_from TIMESTAMPTZ;
+ALTER TABLE search_parameters ALTER COLUMN indexed_from SET DEFAULT NOW();
+
+COMMENT ON COLUMN search_parameters.indexed_from IS 'Resources written before this instant may lack index rows for the parameter; NULL once reindexed';
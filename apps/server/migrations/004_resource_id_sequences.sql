@@ -0,0 +1,11 @@
+-- ============================================================================
+-- SEQUENTIAL RESOURCE IDS
+-- Per-type counters backing `fhir.id_strategy = sequential`
+-- ============================================================================
+
+CREATE TABLE resource_id_sequences (
+    resource_type VARCHAR(64) PRIMARY KEY,
+    last_value BIGINT NOT NULL
+);
+
+COMMENT ON TABLE resource_id_sequences IS 'Last id handed out per resource type when ids are assigned sequentially';
@@ -0,0 +1,23 @@
+-- ============================================================================
+-- SEARCH PARAMETER STATUS
+-- Publication status and experimental flag of the defining SearchParameter
+-- ============================================================================
+
+-- `active` only says whether the parameter is searchable; searches on parameters that
+-- are retired or experimental still succeed but warn the client.
+ALTER TABLE search_parameters ADD COLUMN status VARCHAR(20);
+ALTER TABLE search_parameters ADD COLUMN experimental BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- Backfill from the stored SearchParameter resources the rows were registered from.
+UPDATE search_parameters sp
+SET status = r.resource->>'status',
+    experimental = COALESCE((r.resource->>'experimental')::BOOLEAN, FALSE)
+FROM resources r
+WHERE r.resource_type = 'SearchParameter'
+    AND r.is_current = TRUE
+    AND r.deleted = FALSE
+    AND r.resource->>'url' = sp.url
+    AND r.resource->>'code' = sp.code;
+
+COMMENT ON COLUMN search_parameters.status IS 'SearchParameter.status (draft | active | retired | unknown)';
+COMMENT ON COLUMN search_parameters.experimental IS 'SearchParameter.experimental; searches on the parameter carry a warning';
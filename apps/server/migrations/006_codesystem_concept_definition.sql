@@ -0,0 +1,38 @@
+-- ============================================================================
+-- CODESYSTEM CONCEPT DEFINITIONS
+-- Concept definitions returned by $lookup
+-- ============================================================================
+
+ALTER TABLE codesystem_concepts ADD COLUMN definition TEXT;
+
+-- Backfill from the stored CodeSystem resources the concepts were indexed from, including
+-- concepts nested under `concept.concept`.
+WITH RECURSIVE concepts AS (
+    SELECT r.resource->>'url' AS system,
+        r.resource->>'version' AS version,
+        c.concept
+    FROM resources r
+    CROSS JOIN LATERAL jsonb_array_elements(
+        CASE WHEN jsonb_typeof(r.resource->'concept') = 'array'
+            THEN r.resource->'concept' ELSE '[]'::JSONB END
+    ) AS c(concept)
+    WHERE r.resource_type = 'CodeSystem'
+        AND r.is_current = TRUE
+        AND r.deleted = FALSE
+    UNION ALL
+    SELECT parent.system, parent.version, c.concept
+    FROM concepts parent
+    CROSS JOIN LATERAL jsonb_array_elements(
+        CASE WHEN jsonb_typeof(parent.concept->'concept') = 'array'
+            THEN parent.concept->'concept' ELSE '[]'::JSONB END
+    ) AS c(concept)
+)
+UPDATE codesystem_concepts cc
+SET definition = concepts.concept->>'definition'
+FROM concepts
+WHERE concepts.system = cc.system
+    AND concepts.version IS NOT DISTINCT FROM cc.version
+    AND concepts.concept->>'code' = cc.code
+    AND concepts.concept ? 'definition';
+
+COMMENT ON COLUMN codesystem_concepts.definition IS 'Concept definition text (CodeSystem.concept.definition)';
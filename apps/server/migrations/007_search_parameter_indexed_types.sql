@@ -0,0 +1,12 @@
+-- ============================================================================
+-- SEARCH PARAMETER PER-TYPE INDEX COVERAGE
+-- Type-level reindexes of parameters shared by every resource type
+-- ============================================================================
+
+-- A type-level reindex only covers one resource type, so it cannot clear the
+-- `indexed_from` watermark of a Resource or DomainResource parameter. The types it
+-- covered are recorded here instead; the watermark no longer applies to them. Reset
+-- whenever the watermark is renewed, and cleared together with it.
+ALTER TABLE search_parameters ADD COLUMN indexed_types TEXT[] NOT NULL DEFAULT '{}';
+
+COMMENT ON COLUMN search_parameters.indexed_types IS 'Resource types reindexed since indexed_from was set; the watermark does not apply to them';
@@ -102,7 +102,7 @@ impl ContentFormat {
 
     /// Check if this format is currently supported by the server
     pub fn is_supported(&self) -> bool {
-        matches!(self, Self::Json | Self::Xml)
+        matches!(self, Self::Json | Self::Xml | Self::Turtle)
     }
 }
 
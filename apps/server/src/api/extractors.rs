@@ -1,103 +1,52 @@
 //! Custom Axum extractors for FHIR content types.
 
+use crate::{config::FhirResourcesConfig, state::AppState};
 use axum::{
     async_trait,
     body::Bytes,
     extract::{FromRequest, Request},
-    http::{HeaderMap, StatusCode},
+    http::HeaderMap,
     response::{IntoResponse, Response},
 };
+use ferrum_format::XmlToJsonOptions;
 use serde_json::Value as JsonValue;
 
 /// Axum extractor that accepts both `application/fhir+json` and `application/fhir+xml`
 /// (plus their generic variants `application/json` and `application/xml`/`text/xml`).
 ///
-/// XML bodies are converted to JSON via `ferrum_format::xml_to_json` so that
+/// XML bodies are converted to JSON via `ferrum_format::xml_to_json_with` so that
 /// downstream handlers always work with `serde_json::Value`.
 pub struct FhirBody(pub JsonValue);
 
 /// Error type for [`FhirBody`] extraction failures.
-pub struct FhirBodyRejection {
-    status: StatusCode,
-    message: String,
-}
+///
+/// Rendered through [`crate::Error`] so rejected bodies get the same OperationOutcome,
+/// status mapping and content type as handler errors.
+pub struct FhirBodyRejection(crate::Error);
 
 impl IntoResponse for FhirBodyRejection {
     fn into_response(self) -> Response {
-        let body = serde_json::json!({
-            "resourceType": "OperationOutcome",
-            "issue": [{
-                "severity": "error",
-                "code": "invalid",
-                "diagnostics": self.message,
-            }]
-        });
-        (self.status, axum::Json(body)).into_response()
+        self.0.into_response()
     }
 }
 
 #[async_trait]
-impl<S> FromRequest<S> for FhirBody
-where
-    S: Send + Sync,
-{
+impl FromRequest<AppState> for FhirBody {
     type Rejection = FhirBodyRejection;
 
-    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
-        let content_type = req
-            .headers()
-            .get("content-type")
-            .and_then(|v| v.to_str().ok())
-            .unwrap_or("")
-            .to_lowercase();
-
-        // Extract just the media type (before any ;charset= etc.)
-        let media_type = content_type.split(';').next().unwrap_or("").trim();
-
-        let is_xml = matches!(
-            media_type,
-            "application/fhir+xml" | "application/xml" | "text/xml"
-        );
+    async fn from_request(req: Request, state: &AppState) -> Result<Self, Self::Rejection> {
+        let headers = req.headers().clone();
 
         let bytes = Bytes::from_request(req, state).await.map_err(|e| {
-            FhirBodyRejection {
-                status: StatusCode::BAD_REQUEST,
-                message: format!("Failed to read request body: {}", e),
-            }
+            FhirBodyRejection(crate::Error::Validation(format!(
+                "Failed to read request body: {}",
+                e
+            )))
         })?;
 
-        if is_xml {
-            let xml_str = std::str::from_utf8(&bytes).map_err(|_| FhirBodyRejection {
-                status: StatusCode::BAD_REQUEST,
-                message: "Request body is not valid UTF-8".to_string(),
-            })?;
-
-            let json_str =
-                ferrum_format::xml_to_json(xml_str).map_err(|e| FhirBodyRejection {
-                    status: StatusCode::BAD_REQUEST,
-                    message: format!("Invalid FHIR XML: {}", e),
-                })?;
-
-            let value: JsonValue =
-                serde_json::from_str(&json_str).map_err(|e| FhirBodyRejection {
-                    status: StatusCode::BAD_REQUEST,
-                    message: format!(
-                        "Failed to parse converted XML as JSON (internal error): {}",
-                        e
-                    ),
-                })?;
-
-            Ok(FhirBody(value))
-        } else {
-            // Default: treat as JSON (covers application/fhir+json, application/json, and missing content-type)
-            let value: JsonValue =
-                serde_json::from_slice(&bytes).map_err(|e| FhirBodyRejection {
-                    status: StatusCode::BAD_REQUEST,
-                    message: format!("Invalid JSON in request body: {}", e),
-                })?;
-
-            Ok(FhirBody(value))
-        }
+        parse_fhir_body(&bytes, &headers, &state.config.fhir.resources)
+            .map(FhirBody)
+            .map_err(FhirBodyRejection)
     }
 }
 
@@ -117,13 +66,21 @@ fn is_xml_content_type(headers: &HeaderMap) -> bool {
 /// Parse a FHIR resource body from raw bytes, converting from XML if needed.
 ///
 /// Use this in handlers that manually read the request body instead of using
-/// the [`FhirBody`] extractor.
-pub fn parse_fhir_body(bytes: &[u8], headers: &HeaderMap) -> crate::Result<JsonValue> {
+/// the [`FhirBody`] extractor. XML narratives are sanitized when `resources` asks for it.
+pub fn parse_fhir_body(
+    bytes: &[u8],
+    headers: &HeaderMap,
+    resources: &FhirResourcesConfig,
+) -> crate::Result<JsonValue> {
     if is_xml_content_type(headers) {
         let xml_str = std::str::from_utf8(bytes).map_err(|_| {
             crate::Error::InvalidResource("Request body is not valid UTF-8".to_string())
         })?;
-        let json_str = ferrum_format::xml_to_json(xml_str)
+        let options = XmlToJsonOptions {
+            sanitize_narrative: resources.sanitize_xml_narrative,
+            ..Default::default()
+        };
+        let json_str = ferrum_format::xml_to_json_with(xml_str, options)
             .map_err(|e| crate::Error::InvalidResource(format!("Invalid FHIR XML: {}", e)))?;
         serde_json::from_str(&json_str).map_err(|e| {
             crate::Error::Internal(format!(
@@ -132,8 +89,35 @@ pub fn parse_fhir_body(bytes: &[u8], headers: &HeaderMap) -> crate::Result<JsonV
             ))
         })
     } else {
-        serde_json::from_slice(bytes).map_err(|e| {
-            crate::Error::InvalidResource(format!("Invalid JSON in request body: {}", e))
-        })
+        serde_json::from_slice(bytes)
+            .map_err(|e| crate::Error::InvalidResource(describe_json_error(&e, bytes)))
     }
 }
+
+/// Diagnostics for a malformed JSON body, e.g.
+/// `Invalid JSON in request body at line 3 column 7 (byte offset 42): EOF while parsing an object`.
+fn describe_json_error(error: &serde_json::Error, body: &[u8]) -> String {
+    if error.line() == 0 {
+        // Not a syntax error (e.g. an I/O error); serde_json has no position to report
+        return format!("Invalid JSON in request body: {}", error);
+    }
+
+    let message = error.to_string();
+    let location = format!(" at line {} column {}", error.line(), error.column());
+    let message = message.strip_suffix(&location).unwrap_or(&message);
+
+    // serde_json reports the 1-based line and column (in bytes) of the offending byte
+    let line_start: usize = body
+        .split(|b| *b == b'\n')
+        .take(error.line() - 1)
+        .map(|line| line.len() + 1)
+        .sum();
+
+    format!(
+        "Invalid JSON in request body at line {} column {} (byte offset {}): {}",
+        error.line(),
+        error.column(),
+        line_start + error.column().saturating_sub(1),
+        message
+    )
+}
@@ -4,7 +4,7 @@ use crate::services::admin::{AuditEventListQuery, SearchParameterListQuery, Tran
 use crate::{state::AppState, Result};
 use axum::{
     extract::Query,
-    extract::{Path, State},
+    extract::{Path, RawQuery, State},
     http::header,
     http::HeaderMap,
     http::StatusCode,
@@ -52,6 +52,39 @@ pub async fn get_search_hash_collisions(State(state): State<AppState>) -> Result
     Ok((StatusCode::OK, Json(status)).into_response())
 }
 
+/// Preview the SQL for a type-level search (`GET /admin/search/explain?type=...&...`).
+///
+/// The search is resolved but not executed; bind values carrying client data are redacted.
+pub async fn explain_search(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    RawQuery(query): RawQuery,
+) -> Result<Response> {
+    let (type_items, search_items): (Vec<_>, Vec<_>) =
+        url::form_urlencoded::parse(query.unwrap_or_default().as_bytes())
+            .into_owned()
+            .partition(|(key, _)| key == "type");
+    let Some((_, resource_type)) = type_items.into_iter().last() else {
+        return Err(crate::Error::Validation(
+            "Missing required 'type' query parameter".to_string(),
+        ));
+    };
+    if !crate::models::is_known_resource_type(&resource_type) {
+        return Err(crate::Error::Validation(format!(
+            "Invalid resource type: {}",
+            resource_type
+        )));
+    }
+
+    let params = crate::db::search::params::SearchParameters::from_items(&search_items)?;
+    let base_url = crate::api::url::base_url_from_headers(&headers, &state.config.server);
+    let explanation = state
+        .search_engine
+        .explain_search(&resource_type, &params, Some(&base_url))
+        .await?;
+    Ok((StatusCode::OK, Json(explanation)).into_response())
+}
+
 pub async fn list_search_parameters(
     State(state): State<AppState>,
     Query(query): Query<SearchParameterListQuery>,
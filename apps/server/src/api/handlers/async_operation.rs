@@ -0,0 +1,134 @@
+//! Asynchronous operation handlers (`Prefer: respond-async`, `$async-status`)
+//!
+//! The status endpoint shares the `$operation` route but is not a registered operation.
+//! See [`crate::services::async_operation`].
+
+use crate::{
+    api::url as api_url,
+    auth::Principal,
+    models::{OperationContext, OperationRequest},
+    queue::JobStatus,
+    services::async_operation::{self, AsyncOperationResult, ASYNC_OPERATION_JOB_TYPE},
+    state::AppState,
+    Result,
+};
+use axum::{
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use uuid::Uuid;
+
+/// Start an operation in the background
+///
+/// Returns 202 Accepted with a `Content-Location` pointing at the status endpoint.
+pub async fn kick_off(
+    state: &AppState,
+    headers: &HeaderMap,
+    request: OperationRequest,
+    query: &[(String, String)],
+    principal: Option<&Principal>,
+) -> Result<Response> {
+    let base_url = api_url::base_url_from_headers(headers, &state.config.server);
+    let request_path = match &request.context {
+        OperationContext::System => format!("{}/${}", base_url, request.operation_name),
+        OperationContext::Type(rt) => format!("{}/{}/${}", base_url, rt, request.operation_name),
+        OperationContext::Instance(rt, id) => {
+            format!("{}/{}/{}/${}", base_url, rt, id, request.operation_name)
+        }
+    };
+    let request_url = if query.is_empty() {
+        request_path
+    } else {
+        let mut serializer = url::form_urlencoded::Serializer::new(String::new());
+        serializer.extend_pairs(query.iter());
+        format!("{}?{}", request_path, serializer.finish())
+    };
+
+    let job_id = async_operation::kick_off(
+        state.job_queue.clone(),
+        state.operation_executor.clone(),
+        request,
+        request_url,
+        base_url.clone(),
+        principal,
+    )
+    .await?;
+
+    Ok((
+        StatusCode::ACCEPTED,
+        [(
+            header::CONTENT_LOCATION,
+            async_operation::status_url(&base_url, job_id),
+        )],
+    )
+        .into_response())
+}
+
+/// Report the status of an asynchronous request (GET [base]/$async-status?_jobId=...)
+///
+/// - 202 with `X-Progress` while the job is pending or running
+/// - 200 with a `batch-response` Bundle holding the operation's response once finished
+/// - 500 with an OperationOutcome if the job could not record a result
+///
+/// Requests by another principal or in another security-label scope get 404.
+pub async fn poll_status(
+    state: &AppState,
+    query: Vec<(String, String)>,
+    principal: Option<&Principal>,
+) -> Result<Response> {
+    let raw = query
+        .iter()
+        .find(|(k, _)| k == "_jobId")
+        .map(|(_, v)| v.as_str())
+        .ok_or_else(|| crate::Error::Validation("Missing _jobId parameter".to_string()))?;
+    let job_id = Uuid::parse_str(raw)
+        .map_err(|_| crate::Error::Validation(format!("Invalid _jobId: {}", raw)))?;
+
+    let job = state
+        .job_queue
+        .get_job(job_id)
+        .await?
+        .filter(|job| job.job_type == ASYNC_OPERATION_JOB_TYPE)
+        .filter(|job| {
+            async_operation::job_params(job).is_ok_and(|params| params.is_visible_to(principal))
+        })
+        .ok_or_else(|| crate::Error::NotFound(format!("Async request {} not found", job_id)))?;
+
+    match job.status {
+        JobStatus::Pending | JobStatus::Running | JobStatus::Retrying => Ok((
+            StatusCode::ACCEPTED,
+            [
+                ("x-progress", "in-progress".to_string()),
+                ("retry-after", "1".to_string()),
+            ],
+        )
+            .into_response()),
+        JobStatus::Failed => Err(crate::Error::Internal(format!(
+            "Async request {} failed: {}",
+            job_id,
+            job.error_message.unwrap_or_default()
+        ))),
+        JobStatus::Cancelled => Err(crate::Error::NotFound(format!(
+            "Async request {} was cancelled",
+            job_id
+        ))),
+        JobStatus::Completed => {
+            let result: AsyncOperationResult = job
+                .progress
+                .ok_or_else(|| {
+                    crate::Error::Internal("Completed async request has no result".to_string())
+                })
+                .and_then(|progress| {
+                    serde_json::from_value(progress).map_err(|e| {
+                        crate::Error::Internal(format!("Invalid async request result: {}", e))
+                    })
+                })?;
+            Ok((
+                StatusCode::OK,
+                Json(async_operation::response_bundle(&result)),
+            )
+                .into_response())
+        }
+    }
+}
@@ -22,19 +22,6 @@ use serde_json::Value as JsonValue;
 use std::collections::HashMap;
 use url::Url;
 
-fn build_base_url_from_headers(headers: &HeaderMap) -> String {
-    let scheme = headers
-        .get("x-forwarded-proto")
-        .and_then(|v| v.to_str().ok())
-        .unwrap_or("http");
-    let host = headers
-        .get("x-forwarded-host")
-        .or_else(|| headers.get("host"))
-        .and_then(|v| v.to_str().ok())
-        .unwrap_or("localhost");
-    format!("{}://{}/fhir", scheme, host)
-}
-
 /// Handle batch, transaction, and history bundle POST requests (POST /fhir/)
 ///
 /// Accepts FHIR Bundle with type 'batch', 'transaction', or 'history' and processes
@@ -74,7 +61,10 @@ pub async fn batch_transaction(
 
     let options = BundleRequestOptions {
         prefer_return,
-        base_url: Some(build_base_url_from_headers(&headers)),
+        base_url: Some(crate::api::url::base_url_from_headers(
+            &headers,
+            &state.config.server,
+        )),
     };
 
     let response_bundle = match bundle_type.as_str() {
@@ -126,7 +116,7 @@ pub async fn batch_transaction(
     let negotiation = ContentNegotiation::from_request(&query_params, &headers, &default_format);
     if !negotiation.format.is_supported() {
         return Err(crate::Error::Validation(format!(
-            "Unsupported format: {}. Supported formats: application/fhir+json, application/fhir+xml",
+            "Unsupported format: {}. Supported formats: application/fhir+json, application/fhir+xml, application/fhir+turtle",
             negotiation.format.mime_type()
         )));
     }
@@ -134,7 +124,7 @@ pub async fn batch_transaction(
     let formatter = ResourceFormatter::new(negotiation);
     let formatted_body = formatter
         .format_resource(response_bundle)
-        .map_err(|e| crate::Error::Internal(e.to_string()))?;
+        .map_err(crate::Error::from)?;
 
     let base_response = StatusCode::OK.into_response();
     let (mut parts, _) = base_response.into_parts();
@@ -0,0 +1,279 @@
+//! Bulk Data export handlers (`$export`, `$export-poll-status`, `$export-file`)
+//!
+//! These share the `$operation` routes but are not registered operations: the kick-off
+//! only enqueues a `bulk_export` job, and the status endpoint reports on that job.
+//! See https://hl7.org/fhir/uv/bulkdata/export.html
+
+use crate::{
+    api::{headers::prefer_respond_async, url as api_url},
+    models::is_known_resource_type,
+    queue::{JobPriority, JobStatus},
+    services::bulk_export::{
+        job_output_dir, BulkExportParams, BulkExportResult, BULK_EXPORT_JOB_TYPE,
+    },
+    state::AppState,
+    Result,
+};
+use axum::{
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+const NDJSON_CONTENT_TYPE: &str = "application/fhir+ndjson";
+
+/// Kick off an export (GET [base]/$export or GET [base]/{type}/$export)
+///
+/// Returns 202 Accepted with a `Content-Location` pointing at the status endpoint.
+pub async fn kick_off(
+    state: &AppState,
+    headers: &HeaderMap,
+    resource_type: Option<&str>,
+    query: Vec<(String, String)>,
+) -> Result<Response> {
+    if !prefer_respond_async(headers) {
+        return Err(crate::Error::Validation(
+            "$export requires the header 'Prefer: respond-async'".to_string(),
+        ));
+    }
+
+    let mut types: Vec<String> = Vec::new();
+    let mut since: Option<DateTime<Utc>> = None;
+
+    for (key, value) in &query {
+        match key.as_str() {
+            "_outputFormat" => {
+                if !matches!(
+                    value.as_str(),
+                    "application/fhir+ndjson" | "application/ndjson" | "ndjson"
+                ) {
+                    return Err(crate::Error::Validation(format!(
+                        "Unsupported _outputFormat: {}",
+                        value
+                    )));
+                }
+            }
+            "_type" => {
+                types.extend(
+                    value
+                        .split(',')
+                        .map(|t| t.trim())
+                        .filter(|t| !t.is_empty())
+                        .map(|t| t.to_string()),
+                );
+            }
+            "_since" => {
+                let parsed = DateTime::parse_from_rfc3339(value).map_err(|_| {
+                    crate::Error::Validation(format!("Invalid _since instant: {}", value))
+                })?;
+                since = Some(parsed.with_timezone(&Utc));
+            }
+            other => {
+                return Err(crate::Error::NotImplemented(format!(
+                    "$export parameter '{}' is not supported",
+                    other
+                )));
+            }
+        }
+    }
+
+    for t in &types {
+        if !is_known_resource_type(t) {
+            return Err(crate::Error::Validation(format!(
+                "Invalid resource type in _type: {}",
+                t
+            )));
+        }
+        crate::api::fhir_access::ensure_resource_type_supported(state, t)?;
+    }
+
+    if let Some(rt) = resource_type {
+        if types.iter().any(|t| t != rt) {
+            return Err(crate::Error::Validation(format!(
+                "_type must only contain {} for a type-level $export",
+                rt
+            )));
+        }
+        types = vec![rt.to_string()];
+    }
+    // Drop repeated types, keeping the order the client asked for
+    let mut seen = std::collections::HashSet::new();
+    types.retain(|t| seen.insert(t.clone()));
+
+    let base_url = api_url::base_url_from_headers(headers, &state.config.server);
+    let request_path = match resource_type {
+        Some(rt) => format!("{}/{}/$export", base_url, rt),
+        None => format!("{}/$export", base_url),
+    };
+    let request = if query.is_empty() {
+        request_path
+    } else {
+        let mut serializer = url::form_urlencoded::Serializer::new(String::new());
+        serializer.extend_pairs(query.iter());
+        format!("{}?{}", request_path, serializer.finish())
+    };
+
+    let params = BulkExportParams {
+        resource_types: types,
+        since,
+        output_dir: state.config.fhir.bulk_export.output_dir.clone(),
+        request,
+        security_scope: crate::security_labels::current_scope(),
+    };
+    let params = serde_json::to_value(params).map_err(|e| {
+        crate::Error::Internal(format!("Failed to serialize export parameters: {}", e))
+    })?;
+
+    let job_id = state
+        .job_queue
+        .enqueue(
+            BULK_EXPORT_JOB_TYPE.to_string(),
+            params,
+            JobPriority::Normal,
+            None,
+        )
+        .await?;
+
+    let status_url = format!("{}/$export-poll-status?_jobId={}", base_url, job_id);
+    Ok((
+        StatusCode::ACCEPTED,
+        [(header::CONTENT_LOCATION, status_url)],
+    )
+        .into_response())
+}
+
+/// Report export status (GET [base]/$export-poll-status?_jobId=...)
+///
+/// - 202 with `X-Progress` while the job is pending or running
+/// - 200 with the completion manifest once finished
+/// - 500 with an OperationOutcome if the job failed
+pub async fn poll_status(
+    state: &AppState,
+    headers: &HeaderMap,
+    query: Vec<(String, String)>,
+) -> Result<Response> {
+    let job_id = job_id_param(&query)?;
+    let job = state
+        .job_queue
+        .get_job(job_id)
+        .await?
+        .filter(|job| job.job_type == BULK_EXPORT_JOB_TYPE)
+        .ok_or_else(|| crate::Error::NotFound(format!("Export job {} not found", job_id)))?;
+
+    match job.status {
+        JobStatus::Pending | JobStatus::Running | JobStatus::Retrying => Ok((
+            StatusCode::ACCEPTED,
+            [
+                (
+                    "x-progress",
+                    format!("in-progress ({} resources exported)", job.processed_items),
+                ),
+                ("retry-after", "5".to_string()),
+            ],
+        )
+            .into_response()),
+        JobStatus::Failed => Err(crate::Error::Internal(format!(
+            "Export job {} failed: {}",
+            job_id,
+            job.error_message.unwrap_or_default()
+        ))),
+        JobStatus::Cancelled => Err(crate::Error::NotFound(format!(
+            "Export job {} was cancelled",
+            job_id
+        ))),
+        JobStatus::Completed => {
+            let result = export_result(&job.progress)?;
+            let base_url = api_url::base_url_from_headers(headers, &state.config.server);
+            let output = result
+                .output
+                .iter()
+                .map(|file| {
+                    serde_json::json!({
+                        "type": file.resource_type,
+                        "url": format!(
+                            "{}/$export-file?_jobId={}&_file={}",
+                            base_url, job_id, file.file_name
+                        ),
+                        "count": file.count,
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            Ok((
+                StatusCode::OK,
+                Json(serde_json::json!({
+                    "transactionTime": result.transaction_time.to_rfc3339(),
+                    "request": result.request,
+                    "requiresAccessToken": false,
+                    "output": output,
+                    "error": [],
+                })),
+            )
+                .into_response())
+        }
+    }
+}
+
+/// Download one export file (GET [base]/$export-file?_jobId=...&_file=...)
+///
+/// Only files listed in the job's manifest can be fetched.
+pub async fn download_file(state: &AppState, query: Vec<(String, String)>) -> Result<Response> {
+    let job_id = job_id_param(&query)?;
+    let file_name = query
+        .iter()
+        .find(|(k, _)| k == "_file")
+        .map(|(_, v)| v.as_str())
+        .ok_or_else(|| crate::Error::Validation("Missing _file parameter".to_string()))?;
+
+    let job = state
+        .job_queue
+        .get_job(job_id)
+        .await?
+        .filter(|job| job.job_type == BULK_EXPORT_JOB_TYPE && job.status == JobStatus::Completed)
+        .ok_or_else(|| crate::Error::NotFound(format!("Export job {} not found", job_id)))?;
+
+    let result = export_result(&job.progress)?;
+    if !result.output.iter().any(|file| file.file_name == file_name) {
+        return Err(crate::Error::NotFound(format!(
+            "Export file {} not found",
+            file_name
+        )));
+    }
+
+    let params: BulkExportParams = serde_json::from_value(job.parameters)
+        .map_err(|e| crate::Error::Internal(format!("Failed to parse export parameters: {}", e)))?;
+    let path = job_output_dir(&params.output_dir, job_id).join(file_name);
+    let body = tokio::fs::read(&path).await.map_err(|e| {
+        crate::Error::Internal(format!(
+            "Failed to read export file {}: {}",
+            path.display(),
+            e
+        ))
+    })?;
+
+    Ok((
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, NDJSON_CONTENT_TYPE)],
+        body,
+    )
+        .into_response())
+}
+
+fn job_id_param(query: &[(String, String)]) -> Result<Uuid> {
+    let raw = query
+        .iter()
+        .find(|(k, _)| k == "_jobId")
+        .map(|(_, v)| v.as_str())
+        .ok_or_else(|| crate::Error::Validation("Missing _jobId parameter".to_string()))?;
+    Uuid::parse_str(raw).map_err(|_| crate::Error::Validation(format!("Invalid _jobId: {}", raw)))
+}
+
+fn export_result(progress: &Option<serde_json::Value>) -> Result<BulkExportResult> {
+    let progress = progress.clone().ok_or_else(|| {
+        crate::Error::Internal("Completed export job has no manifest".to_string())
+    })?;
+    serde_json::from_value(progress)
+        .map_err(|e| crate::Error::Internal(format!("Invalid export manifest: {}", e)))
+}
@@ -12,7 +12,10 @@ use crate::{
         resource_formatter::ResourceFormatter,
         url as api_url,
     },
-    models::{is_known_resource_type, HistoryMethod, ResourceOperation, UpdateParams},
+    db::search::params::SummaryMode,
+    models::{
+        is_known_resource_type, HistoryCursor, HistoryMethod, ResourceOperation, UpdateParams,
+    },
     runtime_config::ConfigKey,
     services::conditional::parse_if_none_match_for_conditional_update,
     state::AppState,
@@ -26,7 +29,6 @@ use axum::{
     response::{IntoResponse, Response},
 };
 use chrono::{DateTime, Utc};
-use json_patch::PatchErrorKind;
 use serde_json::Value as JsonValue;
 use std::collections::{HashMap, HashSet};
 
@@ -58,9 +60,9 @@ async fn runtime_default_prefer_return(state: &AppState) -> String {
         .await
 }
 
-fn build_base_url(headers: &HeaderMap, request: &Request) -> String {
+fn build_base_url(state: &AppState, headers: &HeaderMap, request: &Request) -> String {
     // Prefer forwarding headers when present.
-    let mut base_url = api_url::base_url_from_headers(headers);
+    let mut base_url = api_url::forwarded_base_url(headers);
 
     // If the request URI contains an explicit scheme (uncommon), prefer it.
     if let Some(scheme) = request.uri().scheme_str() {
@@ -69,7 +71,7 @@ fn build_base_url(headers: &HeaderMap, request: &Request) -> String {
         }
     }
 
-    base_url
+    api_url::trusted_base_url(base_url, &state.config.server)
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -85,11 +87,81 @@ struct HistoryQuery {
     since: Option<DateTime<Utc>>,
     at: Option<DateTime<Utc>>,
     sort: HistorySort,
+    /// `_type` restriction (system-level history only)
+    types: Vec<String>,
+    /// `_cursor`: resume after the last entry of a previous page
+    cursor: Option<HistoryCursor>,
+    items: Vec<(String, String)>,
     query_params: HashMap<String, String>,
     raw_query: Option<String>,
 }
 
-fn parse_history_query(raw_query: Option<&str>) -> Result<HistoryQuery> {
+/// Encode a history cursor as an opaque `_cursor` value.
+///
+/// Uses the search cursor format with `resourceType/id/versionId` as the key.
+fn encode_history_cursor(cursor: &HistoryCursor) -> String {
+    crate::db::search::query_builder::encode_cursor(
+        &cursor
+            .last_updated
+            .to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true),
+        &format!(
+            "{}/{}/{}",
+            cursor.resource_type, cursor.id, cursor.version_id
+        ),
+    )
+}
+
+fn decode_history_cursor(raw: &str) -> Result<HistoryCursor> {
+    let invalid = || crate::Error::Validation(format!("Invalid _cursor value: {}", raw));
+    let (timestamp, key) =
+        crate::db::search::query_builder::decode_cursor(raw).ok_or_else(invalid)?;
+    let last_updated = chrono::DateTime::parse_from_rfc3339(&timestamp)
+        .map_err(|_| invalid())?
+        .with_timezone(&Utc);
+    let mut parts = key.splitn(3, '/');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(resource_type), Some(id), Some(version_id)) => Ok(HistoryCursor {
+            last_updated,
+            resource_type: resource_type.to_string(),
+            id: id.to_string(),
+            version_id: version_id.parse().map_err(|_| invalid())?,
+        }),
+        _ => Err(invalid()),
+    }
+}
+
+/// Bundle links for a history page: `self`, plus `next` when more entries exist.
+fn history_links(
+    path_url: &str,
+    history_query: &HistoryQuery,
+    next_cursor: Option<&HistoryCursor>,
+) -> Vec<JsonValue> {
+    let mut links = vec![serde_json::json!({
+        "relation": "self",
+        "url": match history_query.raw_query.as_deref() {
+            Some(q) if !q.is_empty() => format!("{}?{}", path_url, q),
+            _ => path_url.to_string(),
+        }
+    })];
+
+    if let Some(cursor) = next_cursor {
+        let mut serializer = url::form_urlencoded::Serializer::new(String::new());
+        for (key, value) in &history_query.items {
+            if key != "_cursor" {
+                serializer.append_pair(key, value);
+            }
+        }
+        serializer.append_pair("_cursor", &encode_history_cursor(cursor));
+        links.push(serde_json::json!({
+            "relation": "next",
+            "url": format!("{}?{}", path_url, serializer.finish()),
+        }));
+    }
+
+    links
+}
+
+fn parse_history_query(raw_query: Option<&str>, system_level: bool) -> Result<HistoryQuery> {
     let raw_query_owned = raw_query.map(|s| s.to_string());
     let items = raw_query
         .map(parse_form_urlencoded)
@@ -101,6 +173,8 @@ fn parse_history_query(raw_query: Option<&str>) -> Result<HistoryQuery> {
     let mut since: Option<DateTime<Utc>> = None;
     let mut at: Option<DateTime<Utc>> = None;
     let mut sort = HistorySort::LastUpdatedDesc;
+    let mut types: Vec<String> = Vec::new();
+    let mut cursor: Option<HistoryCursor> = None;
 
     for (k, v) in &items {
         // History parameters SHALL NOT appear more than once.
@@ -152,6 +226,27 @@ fn parse_history_query(raw_query: Option<&str>) -> Result<HistoryQuery> {
                     .with_timezone(&Utc);
                 at = Some(parsed);
             }
+            "_type" if system_level => {
+                for t in v.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+                    if !is_known_resource_type(t) {
+                        return Err(crate::Error::Validation(format!(
+                            "Invalid resource type in _type: {}",
+                            t
+                        )));
+                    }
+                    if !types.iter().any(|existing| existing == t) {
+                        types.push(t.to_string());
+                    }
+                }
+                if types.is_empty() {
+                    return Err(crate::Error::Validation(
+                        "_type must list at least one resource type".to_string(),
+                    ));
+                }
+            }
+            "_cursor" => {
+                cursor = Some(decode_history_cursor(v)?);
+            }
             "_list" => {
                 return Err(crate::Error::NotImplemented(
                     "History parameter '_list' is not yet supported".to_string(),
@@ -180,7 +275,10 @@ fn parse_history_query(raw_query: Option<&str>) -> Result<HistoryQuery> {
         since,
         at,
         sort,
+        types,
+        cursor,
         query_params: items_to_single_map_last(&items),
+        items,
         raw_query: raw_query_owned,
     })
 }
@@ -251,6 +349,36 @@ fn get_effective_prefer_return(headers: &HeaderMap, default_config: &str) -> Pre
     }
 }
 
+/// Apply `_summary` / `_elements` (Search Result Parameters) to a single resource
+///
+/// Per FHIR spec 3.2.1.7 these parameters are also valid on read/vread.
+/// `_summary` takes precedence over `_elements`; `_summary=count` is a no-op here.
+fn apply_summary_params(
+    state: &AppState,
+    resource: JsonValue,
+    query_params: &HashMap<String, String>,
+) -> Result<JsonValue> {
+    if let Some(value) = query_params.get("_summary") {
+        let mode = SummaryMode::parse(value).ok_or_else(|| {
+            crate::Error::Validation(format!("Invalid _summary value: {}", value))
+        })?;
+        return state.summary_filter.filter_resource(resource, mode);
+    }
+
+    if let Some(elements) = query_params.get("_elements") {
+        let elements = elements
+            .split(',')
+            .map(|e| e.trim().to_string())
+            .filter(|e| !e.is_empty())
+            .collect::<Vec<_>>();
+        if !elements.is_empty() {
+            return state.summary_filter.filter_elements(resource, &elements);
+        }
+    }
+
+    Ok(resource)
+}
+
 /// Format and create response with proper content negotiation
 ///
 /// Returns a Response with:
@@ -270,7 +398,7 @@ fn format_resource_response(
     // Check if requested format is supported
     if !negotiation.format.is_supported() {
         return Err(crate::Error::Validation(format!(
-            "Unsupported format: {}. Supported formats: application/fhir+json, application/fhir+xml",
+            "Unsupported format: {}. Supported formats: application/fhir+json, application/fhir+xml, application/fhir+turtle",
             negotiation.format.mime_type()
         )));
     }
@@ -279,7 +407,7 @@ fn format_resource_response(
     let formatter = ResourceFormatter::new(negotiation);
     let formatted_body = formatter
         .format_resource(resource)
-        .map_err(|e| crate::Error::Internal(e.to_string()))?;
+        .map_err(crate::Error::from)?;
 
     // Build response with correct Content-Type
     let (mut parts, _) = base_response.into_parts();
@@ -309,7 +437,7 @@ pub async fn create_resource(
     FhirBody(resource): FhirBody,
 ) -> Result<Response> {
     let service = &state.crud_service;
-    let base_url = api_url::base_url_from_headers(&headers);
+    let base_url = api_url::base_url_from_headers(&headers, &state.config.server);
     let mut resource = resource;
 
     let default_format = runtime_default_format(&state).await;
@@ -602,14 +730,10 @@ pub async fn read_resource(
     }
 
     // Build response with content negotiation
+    let body = apply_summary_params(&state, resource.resource, &params)?;
     let base_response = StatusCode::OK.into_response();
-    let response = format_resource_response(
-        resource.resource,
-        &params,
-        &headers,
-        &default_format,
-        base_response,
-    )?;
+    let response =
+        format_resource_response(body, &params, &headers, &default_format, base_response)?;
 
     Ok(response_headers.apply_to_response(response))
 }
@@ -649,7 +773,7 @@ pub async fn update_resource(
         None
     };
 
-    let base_url = api_url::base_url_from_headers(&headers);
+    let base_url = api_url::base_url_from_headers(&headers, &state.config.server);
     let mut resource = resource;
     state
         .conditional_reference_resolver
@@ -828,14 +952,10 @@ pub async fn vread_resource(
             .with_cache_control_immutable(31_536_000);
 
     // Build response with content negotiation
+    let body = apply_summary_params(&state, resource.resource, &params)?;
     let base_response = StatusCode::OK.into_response();
-    let response = format_resource_response(
-        resource.resource,
-        &params,
-        &headers,
-        &default_format,
-        base_response,
-    )?;
+    let response =
+        format_resource_response(body, &params, &headers, &default_format, base_response)?;
 
     Ok(response_headers.apply_to_response(response))
 }
@@ -861,7 +981,7 @@ pub async fn resource_history(
 
     let service = &state.crud_service;
     let default_format = runtime_default_format(&state).await;
-    let history_query = parse_history_query(request.uri().query())?;
+    let history_query = parse_history_query(request.uri().query(), false)?;
     let sort_ascending = matches!(history_query.sort, HistorySort::LastUpdatedAsc);
 
     let count = history_query.count;
@@ -869,13 +989,21 @@ pub async fn resource_history(
     let at = history_query.at;
 
     let history = service
-        .resource_history(&resource_type, &id, count, since, at, sort_ascending)
+        .resource_history(
+            &resource_type,
+            &id,
+            count,
+            since,
+            at,
+            sort_ascending,
+            history_query.cursor.as_ref(),
+        )
         .await?;
 
     // Build Bundle per FHIR spec.
     // NOTE: Spec requires "sorted with oldest versions last" (i.e., newest first).
     // Store queries already apply ordering, but `_sort=none` is treated as implementation-defined.
-    let base_url = build_base_url(&headers, &request);
+    let base_url = build_base_url(&state, &headers, &request);
     let mut entries = Vec::with_capacity(history.entries.len());
     for entry in history.entries {
         let resource = if matches!(entry.method, HistoryMethod::Delete) {
@@ -898,13 +1026,11 @@ pub async fn resource_history(
         "resourceType": "Bundle",
         "type": "history",
         "total": history.total,
-        "link": [{
-            "relation": "self",
-            "url": match history_query.raw_query.as_deref() {
-                Some(q) if !q.is_empty() => format!("{}/{}/{}/_history?{}", base_url, resource_type, id, q),
-                _ => format!("{}/{}/{}/_history", base_url, resource_type, id),
-            }
-        }],
+        "link": history_links(
+            &format!("{}/{}/{}/_history", base_url, resource_type, id),
+            &history_query,
+            history.next_cursor.as_ref(),
+        ),
         "entry": entries
     });
 
@@ -962,14 +1088,17 @@ pub async fn conditional_update_resource(
 
     let query_params = items_to_single_map_last(&query_items);
 
-    let base_url = build_base_url(&headers, &request);
+    let base_url = build_base_url(&state, &headers, &request);
 
     // Parse resource body (JSON or XML).
     let body_bytes = axum::body::to_bytes(request.into_body(), usize::MAX)
         .await
         .map_err(|e| crate::Error::Validation(format!("Failed to read request body: {}", e)))?;
-    let mut resource: JsonValue =
-        crate::api::extractors::parse_fhir_body(&body_bytes, &headers)?;
+    let mut resource: JsonValue = crate::api::extractors::parse_fhir_body(
+        &body_bytes,
+        &headers,
+        &state.config.fhir.resources,
+    )?;
 
     // Determine target ID based on match results + optional client-provided id.
     let id_in_body = resource
@@ -1165,23 +1294,10 @@ pub async fn patch_resource(
         }
     }
 
-    let mut patched = current.resource.clone();
-    json_patch::patch(&mut patched, &patch.0).map_err(|e| match e.kind {
-        PatchErrorKind::TestFailed => crate::Error::UnprocessableEntity(e.to_string()),
-        _ => crate::Error::InvalidResource(e.to_string()),
-    })?;
-
-    let obj = patched.as_object_mut().ok_or_else(|| {
-        crate::Error::InvalidResource("Patched resource must be a JSON object".to_string())
-    })?;
-    obj.insert(
-        "resourceType".to_string(),
-        serde_json::json!(&resource_type),
-    );
-    obj.insert("id".to_string(), serde_json::json!(&id));
-    obj.remove("text");
+    let mut patched =
+        crate::services::crud::apply_json_patch(&current.resource, &patch, &resource_type, &id)?;
 
-    let base_url = api_url::base_url_from_headers(&headers);
+    let base_url = api_url::base_url_from_headers(&headers, &state.config.server);
     state
         .conditional_reference_resolver
         .resolve(&mut patched, Some(&base_url))
@@ -1276,7 +1392,7 @@ pub async fn conditional_patch_resource(
     }
 
     let query_params = items_to_single_map_last(&query_items);
-    let base_url = build_base_url(&headers, &request);
+    let base_url = build_base_url(&state, &headers, &request);
 
     let content_type = headers
         .get("content-type")
@@ -1351,21 +1467,8 @@ pub async fn conditional_patch_resource(
         }
     }
 
-    let mut patched = current.resource.clone();
-    json_patch::patch(&mut patched, &patch.0).map_err(|e| match e.kind {
-        PatchErrorKind::TestFailed => crate::Error::UnprocessableEntity(e.to_string()),
-        _ => crate::Error::InvalidResource(e.to_string()),
-    })?;
-
-    let obj = patched.as_object_mut().ok_or_else(|| {
-        crate::Error::InvalidResource("Patched resource must be a JSON object".to_string())
-    })?;
-    obj.insert(
-        "resourceType".to_string(),
-        serde_json::json!(&resource_type),
-    );
-    obj.insert("id".to_string(), serde_json::json!(&id));
-    obj.remove("text");
+    let mut patched =
+        crate::services::crud::apply_json_patch(&current.resource, &patch, &resource_type, &id)?;
 
     state
         .conditional_reference_resolver
@@ -1506,7 +1609,7 @@ pub async fn conditional_delete_resource(
         ));
     }
 
-    let base_url = build_base_url(&headers, &request);
+    let base_url = build_base_url(&state, &headers, &request);
 
     // Per spec: request body SHALL be empty.
     let body_bytes = axum::body::to_bytes(request.into_body(), usize::MAX)
@@ -1587,7 +1690,7 @@ pub async fn type_history(
     crate::api::fhir_access::ensure_resource_type_supported(&state, &resource_type)?;
 
     let default_format = runtime_default_format(&state).await;
-    let history_query = parse_history_query(request.uri().query())?;
+    let history_query = parse_history_query(request.uri().query(), false)?;
     let sort_ascending = matches!(history_query.sort, HistorySort::LastUpdatedAsc);
     let count = history_query.count;
     let since = history_query.since;
@@ -1595,10 +1698,17 @@ pub async fn type_history(
 
     let history = state
         .crud_service
-        .type_history(&resource_type, count, since, at, sort_ascending)
+        .type_history(
+            &resource_type,
+            count,
+            since,
+            at,
+            sort_ascending,
+            history_query.cursor.as_ref(),
+        )
         .await?;
 
-    let base_url = build_base_url(&headers, &request);
+    let base_url = build_base_url(&state, &headers, &request);
     let mut entries = Vec::with_capacity(history.entries.len());
     for entry in history.entries {
         let resource = if matches!(entry.method, HistoryMethod::Delete) {
@@ -1620,13 +1730,11 @@ pub async fn type_history(
     let bundle = serde_json::json!({
         "resourceType": "Bundle",
         "type": "history",
-        "link": [{
-            "relation": "self",
-            "url": match history_query.raw_query.as_deref() {
-                Some(q) if !q.is_empty() => format!("{}/{}/_history?{}", base_url, resource_type, q),
-                _ => format!("{}/{}/_history", base_url, resource_type),
-            }
-        }],
+        "link": history_links(
+            &format!("{}/{}/_history", base_url, resource_type),
+            &history_query,
+            history.next_cursor.as_ref(),
+        ),
         "entry": entries
     });
 
@@ -1665,7 +1773,7 @@ pub async fn system_delete(State(state): State<AppState>, request: Request) -> R
         .transpose()?
         .unwrap_or_default();
 
-    let base_url = build_base_url(&headers, &request);
+    let base_url = build_base_url(&state, &headers, &request);
 
     // Per spec: request body SHALL be empty.
     let body_bytes = axum::body::to_bytes(request.into_body(), usize::MAX)
@@ -1699,6 +1807,7 @@ pub async fn system_delete(State(state): State<AppState>, request: Request) -> R
 /// Spec-compliant behavior:
 /// - 200 OK with Bundle containing history for all resources
 /// - Supports _count and _since parameters
+/// - `_type` restricts the history to a comma-separated list of resource types
 pub async fn system_history(
     State(state): State<AppState>,
     headers: HeaderMap,
@@ -1712,18 +1821,28 @@ pub async fn system_history(
     .await?;
 
     let default_format = runtime_default_format(&state).await;
-    let history_query = parse_history_query(request.uri().query())?;
+    let history_query = parse_history_query(request.uri().query(), true)?;
     let sort_ascending = matches!(history_query.sort, HistorySort::LastUpdatedAsc);
     let count = history_query.count;
     let since = history_query.since;
     let at = history_query.at;
+    for resource_type in &history_query.types {
+        crate::api::fhir_access::ensure_resource_type_supported(&state, resource_type)?;
+    }
 
     let history = state
         .crud_service
-        .system_history(count, since, at, sort_ascending)
+        .system_history(
+            &history_query.types,
+            count,
+            since,
+            at,
+            sort_ascending,
+            history_query.cursor.as_ref(),
+        )
         .await?;
 
-    let base_url = build_base_url(&headers, &request);
+    let base_url = build_base_url(&state, &headers, &request);
     let mut entries = Vec::with_capacity(history.entries.len());
     for entry in history.entries {
         let resource = if matches!(entry.method, HistoryMethod::Delete) {
@@ -1745,13 +1864,11 @@ pub async fn system_history(
     let bundle = serde_json::json!({
         "resourceType": "Bundle",
         "type": "history",
-        "link": [{
-            "relation": "self",
-            "url": match history_query.raw_query.as_deref() {
-                Some(q) if !q.is_empty() => format!("{}/_history?{}", base_url, q),
-                _ => format!("{}/_history", base_url),
-            }
-        }],
+        "link": history_links(
+            &format!("{}/_history", base_url),
+            &history_query,
+            history.next_cursor.as_ref(),
+        ),
         "entry": entries
     });
 
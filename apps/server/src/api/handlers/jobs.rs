@@ -161,6 +161,46 @@ pub async fn delete_job(
     }
 }
 
+/// Enqueue a reindex of all current resources of a single type
+///
+/// Requests for a type that already has a pending reindex job are coalesced into that job; a
+/// running job does not absorb new requests. Progress is reported through the regular jobs
+/// endpoints.
+pub async fn reindex_resource_type(
+    State(state): State<AppState>,
+    Path(resource_type): Path<String>,
+) -> Result<Response> {
+    if !crate::models::is_known_resource_type(&resource_type) {
+        return Err(crate::Error::Validation(format!(
+            "Invalid resource type: {}",
+            resource_type
+        )));
+    }
+
+    let (job_id, created) = state
+        .job_queue
+        .enqueue_unique(
+            "reindex".to_string(),
+            json!({
+                "resource_type": resource_type,
+                "resource_id": null,
+            }),
+            crate::queue::JobPriority::Normal,
+            None,
+        )
+        .await?;
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(json!({
+            "jobId": job_id,
+            "resourceType": resource_type,
+            "coalesced": !created
+        })),
+    )
+        .into_response())
+}
+
 /// Get queue health and statistics
 pub async fn get_queue_health(State(state): State<AppState>) -> Result<Response> {
     let health = state.job_queue.health_check().await?;
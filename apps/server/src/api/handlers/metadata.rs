@@ -52,7 +52,7 @@ pub async fn capability_statement(
     }
 
     // Generate capability statement
-    let base_url = crate::api::url::base_url_from_headers(&headers);
+    let base_url = crate::api::url::base_url_from_headers(&headers, &state.config.server);
     let capability_statement = state
         .metadata_service
         .get_capability_statement(mode, &base_url)
@@ -104,7 +104,7 @@ fn format_resource_response(
     // Check if requested format is supported
     if !negotiation.format.is_supported() {
         return Err(crate::Error::Validation(format!(
-            "Unsupported format: {}. Supported formats: application/fhir+json, application/fhir+xml",
+            "Unsupported format: {}. Supported formats: application/fhir+json, application/fhir+xml, application/fhir+turtle",
             negotiation.format.mime_type()
         )));
     }
@@ -113,7 +113,7 @@ fn format_resource_response(
     let formatter = ResourceFormatter::new(negotiation);
     let formatted_body = formatter
         .format_resource(resource)
-        .map_err(|e| crate::Error::Internal(e.to_string()))?;
+        .map_err(crate::Error::from)?;
 
     // Build response with correct Content-Type
     let (mut parts, _) = base_response.into_parts();
@@ -7,7 +7,9 @@
 //! - Error handling
 
 pub mod admin;
+pub mod async_operation;
 pub mod batch;
+pub mod bulk_export;
 pub mod crud;
 pub mod jobs;
 pub mod metadata;
@@ -2,6 +2,7 @@
 
 use crate::{
     api::{content_negotiation::ContentNegotiation, resource_formatter::ResourceFormatter},
+    auth::AuthenticatedPrincipal,
     models::{OperationContext, OperationRequest, OperationResult, Parameters},
     runtime_config::ConfigKey,
     state::AppState,
@@ -23,6 +24,7 @@ pub async fn operation_system(
     method: Method,
     Query(query): Query<Vec<(String, String)>>,
     Path(operation): Path<String>,
+    principal: Option<AuthenticatedPrincipal>,
     body: Bytes,
 ) -> Result<Response> {
     crate::api::fhir_access::ensure_interaction_enabled_runtime(
@@ -32,6 +34,32 @@ pub async fn operation_system(
     )
     .await?;
 
+    // Bulk Data export shares the `$operation` route but runs as a background job.
+    if method == Method::GET {
+        match operation.as_str() {
+            "export" => {
+                return crate::api::handlers::bulk_export::kick_off(&state, &headers, None, query)
+                    .await
+            }
+            "export-poll-status" => {
+                return crate::api::handlers::bulk_export::poll_status(&state, &headers, query)
+                    .await
+            }
+            "export-file" => {
+                return crate::api::handlers::bulk_export::download_file(&state, query).await
+            }
+            "async-status" => {
+                return crate::api::handlers::async_operation::poll_status(
+                    &state,
+                    query,
+                    principal.as_ref().map(|p| &p.0),
+                )
+                .await
+            }
+            _ => {}
+        }
+    }
+
     execute_operation(
         state,
         headers,
@@ -39,6 +67,7 @@ pub async fn operation_system(
         operation,
         OperationContext::System,
         query,
+        principal,
         body,
     )
     .await
@@ -51,6 +80,7 @@ pub async fn operation_type(
     method: Method,
     Query(query): Query<Vec<(String, String)>>,
     Path((resource_type, operation)): Path<(String, String)>,
+    principal: Option<AuthenticatedPrincipal>,
     body: Bytes,
 ) -> Result<Response> {
     crate::api::fhir_access::ensure_interaction_enabled_runtime(
@@ -61,6 +91,27 @@ pub async fn operation_type(
     .await?;
     crate::api::fhir_access::ensure_resource_type_supported(&state, &resource_type)?;
 
+    if operation == "export" && method == Method::GET {
+        return crate::api::handlers::bulk_export::kick_off(
+            &state,
+            &headers,
+            Some(&resource_type),
+            query,
+        )
+        .await;
+    }
+
+    // Search parameter dry-run shares the `$operation` route but is not a registered operation.
+    if operation == "validate-search" && method == Method::GET {
+        return crate::api::handlers::search::validate_search_type(
+            &state,
+            &headers,
+            &resource_type,
+            query,
+        )
+        .await;
+    }
+
     execute_operation(
         state,
         headers,
@@ -68,6 +119,7 @@ pub async fn operation_type(
         operation,
         OperationContext::Type(resource_type),
         query,
+        principal,
         body,
     )
     .await
@@ -80,6 +132,7 @@ pub async fn operation_instance(
     method: Method,
     Query(query): Query<Vec<(String, String)>>,
     Path((resource_type, id, operation)): Path<(String, String, String)>,
+    principal: Option<AuthenticatedPrincipal>,
     body: Bytes,
 ) -> Result<Response> {
     crate::api::fhir_access::ensure_interaction_enabled_runtime(
@@ -97,6 +150,7 @@ pub async fn operation_instance(
         operation,
         OperationContext::Instance(resource_type, id),
         query,
+        principal,
         body,
     )
     .await
@@ -109,6 +163,7 @@ async fn execute_operation(
     operation: String,
     context: OperationContext,
     query: Vec<(String, String)>,
+    principal: Option<AuthenticatedPrincipal>,
     body: Bytes,
 ) -> Result<Response> {
     let default_format: String = state
@@ -137,7 +192,7 @@ async fn execute_operation(
         Parameters::new()
     } else {
         let value: serde_json::Value =
-            crate::api::extractors::parse_fhir_body(&body, &headers)?;
+            crate::api::extractors::parse_fhir_body(&body, &headers, &state.config.fhir.resources)?;
         serde_json::from_value(value).map_err(|e| {
             crate::Error::Validation(format!("Invalid Parameters resource: {}", e))
         })?
@@ -204,6 +259,17 @@ async fn execute_operation(
         parameters,
     };
 
+    if crate::api::headers::prefer_respond_async(&headers) {
+        return crate::api::handlers::async_operation::kick_off(
+            &state,
+            &headers,
+            request,
+            &query,
+            principal.as_ref().map(|p| &p.0),
+        )
+        .await;
+    }
+
     let result = state.operation_executor.execute(request).await?;
 
     // Build query map for content negotiation.
@@ -220,7 +286,7 @@ async fn execute_operation(
                 ContentNegotiation::from_request(&query_params, &headers, &default_format);
             if !negotiation.format.is_supported() {
                 return Err(crate::Error::Validation(format!(
-                    "Unsupported format: {}. Supported formats: application/fhir+json, application/fhir+xml",
+                    "Unsupported format: {}. Supported formats: application/fhir+json, application/fhir+xml, application/fhir+turtle",
                     negotiation.format.mime_type()
                 )));
             }
@@ -228,7 +294,7 @@ async fn execute_operation(
             let formatter = ResourceFormatter::new(negotiation);
             let formatted_body = formatter
                 .format_resource(resource)
-                .map_err(|e| crate::Error::Internal(e.to_string()))?;
+                .map_err(crate::Error::from)?;
 
             let base_response = StatusCode::OK.into_response();
             let (mut parts, _) = base_response.into_parts();
@@ -247,7 +313,7 @@ async fn execute_operation(
                 ContentNegotiation::from_request(&query_params, &headers, &default_format);
             if !negotiation.format.is_supported() {
                 return Err(crate::Error::Validation(format!(
-                    "Unsupported format: {}. Supported formats: application/fhir+json, application/fhir+xml",
+                    "Unsupported format: {}. Supported formats: application/fhir+json, application/fhir+xml, application/fhir+turtle",
                     negotiation.format.mime_type()
                 )));
             }
@@ -258,7 +324,7 @@ async fn execute_operation(
             let formatter = ResourceFormatter::new(negotiation);
             let formatted_body = formatter
                 .format_resource(payload)
-                .map_err(|e| crate::Error::Internal(e.to_string()))?;
+                .map_err(crate::Error::from)?;
 
             let base_response = StatusCode::OK.into_response();
             let (mut parts, _) = base_response.into_parts();
@@ -277,7 +343,7 @@ async fn execute_operation(
                 ContentNegotiation::from_request(&query_params, &headers, &default_format);
             if !negotiation.format.is_supported() {
                 return Err(crate::Error::Validation(format!(
-                    "Unsupported format: {}. Supported formats: application/fhir+json, application/fhir+xml",
+                    "Unsupported format: {}. Supported formats: application/fhir+json, application/fhir+xml, application/fhir+turtle",
                     negotiation.format.mime_type()
                 )));
             }
@@ -285,7 +351,7 @@ async fn execute_operation(
             let formatter = ResourceFormatter::new(negotiation);
             let formatted_body = formatter
                 .format_resource(outcome)
-                .map_err(|e| crate::Error::Internal(e.to_string()))?;
+                .map_err(crate::Error::from)?;
 
             let base_response = StatusCode::OK.into_response();
             let (mut parts, _) = base_response.into_parts();
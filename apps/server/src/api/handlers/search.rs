@@ -2,6 +2,7 @@
 //!
 //! Handles FHIR search operations:
 //! - Type-level search (GET/POST /{resource_type})
+//! - Search parameter dry-run (GET /{resource_type}/$validate-search)
 //! - System-level search (GET/POST /)
 //! - Compartment search:
 //!   - GET  /{compartment_type}/{compartment_id}/*{?params}
@@ -11,20 +12,24 @@
 
 use crate::{
     api::{
-        content_negotiation::ContentNegotiation, headers::extract_prefer_handling,
-        resource_formatter::ResourceFormatter, url as api_url,
+        content_negotiation::ContentNegotiation,
+        headers::{extract_if_none_match, extract_prefer_handling, PreferHandling},
+        resource_formatter::ResourceFormatter,
+        url as api_url,
     },
     runtime_config::ConfigKey,
+    services::search::push_outcome_entry,
     state::AppState,
     Result,
 };
 use axum::{
     body::Body,
     extract::{Path, Request, State},
-    http::{HeaderMap, Method, StatusCode},
+    http::{header, HeaderMap, HeaderValue, Method, StatusCode},
     response::{IntoResponse, Response},
 };
 use serde::Deserialize;
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 
 /// Common search handler logic shared across all search operations
@@ -34,9 +39,10 @@ use std::collections::HashMap;
 /// - Parsing search parameters from query string and POST body
 /// - Executing the search via the provided closure
 /// - Checking for unknown parameters
+/// - Warning about retired or experimental parameters
 /// - Formatting the response with content negotiation
 async fn handle_search<F, Fut>(
-    _state: &AppState,
+    state: &AppState,
     headers: &HeaderMap,
     request: Request,
     resource_context: &str,
@@ -50,7 +56,7 @@ where
     // Extract base URL (scheme://host/fhir), honoring forwarding headers.
     let uri = request.uri();
     let raw_query = uri.query().map(|s| s.to_string());
-    let base_url = api_url::base_url_from_headers(headers);
+    let base_url = api_url::base_url_from_headers(headers, &state.config.server);
 
     // Extract method and body
     let method = request.method().clone();
@@ -66,20 +72,81 @@ where
     let query_string = build_query_string(&items);
 
     // Execute search via provided closure (pass owned values to avoid lifetime issues)
-    let bundle_result = execute_search(items, query_string, base_url).await?;
+    let bundle_result = execute_search(items, query_string.clone(), base_url).await?;
 
-    // Check for unknown parameters and handle based on Prefer header
+    // Check for unknown and not-yet-indexed parameters and handle based on Prefer header
     let bundle = check_unknown_params(bundle_result, headers, resource_context)?;
+    let bundle = check_unindexed_params(bundle, headers, resource_context);
+    let (bundle, param_warnings) = check_param_warnings(bundle);
+
+    // Conditional search (If-None-Match): 304 when the result set is unchanged
+    let format = ContentNegotiation::from_request(&query_params, headers, default_format).format;
+    let etag = search_bundle_etag(resource_context, &query_string, format.mime_type(), &bundle);
+    let etag_value = HeaderValue::from_str(&etag)
+        .map_err(|e| crate::Error::Internal(format!("Invalid ETag: {}", e)))?;
+    if let Some(if_none_match) = extract_if_none_match(headers) {
+        if if_none_match
+            .split(',')
+            .any(|candidate| candidate.trim() == etag)
+        {
+            let mut response = StatusCode::NOT_MODIFIED.into_response();
+            response.headers_mut().insert(header::ETAG, etag_value);
+            return Ok(response);
+        }
+    }
 
     // Format response with content negotiation
     let base_response = StatusCode::OK.into_response();
-    format_search_response(
+    let mut response = format_search_response(
         bundle,
         &query_params,
         headers,
         default_format,
         base_response,
-    )
+    )?;
+    response.headers_mut().insert(header::ETAG, etag_value);
+    for warning in &param_warnings {
+        if let Ok(value) = HeaderValue::from_str(&format!("299 - \"{}\"", warning)) {
+            response.headers_mut().append(header::WARNING, value);
+        }
+    }
+    Ok(response)
+}
+
+/// Weak ETag for a search result set.
+///
+/// Hashes the search context, query and response format together with the total and every
+/// entry as returned (resource, `meta` included). An empty result set therefore still has a
+/// stable validator, and any change to a matching resource changes it, including meta-only
+/// changes such as `$meta-add` that keep `versionId` and `lastUpdated`.
+fn search_bundle_etag(
+    resource_context: &str,
+    query_string: &str,
+    format: &str,
+    bundle: &serde_json::Value,
+) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(resource_context.as_bytes());
+    hasher.update(b"?");
+    hasher.update(query_string.as_bytes());
+    hasher.update(b"\nformat=");
+    hasher.update(format.as_bytes());
+    if let Some(total) = bundle.get("total") {
+        hasher.update(b"\ntotal=");
+        hasher.update(total.to_string().as_bytes());
+    }
+
+    for entry in bundle
+        .get("entry")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+    {
+        hasher.update(b"\n");
+        hasher.update(entry.to_string().as_bytes());
+    }
+
+    format!("W/\"{:x}\"", hasher.finalize())
 }
 
 /// Format and create search response with proper content negotiation
@@ -101,7 +168,7 @@ fn format_search_response(
     // Check if requested format is supported
     if !negotiation.format.is_supported() {
         return Err(crate::Error::Validation(format!(
-            "Unsupported format: {}. Supported formats: application/fhir+json, application/fhir+xml",
+            "Unsupported format: {}. Supported formats: application/fhir+json, application/fhir+xml, application/fhir+turtle",
             negotiation.format.mime_type()
         )));
     }
@@ -110,7 +177,7 @@ fn format_search_response(
     let formatter = ResourceFormatter::new(negotiation);
     let formatted_body = formatter
         .format_resource(bundle)
-        .map_err(|e| crate::Error::Internal(e.to_string()))?;
+        .map_err(crate::Error::from)?;
 
     // Build response with correct Content-Type
     let (mut parts, _) = base_response.into_parts();
@@ -300,6 +367,48 @@ pub(crate) async fn search_compartment(
     .await
 }
 
+/// Validate search parameters without executing the search (GET /{resource_type}/$validate-search)
+///
+/// Runs parameter resolution only and returns 200 OK with an OperationOutcome containing one
+/// issue per parameter: `information` when supported, `error`/`not-supported` with a reason
+/// otherwise. Control parameters (`_format`, `_pretty`) are used for content negotiation.
+pub async fn validate_search_type(
+    state: &AppState,
+    headers: &HeaderMap,
+    resource_type: &str,
+    items: Vec<(String, String)>,
+) -> Result<Response> {
+    crate::api::fhir_access::ensure_interaction_enabled_runtime(
+        state,
+        ConfigKey::InteractionsTypeSearch,
+        "search-type",
+    )
+    .await?;
+
+    let default_format: String = state
+        .runtime_config_cache
+        .get(ConfigKey::FormatDefault)
+        .await;
+    let query_params = items_to_single_map_last(&items);
+    let search_items = items
+        .into_iter()
+        .filter(|(k, _)| !matches!(k.as_str(), "_format" | "_pretty"))
+        .collect::<Vec<_>>();
+
+    let outcome = state
+        .search_service
+        .validate_search_type(resource_type, &search_items)
+        .await?;
+
+    format_search_response(
+        outcome,
+        &query_params,
+        headers,
+        &default_format,
+        StatusCode::OK.into_response(),
+    )
+}
+
 /// Extract and merge search parameters from query string and POST body.
 ///
 /// Per FHIR spec:
@@ -374,7 +483,8 @@ fn build_query_string(items: &[(String, String)]) -> String {
 ///
 /// Per FHIR spec on unknown/unsupported parameters:
 /// - If Prefer: handling=strict, returns error for unknown params
-/// - If Prefer: handling=lenient (default), silently ignores them
+/// - If Prefer: handling=lenient (default), ignores them and reports them in an
+///   OperationOutcome entry (search.mode = "outcome")
 /// - Removes the temporary _unknown_params field from Bundle
 fn check_unknown_params(
     mut bundle: serde_json::Value,
@@ -383,26 +493,136 @@ fn check_unknown_params(
 ) -> Result<serde_json::Value> {
     let handling = extract_prefer_handling(headers);
 
-    if let Some(bundle_obj) = bundle.as_object_mut() {
-        if let Some(unknown_params) = bundle_obj.remove("_unknown_params") {
-            if let Some(unknown_array) = unknown_params.as_array() {
-                if !unknown_array.is_empty()
-                    && handling == crate::api::headers::PreferHandling::Strict
-                {
-                    let unknown_list: Vec<String> = unknown_array
-                        .iter()
-                        .filter_map(|v| v.as_str().map(|s| s.to_string()))
-                        .collect();
-
-                    return Err(crate::Error::Validation(format!(
-                        "Unknown or unsupported search parameters for {}: {}",
-                        resource_type,
-                        unknown_list.join(", ")
-                    )));
-                }
-            }
-        }
+    let Some(bundle_obj) = bundle.as_object_mut() else {
+        return Ok(bundle);
+    };
+
+    let unknown_list: Vec<String> = bundle_obj
+        .remove("_unknown_params")
+        .and_then(|v| v.as_array().cloned())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|v| v.as_str().map(|s| s.to_string()))
+        .collect();
+
+    if unknown_list.is_empty() {
+        return Ok(bundle);
+    }
+
+    if handling == PreferHandling::Strict {
+        return Err(crate::Error::Validation(format!(
+            "Unknown or unsupported search parameters for {}: {}",
+            resource_type,
+            unknown_list.join(", ")
+        )));
     }
 
+    let outcome = serde_json::json!({
+        "resource": {
+            "resourceType": "OperationOutcome",
+            "issue": unknown_list.iter().map(|name| serde_json::json!({
+                "severity": "warning",
+                "code": "not-supported",
+                "diagnostics": format!(
+                    "Unknown or unsupported search parameter for {} ignored: {}",
+                    resource_type, name
+                )
+            })).collect::<Vec<_>>()
+        },
+        "search": {
+            "mode": "outcome"
+        }
+    });
+
+    push_outcome_entry(bundle_obj, outcome);
     Ok(bundle)
 }
+
+/// Warn about parameters whose index may be incomplete
+///
+/// Parameters added (or changed) after resources were stored only cover those resources
+/// once a reindex has run. Under Prefer: handling=strict the search reports this as an
+/// OperationOutcome entry (search.mode = "outcome"); lenient handling stays silent.
+/// Removes the temporary _unindexed_params field from Bundle.
+fn check_unindexed_params(
+    mut bundle: serde_json::Value,
+    headers: &HeaderMap,
+    resource_type: &str,
+) -> serde_json::Value {
+    let Some(bundle_obj) = bundle.as_object_mut() else {
+        return bundle;
+    };
+
+    let unindexed_list: Vec<String> = bundle_obj
+        .remove("_unindexed_params")
+        .and_then(|v| v.as_array().cloned())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|v| v.as_str().map(|s| s.to_string()))
+        .collect();
+
+    if unindexed_list.is_empty() || extract_prefer_handling(headers) != PreferHandling::Strict {
+        return bundle;
+    }
+
+    let outcome = serde_json::json!({
+        "resource": {
+            "resourceType": "OperationOutcome",
+            "issue": unindexed_list.iter().map(|name| serde_json::json!({
+                "severity": "warning",
+                "code": "incomplete",
+                "diagnostics": format!(
+                    "Search parameter {} for {} is not indexed for all existing resources; results may be incomplete until a reindex runs",
+                    name, resource_type
+                )
+            })).collect::<Vec<_>>()
+        },
+        "search": {
+            "mode": "outcome"
+        }
+    });
+
+    push_outcome_entry(bundle_obj, outcome);
+    bundle
+}
+
+/// Report parameters whose SearchParameter is retired or experimental
+///
+/// The search itself is unaffected. Each warning becomes an `information` issue in an
+/// OperationOutcome entry (search.mode = "outcome") and is returned so the caller can
+/// also send it as an HTTP `Warning` header. Removes the temporary _param_warnings field
+/// from Bundle.
+fn check_param_warnings(mut bundle: serde_json::Value) -> (serde_json::Value, Vec<String>) {
+    let Some(bundle_obj) = bundle.as_object_mut() else {
+        return (bundle, Vec::new());
+    };
+
+    let warnings: Vec<String> = bundle_obj
+        .remove("_param_warnings")
+        .and_then(|v| v.as_array().cloned())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|v| v.as_str().map(|s| s.to_string()))
+        .collect();
+
+    if warnings.is_empty() {
+        return (bundle, warnings);
+    }
+
+    let outcome = serde_json::json!({
+        "resource": {
+            "resourceType": "OperationOutcome",
+            "issue": warnings.iter().map(|warning| serde_json::json!({
+                "severity": "information",
+                "code": "informational",
+                "diagnostics": warning
+            })).collect::<Vec<_>>()
+        },
+        "search": {
+            "mode": "outcome"
+        }
+    });
+
+    push_outcome_entry(bundle_obj, outcome);
+    (bundle, warnings)
+}
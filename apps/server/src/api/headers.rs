@@ -227,6 +227,19 @@ pub fn prefer_minimal(headers: &HeaderMap) -> bool {
     extract_prefer_return(headers) == PreferReturn::Minimal
 }
 
+/// Check if client requested asynchronous processing (`Prefer: respond-async`)
+///
+/// Required by the Bulk Data `$export` kick-off request; other operations run as a background
+/// job when it is present.
+pub fn prefer_respond_async(headers: &HeaderMap) -> bool {
+    headers
+        .get_all("prefer")
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .any(|pref| pref.trim().eq_ignore_ascii_case("respond-async"))
+}
+
 /// Check if client prefers OperationOutcome response
 ///
 /// Returns true if Prefer header contains `return=OperationOutcome`.
@@ -8,6 +8,7 @@ pub mod headers;
 pub mod middleware;
 pub mod resource_formatter;
 pub mod routes;
+pub mod turtle;
 pub mod url;
 
 use crate::state::AppState;
@@ -33,9 +34,14 @@ pub fn create_router(state: AppState) -> NormalizePath<Router> {
     let cors_origins = state.config.server.cors_origins.clone();
     let fhir_auth_state = state.clone();
     let fhir_audit_state = state.clone();
+    let fhir_security_label_state = state.clone();
     let admin_auth_state = state.clone();
 
     let fhir_router = routes::fhir::fhir_routes()
+        .layer(axum::middleware::from_fn_with_state(
+            fhir_security_label_state,
+            crate::security_labels::security_label_middleware,
+        ))
         .layer(axum::middleware::from_fn_with_state(
             fhir_audit_state,
             middleware::audit_middleware,
@@ -1,7 +1,7 @@
 //! Resource Formatting
 //!
 //! Handles formatting FHIR resources according to content negotiation:
-//! - Format conversion (JSON to XML and vice versa)
+//! - Format conversion (JSON to XML and vice versa, JSON to Turtle)
 //! - Pretty printing
 //!
 //! Note: Resource filtering (_summary, _elements) is handled by SearchService,
@@ -10,6 +10,7 @@
 //! See: http://hl7.org/fhir/http.html#parameters
 
 use crate::api::content_negotiation::{ContentFormat, ContentNegotiation};
+use crate::api::turtle::{resource_to_turtle, TurtleError};
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response as AxumResponse};
 use serde_json::Value as JsonValue;
@@ -25,6 +26,23 @@ pub enum FormatError {
 
     #[error("JSON serialization error: {0}")]
     JsonError(#[from] serde_json::Error),
+
+    #[error(transparent)]
+    Turtle(#[from] TurtleError),
+}
+
+impl From<FormatError> for crate::Error {
+    /// Resources the requested format cannot represent are reported as 406 Not Acceptable.
+    fn from(err: FormatError) -> Self {
+        match err {
+            FormatError::UnsupportedFormat(_) | FormatError::Turtle(_) => {
+                crate::Error::NotAcceptable(err.to_string())
+            }
+            FormatError::ConversionFailed(_) | FormatError::JsonError(_) => {
+                crate::Error::Internal(err.to_string())
+            }
+        }
+    }
 }
 
 impl IntoResponse for FormatError {
@@ -46,7 +64,7 @@ impl ResourceFormatter {
 
     /// Format a FHIR resource according to content negotiation preferences
     ///
-    /// This only handles format conversion (JSON/XML) and pretty printing.
+    /// This only handles format conversion (JSON/XML/Turtle) and pretty printing.
     /// Resource filtering (_summary, _elements) is handled by SearchService.
     pub fn format_resource(&self, resource: JsonValue) -> Result<Vec<u8>, FormatError> {
         self.convert_format(resource)
@@ -69,8 +87,12 @@ impl ResourceFormatter {
                 let xml_str = ferrum_format::json_to_xml(&json_str)?;
                 Ok(xml_str.into_bytes())
             }
-            ContentFormat::Html | ContentFormat::Turtle => {
-                // These formats are not yet supported
+            ContentFormat::Turtle => {
+                // Turtle format - FHIR RDF subset, see `api::turtle`
+                Ok(resource_to_turtle(&resource)?.into_bytes())
+            }
+            ContentFormat::Html => {
+                // This format is not yet supported
                 Err(FormatError::UnsupportedFormat(self.negotiation.format))
             }
         }
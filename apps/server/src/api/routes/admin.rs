@@ -41,6 +41,10 @@ pub fn admin_routes() -> Router<AppState> {
             "/resources/references/batch",
             post(admin::get_batch_references),
         )
+        .route(
+            "/resources/:resource_type/reindex",
+            post(jobs::reindex_resource_type),
+        )
         // Search parameter indexing status
         .route(
             "/search-parameters/indexing-status",
@@ -65,6 +69,7 @@ pub fn admin_routes() -> Router<AppState> {
             "/search/hash-collisions",
             get(admin::get_search_hash_collisions),
         )
+        .route("/search/explain", get(admin::explain_search))
         // Compartment memberships
         .route(
             "/compartments/memberships",
@@ -0,0 +1,277 @@
+//! FHIR RDF Turtle Serialization
+//!
+//! Writes a FHIR JSON resource as Turtle following the structure of the FHIR RDF mapping:
+//! the resource is a node typed `fhir:{ResourceType}`, every element is a blank node holding
+//! either a `fhir:value` (primitives) or its child elements, and repeating elements carry a
+//! `fhir:index`.
+//!
+//! Supported subset (other shapes fail with [`TurtleError::UnsupportedShape`]):
+//! - Predicates are named by element path from the resource root (`fhir:Patient.name.family`),
+//!   as type metadata for nested datatypes is not available here
+//! - Strings are plain literals; booleans and numbers are typed `xsd:boolean`, `xsd:integer`
+//!   and `xsd:decimal`
+//! - Nested resources (`contained`, Bundle entries) are blank nodes typed `fhir:{ResourceType}`
+//!   whose predicates restart at their own resource type
+//! - Primitive extensions (`_element`) are not supported
+//!
+//! See: http://hl7.org/fhir/rdf.html
+
+use serde_json::{Map, Value as JsonValue};
+
+const PREFIXES: &str = "@prefix fhir: <http://hl7.org/fhir/> .\n\
+                        @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .\n\n";
+
+/// Error type for Turtle serialization
+#[derive(Debug, thiserror::Error)]
+pub enum TurtleError {
+    #[error("Resource cannot be represented as Turtle: {0}")]
+    UnsupportedShape(String),
+}
+
+/// Serialize a FHIR resource to Turtle
+pub fn resource_to_turtle(resource: &JsonValue) -> Result<String, TurtleError> {
+    let obj = resource
+        .as_object()
+        .ok_or_else(|| TurtleError::UnsupportedShape("expected a JSON object".to_string()))?;
+    let resource_type = obj
+        .get("resourceType")
+        .and_then(JsonValue::as_str)
+        .filter(|rt| is_element_name(rt))
+        .ok_or_else(|| TurtleError::UnsupportedShape("missing resourceType".to_string()))?;
+
+    // Resource ids are restricted to [A-Za-z0-9-.], so they can be used in a relative IRI.
+    let subject = match obj.get("id").and_then(JsonValue::as_str) {
+        Some(id) if is_logical_id(id) => format!("<{}/{}>", resource_type, id),
+        _ => "[]".to_string(),
+    };
+
+    let mut statements = vec![
+        format!("a fhir:{}", resource_type),
+        "fhir:nodeRole fhir:treeRoot".to_string(),
+    ];
+    statements.extend(properties(resource_type, obj, 1, true)?);
+
+    Ok(format!(
+        "{}{} {} .\n",
+        PREFIXES,
+        subject,
+        statements.join(&format!(" ;\n{}", indent(1)))
+    ))
+}
+
+/// `fhir:{path}.{name} {objects}` statements for the elements of `obj`
+fn properties(
+    path: &str,
+    obj: &Map<String, JsonValue>,
+    depth: usize,
+    resource_root: bool,
+) -> Result<Vec<String>, TurtleError> {
+    let mut statements = Vec::new();
+    for (name, value) in obj {
+        if resource_root && name == "resourceType" {
+            continue;
+        }
+        if name.starts_with('_') {
+            return Err(TurtleError::UnsupportedShape(format!(
+                "primitive extensions are not supported ({}.{})",
+                path, name
+            )));
+        }
+        if !is_element_name(name) {
+            return Err(TurtleError::UnsupportedShape(format!(
+                "invalid element name '{}' in {}",
+                name, path
+            )));
+        }
+
+        let element_path = format!("{}.{}", path, name);
+        let objects = match value {
+            JsonValue::Null => continue,
+            JsonValue::Array(items) if items.is_empty() => continue,
+            JsonValue::Array(items) => items
+                .iter()
+                .enumerate()
+                .map(|(index, item)| node(&element_path, item, Some(index), depth))
+                .collect::<Result<Vec<_>, _>>()?
+                .join(", "),
+            other => node(&element_path, other, None, depth)?,
+        };
+        statements.push(format!("fhir:{} {}", element_path, objects));
+    }
+    Ok(statements)
+}
+
+/// Blank node for a single element value
+fn node(
+    path: &str,
+    value: &JsonValue,
+    index: Option<usize>,
+    depth: usize,
+) -> Result<String, TurtleError> {
+    let mut statements = Vec::new();
+    match value {
+        JsonValue::Object(obj) => {
+            // A nested resource starts a new path at its own type.
+            let resource_type = match obj.get("resourceType") {
+                Some(rt) => Some(rt.as_str().filter(|rt| is_element_name(rt)).ok_or_else(
+                    || TurtleError::UnsupportedShape(format!("invalid resourceType in {}", path)),
+                )?),
+                None => None,
+            };
+            if let Some(resource_type) = resource_type {
+                statements.push(format!("a fhir:{}", resource_type));
+            }
+            if let Some(index) = index {
+                statements.push(format!("fhir:index {}", index));
+            }
+            statements.extend(properties(
+                resource_type.unwrap_or(path),
+                obj,
+                depth + 1,
+                resource_type.is_some(),
+            )?);
+            if statements.is_empty() {
+                return Ok("[ ]".to_string());
+            }
+            let inner = indent(depth + 1);
+            Ok(format!(
+                "[\n{}{}\n{}]",
+                inner,
+                statements.join(&format!(" ;\n{}", inner)),
+                indent(depth)
+            ))
+        }
+        JsonValue::Array(_) | JsonValue::Null => Err(TurtleError::UnsupportedShape(format!(
+            "nested arrays and null array items are not supported ({})",
+            path
+        ))),
+        primitive => {
+            statements.push(format!("fhir:value {}", literal(primitive)));
+            if let Some(index) = index {
+                statements.push(format!("fhir:index {}", index));
+            }
+            Ok(format!("[ {} ]", statements.join(" ; ")))
+        }
+    }
+}
+
+fn literal(value: &JsonValue) -> String {
+    match value {
+        JsonValue::Bool(b) => format!("\"{}\"^^xsd:boolean", b),
+        JsonValue::Number(n) if n.is_i64() || n.is_u64() => format!("\"{}\"^^xsd:integer", n),
+        JsonValue::Number(n) => format!("\"{}\"^^xsd:decimal", n),
+        JsonValue::String(s) => quote(s),
+        other => quote(&other.to_string()),
+    }
+}
+
+/// Turtle string literal with `\`, `"` and line breaks escaped
+fn quote(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn indent(depth: usize) -> String {
+    "  ".repeat(depth)
+}
+
+fn is_element_name(name: &str) -> bool {
+    name.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+        && name.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+fn is_logical_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= 64
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_patient_to_turtle() {
+        let patient = json!({
+            "resourceType": "Patient",
+            "id": "p1",
+            "active": true,
+            "name": [{"family": "O\"Brien", "given": ["Jane", "Q"]}]
+        });
+
+        let turtle = resource_to_turtle(&patient).unwrap();
+
+        assert_eq!(
+            turtle,
+            r#"@prefix fhir: <http://hl7.org/fhir/> .
+@prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+
+<Patient/p1> a fhir:Patient ;
+  fhir:nodeRole fhir:treeRoot ;
+  fhir:Patient.id [ fhir:value "p1" ] ;
+  fhir:Patient.active [ fhir:value "true"^^xsd:boolean ] ;
+  fhir:Patient.name [
+    fhir:index 0 ;
+    fhir:Patient.name.family [ fhir:value "O\"Brien" ] ;
+    fhir:Patient.name.given [ fhir:value "Jane" ; fhir:index 0 ], [ fhir:value "Q" ; fhir:index 1 ]
+  ] .
+"#
+        );
+    }
+
+    #[test]
+    fn test_bundle_entries_are_typed_resource_nodes() {
+        let bundle = json!({
+            "resourceType": "Bundle",
+            "type": "searchset",
+            "entry": [{"resource": {"resourceType": "Patient", "id": "p1"}}]
+        });
+
+        let turtle = resource_to_turtle(&bundle).unwrap();
+
+        assert!(
+            turtle.ends_with(
+                r#"[] a fhir:Bundle ;
+  fhir:nodeRole fhir:treeRoot ;
+  fhir:Bundle.type [ fhir:value "searchset" ] ;
+  fhir:Bundle.entry [
+    fhir:index 0 ;
+    fhir:Bundle.entry.resource [
+      a fhir:Patient ;
+      fhir:Patient.id [ fhir:value "p1" ]
+    ]
+  ] .
+"#
+            ),
+            "{}",
+            turtle
+        );
+    }
+
+    #[test]
+    fn test_unsupported_shapes_are_rejected() {
+        let primitive_extension = json!({
+            "resourceType": "Patient",
+            "_birthDate": {"extension": [{"url": "http://example.org", "valueString": "x"}]}
+        });
+        assert!(matches!(
+            resource_to_turtle(&primitive_extension),
+            Err(TurtleError::UnsupportedShape(_))
+        ));
+    }
+}
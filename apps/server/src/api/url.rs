@@ -1,12 +1,19 @@
 //! URL helpers for building absolute FHIR base URLs.
 
+use crate::config::ServerConfig;
 use axum::http::HeaderMap;
 
 /// Build the FHIR base URL (`{scheme}://{host}/fhir`) using forwarding headers when present.
 ///
 /// This is important for correct Bundle links and CapabilityStatement URLs when running behind
-/// reverse proxies.
-pub fn base_url_from_headers(headers: &HeaderMap) -> String {
+/// reverse proxies. The derived URL is only used when it is trusted by the server configuration
+/// (see [`trusted_base_url`]).
+pub fn base_url_from_headers(headers: &HeaderMap, server: &ServerConfig) -> String {
+    trusted_base_url(forwarded_base_url(headers), server)
+}
+
+/// Derive the FHIR base URL from forwarding headers without any trust check.
+pub fn forwarded_base_url(headers: &HeaderMap) -> String {
     let scheme = headers
         .get("x-forwarded-proto")
         .or_else(|| headers.get("x-forwarded-scheme"))
@@ -21,3 +28,43 @@ pub fn base_url_from_headers(headers: &HeaderMap) -> String {
 
     format!("{}://{}/fhir", scheme, host)
 }
+
+/// Check a header-derived base URL against `server.base_url` and `server.trusted_base_urls`.
+///
+/// When neither is configured every derived URL is accepted. Otherwise an untrusted URL (e.g. a
+/// spoofed `X-Forwarded-Host`) falls back to the canonical base URL, or the first trusted one.
+pub fn trusted_base_url(derived: String, server: &ServerConfig) -> String {
+    let canonical = server
+        .base_url
+        .as_deref()
+        .map(normalize_base_url)
+        .filter(|url| !url.is_empty());
+    let trusted: Vec<&str> = server
+        .trusted_base_urls
+        .iter()
+        .map(|url| normalize_base_url(url))
+        .filter(|url| !url.is_empty())
+        .collect();
+
+    if canonical.is_none() && trusted.is_empty() {
+        return derived;
+    }
+
+    let candidate = normalize_base_url(&derived);
+    if let Some(matched) = canonical
+        .iter()
+        .chain(trusted.iter())
+        .find(|url| url.eq_ignore_ascii_case(candidate))
+    {
+        return matched.to_string();
+    }
+
+    canonical
+        .or_else(|| trusted.first().copied())
+        .unwrap_or(candidate)
+        .to_string()
+}
+
+fn normalize_base_url(url: &str) -> &str {
+    url.trim().trim_end_matches('/')
+}
@@ -31,6 +31,9 @@ pub struct Principal {
     pub audience: Option<Vec<String>>,
     pub client_id: Option<String>,
     pub patient: Option<String>,
+    /// Security labels granted to the principal (`auth.security_labels.claim`).
+    #[serde(default)]
+    pub security_labels: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -273,6 +276,8 @@ impl AuthManager {
             .and_then(|v| v.as_str())
             .map(|s| s.to_string());
 
+        let security_labels = extract_string_list(&claims, &self.config.auth.security_labels.claim);
+
         Principal {
             subject,
             scopes,
@@ -280,6 +285,7 @@ impl AuthManager {
             audience,
             client_id,
             patient,
+            security_labels,
         }
     }
 
@@ -408,6 +414,18 @@ fn extract_scopes(claims: &serde_json::Value) -> Vec<String> {
     Vec::new()
 }
 
+/// Read a claim holding either a JSON array of strings or a space-delimited string.
+fn extract_string_list(claims: &serde_json::Value, claim: &str) -> Vec<String> {
+    match claims.get(claim) {
+        Some(serde_json::Value::String(s)) => s.split_whitespace().map(|s| s.to_string()).collect(),
+        Some(serde_json::Value::Array(arr)) => arr
+            .iter()
+            .filter_map(|v| v.as_str().map(|s| s.to_string()))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
 /// Extractor for the authenticated principal attached by middleware.
 ///
 /// Use `Option<AuthenticatedPrincipal>` in handlers for optional auth.
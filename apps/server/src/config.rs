@@ -1,5 +1,9 @@
 //! Configuration management for the FHIR server
 
+use crate::services::{
+    id_strategy::IdStrategy, narrative::NarrativeMode,
+    referential_integrity::ReferentialIntegrityMode,
+};
 use serde::Deserialize;
 use std::net::SocketAddr;
 
@@ -32,6 +36,14 @@ pub struct ServerConfig {
     /// Default: 50 MB
     #[serde(default = "default_max_response_body_size")]
     pub max_response_body_size: usize,
+    /// Canonical public FHIR base URL (e.g. `https://fhir.example.org/fhir`).
+    /// Used for links and references when the forwarded base URL is not trusted.
+    #[serde(default)]
+    pub base_url: Option<String>,
+    /// Additional base URLs that may be derived from forwarding headers (one per tenant).
+    /// When this and `base_url` are both unset, any forwarded host is accepted.
+    #[serde(default)]
+    pub trusted_base_urls: Vec<String>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -97,6 +109,10 @@ pub struct FhirConfig {
     #[serde(default)]
     pub packages: Vec<FhirPackageConfig>,
     pub registry_url: Option<String>,
+    /// Keep partial package downloads in `<package cache>/.downloads` so an interrupted
+    /// transfer resumes from where it stopped on the next install. Default: false
+    #[serde(default)]
+    pub resumable_package_downloads: bool,
     /// Install internal packages from fhir_packages/ directory
     #[serde(default = "default_true")]
     pub install_internal_packages: bool,
@@ -119,14 +135,31 @@ pub struct FhirConfig {
     /// Default: true (allow update-as-create)
     #[serde(default = "default_true")]
     pub allow_update_create: bool,
+    /// How ids are assigned on create (POST):
+    /// - "uuid" (default): random UUID
+    /// - "sequential": per-resource-type counter (1, 2, 3, ...), skipping ids already in use
+    /// - "client-allowed": keep a valid client-supplied `id`, otherwise generate a UUID; an id
+    ///   already in use is rejected with 409
+    ///
+    /// Applies to POST entries of batch and transaction bundles as well.
+    #[serde(default)]
+    pub id_strategy: IdStrategy,
     /// When true, DELETE physically removes the resource and its history from storage.
     /// When false (default), DELETE is a soft delete that creates a deleted history entry.
     #[serde(default)]
     pub hard_delete: bool,
+    /// Enable `$expunge`, which permanently removes previous versions and deleted resources.
+    /// Default: false
+    #[serde(default)]
+    pub enable_expunge: bool,
     #[serde(default)]
     pub capability_statement: CapabilityStatementConfig,
     #[serde(default)]
     pub referential_integrity: ReferentialIntegrityConfig,
+    #[serde(default)]
+    pub resources: FhirResourcesConfig,
+    #[serde(default)]
+    pub bulk_export: BulkExportConfig,
 }
 
 /// Configuration for enabling/disabling specific FHIR interactions.
@@ -320,10 +353,17 @@ pub struct FhirSearchConfig {
     #[serde(default = "default_search_default_count")]
     pub default_count: usize,
     /// Maximum allowed _count value to prevent overly large result sets.
-    /// Requests exceeding this will return "too-costly" error.
+    /// Requests exceeding this will return "too-costly" error. Search requests
+    /// are clamped to `max_page_size` before this check.
     /// Default: 1000
     #[serde(default = "default_search_max_count")]
     pub max_count: usize,
+    /// Maximum page size returned by search.
+    /// Larger _count values are clamped to this size and an OperationOutcome
+    /// warning is added to the searchset Bundle.
+    /// Default: 1000
+    #[serde(default = "default_search_max_page_size")]
+    pub max_page_size: usize,
     /// Maximum total results across all pages (_maxresults cap).
     /// Default: 10000
     #[serde(default = "default_search_max_total_results")]
@@ -336,7 +376,18 @@ pub struct FhirSearchConfig {
     /// Default: 10
     #[serde(default = "default_search_max_includes")]
     pub max_includes: usize,
+    /// Maximum number of reference parameters a wildcard `_include=*` or
+    /// `_revinclude=*` may expand to. Default: 50
+    #[serde(default = "default_search_max_wildcard_include_params")]
+    pub max_wildcard_include_params: usize,
+    /// Implicit ordering for searches without `_sort`, in `_sort` syntax.
+    /// Only `_id` and `_lastUpdated` are accepted; `id` is always appended as the
+    /// final tie-breaker. Default: "-_lastUpdated"
+    #[serde(default = "default_search_default_sort")]
+    pub default_sort: String,
     /// SearchParameter.status values treated as active.
+    /// Parameters with any other status are not searchable. Listing "retired" keeps retired
+    /// parameters searchable, with a warning on every search that uses them.
     /// Default: ["draft", "active"]
     #[serde(default = "default_search_parameter_active_statuses")]
     pub search_parameter_active_statuses: Vec<String>,
@@ -346,6 +397,9 @@ pub struct FhirSearchConfig {
     /// Default: true
     #[serde(default = "default_true")]
     pub inline_indexing: bool,
+    /// In-memory cache of search results (off by default).
+    #[serde(default)]
+    pub cache: SearchCacheConfig,
 }
 
 impl Default for FhirSearchConfig {
@@ -355,11 +409,44 @@ impl Default for FhirSearchConfig {
             enable_content: true,
             default_count: default_search_default_count(),
             max_count: default_search_max_count(),
+            max_page_size: default_search_max_page_size(),
             max_total_results: default_search_max_total_results(),
             max_include_depth: default_search_max_include_depth(),
             max_includes: default_search_max_includes(),
+            max_wildcard_include_params: default_search_max_wildcard_include_params(),
+            default_sort: default_search_default_sort(),
             search_parameter_active_statuses: default_search_parameter_active_statuses(),
             inline_indexing: true,
+            cache: SearchCacheConfig::default(),
+        }
+    }
+}
+
+/// In-memory search result cache (`fhir.search.cache`).
+///
+/// Identical searches by principals with the same security-label scope are served from
+/// memory until the TTL expires or a resource of a searched type is written.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SearchCacheConfig {
+    /// Default: false
+    #[serde(default)]
+    pub enabled: bool,
+    /// How long a cached result may be served. Also bounds staleness for writes that
+    /// bypass resource hooks. Default: 5
+    #[serde(default = "default_search_cache_ttl_seconds")]
+    pub ttl_seconds: u64,
+    /// Maximum number of cached results; the least recently used are evicted first.
+    /// Default: 1000
+    #[serde(default = "default_search_cache_max_entries")]
+    pub max_entries: usize,
+}
+
+impl Default for SearchCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            ttl_seconds: default_search_cache_ttl_seconds(),
+            max_entries: default_search_cache_max_entries(),
         }
     }
 }
@@ -387,6 +474,12 @@ pub struct FhirPathConfig {
     /// Only applies when enable_external_http is true. Default: 5
     #[serde(default = "default_http_timeout")]
     pub http_timeout_seconds: u64,
+
+    /// Enable the non-standard `$fhirpath` instance operation, which evaluates an
+    /// arbitrary expression against a stored resource. Intended for debugging only.
+    /// Default: false
+    #[serde(default)]
+    pub enable_operation: bool,
 }
 
 impl Default for FhirPathConfig {
@@ -396,6 +489,7 @@ impl Default for FhirPathConfig {
             enable_external_http: false,
             resolve_cache_size: default_resolve_cache_size(),
             http_timeout_seconds: default_http_timeout(),
+            enable_operation: false,
         }
     }
 }
@@ -573,25 +667,53 @@ pub struct CapabilityStatementConfig {
 ///
 /// Controls whether the server validates that references point to existing resources
 /// and prevents deletion of resources that are referenced by others.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize)]
 pub struct ReferentialIntegrityConfig {
     /// Enforcement mode:
-    /// - "lenient" (default): no checks, current behavior
-    /// - "strict": reject writes with broken refs, reject deletes of referenced resources
-    #[serde(default = "default_referential_integrity_mode")]
-    pub mode: String,
+    /// - "off" / "lenient" (default): no checks
+    /// - "logical": check references on create/update and log broken ones, but accept the write
+    /// - "strict": reject writes with broken refs (422), reject deletes of referenced resources
+    #[serde(default)]
+    pub mode: ReferentialIntegrityMode,
+}
+
+/// Processing applied to resources as they are written.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct FhirResourcesConfig {
+    /// Narrative generation for resources created (POST) without a `text.div`:
+    /// - "off" (default): store resources as sent
+    /// - "minimal": add a generated narrative with the resource type, id and a few
+    ///   human-readable elements (name, code, status, ...)
+    ///
+    /// An existing narrative is never replaced.
+    #[serde(default)]
+    pub generate_narrative: NarrativeMode,
+    /// Strip active content (scripts, event handlers, `javascript:`/`data:` URLs, embedded
+    /// objects, styles) from the narrative of resources sent as XML. Default: false
+    #[serde(default)]
+    pub sanitize_xml_narrative: bool,
+}
+
+/// Bulk Data export (`$export`) configuration.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BulkExportConfig {
+    /// Directory where export jobs write their NDJSON files (one subdirectory per job).
+    /// Must be reachable by both the API server and the workers.
+    /// Default: "./data/bulk_export"
+    #[serde(default = "default_bulk_export_output_dir")]
+    pub output_dir: String,
 }
 
-impl Default for ReferentialIntegrityConfig {
+impl Default for BulkExportConfig {
     fn default() -> Self {
         Self {
-            mode: default_referential_integrity_mode(),
+            output_dir: default_bulk_export_output_dir(),
         }
     }
 }
 
-fn default_referential_integrity_mode() -> String {
-    "lenient".to_string()
+fn default_bulk_export_output_dir() -> String {
+    "./data/bulk_export".to_string()
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -801,6 +923,9 @@ pub struct AuthConfig {
 
     #[serde(default)]
     pub oidc: OidcAuthConfig,
+
+    #[serde(default)]
+    pub security_labels: SecurityLabelsAuthConfig,
 }
 
 impl Default for AuthConfig {
@@ -810,6 +935,38 @@ impl Default for AuthConfig {
             required: true,
             public_paths: default_auth_public_paths(),
             oidc: OidcAuthConfig::default(),
+            security_labels: SecurityLabelsAuthConfig::default(),
+        }
+    }
+}
+
+/// Security-label (`meta.security`) access filtering.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SecurityLabelsAuthConfig {
+    /// When enabled, resources carrying a security label the principal is not granted are
+    /// hidden from every read path (read, vread, history, search, batch, operations and
+    /// bulk export). Unlabeled resources stay visible; anonymous requests only see
+    /// unlabeled resources.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Token claim listing the security labels granted to the principal (a JSON array or a
+    /// space-delimited string of `system|code`, `|code` or bare `code` entries).
+    #[serde(default = "default_security_labels_claim")]
+    pub claim: String,
+
+    /// Label system assumed for bare codes in the claim.
+    /// Default: `http://terminology.hl7.org/CodeSystem/v3-Confidentiality`
+    #[serde(default = "default_security_labels_default_system")]
+    pub default_system: String,
+}
+
+impl Default for SecurityLabelsAuthConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            claim: default_security_labels_claim(),
+            default_system: default_security_labels_default_system(),
         }
     }
 }
@@ -1078,6 +1235,10 @@ fn default_search_max_count() -> usize {
     1000
 }
 
+fn default_search_max_page_size() -> usize {
+    1000
+}
+
 fn default_search_max_total_results() -> usize {
     10000
 }
@@ -1090,10 +1251,26 @@ fn default_search_max_includes() -> usize {
     10
 }
 
+fn default_search_max_wildcard_include_params() -> usize {
+    50
+}
+
+fn default_search_default_sort() -> String {
+    crate::db::search::query_builder::DEFAULT_SORT.to_string()
+}
+
 fn default_search_parameter_active_statuses() -> Vec<String> {
     vec!["draft".to_string(), "active".to_string()]
 }
 
+fn default_search_cache_ttl_seconds() -> u64 {
+    5
+}
+
+fn default_search_cache_max_entries() -> usize {
+    1000
+}
+
 fn default_resolve_cache_size() -> usize {
     100
 }
@@ -1130,6 +1307,14 @@ fn default_oidc_http_timeout_seconds() -> u64 {
     5
 }
 
+fn default_security_labels_claim() -> String {
+    "security_labels".to_string()
+}
+
+fn default_security_labels_default_system() -> String {
+    "http://terminology.hl7.org/CodeSystem/v3-Confidentiality".to_string()
+}
+
 impl Default for CapabilityStatementConfig {
     fn default() -> Self {
         Self {
@@ -1189,6 +1374,10 @@ impl Config {
                 default_search_default_count() as i64,
             )?
             .set_default("fhir.search.max_count", default_search_max_count() as i64)?
+            .set_default(
+                "fhir.search.max_page_size",
+                default_search_max_page_size() as i64,
+            )?
             .set_default(
                 "fhir.search.max_total_results",
                 default_search_max_total_results() as i64,
@@ -1201,11 +1390,28 @@ impl Config {
                 "fhir.search.max_includes",
                 default_search_max_includes() as i64,
             )?
+            .set_default(
+                "fhir.search.max_wildcard_include_params",
+                default_search_max_wildcard_include_params() as i64,
+            )?
+            .set_default("fhir.search.default_sort", default_search_default_sort())?
+            .set_default("fhir.search.cache.enabled", false)?
+            .set_default(
+                "fhir.search.cache.ttl_seconds",
+                default_search_cache_ttl_seconds() as i64,
+            )?
+            .set_default(
+                "fhir.search.cache.max_entries",
+                default_search_cache_max_entries() as i64,
+            )?
             .set_default("fhir.default_format", default_format())?
             .set_default("fhir.default_prefer_return", default_prefer_return())?
             .set_default("fhir.allow_update_create", default_true())?
+            .set_default("fhir.id_strategy", "uuid")?
             .set_default("fhir.hard_delete", default_false())?
-            .set_default("fhir.referential_integrity.mode", default_referential_integrity_mode())?
+            .set_default("fhir.referential_integrity.mode", "lenient")?
+            .set_default("fhir.resources.generate_narrative", "off")?
+            .set_default("fhir.bulk_export.output_dir", default_bulk_export_output_dir())?
             .set_default("workers.enabled", default_true())?
             .set_default("workers.embedded", default_true())?
             .set_default("workers.poll_interval_seconds", default_poll_interval())?
@@ -1271,6 +1477,15 @@ impl Config {
                 "auth.oidc.http_timeout_seconds",
                 default_oidc_http_timeout_seconds() as i64,
             )?
+            .set_default("auth.security_labels.enabled", default_false())?
+            .set_default(
+                "auth.security_labels.claim",
+                default_security_labels_claim(),
+            )?
+            .set_default(
+                "auth.security_labels.default_system",
+                default_security_labels_default_system(),
+            )?
             // Add config file if exists
             .add_source(config::File::with_name("ferrum").required(false))
             // Override with environment variables
@@ -1285,6 +1500,7 @@ impl Config {
                     // Explicitly specify which keys are lists to prevent other values
                     // from being incorrectly parsed as arrays
                     .with_list_parse_key("server.cors_origins")
+                    .with_list_parse_key("server.trusted_base_urls")
                     .with_list_parse_key("fhir.search.search_parameter_active_statuses")
                     .with_list_parse_key("fhir.capability_statement.supported_resources")
                     .with_list_parse_key("auth.public_paths")
@@ -1312,6 +1528,25 @@ impl Config {
 
     /// Validate configuration
     pub fn validate(&self) -> Result<(), String> {
+        for (key, value) in self
+            .server
+            .base_url
+            .iter()
+            .map(|value| ("server.base_url", value))
+            .chain(
+                self.server
+                    .trusted_base_urls
+                    .iter()
+                    .map(|value| ("server.trusted_base_urls", value)),
+            )
+        {
+            let parsed =
+                url::Url::parse(value).map_err(|e| format!("{}: '{}': {}", key, value, e))?;
+            if !matches!(parsed.scheme(), "http" | "https") {
+                return Err(format!("{} must use http or https (got '{}')", key, value));
+            }
+        }
+
         // Validate default package filters
         self.fhir.default_packages.core.filter.validate()?;
         self.fhir.default_packages.extensions.filter.validate()?;
@@ -1327,6 +1562,18 @@ impl Config {
             })?;
         }
 
+        crate::db::search::query_builder::parse_default_sort(&self.fhir.search.default_sort)
+            .map_err(|e| format!("fhir.search.default_sort: {}", e))?;
+
+        if self.fhir.search.cache.enabled {
+            if self.fhir.search.cache.ttl_seconds == 0 {
+                return Err("fhir.search.cache.ttl_seconds must be > 0".to_string());
+            }
+            if self.fhir.search.cache.max_entries == 0 {
+                return Err("fhir.search.cache.max_entries must be > 0".to_string());
+            }
+        }
+
         if self.workers.poll_interval_seconds == 0 {
             return Err("workers.poll_interval_seconds must be > 0".to_string());
         }
@@ -1369,6 +1616,13 @@ impl Config {
             }
         }
 
+        if self.auth.security_labels.enabled && self.auth.security_labels.claim.trim().is_empty() {
+            return Err(
+                "auth.security_labels.claim must not be empty when security labels are enabled"
+                    .to_string(),
+            );
+        }
+
         if self.ui.session_ttl_seconds == 0 {
             return Err("ui.session_ttl_seconds must be > 0".to_string());
         }
@@ -43,7 +43,7 @@ pub fn db_backed_fhir_context_with_fallback(
 ) -> Result<Arc<dyn FhirContext>> {
     let db: Arc<dyn ConformanceResourceProvider> = Arc::new(DbConformanceProvider::new(pool));
     let provider: Arc<dyn ConformanceResourceProvider> =
-        Arc::new(FallbackConformanceProvider::new(db, fallback));
+        Arc::new(FallbackConformanceProvider::new(vec![db, fallback]));
     let ctx =
         FlexibleFhirContext::new(provider).map_err(|e| crate::Error::FhirContext(e.to_string()))?;
     Ok(Arc::new(ctx))
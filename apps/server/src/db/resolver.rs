@@ -301,7 +301,7 @@ impl FhirResourceResolver {
             }
         };
 
-        Ok(result)
+        Ok(result.filter(crate::security_labels::is_visible))
     }
 
     /// Resolve a canonical reference by searching for URL (and optionally version)
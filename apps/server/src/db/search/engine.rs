@@ -18,12 +18,18 @@ pub use query_builder::QueryBuilder;
 mod api;
 mod compartments;
 mod execute;
+mod explain;
 mod filter;
 mod includes;
 mod normalize;
 mod resolve;
 mod sort;
 mod util;
+mod validate;
+
+pub use explain::SearchExplanation;
+pub(crate) use util::is_valid_fhir_logical_id;
+pub use validate::SearchParamCheck;
 
 /// Search engine executes FHIR searches against the database
 pub struct SearchEngine {
@@ -33,5 +39,7 @@ pub struct SearchEngine {
     enable_text_search: bool,
     enable_content_search: bool,
     runtime_config_cache: Option<Arc<RuntimeConfigCache>>,
+    /// Ordering for searches without `_sort` (parsed from `search_config.default_sort`).
+    default_sort: Vec<query_builder::ResolvedSort>,
     search_config: crate::config::FhirSearchConfig,
 }
@@ -6,11 +6,20 @@ use crate::Result;
 use sqlx::PgConnection;
 use sqlx::PgPool;
 use std::sync::Arc;
+use std::time::Instant;
 
 impl SearchEngine {
     /// Create a new search engine.
     pub fn new(db_pool: PgPool, search_config: crate::config::FhirSearchConfig) -> Self {
         let param_cache = Arc::new(SearchParamCache::new(db_pool.clone()));
+        // The configured value is validated at startup; fall back to the built-in default
+        // for engines constructed from unvalidated configs.
+        let default_sort = query_builder::parse_default_sort(&search_config.default_sort)
+            .unwrap_or_else(|e| {
+                tracing::warn!("Ignoring fhir.search.default_sort: {}", e);
+                query_builder::parse_default_sort(query_builder::DEFAULT_SORT)
+                    .expect("valid built-in default sort")
+            });
         Self {
             db_pool,
             param_cache,
@@ -18,6 +27,7 @@ impl SearchEngine {
             enable_text_search: search_config.enable_text,
             enable_content_search: search_config.enable_content,
             runtime_config_cache: None,
+            default_sort,
             search_config,
         }
     }
@@ -96,6 +106,8 @@ impl SearchEngine {
             max_includes,
         )?;
 
+        let started = Instant::now();
+
         // Resolve search parameters to their types
         let (mut resolved_params, mut resolved_filter, unknown_params) =
             if let Some(rt) = resource_type {
@@ -120,10 +132,22 @@ impl SearchEngine {
                 .await?;
         }
 
+        let param_codes: Vec<String> = resolved_params.iter().map(|p| p.code.clone()).collect();
+
         let resolved_sort = self
             .resolve_sort_params(conn, resource_type, params)
             .await?;
 
+        let (unindexed_params, param_warnings) = match searched_type_hint {
+            Some(rt) => (
+                self.find_unindexed_params(conn, rt, &resolved_params)
+                    .await?,
+                self.find_param_status_warnings(conn, rt, &resolved_params)
+                    .await?,
+            ),
+            None => (Vec::new(), Vec::new()),
+        };
+
         // Skip fetching resources for `_summary=count` mode.
         let should_fetch_resources = !query_builder::should_skip_main_query(params);
 
@@ -135,6 +159,7 @@ impl SearchEngine {
             )
             .with_filter(resolved_filter.clone())
             .with_resolved_sort(resolved_sort.clone())
+            .with_default_sort(self.default_sort.clone())
             .with_base_url(base_url)
             .with_default_count(default_count);
             self.execute_search(conn, query).await?
@@ -146,11 +171,13 @@ impl SearchEngine {
         }
 
         // Handle _include and _revinclude (skip for summary=count)
-        let included = if should_fetch_resources && params.has_includes() {
-            self.fetch_includes(conn, &resources, params).await?
+        let mut included = if should_fetch_resources && params.has_includes() {
+            self.fetch_includes(conn, &resources, params, max_include_depth)
+                .await?
         } else {
             Vec::new()
         };
+        included.retain(crate::security_labels::is_visible);
 
         // Calculate total if requested
         let total = if params.should_calculate_total() {
@@ -168,11 +195,19 @@ impl SearchEngine {
             None
         };
 
+        crate::metrics::observe_search_parameter_durations(
+            resource_type.unwrap_or("*"),
+            param_codes.iter().map(String::as_str),
+            started.elapsed(),
+        );
+
         Ok(SearchResult {
             resources,
             total,
             included,
             unknown_params,
+            unindexed_params,
+            param_warnings,
         })
     }
 
@@ -262,9 +297,13 @@ impl SearchEngine {
                 total: Some(0),
                 included: Vec::new(),
                 unknown_params: Vec::new(),
+                unindexed_params: Vec::new(),
+                param_warnings: Vec::new(),
             });
         }
 
+        let started = Instant::now();
+
         // Resolve search parameters to their types (for tracking unknown params)
         let (mut resolved_params, mut resolved_filter, unknown_params) =
             if let Some(rt) = resource_type {
@@ -289,10 +328,22 @@ impl SearchEngine {
                 .await?;
         }
 
+        let param_codes: Vec<String> = resolved_params.iter().map(|p| p.code.clone()).collect();
+
         let resolved_sort = self
             .resolve_sort_params(conn, resource_type, params)
             .await?;
 
+        let (unindexed_params, param_warnings) = match searched_type_hint {
+            Some(rt) => (
+                self.find_unindexed_params(conn, rt, &resolved_params)
+                    .await?,
+                self.find_param_status_warnings(conn, rt, &resolved_params)
+                    .await?,
+            ),
+            None => (Vec::new(), Vec::new()),
+        };
+
         let compartment = self
             .load_compartment_filter(conn, compartment_type, compartment_id, resource_type)
             .await?;
@@ -308,6 +359,7 @@ impl SearchEngine {
             )
             .with_filter(resolved_filter.clone())
             .with_resolved_sort(resolved_sort.clone())
+            .with_default_sort(self.default_sort.clone())
             .with_base_url(base_url)
             .with_default_count(default_count);
             self.execute_search(conn, query).await?
@@ -315,11 +367,13 @@ impl SearchEngine {
             Vec::new()
         };
 
-        let included = if should_fetch_resources && params.has_includes() {
-            self.fetch_includes(conn, &resources, params).await?
+        let mut included = if should_fetch_resources && params.has_includes() {
+            self.fetch_includes(conn, &resources, params, max_include_depth)
+                .await?
         } else {
             Vec::new()
         };
+        included.retain(crate::security_labels::is_visible);
 
         let total = if params.should_calculate_total() {
             let query =
@@ -333,11 +387,19 @@ impl SearchEngine {
             None
         };
 
+        crate::metrics::observe_search_parameter_durations(
+            resource_type.unwrap_or("*"),
+            param_codes.iter().map(String::as_str),
+            started.elapsed(),
+        );
+
         Ok(SearchResult {
             resources,
             total,
             included,
             unknown_params,
+            unindexed_params,
+            param_warnings,
         })
     }
 }
@@ -0,0 +1,93 @@
+use super::{query_builder, QueryBuilder, SearchEngine, SearchParameters};
+use crate::Result;
+use serde::Serialize;
+use std::collections::HashSet;
+
+/// Placeholder shown instead of bind values that may carry client data.
+const REDACTED: &str = "<redacted>";
+
+/// SQL a type-level search would run, built without executing it.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchExplanation {
+    pub resource_type: String,
+    /// Parameterized SQL for the match query (`$1`, `$2`, ... refer to `bind_values`).
+    pub sql: String,
+    /// Bind values in placeholder order. Only resource types and parameter codes are
+    /// shown; everything else (search values, cursors) is redacted.
+    pub bind_values: Vec<serde_json::Value>,
+    /// Parameters that did not resolve and are left out of the SQL.
+    pub unknown_params: Vec<String>,
+}
+
+impl SearchEngine {
+    /// Run the resolve pipeline for a type-level search and return the generated SQL.
+    pub async fn explain_search(
+        &self,
+        resource_type: &str,
+        params: &SearchParameters,
+        base_url: Option<&str>,
+    ) -> Result<SearchExplanation> {
+        let mut conn = self
+            .db_pool
+            .acquire()
+            .await
+            .map_err(crate::Error::Database)?;
+
+        let (mut resolved_params, mut resolved_filter, unknown_params) = self
+            .resolve_search_params_type(&mut conn, resource_type, params)
+            .await?;
+        self.normalize_search_params(
+            &mut conn,
+            &mut resolved_params,
+            base_url,
+            Some(resource_type),
+        )
+        .await?;
+        if let Some(f) = resolved_filter.as_mut() {
+            self.normalize_filter_expr(&mut conn, f, base_url, Some(resource_type))
+                .await?;
+        }
+        let resolved_sort = self
+            .resolve_sort_params(&mut conn, Some(resource_type), params)
+            .await?;
+
+        let mut visible: HashSet<&str> = HashSet::new();
+        visible.insert(resource_type);
+        visible.extend(resolved_params.iter().map(|p| p.code.as_str()));
+
+        let (sql, binds) = QueryBuilder::with_resolved_params(
+            Some(resource_type),
+            params,
+            resolved_params.clone(),
+        )
+        .with_filter(resolved_filter)
+        .with_resolved_sort(resolved_sort)
+        .with_default_sort(self.default_sort.clone())
+        .with_base_url(base_url)
+        .with_default_count(self.search_config.default_count)
+        .build_sql();
+
+        let bind_values = binds
+            .iter()
+            .map(|value| match value {
+                query_builder::BindValue::Text(v) if visible.contains(v.as_str()) => {
+                    serde_json::Value::from(v.as_str())
+                }
+                query_builder::BindValue::TextArray(vs)
+                    if vs.iter().all(|v| visible.contains(v.as_str())) =>
+                {
+                    serde_json::Value::from(vs.clone())
+                }
+                _ => serde_json::Value::from(REDACTED),
+            })
+            .collect();
+
+        Ok(SearchExplanation {
+            resource_type: resource_type.to_string(),
+            sql,
+            bind_values,
+            unknown_params,
+        })
+    }
+}
@@ -1,15 +1,22 @@
 use super::{params, JsonValue, SearchEngine, SearchParameters};
+use crate::runtime_config::ConfigKey;
 use crate::Result;
 use sqlx::PgConnection;
 use std::collections::HashSet;
 
 impl SearchEngine {
     /// Fetch included resources based on `_include` and `_revinclude`.
+    ///
+    /// `:iterate` directives are applied repeatedly to newly included resources until
+    /// no new resources are found or `max_iterate_depth` passes have run. The
+    /// `processed` set doubles as the cycle guard: a resource is only ever added once.
+    /// Explicit directives already covered by a wildcard (`*`) directive are dropped.
     pub(super) async fn fetch_includes(
         &self,
         conn: &mut PgConnection,
         resources: &[JsonValue],
         params: &SearchParameters,
+        max_iterate_depth: usize,
     ) -> Result<Vec<JsonValue>> {
         let mut processed: HashSet<(String, String)> = HashSet::new();
         for r in resources {
@@ -21,236 +28,318 @@ impl SearchEngine {
             }
         }
 
+        let max_wildcard_params = self.max_wildcard_include_params().await;
+        let include: Vec<&params::IncludeParam> = params
+            .include
+            .iter()
+            .filter(|s| !covered_by_wildcard(s, &params.include))
+            .collect();
+        let revinclude: Vec<&params::IncludeParam> = params
+            .revinclude
+            .iter()
+            .filter(|s| !covered_by_wildcard(s, &params.revinclude))
+            .collect();
+
         let mut included = Vec::new();
 
         // Non-iterating includes apply only to the matching resources.
-        for spec in params.include.iter().filter(|s| !s.iterate) {
+        for spec in include.iter().filter(|s| !s.iterate) {
             self.collect_includes(
                 conn,
                 spec,
                 false,
                 resources,
+                max_wildcard_params,
                 &mut processed,
                 &mut included,
-                0,
             )
             .await?;
         }
-        for spec in params.revinclude.iter().filter(|s| !s.iterate) {
+        for spec in revinclude.iter().filter(|s| !s.iterate) {
             self.collect_includes(
                 conn,
                 spec,
                 true,
                 resources,
+                max_wildcard_params,
                 &mut processed,
                 &mut included,
-                0,
             )
             .await?;
         }
 
         // Iterating includes apply to included resources as well as matching resources.
-        // We loop to a fixpoint because multiple `:iterate` directives can feed each other.
-        const MAX_ITERATE_PASSES: usize = 3;
-        for _pass in 0..MAX_ITERATE_PASSES {
-            let before = processed.len();
-            let mut sources = Vec::with_capacity(resources.len() + included.len());
-            sources.extend_from_slice(resources);
-            sources.extend_from_slice(&included);
+        // The first pass starts from everything found so far; later passes only follow
+        // the resources added by the previous pass, since everything else was already
+        // visited. Multiple `:iterate` directives can feed each other within a pass.
+        let has_iterate = include.iter().chain(revinclude.iter()).any(|s| s.iterate);
+        if !has_iterate {
+            return Ok(included);
+        }
+
+        let mut frontier = Vec::with_capacity(resources.len() + included.len());
+        frontier.extend_from_slice(resources);
+        frontier.extend_from_slice(&included);
+
+        for _pass in 0..max_iterate_depth {
+            let mut added = Vec::new();
 
-            for spec in params.include.iter().filter(|s| s.iterate) {
+            for spec in include.iter().filter(|s| s.iterate) {
                 self.collect_includes(
                     conn,
                     spec,
                     false,
-                    &sources,
+                    &frontier,
+                    max_wildcard_params,
                     &mut processed,
-                    &mut included,
-                    0,
+                    &mut added,
                 )
                 .await?;
             }
-            for spec in params.revinclude.iter().filter(|s| s.iterate) {
-                self.collect_includes(conn, spec, true, &sources, &mut processed, &mut included, 0)
-                    .await?;
+            for spec in revinclude.iter().filter(|s| s.iterate) {
+                self.collect_includes(
+                    conn,
+                    spec,
+                    true,
+                    &frontier,
+                    max_wildcard_params,
+                    &mut processed,
+                    &mut added,
+                )
+                .await?;
             }
 
-            if processed.len() == before {
+            if added.is_empty() {
                 break;
             }
+
+            included.extend_from_slice(&added);
+            frontier = added;
         }
 
         Ok(included)
     }
 
+    /// Follow a single `_include`/`_revinclude` hop from `source_resources`.
+    ///
+    /// Resources already present in `processed` are skipped; new ones are appended to `out`.
+    /// A wildcard parameter (`*`) is first expanded to the concrete reference parameters
+    /// that can link the sources to other resources; an expansion larger than
+    /// `max_wildcard_params` is rejected before any include query runs.
+    #[allow(clippy::too_many_arguments)]
     pub(super) async fn collect_includes(
         &self,
         conn: &mut PgConnection,
         spec: &params::IncludeParam,
         is_reverse: bool,
         source_resources: &[JsonValue],
+        max_wildcard_params: usize,
         processed: &mut HashSet<(String, String)>,
         out: &mut Vec<JsonValue>,
-        depth: usize,
     ) -> Result<()> {
-        const MAX_DEPTH: usize = 3;
-        let mut current_depth = depth;
-        let mut current_sources: Vec<JsonValue> = source_resources.to_vec();
-
-        loop {
-            if current_sources.is_empty() {
-                return Ok(());
-            }
-            if spec.iterate && current_depth >= MAX_DEPTH {
-                return Ok(());
+        let mut src_types = Vec::new();
+        let mut src_ids = Vec::new();
+        for r in source_resources {
+            let Some(rt) = r.get("resourceType").and_then(|v| v.as_str()) else {
+                continue;
+            };
+            let Some(id) = r.get("id").and_then(|v| v.as_str()) else {
+                continue;
+            };
+            // For _include, source_type filters which source resources to follow refs from.
+            // For _revinclude, source_type is the type of resources TO include (not the sources),
+            // so we skip this filter — the SQL query filters sr.resource_type instead.
+            if !is_reverse && spec.source_type != "*" && spec.source_type != rt {
+                continue;
             }
+            src_types.push(rt.to_string());
+            src_ids.push(id.to_string());
+        }
+
+        if src_types.is_empty() {
+            return Ok(());
+        }
 
-            let mut src_types = Vec::new();
-            let mut src_ids = Vec::new();
-            for r in &current_sources {
-                let Some(rt) = r.get("resourceType").and_then(|v| v.as_str()) else {
-                    continue;
-                };
-                let Some(id) = r.get("id").and_then(|v| v.as_str()) else {
-                    continue;
-                };
-                // For _include, source_type filters which source resources to follow refs from.
-                // For _revinclude, source_type is the type of resources TO include (not the sources),
-                // so we skip this filter — the SQL query filters sr.resource_type instead.
-                if !is_reverse && spec.source_type != "*" && spec.source_type != rt {
-                    continue;
+        let wildcard_params =
+            if spec.param == "*" {
+                let expanded = self
+                    .expand_wildcard_include(conn, spec, is_reverse, &src_types)
+                    .await?;
+                if expanded.0.is_empty() {
+                    return Ok(());
                 }
-                src_types.push(rt.to_string());
-                src_ids.push(id.to_string());
-            }
+                if expanded.0.len() > max_wildcard_params {
+                    return Err(crate::Error::TooCostly(format!(
+                    "Wildcard {} expands to {} reference parameters, exceeding the maximum of {}",
+                    if is_reverse { "_revinclude" } else { "_include" },
+                    expanded.0.len(),
+                    max_wildcard_params
+                )));
+                }
+                Some(expanded)
+            } else {
+                None
+            };
 
-            if src_types.is_empty() {
-                return Ok(());
-            }
+        // $1/$2 are always src_types/src_ids; track the next bind parameter index.
+        let mut next_bind = 3u32;
+        let mut sql = if is_reverse {
+            // Find resources that reference our sources.
+            String::from(
+                r#"
+                SELECT DISTINCT r.resource
+                FROM search_reference sr
+                INNER JOIN UNNEST($1::text[], $2::text[]) AS tgt(ttype, tid)
+                    ON sr.target_type = tgt.ttype AND sr.target_id = tgt.tid
+                INNER JOIN resources r
+                    ON r.resource_type = sr.resource_type AND r.id = sr.resource_id AND r.version_id = sr.version_id
+                WHERE r.is_current = true AND r.deleted = false
+                "#,
+            )
+        } else {
+            // Follow references from our sources.
+            String::from(
+                r#"
+                SELECT DISTINCT r.resource
+                FROM resources src
+                INNER JOIN UNNEST($1::text[], $2::text[]) AS s(rtype, rid)
+                    ON src.resource_type = s.rtype AND src.id = s.rid
+                INNER JOIN search_reference sr
+                    ON sr.resource_type = src.resource_type AND sr.resource_id = src.id AND sr.version_id = src.version_id
+                INNER JOIN resources r
+                    ON r.resource_type = sr.target_type AND r.id = sr.target_id
+                WHERE src.is_current = true AND src.deleted = false
+                  AND r.is_current = true AND r.deleted = false
+                "#,
+            )
+        };
 
-            let included: Vec<JsonValue> = if is_reverse {
-                // Find resources that reference our sources.
-                // Track bind parameter index; $1/$2 are always src_types/src_ids.
-                let mut next_bind = 3u32;
-                let mut sql = String::from(
-                    r#"
-                    SELECT DISTINCT r.resource
-                    FROM search_reference sr
-                    INNER JOIN UNNEST($1::text[], $2::text[]) AS tgt(ttype, tid)
-                        ON sr.target_type = tgt.ttype AND sr.target_id = tgt.tid
-                    INNER JOIN resources r
-                        ON r.resource_type = sr.resource_type AND r.id = sr.resource_id AND r.version_id = sr.version_id
-                    WHERE r.is_current = true AND r.deleted = false
-                    "#,
-                );
+        // For _revinclude, filter by the referencing resource type (e.g. only Condition rows).
+        let filter_source_type = is_reverse && spec.source_type != "*";
+        if filter_source_type {
+            sql.push_str(&format!(" AND sr.resource_type = ${next_bind}"));
+            next_bind += 1;
+        }
+        if wildcard_params.is_some() {
+            sql.push_str(&format!(
+                " AND (sr.resource_type, sr.parameter_name) IN (SELECT * FROM UNNEST(${}::text[], ${}::text[]))",
+                next_bind,
+                next_bind + 1
+            ));
+            next_bind += 2;
+        } else {
+            sql.push_str(&format!(" AND sr.parameter_name = ${next_bind}"));
+            next_bind += 1;
+        }
+        if spec.target_type.is_some() {
+            sql.push_str(&format!(" AND sr.target_type = ${next_bind}"));
+            // next_bind += 1; // last bind
+        }
 
-                // Filter by the source resource type (e.g. only Condition rows, not all)
-                let filter_source_type = spec.source_type != "*";
-                if filter_source_type {
-                    sql.push_str(&format!(" AND sr.resource_type = ${next_bind}"));
-                    next_bind += 1;
-                }
+        let mut q = sqlx::query_scalar::<_, JsonValue>(&sql)
+            .bind(&src_types)
+            .bind(&src_ids);
+        if filter_source_type {
+            q = q.bind(spec.source_type.clone());
+        }
+        if let Some((param_types, param_names)) = &wildcard_params {
+            q = q.bind(param_types).bind(param_names);
+        } else {
+            q = q.bind(spec.param.clone());
+        }
+        if let Some(tt) = &spec.target_type {
+            q = q.bind(tt.clone());
+        }
+        let included: Vec<JsonValue> = q
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(crate::Error::Database)?;
 
-                if spec.param != "*" {
-                    sql.push_str(&format!(" AND sr.parameter_name = ${next_bind}"));
-                    next_bind += 1;
-                }
-                if spec.target_type.is_some() {
-                    sql.push_str(&format!(" AND sr.target_type = ${next_bind}"));
-                    // next_bind += 1; // last bind
-                }
+        for r in included {
+            let Some(rt) = r.get("resourceType").and_then(|v| v.as_str()) else {
+                continue;
+            };
+            let Some(id) = r.get("id").and_then(|v| v.as_str()) else {
+                continue;
+            };
+            let key = (rt.to_string(), id.to_string());
+            if processed.insert(key) {
+                out.push(r);
+            }
+        }
 
-                let mut q = sqlx::query_scalar::<_, JsonValue>(&sql)
-                    .bind(&src_types)
-                    .bind(&src_ids);
-                if filter_source_type {
-                    q = q.bind(spec.source_type.clone());
-                }
-                if spec.param != "*" {
-                    q = q.bind(spec.param.clone());
-                }
-                if let Some(tt) = &spec.target_type {
-                    q = q.bind(tt.clone());
-                }
-                q.fetch_all(&mut *conn)
-                    .await
-                    .map_err(crate::Error::Database)?
-            } else {
-                // Follow references from our sources.
-                let mut sql = String::from(
-                    r#"
-                    SELECT DISTINCT r.resource
-                    FROM resources src
-                    INNER JOIN UNNEST($1::text[], $2::text[]) AS s(rtype, rid)
-                        ON src.resource_type = s.rtype AND src.id = s.rid
-                    INNER JOIN search_reference sr
-                        ON sr.resource_type = src.resource_type AND sr.resource_id = src.id AND sr.version_id = src.version_id
-                    INNER JOIN resources r
-                        ON r.resource_type = sr.target_type AND r.id = sr.target_id
-                    WHERE src.is_current = true AND src.deleted = false
-                      AND r.is_current = true AND r.deleted = false
-                    "#,
-                );
+        Ok(())
+    }
 
-                if spec.param == "*" && spec.target_type.is_none() {
-                    sqlx::query_scalar::<_, JsonValue>(&sql)
-                        .bind(&src_types)
-                        .bind(&src_ids)
-                        .fetch_all(&mut *conn)
-                        .await
-                        .map_err(crate::Error::Database)?
-                } else if spec.param == "*" {
-                    sql.push_str(" AND sr.target_type = $3");
-                    sqlx::query_scalar::<_, JsonValue>(&sql)
-                        .bind(&src_types)
-                        .bind(&src_ids)
-                        .bind(spec.target_type.clone().unwrap())
-                        .fetch_all(&mut *conn)
-                        .await
-                        .map_err(crate::Error::Database)?
-                } else if spec.target_type.is_none() {
-                    sql.push_str(" AND sr.parameter_name = $3");
-                    sqlx::query_scalar::<_, JsonValue>(&sql)
-                        .bind(&src_types)
-                        .bind(&src_ids)
-                        .bind(spec.param.clone())
-                        .fetch_all(&mut *conn)
-                        .await
-                        .map_err(crate::Error::Database)?
-                } else {
-                    sql.push_str(" AND sr.parameter_name = $3 AND sr.target_type = $4");
-                    sqlx::query_scalar::<_, JsonValue>(&sql)
-                        .bind(&src_types)
-                        .bind(&src_ids)
-                        .bind(spec.param.clone())
-                        .bind(spec.target_type.clone().unwrap())
-                        .fetch_all(&mut *conn)
-                        .await
-                        .map_err(crate::Error::Database)?
-                }
-            };
+    /// Expand a wildcard include to the reference parameters that can link the sources.
+    ///
+    /// The expansion comes from the search parameter definitions rather than the stored
+    /// references, so its size is known before any resource data is queried. Returns parallel
+    /// `(resource_type, parameter_name)` arrays of the referencing side, sorted for a stable
+    /// expansion.
+    async fn expand_wildcard_include(
+        &self,
+        conn: &mut PgConnection,
+        spec: &params::IncludeParam,
+        is_reverse: bool,
+        src_types: &[String],
+    ) -> Result<(Vec<String>, Vec<String>)> {
+        let mut src_types: Vec<&str> = src_types.iter().map(String::as_str).collect();
+        src_types.sort_unstable();
+        src_types.dedup();
+
+        // For _include the sources hold the references; for _revinclude they are the targets.
+        let referencing_types = if !is_reverse {
+            src_types.iter().map(|rt| rt.to_string()).collect()
+        } else if spec.source_type != "*" {
+            vec![spec.source_type.clone()]
+        } else {
+            self.param_cache
+                .reference_param_types_with_conn(conn)
+                .await?
+        };
 
-            let mut newly_added = Vec::new();
-            for r in included {
-                let Some(rt) = r.get("resourceType").and_then(|v| v.as_str()) else {
-                    continue;
-                };
-                let Some(id) = r.get("id").and_then(|v| v.as_str()) else {
-                    continue;
-                };
-                let key = (rt.to_string(), id.to_string());
-                if processed.insert(key) {
-                    newly_added.push(r.clone());
-                    out.push(r);
+        let mut param_types = Vec::new();
+        let mut param_names = Vec::new();
+        for rt in referencing_types {
+            for def in self
+                .param_cache
+                .reference_params_with_conn(conn, &rt)
+                .await?
+            {
+                let may_target =
+                    |t: &str| def.targets.is_empty() || def.targets.iter().any(|x| x == t);
+                let links_sources = !is_reverse || src_types.iter().any(|t| may_target(t));
+                let matches_target = spec.target_type.as_deref().is_none_or(may_target);
+                if links_sources && matches_target {
+                    param_types.push(rt.clone());
+                    param_names.push(def.code);
                 }
             }
+        }
 
-            if !spec.iterate || newly_added.is_empty() {
-                return Ok(());
-            }
+        Ok((param_types, param_names))
+    }
 
-            current_sources = newly_added;
-            current_depth += 1;
+    /// Configured cap on how many parameters a wildcard include may expand to.
+    async fn max_wildcard_include_params(&self) -> usize {
+        match &self.runtime_config_cache {
+            Some(cache) => cache.get(ConfigKey::SearchMaxWildcardIncludeParams).await,
+            None => self.search_config.max_wildcard_include_params,
         }
     }
 }
+
+/// Whether an explicit include is already covered by a wildcard include in the same list.
+///
+/// Covered specs are skipped: the wildcard reaches the same resources, so running them
+/// again would only repeat the join.
+fn covered_by_wildcard(spec: &params::IncludeParam, specs: &[params::IncludeParam]) -> bool {
+    spec.param != "*"
+        && specs.iter().any(|w| {
+            w.param == "*"
+                && w.iterate == spec.iterate
+                && (w.source_type == "*" || w.source_type == spec.source_type)
+                && (w.target_type.is_none() || w.target_type == spec.target_type)
+        })
+}
@@ -837,6 +837,119 @@ impl SearchEngine {
         Ok((resolved, filter, unknown))
     }
 
+    /// Codes of resolved parameters whose index may not cover every stored resource.
+    ///
+    /// A parameter's `indexed_from` watermark is set when it is created or its expression
+    /// changes, and cleared once a reindex covers it (for Resource and DomainResource
+    /// parameters, a type-level reindex only lifts it for that type). Current resources last
+    /// written before the watermark were indexed without the parameter, so searching on it
+    /// can miss them. The watermark is cached with the parameter definition, so searches on
+    /// fully indexed parameters need no extra query.
+    pub(super) async fn find_unindexed_params(
+        &self,
+        conn: &mut PgConnection,
+        resource_type: &str,
+        resolved: &[query_builder::ResolvedParam],
+    ) -> Result<Vec<String>> {
+        // Built-ins read resource columns directly and never depend on index rows.
+        let mut codes: Vec<&str> = resolved
+            .iter()
+            .map(|p| p.code.as_str())
+            .filter(|code| !matches!(*code, "_id" | "_lastUpdated" | "_in" | "_list"))
+            .collect();
+        codes.sort();
+        codes.dedup();
+
+        let mut pending_ids = Vec::new();
+        for code in codes {
+            if let Some(def) = self
+                .param_cache
+                .get_param_with_conn(conn, resource_type, code)
+                .await?
+            {
+                if !def.is_indexed_for(resource_type) {
+                    pending_ids.push(def.id);
+                }
+            }
+        }
+        if pending_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let rows: Vec<(String, bool, bool)> = sqlx::query_as(
+            r#"
+            SELECT sp.code,
+                   sp.indexed_from IS NOT NULL AND NOT ($1 = ANY(sp.indexed_types)) AS pending,
+                   sp.indexed_from IS NOT NULL AND NOT ($1 = ANY(sp.indexed_types)) AND EXISTS (
+                       SELECT 1 FROM resources r
+                       WHERE r.resource_type = $1
+                         AND r.is_current = true AND r.deleted = false
+                         AND r.last_updated < sp.indexed_from
+                   ) AS unindexed
+            FROM search_parameters sp
+            WHERE sp.id = ANY($2)
+            ORDER BY sp.code
+            "#,
+        )
+        .bind(resource_type)
+        .bind(&pending_ids)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(crate::Error::Database)?;
+
+        let mut unindexed = Vec::new();
+        for (code, pending, is_unindexed) in rows {
+            if !pending {
+                // Reindexed since the definition was cached.
+                self.param_cache.mark_indexed(resource_type, &code);
+            } else if is_unindexed {
+                unindexed.push(code);
+            }
+        }
+        Ok(unindexed)
+    }
+
+    /// Warnings for resolved parameters whose SearchParameter is retired or experimental.
+    ///
+    /// Such parameters still take part in the search; the client is only told that it
+    /// relies on a definition that may change or go away. Only active parameters resolve, so
+    /// retired ones reach this point only when `search_parameter_active_statuses` lists
+    /// `retired`.
+    pub(super) async fn find_param_status_warnings(
+        &self,
+        conn: &mut PgConnection,
+        resource_type: &str,
+        resolved: &[query_builder::ResolvedParam],
+    ) -> Result<Vec<String>> {
+        let mut codes: Vec<&str> = resolved.iter().map(|p| p.code.as_str()).collect();
+        codes.sort();
+        codes.dedup();
+
+        let mut warnings = Vec::new();
+        for code in codes {
+            let Some(def) = self
+                .param_cache
+                .get_param_with_conn(conn, resource_type, code)
+                .await?
+            else {
+                continue;
+            };
+            if def.is_retired() {
+                warnings.push(format!(
+                    "Search parameter {} for {} is retired",
+                    code, resource_type
+                ));
+            }
+            if def.experimental {
+                warnings.push(format!(
+                    "Search parameter {} for {} is experimental",
+                    code, resource_type
+                ));
+            }
+        }
+        Ok(warnings)
+    }
+
     pub(super) fn resolve_builtin_param(
         &self,
         p: &params::RawSearchParam,
@@ -1208,10 +1321,18 @@ impl SearchEngine {
             }
         }
 
-        // If still no target types, use the reference parameter's target types from definition
+        // If still no target types, use the reference parameter's declared targets
+        if target_types.is_empty() {
+            target_types = base_def
+                .targets
+                .iter()
+                .filter(|t| query_builder::is_valid_resource_type(t))
+                .cloned()
+                .collect();
+        }
+
+        // Last resort for definitions without declared targets: guess from the parameter name
         if target_types.is_empty() {
-            // Parse target types from expression
-            // For now, try common reference targets from the parameter name
             target_types = infer_reference_targets(resource_type, &base_def.code);
         }
 
@@ -1434,7 +1555,8 @@ fn parse_param_and_modifier(s: &str) -> (String, Option<query_builder::SearchMod
 }
 
 /// Infer likely reference target types based on the parameter name and source resource type
-/// This is a heuristic for when the chain doesn't explicitly specify the target type
+/// This is a heuristic for when neither the chain nor the parameter definition specifies the
+/// target type
 fn infer_reference_targets(resource_type: &str, param_code: &str) -> Vec<String> {
     // Common reference parameter names and their typical targets
     match param_code.to_lowercase().as_str() {
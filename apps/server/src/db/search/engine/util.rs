@@ -15,7 +15,7 @@ pub(super) fn is_untyped_logical_id_reference(raw: &str) -> bool {
     !s.contains('/')
 }
 
-pub(super) fn is_valid_fhir_logical_id(value: &str) -> bool {
+pub(crate) fn is_valid_fhir_logical_id(value: &str) -> bool {
     // FHIR id: [A-Za-z0-9\\-\\.]{1,64}
     let len = value.len();
     if len == 0 || len > 64 {
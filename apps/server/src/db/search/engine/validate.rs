@@ -0,0 +1,150 @@
+use super::{query_builder, SearchEngine, SearchParameters};
+use crate::db::search::parameter_lookup::SearchParamDef;
+use crate::db::search::params::RawSearchParam;
+use crate::Result;
+use std::collections::HashMap;
+
+/// Outcome of resolving a single search parameter without executing the search.
+#[derive(Debug, Clone)]
+pub struct SearchParamCheck {
+    /// Parameter name as supplied by the client (including modifiers/chains)
+    pub name: String,
+    /// Whether the server would honour the parameter
+    pub supported: bool,
+    /// Why the parameter is not supported (None when supported)
+    pub reason: Option<String>,
+}
+
+impl SearchEngine {
+    /// Resolve search parameters for a type-level search without running any search SQL.
+    ///
+    /// Each parameter is checked independently so that one invalid parameter does not mask
+    /// the status of the others. Repeats of the same code are resolved together so that
+    /// `multiple_and` restrictions are still enforced.
+    pub async fn validate_search_params(
+        &self,
+        resource_type: &str,
+        items: &[(String, String)],
+    ) -> Result<Vec<SearchParamCheck>> {
+        let mut conn = self
+            .db_pool
+            .acquire()
+            .await
+            .map_err(crate::Error::Database)?;
+
+        let mut checks = Vec::new();
+        let mut groups: Vec<(String, Vec<(String, String)>)> = Vec::new();
+        let mut group_index: HashMap<String, usize> = HashMap::new();
+
+        for (key, value) in items {
+            let single = [(key.clone(), value.clone())];
+            let parsed = match SearchParameters::from_items(&single) {
+                Ok(parsed) => parsed,
+                Err(crate::Error::Validation(msg)) => {
+                    checks.push(SearchParamCheck {
+                        name: key.clone(),
+                        supported: false,
+                        reason: Some(msg),
+                    });
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+
+            // Result parameters (_count, _sort, _include, ...) only need to parse.
+            let Some(code) = parsed.resource_params.first().map(|p| p.code.clone()) else {
+                checks.push(SearchParamCheck {
+                    name: key.clone(),
+                    supported: true,
+                    reason: None,
+                });
+                continue;
+            };
+
+            match group_index.get(&code) {
+                Some(&idx) => groups[idx].1.push((key.clone(), value.clone())),
+                None => {
+                    group_index.insert(code.clone(), groups.len());
+                    groups.push((code, vec![(key.clone(), value.clone())]));
+                }
+            }
+        }
+
+        for (code, group_items) in groups {
+            let params = SearchParameters::from_items(&group_items)?;
+            match self
+                .resolve_search_params_type(&mut conn, resource_type, &params)
+                .await
+            {
+                Ok((_, _, unknown)) => {
+                    let def = self
+                        .param_cache
+                        .get_param_with_conn(&mut conn, resource_type, &code)
+                        .await?;
+                    for p in &params.resource_params {
+                        // Mirrors the checks that put a parameter in `unknown`, in the same order.
+                        let reason = if !unknown.contains(&p.raw_name) {
+                            None
+                        } else if let Some(def) = &def {
+                            Some(unsupported_reason(p, def, params.resource_params.len()))
+                        } else {
+                            Some(format!(
+                                "Unknown search parameter '{}' for {}",
+                                p.code, resource_type
+                            ))
+                        };
+                        checks.push(SearchParamCheck {
+                            name: p.raw_name.clone(),
+                            supported: reason.is_none(),
+                            reason,
+                        });
+                    }
+                }
+                Err(crate::Error::Validation(msg)) => {
+                    for p in &params.resource_params {
+                        checks.push(SearchParamCheck {
+                            name: p.raw_name.clone(),
+                            supported: false,
+                            reason: Some(msg.clone()),
+                        });
+                    }
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(checks)
+    }
+}
+
+/// Why a known parameter that `resolve_search_params_type` left unresolved was rejected.
+///
+/// `repeats` is the number of times the parameter's code occurs in the search.
+fn unsupported_reason(p: &RawSearchParam, def: &SearchParamDef, repeats: usize) -> String {
+    if p.chain.is_some() {
+        if let Some(m) = p.modifier.as_deref() {
+            if query_builder::SearchModifier::from_str(m).is_none()
+                && !query_builder::is_valid_resource_type(m)
+            {
+                return format!(
+                    "Unsupported modifier '{}' for chained search parameter '{}'",
+                    m, p.raw_name
+                );
+            }
+        }
+        return format!(
+            "Chained search parameter '{}' could not be resolved",
+            p.raw_name
+        );
+    }
+    if repeats > 1 && !def.multiple_and {
+        return format!("Search parameter '{}' cannot be repeated (AND)", p.code);
+    }
+    if p.or_values.len() > 1 && !def.multiple_or {
+        return format!(
+            "Search parameter '{}' does not allow comma-separated values (OR)",
+            p.code
+        );
+    }
+    format!("Search parameter '{}' is not supported", p.raw_name)
+}
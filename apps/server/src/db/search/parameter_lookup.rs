@@ -85,6 +85,28 @@ pub struct SearchParamDef {
     pub chains: Vec<String>,
     pub targets: Vec<String>,
     pub components: Vec<CompositeComponentDef>,
+    /// `SearchParameter.status` (draft | active | retired | unknown)
+    pub status: Option<String>,
+    /// `SearchParameter.experimental`
+    pub experimental: bool,
+    /// Index watermark: resources written before it may lack index rows for the parameter
+    pub indexed_from: Option<chrono::DateTime<chrono::Utc>>,
+    /// Resource types reindexed since `indexed_from` was set
+    pub indexed_types: Vec<String>,
+}
+
+impl SearchParamDef {
+    /// Whether the defining SearchParameter is retired
+    pub fn is_retired(&self) -> bool {
+        self.status
+            .as_deref()
+            .is_some_and(|s| s.eq_ignore_ascii_case("retired"))
+    }
+
+    /// Whether the index covers every stored `resource_type` resource (no pending watermark)
+    pub fn is_indexed_for(&self, resource_type: &str) -> bool {
+        self.indexed_from.is_none() || self.indexed_types.iter().any(|t| t == resource_type)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -96,11 +118,50 @@ pub struct CompositeComponentDef {
     pub component_type: SearchParamType,
 }
 
+/// Common meta parameters available on every resource type, even when no SearchParameter
+/// resource defines them (e.g. when no core package is loaded).
+///
+/// Entries are `(code, type, expression)`. A stored definition with the same code takes
+/// precedence, both at indexing time and when resolving a search.
+pub(crate) const META_SEARCH_PARAMS: &[(&str, &str, &str)] = &[
+    ("_tag", "token", "meta.tag"),
+    ("_security", "token", "meta.security"),
+    ("_profile", "uri", "meta.profile"),
+];
+
+fn meta_param_def(resource_type: &str, code: &str) -> Option<SearchParamDef> {
+    let (code, param_type, expression) = META_SEARCH_PARAMS
+        .iter()
+        .find(|(meta_code, _, _)| *meta_code == code)?;
+
+    Some(SearchParamDef {
+        id: 0,
+        code: code.to_string(),
+        resource_type: resource_type.to_string(),
+        param_type: SearchParamType::try_from_str(param_type)?,
+        expression: Some(expression.to_string()),
+        url: None,
+        multiple_or: true,
+        multiple_and: true,
+        comparators: Vec::new(),
+        modifiers: Vec::new(),
+        chains: Vec::new(),
+        targets: Vec::new(),
+        components: Vec::new(),
+        status: None,
+        experimental: false,
+        indexed_from: None,
+        indexed_types: Vec::new(),
+    })
+}
+
 /// Cache for search parameter definitions
 pub struct SearchParamCache {
     db_pool: PgPool,
     // Cache: (resource_type, code) -> SearchParamDef
     cache: std::sync::RwLock<HashMap<(String, String), SearchParamDef>>,
+    // Cache: (resource_type, code) of every active reference parameter, loaded on first use
+    reference_params: std::sync::RwLock<Option<Vec<(String, String)>>>,
 }
 
 impl SearchParamCache {
@@ -108,6 +169,7 @@ impl SearchParamCache {
         Self {
             db_pool,
             cache: std::sync::RwLock::new(HashMap::new()),
+            reference_params: std::sync::RwLock::new(None),
         }
     }
 
@@ -115,6 +177,15 @@ impl SearchParamCache {
     pub fn invalidate(&self) {
         let mut cache = self.cache.write().unwrap();
         cache.clear();
+        *self.reference_params.write().unwrap() = None;
+    }
+
+    /// Drop the cached index watermark of `code` for `resource_type` once a reindex covered it.
+    pub fn mark_indexed(&self, resource_type: &str, code: &str) {
+        let mut cache = self.cache.write().unwrap();
+        if let Some(def) = cache.get_mut(&(resource_type.to_string(), code.to_string())) {
+            def.indexed_from = None;
+        }
     }
 
     pub async fn get_param_with_conn(
@@ -154,6 +225,13 @@ impl SearchParamCache {
             }
         }
 
+        if let Some(def) = meta_param_def(resource_type, code) {
+            tracing::debug!("Using built-in meta param {} for {}", code, resource_type);
+            let mut cache = self.cache.write().unwrap();
+            cache.insert(key, def.clone());
+            return Ok(Some(def));
+        }
+
         tracing::debug!("Param {}.{} not found", resource_type, code);
         Ok(None)
     }
@@ -173,6 +251,78 @@ impl SearchParamCache {
             .await
     }
 
+    /// Reference parameters usable on `resource_type`, sorted by code.
+    ///
+    /// Includes parameters inherited from `DomainResource` and `Resource`; a type-specific
+    /// definition takes precedence over an inherited one with the same code.
+    pub async fn reference_params_with_conn(
+        &self,
+        conn: &mut PgConnection,
+        resource_type: &str,
+    ) -> Result<Vec<SearchParamDef>> {
+        let mut codes: Vec<String> = self
+            .reference_param_index(conn)
+            .await?
+            .into_iter()
+            .filter(|(rt, _)| rt == resource_type || rt == "DomainResource" || rt == "Resource")
+            .map(|(_, code)| code)
+            .collect();
+        codes.sort();
+        codes.dedup();
+
+        let mut defs = Vec::with_capacity(codes.len());
+        for code in codes {
+            if let Some(def) = self
+                .get_param_with_conn(conn, resource_type, &code)
+                .await?
+                .filter(|def| def.param_type == SearchParamType::Reference)
+            {
+                defs.push(def);
+            }
+        }
+        Ok(defs)
+    }
+
+    /// Resource types that define reference parameters of their own, sorted.
+    pub async fn reference_param_types_with_conn(
+        &self,
+        conn: &mut PgConnection,
+    ) -> Result<Vec<String>> {
+        let mut types: Vec<String> = self
+            .reference_param_index(conn)
+            .await?
+            .into_iter()
+            .map(|(rt, _)| rt)
+            .filter(|rt| rt != "DomainResource" && rt != "Resource")
+            .collect();
+        types.sort();
+        types.dedup();
+        Ok(types)
+    }
+
+    async fn reference_param_index(
+        &self,
+        conn: &mut PgConnection,
+    ) -> Result<Vec<(String, String)>> {
+        if let Some(index) = self.reference_params.read().unwrap().as_ref() {
+            return Ok(index.clone());
+        }
+
+        let index: Vec<(String, String)> = sqlx::query_as(
+            r#"
+            SELECT DISTINCT resource_type, code
+            FROM search_parameters
+            WHERE type = 'reference' AND active = true
+            "#,
+        )
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(crate::Error::Database)?;
+
+        *self.reference_params.write().unwrap() = Some(index.clone());
+        Ok(index)
+    }
+
     async fn query_param_with_conn(
         &self,
         conn: &mut PgConnection,
@@ -192,11 +342,16 @@ impl SearchParamCache {
             Option<Vec<String>>,
             Option<Vec<String>>,
             Option<Vec<String>>,
+            Option<String>,
+            bool,
+            Option<chrono::DateTime<chrono::Utc>>,
+            Vec<String>,
         )> = sqlx::query_as(
             r#"
             SELECT id, code, resource_type, type, expression, url,
                    multiple_or, multiple_and,
-                   comparators, modifiers, chains, targets
+                   comparators, modifiers, chains, targets,
+                   status, experimental, indexed_from, indexed_types
             FROM search_parameters
             WHERE resource_type = $1 AND code = $2 AND active = true
             LIMIT 1
@@ -221,6 +376,10 @@ impl SearchParamCache {
             modifiers,
             chains,
             targets,
+            status,
+            experimental,
+            indexed_from,
+            indexed_types,
         )) = row
         else {
             return Ok(None);
@@ -248,6 +407,10 @@ impl SearchParamCache {
             chains: chains.unwrap_or_default(),
             targets: targets.unwrap_or_default(),
             components: Vec::new(),
+            status,
+            experimental,
+            indexed_from,
+            indexed_types,
         };
 
         if def.param_type == SearchParamType::Composite {
@@ -135,6 +135,20 @@ pub enum SummaryMode {
     False,
 }
 
+impl SummaryMode {
+    /// Parse a `_summary` parameter value
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "true" => Some(Self::True),
+            "text" => Some(Self::Text),
+            "data" => Some(Self::Data),
+            "count" => Some(Self::Count),
+            "false" => Some(Self::False),
+            _ => None,
+        }
+    }
+}
+
 /// Cursor direction for keyset pagination
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CursorDirection {
@@ -205,12 +219,6 @@ impl SearchParameters {
                     let parsed: usize = value.parse().map_err(|_| {
                         crate::Error::Validation(format!("Invalid _count value: {}", value))
                     })?;
-
-                    // Per FHIR spec 3.2.1.7.3: _count=0 SHALL be treated as _summary=count
-                    if parsed == 0 {
-                        summary = Some(SummaryMode::Count);
-                    }
-
                     count = Some(parsed);
                 }
                 "_offset" => {
@@ -304,19 +312,9 @@ impl SearchParameters {
                     )));
                 }
                 "_summary" => {
-                    summary = Some(match value.as_str() {
-                        "true" => SummaryMode::True,
-                        "text" => SummaryMode::Text,
-                        "data" => SummaryMode::Data,
-                        "count" => SummaryMode::Count,
-                        "false" => SummaryMode::False,
-                        _ => {
-                            return Err(crate::Error::Validation(format!(
-                                "Invalid _summary value: {}",
-                                value
-                            )));
-                        }
-                    });
+                    summary = Some(SummaryMode::parse(value).ok_or_else(|| {
+                        crate::Error::Validation(format!("Invalid _summary value: {}", value))
+                    })?);
                 }
                 "_elements" => {
                     elements.extend(
@@ -383,6 +381,12 @@ impl SearchParameters {
             }
         }
 
+        // Per FHIR spec 3.2.1.7.3: _count=0 SHALL be treated as _summary=count, regardless of
+        // where `_summary` appears in the query.
+        if count == Some(0) {
+            summary = Some(SummaryMode::Count);
+        }
+
         Ok(Self {
             resource_params,
             types,
@@ -421,6 +425,12 @@ impl SearchParameters {
             if param.is_empty() {
                 continue;
             }
+            if param.contains('.') {
+                return Err(crate::Error::Validation(format!(
+                    "Chained _sort parameters are not supported: {}",
+                    raw.trim()
+                )));
+            }
 
             let mut modifier: Option<String> = None;
             let mut tail_dir: Option<&str> = None;
@@ -853,6 +863,32 @@ mod tests {
         assert!(err.to_string().contains("_sort"));
     }
 
+    #[test]
+    fn sort_parses_multiple_keys_in_order() {
+        let items = vec![("_sort".to_string(), "status,-date,code:text".to_string())];
+        let params = SearchParameters::from_items(&items).unwrap();
+        let keys: Vec<(&str, bool, Option<&str>)> = params
+            .sort
+            .iter()
+            .map(|s| (s.param.as_str(), s.ascending, s.modifier.as_deref()))
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                ("status", true, None),
+                ("date", false, None),
+                ("code", true, Some("text")),
+            ]
+        );
+    }
+
+    #[test]
+    fn sort_rejects_chained_keys() {
+        let items = vec![("_sort".to_string(), "status,-date,patient.name".to_string())];
+        let err = SearchParameters::from_items(&items).unwrap_err();
+        assert!(err.to_string().contains("patient.name"));
+    }
+
     #[test]
     fn filter_parameter_preserves_commas() {
         let items = vec![(
@@ -468,6 +468,46 @@ mod tests {
         assert!(sql.contains("FROM search_string sp"));
     }
 
+    #[test]
+    fn builds_date_po_filter_as_overlap() {
+        let expr = FilterExpr::Atom(FilterAtom {
+            chain: Vec::new(),
+            kind: FilterAtomKind::DateOverlaps {
+                code: "date".to_string(),
+                value: "2015".to_string(),
+            },
+        });
+
+        let mut binds = Vec::new();
+        let sql = expr.build_sql(&mut binds, None, Some("Encounter"), "r");
+        assert!(sql.contains("FROM search_date sp"));
+        assert!(sql.contains("sp.start_date < $3::timestamptz AND sp.end_date > $2::timestamptz"));
+        assert!(
+            !sql.contains("sp.start_date >="),
+            "po must not use containment"
+        );
+        assert!(
+            !sql.contains("sp.end_date <="),
+            "po must not use containment"
+        );
+
+        let bound: Vec<&str> = binds
+            .iter()
+            .map(|b| match b {
+                BindValue::Text(s) => s.as_str(),
+                BindValue::TextArray(_) => panic!("unexpected array bind"),
+            })
+            .collect();
+        assert_eq!(
+            bound,
+            vec![
+                "date",
+                "2015-01-01T00:00:00+00:00",
+                "2016-01-01T00:00:00+00:00"
+            ]
+        );
+    }
+
     #[test]
     fn builds_has_filter_sql() {
         let spec = ReverseChainSpec {
@@ -252,7 +252,7 @@ pub struct ResolvedParam {
 }
 
 /// Decode cursor from base64url format: "timestamp,id"
-fn decode_cursor(cursor: &str) -> Option<(String, String)> {
+pub fn decode_cursor(cursor: &str) -> Option<(String, String)> {
     let decoded = URL_SAFE_NO_PAD.decode(cursor).ok()?;
     let decoded_str = String::from_utf8(decoded).ok()?;
     let parts: Vec<&str> = decoded_str.splitn(2, ',').collect();
@@ -279,6 +279,8 @@ pub struct QueryBuilder {
     resolved_params: Vec<ResolvedParam>,
     filter: Option<FilterExpr>,
     resolved_sort: Vec<ResolvedSort>,
+    /// Ordering used when the search has no `_sort`.
+    default_sort: Vec<ResolvedSort>,
     /// Request base URL (scheme://host[/path]) used to resolve local absolute references.
     base_url: Option<String>,
 }
@@ -300,6 +302,44 @@ pub struct ResolvedSort {
     pub ascending: bool,
 }
 
+/// Implicit ordering applied when a search has no `_sort`: newest first.
+pub const DEFAULT_SORT: &str = "-_lastUpdated";
+
+/// Parse a default sort (`_sort` syntax) into resolved sort keys.
+///
+/// Only `_id` and `_lastUpdated` are accepted: the default applies to every resource type,
+/// and pagination cursors are built from those two columns.
+pub fn parse_default_sort(value: &str) -> Result<Vec<ResolvedSort>, String> {
+    let mut out: Vec<ResolvedSort> = Vec::new();
+    for raw in value.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        let (name, ascending) = match raw.strip_prefix('-') {
+            Some(rest) => (rest, false),
+            None => (raw, true),
+        };
+        let key = match name {
+            "_id" => ResolvedSortKey::Id,
+            "_lastUpdated" => ResolvedSortKey::LastUpdated,
+            other => {
+                return Err(format!(
+                    "unsupported default sort key '{}' (expected _id or _lastUpdated)",
+                    other
+                ))
+            }
+        };
+        if out
+            .iter()
+            .any(|s| std::mem::discriminant(&s.key) == std::mem::discriminant(&key))
+        {
+            return Err(format!("duplicate default sort key '{}'", name));
+        }
+        out.push(ResolvedSort { key, ascending });
+    }
+    if out.is_empty() {
+        return Err("default sort must not be empty".to_string());
+    }
+    Ok(out)
+}
+
 /// Compartment filter.
 #[derive(Debug, Clone)]
 pub struct CompartmentFilter {
@@ -329,6 +369,7 @@ impl QueryBuilder {
             resolved_params,
             filter: None,
             resolved_sort: Vec::new(),
+            default_sort: parse_default_sort(DEFAULT_SORT).expect("valid built-in default sort"),
             base_url: None,
         }
     }
@@ -352,6 +393,7 @@ impl QueryBuilder {
             resolved_params,
             filter: None,
             resolved_sort: Vec::new(),
+            default_sort: parse_default_sort(DEFAULT_SORT).expect("valid built-in default sort"),
             base_url: None,
         }
     }
@@ -366,6 +408,11 @@ impl QueryBuilder {
         self
     }
 
+    pub fn with_default_sort(mut self, default_sort: Vec<ResolvedSort>) -> Self {
+        self.default_sort = default_sort;
+        self
+    }
+
     pub fn with_base_url(mut self, base_url: Option<&str>) -> Self {
         self.base_url = base_url.map(|s| s.trim_end_matches('/').to_string());
         self
@@ -387,6 +434,7 @@ impl QueryBuilder {
 
         self.push_resource_type_filters(&mut sql, &mut bind_params);
         self.push_compartment_filter(&mut sql, &mut bind_params);
+        self.push_security_label_filter(&mut sql, &mut bind_params);
 
         for resolved in &self.resolved_params {
             let clause = claueses::build_param_clause(
@@ -418,14 +466,19 @@ impl QueryBuilder {
                 if let Some((timestamp, id)) = decode_cursor(cursor) {
                     let ts_idx = push_text(&mut bind_params, timestamp);
                     let id_idx = push_text(&mut bind_params, id);
-                    let cmp = match self.params.cursor_direction {
-                        CursorDirection::Prev => ">",
-                        _ => "<",
-                    };
-                    sql.push_str(&format!(
-                        " AND (r.last_updated, r.id) {} (${}::timestamptz, ${})",
-                        cmp, ts_idx, id_idx
-                    ));
+                    if self.resolved_sort.is_empty() {
+                        sql.push_str(" AND ");
+                        sql.push_str(&self.default_sort_cursor_clause(ts_idx, id_idx));
+                    } else {
+                        let cmp = match self.params.cursor_direction {
+                            CursorDirection::Prev => ">",
+                            _ => "<",
+                        };
+                        sql.push_str(&format!(
+                            " AND (r.last_updated, r.id) {} (${}::timestamptz, ${})",
+                            cmp, ts_idx, id_idx
+                        ));
+                    }
                 }
             }
         }
@@ -457,6 +510,7 @@ impl QueryBuilder {
 
         self.push_resource_type_filters(&mut sql, &mut bind_params);
         self.push_compartment_filter(&mut sql, &mut bind_params);
+        self.push_security_label_filter(&mut sql, &mut bind_params);
 
         for resolved in &self.resolved_params {
             let clause = claueses::build_param_clause(
@@ -498,6 +552,16 @@ impl QueryBuilder {
         }
     }
 
+    /// Exclude resources carrying a `meta.security` label outside the current request's scope.
+    fn push_security_label_filter(&self, sql: &mut String, bind_params: &mut Vec<BindValue>) {
+        let Some(tokens) = crate::security_labels::current_scope_tokens() else {
+            return;
+        };
+        let idx = push_text_array(bind_params, tokens);
+        sql.push_str(" AND ");
+        sql.push_str(&crate::security_labels::visible_sql("r.resource", idx));
+    }
+
     fn push_compartment_filter(&self, sql: &mut String, bind_params: &mut Vec<BindValue>) {
         let Some(comp) = &self.compartment else {
             return;
@@ -550,11 +614,74 @@ impl QueryBuilder {
         }
     }
 
+    /// Keys of the implicit ordering, with `id` appended as the final tie-breaker.
+    fn default_sort_keys(&self) -> Vec<ResolvedSort> {
+        let mut keys = self.default_sort.clone();
+        if !keys.iter().any(|s| matches!(s.key, ResolvedSortKey::Id)) {
+            keys.push(ResolvedSort {
+                key: ResolvedSortKey::Id,
+                ascending: false,
+            });
+        }
+        keys
+    }
+
+    /// Keyset condition selecting the rows after the cursor under the implicit ordering.
+    ///
+    /// Uniform directions compare as a row value; mixed directions need the expanded
+    /// `(a > x) OR (a = x AND b < y)` form.
+    fn default_sort_cursor_clause(&self, ts_idx: usize, id_idx: usize) -> String {
+        let reverse = self.params.cursor_direction == CursorDirection::Prev;
+        let keys: Vec<(&str, String, &str)> = self
+            .default_sort_keys()
+            .iter()
+            .map(|s| {
+                let (column, value) = match s.key {
+                    ResolvedSortKey::LastUpdated => {
+                        ("r.last_updated", format!("${}::timestamptz", ts_idx))
+                    }
+                    _ => ("r.id", format!("${}", id_idx)),
+                };
+                let cmp = if s.ascending ^ reverse { ">" } else { "<" };
+                (column, value, cmp)
+            })
+            .collect();
+
+        if keys.iter().all(|(_, _, cmp)| *cmp == keys[0].2) {
+            let columns: Vec<&str> = keys.iter().map(|(c, _, _)| *c).collect();
+            let values: Vec<&str> = keys.iter().map(|(_, v, _)| v.as_str()).collect();
+            return format!(
+                "({}) {} ({})",
+                columns.join(", "),
+                keys[0].2,
+                values.join(", ")
+            );
+        }
+
+        let mut alternatives = Vec::new();
+        for (i, (column, value, cmp)) in keys.iter().enumerate() {
+            let mut terms: Vec<String> = keys[..i]
+                .iter()
+                .map(|(c, v, _)| format!("{} = {}", c, v))
+                .collect();
+            terms.push(format!("{} {} {}", column, cmp, value));
+            alternatives.push(format!("({})", terms.join(" AND ")));
+        }
+        format!("({})", alternatives.join(" OR "))
+    }
+
     fn push_order_by(&self, sql: &mut String, bind_params: &mut Vec<BindValue>) {
         let mut order_by = Vec::new();
         let reverse_paging = self.params.cursor_direction.is_reverse();
 
-        for s in &self.resolved_sort {
+        // Without `_sort`, the configured default applies (already tie-broken on id).
+        let sort = if self.resolved_sort.is_empty() {
+            self.default_sort_keys()
+        } else {
+            self.resolved_sort.clone()
+        };
+
+        for s in &sort {
             let dir = if s.ascending ^ reverse_paging {
                 "ASC"
             } else {
@@ -581,14 +708,8 @@ impl QueryBuilder {
             }
         }
 
-        if order_by.is_empty() {
-            let dir = if reverse_paging { "ASC" } else { "DESC" };
-            sql.push_str(&format!(" ORDER BY r.last_updated {dir}, r.id {dir}"));
-            return;
-        }
-
         // Ensure deterministic ordering for pagination.
-        if !order_by.iter().any(|o| o.contains("r.id")) {
+        if !sort.iter().any(|s| matches!(s.key, ResolvedSortKey::Id)) {
             let dir = if reverse_paging { "ASC" } else { "DESC" };
             order_by.push(format!("r.id {dir}"));
         }
@@ -749,6 +870,132 @@ mod tests {
             .0
     }
 
+    #[test]
+    fn count_zero_skips_main_query_regardless_of_summary_position() {
+        for items in [
+            vec![("_count", "0")],
+            vec![("_count", "0"), ("_summary", "true")],
+            vec![("_summary", "data"), ("_count", "0")],
+        ] {
+            let items: Vec<(String, String)> = items
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect();
+            let params = SearchParameters::from_items(&items).unwrap();
+            assert!(should_skip_main_query(&params), "{:?}", items);
+            assert!(params.should_calculate_total(), "{:?}", items);
+        }
+
+        let params =
+            SearchParameters::from_items(&[("_count".to_string(), "1".to_string())]).unwrap();
+        assert!(!should_skip_main_query(&params));
+    }
+
+    #[test]
+    fn order_by_preserves_multi_key_sort_order_and_direction() {
+        let params = empty_params();
+        let sort = vec![
+            ResolvedSort {
+                key: ResolvedSortKey::Param {
+                    code: "status".to_string(),
+                    param_type: SearchParamType::Token,
+                    modifier: None,
+                },
+                ascending: true,
+            },
+            ResolvedSort {
+                key: ResolvedSortKey::Param {
+                    code: "date".to_string(),
+                    param_type: SearchParamType::Date,
+                    modifier: None,
+                },
+                ascending: false,
+            },
+            ResolvedSort {
+                key: ResolvedSortKey::Id,
+                ascending: true,
+            },
+        ];
+        let (sql, _) = QueryBuilder::new(Some("Observation"), &params)
+            .with_resolved_sort(sort)
+            .build_sql();
+
+        let order_by = &sql[sql.find(" ORDER BY ").expect("ORDER BY clause")..];
+        let token = order_by.find("FROM search_token st").unwrap();
+        let date = order_by.find("FROM search_date sd").unwrap();
+        let id = order_by.find("r.id ASC").unwrap();
+        assert!(token < date && date < id, "{order_by}");
+
+        let token_dir = &order_by[token..date];
+        assert!(token_dir.contains(") ASC NULLS LAST"), "{order_by}");
+        let date_dir = &order_by[date..id];
+        assert!(date_dir.contains(") DESC NULLS LAST"), "{order_by}");
+        // The explicit _id key already makes ordering deterministic.
+        assert!(!order_by.contains("r.id DESC"), "{order_by}");
+    }
+
+    #[test]
+    fn order_by_uses_configured_default_sort_with_id_tie_breaker() {
+        let params = empty_params();
+        let (sql, _) = QueryBuilder::new(Some("Patient"), &params).build_sql();
+        assert!(
+            sql.contains(" ORDER BY r.last_updated DESC, r.id DESC LIMIT"),
+            "{sql}"
+        );
+
+        let (sql, _) = QueryBuilder::new(Some("Patient"), &params)
+            .with_default_sort(parse_default_sort("_id").unwrap())
+            .build_sql();
+        assert!(sql.contains(" ORDER BY r.id ASC LIMIT"), "{sql}");
+
+        let (sql, _) = QueryBuilder::new(Some("Patient"), &params)
+            .with_default_sort(parse_default_sort("_lastUpdated").unwrap())
+            .build_sql();
+        assert!(
+            sql.contains(" ORDER BY r.last_updated ASC, r.id DESC LIMIT"),
+            "{sql}"
+        );
+    }
+
+    #[test]
+    fn cursor_follows_configured_default_sort() {
+        let cursor = encode_cursor("2024-01-01T00:00:00Z", "abc");
+        let params =
+            SearchParameters::from_items(&[("_cursor".to_string(), cursor.clone())]).unwrap();
+
+        let (sql, _) = QueryBuilder::new(Some("Patient"), &params).build_sql();
+        assert!(
+            sql.contains("(r.last_updated, r.id) < ($2::timestamptz, $3)"),
+            "{sql}"
+        );
+
+        let (sql, _) = QueryBuilder::new(Some("Patient"), &params)
+            .with_default_sort(parse_default_sort("_id").unwrap())
+            .build_sql();
+        assert!(sql.contains("(r.id) > ($3)"), "{sql}");
+
+        let (sql, _) = QueryBuilder::new(Some("Patient"), &params)
+            .with_default_sort(parse_default_sort("_lastUpdated").unwrap())
+            .build_sql();
+        assert!(
+            sql.contains(
+                "((r.last_updated > $2::timestamptz) OR (r.last_updated = $2::timestamptz AND r.id < $3))"
+            ),
+            "{sql}"
+        );
+    }
+
+    #[test]
+    fn parse_default_sort_accepts_only_id_and_last_updated() {
+        let sort = parse_default_sort("-_lastUpdated, _id").unwrap();
+        assert!(matches!(sort[0].key, ResolvedSortKey::LastUpdated) && !sort[0].ascending);
+        assert!(matches!(sort[1].key, ResolvedSortKey::Id) && sort[1].ascending);
+
+        assert!(parse_default_sort("name").is_err());
+        assert!(parse_default_sort("_id,-_id").is_err());
+        assert!(parse_default_sort(" ").is_err());
+    }
+
     #[test]
     fn parse_reference_query_supports_relative_absolute_canonical() {
         let base = Some("http://example.org/fhir");
@@ -1204,6 +1451,55 @@ mod tests {
         assert!(!sql.contains("r.last_updated <="));
     }
 
+    #[test]
+    fn last_updated_ge_and_le_form_closed_open_interval() {
+        let last_updated = |prefix: SearchPrefix, raw: &str| ResolvedParam {
+            raw_name: "_lastUpdated".to_string(),
+            code: "_lastUpdated".to_string(),
+            param_type: SearchParamType::Special,
+            modifier: None,
+            chain: None,
+            values: vec![SearchValue {
+                raw: raw.to_string(),
+                prefix: Some(prefix),
+            }],
+            composite: None,
+            reverse_chain: None,
+            chain_metadata: None,
+        };
+
+        // _lastUpdated=ge2020-01-01&_lastUpdated=le2020-12-31
+        let params = empty_params();
+        let (sql, binds) = QueryBuilder::with_resolved_params(
+            Some("Observation"),
+            &params,
+            vec![
+                last_updated(SearchPrefix::Ge, "2020-01-01"),
+                last_updated(SearchPrefix::Le, "2020-12-31"),
+            ],
+        )
+        .build_sql();
+
+        // `le` on a day covers the whole day, so the upper bound is the next midnight.
+        let bind_index = |expected: &str| {
+            binds
+                .iter()
+                .position(|b| matches!(b, BindValue::Text(v) if v == expected))
+                .map(|i| i + 1)
+                .unwrap_or_else(|| panic!("missing bind {expected}: {binds:?}"))
+        };
+        let lower = bind_index("2020-01-01T00:00:00+00:00");
+        let upper = bind_index("2021-01-01T00:00:00+00:00");
+
+        assert!(
+            sql.contains(&format!(
+                " AND r.last_updated >= ${lower}::timestamptz AND r.last_updated < ${upper}::timestamptz"
+            )),
+            "{sql}"
+        );
+        assert!(!sql.contains("r.last_updated <="), "{sql}");
+    }
+
     #[test]
     fn token_default_uses_code_ci_with_fallback() {
         let sql = build_sql(
@@ -7,10 +7,90 @@ use sqlx::{PgPool, Row};
 
 use crate::{
     db::traits::ResourceStore,
-    models::{HistoryEntry, HistoryMethod, HistoryResult, Resource},
+    models::{
+        HistoryCursor, HistoryEntry, HistoryMethod, HistoryResult, Resource, DEFAULT_HISTORY_COUNT,
+    },
+    security_labels::{self, visible_sql},
+    services::IndexingService,
     Error, Result,
 };
 
+type PgQuery<'q> = sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments>;
+
+/// Rows to fetch for a history page: one beyond `_count` to detect a next page.
+fn history_fetch_limit(count: Option<i32>) -> i64 {
+    count.unwrap_or(DEFAULT_HISTORY_COUNT).max(0) as i64 + 1
+}
+
+/// Keyset condition resuming a history listing after the cursor bound at `$first..=$first+3`.
+///
+/// Matches the `(last_updated, resource_type, id, version_id)` ordering of history queries.
+fn history_cursor_clause(first: usize, sort_ascending: bool) -> String {
+    let cmp = if sort_ascending { ">" } else { "<" };
+    format!(
+        "(${a}::TIMESTAMPTZ IS NULL OR (last_updated, resource_type, id, version_id) {cmp} (${a}, ${b}::TEXT, ${c}::TEXT, ${d}::INTEGER))",
+        a = first,
+        b = first + 1,
+        c = first + 2,
+        d = first + 3,
+    )
+}
+
+fn bind_history_cursor<'q>(query: PgQuery<'q>, cursor: Option<&'q HistoryCursor>) -> PgQuery<'q> {
+    query
+        .bind(cursor.map(|c| c.last_updated))
+        .bind(cursor.map(|c| c.resource_type.as_str()))
+        .bind(cursor.map(|c| c.id.as_str()))
+        .bind(cursor.map(|c| c.version_id))
+}
+
+/// Reject a write to `resource_type/id` when its current version is hidden by the current
+/// security-label scope; hidden resources are reported as not found, as on read.
+pub(crate) async fn ensure_current_visible<'e, E>(
+    executor: E,
+    resource_type: &str,
+    id: &str,
+) -> Result<()>
+where
+    E: sqlx::PgExecutor<'e>,
+{
+    let Some(tokens) = security_labels::current_scope_tokens() else {
+        return Ok(());
+    };
+    let sql = format!(
+        "SELECT NOT {} AS hidden
+         FROM resources
+         WHERE resource_type = $1 AND id = $2 AND is_current = true",
+        visible_sql("resource", 3)
+    );
+    let hidden = sqlx::query_scalar::<_, bool>(&sql)
+        .bind(resource_type)
+        .bind(id)
+        .bind(tokens)
+        .fetch_optional(executor)
+        .await
+        .map_err(Error::Database)?
+        .unwrap_or(false);
+    if hidden {
+        return Err(Error::ResourceNotFound {
+            resource_type: resource_type.to_string(),
+            id: id.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// What [`PostgresResourceStore::expunge`] removes.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExpungeOptions {
+    /// Resources whose current version is a delete, with all of their versions.
+    pub deleted_resources: bool,
+    /// Every non-current version.
+    pub previous_versions: bool,
+    /// At most this many versions in total.
+    pub limit: Option<u64>,
+}
+
 /// PostgreSQL-backed ResourceStore implementation
 #[derive(Clone)]
 pub struct PostgresResourceStore {
@@ -22,6 +102,9 @@ impl PostgresResourceStore {
         Self { pool }
     }
 
+    /// Type-level history, ordered by `(last_updated, id, version_id)`.
+    ///
+    /// Returns up to `_count + 1` rows; see [`HistoryResult::paginate`].
     pub async fn history_type_resources(
         &self,
         resource_type: &str,
@@ -29,25 +112,31 @@ impl PostgresResourceStore {
         since: Option<chrono::DateTime<chrono::Utc>>,
         at: Option<chrono::DateTime<chrono::Utc>>,
         sort_ascending: bool,
+        cursor: Option<&HistoryCursor>,
     ) -> Result<Vec<Resource>> {
+        let limit = history_fetch_limit(count);
+        let order = if sort_ascending { "ASC" } else { "DESC" };
+        let after_cursor = history_cursor_clause(4, sort_ascending);
+        let visible = visible_sql("resource", 8);
+        let scope = security_labels::current_scope_tokens();
+
         // _at: for each resource of this type, return the version that was current at the instant.
         if let Some(at_instant) = at {
-            let limit = count.unwrap_or(100);
-            let order = if sort_ascending { "ASC" } else { "DESC" };
-
             let sql = "SELECT DISTINCT ON (id) id, resource_type, version_id, resource, last_updated, deleted
                  FROM resources
                  WHERE resource_type = $1 AND last_updated <= $2
                  ORDER BY id, version_id DESC".to_string();
-            // Wrap in an outer query for ordering and LIMIT
+            // Wrap in an outer query for paging, ordering and LIMIT
             let sql = format!(
-                "SELECT * FROM ({sql}) sub ORDER BY last_updated {order}, id ASC LIMIT $3"
+                "SELECT * FROM ({sql}) sub WHERE {after_cursor} AND {visible} ORDER BY last_updated {order}, id {order} LIMIT $3"
             );
 
-            let rows = sqlx::query(&sql)
+            let query = sqlx::query(&sql)
                 .bind(resource_type)
                 .bind(at_instant)
-                .bind(limit as i64)
+                .bind(limit);
+            let rows = bind_history_cursor(query, cursor)
+                .bind(scope)
                 .fetch_all(&self.pool)
                 .await
                 .map_err(Error::Database)?;
@@ -65,23 +154,24 @@ impl PostgresResourceStore {
                 .collect());
         }
 
-        let limit = count.unwrap_or(100);
-        let order = if sort_ascending { "ASC" } else { "DESC" };
-
         // Note: `order` is injected from a boolean and is not user-controlled.
         let sql = format!(
             "SELECT id, resource_type, version_id, resource, last_updated, deleted
              FROM resources
              WHERE resource_type = $1
                AND ($2::TIMESTAMPTZ IS NULL OR last_updated >= $2)
-             ORDER BY last_updated {order}, id ASC, version_id {order}
+               AND {after_cursor}
+               AND {visible}
+             ORDER BY last_updated {order}, id {order}, version_id {order}
              LIMIT $3"
         );
 
-        let rows = sqlx::query(&sql)
+        let query = sqlx::query(&sql)
             .bind(resource_type)
             .bind(since)
-            .bind(limit as i64)
+            .bind(limit);
+        let rows = bind_history_cursor(query, cursor)
+            .bind(scope)
             .fetch_all(&self.pool)
             .await
             .map_err(Error::Database)?;
@@ -99,29 +189,38 @@ impl PostgresResourceStore {
             .collect())
     }
 
+    /// System-level history, ordered by `(last_updated, resource_type, id, version_id)`.
+    ///
+    /// Returns up to `_count + 1` rows; see [`HistoryResult::paginate`].
     pub async fn history_system_resources(
         &self,
+        types: &[String],
         count: Option<i32>,
         since: Option<chrono::DateTime<chrono::Utc>>,
         at: Option<chrono::DateTime<chrono::Utc>>,
         sort_ascending: bool,
+        cursor: Option<&HistoryCursor>,
     ) -> Result<Vec<Resource>> {
+        let limit = history_fetch_limit(count);
+        let order = if sort_ascending { "ASC" } else { "DESC" };
+        let after_cursor = history_cursor_clause(4, sort_ascending);
+        let visible = visible_sql("resource", 8);
+        let scope = security_labels::current_scope_tokens();
+
         // _at: for each resource across all types, return the version that was current at the instant.
         if let Some(at_instant) = at {
-            let limit = count.unwrap_or(100);
-            let order = if sort_ascending { "ASC" } else { "DESC" };
-
             let sql = "SELECT DISTINCT ON (resource_type, id) id, resource_type, version_id, resource, last_updated, deleted
                  FROM resources
                  WHERE last_updated <= $1
+                   AND (cardinality($3::TEXT[]) = 0 OR resource_type = ANY($3))
                  ORDER BY resource_type, id, version_id DESC".to_string();
             let sql = format!(
-                "SELECT * FROM ({sql}) sub ORDER BY last_updated {order}, resource_type ASC, id ASC LIMIT $2"
+                "SELECT * FROM ({sql}) sub WHERE {after_cursor} AND {visible} ORDER BY last_updated {order}, resource_type {order}, id {order} LIMIT $2"
             );
 
-            let rows = sqlx::query(&sql)
-                .bind(at_instant)
-                .bind(limit as i64)
+            let query = sqlx::query(&sql).bind(at_instant).bind(limit).bind(types);
+            let rows = bind_history_cursor(query, cursor)
+                .bind(scope)
                 .fetch_all(&self.pool)
                 .await
                 .map_err(Error::Database)?;
@@ -139,21 +238,21 @@ impl PostgresResourceStore {
                 .collect());
         }
 
-        let limit = count.unwrap_or(100);
-        let order = if sort_ascending { "ASC" } else { "DESC" };
-
         // Note: `order` is injected from a boolean and is not user-controlled.
         let sql = format!(
             "SELECT id, resource_type, version_id, resource, last_updated, deleted
              FROM resources
              WHERE ($1::TIMESTAMPTZ IS NULL OR last_updated >= $1)
-             ORDER BY last_updated {order}, resource_type ASC, id ASC, version_id {order}
+               AND (cardinality($3::TEXT[]) = 0 OR resource_type = ANY($3))
+               AND {after_cursor}
+               AND {visible}
+             ORDER BY last_updated {order}, resource_type {order}, id {order}, version_id {order}
              LIMIT $2"
         );
 
-        let rows = sqlx::query(&sql)
-            .bind(since)
-            .bind(limit as i64)
+        let query = sqlx::query(&sql).bind(since).bind(limit).bind(types);
+        let rows = bind_history_cursor(query, cursor)
+            .bind(scope)
             .fetch_all(&self.pool)
             .await
             .map_err(Error::Database)?;
@@ -368,6 +467,53 @@ impl PostgresResourceStore {
         Ok(rows.into_iter().map(|r| r.get("resource_type")).collect())
     }
 
+    /// Load a page of current, non-deleted resources of one type for bulk export.
+    ///
+    /// Pages are keyed by `id` (pass the last id of the previous page as `after_id`);
+    /// `since` restricts the page to resources updated at or after that instant.
+    pub async fn list_resources_for_export(
+        &self,
+        resource_type: &str,
+        since: Option<chrono::DateTime<Utc>>,
+        after_id: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<Resource>> {
+        let sql = format!(
+            "SELECT id, resource_type, version_id, resource, last_updated, deleted
+             FROM resources
+             WHERE resource_type = $1
+               AND is_current = true
+               AND deleted = false
+               AND ($2::timestamptz IS NULL OR last_updated >= $2)
+               AND ($3::varchar IS NULL OR id > $3)
+               AND {}
+             ORDER BY id
+             LIMIT $4",
+            visible_sql("resource", 5)
+        );
+        let rows = sqlx::query(&sql)
+            .bind(resource_type)
+            .bind(since)
+            .bind(after_id)
+            .bind(limit)
+            .bind(security_labels::current_scope_tokens())
+            .fetch_all(&self.pool)
+            .await
+            .map_err(Error::Database)?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| Resource {
+                id: row.get("id"),
+                resource_type: row.get("resource_type"),
+                version_id: row.get("version_id"),
+                resource: row.get("resource"),
+                last_updated: row.get("last_updated"),
+                deleted: row.get("deleted"),
+            })
+            .collect())
+    }
+
     /// Load multiple resources in a single query for batch processing
     ///
     /// This is used by background workers to efficiently load resources
@@ -381,20 +527,24 @@ impl PostgresResourceStore {
             return Ok(Vec::new());
         }
 
-        let rows = sqlx::query(
+        let sql = format!(
             "SELECT id, resource_type, version_id, resource, last_updated, deleted
              FROM resources
              WHERE resource_type = $1
                AND id = ANY($2)
                AND is_current = true
                AND deleted = false
+               AND {}
              ORDER BY id",
-        )
-        .bind(resource_type)
-        .bind(resource_ids)
-        .fetch_all(&self.pool)
-        .await
-        .map_err(Error::Database)?;
+            visible_sql("resource", 3)
+        );
+        let rows = sqlx::query(&sql)
+            .bind(resource_type)
+            .bind(resource_ids)
+            .bind(security_labels::current_scope_tokens())
+            .fetch_all(&self.pool)
+            .await
+            .map_err(Error::Database)?;
 
         let resources: Vec<Resource> = rows
             .into_iter()
@@ -620,6 +770,294 @@ impl PostgresResourceStore {
         tx.commit().await.map_err(crate::Error::Database)?;
         Ok(())
     }
+
+    /// Permanently remove old versions and/or deleted resources (`$expunge`).
+    ///
+    /// `id` narrows the scope to a single resource; otherwise every resource of the type is
+    /// considered. A current version is only removed when it is a delete marker and
+    /// `deleted_resources` is set. Search index rows go with their cascading foreign keys.
+    ///
+    /// With a `limit`, deleted resources are removed whole (oldest first) while all their
+    /// versions fit, then the oldest previous versions fill the rest.
+    ///
+    /// Returns the number of rows removed from the `resources` table.
+    pub async fn expunge(
+        &self,
+        resource_type: &str,
+        id: Option<&str>,
+        options: ExpungeOptions,
+    ) -> Result<u64> {
+        let mut tx = self.pool.begin().await.map_err(Error::Database)?;
+
+        if let Some(id) = id {
+            let row = sqlx::query(
+                "SELECT deleted
+                 FROM resources
+                 WHERE resource_type = $1 AND id = $2 AND is_current = true
+                 FOR UPDATE",
+            )
+            .bind(resource_type)
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(Error::Database)?;
+
+            let Some(row) = row else {
+                return Err(Error::ResourceNotFound {
+                    resource_type: resource_type.to_string(),
+                    id: id.to_string(),
+                });
+            };
+
+            let deleted: bool = row.get::<Option<bool>, _>("deleted").unwrap_or(false);
+            if options.deleted_resources && !options.previous_versions && !deleted {
+                return Err(Error::Validation(format!(
+                    "Cannot expunge the current version of {}/{}: resource is not deleted",
+                    resource_type, id
+                )));
+            }
+        }
+
+        let mut removed: u64 = 0;
+
+        if options.deleted_resources {
+            let candidates: Vec<(String, i64)> = sqlx::query_as(
+                "SELECT r.id,
+                        (SELECT COUNT(*) FROM resources v
+                         WHERE v.resource_type = r.resource_type AND v.id = r.id)
+                 FROM resources r
+                 WHERE r.resource_type = $1
+                   AND ($2::varchar IS NULL OR r.id = $2)
+                   AND r.is_current = true
+                   AND r.deleted = true
+                 ORDER BY r.last_updated, r.id",
+            )
+            .bind(resource_type)
+            .bind(id)
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(Error::Database)?;
+
+            let mut ids = Vec::new();
+            let mut budget = options.limit;
+            for (candidate, versions) in candidates {
+                let versions = versions as u64;
+                if let Some(left) = budget.as_mut() {
+                    if versions > *left {
+                        break;
+                    }
+                    *left -= versions;
+                }
+                ids.push(candidate);
+            }
+
+            if !ids.is_empty() {
+                removed += sqlx::query(
+                    "DELETE FROM resources
+                     WHERE resource_type = $1 AND id = ANY($2)",
+                )
+                .bind(resource_type)
+                .bind(&ids)
+                .execute(&mut *tx)
+                .await
+                .map_err(Error::Database)?
+                .rows_affected();
+
+                sqlx::query(
+                    "DELETE FROM resource_versions
+                     WHERE resource_type = $1 AND id = ANY($2)",
+                )
+                .bind(resource_type)
+                .bind(&ids)
+                .execute(&mut *tx)
+                .await
+                .map_err(Error::Database)?;
+
+                // Membership edges are keyed by collection rather than version (no FK cascade).
+                sqlx::query(
+                    "DELETE FROM search_membership_in
+                     WHERE collection_type = $1 AND collection_id = ANY($2)",
+                )
+                .bind(resource_type)
+                .bind(&ids)
+                .execute(&mut *tx)
+                .await
+                .map_err(Error::Database)?;
+
+                if resource_type == "List" {
+                    sqlx::query("DELETE FROM search_membership_list WHERE list_id = ANY($1)")
+                        .bind(&ids)
+                        .execute(&mut *tx)
+                        .await
+                        .map_err(Error::Database)?;
+                }
+            }
+        }
+
+        let remaining = options.limit.map(|limit| limit.saturating_sub(removed));
+        if options.previous_versions && remaining != Some(0) {
+            removed += sqlx::query(
+                "DELETE FROM resources
+                 WHERE (resource_type, id, version_id) IN (
+                     SELECT resource_type, id, version_id
+                     FROM resources
+                     WHERE resource_type = $1
+                       AND ($2::varchar IS NULL OR id = $2)
+                       AND is_current = false
+                     ORDER BY last_updated, id, version_id
+                     LIMIT $3
+                 )",
+            )
+            .bind(resource_type)
+            .bind(id)
+            .bind(remaining.map(|n| n as i64))
+            .execute(&mut *tx)
+            .await
+            .map_err(Error::Database)?
+            .rows_affected();
+        }
+
+        tx.commit().await.map_err(Error::Database)?;
+        Ok(removed)
+    }
+
+    /// Next free id for `resource_type` under the sequential id strategy.
+    ///
+    /// Counter values already taken by another resource (e.g. a client id created via PUT) are
+    /// skipped.
+    pub async fn next_sequential_id(&self, resource_type: &str) -> Result<String> {
+        loop {
+            let value: i64 = sqlx::query_scalar(
+                "INSERT INTO resource_id_sequences (resource_type, last_value)
+                 VALUES ($1, 1)
+                 ON CONFLICT (resource_type)
+                 DO UPDATE SET last_value = resource_id_sequences.last_value + 1
+                 RETURNING last_value",
+            )
+            .bind(resource_type)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(Error::Database)?;
+
+            let id = value.to_string();
+            if !self.resource_id_in_use(resource_type, &id).await? {
+                return Ok(id);
+            }
+        }
+    }
+
+    /// Whether any version (including a delete) exists for `resource_type/id`.
+    pub async fn resource_id_in_use(&self, resource_type: &str, id: &str) -> Result<bool> {
+        sqlx::query_scalar(
+            "SELECT EXISTS(
+                 SELECT 1 FROM resource_versions WHERE resource_type = $1 AND id = $2
+             )",
+        )
+        .bind(resource_type)
+        .bind(id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(Error::Database)
+    }
+
+    /// Rewrite `meta` of the current version in place (`$meta-add` / `$meta-delete`).
+    ///
+    /// Meta changes are not changes to the resource, so neither `versionId` nor `lastUpdated`
+    /// move; only the stored JSON and the extracted `meta_tags` / `meta_source` columns change.
+    /// The version is re-indexed in the same transaction, so `_tag`, `_security` and `_profile`
+    /// searches see the change as soon as it commits. Returns the updated current version.
+    pub async fn update_current_meta<F>(
+        &self,
+        resource_type: &str,
+        id: &str,
+        indexing: &IndexingService,
+        apply: F,
+    ) -> Result<Resource>
+    where
+        F: FnOnce(&mut serde_json::Map<String, JsonValue>),
+    {
+        let mut tx = self.pool.begin().await.map_err(Error::Database)?;
+
+        let row = sqlx::query(
+            "SELECT version_id, resource, last_updated, deleted
+             FROM resources
+             WHERE resource_type = $1 AND id = $2 AND is_current = true
+             FOR UPDATE",
+        )
+        .bind(resource_type)
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(Error::Database)?;
+
+        let Some(row) = row else {
+            return Err(Error::ResourceNotFound {
+                resource_type: resource_type.to_string(),
+                id: id.to_string(),
+            });
+        };
+
+        let version_id: i32 = row.get("version_id");
+        if row.get::<Option<bool>, _>("deleted").unwrap_or(false) {
+            return Err(Error::ResourceDeleted {
+                resource_type: resource_type.to_string(),
+                id: id.to_string(),
+                version_id: Some(version_id),
+            });
+        }
+
+        let mut resource: JsonValue = row.get("resource");
+        if !security_labels::is_visible(&resource) {
+            return Err(Error::ResourceNotFound {
+                resource_type: resource_type.to_string(),
+                id: id.to_string(),
+            });
+        }
+        let obj = resource.as_object_mut().ok_or_else(|| {
+            Error::Internal(format!(
+                "Stored resource {}/{} is not a JSON object",
+                resource_type, id
+            ))
+        })?;
+        let meta = obj
+            .entry("meta".to_string())
+            .or_insert_with(|| JsonValue::Object(serde_json::Map::new()));
+        if !meta.is_object() {
+            *meta = JsonValue::Object(serde_json::Map::new());
+        }
+        apply(meta.as_object_mut().expect("meta is an object"));
+
+        sqlx::query(
+            "UPDATE resources
+             SET resource = $4, meta_source = $5, meta_tags = $6
+             WHERE resource_type = $1 AND id = $2 AND version_id = $3",
+        )
+        .bind(resource_type)
+        .bind(id)
+        .bind(version_id)
+        .bind(&resource)
+        .bind(Self::extract_meta_source(&resource))
+        .bind(Self::extract_meta_tags(&resource))
+        .execute(&mut *tx)
+        .await
+        .map_err(Error::Database)?;
+
+        let updated = Resource {
+            id: id.to_string(),
+            resource_type: resource_type.to_string(),
+            version_id,
+            resource,
+            last_updated: row.get("last_updated"),
+            deleted: false,
+        };
+        indexing
+            .index_resource_in_transaction(&mut tx, &updated)
+            .await?;
+
+        tx.commit().await.map_err(Error::Database)?;
+
+        Ok(updated)
+    }
 }
 
 #[async_trait]
@@ -632,21 +1070,23 @@ impl ResourceStore for PostgresResourceStore {
             .ok_or_else(|| Error::InvalidResource("Missing id field".to_string()))?
             .to_string();
 
-        // Atomically get next version_id
-        let version_row = sqlx::query(
+        // Claim the id; a concurrent create of the same id loses here rather than
+        // writing a second version 1.
+        let version_id: i32 = sqlx::query_scalar(
             "INSERT INTO resource_versions (resource_type, id, next_version)
              VALUES ($1, $2, 1)
-             ON CONFLICT (resource_type, id)
-             DO UPDATE SET next_version = resource_versions.next_version + 1
+             ON CONFLICT (resource_type, id) DO NOTHING
              RETURNING next_version",
         )
         .bind(resource_type)
         .bind(&id)
-        .fetch_one(&self.pool)
+        .fetch_optional(&self.pool)
         .await
-        .map_err(Error::Database)?;
+        .map_err(Error::Database)?
+        .ok_or_else(|| {
+            Error::BusinessRule(format!("Resource {}/{} already exists", resource_type, id))
+        })?;
 
-        let version_id: i32 = version_row.get("next_version");
         let now = Self::extract_meta_last_updated(&resource).unwrap_or_else(Utc::now);
         let url = Self::extract_url(&resource);
         let meta_source = Self::extract_meta_source(&resource);
@@ -679,6 +1119,8 @@ impl ResourceStore for PostgresResourceStore {
     }
 
     async fn upsert(&self, resource_type: &str, id: &str, resource: JsonValue) -> Result<Resource> {
+        ensure_current_visible(&self.pool, resource_type, id).await?;
+
         // Atomically get next version_id
         let version_row = sqlx::query(
             "INSERT INTO resource_versions (resource_type, id, next_version)
@@ -750,14 +1192,16 @@ impl ResourceStore for PostgresResourceStore {
         .await
         .map_err(Error::Database)?;
 
-        Ok(row.map(|r| Resource {
-            id: r.get("id"),
-            resource_type: r.get("resource_type"),
-            version_id: r.get("version_id"),
-            resource: r.get("resource"),
-            last_updated: r.get("last_updated"),
-            deleted: r.get("deleted"),
-        }))
+        Ok(row
+            .map(|r| Resource {
+                id: r.get("id"),
+                resource_type: r.get("resource_type"),
+                version_id: r.get("version_id"),
+                resource: r.get("resource"),
+                last_updated: r.get("last_updated"),
+                deleted: r.get("deleted"),
+            })
+            .filter(|r| security_labels::is_visible(&r.resource)))
     }
 
     async fn update(
@@ -767,6 +1211,8 @@ impl ResourceStore for PostgresResourceStore {
         resource: JsonValue,
         expected_version: Option<i32>,
     ) -> Result<Resource> {
+        ensure_current_visible(&self.pool, resource_type, id).await?;
+
         // Get current version
         let current = sqlx::query(
             "SELECT version_id FROM resources
@@ -853,6 +1299,8 @@ impl ResourceStore for PostgresResourceStore {
     }
 
     async fn delete(&self, resource_type: &str, id: &str) -> Result<i32> {
+        ensure_current_visible(&self.pool, resource_type, id).await?;
+
         // Get current version
         let current = sqlx::query(
             "SELECT version_id, deleted FROM resources
@@ -942,11 +1390,20 @@ impl ResourceStore for PostgresResourceStore {
             version_id,
         })?;
 
+        let resource: JsonValue = row.get("resource");
+        if !security_labels::is_visible(&resource) {
+            return Err(Error::VersionNotFound {
+                resource_type: resource_type.to_string(),
+                id: id.to_string(),
+                version_id,
+            });
+        }
+
         Ok(Resource {
             id: row.get("id"),
             resource_type: row.get("resource_type"),
             version_id: row.get("version_id"),
-            resource: row.get("resource"),
+            resource,
             last_updated: row.get("last_updated"),
             deleted: row.get("deleted"),
         })
@@ -960,6 +1417,7 @@ impl ResourceStore for PostgresResourceStore {
         since: Option<chrono::DateTime<chrono::Utc>>,
         at: Option<chrono::DateTime<chrono::Utc>>,
         sort_ascending: bool,
+        cursor: Option<&HistoryCursor>,
     ) -> Result<HistoryResult> {
         // _at: return only the version that was current at the given instant.
         // That is the version with the highest last_updated <= _at.
@@ -976,7 +1434,8 @@ impl ResourceStore for PostgresResourceStore {
             .bind(at_instant)
             .fetch_optional(&self.pool)
             .await
-            .map_err(Error::Database)?;
+            .map_err(Error::Database)?
+            .filter(|r| security_labels::is_visible(&r.get::<JsonValue, _>("resource")));
 
             let entries = match row {
                 Some(r) => {
@@ -1008,11 +1467,15 @@ impl ResourceStore for PostgresResourceStore {
             return Ok(HistoryResult {
                 entries,
                 total: Some(total),
+                next_cursor: None,
             });
         }
 
-        let limit = count.unwrap_or(100);
+        let limit = history_fetch_limit(count);
         let order = if sort_ascending { "ASC" } else { "DESC" };
+        let after_cursor = history_cursor_clause(5, sort_ascending);
+        let visible = visible_sql("resource", 9);
+        let scope = security_labels::current_scope_tokens();
 
         // Note: `order` is injected from a boolean and is not user-controlled.
         let sql = format!(
@@ -1020,15 +1483,19 @@ impl ResourceStore for PostgresResourceStore {
              FROM resources
              WHERE resource_type = $1 AND id = $2
                AND ($3::TIMESTAMPTZ IS NULL OR last_updated >= $3)
+               AND {after_cursor}
+               AND {visible}
              ORDER BY last_updated {order}, version_id {order}
              LIMIT $4"
         );
 
-        let rows = sqlx::query(&sql)
+        let query = sqlx::query(&sql)
             .bind(resource_type)
             .bind(id)
             .bind(since)
-            .bind(limit as i64)
+            .bind(limit);
+        let rows = bind_history_cursor(query, cursor)
+            .bind(scope.clone())
             .fetch_all(&self.pool)
             .await
             .map_err(Error::Database)?;
@@ -1062,25 +1529,26 @@ impl ResourceStore for PostgresResourceStore {
             .collect();
 
         // Get total count
-        let total_row = sqlx::query(
+        let total_sql = format!(
             "SELECT COUNT(*) as count
              FROM resources
              WHERE resource_type = $1 AND id = $2
-               AND ($3::TIMESTAMPTZ IS NULL OR last_updated >= $3)",
-        )
-        .bind(resource_type)
-        .bind(id)
-        .bind(since)
-        .fetch_one(&self.pool)
-        .await
-        .map_err(Error::Database)?;
+               AND ($3::TIMESTAMPTZ IS NULL OR last_updated >= $3)
+               AND {}",
+            visible_sql("resource", 4)
+        );
+        let total_row = sqlx::query(&total_sql)
+            .bind(resource_type)
+            .bind(id)
+            .bind(since)
+            .bind(scope)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(Error::Database)?;
 
         let total: i64 = total_row.get("count");
 
-        Ok(HistoryResult {
-            entries,
-            total: Some(total),
-        })
+        Ok(HistoryResult::paginate(entries, count, Some(total)))
     }
 
     async fn search(
@@ -24,6 +24,7 @@ pub struct ConceptRow {
 #[derive(Debug, Clone)]
 pub struct ConceptDetails {
     pub display: Option<String>,
+    pub definition: Option<String>,
     pub properties: Option<JsonValue>,
     pub designations: Option<JsonValue>,
 }
@@ -137,7 +138,7 @@ impl TerminologyRepository {
         version: Option<&str>,
     ) -> Result<Option<ConceptDetails>> {
         let row = sqlx::query(
-            "SELECT display, properties, designations
+            "SELECT display, definition, properties, designations
              FROM codesystem_concepts
              WHERE system = $1
                AND code = $2
@@ -153,6 +154,7 @@ impl TerminologyRepository {
 
         Ok(row.map(|r| ConceptDetails {
             display: Some(r.get("display")),
+            definition: r.get("definition"),
             properties: r.get("properties"),
             designations: r.get("designations"),
         }))
@@ -1,7 +1,7 @@
 //! Core traits for FHIR REST storage backends
 
 use crate::{
-    models::fhir::{HistoryResult, Resource},
+    models::fhir::{HistoryCursor, HistoryResult, Resource},
     Result,
 };
 use async_trait::async_trait;
@@ -107,9 +107,10 @@ pub trait ResourceStore: Send + Sync + Clone {
     /// * `since` - Only return versions created at or after this instant
     /// * `at` - Only return the version(s) that were current at this instant
     /// * `sort_ascending` - Sort by `_lastUpdated` ascending when true, descending when false
+    /// * `cursor` - Resume after this entry (from a previous page's `next_cursor`)
     ///
     /// # Returns
-    /// History bundle with all versions (newest first)
+    /// One page of versions (newest first), with `next_cursor` set if more exist
     async fn history(
         &self,
         resource_type: &str,
@@ -118,6 +119,7 @@ pub trait ResourceStore: Send + Sync + Clone {
         since: Option<DateTime<Utc>>,
         at: Option<DateTime<Utc>>,
         sort_ascending: bool,
+        cursor: Option<&HistoryCursor>,
     ) -> Result<HistoryResult>;
 
     /// Search for resources matching criteria
@@ -6,10 +6,11 @@ use serde_json::Value as JsonValue;
 use sqlx::{Postgres, Row, Transaction};
 
 use super::{
+    store::ensure_current_visible,
     traits::{ResourceTransaction, TransactionContext},
     PostgresResourceStore,
 };
-use crate::{models::Resource, Error, Result};
+use crate::{models::Resource, security_labels, Error, Result};
 
 /// PostgreSQL transaction context
 pub struct PostgresTransactionContext {
@@ -149,20 +150,21 @@ impl TransactionContext for PostgresTransactionContext {
 
         let tx = self.tx_mut()?;
 
-        let version_row = sqlx::query(
+        // Ids are never reused, so an existing row means the create conflicts.
+        let version_id: i32 = sqlx::query_scalar(
             "INSERT INTO resource_versions (resource_type, id, next_version)
              VALUES ($1, $2, 1)
-             ON CONFLICT (resource_type, id)
-             DO UPDATE SET next_version = resource_versions.next_version + 1
+             ON CONFLICT (resource_type, id) DO NOTHING
              RETURNING next_version",
         )
         .bind(resource_type)
         .bind(&id)
-        .fetch_one(&mut **tx)
+        .fetch_optional(&mut **tx)
         .await
-        .map_err(Error::Database)?;
-
-        let version_id: i32 = version_row.get("next_version");
+        .map_err(Error::Database)?
+        .ok_or_else(|| {
+            Error::BusinessRule(format!("Resource {}/{} already exists", resource_type, id))
+        })?;
 
         sqlx::query(
             "INSERT INTO resources (id, resource_type, version_id, resource, last_updated, url, meta_source, meta_tags, deleted, is_current)
@@ -203,6 +205,7 @@ impl TransactionContext for PostgresTransactionContext {
         let meta_tags = PostgresResourceStore::extract_meta_tags(&resource);
 
         let tx = self.tx_mut()?;
+        ensure_current_visible(&mut **tx, resource_type, id).await?;
 
         let version_row = sqlx::query(
             "INSERT INTO resource_versions (resource_type, id, next_version)
@@ -269,18 +272,21 @@ impl TransactionContext for PostgresTransactionContext {
         .await
         .map_err(Error::Database)?;
 
-        Ok(current.map(|row| Resource {
-            id: row.get("id"),
-            resource_type: row.get("resource_type"),
-            version_id: row.get("version_id"),
-            resource: row.get("resource"),
-            last_updated: row.get("last_updated"),
-            deleted: row.get("deleted"),
-        }))
+        Ok(current
+            .map(|row| Resource {
+                id: row.get("id"),
+                resource_type: row.get("resource_type"),
+                version_id: row.get("version_id"),
+                resource: row.get("resource"),
+                last_updated: row.get("last_updated"),
+                deleted: row.get("deleted"),
+            })
+            .filter(|r| security_labels::is_visible(&r.resource)))
     }
 
     async fn delete(&mut self, resource_type: &str, id: &str) -> Result<i32> {
         let tx = self.tx_mut()?;
+        ensure_current_visible(&mut **tx, resource_type, id).await?;
 
         let current = sqlx::query(
             "SELECT version_id, deleted FROM resources
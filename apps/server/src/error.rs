@@ -59,6 +59,9 @@ pub enum Error {
     #[error("Unsupported media type: {0}")]
     UnsupportedMediaType(String),
 
+    #[error("Not acceptable: {0}")]
+    NotAcceptable(String),
+
     #[error("Unprocessable entity: {0}")]
     UnprocessableEntity(String),
 
@@ -90,6 +93,24 @@ pub enum Error {
     Other(#[from] anyhow::Error),
 }
 
+/// PostgreSQL SQLSTATE `query_canceled`, raised when `statement_timeout` expires.
+const SQLSTATE_QUERY_CANCELED: &str = "57014";
+
+/// Seconds clients are asked to wait before retrying a timed-out request.
+const TIMEOUT_RETRY_AFTER_SECONDS: &str = "5";
+
+impl Error {
+    /// Whether a database statement was cancelled, typically by `statement_timeout`.
+    pub fn is_statement_timeout(&self) -> bool {
+        match self {
+            Error::Database(sqlx::Error::Database(db)) => {
+                db.code().as_deref() == Some(SQLSTATE_QUERY_CANCELED)
+            }
+            _ => false,
+        }
+    }
+}
+
 impl IntoResponse for Error {
     fn into_response(self) -> Response {
         let (status, error_message, etag) = match &self {
@@ -114,11 +135,20 @@ impl IntoResponse for Error {
             Error::UnsupportedMediaType(_) => {
                 (StatusCode::UNSUPPORTED_MEDIA_TYPE, self.to_string(), None)
             }
+            Error::NotAcceptable(_) => (StatusCode::NOT_ACCEPTABLE, self.to_string(), None),
             Error::UnprocessableEntity(_) => {
                 (StatusCode::UNPROCESSABLE_ENTITY, self.to_string(), None)
             }
             Error::NotImplemented(_) => (StatusCode::NOT_IMPLEMENTED, self.to_string(), None),
             Error::TooCostly(_) => (StatusCode::FORBIDDEN, self.to_string(), None),
+            Error::Database(_) if self.is_statement_timeout() => {
+                tracing::warn!("Database statement timed out: {}", self);
+                (
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    "The request took too long to process and was cancelled".to_string(),
+                    None,
+                )
+            }
             Error::Database(_)
             | Error::JobQueue(_)
             | Error::Internal(_)
@@ -154,6 +184,13 @@ impl IntoResponse for Error {
             HeaderValue::from_static("application/fhir+json; charset=utf-8"),
         );
 
+        if status == StatusCode::SERVICE_UNAVAILABLE {
+            response.headers_mut().insert(
+                header::RETRY_AFTER,
+                HeaderValue::from_static(TIMEOUT_RETRY_AFTER_SECONDS),
+            );
+        }
+
         // Per FHIR spec: MAY include ETag on deleted resource errors
         if let Some(version_id) = etag {
             let etag_value = format!("W/\"{}\"", version_id);
@@ -172,12 +209,14 @@ fn status_to_fhir_code(status: StatusCode) -> &'static str {
         StatusCode::NOT_FOUND => "not-found",
         StatusCode::GONE => "deleted",
         StatusCode::UNSUPPORTED_MEDIA_TYPE => "not-supported",
+        StatusCode::NOT_ACCEPTABLE => "not-supported",
         StatusCode::METHOD_NOT_ALLOWED => "not-supported",
         StatusCode::CONFLICT => "conflict",
         StatusCode::PRECONDITION_FAILED => "conflict",
         StatusCode::UNPROCESSABLE_ENTITY => "processing",
         StatusCode::NOT_IMPLEMENTED => "not-supported",
         StatusCode::FORBIDDEN => "too-costly",
+        StatusCode::SERVICE_UNAVAILABLE => "timeout",
         _ => "exception",
     }
 }
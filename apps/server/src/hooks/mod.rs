@@ -7,6 +7,7 @@ use crate::Result;
 
 pub mod compartment_definition;
 pub mod computed;
+pub mod search_cache;
 pub mod search_index;
 pub mod search_parameter;
 pub mod terminology;
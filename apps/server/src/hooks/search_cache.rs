@@ -0,0 +1,36 @@
+//! Search cache invalidation hook
+//!
+//! Evicts cached search results that depend on the type of a created, updated or deleted
+//! resource (see [`SearchCache::invalidate`]).
+
+use crate::{hooks::ResourceHook, models::Resource, services::search_cache::SearchCache, Result};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+pub struct SearchCacheHook {
+    cache: Arc<SearchCache>,
+}
+
+impl SearchCacheHook {
+    pub fn new(cache: Arc<SearchCache>) -> Self {
+        Self { cache }
+    }
+}
+
+#[async_trait]
+impl ResourceHook for SearchCacheHook {
+    async fn on_created(&self, resource: &Resource) -> Result<()> {
+        self.cache.invalidate(&resource.resource_type);
+        Ok(())
+    }
+
+    async fn on_updated(&self, resource: &Resource) -> Result<()> {
+        self.cache.invalidate(&resource.resource_type);
+        Ok(())
+    }
+
+    async fn on_deleted(&self, resource_type: &str, _id: &str, _version: i32) -> Result<()> {
+        self.cache.invalidate(resource_type);
+        Ok(())
+    }
+}
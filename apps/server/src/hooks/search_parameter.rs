@@ -91,6 +91,10 @@ impl SearchParameterHook {
                     .any(|allowed| allowed.eq_ignore_ascii_case(s))
             })
             .unwrap_or(false);
+        let experimental = resource
+            .get("experimental")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
 
         // Extract optional fields
         let multiple_or = resource
@@ -149,15 +153,19 @@ impl SearchParameterHook {
                     .unwrap_or_else(|| Cow::Borrowed(expr))
             });
 
-            // Upsert into search_parameters table (one row per base type)
+            // Upsert into search_parameters table (one row per base type). New rows, changed
+            // expressions and reactivations get a fresh `indexed_from` watermark (and lose
+            // any per-type coverage), since resources already stored were indexed without them.
             let row = sqlx::query(
                 r#"
                 INSERT INTO search_parameters (
                     code, resource_type, type, expression, url, description,
                     active, multiple_or, multiple_and, comparators, modifiers, chains, targets,
-                    created_at, updated_at
+                    status, experimental, created_at, updated_at
+                )
+                VALUES (
+                    $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, NOW(), NOW()
                 )
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, NOW(), NOW())
                 ON CONFLICT (code, resource_type)
                 DO UPDATE SET
                     type = EXCLUDED.type,
@@ -171,6 +179,20 @@ impl SearchParameterHook {
                     modifiers = EXCLUDED.modifiers,
                     chains = EXCLUDED.chains,
                     targets = EXCLUDED.targets,
+                    status = EXCLUDED.status,
+                    experimental = EXCLUDED.experimental,
+                    indexed_from = CASE
+                        WHEN search_parameters.expression IS DISTINCT FROM EXCLUDED.expression
+                            OR (NOT search_parameters.active AND EXCLUDED.active)
+                        THEN NOW()
+                        ELSE search_parameters.indexed_from
+                    END,
+                    indexed_types = CASE
+                        WHEN search_parameters.expression IS DISTINCT FROM EXCLUDED.expression
+                            OR (NOT search_parameters.active AND EXCLUDED.active)
+                        THEN '{}'
+                        ELSE search_parameters.indexed_types
+                    END,
                     updated_at = NOW()
                 RETURNING id
                 "#,
@@ -188,6 +210,8 @@ impl SearchParameterHook {
             .bind(modifiers.as_deref())
             .bind(chains.as_deref())
             .bind(targets.as_deref())
+            .bind(status)
+            .bind(experimental)
             .fetch_one(&self.pool)
             .await
             .map_err(crate::Error::Database)?;
@@ -52,14 +52,16 @@ impl TerminologyHook {
                     .get("display")
                     .and_then(|v| v.as_str())
                     .unwrap_or(code);
+                let definition = concept.get("definition").and_then(|v| v.as_str());
                 let properties = concept.get("property").cloned();
                 let designations = concept.get("designation").cloned();
 
                 sqlx::query(
-                    "INSERT INTO codesystem_concepts (system, version, code, display, properties, designations)
-                     VALUES ($1, $2, $3, $4, $5, $6)
+                    "INSERT INTO codesystem_concepts (system, version, code, display, definition, properties, designations)
+                     VALUES ($1, $2, $3, $4, $5, $6, $7)
                      ON CONFLICT (system, version, code) DO UPDATE
                        SET display = EXCLUDED.display,
+                           definition = EXCLUDED.definition,
                            properties = EXCLUDED.properties,
                            designations = EXCLUDED.designations,
                            updated_at = NOW()",
@@ -68,6 +70,7 @@ impl TerminologyHook {
                 .bind(version)
                 .bind(code)
                 .bind(display)
+                .bind(definition)
                 .bind(properties)
                 .bind(designations)
                 .execute(&mut *tx)
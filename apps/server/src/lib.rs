@@ -31,6 +31,7 @@ pub mod models;
 pub mod queue;
 pub mod request_context;
 pub mod runtime_config;
+pub mod security_labels;
 pub mod services;
 pub mod startup;
 pub mod state;
@@ -53,6 +53,12 @@ async fn main() -> anyhow::Result<()> {
         .await
         .context("Failed to initialize application state")?;
 
+    // Resume asynchronous operation requests left pending by a previous run
+    tokio::spawn(ferrum::services::async_operation::resume(
+        state.job_queue.clone(),
+        state.operation_executor.clone(),
+    ));
+
     // Create router
     let app = create_router(state);
 
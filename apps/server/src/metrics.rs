@@ -90,6 +90,15 @@ lazy_static! {
     )
     .expect("Failed to register FHIR_SEARCH_RESULTS");
 
+    /// Search latency (parameter resolution and SQL) attributed to each search parameter used
+    pub static ref FHIR_SEARCH_PARAMETER_DURATION_SECONDS: HistogramVec = register_histogram_vec!(
+        "fhir_search_parameter_duration_seconds",
+        "Search resolution and SQL duration in seconds by search parameter",
+        &["resource_type", "parameter"],
+        vec![0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0]
+    )
+    .expect("Failed to register FHIR_SEARCH_PARAMETER_DURATION_SECONDS");
+
     /// FHIR batch/transaction operations
     pub static ref FHIR_BATCH_OPERATIONS_TOTAL: IntCounterVec = register_int_counter_vec!(
         "fhir_batch_operations_total",
@@ -223,6 +232,25 @@ lazy_static! {
     .expect("Failed to register RESOURCE_VERSIONS");
 }
 
+/// Record one search's duration under each distinct parameter code it used.
+///
+/// Chained and composite parameters are attributed to their top-level code, so
+/// `subject:Patient.name` counts towards `subject`.
+pub fn observe_search_parameter_durations<'a>(
+    resource_type: &str,
+    codes: impl IntoIterator<Item = &'a str>,
+    elapsed: std::time::Duration,
+) {
+    let mut seen = std::collections::HashSet::new();
+    for code in codes {
+        if seen.insert(code) {
+            FHIR_SEARCH_PARAMETER_DURATION_SECONDS
+                .with_label_values(&[resource_type, code])
+                .observe(elapsed.as_secs_f64());
+        }
+    }
+}
+
 /// Helper to sanitize path for metrics labels (remove IDs, limit cardinality)
 pub fn sanitize_path(path: &str) -> String {
     // Remove /fhir prefix if present
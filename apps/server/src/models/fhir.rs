@@ -106,11 +106,67 @@ pub enum HistoryMethod {
     Delete, // Deleted
 }
 
+/// Page size used for history when `_count` is absent
+pub const DEFAULT_HISTORY_COUNT: i32 = 100;
+
+/// Position in a history listing: the last entry of the previous page.
+///
+/// History is ordered by `(last_updated, resource_type, id, version_id)`, which is unique
+/// per entry, so the next page resumes strictly after this key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryCursor {
+    pub last_updated: DateTime<Utc>,
+    pub resource_type: String,
+    pub id: String,
+    pub version_id: i32,
+}
+
+impl HistoryCursor {
+    /// Cursor resuming right after `resource`
+    pub fn after(resource: &Resource) -> Self {
+        Self {
+            last_updated: resource.last_updated,
+            resource_type: resource.resource_type.clone(),
+            id: resource.id.clone(),
+            version_id: resource.version_id,
+        }
+    }
+}
+
 /// History bundle result
 #[derive(Debug, Clone)]
 pub struct HistoryResult {
     pub entries: Vec<HistoryEntry>,
     pub total: Option<i64>,
+    /// Where the next page starts, if more entries exist
+    pub next_cursor: Option<HistoryCursor>,
+}
+
+impl HistoryResult {
+    /// Build a page from entries fetched with one row beyond the page size.
+    ///
+    /// The extra row is dropped; its presence only signals that a next page exists.
+    pub fn paginate(
+        mut entries: Vec<HistoryEntry>,
+        count: Option<i32>,
+        total: Option<i64>,
+    ) -> Self {
+        let limit = count.unwrap_or(DEFAULT_HISTORY_COUNT).max(0) as usize;
+        let next_cursor = if entries.len() > limit {
+            entries.truncate(limit);
+            entries
+                .last()
+                .map(|entry| HistoryCursor::after(&entry.resource))
+        } else {
+            None
+        };
+
+        Self {
+            entries,
+            total,
+            next_cursor,
+        }
+    }
 }
 
 /// Version-aware update parameters
@@ -5,8 +5,8 @@ pub mod operations;
 pub mod resource_types;
 
 pub use fhir::{
-    ConditionalParams, CreateParams, HistoryEntry, HistoryMethod, HistoryResult, Resource,
-    ResourceOperation, ResourceResult, UpdateParams,
+    ConditionalParams, CreateParams, HistoryCursor, HistoryEntry, HistoryMethod, HistoryResult,
+    Resource, ResourceOperation, ResourceResult, UpdateParams, DEFAULT_HISTORY_COUNT,
 };
 pub use operations::*;
 pub use resource_types::{is_known_resource_type, RESOURCE_TYPES};
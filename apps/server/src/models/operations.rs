@@ -171,6 +171,14 @@ impl Parameters {
         self.parameter.get_or_insert_with(Vec::new).push(param);
     }
 
+    pub fn add_value_meta(&mut self, name: String, meta: JsonValue) {
+        let param = Parameter {
+            name,
+            value: ParameterValue::Value(HashMap::from([("valueMeta".to_string(), meta)])),
+        };
+        self.parameter.get_or_insert_with(Vec::new).push(param);
+    }
+
     pub fn add_resource(&mut self, name: String, resource: JsonValue) {
         let param = Parameter {
             name,
@@ -5,6 +5,9 @@
 //!
 //! Primary use-case: deterministic integration tests that need search indexing to
 //! be completed before a response is observed.
+//!
+//! `async_operation` jobs are the exception: they stay pending until claimed with
+//! `dequeue`, as with the Postgres queue.
 
 use super::{Job, JobPriority, JobQueue, JobStatus, RetryPolicy};
 use crate::{db::PostgresResourceStore, services::IndexingService, Result};
@@ -99,6 +102,7 @@ impl InlineJobQueue {
             }
         } else {
             let resource_type = params.resource_type.as_deref();
+            let started_at = Utc::now();
             let mut after_id: Option<String> = None;
 
             loop {
@@ -134,6 +138,10 @@ impl InlineJobQueue {
 
                 after_id = page.last().map(|(_, id)| id.clone());
             }
+
+            self.indexing_service
+                .mark_search_parameters_indexed(resource_type, started_at)
+                .await?;
         }
 
         self.update_progress(
@@ -182,10 +190,16 @@ impl JobQueue for InlineJobQueue {
                 crate::Error::Internal(format!("Failed to serialize retry policy: {}", e))
             })?;
 
+        // Claimed and run by the API server (see `services::async_operation::run_pending`).
+        let claimed_later = job_type == crate::services::async_operation::ASYNC_OPERATION_JOB_TYPE;
         let job = Job {
             id: job_id,
             job_type: job_type.clone(),
-            status: JobStatus::Running,
+            status: if claimed_later {
+                JobStatus::Pending
+            } else {
+                JobStatus::Running
+            },
             priority: priority as i32,
             parameters: parameters.clone(),
             progress: None,
@@ -198,21 +212,33 @@ impl JobQueue for InlineJobQueue {
             scheduled_at: None,
             cancel_requested: false,
             created_at: now,
-            started_at: Some(now),
+            started_at: (!claimed_later).then_some(now),
             completed_at: None,
-            worker_id: Some("inline".to_string()),
+            worker_id: (!claimed_later).then(|| "inline".to_string()),
         };
         self.insert_job(job);
+        if claimed_later {
+            return Ok(job_id);
+        }
 
-        let result = match job_type.as_str() {
-            "index_search" => self.run_index_search(job_id, parameters).await,
-            "reindex" => self.run_reindex(job_id, parameters).await,
-            // Unsupported jobs are treated as no-ops in inline mode.
-            _ => {
-                self.complete_job(job_id, None).await?;
-                Ok(())
+        // Jobs run like on a worker, outside the enqueuing request's security-label scope;
+        // jobs that need one carry it in their parameters.
+        let result = crate::security_labels::with_scope(None, async {
+            match job_type.as_str() {
+                "index_search" => self.run_index_search(job_id, parameters).await,
+                "reindex" => self.run_reindex(job_id, parameters).await,
+                crate::services::bulk_export::BULK_EXPORT_JOB_TYPE => {
+                    crate::services::bulk_export::BulkExportService::new(
+                        PostgresResourceStore::new(self.pool.clone()),
+                    )
+                    .run(self, job_id)
+                    .await
+                }
+                // Unsupported jobs are treated as no-ops in inline mode.
+                _ => self.complete_job(job_id, None).await,
             }
-        };
+        })
+        .await;
 
         if let Err(e) = result {
             let _ = self
@@ -224,8 +250,23 @@ impl JobQueue for InlineJobQueue {
         Ok(job_id)
     }
 
-    async fn dequeue(&self, _job_types: &[String], _worker_id: &str) -> Result<Option<Job>> {
-        Ok(None)
+    async fn dequeue(&self, job_types: &[String], worker_id: &str) -> Result<Option<Job>> {
+        let mut jobs = self.jobs.lock().unwrap();
+        let Some(job) = jobs
+            .values_mut()
+            .filter(|job| {
+                job.status == JobStatus::Pending
+                    && !job.cancel_requested
+                    && job_types.contains(&job.job_type)
+            })
+            .min_by_key(|job| (std::cmp::Reverse(job.priority), job.created_at))
+        else {
+            return Ok(None);
+        };
+        job.status = JobStatus::Running;
+        job.started_at = Some(Utc::now());
+        job.worker_id = Some(worker_id.to_string());
+        Ok(Some(job.clone()))
     }
 
     async fn listen<'a>(&'a self, _job_types: &'a [String]) -> Result<BoxStream<'a, Result<Job>>> {
@@ -285,6 +326,23 @@ impl JobQueue for InlineJobQueue {
         })
     }
 
+    async fn fail_running_jobs(&self, job_type: &str, error_message: String) -> Result<i64> {
+        let now = Utc::now();
+        let mut jobs = self.jobs.lock().unwrap();
+        let mut failed = 0;
+        for job in jobs
+            .values_mut()
+            .filter(|job| job.job_type == job_type && job.status == JobStatus::Running)
+        {
+            job.status = JobStatus::Failed;
+            job.error_message = Some(error_message.clone());
+            job.last_error_at = Some(now);
+            job.completed_at = Some(now);
+            failed += 1;
+        }
+        Ok(failed)
+    }
+
     async fn cancel_job(&self, job_id: Uuid) -> Result<bool> {
         let mut jobs = self.jobs.lock().unwrap();
         if let Some(job) = jobs.get_mut(&job_id) {
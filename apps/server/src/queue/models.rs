@@ -78,6 +78,11 @@ pub enum JobType {
         resource_type: Option<String>,
         resource_id: Option<String>,
     },
+    /// Bulk Data export of resource types as NDJSON files
+    BulkExport {
+        resource_types: Vec<String>,
+        since: Option<DateTime<Utc>>,
+    },
 }
 
 impl JobType {
@@ -89,6 +94,7 @@ impl JobType {
             JobType::UpdateSearchParameters { .. } => "update_search_parameters",
             JobType::InstallPackage { .. } => "install_package",
             JobType::Reindex { .. } => "reindex",
+            JobType::BulkExport { .. } => "bulk_export",
         }
     }
 }
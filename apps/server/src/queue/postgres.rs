@@ -71,6 +71,91 @@ impl JobQueue for PostgresJobQueue {
         Ok(job_id)
     }
 
+    async fn enqueue_unique(
+        &self,
+        job_type: String,
+        parameters: serde_json::Value,
+        priority: JobPriority,
+        retry_policy: Option<RetryPolicy>,
+    ) -> Result<(Uuid, bool)> {
+        let mut tx = self.pool.begin().await.map_err(crate::Error::Database)?;
+
+        // Serialize concurrent callers for the same job so only one of them inserts.
+        sqlx::query("SELECT pg_advisory_xact_lock(hashtext($1 || ':' || $2::text))")
+            .bind(&job_type)
+            .bind(&parameters)
+            .execute(&mut *tx)
+            .await
+            .map_err(crate::Error::Database)?;
+
+        let existing: Option<Uuid> = sqlx::query_scalar(
+            r#"
+            SELECT id
+            FROM jobs
+            WHERE job_type = $1
+              AND parameters = $2
+              AND status = 'pending'
+              AND cancel_requested = FALSE
+            ORDER BY created_at ASC
+            LIMIT 1
+            "#,
+        )
+        .bind(&job_type)
+        .bind(&parameters)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(crate::Error::Database)?;
+
+        if let Some(job_id) = existing {
+            tx.commit().await.map_err(crate::Error::Database)?;
+            tracing::info!(
+                "Coalesced job request into existing job: {} (type: {})",
+                job_id,
+                job_type
+            );
+            return Ok((job_id, false));
+        }
+
+        let job_id = Uuid::new_v4();
+        let retry_policy_json =
+            serde_json::to_value(retry_policy.unwrap_or_default()).map_err(|e| {
+                crate::Error::Internal(format!("Failed to serialize retry policy: {}", e))
+            })?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO jobs (id, job_type, status, parameters, priority, retry_policy)
+            VALUES ($1, $2, 'pending', $3, $4, $5)
+            "#,
+        )
+        .bind(job_id)
+        .bind(&job_type)
+        .bind(&parameters)
+        .bind(priority as i32)
+        .bind(retry_policy_json)
+        .execute(&mut *tx)
+        .await
+        .map_err(crate::Error::Database)?;
+
+        // Delivered to listeners on commit
+        sqlx::query("SELECT pg_notify('job_queue', $1)")
+            .bind(&job_type)
+            .execute(&mut *tx)
+            .await
+            .map_err(crate::Error::Database)?;
+
+        tx.commit().await.map_err(crate::Error::Database)?;
+
+        tracing::info!(
+            "Enqueued job: {} (type: {}, priority: {:?})",
+            job_id,
+            job_type,
+            priority
+        );
+
+        Ok((job_id, true))
+    }
+
     async fn dequeue(&self, job_types: &[String], worker_id: &str) -> Result<Option<Job>> {
         let now = chrono::Utc::now();
 
@@ -359,6 +444,35 @@ impl JobQueue for PostgresJobQueue {
         Ok(())
     }
 
+    async fn fail_running_jobs(&self, job_type: &str, error_message: String) -> Result<i64> {
+        let result = sqlx::query(
+            r#"
+            UPDATE jobs
+            SET status = 'failed',
+                completed_at = NOW(),
+                error_message = $2,
+                last_error_at = NOW()
+            WHERE job_type = $1 AND status = 'running'
+            "#,
+        )
+        .bind(job_type)
+        .bind(&error_message)
+        .execute(&self.pool)
+        .await
+        .map_err(crate::Error::Database)?;
+
+        let failed = result.rows_affected() as i64;
+        if failed > 0 {
+            tracing::warn!(
+                "Failed {} running {} jobs: {}",
+                failed,
+                job_type,
+                error_message
+            );
+        }
+        Ok(failed)
+    }
+
     async fn cancel_job(&self, job_id: Uuid) -> Result<bool> {
         let now = chrono::Utc::now();
 
@@ -18,6 +18,23 @@ pub trait JobQueue: Send + Sync {
         retry_policy: Option<RetryPolicy>,
     ) -> Result<Uuid>;
 
+    /// Enqueue a job unless an equivalent one (same type and parameters) is already pending.
+    ///
+    /// A running job may already be past the data the caller wants processed, so it never
+    /// absorbs a new request. Returns the job id and whether a new job was created.
+    async fn enqueue_unique(
+        &self,
+        job_type: String,
+        parameters: serde_json::Value,
+        priority: JobPriority,
+        retry_policy: Option<RetryPolicy>,
+    ) -> Result<(Uuid, bool)> {
+        let job_id = self
+            .enqueue(job_type, parameters, priority, retry_policy)
+            .await?;
+        Ok((job_id, true))
+    }
+
     /// Dequeue the next available job
     async fn dequeue(&self, job_types: &[String], worker_id: &str) -> Result<Option<Job>>;
 
@@ -47,6 +64,12 @@ pub trait JobQueue: Send + Sync {
     /// Mark job as failed and optionally schedule retry
     async fn fail_job(&self, job_id: Uuid, error_message: String, retry: bool) -> Result<()>;
 
+    /// Mark every running job of `job_type` as failed, returning how many were failed.
+    ///
+    /// Used at start-up for job types run by the server process itself: a job still running
+    /// then was interrupted by the previous shutdown and will never complete.
+    async fn fail_running_jobs(&self, job_type: &str, error_message: String) -> Result<i64>;
+
     /// Request job cancellation
     async fn cancel_job(&self, job_id: Uuid) -> Result<bool>;
 
@@ -114,6 +114,9 @@ impl RuntimeConfigCache {
             ConfigKey::SearchMaxCount => {
                 JsonValue::Number(self.static_config.fhir.search.max_count.into())
             }
+            ConfigKey::SearchMaxPageSize => {
+                JsonValue::Number(self.static_config.fhir.search.max_page_size.into())
+            }
             ConfigKey::SearchMaxTotalResults => {
                 JsonValue::Number(self.static_config.fhir.search.max_total_results.into())
             }
@@ -123,6 +126,13 @@ impl RuntimeConfigCache {
             ConfigKey::SearchMaxIncludes => {
                 JsonValue::Number(self.static_config.fhir.search.max_includes.into())
             }
+            ConfigKey::SearchMaxWildcardIncludeParams => JsonValue::Number(
+                self.static_config
+                    .fhir
+                    .search
+                    .max_wildcard_include_params
+                    .into(),
+            ),
 
             // Interactions - Instance
             ConfigKey::InteractionsInstanceRead => {
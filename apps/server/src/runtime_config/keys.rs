@@ -86,9 +86,11 @@ pub enum ConfigKey {
     // Search
     SearchDefaultCount,
     SearchMaxCount,
+    SearchMaxPageSize,
     SearchMaxTotalResults,
     SearchMaxIncludeDepth,
     SearchMaxIncludes,
+    SearchMaxWildcardIncludeParams,
 
     // Interactions - Instance
     InteractionsInstanceRead,
@@ -167,9 +169,11 @@ impl ConfigKey {
             // Search
             ConfigKey::SearchDefaultCount => "fhir.search.default_count",
             ConfigKey::SearchMaxCount => "fhir.search.max_count",
+            ConfigKey::SearchMaxPageSize => "fhir.search.max_page_size",
             ConfigKey::SearchMaxTotalResults => "fhir.search.max_total_results",
             ConfigKey::SearchMaxIncludeDepth => "fhir.search.max_include_depth",
             ConfigKey::SearchMaxIncludes => "fhir.search.max_includes",
+            ConfigKey::SearchMaxWildcardIncludeParams => "fhir.search.max_wildcard_include_params",
 
             // Interactions - Instance
             ConfigKey::InteractionsInstanceRead => "fhir.interactions.instance.read",
@@ -260,9 +264,11 @@ impl ConfigKey {
 
             ConfigKey::SearchDefaultCount
             | ConfigKey::SearchMaxCount
+            | ConfigKey::SearchMaxPageSize
             | ConfigKey::SearchMaxTotalResults
             | ConfigKey::SearchMaxIncludeDepth
-            | ConfigKey::SearchMaxIncludes => ConfigCategory::Search,
+            | ConfigKey::SearchMaxIncludes
+            | ConfigKey::SearchMaxWildcardIncludeParams => ConfigCategory::Search,
 
             ConfigKey::InteractionsInstanceRead
             | ConfigKey::InteractionsInstanceVread
@@ -329,9 +335,11 @@ impl ConfigKey {
 
             ConfigKey::SearchDefaultCount
             | ConfigKey::SearchMaxCount
+            | ConfigKey::SearchMaxPageSize
             | ConfigKey::SearchMaxTotalResults
             | ConfigKey::SearchMaxIncludeDepth
-            | ConfigKey::SearchMaxIncludes => ConfigValueType::Integer,
+            | ConfigKey::SearchMaxIncludes
+            | ConfigKey::SearchMaxWildcardIncludeParams => ConfigValueType::Integer,
 
             ConfigKey::FormatDefault | ConfigKey::FormatDefaultPreferReturn => {
                 ConfigValueType::StringEnum
@@ -351,6 +359,9 @@ impl ConfigKey {
             // Search
             ConfigKey::SearchDefaultCount => "Default page size when _count is not specified",
             ConfigKey::SearchMaxCount => "Maximum allowed _count value",
+            ConfigKey::SearchMaxPageSize => {
+                "Page size cap; larger _count values are clamped with a warning"
+            }
             ConfigKey::SearchMaxTotalResults => "Maximum total results across all pages",
             ConfigKey::SearchMaxIncludeDepth => {
                 "Maximum depth for _include:iterate and _revinclude:iterate"
@@ -358,6 +369,9 @@ impl ConfigKey {
             ConfigKey::SearchMaxIncludes => {
                 "Maximum number of _include/_revinclude parameters allowed"
             }
+            ConfigKey::SearchMaxWildcardIncludeParams => {
+                "Maximum number of reference parameters a wildcard _include/_revinclude may expand to"
+            }
 
             // Interactions - Instance
             ConfigKey::InteractionsInstanceRead => "Enable GET /{type}/{id}",
@@ -472,9 +486,11 @@ impl ConfigKey {
         match self {
             ConfigKey::SearchDefaultCount => Some((1, 1000)),
             ConfigKey::SearchMaxCount => Some((1, 10000)),
+            ConfigKey::SearchMaxPageSize => Some((1, 10000)),
             ConfigKey::SearchMaxTotalResults => Some((1, 100000)),
             ConfigKey::SearchMaxIncludeDepth => Some((0, 10)),
             ConfigKey::SearchMaxIncludes => Some((0, 50)),
+            ConfigKey::SearchMaxWildcardIncludeParams => Some((1, 500)),
             _ => None,
         }
     }
@@ -487,9 +503,13 @@ impl ConfigKey {
 
             "fhir.search.default_count" => Some(ConfigKey::SearchDefaultCount),
             "fhir.search.max_count" => Some(ConfigKey::SearchMaxCount),
+            "fhir.search.max_page_size" => Some(ConfigKey::SearchMaxPageSize),
             "fhir.search.max_total_results" => Some(ConfigKey::SearchMaxTotalResults),
             "fhir.search.max_include_depth" => Some(ConfigKey::SearchMaxIncludeDepth),
             "fhir.search.max_includes" => Some(ConfigKey::SearchMaxIncludes),
+            "fhir.search.max_wildcard_include_params" => {
+                Some(ConfigKey::SearchMaxWildcardIncludeParams)
+            }
 
             "fhir.interactions.instance.read" => Some(ConfigKey::InteractionsInstanceRead),
             "fhir.interactions.instance.vread" => Some(ConfigKey::InteractionsInstanceVread),
@@ -595,9 +615,11 @@ impl ConfigKey {
             // Search
             ConfigKey::SearchDefaultCount,
             ConfigKey::SearchMaxCount,
+            ConfigKey::SearchMaxPageSize,
             ConfigKey::SearchMaxTotalResults,
             ConfigKey::SearchMaxIncludeDepth,
             ConfigKey::SearchMaxIncludes,
+            ConfigKey::SearchMaxWildcardIncludeParams,
             // Interactions - Instance
             ConfigKey::InteractionsInstanceRead,
             ConfigKey::InteractionsInstanceVread,
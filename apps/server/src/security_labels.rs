@@ -0,0 +1,262 @@
+//! Security-label (`meta.security`) access filtering.
+//!
+//! A [`SecurityLabelPolicy`] turns the authenticated principal into a
+//! [`SecurityLabelScope`]: the labels (`system` + `code`) the request may see. A resource is
+//! visible when every label it carries is in scope, so unlabeled resources are always visible.
+//!
+//! [`security_label_middleware`] resolves the scope once per FHIR request and makes it the
+//! task's [`current_scope`]. The resource store and the search engine enforce it on every read
+//! path (read, vread, history, search, includes, batch entries and operations); background jobs
+//! carry the scope of the request that started them and re-enter it with [`with_scope`].
+//! Hidden resources are reported as not found and cannot be overwritten.
+
+use crate::auth::Principal;
+use crate::config::SecurityLabelsAuthConfig;
+use crate::state::AppState;
+use axum::{extract::State, response::Response};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::future::Future;
+use std::sync::Arc;
+
+tokio::task_local! {
+    static REQUEST_SCOPE: Option<SecurityLabelScope>;
+}
+
+/// A security label, compared on both `system` and `code`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SecurityLabel {
+    pub system: Option<String>,
+    pub code: String,
+}
+
+impl SecurityLabel {
+    /// Parse a granted label: `system|code`, `|code` (no system), or a bare `code` in
+    /// `default_system`.
+    pub fn parse(value: &str, default_system: &str) -> Option<Self> {
+        let (system, code) = match value.split_once('|') {
+            Some((system, code)) => ((!system.is_empty()).then(|| system.to_string()), code),
+            None => ((!default_system.is_empty()).then(|| default_system.to_string()), value),
+        };
+        (!code.is_empty()).then(|| Self {
+            system,
+            code: code.to_string(),
+        })
+    }
+
+    /// `system|code` token form, as matched in SQL by [`visible_sql`].
+    pub fn token(&self) -> String {
+        format!("{}|{}", self.system.as_deref().unwrap_or(""), self.code)
+    }
+
+    fn matches(&self, label: &JsonValue) -> bool {
+        label.get("code").and_then(|c| c.as_str()) == Some(self.code.as_str())
+            && label.get("system").and_then(|s| s.as_str()) == self.system.as_deref()
+    }
+}
+
+/// Security labels a request is allowed to see.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SecurityLabelScope {
+    pub allowed: Vec<SecurityLabel>,
+}
+
+impl SecurityLabelScope {
+    pub fn new(allowed: Vec<SecurityLabel>) -> Self {
+        Self { allowed }
+    }
+
+    /// Whether every `meta.security` label of `resource` is in scope.
+    pub fn permits(&self, resource: &JsonValue) -> bool {
+        let Some(labels) = resource
+            .get("meta")
+            .and_then(|m| m.get("security"))
+            .and_then(|s| s.as_array())
+        else {
+            return true;
+        };
+        labels
+            .iter()
+            .all(|label| self.allowed.iter().any(|allowed| allowed.matches(label)))
+    }
+
+    /// Granted labels as sorted `system|code` tokens.
+    pub fn tokens(&self) -> Vec<String> {
+        let mut tokens: Vec<String> = self.allowed.iter().map(SecurityLabel::token).collect();
+        tokens.sort();
+        tokens.dedup();
+        tokens
+    }
+}
+
+/// Decides which security labels a request may see.
+pub trait SecurityLabelPolicy: Send + Sync {
+    /// Scope for a request; `None` means unrestricted.
+    fn scope_for(&self, principal: Option<&Principal>) -> Option<SecurityLabelScope>;
+}
+
+/// Policy used when security-label filtering is disabled.
+pub struct UnrestrictedPolicy;
+
+impl SecurityLabelPolicy for UnrestrictedPolicy {
+    fn scope_for(&self, _principal: Option<&Principal>) -> Option<SecurityLabelScope> {
+        None
+    }
+}
+
+/// Grants the labels listed in the principal's token claim (`auth.security_labels.claim`).
+///
+/// Bare codes in the claim belong to `auth.security_labels.default_system`. Anonymous
+/// requests get an empty scope and therefore only see unlabeled resources.
+pub struct ClaimSecurityLabelPolicy {
+    pub default_system: String,
+}
+
+impl SecurityLabelPolicy for ClaimSecurityLabelPolicy {
+    fn scope_for(&self, principal: Option<&Principal>) -> Option<SecurityLabelScope> {
+        Some(SecurityLabelScope::new(
+            principal
+                .map(|p| {
+                    p.security_labels
+                        .iter()
+                        .filter_map(|label| SecurityLabel::parse(label, &self.default_system))
+                        .collect()
+                })
+                .unwrap_or_default(),
+        ))
+    }
+}
+
+/// Policy for the configured `auth.security_labels` settings.
+pub fn policy_from_config(config: &SecurityLabelsAuthConfig) -> Arc<dyn SecurityLabelPolicy> {
+    if config.enabled {
+        Arc::new(ClaimSecurityLabelPolicy {
+            default_system: config.default_system.clone(),
+        })
+    } else {
+        Arc::new(UnrestrictedPolicy)
+    }
+}
+
+/// Middleware resolving the principal's scope and running the request within it.
+///
+/// Must run inside `auth_middleware` so the `Principal` extension is already attached.
+pub async fn security_label_middleware(
+    State(state): State<AppState>,
+    req: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> Response {
+    let scope = state
+        .security_label_policy
+        .scope_for(req.extensions().get::<Principal>());
+    with_scope(scope, next.run(req)).await
+}
+
+/// Run `fut` with `scope` as the current scope (`None` = unrestricted).
+pub async fn with_scope<F: Future>(scope: Option<SecurityLabelScope>, fut: F) -> F::Output {
+    REQUEST_SCOPE.scope(scope, fut).await
+}
+
+/// Scope of the current request or job; `None` (unrestricted) outside of one.
+pub fn current_scope() -> Option<SecurityLabelScope> {
+    REQUEST_SCOPE.try_with(|scope| scope.clone()).ok().flatten()
+}
+
+/// Whether `resource` is visible in the current scope.
+pub fn is_visible(resource: &JsonValue) -> bool {
+    REQUEST_SCOPE
+        .try_with(|scope| scope.as_ref().is_none_or(|scope| scope.permits(resource)))
+        .unwrap_or(true)
+}
+
+/// Tokens to bind for [`visible_sql`] in the current scope (`None` = unrestricted).
+pub(crate) fn current_scope_tokens() -> Option<Vec<String>> {
+    REQUEST_SCOPE
+        .try_with(|scope| scope.as_ref().map(SecurityLabelScope::tokens))
+        .ok()
+        .flatten()
+}
+
+/// SQL predicate: the resource JSON in `column` carries no `meta.security` label outside the
+/// `system|code` tokens bound as `$param` (a `TEXT[]`; NULL means unrestricted).
+pub(crate) fn visible_sql(column: &str, param: usize) -> String {
+    format!(
+        "(${param}::TEXT[] IS NULL OR NOT EXISTS (SELECT 1 FROM jsonb_array_elements(COALESCE({column}->'meta'->'security', '[]'::jsonb)) AS label WHERE NOT COALESCE(COALESCE(label->>'system', '') || '|' || (label->>'code') = ANY(${param}::TEXT[]), false)))"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const CONFIDENTIALITY: &str = "http://terminology.hl7.org/CodeSystem/v3-Confidentiality";
+
+    fn labeled(labels: &[(&str, &str)]) -> JsonValue {
+        let security: Vec<JsonValue> = labels
+            .iter()
+            .map(|(system, code)| json!({"system": system, "code": code}))
+            .collect();
+        json!({"resourceType": "Patient", "meta": {"security": security}})
+    }
+
+    fn scope(labels: &[&str]) -> SecurityLabelScope {
+        SecurityLabelScope::new(
+            labels
+                .iter()
+                .filter_map(|l| SecurityLabel::parse(l, CONFIDENTIALITY))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn scope_permits_only_granted_labels() {
+        let scope = scope(&["R"]);
+        assert!(scope.permits(&json!({"resourceType": "Patient"})));
+        assert!(scope.permits(&labeled(&[(CONFIDENTIALITY, "R")])));
+        assert!(!scope.permits(&labeled(&[(CONFIDENTIALITY, "HIGH")])));
+        assert!(!scope.permits(&labeled(&[
+            (CONFIDENTIALITY, "R"),
+            (CONFIDENTIALITY, "HIGH")
+        ])));
+    }
+
+    #[test]
+    fn labels_match_on_system_and_code() {
+        let scope = scope(&["R", "http://example.org/labels|R"]);
+        assert!(scope.permits(&labeled(&[("http://example.org/labels", "R")])));
+        assert!(!scope.permits(&labeled(&[("http://example.org/other", "R")])));
+        assert!(!scope.permits(&json!({"meta": {"security": [{"code": "R"}]}})));
+        assert!(self::scope(&["|R"]).permits(&json!({"meta": {"security": [{"code": "R"}]}})));
+    }
+
+    #[test]
+    fn claim_policy_restricts_anonymous_requests_to_unlabeled_resources() {
+        let policy = ClaimSecurityLabelPolicy {
+            default_system: CONFIDENTIALITY.to_string(),
+        };
+        let scope = policy.scope_for(None).unwrap();
+        assert!(scope.permits(&json!({"resourceType": "Patient"})));
+        assert!(!scope.permits(&labeled(&[(CONFIDENTIALITY, "R")])));
+        assert!(UnrestrictedPolicy.scope_for(None).is_none());
+    }
+
+    #[tokio::test]
+    async fn current_scope_is_task_local() {
+        let high = labeled(&[(CONFIDENTIALITY, "HIGH")]);
+        assert!(current_scope().is_none());
+        assert!(is_visible(&high));
+
+        let restricted = scope(&["R"]);
+        with_scope(Some(restricted.clone()), async {
+            assert_eq!(current_scope(), Some(restricted));
+            assert!(!is_visible(&high));
+            assert_eq!(
+                current_scope_tokens(),
+                Some(vec![format!("{}|R", CONFIDENTIALITY)])
+            );
+            with_scope(None, async { assert!(is_visible(&high)) }).await;
+        })
+        .await;
+    }
+}
@@ -0,0 +1,266 @@
+//! Asynchronous operation requests (`Prefer: respond-async`)
+//!
+//! Any operation invoked with `Prefer: respond-async` is recorded as an `async_operation`
+//! job and runs in the background instead of holding the request open. The kick-off
+//! returns 202 Accepted with a `Content-Location` pointing at
+//! `[base]/$async-status?_jobId=...`, which reports progress until the job completes and
+//! then returns the operation's response as a `batch-response` Bundle.
+//!
+//! Jobs are claimed and run by the API server, which owns the full operation executor: each
+//! kick-off drains the pending `async_operation` jobs, and server start-up resumes the ones left
+//! pending by a restart. Jobs a restart interrupted while running are failed instead of run
+//! again, since the operation may already have changed state. A job is recreated from its parameters alone, including the
+//! security-label scope and principal of the request that started it; only that principal
+//! may poll it. The outcome is stored as the job's final results, so the admin jobs API
+//! reports it as well. `$export` keeps the kick-off and status endpoints defined by the Bulk
+//! Data spec.
+//!
+//! See: https://hl7.org/fhir/async.html
+
+use crate::{
+    auth::Principal,
+    models::{OperationContext, OperationRequest, OperationResult},
+    queue::{Job, JobPriority, JobQueue},
+    security_labels::{self, SecurityLabelScope},
+    services::OperationExecutor,
+    Result,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value as JsonValue};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Job type name used for asynchronous operation requests.
+pub const ASYNC_OPERATION_JOB_TYPE: &str = "async_operation";
+
+/// Parameters of an `async_operation` job.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AsyncOperationParams {
+    /// Operation code, without the `$`.
+    pub operation: String,
+    /// Kick-off request URL.
+    pub request: String,
+    /// Input parameters after merging body and query string.
+    pub parameters: JsonValue,
+    /// Resource type of a type- or instance-level invocation.
+    #[serde(default)]
+    pub resource_type: Option<String>,
+    /// Resource id of an instance-level invocation.
+    #[serde(default)]
+    pub id: Option<String>,
+    /// FHIR base URL of the kick-off request, for the result location.
+    pub base_url: String,
+    /// Subject of the principal that started the request; only it may poll the status.
+    #[serde(default)]
+    pub principal: Option<String>,
+    /// Security-label scope the operation runs in.
+    #[serde(default)]
+    pub security_scope: Option<SecurityLabelScope>,
+}
+
+impl AsyncOperationParams {
+    /// Whether the request polling the status is the one that started the job.
+    pub fn is_visible_to(&self, principal: Option<&Principal>) -> bool {
+        self.principal.as_deref() == principal.map(|p| p.subject.as_str())
+            && self.security_scope == security_labels::current_scope()
+    }
+
+    fn operation_request(&self) -> Result<OperationRequest> {
+        let context = match (&self.resource_type, &self.id) {
+            (Some(rt), Some(id)) => OperationContext::Instance(rt.clone(), id.clone()),
+            (Some(rt), None) => OperationContext::Type(rt.clone()),
+            _ => OperationContext::System,
+        };
+        let parameters = serde_json::from_value(self.parameters.clone()).map_err(|e| {
+            crate::Error::Internal(format!("Invalid async operation parameters: {}", e))
+        })?;
+        Ok(OperationRequest {
+            operation_name: self.operation.clone(),
+            context,
+            parameters,
+        })
+    }
+}
+
+/// Final results of a completed `async_operation` job (stored as the job's progress data).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AsyncOperationResult {
+    /// Where the result can be fetched (the status endpoint).
+    pub location: String,
+    /// `batch-response` entry holding the operation's response, or its error outcome.
+    pub entry: JsonValue,
+}
+
+/// Enqueue an `async_operation` job for `request` and start running it.
+///
+/// `base_url` is the FHIR base the [`status_url`] is reported under.
+pub async fn kick_off(
+    job_queue: Arc<dyn JobQueue>,
+    executor: Arc<OperationExecutor>,
+    request: OperationRequest,
+    request_url: String,
+    base_url: String,
+    principal: Option<&Principal>,
+) -> Result<Uuid> {
+    let (resource_type, id) = match &request.context {
+        OperationContext::System => (None, None),
+        OperationContext::Type(rt) => (Some(rt.clone()), None),
+        OperationContext::Instance(rt, id) => (Some(rt.clone()), Some(id.clone())),
+    };
+    let params = AsyncOperationParams {
+        operation: request.operation_name,
+        request: request_url,
+        parameters: serde_json::to_value(&request.parameters).map_err(|e| {
+            crate::Error::Internal(format!("Failed to serialize operation parameters: {}", e))
+        })?,
+        resource_type,
+        id,
+        base_url,
+        principal: principal.map(|p| p.subject.clone()),
+        security_scope: security_labels::current_scope(),
+    };
+    let params = serde_json::to_value(params).map_err(|e| {
+        crate::Error::Internal(format!("Failed to serialize job parameters: {}", e))
+    })?;
+
+    let job_id = job_queue
+        .enqueue(
+            ASYNC_OPERATION_JOB_TYPE.to_string(),
+            params,
+            JobPriority::Normal,
+            None,
+        )
+        .await?;
+
+    tokio::spawn(run_pending(job_queue, executor));
+
+    Ok(job_id)
+}
+
+/// Recover `async_operation` jobs at server start-up.
+///
+/// Jobs left running by the previous process are failed, then the pending ones are run.
+pub async fn resume(job_queue: Arc<dyn JobQueue>, executor: Arc<OperationExecutor>) {
+    if let Err(e) = job_queue
+        .fail_running_jobs(
+            ASYNC_OPERATION_JOB_TYPE,
+            "Interrupted by a server restart".to_string(),
+        )
+        .await
+    {
+        tracing::error!("Failed to fail interrupted async operation jobs: {}", e);
+    }
+    run_pending(job_queue, executor).await;
+}
+
+/// Claim and run pending `async_operation` jobs until none is left.
+///
+/// Called after each kick-off and by [`resume`] at server start-up, so jobs enqueued before a
+/// restart are resumed.
+pub async fn run_pending(job_queue: Arc<dyn JobQueue>, executor: Arc<OperationExecutor>) {
+    let worker_id = format!("api-{}", Uuid::new_v4());
+    let job_types = [ASYNC_OPERATION_JOB_TYPE.to_string()];
+    loop {
+        match job_queue.dequeue(&job_types, &worker_id).await {
+            Ok(Some(job)) => run_job(job_queue.as_ref(), &executor, job).await,
+            Ok(None) => break,
+            Err(e) => {
+                tracing::error!("Failed to claim async operation job: {}", e);
+                break;
+            }
+        }
+    }
+}
+
+/// Status endpoint of an asynchronous request.
+pub fn status_url(base_url: &str, job_id: Uuid) -> String {
+    format!("{}/$async-status?_jobId={}", base_url, job_id)
+}
+
+/// Parse the job's parameters back into an async operation request.
+pub fn job_params(job: &Job) -> Result<AsyncOperationParams> {
+    serde_json::from_value(job.parameters.clone())
+        .map_err(|e| crate::Error::Internal(format!("Invalid async operation job: {}", e)))
+}
+
+async fn run_job(job_queue: &dyn JobQueue, executor: &OperationExecutor, job: Job) {
+    let job_id = job.id;
+    let params = match job_params(&job) {
+        Ok(params) => params,
+        Err(e) => {
+            if let Err(e) = job_queue.fail_job(job_id, e.to_string(), false).await {
+                tracing::error!("Failed to fail async operation job {}: {}", job_id, e);
+            }
+            return;
+        }
+    };
+
+    let entry = match params.operation_request() {
+        Ok(request) => {
+            match security_labels::with_scope(
+                params.security_scope.clone(),
+                executor.execute(request),
+            )
+            .await
+            {
+                Ok(result) => result_entry(result),
+                Err(e) => error_entry(&e),
+            }
+        }
+        Err(e) => error_entry(&e),
+    };
+    let location = status_url(&params.base_url, job_id);
+    let outcome = match serde_json::to_value(AsyncOperationResult { location, entry }) {
+        Ok(result) => job_queue.complete_job(job_id, Some(result)).await,
+        Err(e) => {
+            job_queue
+                .fail_job(job_id, format!("Failed to store result: {}", e), false)
+                .await
+        }
+    };
+    if let Err(e) = outcome {
+        tracing::error!(
+            "Failed to record result of async ${} job {}: {}",
+            params.operation,
+            job_id,
+            e
+        );
+    }
+}
+
+/// `batch-response` Bundle reporting a completed job's result
+pub fn response_bundle(result: &AsyncOperationResult) -> JsonValue {
+    json!({
+        "resourceType": "Bundle",
+        "type": "batch-response",
+        "entry": [result.entry],
+    })
+}
+
+fn result_entry(result: OperationResult) -> JsonValue {
+    let resource = match result {
+        OperationResult::NoContent => return json!({ "response": { "status": "204 No Content" } }),
+        OperationResult::Resource(resource) | OperationResult::OperationOutcome(resource) => {
+            resource
+        }
+        OperationResult::Parameters(params) => match serde_json::to_value(params) {
+            Ok(params) => params,
+            Err(e) => {
+                return error_entry(&crate::Error::Internal(format!(
+                    "Failed to serialize Parameters: {}",
+                    e
+                )))
+            }
+        },
+    };
+    json!({
+        "resource": resource,
+        "response": { "status": "200 OK" },
+    })
+}
+
+fn error_entry(err: &crate::Error) -> JsonValue {
+    serde_json::to_value(crate::services::batch::create_error_entry(None, err))
+        .unwrap_or_else(|_| json!({ "response": { "status": "500 Internal Server Error" } }))
+}
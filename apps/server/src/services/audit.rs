@@ -706,6 +706,7 @@ mod tests {
                 audience: None,
                 client_id: None,
                 patient: None,
+                security_labels: vec![],
             }),
             client_ip: Some("127.0.0.1".to_string()),
             user_agent: Some("ua".to_string()),
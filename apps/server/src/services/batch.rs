@@ -12,11 +12,13 @@ use crate::{
     models::UpdateParams,
     queue::{JobPriority, JobQueue},
     runtime_config::RuntimeConfigCache,
-    services::CrudService,
+    services::{
+        id_strategy::IdStrategy, narrative::NarrativeMode,
+        referential_integrity::ReferentialIntegrityMode, CrudService,
+    },
     Result,
 };
 use axum::http::StatusCode;
-use json_patch::PatchErrorKind;
 use serde_json::{json, Value as JsonValue};
 use std::{
     collections::{HashMap, HashSet},
@@ -61,7 +63,9 @@ pub struct BatchService {
     allow_update_create: bool,
     hard_delete: bool,
     runtime_config_cache: Option<Arc<RuntimeConfigCache>>,
-    referential_integrity_mode: String,
+    referential_integrity_mode: ReferentialIntegrityMode,
+    id_strategy: IdStrategy,
+    generate_narrative: NarrativeMode,
     transaction_recorder: Option<TransactionRecorder>,
 }
 
@@ -83,7 +87,9 @@ impl BatchService {
             allow_update_create,
             hard_delete,
             runtime_config_cache: None,
-            referential_integrity_mode: "lenient".to_string(),
+            referential_integrity_mode: ReferentialIntegrityMode::Off,
+            id_strategy: IdStrategy::Uuid,
+            generate_narrative: NarrativeMode::Off,
             transaction_recorder: None,
         }
     }
@@ -92,10 +98,18 @@ impl BatchService {
         self.transaction_recorder = Some(recorder);
     }
 
-    pub fn set_referential_integrity_mode(&mut self, mode: String) {
+    pub fn set_referential_integrity_mode(&mut self, mode: ReferentialIntegrityMode) {
         self.referential_integrity_mode = mode;
     }
 
+    pub fn set_id_strategy(&mut self, strategy: IdStrategy) {
+        self.id_strategy = strategy;
+    }
+
+    pub fn set_generate_narrative(&mut self, mode: NarrativeMode) {
+        self.generate_narrative = mode;
+    }
+
     pub fn new_with_runtime_config(
         store: PostgresResourceStore,
         hooks: Vec<Arc<dyn ResourceHook>>,
@@ -265,7 +279,9 @@ impl BatchService {
                 self.hard_delete,
             )
         };
-        crud.set_referential_integrity_mode(self.referential_integrity_mode.clone());
+        crud.set_referential_integrity_mode(self.referential_integrity_mode);
+        crud.set_id_strategy(self.id_strategy);
+        crud.set_generate_narrative(self.generate_narrative);
 
         for index in ordered {
             if let Some(err) = pre_errors.get(&index) {
@@ -657,23 +673,12 @@ impl BatchService {
                     }
                 }
 
-                let mut patched = current.resource.clone();
-                json_patch::patch(&mut patched, &patch.0).map_err(|e| match e.kind {
-                    PatchErrorKind::TestFailed => crate::Error::UnprocessableEntity(e.to_string()),
-                    _ => crate::Error::InvalidResource(e.to_string()),
-                })?;
-
-                let obj = patched.as_object_mut().ok_or_else(|| {
-                    crate::Error::InvalidResource(
-                        "Patched resource must be a JSON object".to_string(),
-                    )
-                })?;
-                obj.insert(
-                    "resourceType".to_string(),
-                    serde_json::json!(&resource_type),
-                );
-                obj.insert("id".to_string(), serde_json::json!(&resource_id));
-                obj.remove("text");
+                let mut patched = crate::services::crud::apply_json_patch(
+                    &current.resource,
+                    &patch,
+                    &resource_type,
+                    &resource_id,
+                )?;
 
                 crate::services::conditional_references::resolve_conditional_references(
                     self.search_engine.as_ref(),
@@ -1133,6 +1138,7 @@ fn status_to_fhir_code(status: StatusCode) -> &'static str {
         StatusCode::PRECONDITION_FAILED => "conflict",
         StatusCode::UNPROCESSABLE_ENTITY => "processing",
         StatusCode::UNSUPPORTED_MEDIA_TYPE => "not-supported",
+        StatusCode::SERVICE_UNAVAILABLE => "timeout",
         _ => "exception",
     }
 }
@@ -1153,9 +1159,11 @@ fn error_status(err: &crate::Error) -> StatusCode {
         crate::Error::MethodNotAllowed(_) => StatusCode::METHOD_NOT_ALLOWED,
         crate::Error::Search(_) => StatusCode::BAD_REQUEST,
         crate::Error::UnsupportedMediaType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+        crate::Error::NotAcceptable(_) => StatusCode::NOT_ACCEPTABLE,
         crate::Error::UnprocessableEntity(_) => StatusCode::UNPROCESSABLE_ENTITY,
         crate::Error::NotImplemented(_) => StatusCode::NOT_IMPLEMENTED,
         crate::Error::TooCostly(_) => StatusCode::FORBIDDEN,
+        crate::Error::Database(_) if err.is_statement_timeout() => StatusCode::SERVICE_UNAVAILABLE,
         crate::Error::Database(_)
         | crate::Error::JobQueue(_)
         | crate::Error::FhirContext(_)
@@ -1166,7 +1174,8 @@ fn error_status(err: &crate::Error) -> StatusCode {
     }
 }
 
-fn create_error_entry(full_url: Option<&str>, err: &crate::Error) -> BundleEntry {
+/// Bundle entry reporting `err` as an OperationOutcome in `response.outcome`
+pub(crate) fn create_error_entry(full_url: Option<&str>, err: &crate::Error) -> BundleEntry {
     let status = error_status(err);
     let outcome = json!({
         "resourceType": "OperationOutcome",
@@ -0,0 +1,220 @@
+//! Bulk Data export (`$export`)
+//!
+//! Kick-off requests only enqueue a `bulk_export` job; the job writes one NDJSON file per
+//! resource type into `<output_dir>/<job_id>/` and stores the completion manifest as the
+//! job's final results, which the status endpoint then reports.
+
+use crate::{
+    db::PostgresResourceStore,
+    queue::JobQueue,
+    security_labels::{self, SecurityLabelScope},
+    Result,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::io::AsyncWriteExt;
+use uuid::Uuid;
+
+/// Job type name used for export jobs.
+pub const BULK_EXPORT_JOB_TYPE: &str = "bulk_export";
+
+/// Page size used when streaming resources out of the store.
+const EXPORT_BATCH_SIZE: i64 = 500;
+
+/// Parameters of a `bulk_export` job.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkExportParams {
+    /// Resource types to export (from `_type` or the type-level path).
+    /// Empty means every resource type currently stored.
+    pub resource_types: Vec<String>,
+    /// `_since`: only export resources updated at or after this instant.
+    pub since: Option<DateTime<Utc>>,
+    /// Root directory for export output; files go to `<output_dir>/<job_id>/`.
+    pub output_dir: String,
+    /// Kick-off request URL, echoed in the manifest.
+    pub request: String,
+    /// Security-label scope of the kick-off request; only resources visible in it are
+    /// exported. `None` exports everything.
+    #[serde(default)]
+    pub security_scope: Option<SecurityLabelScope>,
+}
+
+/// One NDJSON file produced by an export job.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkExportOutput {
+    #[serde(rename = "type")]
+    pub resource_type: String,
+    pub file_name: String,
+    pub count: usize,
+}
+
+/// Final results of a completed export job (stored as the job's progress data).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkExportResult {
+    pub transaction_time: DateTime<Utc>,
+    pub request: String,
+    pub output: Vec<BulkExportOutput>,
+}
+
+/// Directory holding the files of one export job.
+pub fn job_output_dir(output_dir: &str, job_id: Uuid) -> PathBuf {
+    Path::new(output_dir).join(job_id.to_string())
+}
+
+/// Writes NDJSON export files for `bulk_export` jobs.
+pub struct BulkExportService {
+    store: PostgresResourceStore,
+}
+
+impl BulkExportService {
+    pub fn new(store: PostgresResourceStore) -> Self {
+        Self { store }
+    }
+
+    /// Run an export job to completion.
+    ///
+    /// Types without matching resources produce no file. Progress is reported per resource
+    /// type; a cancelled job stops between types and leaves partial output behind.
+    pub async fn run(&self, job_queue: &dyn JobQueue, job_id: Uuid) -> Result<()> {
+        let job = job_queue
+            .get_job(job_id)
+            .await?
+            .ok_or_else(|| crate::Error::JobQueue(format!("Export job {} not found", job_id)))?;
+        let params: BulkExportParams = serde_json::from_value(job.parameters).map_err(|e| {
+            crate::Error::Internal(format!("Failed to parse export parameters: {}", e))
+        })?;
+
+        let scope = params.security_scope.clone();
+        security_labels::with_scope(scope, self.export(job_queue, job_id, params)).await
+    }
+
+    async fn export(
+        &self,
+        job_queue: &dyn JobQueue,
+        job_id: Uuid,
+        params: BulkExportParams,
+    ) -> Result<()> {
+        let transaction_time = Utc::now();
+        let dir = job_output_dir(&params.output_dir, job_id);
+        tokio::fs::create_dir_all(&dir).await.map_err(|e| {
+            crate::Error::Internal(format!(
+                "Failed to create export directory {}: {}",
+                dir.display(),
+                e
+            ))
+        })?;
+
+        let resource_types = if params.resource_types.is_empty() {
+            self.store.list_distinct_resource_types().await?
+        } else {
+            params.resource_types.clone()
+        };
+
+        let mut output = Vec::new();
+        let mut exported: usize = 0;
+        for resource_type in &resource_types {
+            if job_queue.is_cancelled(job_id).await? {
+                tracing::info!(
+                    "Export job {} cancelled after {} resources",
+                    job_id,
+                    exported
+                );
+                return Ok(());
+            }
+
+            if let Some(file) = self.export_type(&dir, resource_type, params.since).await? {
+                exported += file.count;
+                output.push(file);
+            }
+
+            job_queue
+                .update_progress(job_id, exported as i32, None, None)
+                .await?;
+        }
+
+        let result = BulkExportResult {
+            transaction_time,
+            request: params.request,
+            output,
+        };
+        let result = serde_json::to_value(result).map_err(|e| {
+            crate::Error::Internal(format!("Failed to serialize export result: {}", e))
+        })?;
+
+        job_queue
+            .update_progress(job_id, exported as i32, Some(exported as i32), None)
+            .await?;
+        job_queue.complete_job(job_id, Some(result)).await
+    }
+
+    async fn export_type(
+        &self,
+        dir: &Path,
+        resource_type: &str,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Option<BulkExportOutput>> {
+        let file_name = format!("{}.ndjson", resource_type);
+        let path = dir.join(&file_name);
+        let io_err = |e: std::io::Error| {
+            crate::Error::Internal(format!(
+                "Failed to write export file {}: {}",
+                path.display(),
+                e
+            ))
+        };
+
+        let mut writer: Option<tokio::io::BufWriter<tokio::fs::File>> = None;
+        let mut count: usize = 0;
+        let mut after_id: Option<String> = None;
+
+        loop {
+            let page = self
+                .store
+                .list_resources_for_export(
+                    resource_type,
+                    since,
+                    after_id.as_deref(),
+                    EXPORT_BATCH_SIZE,
+                )
+                .await?;
+
+            if page.is_empty() {
+                break;
+            }
+
+            if writer.is_none() {
+                let file = tokio::fs::File::create(&path).await.map_err(io_err)?;
+                writer = Some(tokio::io::BufWriter::new(file));
+            }
+            let out = writer.as_mut().expect("writer initialized above");
+
+            for resource in &page {
+                let mut line = serde_json::to_vec(&resource.resource).map_err(|e| {
+                    crate::Error::Internal(format!("Failed to serialize resource: {}", e))
+                })?;
+                line.push(b'\n');
+                out.write_all(&line).await.map_err(io_err)?;
+            }
+            count += page.len();
+
+            if (page.len() as i64) < EXPORT_BATCH_SIZE {
+                break;
+            }
+            after_id = page.last().map(|r| r.id.clone());
+        }
+
+        let Some(mut out) = writer else {
+            return Ok(None);
+        };
+        out.flush().await.map_err(io_err)?;
+
+        Ok(Some(BulkExportOutput {
+            resource_type: resource_type.to_string(),
+            file_name,
+            count,
+        }))
+    }
+}
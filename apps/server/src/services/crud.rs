@@ -6,8 +6,8 @@ use crate::{
     db::{PostgresResourceStore, ResourceStore},
     hooks::ResourceHook,
     models::{
-        is_known_resource_type, CreateParams, HistoryEntry, HistoryMethod, HistoryResult, Resource,
-        ResourceOperation, ResourceResult, UpdateParams,
+        is_known_resource_type, CreateParams, HistoryCursor, HistoryEntry, HistoryMethod,
+        HistoryResult, Resource, ResourceOperation, ResourceResult, UpdateParams,
     },
     queue::{JobPriority, JobQueue},
     runtime_config::{ConfigKey, RuntimeConfigCache},
@@ -18,7 +18,10 @@ use chrono::Utc;
 use json_patch::PatchErrorKind;
 use serde_json::Value as JsonValue;
 use std::sync::Arc;
-use uuid::Uuid;
+
+use super::id_strategy::IdStrategy;
+use super::narrative::NarrativeMode;
+use super::referential_integrity::{report_missing_references, ReferentialIntegrityMode};
 
 pub struct CrudService {
     store: PostgresResourceStore,
@@ -28,7 +31,9 @@ pub struct CrudService {
     allow_update_create: bool,
     hard_delete: bool,
     runtime_config_cache: Option<Arc<RuntimeConfigCache>>,
-    referential_integrity_mode: String,
+    referential_integrity_mode: ReferentialIntegrityMode,
+    id_strategy: IdStrategy,
+    generate_narrative: NarrativeMode,
 }
 
 impl CrudService {
@@ -49,7 +54,9 @@ impl CrudService {
             allow_update_create,
             hard_delete,
             runtime_config_cache: None,
-            referential_integrity_mode: "lenient".to_string(),
+            referential_integrity_mode: ReferentialIntegrityMode::Off,
+            id_strategy: IdStrategy::Uuid,
+            generate_narrative: NarrativeMode::Off,
         }
     }
 
@@ -82,7 +89,9 @@ impl CrudService {
             allow_update_create,
             hard_delete,
             runtime_config_cache: None,
-            referential_integrity_mode: "lenient".to_string(),
+            referential_integrity_mode: ReferentialIntegrityMode::Off,
+            id_strategy: IdStrategy::Uuid,
+            generate_narrative: NarrativeMode::Off,
         }
     }
 
@@ -102,7 +111,9 @@ impl CrudService {
             allow_update_create,
             hard_delete,
             runtime_config_cache: None,
-            referential_integrity_mode: "lenient".to_string(),
+            referential_integrity_mode: ReferentialIntegrityMode::Off,
+            id_strategy: IdStrategy::Uuid,
+            generate_narrative: NarrativeMode::Off,
         }
     }
 
@@ -127,10 +138,18 @@ impl CrudService {
         service
     }
 
-    pub fn set_referential_integrity_mode(&mut self, mode: String) {
+    pub fn set_referential_integrity_mode(&mut self, mode: ReferentialIntegrityMode) {
         self.referential_integrity_mode = mode;
     }
 
+    pub fn set_id_strategy(&mut self, strategy: IdStrategy) {
+        self.id_strategy = strategy;
+    }
+
+    pub fn set_generate_narrative(&mut self, mode: NarrativeMode) {
+        self.generate_narrative = mode;
+    }
+
     async fn allow_update_create_effective(&self) -> bool {
         if let Some(cache) = &self.runtime_config_cache {
             return cache.get(ConfigKey::BehaviorAllowUpdateCreate).await;
@@ -148,9 +167,11 @@ impl CrudService {
     /// Create a new resource (POST /{resourceType})
     ///
     /// Spec-compliant behavior:
-    /// - Generates server-assigned ID (UUID)
+    /// - Assigns the ID according to `fhir.id_strategy` (UUID by default)
     /// - Populates meta.versionId = 1
     /// - Populates meta.lastUpdated
+    /// - Adds a generated narrative when `fhir.resources.generate_narrative` asks for one
+    ///   and the resource has none
     ///
     /// NOTE: Conditional create (If-None-Exist) should be handled at the handler level
     /// using SearchEngine, not in this service method. The service layer doesn't have
@@ -178,14 +199,18 @@ impl CrudService {
             }
         }
 
-        // Generate server-assigned ID
-        let id = Uuid::new_v4().to_string();
+        let id = self
+            .id_strategy
+            .assign_id(&self.store, resource_type, &resource)
+            .await?;
 
         // Populate meta
         self.populate_meta(&mut resource, &id, 1, Utc::now());
 
-        // Referential integrity check (strict mode)
-        if self.is_strict_referential_integrity() {
+        self.generate_narrative.apply(&mut resource);
+
+        // Referential integrity check (logical/strict mode)
+        if self.checks_references_on_write() {
             self.validate_references(&resource).await?;
         }
 
@@ -358,8 +383,8 @@ impl CrudService {
             }
         };
 
-        // Referential integrity check (strict mode)
-        if self.is_strict_referential_integrity() {
+        // Referential integrity check (logical/strict mode)
+        if self.checks_references_on_write() {
             self.validate_references(&resource).await?;
         }
 
@@ -410,29 +435,14 @@ impl CrudService {
             }
         }
 
-        let mut patched = current.resource.clone();
-        json_patch::patch(&mut patched, &patch.0).map_err(|e| match e.kind {
-            PatchErrorKind::TestFailed => Error::UnprocessableEntity(e.to_string()),
-            _ => Error::InvalidResource(e.to_string()),
-        })?;
-
-        // Prevent changing the resource identity via PATCH.
-        let obj = patched.as_object_mut().ok_or_else(|| {
-            Error::InvalidResource("Patched resource must be a JSON object".to_string())
-        })?;
-        obj.insert("resourceType".to_string(), serde_json::json!(resource_type));
-        obj.insert("id".to_string(), serde_json::json!(id));
-        // Narrative safety: PATCH changes data without updating narrative.
-        // To ensure the narrative is not clinically unsafe, drop it after applying the patch.
-        // (FHIR spec allows servers to delete narrative if they cannot safely maintain it.)
-        obj.remove("text");
+        let mut patched = apply_json_patch(&current.resource, &patch, resource_type, id)?;
 
         // Update-as-create is not allowed for PATCH: we already resolved `current`.
         let new_version = current.version_id + 1;
         self.populate_meta(&mut patched, id, new_version, Utc::now());
 
-        // Referential integrity check (strict mode)
-        if self.is_strict_referential_integrity() {
+        // Referential integrity check (logical/strict mode)
+        if self.checks_references_on_write() {
             self.validate_references(&patched).await?;
         }
 
@@ -565,6 +575,7 @@ impl CrudService {
     /// - Returns all versions (newest first)
     /// - Includes deleted versions
     /// - Supports _count, _since and _at parameters
+    /// - Pages with `cursor` (the previous page's `next_cursor`)
     pub async fn resource_history(
         &self,
         resource_type: &str,
@@ -573,11 +584,12 @@ impl CrudService {
         since: Option<chrono::DateTime<chrono::Utc>>,
         at: Option<chrono::DateTime<chrono::Utc>>,
         sort_ascending: bool,
+        cursor: Option<&HistoryCursor>,
     ) -> Result<HistoryResult> {
         self.validate_resource_type_name(resource_type)?;
 
         self.store
-            .history(resource_type, id, count, since, at, sort_ascending)
+            .history(resource_type, id, count, since, at, sort_ascending, cursor)
             .await
     }
 
@@ -586,6 +598,7 @@ impl CrudService {
     /// Spec-compliant behavior:
     /// - Includes all versions of all resources of the given type (including deletions)
     /// - Supports `_count`, `_since`, `_at`, and `_sort` (via handler validation)
+    /// - Pages with `cursor` (the previous page's `next_cursor`)
     pub async fn type_history(
         &self,
         resource_type: &str,
@@ -593,12 +606,13 @@ impl CrudService {
         since: Option<chrono::DateTime<chrono::Utc>>,
         at: Option<chrono::DateTime<chrono::Utc>>,
         sort_ascending: bool,
+        cursor: Option<&HistoryCursor>,
     ) -> Result<HistoryResult> {
         self.validate_resource_type_name(resource_type)?;
 
         let resources = self
             .store
-            .history_type_resources(resource_type, count, since, at, sort_ascending)
+            .history_type_resources(resource_type, count, since, at, sort_ascending, cursor)
             .await?;
 
         let entries = resources
@@ -615,10 +629,7 @@ impl CrudService {
             })
             .collect();
 
-        Ok(HistoryResult {
-            entries,
-            total: None,
-        })
+        Ok(HistoryResult::paginate(entries, count, None))
     }
 
     /// Get system-wide history (GET /_history)
@@ -626,16 +637,24 @@ impl CrudService {
     /// Spec-compliant behavior:
     /// - Includes all versions of all resources (including deletions)
     /// - Supports `_count`, `_since`, `_at`, and `_sort` (via handler validation)
+    /// - A non-empty `types` restricts the history to those resource types (`_type`)
+    /// - Pages with `cursor` (the previous page's `next_cursor`)
     pub async fn system_history(
         &self,
+        types: &[String],
         count: Option<i32>,
         since: Option<chrono::DateTime<chrono::Utc>>,
         at: Option<chrono::DateTime<chrono::Utc>>,
         sort_ascending: bool,
+        cursor: Option<&HistoryCursor>,
     ) -> Result<HistoryResult> {
+        for resource_type in types {
+            self.validate_resource_type_name(resource_type)?;
+        }
+
         let resources = self
             .store
-            .history_system_resources(count, since, at, sort_ascending)
+            .history_system_resources(types, count, since, at, sort_ascending, cursor)
             .await?;
 
         let entries = resources
@@ -652,14 +671,16 @@ impl CrudService {
             })
             .collect();
 
-        Ok(HistoryResult {
-            entries,
-            total: None,
-        })
+        Ok(HistoryResult::paginate(entries, count, None))
     }
 
     fn is_strict_referential_integrity(&self) -> bool {
-        self.referential_integrity_mode == "strict"
+        self.referential_integrity_mode == ReferentialIntegrityMode::Strict
+    }
+
+    /// Whether writes check their references (logical and strict modes).
+    fn checks_references_on_write(&self) -> bool {
+        self.referential_integrity_mode != ReferentialIntegrityMode::Off
     }
 
     /// Validate that all relative references in the resource point to existing resources.
@@ -695,14 +716,7 @@ impl CrudService {
             .map(|(rt, id)| format!("{}/{}", rt, id))
             .collect();
 
-        if !missing.is_empty() {
-            return Err(Error::BusinessRule(format!(
-                "Referential integrity violation: the following referenced resources do not exist: {}",
-                missing.join(", ")
-            )));
-        }
-
-        Ok(())
+        report_missing_references(self.referential_integrity_mode, resource, &missing)
     }
 
     /// Check that no other resources reference this resource before deletion.
@@ -861,3 +875,41 @@ fn validate_version_match(resource: &Resource, expected_version: i32) -> Result<
     }
     Ok(())
 }
+
+/// Apply a JSON Patch to a resource's current representation
+///
+/// The patch must not change the resource identity: a result whose `resourceType`
+/// or `id` differs from the target is rejected with 400. The narrative is dropped
+/// because PATCH changes data without updating it, which could leave it clinically unsafe.
+pub fn apply_json_patch(
+    current: &JsonValue,
+    patch: &json_patch::Patch,
+    resource_type: &str,
+    id: &str,
+) -> Result<JsonValue> {
+    let mut patched = current.clone();
+    json_patch::patch(&mut patched, &patch.0).map_err(|e| match e.kind {
+        PatchErrorKind::TestFailed => Error::UnprocessableEntity(e.to_string()),
+        _ => Error::InvalidResource(e.to_string()),
+    })?;
+
+    let obj = patched.as_object_mut().ok_or_else(|| {
+        Error::InvalidResource("Patched resource must be a JSON object".to_string())
+    })?;
+
+    if obj.get("resourceType").and_then(|v| v.as_str()) != Some(resource_type) {
+        return Err(Error::InvalidResource(format!(
+            "PATCH must not change resourceType (expected '{}')",
+            resource_type
+        )));
+    }
+    if obj.get("id").and_then(|v| v.as_str()) != Some(id) {
+        return Err(Error::InvalidResource(format!(
+            "PATCH must not change resource id (expected '{}')",
+            id
+        )));
+    }
+
+    obj.remove("text");
+    Ok(patched)
+}
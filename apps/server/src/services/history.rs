@@ -551,6 +551,7 @@ fn error_status(err: &crate::Error) -> StatusCode {
         crate::Error::MethodNotAllowed(_) => StatusCode::METHOD_NOT_ALLOWED,
         crate::Error::Search(_) => StatusCode::BAD_REQUEST,
         crate::Error::UnsupportedMediaType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+        crate::Error::NotAcceptable(_) => StatusCode::NOT_ACCEPTABLE,
         crate::Error::UnprocessableEntity(_) => StatusCode::UNPROCESSABLE_ENTITY,
         crate::Error::NotImplemented(_) => StatusCode::NOT_IMPLEMENTED,
         crate::Error::TooCostly(_) => StatusCode::FORBIDDEN,
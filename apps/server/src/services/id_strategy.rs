@@ -0,0 +1,54 @@
+//! Resource id assignment on create (`fhir.id_strategy`)
+//!
+//! Used by CrudService when a resource is created via POST.
+
+use crate::{db::search::engine::is_valid_fhir_logical_id, db::PostgresResourceStore, Result};
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+use uuid::Uuid;
+
+/// How ids are assigned to resources created via POST.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum IdStrategy {
+    /// Random UUIDs.
+    #[default]
+    Uuid,
+    /// Per-type counter: `1`, `2`, `3`, ...
+    Sequential,
+    /// Keep the client-supplied `id` when present, otherwise generate a UUID.
+    ClientAllowed,
+}
+
+impl IdStrategy {
+    /// Pick the id for a new `resource_type` resource.
+    ///
+    /// Under `ClientAllowed` a client id must be a valid FHIR logical id. Whether it is free is
+    /// left to the store's create, which rejects ids already in use with 409.
+    pub(crate) async fn assign_id(
+        self,
+        store: &PostgresResourceStore,
+        resource_type: &str,
+        resource: &JsonValue,
+    ) -> Result<String> {
+        match self {
+            Self::Uuid => Ok(Uuid::new_v4().to_string()),
+            Self::Sequential => store.next_sequential_id(resource_type).await,
+            Self::ClientAllowed => {
+                let Some(client_id) = resource.get("id") else {
+                    return Ok(Uuid::new_v4().to_string());
+                };
+                let client_id = client_id.as_str().ok_or_else(|| {
+                    crate::Error::InvalidResource("Resource id must be a string".to_string())
+                })?;
+                if !is_valid_fhir_logical_id(client_id) {
+                    return Err(crate::Error::InvalidResource(format!(
+                        "Invalid resource id '{}': expected 1-64 characters of [A-Za-z0-9-.]",
+                        client_id
+                    )));
+                }
+                Ok(client_id.to_string())
+            }
+        }
+    }
+}
@@ -54,6 +54,7 @@ impl IndexingService {
                         base_type: Some(resource.resource_type.clone()),
                         strict: false,
                         infer_base_type: false,
+                        resource_resolver: None,
                     },
                 )
                 .map_err(|e| crate::Error::FhirPath(e.to_string()))?;
@@ -80,6 +81,7 @@ impl IndexingService {
                                 base_type: None,
                                 strict: false,
                                 infer_base_type: false,
+                                resource_resolver: None,
                             },
                         )
                         .map_err(|e| crate::Error::FhirPath(e.to_string()))?;
@@ -1,5 +1,6 @@
 //! Indexing service - manages search parameter indexing.
 
+use crate::db::search::parameter_lookup::META_SEARCH_PARAMS;
 use crate::models::Resource;
 use crate::{db::IndexingRepository, Result};
 use sqlx::{PgPool, Row};
@@ -174,6 +175,50 @@ impl IndexingService {
         }
     }
 
+    /// Clear the `indexed_from` watermark of search parameters covered by a finished reindex.
+    ///
+    /// Only watermarks set before `started_at` are cleared: parameters created or changed
+    /// while the reindex ran may have missed resources it had already visited. A type-level
+    /// reindex clears the watermarks of that type's own parameters and records the type in
+    /// `indexed_types` for Resource and DomainResource parameters, whose watermark still
+    /// applies to the other types; `None` covers every parameter.
+    pub async fn mark_search_parameters_indexed(
+        &self,
+        resource_type: Option<&str>,
+        started_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            UPDATE search_parameters
+            SET indexed_from = CASE
+                    WHEN $2::TEXT IS NULL OR resource_type = $2 THEN NULL
+                    ELSE indexed_from
+                END,
+                indexed_types = CASE
+                    WHEN $2::TEXT IS NULL OR resource_type = $2 THEN '{}'
+                    ELSE array_append(indexed_types, $2)
+                END
+            WHERE indexed_from IS NOT NULL
+              AND indexed_from <= $1
+              AND (
+                  $2::TEXT IS NULL
+                  OR resource_type = $2
+                  OR (
+                      resource_type IN ('Resource', 'DomainResource')
+                      AND NOT ($2 = ANY(indexed_types))
+                  )
+              )
+            "#,
+        )
+        .bind(started_at)
+        .bind(resource_type)
+        .execute(&self.pool)
+        .await
+        .map_err(crate::Error::Database)?;
+
+        Ok(result.rows_affected())
+    }
+
     /// Seed mapping from transaction `Bundle.entry.fullUrl` to resolved identity (`ResourceType/id`).
     ///
     /// This is used as an additional fallback for `resolve()` during indexing when resources
@@ -187,6 +232,40 @@ impl IndexingService {
 
     /// Index a single resource
     pub async fn index_resource(&self, resource: &Resource) -> Result<()> {
+        let Some(search_params) = self.prepare_resource_index(resource).await? else {
+            return Ok(());
+        };
+
+        let mut tx = self.pool.begin().await.map_err(crate::Error::Database)?;
+        self.write_resource_index(&mut tx, resource, &search_params)
+            .await?;
+        tx.commit().await.map_err(crate::Error::Database)?;
+        Ok(())
+    }
+
+    /// Index a single resource inside a transaction owned by the caller.
+    ///
+    /// Used by in-place writes (`$meta-add` / `$meta-delete`) so the index rows commit or roll
+    /// back together with the resource change instead of lagging behind it.
+    pub async fn index_resource_in_transaction(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        resource: &Resource,
+    ) -> Result<()> {
+        let Some(search_params) = self.prepare_resource_index(resource).await? else {
+            return Ok(());
+        };
+        self.write_resource_index(tx, resource, &search_params)
+            .await
+    }
+
+    /// Fetch the parameters to index for `resource` and pre-warm `resolve()` lookups.
+    ///
+    /// Returns `None` when there is nothing to index for the resource type.
+    async fn prepare_resource_index(
+        &self,
+        resource: &Resource,
+    ) -> Result<Option<Vec<SearchParameter>>> {
         let search_params = self
             .fetch_search_parameters(&resource.resource_type)
             .await?;
@@ -197,18 +276,12 @@ impl IndexingService {
         );
 
         if search_params.is_empty() && !needs_membership_indexes {
-            return Ok(());
+            return Ok(None);
         }
 
-        let needs_resolve = search_params.iter().any(|p| {
-            p.expression
-                .as_deref()
-                .is_some_and(|expr| expr.contains("resolve("))
-        });
-
         // Pre-warm reference resolution cache if any parameters for this type use `resolve()`.
-        // This runs outside of the write transaction to avoid deadlocks on small pools.
-        if needs_resolve {
+        // This runs before the indexing writes so lookups never wait on the write transaction.
+        if needs_resolve(&search_params) {
             if let Err(e) = self
                 .fhirpath_resolver
                 .prewarm_cache_for_resource(&resource.resource)
@@ -223,15 +296,22 @@ impl IndexingService {
             }
         }
 
-        let mut tx = self.pool.begin().await.map_err(crate::Error::Database)?;
+        Ok(Some(search_params))
+    }
 
+    async fn write_resource_index(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        resource: &Resource,
+        search_params: &[SearchParameter],
+    ) -> Result<()> {
         // Acquire advisory lock to prevent concurrent indexing
-        Self::acquire_indexing_lock(&mut tx, &resource.resource_type, &resource.id).await?;
+        Self::acquire_indexing_lock(tx, &resource.resource_type, &resource.id).await?;
 
         if !search_params.is_empty() {
             // Clear old entries
             self.clear_search_entries(
-                &mut tx,
+                tx,
                 &resource.resource_type,
                 &resource.id,
                 resource.version_id,
@@ -243,7 +323,7 @@ impl IndexingService {
             // NOTE: `ferrum_fhirpath::Value::from_json()` uses lazy objects. The current
             // `resolve()` implementation expects reference objects to be materialized, so we
             // materialize the root only when at least one expression uses `resolve()`.
-            let root = if needs_resolve {
+            let root = if needs_resolve(search_params) {
                 root.materialize()
             } else {
                 root
@@ -251,22 +331,19 @@ impl IndexingService {
             let ctx = Context::new(root);
 
             // Extract and insert for each parameter
-            for param in &search_params {
-                if let Err(e) = self.process_parameter(&mut tx, resource, param, &ctx).await {
+            for param in search_params {
+                if let Err(e) = self.process_parameter(tx, resource, param, &ctx).await {
                     tracing::warn!("Failed to index parameter {}: {}", param.code, e);
                 }
             }
         }
 
         // Update `_in` / `_list` membership indexes derived from collection resources.
-        self.update_membership_indexes(&mut tx, resource).await?;
+        self.update_membership_indexes(tx, resource).await?;
 
         // Update indexing status before commit
-        self.update_index_status(&mut tx, resource, search_params.len())
-            .await?;
-
-        tx.commit().await.map_err(crate::Error::Database)?;
-        Ok(())
+        self.update_index_status(tx, resource, search_params.len())
+            .await
     }
 
     /// Index multiple resources of the same type in a single transaction
@@ -306,12 +383,7 @@ impl IndexingService {
             if !search_params.is_empty()
                 || matches!(resource_type.as_str(), "CareTeam" | "Group" | "List")
             {
-                let needs_resolve = search_params.iter().any(|p| {
-                    p.expression
-                        .as_deref()
-                        .is_some_and(|expr| expr.contains("resolve("))
-                });
-                resolve_by_type.insert(resource_type.clone(), needs_resolve);
+                resolve_by_type.insert(resource_type.clone(), needs_resolve(&search_params));
                 params_by_type.insert(resource_type.clone(), search_params);
             }
         }
@@ -642,6 +714,19 @@ impl IndexingService {
         let mut seen = std::collections::HashSet::new();
         params.retain(|p| seen.insert(p.code.clone()));
 
+        // Common meta parameters are always indexed; a stored definition wins if present.
+        for (code, param_type, expression) in META_SEARCH_PARAMS {
+            if seen.insert(code.to_string()) {
+                params.push(SearchParameter {
+                    id: 0,
+                    code: code.to_string(),
+                    r#type: param_type.to_string(),
+                    expression: Some(expression.to_string()),
+                    components: None,
+                });
+            }
+        }
+
         // Store in cache
         {
             let mut cache = self.search_params_cache.write().unwrap();
@@ -1151,6 +1236,15 @@ pub(crate) struct SearchParameter {
     pub(super) components: Option<serde_json::Value>,
 }
 
+/// Whether any parameter expression uses `resolve()` and needs materialized references.
+fn needs_resolve(search_params: &[SearchParameter]) -> bool {
+    search_params.iter().any(|p| {
+        p.expression
+            .as_deref()
+            .is_some_and(|expr| expr.contains("resolve("))
+    })
+}
+
 /// Helper to get or compile FHIRPath plan for a search parameter (used by bulk indexer)
 pub(super) fn get_or_compile_plan(
     param: &SearchParameter,
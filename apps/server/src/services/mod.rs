@@ -4,21 +4,26 @@
 //! applying business rules, and managing transactions.
 
 pub mod admin;
+pub mod async_operation;
 pub mod audit;
 pub mod batch;
+pub mod bulk_export;
 pub mod conditional;
 pub mod conditional_references;
 pub mod crud;
 pub mod history;
+pub mod id_strategy;
 pub mod indexing;
 pub mod metadata;
 pub mod metrics;
+pub mod narrative;
 pub mod operation_executor;
 pub mod operation_registry;
 pub mod package;
-pub(crate) mod referential_integrity;
+pub mod referential_integrity;
 pub mod runtime_config;
 pub mod search;
+pub mod search_cache;
 pub mod summary;
 pub mod system;
 pub mod terminology;
@@ -0,0 +1,156 @@
+//! Narrative generation on create (`fhir.resources.generate_narrative`)
+//!
+//! Used by CrudService when a resource is created via POST.
+
+use serde::Deserialize;
+use serde_json::{json, Value as JsonValue};
+
+/// Elements summarized in a `Minimal` narrative, with their labels, in display order.
+const SUMMARY_ELEMENTS: &[(&str, &str)] = &[
+    ("name", "Name"),
+    ("title", "Title"),
+    ("code", "Code"),
+    ("status", "Status"),
+    ("gender", "Gender"),
+    ("birthDate", "Birth date"),
+];
+
+/// At most this many elements are summarized besides the resource type and id.
+const MAX_SUMMARY_ELEMENTS: usize = 3;
+
+/// Whether a narrative is generated for resources created without one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NarrativeMode {
+    /// Store resources as sent.
+    #[default]
+    Off,
+    /// Add a `text` summarizing the resource type, id and a few human-readable elements.
+    Minimal,
+}
+
+impl NarrativeMode {
+    /// Add a generated narrative to `resource` unless it already has one.
+    ///
+    /// Must run after the id is assigned so the summary can mention it.
+    pub(crate) fn apply(self, resource: &mut JsonValue) {
+        if self == Self::Off || has_narrative(resource) {
+            return;
+        }
+        let Some(div) = minimal_div(resource) else {
+            return;
+        };
+        if let Some(obj) = resource.as_object_mut() {
+            obj.insert(
+                "text".to_string(),
+                json!({ "status": "generated", "div": div }),
+            );
+        }
+    }
+}
+
+fn has_narrative(resource: &JsonValue) -> bool {
+    resource
+        .get("text")
+        .and_then(|text| text.get("div"))
+        .and_then(JsonValue::as_str)
+        .is_some_and(|div| !div.trim().is_empty())
+}
+
+fn minimal_div(resource: &JsonValue) -> Option<String> {
+    let resource_type = resource.get("resourceType")?.as_str()?;
+    let mut paragraphs = vec![match resource.get("id").and_then(JsonValue::as_str) {
+        Some(id) => format!("{} {}", resource_type, id),
+        None => resource_type.to_string(),
+    }];
+    paragraphs.extend(
+        SUMMARY_ELEMENTS
+            .iter()
+            .filter_map(|(element, label)| {
+                let value = resource.get(*element).and_then(display_text)?;
+                Some(format!("{}: {}", label, value))
+            })
+            .take(MAX_SUMMARY_ELEMENTS),
+    );
+
+    match ferrum_format::narrative_div(&paragraphs) {
+        Ok(div) => Some(div),
+        Err(e) => {
+            tracing::warn!("Failed to build narrative for {}: {}", resource_type, e);
+            None
+        }
+    }
+}
+
+/// Human-readable text of a primitive, HumanName or CodeableConcept (first entry of arrays).
+fn display_text(value: &JsonValue) -> Option<String> {
+    match value {
+        JsonValue::String(s) if !s.trim().is_empty() => Some(s.clone()),
+        JsonValue::Bool(_) | JsonValue::Number(_) => Some(value.to_string()),
+        JsonValue::Array(items) => items.iter().find_map(display_text),
+        JsonValue::Object(obj) => {
+            if let Some(text) = obj.get("text").and_then(display_text) {
+                return Some(text);
+            }
+            // HumanName
+            let parts: Vec<&str> = ["prefix", "given", "family", "suffix"]
+                .iter()
+                .filter_map(|part| obj.get(*part))
+                .flat_map(|part| match part {
+                    JsonValue::Array(items) => items.iter().filter_map(JsonValue::as_str).collect(),
+                    other => other.as_str().into_iter().collect::<Vec<_>>(),
+                })
+                .collect();
+            if !parts.is_empty() {
+                return Some(parts.join(" "));
+            }
+            // CodeableConcept
+            obj.get("coding")
+                .and_then(JsonValue::as_array)?
+                .iter()
+                .find_map(|coding| {
+                    coding
+                        .get("display")
+                        .or_else(|| coding.get("code"))
+                        .and_then(display_text)
+                })
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn minimal_mode_summarizes_patient() {
+        let mut patient = json!({
+            "resourceType": "Patient",
+            "id": "p1",
+            "name": [{"given": ["Ada"], "family": "Lovelace"}],
+            "gender": "female",
+            "birthDate": "1815-12-10"
+        });
+        NarrativeMode::Minimal.apply(&mut patient);
+
+        assert_eq!(patient["text"]["status"], "generated");
+        assert_eq!(
+            patient["text"]["div"],
+            "<div xmlns=\"http://www.w3.org/1999/xhtml\"><p>Patient p1</p><p>Name: Ada Lovelace</p>\
+             <p>Gender: female</p><p>Birth date: 1815-12-10</p></div>"
+        );
+    }
+
+    #[test]
+    fn existing_narrative_and_off_mode_are_left_alone() {
+        let text = json!({"status": "additional", "div": "<div xmlns=\"http://www.w3.org/1999/xhtml\">Mine</div>"});
+        let mut with_text = json!({"resourceType": "Patient", "id": "p1", "text": text});
+        NarrativeMode::Minimal.apply(&mut with_text);
+        assert_eq!(with_text["text"], text);
+
+        let mut without_text = json!({"resourceType": "Patient", "id": "p1"});
+        NarrativeMode::Off.apply(&mut without_text);
+        assert!(without_text.get("text").is_none());
+    }
+}
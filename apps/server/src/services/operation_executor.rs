@@ -1,11 +1,19 @@
 use crate::db::search::engine::SearchEngine;
+use crate::db::store::ExpungeOptions;
 use crate::db::PostgresResourceStore;
 use crate::error::{Error, Result};
-use crate::models::{OperationContext, OperationRequest, OperationResult, Parameters};
+use crate::models::{
+    OperationContext, OperationRequest, OperationResult, Parameter, ParameterValue, Parameters,
+};
 use crate::queue::{JobPriority, JobQueue};
-use crate::services::{IndexingService, PackageService, TerminologyService};
+use crate::services::{
+    search_cache::SearchCache, IndexingService, PackageService, TerminologyService,
+};
 use async_trait::async_trait;
-use serde_json::json;
+use ferrum_context::FhirContext;
+use ferrum_fhirpath::Engine as FhirPathEngine;
+use serde_json::{json, Value as JsonValue};
+use std::collections::HashMap;
 use std::sync::Arc;
 
 #[async_trait]
@@ -16,12 +24,18 @@ pub trait Operation: Send + Sync {
 pub struct OperationExecutor {
     #[allow(dead_code)] // Reserved for future use
     package_service: Option<Arc<PackageService>>,
-    #[allow(dead_code)] // Reserved for future use
     indexing_service: Option<Arc<IndexingService>>,
     terminology_service: Option<Arc<TerminologyService>>,
     job_queue: Option<Arc<dyn JobQueue>>,
     search_engine: Option<Arc<SearchEngine>>,
     store: Option<PostgresResourceStore>,
+    fhirpath_engine: Option<Arc<FhirPathEngine>>,
+    fhir_context: Option<Arc<dyn FhirContext>>,
+    /// Whether the non-standard `$fhirpath` debugging operation may be invoked.
+    fhirpath_operation_enabled: bool,
+    /// Whether `$expunge` may permanently remove history and deleted resources.
+    expunge_enabled: bool,
+    search_cache: Option<Arc<SearchCache>>,
 }
 
 impl OperationExecutor {
@@ -33,6 +47,11 @@ impl OperationExecutor {
             job_queue: None,
             search_engine: None,
             store: None,
+            fhirpath_engine: None,
+            fhir_context: None,
+            fhirpath_operation_enabled: false,
+            expunge_enabled: false,
+            search_cache: None,
         }
     }
 
@@ -44,6 +63,7 @@ impl OperationExecutor {
         job_queue: Arc<dyn JobQueue>,
         search_engine: Arc<SearchEngine>,
         store: PostgresResourceStore,
+        fhirpath_engine: Arc<FhirPathEngine>,
     ) -> Self {
         Self {
             package_service: Some(package_service),
@@ -52,13 +72,39 @@ impl OperationExecutor {
             job_queue: Some(job_queue),
             search_engine: Some(search_engine),
             store: Some(store),
+            fhirpath_engine: Some(fhirpath_engine),
+            fhir_context: None,
+            fhirpath_operation_enabled: false,
+            expunge_enabled: false,
+            search_cache: None,
         }
     }
 
+    /// Allow the `$fhirpath` operation (`fhir.fhirpath.enable_operation`).
+    pub fn set_fhirpath_operation_enabled(&mut self, enabled: bool) {
+        self.fhirpath_operation_enabled = enabled;
+    }
+
+    /// Allow the `$expunge` operation (`fhir.enable_expunge`).
+    pub fn set_expunge_enabled(&mut self, enabled: bool) {
+        self.expunge_enabled = enabled;
+    }
+
+    /// FHIR context used to resolve base definitions for `$snapshot`.
+    pub fn set_fhir_context(&mut self, fhir_context: Arc<dyn FhirContext>) {
+        self.fhir_context = Some(fhir_context);
+    }
+
+    /// Search result cache evicted by `$meta-add` / `$meta-delete`, which bypass resource hooks.
+    pub fn set_search_cache(&mut self, cache: Arc<SearchCache>) {
+        self.search_cache = Some(cache);
+    }
+
     pub async fn execute(&self, request: OperationRequest) -> Result<OperationResult> {
         match request.operation_name.as_str() {
             "install-package" => self.execute_install_package(request).await,
             "reindex" => self.execute_reindex(request).await,
+            "expunge" => self.execute_expunge(request).await,
             "expand" => self.execute_expand(request).await,
             "lookup" => self.execute_lookup(request).await,
             "validate-code" => self.execute_validate_code(request).await,
@@ -66,6 +112,10 @@ impl OperationExecutor {
             "translate" => self.execute_translate(request).await,
             "closure" => self.execute_closure(request).await,
             "everything" => self.execute_everything(request).await,
+            "meta-add" => self.execute_meta_change(request, MetaChange::Add).await,
+            "meta-delete" => self.execute_meta_change(request, MetaChange::Delete).await,
+            "fhirpath" => self.execute_fhirpath(request).await,
+            "snapshot" => self.execute_snapshot(request).await,
             _ => Err(Error::NotImplemented(format!(
                 "Operation '{}' not yet implemented",
                 request.operation_name
@@ -195,13 +245,13 @@ impl OperationExecutor {
                 .await?;
             job_ids.push(job_id);
         } else if let Some(ref rt) = resource_type {
-            // Type-level: one job for the resource type
+            // Type-level: one job for the resource type (coalesced with a pending one)
             let params = json!({
                 "resource_type": rt,
                 "resource_id": null,
             });
-            let job_id = job_queue
-                .enqueue("reindex".to_string(), params, JobPriority::Normal, None)
+            let (job_id, _) = job_queue
+                .enqueue_unique("reindex".to_string(), params, JobPriority::Normal, None)
                 .await?;
             job_ids.push(job_id);
         } else {
@@ -212,8 +262,8 @@ impl OperationExecutor {
                     "resource_type": rt,
                     "resource_id": null,
                 });
-                let job_id = job_queue
-                    .enqueue("reindex".to_string(), params, JobPriority::Normal, None)
+                let (job_id, _) = job_queue
+                    .enqueue_unique("reindex".to_string(), params, JobPriority::Normal, None)
                     .await?;
                 job_ids.push(job_id);
             }
@@ -243,6 +293,133 @@ impl OperationExecutor {
         Ok(OperationResult::Parameters(response))
     }
 
+    /// $expunge operation - permanently remove old versions and/or deleted resources
+    async fn execute_expunge(&self, request: OperationRequest) -> Result<OperationResult> {
+        if !self.expunge_enabled {
+            return Err(Error::MethodNotAllowed(
+                "$expunge is disabled by configuration (fhir.enable_expunge)".to_string(),
+            ));
+        }
+
+        let store = self
+            .store
+            .as_ref()
+            .ok_or_else(|| Error::Internal("ResourceStore not available".to_string()))?;
+
+        let (resource_type, resource_id) = match &request.context {
+            OperationContext::System => {
+                return Err(Error::InvalidResource(
+                    "$expunge can only be invoked at type or instance level".to_string(),
+                ));
+            }
+            OperationContext::Type(rt) => (rt.as_str(), None),
+            OperationContext::Instance(rt, id) => (rt.as_str(), Some(id.as_str())),
+        };
+
+        let flag = |name: &str| {
+            request
+                .parameters
+                .get_value(name)
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false)
+        };
+        let limit = request
+            .parameters
+            .get_value("limit")
+            .map(|value| {
+                value.as_u64().filter(|limit| *limit > 0).ok_or_else(|| {
+                    Error::Validation("$expunge limit must be a positive integer".to_string())
+                })
+            })
+            .transpose()?;
+        let options = ExpungeOptions {
+            deleted_resources: flag("expungeDeletedResources"),
+            previous_versions: flag("expungePreviousVersions"),
+            limit,
+        };
+
+        if !options.deleted_resources && !options.previous_versions {
+            return Err(Error::Validation(
+                "$expunge requires expungeDeletedResources and/or expungePreviousVersions"
+                    .to_string(),
+            ));
+        }
+
+        let count = store.expunge(resource_type, resource_id, options).await?;
+
+        let mut response = Parameters::new();
+        response.add_resource(
+            "outcome".to_string(),
+            json!({
+                "resourceType": "OperationOutcome",
+                "issue": [{
+                    "severity": "information",
+                    "code": "informational",
+                    "diagnostics": format!("Expunged {} resource version(s)", count)
+                }]
+            }),
+        );
+        response.add_value_integer("count".to_string(), count as i64);
+
+        Ok(OperationResult::Parameters(response))
+    }
+
+    /// $meta-add / $meta-delete operations - edit tags, security labels and profiles
+    ///
+    /// The current version is changed in place (no new version) and re-indexed in the same
+    /// transaction so `_tag`, `_security` and `_profile` searches see the change immediately.
+    async fn execute_meta_change(
+        &self,
+        request: OperationRequest,
+        change: MetaChange,
+    ) -> Result<OperationResult> {
+        let store = self
+            .store
+            .as_ref()
+            .ok_or_else(|| Error::Internal("ResourceStore not available".to_string()))?;
+        let indexing = self
+            .indexing_service
+            .as_ref()
+            .ok_or_else(|| Error::Internal("IndexingService not available".to_string()))?;
+
+        let OperationContext::Instance(resource_type, id) = &request.context else {
+            return Err(Error::InvalidResource(format!(
+                "${} can only be invoked at instance level",
+                request.operation_name
+            )));
+        };
+
+        let meta = request
+            .parameters
+            .get_value("meta")
+            .filter(|v| v.is_object())
+            .ok_or_else(|| {
+                Error::Validation("Missing required parameter: meta (valueMeta)".to_string())
+            })?;
+
+        let updated = store
+            .update_current_meta(resource_type, id, indexing, |target| match change {
+                MetaChange::Add => add_meta(target, meta),
+                MetaChange::Delete => delete_meta(target, meta),
+            })
+            .await?;
+        if let Some(cache) = &self.search_cache {
+            cache.invalidate(resource_type);
+        }
+
+        let mut response = Parameters::new();
+        response.add_value_meta(
+            "return".to_string(),
+            updated
+                .resource
+                .get("meta")
+                .cloned()
+                .unwrap_or_else(|| json!({})),
+        );
+
+        Ok(OperationResult::Parameters(response))
+    }
+
     async fn execute_expand(&self, request: OperationRequest) -> Result<OperationResult> {
         let terminology = self
             .terminology_service
@@ -438,6 +615,249 @@ impl OperationExecutor {
 
         Ok(OperationResult::Resource(bundle))
     }
+
+    /// $fhirpath operation - evaluate an expression against a stored resource (debugging aid)
+    async fn execute_fhirpath(&self, request: OperationRequest) -> Result<OperationResult> {
+        use crate::db::traits::ResourceStore;
+
+        if !self.fhirpath_operation_enabled {
+            return Err(Error::MethodNotAllowed(
+                "$fhirpath is disabled by configuration (fhir.fhirpath.enable_operation)"
+                    .to_string(),
+            ));
+        }
+
+        let OperationContext::Instance(resource_type, id) = &request.context else {
+            return Err(Error::InvalidResource(
+                "$fhirpath can only be invoked at instance level".to_string(),
+            ));
+        };
+
+        let engine = self
+            .fhirpath_engine
+            .as_ref()
+            .ok_or_else(|| Error::Internal("FHIRPath engine not available".to_string()))?;
+        let store = self
+            .store
+            .as_ref()
+            .ok_or_else(|| Error::Internal("ResourceStore not available".to_string()))?;
+
+        let expression = request
+            .parameters
+            .get_value("expression")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| {
+                Error::Validation("Missing required parameter: expression".to_string())
+            })?
+            .to_string();
+
+        let resource = store
+            .read(resource_type, id)
+            .await?
+            .ok_or_else(|| Error::ResourceNotFound {
+                resource_type: resource_type.to_string(),
+                id: id.to_string(),
+            })?;
+
+        let collection = engine
+            .evaluate_json(&expression, resource.resource, None)
+            .map_err(|e| Error::InvalidResource(format!("FHIRPath evaluation failed: {}", e)))?;
+
+        let mut response = Parameters::new();
+        response.add_value_string("expression".to_string(), expression);
+        for value in collection
+            .iter()
+            .filter_map(ferrum_fhirpath::ToJson::to_json)
+        {
+            response
+                .parameter
+                .get_or_insert_with(Vec::new)
+                .push(fhirpath_result_parameter(value));
+        }
+
+        Ok(OperationResult::Parameters(response))
+    }
+
+    /// StructureDefinition/[id]/$snapshot — regenerate the snapshot of a stored profile.
+    ///
+    /// The profile's differential is applied to the snapshot of its `baseDefinition`, resolved
+    /// through the FHIR context. The stored resource is left unchanged; the completed
+    /// StructureDefinition is returned.
+    async fn execute_snapshot(&self, request: OperationRequest) -> Result<OperationResult> {
+        use crate::db::traits::ResourceStore;
+
+        let (resource_type, id) = match &request.context {
+            OperationContext::Instance(rt, id) => (rt.as_str(), id.as_str()),
+            _ => {
+                return Err(Error::Validation(
+                    "$snapshot is only supported at instance level (StructureDefinition/[id]/$snapshot)"
+                        .to_string(),
+                ));
+            }
+        };
+
+        if resource_type != "StructureDefinition" {
+            return Err(Error::Validation(format!(
+                "$snapshot is only supported on StructureDefinition, not {}",
+                resource_type
+            )));
+        }
+
+        let context = self
+            .fhir_context
+            .as_ref()
+            .ok_or_else(|| Error::Internal("FhirContext not available".to_string()))?;
+        let store = self
+            .store
+            .as_ref()
+            .ok_or_else(|| Error::Internal("ResourceStore not available".to_string()))?;
+
+        let mut resource = store
+            .read(resource_type, id)
+            .await?
+            .ok_or_else(|| Error::ResourceNotFound {
+                resource_type: resource_type.to_string(),
+                id: id.to_string(),
+            })?
+            .resource;
+
+        let profile: ferrum_models::StructureDefinition = serde_json::from_value(resource.clone())
+            .map_err(|e| {
+                Error::InvalidResource(format!("Invalid StructureDefinition/{}: {}", id, e))
+            })?;
+        let differential = profile.differential.as_ref().ok_or_else(|| {
+            Error::UnprocessableEntity(format!(
+                "StructureDefinition/{} has no differential to generate a snapshot from",
+                id
+            ))
+        })?;
+        let base_url = profile.base_definition.as_deref().ok_or_else(|| {
+            Error::UnprocessableEntity(format!("StructureDefinition/{} has no baseDefinition", id))
+        })?;
+
+        let base = context
+            .get_structure_definition(base_url)
+            .map_err(|e| Error::FhirContext(e.to_string()))?
+            .ok_or_else(|| {
+                Error::UnprocessableEntity(format!(
+                    "Cannot resolve baseDefinition {} of StructureDefinition/{}",
+                    base_url, id
+                ))
+            })?;
+        let base_snapshot = base.snapshot.as_ref().ok_or_else(|| {
+            Error::UnprocessableEntity(format!(
+                "Base StructureDefinition {} has no snapshot",
+                base_url
+            ))
+        })?;
+
+        let snapshot =
+            ferrum_snapshot::generate_snapshot(base_snapshot, differential, context.as_ref())
+                .map_err(|e| {
+                    Error::UnprocessableEntity(format!(
+                        "Failed to generate snapshot for StructureDefinition/{}: {}",
+                        id, e
+                    ))
+                })?;
+        resource["snapshot"] = serde_json::to_value(snapshot)
+            .map_err(|e| Error::Internal(format!("Failed to serialize snapshot: {}", e)))?;
+
+        Ok(OperationResult::Resource(resource))
+    }
+}
+
+/// Wrap one `$fhirpath` result item as a `result` parameter.
+///
+/// Primitives map to `value[x]` by JSON type and resources are returned inline; other complex
+/// values have no declared type at this point, so they are returned as their JSON text.
+fn fhirpath_result_parameter(value: JsonValue) -> Parameter {
+    let name = "result".to_string();
+    let value_entry = |key: &str, value: JsonValue| Parameter {
+        name: name.clone(),
+        value: ParameterValue::Value(HashMap::from([(key.to_string(), value)])),
+    };
+
+    match value {
+        JsonValue::Bool(_) => value_entry("valueBoolean", value),
+        JsonValue::Number(ref n) if n.is_i64() => value_entry("valueInteger", value),
+        JsonValue::Number(_) => value_entry("valueDecimal", value),
+        JsonValue::String(_) => value_entry("valueString", value),
+        JsonValue::Object(ref map) if map.contains_key("resourceType") => Parameter {
+            name: name.clone(),
+            value: ParameterValue::Resource { resource: value },
+        },
+        other => value_entry("valueString", JsonValue::String(other.to_string())),
+    }
+}
+
+#[derive(Clone, Copy)]
+enum MetaChange {
+    Add,
+    Delete,
+}
+
+/// Meta elements handled by `$meta-add` / `$meta-delete`: coding lists and the profile list.
+const META_CODING_ELEMENTS: [&str; 2] = ["tag", "security"];
+
+fn same_coding(a: &JsonValue, b: &JsonValue) -> bool {
+    a.get("system") == b.get("system") && a.get("code") == b.get("code")
+}
+
+/// Merge `add` into `meta`; codings already present (same system and code) are not duplicated.
+fn add_meta(meta: &mut serde_json::Map<String, JsonValue>, add: &JsonValue) {
+    for element in META_CODING_ELEMENTS {
+        let Some(codings) = add.get(element).and_then(|v| v.as_array()) else {
+            continue;
+        };
+        let target = meta_list(meta, element);
+        for coding in codings {
+            if !target.iter().any(|existing| same_coding(existing, coding)) {
+                target.push(coding.clone());
+            }
+        }
+    }
+
+    if let Some(profiles) = add.get("profile").and_then(|v| v.as_array()) {
+        let target = meta_list(meta, "profile");
+        for profile in profiles {
+            if !target.contains(profile) {
+                target.push(profile.clone());
+            }
+        }
+    }
+}
+
+/// Remove the codings (matched on system and code) and profiles listed in `remove` from `meta`.
+fn delete_meta(meta: &mut serde_json::Map<String, JsonValue>, remove: &JsonValue) {
+    for element in META_CODING_ELEMENTS {
+        let Some(codings) = remove.get(element).and_then(|v| v.as_array()) else {
+            continue;
+        };
+        if let Some(JsonValue::Array(target)) = meta.get_mut(element) {
+            target.retain(|existing| !codings.iter().any(|c| same_coding(existing, c)));
+        }
+    }
+
+    if let Some(profiles) = remove.get("profile").and_then(|v| v.as_array()) {
+        if let Some(JsonValue::Array(target)) = meta.get_mut("profile") {
+            target.retain(|existing| !profiles.contains(existing));
+        }
+    }
+
+    meta.retain(|_, v| !matches!(v, JsonValue::Array(items) if items.is_empty()));
+}
+
+fn meta_list<'a>(
+    meta: &'a mut serde_json::Map<String, JsonValue>,
+    element: &str,
+) -> &'a mut Vec<JsonValue> {
+    let entry = meta
+        .entry(element.to_string())
+        .or_insert_with(|| JsonValue::Array(Vec::new()));
+    if !entry.is_array() {
+        *entry = JsonValue::Array(Vec::new());
+    }
+    entry.as_array_mut().expect("meta list is an array")
 }
 
 impl Default for OperationExecutor {
@@ -2,9 +2,58 @@
 //!
 //! Used by CrudService and TransactionService to validate references.
 
+use crate::Result;
+use serde::Deserialize;
 use serde_json::Value as JsonValue;
 use std::collections::HashSet;
 
+/// How writes treat relative references to resources that do not exist
+/// (`fhir.referential_integrity.mode`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReferentialIntegrityMode {
+    /// No checks (`off`, or the equivalent `lenient`).
+    #[default]
+    #[serde(alias = "lenient")]
+    Off,
+    /// Check references on write and log broken ones, but accept the write.
+    Logical,
+    /// Reject writes with broken references and deletes of referenced resources.
+    Strict,
+}
+
+/// Apply `mode` to the broken references (`Type/id`) found while writing `resource`.
+///
+/// Strict mode rejects the write with 422; logical mode only logs a warning.
+pub(crate) fn report_missing_references(
+    mode: ReferentialIntegrityMode,
+    resource: &JsonValue,
+    missing: &[String],
+) -> Result<()> {
+    if missing.is_empty() || mode == ReferentialIntegrityMode::Off {
+        return Ok(());
+    }
+
+    let message = format!(
+        "Referential integrity violation: the following referenced resources do not exist: {}",
+        missing.join(", ")
+    );
+    if mode == ReferentialIntegrityMode::Strict {
+        return Err(crate::Error::UnprocessableEntity(message));
+    }
+
+    tracing::warn!(
+        resource_type = resource
+            .get("resourceType")
+            .and_then(|v| v.as_str())
+            .unwrap_or(""),
+        id = resource.get("id").and_then(|v| v.as_str()).unwrap_or(""),
+        "{}",
+        message
+    );
+    Ok(())
+}
+
 /// Walk the entire JSON tree and collect relative references of the form `Type/id`.
 ///
 /// Skips fragments (`#...`), absolute URLs (`http://...`), canonical URLs, and URN references.
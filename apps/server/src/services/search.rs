@@ -10,6 +10,8 @@ use crate::{
     db::search::params::{CursorDirection, SearchParameters},
     models::is_known_resource_type,
     runtime_config::{ConfigKey, RuntimeConfigCache},
+    security_labels,
+    services::search_cache::{SearchCache, SearchCacheKey, SearchDependencies},
     services::SummaryFilter,
     Result,
 };
@@ -29,6 +31,12 @@ pub struct SearchResult {
     /// Unknown/unsupported parameters that were ignored
     #[serde(skip)]
     pub unknown_params: Vec<String>,
+    /// Parameters whose index may not cover every stored resource yet (pending reindex)
+    #[serde(skip)]
+    pub unindexed_params: Vec<String>,
+    /// Warnings about parameters whose SearchParameter is retired or experimental
+    #[serde(skip)]
+    pub param_warnings: Vec<String>,
 }
 
 /// Search service coordinates FHIR search operations
@@ -36,6 +44,7 @@ pub struct SearchService {
     search_engine: Arc<SearchEngine>,
     summary_filter: Option<Arc<SummaryFilter>>,
     runtime_config_cache: Arc<RuntimeConfigCache>,
+    search_cache: Option<Arc<SearchCache>>,
 }
 
 impl SearchService {
@@ -48,6 +57,7 @@ impl SearchService {
             search_engine,
             summary_filter: None,
             runtime_config_cache,
+            search_cache: None,
         }
     }
 
@@ -61,9 +71,15 @@ impl SearchService {
             search_engine,
             summary_filter: Some(summary_filter),
             runtime_config_cache,
+            search_cache: None,
         }
     }
 
+    /// Serve repeated identical searches from `cache` (`fhir.search.cache`)
+    pub fn set_search_cache(&mut self, cache: Arc<SearchCache>) {
+        self.search_cache = Some(cache);
+    }
+
     /// Search for resources of a specific type
     ///
     /// GET/POST [base]/{resource_type}?params
@@ -76,7 +92,13 @@ impl SearchService {
     ) -> Result<JsonValue> {
         self.validate_resource_type_name(resource_type)?;
 
-        let params = SearchParameters::from_items(query_items)?;
+        let cache_key = self.cache_key(resource_type, query_string, base_url);
+        if let Some(bundle) = self.cached_bundle(cache_key.as_ref()) {
+            return Ok(bundle);
+        }
+
+        let mut params = SearchParameters::from_items(query_items)?;
+        let (query_items, clamp_outcome) = self.clamp_page_size(&mut params, query_items).await;
         let default_count: usize = self
             .runtime_config_cache
             .get(ConfigKey::SearchDefaultCount)
@@ -89,15 +111,24 @@ impl SearchService {
             .await?;
 
         // Build FHIR searchset Bundle
-        self.build_searchset_bundle(
+        let mut bundle = self.build_searchset_bundle(
             result,
             resource_type,
             query_string,
             base_url,
             &params,
             default_count,
-            query_items,
-        )
+            &query_items,
+        )?;
+        if let (Some(outcome), Some(bundle_obj)) = (clamp_outcome, bundle.as_object_mut()) {
+            push_outcome_entry(bundle_obj, outcome);
+        }
+        self.cache_bundle(
+            cache_key,
+            &bundle,
+            SearchDependencies::for_search(&[resource_type.to_string()], &query_items),
+        );
+        Ok(bundle)
     }
 
     /// Search across all resource types
@@ -110,7 +141,13 @@ impl SearchService {
         query_string: &str,
         base_url: &str,
     ) -> Result<JsonValue> {
-        let params = SearchParameters::from_items(query_items)?;
+        let cache_key = self.cache_key("", query_string, base_url);
+        if let Some(bundle) = self.cached_bundle(cache_key.as_ref()) {
+            return Ok(bundle);
+        }
+
+        let mut params = SearchParameters::from_items(query_items)?;
+        let (query_items, clamp_outcome) = self.clamp_page_size(&mut params, query_items).await;
         let default_count: usize = self
             .runtime_config_cache
             .get(ConfigKey::SearchDefaultCount)
@@ -134,20 +171,78 @@ impl SearchService {
             .await?;
 
         // Build FHIR searchset Bundle
-        self.build_searchset_bundle(
+        let mut bundle = self.build_searchset_bundle(
             result,
             "",
             query_string,
             base_url,
             &params,
             default_count,
-            query_items,
-        )
+            &query_items,
+        )?;
+        if let (Some(outcome), Some(bundle_obj)) = (clamp_outcome, bundle.as_object_mut()) {
+            push_outcome_entry(bundle_obj, outcome);
+        }
+        self.cache_bundle(
+            cache_key,
+            &bundle,
+            SearchDependencies::for_search(&params.types, &query_items),
+        );
+        Ok(bundle)
+    }
+
+    /// Validate search parameters for a type without executing the search
+    ///
+    /// GET [base]/{resource_type}/$validate-search?params
+    /// Returns an OperationOutcome with one issue per parameter describing whether it is supported.
+    pub async fn validate_search_type(
+        &self,
+        resource_type: &str,
+        query_items: &[(String, String)],
+    ) -> Result<JsonValue> {
+        self.validate_resource_type_name(resource_type)?;
+
+        let checks = self
+            .search_engine
+            .validate_search_params(resource_type, query_items)
+            .await?;
+
+        let mut issues = checks
+            .iter()
+            .map(|check| match &check.reason {
+                None => serde_json::json!({
+                    "severity": "information",
+                    "code": "informational",
+                    "diagnostics": format!("Search parameter '{}' is supported", check.name),
+                    "expression": [check.name],
+                }),
+                Some(reason) => serde_json::json!({
+                    "severity": "error",
+                    "code": "not-supported",
+                    "diagnostics": reason,
+                    "expression": [check.name],
+                }),
+            })
+            .collect::<Vec<_>>();
+
+        if issues.is_empty() {
+            issues.push(serde_json::json!({
+                "severity": "information",
+                "code": "informational",
+                "diagnostics": "No search parameters supplied",
+            }));
+        }
+
+        Ok(serde_json::json!({
+            "resourceType": "OperationOutcome",
+            "issue": issues,
+        }))
     }
 
     /// Search within a compartment
     ///
     /// GET/POST [base]/{compartment_type}/{compartment_id}/[{resource_type}]?params
+    #[allow(clippy::too_many_arguments)]
     pub async fn search_compartment(
         &self,
         compartment_type: &str,
@@ -162,7 +257,20 @@ impl SearchService {
             self.validate_resource_type_name(resource_type)?;
         }
 
-        let params = SearchParameters::from_items(query_items)?;
+        // Path used in the Bundle links
+        let search_path = if let Some(rt) = resource_type {
+            format!("{}/{}/{}", compartment_type, compartment_id, rt)
+        } else {
+            // Per FHIR spec, all-types compartment searches use a literal `*` path segment.
+            format!("{}/{}/{}", compartment_type, compartment_id, "*")
+        };
+        let cache_key = self.cache_key(&search_path, query_string, base_url);
+        if let Some(bundle) = self.cached_bundle(cache_key.as_ref()) {
+            return Ok(bundle);
+        }
+
+        let mut params = SearchParameters::from_items(query_items)?;
+        let (query_items, clamp_outcome) = self.clamp_page_size(&mut params, query_items).await;
         let default_count: usize = self
             .runtime_config_cache
             .get(ConfigKey::SearchDefaultCount)
@@ -181,21 +289,58 @@ impl SearchService {
             .await?;
 
         // Build FHIR searchset Bundle
-        let search_path = if let Some(rt) = resource_type {
-            format!("{}/{}/{}", compartment_type, compartment_id, rt)
-        } else {
-            // Per FHIR spec, all-types compartment searches use a literal `*` path segment.
-            format!("{}/{}/{}", compartment_type, compartment_id, "*")
-        };
-        self.build_searchset_bundle(
+        let mut bundle = self.build_searchset_bundle(
             result,
             &search_path,
             query_string,
             base_url,
             &params,
             default_count,
-            query_items,
-        )
+            &query_items,
+        )?;
+        if let (Some(outcome), Some(bundle_obj)) = (clamp_outcome, bundle.as_object_mut()) {
+            push_outcome_entry(bundle_obj, outcome);
+        }
+        // An all-types compartment search depends on every type.
+        let searched_types: Vec<String> = resource_type.map(str::to_string).into_iter().collect();
+        self.cache_bundle(
+            cache_key,
+            &bundle,
+            SearchDependencies::for_search(&searched_types, &query_items),
+        );
+        Ok(bundle)
+    }
+
+    /// Key for the search result cache (in the current security-label scope), when caching
+    /// is enabled
+    fn cache_key(
+        &self,
+        context: &str,
+        query_string: &str,
+        base_url: &str,
+    ) -> Option<SearchCacheKey> {
+        self.search_cache.as_ref()?;
+        Some(SearchCacheKey::new(
+            context,
+            query_string,
+            base_url,
+            security_labels::current_scope().as_ref(),
+        ))
+    }
+
+    fn cached_bundle(&self, key: Option<&SearchCacheKey>) -> Option<JsonValue> {
+        self.search_cache.as_ref()?.get(key?)
+    }
+
+    fn cache_bundle(
+        &self,
+        key: Option<SearchCacheKey>,
+        bundle: &JsonValue,
+        dependencies: SearchDependencies,
+    ) {
+        if let (Some(cache), Some(key)) = (&self.search_cache, key) {
+            cache.insert(key, bundle.clone(), dependencies);
+        }
     }
 
     /// Build a FHIR searchset Bundle from search results
@@ -240,6 +385,12 @@ impl SearchService {
             if !result.unknown_params.is_empty() {
                 bundle["_unknown_params"] = serde_json::json!(result.unknown_params);
             }
+            if !result.unindexed_params.is_empty() {
+                bundle["_unindexed_params"] = serde_json::json!(result.unindexed_params);
+            }
+            if !result.param_warnings.is_empty() {
+                bundle["_param_warnings"] = serde_json::json!(result.param_warnings);
+            }
 
             return Ok(bundle);
         }
@@ -426,14 +577,80 @@ impl SearchService {
         // Add entries
         bundle["entry"] = serde_json::json!(entries);
 
-        // Add unknown parameters as temporary metadata (will be checked and removed by handler)
+        // Add unknown, unindexed and flagged parameters as temporary metadata (will be checked and
+        // removed by handler)
         if !result.unknown_params.is_empty() {
             bundle["_unknown_params"] = serde_json::json!(result.unknown_params);
         }
+        if !result.unindexed_params.is_empty() {
+            bundle["_unindexed_params"] = serde_json::json!(result.unindexed_params);
+        }
+        if !result.param_warnings.is_empty() {
+            bundle["_param_warnings"] = serde_json::json!(result.param_warnings);
+        }
 
         Ok(bundle)
     }
 
+    /// Clamp `_count` to the configured maximum page size
+    ///
+    /// Returns the query items with `_count` rewritten to the effective value (so pagination
+    /// links do not re-request the oversized page) and, when clamped, an OperationOutcome entry
+    /// warning the client.
+    async fn clamp_page_size(
+        &self,
+        params: &mut SearchParameters,
+        query_items: &[(String, String)],
+    ) -> (Vec<(String, String)>, Option<JsonValue>) {
+        let max_page_size: usize = self
+            .runtime_config_cache
+            .get(ConfigKey::SearchMaxPageSize)
+            .await;
+        let max_count: usize = self
+            .runtime_config_cache
+            .get(ConfigKey::SearchMaxCount)
+            .await;
+        // Never clamp to a size the `max_count` limit would reject afterwards.
+        let max_page_size = max_page_size.min(max_count);
+
+        let requested = match params.count {
+            Some(count) if count > max_page_size => count,
+            _ => return (query_items.to_vec(), None),
+        };
+        params.count = Some(max_page_size);
+
+        let query_items = query_items
+            .iter()
+            .map(|(key, value)| {
+                if key == "_count" {
+                    (key.clone(), max_page_size.to_string())
+                } else {
+                    (key.clone(), value.clone())
+                }
+            })
+            .collect();
+
+        let outcome = serde_json::json!({
+            "resource": {
+                "resourceType": "OperationOutcome",
+                "issue": [{
+                    "severity": "warning",
+                    "code": "too-costly",
+                    "diagnostics": format!(
+                        "_count={} exceeds the maximum page size of {}; page size was reduced to {}",
+                        requested, max_page_size, max_page_size
+                    ),
+                    "expression": ["_count"]
+                }]
+            },
+            "search": {
+                "mode": "outcome"
+            }
+        });
+
+        (query_items, Some(outcome))
+    }
+
     /// Build a pagination URL with cursor
     /// Per FHIR spec: preserves _count and _maxresults in pagination links
     fn build_paging_url(
@@ -491,3 +708,16 @@ impl SearchService {
         Ok(())
     }
 }
+
+/// Append an entry (e.g. a `search.mode = "outcome"` OperationOutcome) to a searchset Bundle
+pub(crate) fn push_outcome_entry(
+    bundle_obj: &mut serde_json::Map<String, JsonValue>,
+    outcome: JsonValue,
+) {
+    match bundle_obj.get_mut("entry").and_then(|v| v.as_array_mut()) {
+        Some(entries) => entries.push(outcome),
+        None => {
+            bundle_obj.insert("entry".to_string(), serde_json::json!([outcome]));
+        }
+    }
+}
@@ -0,0 +1,279 @@
+//! In-memory search result cache (`fhir.search.cache`)
+//!
+//! Caches searchset Bundles for a short TTL so identical searches (e.g. dashboards polling
+//! the same query) do not re-run their SQL. Entries are keyed by:
+//! - the search context (resource type, `_type` list or compartment)
+//! - the normalized query string and base URL (both appear in the Bundle links)
+//! - the requester's security-label scope, the only principal-dependent search input, so
+//!   results are never shared between principals that may see different resources
+//!
+//! Writes evict entries through [`SearchCacheHook`](crate::hooks::search_cache::SearchCacheHook):
+//! an entry depends on the resource types it searches, or on every type when the query
+//! reaches other types (chaining, `_has`, `_include`, `_revinclude`, `_list`). Writes that do
+//! not run resource hooks are only picked up once the TTL expires.
+
+use crate::security_labels::SecurityLabelScope;
+use lru::LruCache;
+use serde_json::Value as JsonValue;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Resource types whose changes alter how every search is evaluated.
+const SEARCH_DEFINITION_TYPES: &[&str] = &["SearchParameter", "CompartmentDefinition"];
+
+/// Parameters that make a search depend on resources of other types.
+const CROSS_TYPE_PARAMETERS: &[&str] = &["_has", "_include", "_revinclude", "_list"];
+
+/// Identifies a cached search result
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SearchCacheKey {
+    context: String,
+    query_string: String,
+    base_url: String,
+    /// Sorted allowed `system|code` labels; `None` for unrestricted requests.
+    security_labels: Option<Vec<String>>,
+}
+
+impl SearchCacheKey {
+    pub fn new(
+        context: &str,
+        query_string: &str,
+        base_url: &str,
+        security_scope: Option<&SecurityLabelScope>,
+    ) -> Self {
+        Self {
+            context: context.to_string(),
+            query_string: normalize_query_string(query_string),
+            base_url: base_url.to_string(),
+            security_labels: security_scope.map(SecurityLabelScope::tokens),
+        }
+    }
+}
+
+/// Order-independent form of a query string: decoded pairs, sorted and re-encoded.
+fn normalize_query_string(query_string: &str) -> String {
+    let mut pairs: Vec<(String, String)> = url::form_urlencoded::parse(query_string.as_bytes())
+        .into_owned()
+        .collect();
+    pairs.sort();
+    url::form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(pairs)
+        .finish()
+}
+
+/// Resource types a cached result depends on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SearchDependencies {
+    Types(Vec<String>),
+    /// Evicted by a write of any type.
+    Any,
+}
+
+impl SearchDependencies {
+    /// Dependencies of a search over `resource_types` (empty: all types) with `query_items`.
+    pub fn for_search(resource_types: &[String], query_items: &[(String, String)]) -> Self {
+        let cross_type = query_items.iter().any(|(name, _)| {
+            // Chains may follow a type modifier (`subject:Patient.name`).
+            let base = name.split(':').next().unwrap_or(name);
+            name.contains('.') || CROSS_TYPE_PARAMETERS.contains(&base)
+        });
+        if cross_type || resource_types.is_empty() {
+            Self::Any
+        } else {
+            Self::Types(resource_types.to_vec())
+        }
+    }
+
+    fn includes(&self, resource_type: &str) -> bool {
+        match self {
+            Self::Types(types) => types.iter().any(|t| t == resource_type),
+            Self::Any => true,
+        }
+    }
+}
+
+struct CachedSearch {
+    bundle: JsonValue,
+    dependencies: SearchDependencies,
+    expires_at: Instant,
+}
+
+/// TTL- and size-bounded cache of searchset Bundles
+pub struct SearchCache {
+    ttl: Duration,
+    entries: Mutex<LruCache<SearchCacheKey, CachedSearch>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl SearchCache {
+    pub fn new(ttl: Duration, max_entries: usize) -> Self {
+        let capacity = NonZeroUsize::new(max_entries).unwrap_or(NonZeroUsize::MIN);
+        Self {
+            ttl,
+            entries: Mutex::new(LruCache::new(capacity)),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Cached Bundle for `key`, unless missing or expired
+    pub fn get(&self, key: &SearchCacheKey) -> Option<JsonValue> {
+        let mut entries = self.lock();
+        let bundle = match entries.get(key) {
+            Some(entry) if entry.expires_at > Instant::now() => Some(entry.bundle.clone()),
+            Some(_) => {
+                entries.pop(key);
+                None
+            }
+            None => None,
+        };
+        let counter = if bundle.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        bundle
+    }
+
+    pub fn insert(&self, key: SearchCacheKey, bundle: JsonValue, dependencies: SearchDependencies) {
+        let entry = CachedSearch {
+            bundle,
+            dependencies,
+            expires_at: Instant::now() + self.ttl,
+        };
+        self.lock().put(key, entry);
+    }
+
+    /// Evict every entry that depends on `resource_type`
+    ///
+    /// Changes to search definitions (SearchParameter, CompartmentDefinition) evict everything.
+    pub fn invalidate(&self, resource_type: &str) {
+        let mut entries = self.lock();
+        if SEARCH_DEFINITION_TYPES.contains(&resource_type) {
+            entries.clear();
+            return;
+        }
+        let stale: Vec<SearchCacheKey> = entries
+            .iter()
+            .filter(|(_, entry)| entry.dependencies.includes(resource_type))
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in stale {
+            entries.pop(&key);
+        }
+    }
+
+    /// Number of lookups served from the cache
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Number of lookups that had to run the search
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, LruCache<SearchCacheKey, CachedSearch>> {
+        // The cache holds no invariants a panicking holder could break.
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security_labels::SecurityLabel;
+    use serde_json::json;
+
+    fn patient_search(scope: Option<&SecurityLabelScope>) -> SearchCacheKey {
+        SearchCacheKey::new("Patient", "family=Doe", "http://localhost/fhir", scope)
+    }
+
+    #[test]
+    fn entries_are_scoped_to_security_labels() {
+        let cache = SearchCache::new(Duration::from_secs(60), 10);
+        let restricted = SecurityLabelScope::new(vec![SecurityLabel {
+            system: None,
+            code: "R".to_string(),
+        }]);
+        let deps = SearchDependencies::for_search(&["Patient".to_string()], &[]);
+        cache.insert(patient_search(None), json!({"total": 2}), deps.clone());
+        cache.insert(patient_search(Some(&restricted)), json!({"total": 1}), deps);
+
+        assert_eq!(cache.get(&patient_search(None)), Some(json!({"total": 2})));
+        assert_eq!(
+            cache.get(&patient_search(Some(&restricted))),
+            Some(json!({"total": 1}))
+        );
+        let anonymous = SecurityLabelScope::new(vec![]);
+        assert_eq!(cache.get(&patient_search(Some(&anonymous))), None);
+        assert_eq!((cache.hits(), cache.misses()), (2, 1));
+    }
+
+    #[test]
+    fn writes_evict_dependent_entries_only() {
+        let cache = SearchCache::new(Duration::from_secs(60), 10);
+        let observations = SearchCacheKey::new("Observation", "", "http://localhost/fhir", None);
+        let chained = SearchCacheKey::new(
+            "Observation",
+            "subject.name=Doe",
+            "http://localhost/fhir",
+            None,
+        );
+        cache.insert(
+            observations.clone(),
+            json!({}),
+            SearchDependencies::for_search(&["Observation".to_string()], &[]),
+        );
+        cache.insert(
+            chained.clone(),
+            json!({}),
+            SearchDependencies::for_search(
+                &["Observation".to_string()],
+                &[("subject.name".to_string(), "Doe".to_string())],
+            ),
+        );
+
+        cache.invalidate("Patient");
+        assert!(cache.get(&observations).is_some());
+        assert!(cache.get(&chained).is_none());
+
+        cache.invalidate("SearchParameter");
+        assert!(cache.get(&observations).is_none());
+    }
+
+    #[test]
+    fn chains_after_a_type_modifier_depend_on_every_type() {
+        let deps = SearchDependencies::for_search(
+            &["Observation".to_string()],
+            &[("subject:Patient.name".to_string(), "Doe".to_string())],
+        );
+        assert_eq!(deps, SearchDependencies::Any);
+    }
+
+    #[test]
+    fn keys_ignore_parameter_order_and_encoding() {
+        let key =
+            |query: &str| SearchCacheKey::new("Patient", query, "http://localhost/fhir", None);
+        assert_eq!(key("family=Doe&gender=male"), key("gender=male&family=Doe"));
+        assert_eq!(key("identifier=a%7Cb"), key("identifier=a|b"));
+        assert_ne!(key("family=Doe"), key("family=Roe"));
+    }
+
+    #[test]
+    fn expired_entries_are_not_served() {
+        let cache = SearchCache::new(Duration::ZERO, 10);
+        cache.insert(
+            patient_search(None),
+            json!({}),
+            SearchDependencies::Types(vec!["Patient".to_string()]),
+        );
+        assert!(cache.get(&patient_search(None)).is_none());
+    }
+}
@@ -223,19 +223,29 @@ impl TerminologyService {
             .resolve_system_code(params, context, "CodeSystem")
             .await?;
 
-        let cs_name = match self
+        // Resolve the CodeSystem (latest version unless one is requested) for its name and
+        // the version actually used
+        let code_system = self
             .repo
             .find_resource_by_canonical_url("CodeSystem", &system, version.as_deref())
             .await
-        {
-            Ok(Some(cs_resource)) => cs_resource
-                .get("name")
-                .and_then(|v| v.as_str())
-                .or_else(|| cs_resource.get("title").and_then(|v| v.as_str()))
-                .unwrap_or(system.as_str())
-                .to_string(),
-            _ => system.clone(),
-        };
+            .ok()
+            .flatten();
+        let cs_name = code_system
+            .as_ref()
+            .and_then(|cs| {
+                cs.get("name")
+                    .and_then(|v| v.as_str())
+                    .or_else(|| cs.get("title").and_then(|v| v.as_str()))
+            })
+            .unwrap_or(system.as_str())
+            .to_string();
+        let cs_version = code_system
+            .as_ref()
+            .and_then(|cs| cs.get("version"))
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+            .or(version.clone());
 
         let cs = self
             .find_concept_in_codesystem(&system, version.as_deref(), &code)
@@ -253,13 +263,16 @@ impl TerminologyService {
 
         let mut out = Parameters::new();
         out.add_value_string("name".to_string(), cs_name);
-        if let Some(v) = version {
+        if let Some(v) = cs_version {
             out.add_value_string("version".to_string(), v);
         }
         out.add_value_string(
             "display".to_string(),
             cs.display.clone().unwrap_or_else(|| code.clone()),
         );
+        if let Some(definition) = cs.definition.clone() {
+            out.add_value_string("definition".to_string(), definition);
+        }
 
         // Add properties
         if let Some(properties) = cs.properties {
@@ -290,25 +303,15 @@ impl TerminologyService {
                         });
                     }
 
-                    // Add property value
-                    if let Some(value_code) = prop.get("valueCode") {
-                        prop_parts.push(crate::models::Parameter {
-                            name: "value".to_string(),
-                            value: crate::models::ParameterValue::Value(HashMap::from([(
-                                "valueCode".to_string(),
-                                value_code.clone(),
-                            )])),
-                        });
-                    } else if let Some(value) = prop
-                        .get("valueString")
-                        .or_else(|| prop.get("valueBoolean"))
-                        .or_else(|| prop.get("valueInteger"))
-                        .or_else(|| prop.get("valueDecimal"))
+                    // Add property value, keeping its value[x] type
+                    if let Some((value_key, value)) = prop
+                        .as_object()
+                        .and_then(|obj| obj.iter().find(|(k, _)| k.starts_with("value")))
                     {
                         prop_parts.push(crate::models::Parameter {
                             name: "value".to_string(),
                             value: crate::models::ParameterValue::Value(HashMap::from([(
-                                "valueString".to_string(),
+                                value_key.clone(),
                                 value.clone(),
                             )])),
                         });
@@ -428,12 +431,20 @@ impl TerminologyService {
         context: &OperationContext,
         params: &Parameters,
     ) -> Result<Parameters> {
-        // Resolve map: url param or ConceptMap instance
+        // Resolve map: url (+ conceptMapVersion) param or ConceptMap instance
         let map = if let Some(url) = params.get_value("url").and_then(|v| v.as_str()) {
+            let version = version_param(params, "conceptMapVersion");
+            let version = version.as_deref();
             self.repo
-                .find_resource_by_canonical_url("ConceptMap", url, None)
+                .find_resource_by_canonical_url("ConceptMap", url, version)
                 .await?
-                .ok_or_else(|| Error::NotFound(format!("ConceptMap not found for url '{}'", url)))?
+                .ok_or_else(|| match version {
+                    Some(v) => Error::NotFound(format!(
+                        "ConceptMap not found for url '{}' version '{}'",
+                        url, v
+                    )),
+                    None => Error::NotFound(format!("ConceptMap not found for url '{}'", url)),
+                })?
         } else if let OperationContext::Instance(rt, id) = context {
             if rt != "ConceptMap" {
                 return Err(Error::Validation(
@@ -457,38 +468,85 @@ impl TerminologyService {
             .get_value("reverse")
             .and_then(|v| v.as_bool())
             .unwrap_or(false);
+        let target_system = params
+            .get_value("targetsystem")
+            .or_else(|| params.get_value("targetSystem"))
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
 
         let (system, code) = self.resolve_system_and_code_or_coding(params)?;
-        let matches = translate_with_map(&map, &system, &code, reverse);
+
+        // Follow `unmapped` other-map references, visiting each map once.
+        let mut matches = Vec::new();
+        let mut visited = HashSet::<String>::new();
+        let mut pending = vec![map];
+        while let Some(map) = pending.pop() {
+            if let Some(url) = map.get("url").and_then(|v| v.as_str()) {
+                if !visited.insert(url.to_string()) {
+                    continue;
+                }
+            }
+            let translation = translate_with_map(&map, &system, &code, reverse);
+            matches.extend(translation.matches);
+            for other in translation.other_maps {
+                let (url, version) = match other.split_once('|') {
+                    Some((url, version)) => (url, Some(version)),
+                    None => (other.as_str(), None),
+                };
+                if visited.contains(url) {
+                    continue;
+                }
+                if let Some(next) = self
+                    .repo
+                    .find_resource_by_canonical_url("ConceptMap", url, version)
+                    .await?
+                {
+                    pending.push(next);
+                }
+            }
+        }
+        if let Some(target_system) = &target_system {
+            matches.retain(|m| m.system.as_deref() == Some(target_system.as_str()));
+        }
 
         let mut out = Parameters::new();
-        let result = matches
-            .iter()
-            .any(|m| m.equivalence != "unmatched" && m.equivalence != "disjoint");
+        let result = matches.iter().any(TranslationMatch::is_mapped);
         out.add_value_boolean("result".to_string(), result);
         for m in matches {
             let mut parts = Vec::new();
             parts.push(crate::models::Parameter {
-                name: "equivalence".to_string(),
+                name: m.relationship_kind.to_string(),
                 value: crate::models::ParameterValue::Value(HashMap::from([(
                     "valueCode".to_string(),
-                    JsonValue::String(m.equivalence),
+                    JsonValue::String(m.relationship),
                 )])),
             });
 
-            let mut coding_obj = serde_json::Map::new();
-            coding_obj.insert("system".to_string(), JsonValue::String(m.system));
-            coding_obj.insert("code".to_string(), JsonValue::String(m.code));
-            if let Some(d) = m.display {
-                coding_obj.insert("display".to_string(), JsonValue::String(d));
+            if let (Some(system), Some(code)) = (m.system, m.code) {
+                let mut coding_obj = serde_json::Map::new();
+                coding_obj.insert("system".to_string(), JsonValue::String(system));
+                coding_obj.insert("code".to_string(), JsonValue::String(code));
+                if let Some(d) = m.display {
+                    coding_obj.insert("display".to_string(), JsonValue::String(d));
+                }
+                parts.push(crate::models::Parameter {
+                    name: "concept".to_string(),
+                    value: crate::models::ParameterValue::Value(HashMap::from([(
+                        "valueCoding".to_string(),
+                        JsonValue::Object(coding_obj),
+                    )])),
+                });
+            }
+
+            if let Some(source) = m.source {
+                parts.push(crate::models::Parameter {
+                    name: "source".to_string(),
+                    value: crate::models::ParameterValue::Value(HashMap::from([(
+                        "valueUri".to_string(),
+                        JsonValue::String(source),
+                    )])),
+                });
             }
-            parts.push(crate::models::Parameter {
-                name: "concept".to_string(),
-                value: crate::models::ParameterValue::Value(HashMap::from([(
-                    "valueCoding".to_string(),
-                    JsonValue::Object(coding_obj),
-                )])),
-            });
 
             out.add_parts("match".to_string(), parts);
         }
@@ -808,7 +866,11 @@ impl TerminologyService {
             .and_then(|v| v.as_bool())
             .unwrap_or(true);
 
-        let expanded = self.expand_valueset(&valueset).await?;
+        // A complete stored expansion is authoritative; otherwise expand from the compose.
+        let expanded = match complete_stored_expansion(&valueset) {
+            Some(concepts) => concepts,
+            None => self.expand_valueset(&valueset).await?,
+        };
         let mut found_concept: Option<&Concept> = None;
         for c in &expanded {
             if c.system == system && c.code == code {
@@ -820,7 +882,14 @@ impl TerminologyService {
         let mut out = Parameters::new();
 
         if let Some(concept) = found_concept {
-            let concept_display = concept.display.clone();
+            // Enumerated includes may omit the display; take it from the CodeSystem then.
+            let concept_display = match concept.display.clone() {
+                Some(display) => Some(display),
+                None => self
+                    .find_concept_in_codesystem(&system, None, &code)
+                    .await?
+                    .and_then(|c| c.display),
+            };
             let concept_abstract = concept.abstract_flag.unwrap_or(false);
 
             // Check abstract
@@ -903,9 +972,10 @@ impl TerminologyService {
         }
 
         if let Some(url) = params.get_value("url").and_then(|v| v.as_str()) {
+            let version = params.get_value("valueSetVersion").and_then(|v| v.as_str());
             return self
                 .repo
-                .find_resource_by_canonical_url("ValueSet", url, None)
+                .find_resource_by_canonical_url("ValueSet", url, version)
                 .await?
                 .ok_or_else(|| Error::NotFound(format!("ValueSet not found for url '{}'", url)));
         }
@@ -1296,6 +1366,10 @@ impl TerminologyService {
                 .get("display")
                 .and_then(|v| v.as_str())
                 .map(|s| s.to_string()),
+            definition: c
+                .get("definition")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
             properties: c.get("property").cloned(),
             designations: c.get("designation").cloned(),
         }))
@@ -1338,10 +1412,87 @@ struct Concept {
 
 #[derive(Debug, Clone)]
 struct TranslationMatch {
-    system: String,
-    code: String,
+    /// Target system; `None` for an explicit "no mapping" entry
+    system: Option<String>,
+    code: Option<String>,
     display: Option<String>,
-    equivalence: String,
+    /// `equivalence` (R4 maps) or `relationship` (R5 maps), named after the map's own element
+    relationship_kind: &'static str,
+    relationship: String,
+    /// Canonical URL of the ConceptMap the match came from
+    source: Option<String>,
+}
+
+impl TranslationMatch {
+    /// Whether the match maps the concept (as opposed to recording that it does not map)
+    fn is_mapped(&self) -> bool {
+        self.code.is_some()
+            && !matches!(
+                self.relationship.as_str(),
+                "unmatched" | "disjoint" | "not-related-to"
+            )
+    }
+}
+
+/// Matches from one ConceptMap, plus the maps its `unmapped` elements defer to
+#[derive(Debug, Default)]
+struct Translation {
+    matches: Vec<TranslationMatch>,
+    other_maps: Vec<String>,
+}
+
+/// Relationship stated on a ConceptMap target (or unmapped) element
+///
+/// R5 maps use `relationship`, R4 maps `equivalence`; `default` is reported as an R4
+/// equivalence when the element states neither.
+/// A version parameter as a string
+///
+/// GET query values that look numeric (e.g. `conceptMapVersion=1`) arrive as integers.
+fn version_param(params: &Parameters, name: &str) -> Option<String> {
+    match params.get_value(name)? {
+        JsonValue::String(s) => Some(s.clone()),
+        JsonValue::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+fn map_relationship(element: &JsonValue, default: &str) -> (&'static str, String) {
+    if let Some(r) = element.get("relationship").and_then(|v| v.as_str()) {
+        ("relationship", r.to_string())
+    } else {
+        let eq = element
+            .get("equivalence")
+            .and_then(|v| v.as_str())
+            .unwrap_or(default);
+        ("equivalence", eq.to_string())
+    }
+}
+
+/// Concepts of a ValueSet's stored expansion, if it lists the whole value set
+///
+/// Paged expansions (an `offset`, or fewer entries than `total`) are incomplete and
+/// yield `None`.
+fn complete_stored_expansion(valueset: &JsonValue) -> Option<Vec<Concept>> {
+    let expansion = valueset.get("expansion")?;
+    let contains = expansion.get("contains")?;
+    if expansion
+        .get("offset")
+        .and_then(|v| v.as_u64())
+        .is_some_and(|offset| offset > 0)
+    {
+        return None;
+    }
+
+    let mut out = HashMap::new();
+    extract_valueset_expansion_contains(contains, &mut out);
+    if expansion
+        .get("total")
+        .and_then(|v| v.as_u64())
+        .is_some_and(|total| total > out.len() as u64)
+    {
+        return None;
+    }
+    Some(out.into_values().collect())
 }
 
 fn extract_valueset_expansion_contains(value: &JsonValue, out: &mut HashMap<String, Concept>) {
@@ -1459,13 +1610,16 @@ fn is_ancestor(
     false
 }
 
-fn translate_with_map(
-    map: &JsonValue,
-    system: &str,
-    code: &str,
-    reverse: bool,
-) -> Vec<TranslationMatch> {
-    let mut out = Vec::new();
+/// Translate `system|code` with one ConceptMap
+///
+/// Walks `group.element.target`. Going forward, a source code no group element lists falls
+/// back to the group's `unmapped` rule: `provided` maps the code to itself in the target
+/// system, `fixed` maps it to the given code, and `other-map` defers to another ConceptMap
+/// (returned in `other_maps` for the caller to resolve). R5 elements marked `noMap` and
+/// R4 targets without a code record that the concept does not map.
+fn translate_with_map(map: &JsonValue, system: &str, code: &str, reverse: bool) -> Translation {
+    let mut out = Translation::default();
+    let source_url = map.get("url").and_then(|v| v.as_str()).map(String::from);
     let Some(groups) = map.get("group").and_then(|v| v.as_array()) else {
         return out;
     };
@@ -1481,43 +1635,51 @@ fn translate_with_map(
             continue;
         }
 
-        let Some(elements) = group.get("element").and_then(|v| v.as_array()) else {
-            continue;
-        };
+        let elements = group
+            .get("element")
+            .and_then(|v| v.as_array())
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let mut listed = false;
 
         for element in elements {
             let src_code = element.get("code").and_then(|v| v.as_str()).unwrap_or("");
-            let Some(targets) = element.get("target").and_then(|v| v.as_array()) else {
-                continue;
-            };
+            let targets = element
+                .get("target")
+                .and_then(|v| v.as_array())
+                .map(Vec::as_slice)
+                .unwrap_or_default();
 
             if !reverse {
                 if src_code != code {
                     continue;
                 }
+                listed = true;
+                if element.get("noMap").and_then(|v| v.as_bool()) == Some(true) {
+                    out.matches.push(TranslationMatch {
+                        system: None,
+                        code: None,
+                        display: None,
+                        relationship_kind: "relationship",
+                        relationship: "not-related-to".to_string(),
+                        source: source_url.clone(),
+                    });
+                    continue;
+                }
                 for t in targets {
-                    let t_code = t
-                        .get("code")
-                        .and_then(|v| v.as_str())
-                        .unwrap_or("")
-                        .to_string();
-                    let t_display = t
-                        .get("display")
-                        .and_then(|v| v.as_str())
-                        .map(|s| s.to_string());
-                    let eq = t
-                        .get("equivalence")
-                        .and_then(|v| v.as_str())
-                        .unwrap_or("unmatched")
-                        .to_string();
-                    if !t_code.is_empty() {
-                        out.push(TranslationMatch {
-                            system: target.to_string(),
-                            code: t_code,
-                            display: t_display,
-                            equivalence: eq,
-                        });
-                    }
+                    let (relationship_kind, relationship) = map_relationship(t, "unmatched");
+                    let t_code = t.get("code").and_then(|v| v.as_str());
+                    out.matches.push(TranslationMatch {
+                        system: t_code.map(|_| target.to_string()),
+                        code: t_code.map(String::from),
+                        display: t
+                            .get("display")
+                            .and_then(|v| v.as_str())
+                            .map(|s| s.to_string()),
+                        relationship_kind,
+                        relationship,
+                        source: source_url.clone(),
+                    });
                 }
             } else {
                 for t in targets {
@@ -1525,23 +1687,68 @@ fn translate_with_map(
                     if t_code != code {
                         continue;
                     }
-                    let eq = t
-                        .get("equivalence")
-                        .and_then(|v| v.as_str())
-                        .unwrap_or("unmatched")
-                        .to_string();
-                    out.push(TranslationMatch {
-                        system: source.to_string(),
-                        code: src_code.to_string(),
+                    let (relationship_kind, relationship) = map_relationship(t, "unmatched");
+                    out.matches.push(TranslationMatch {
+                        system: Some(source.to_string()),
+                        code: Some(src_code.to_string()),
                         display: element
                             .get("display")
                             .and_then(|v| v.as_str())
                             .map(|s| s.to_string()),
-                        equivalence: eq,
+                        relationship_kind,
+                        relationship,
+                        source: source_url.clone(),
                     });
                 }
             }
         }
+
+        if reverse || listed {
+            continue;
+        }
+        let Some(unmapped) = group.get("unmapped") else {
+            continue;
+        };
+        match unmapped.get("mode").and_then(|v| v.as_str()) {
+            Some("provided") => {
+                let (relationship_kind, relationship) = map_relationship(unmapped, "equal");
+                out.matches.push(TranslationMatch {
+                    system: Some(target.to_string()),
+                    code: Some(code.to_string()),
+                    display: None,
+                    relationship_kind,
+                    relationship,
+                    source: source_url.clone(),
+                });
+            }
+            Some("fixed") => {
+                let Some(fixed_code) = unmapped.get("code").and_then(|v| v.as_str()) else {
+                    continue;
+                };
+                let (relationship_kind, relationship) = map_relationship(unmapped, "inexact");
+                out.matches.push(TranslationMatch {
+                    system: Some(target.to_string()),
+                    code: Some(fixed_code.to_string()),
+                    display: unmapped
+                        .get("display")
+                        .and_then(|v| v.as_str())
+                        .map(|s| s.to_string()),
+                    relationship_kind,
+                    relationship,
+                    source: source_url.clone(),
+                });
+            }
+            Some("other-map") => {
+                if let Some(other) = unmapped
+                    .get("otherMap")
+                    .or_else(|| unmapped.get("url"))
+                    .and_then(|v| v.as_str())
+                {
+                    out.other_maps.push(other.to_string());
+                }
+            }
+            _ => {}
+        }
     }
 
     out
@@ -11,7 +11,7 @@ use crate::{
     },
     hooks::ResourceHook,
     runtime_config::{ConfigKey, RuntimeConfigCache},
-    services::IndexingService,
+    services::{search_cache::SearchCache, IndexingService},
     Result,
 };
 use axum::http::StatusCode;
@@ -27,6 +27,9 @@ use ferrum_models::{Bundle, BundleEntry, BundleEntryResponse, BundleType, Struct
 use uuid::Uuid;
 
 use super::batch::{BundleRequestOptions, PreferReturn};
+use super::id_strategy::IdStrategy;
+use super::narrative::NarrativeMode;
+use super::referential_integrity::{report_missing_references, ReferentialIntegrityMode};
 use crate::db::search::engine::SearchEngine;
 use crate::services::conditional::{
     build_conditional_search_params_from_items, parse_form_urlencoded,
@@ -85,8 +88,11 @@ pub struct TransactionService {
     allow_update_create: bool,
     hard_delete: bool,
     runtime_config_cache: Option<Arc<RuntimeConfigCache>>,
-    referential_integrity_mode: String,
+    referential_integrity_mode: ReferentialIntegrityMode,
+    id_strategy: IdStrategy,
+    generate_narrative: NarrativeMode,
     transaction_recorder: Option<TransactionRecorder>,
+    search_cache: Option<Arc<SearchCache>>,
 }
 
 impl TransactionService {
@@ -109,19 +115,38 @@ impl TransactionService {
             allow_update_create,
             hard_delete,
             runtime_config_cache: None,
-            referential_integrity_mode: "lenient".to_string(),
+            referential_integrity_mode: ReferentialIntegrityMode::Off,
+            id_strategy: IdStrategy::Uuid,
+            generate_narrative: NarrativeMode::Off,
             transaction_recorder: None,
+            search_cache: None,
         }
     }
 
-    pub fn set_referential_integrity_mode(&mut self, mode: String) {
+    pub fn set_referential_integrity_mode(&mut self, mode: ReferentialIntegrityMode) {
         self.referential_integrity_mode = mode;
     }
 
+    pub fn set_id_strategy(&mut self, strategy: IdStrategy) {
+        self.id_strategy = strategy;
+    }
+
+    pub fn set_generate_narrative(&mut self, mode: NarrativeMode) {
+        self.generate_narrative = mode;
+    }
+
     pub fn set_transaction_recorder(&mut self, recorder: TransactionRecorder) {
         self.transaction_recorder = Some(recorder);
     }
 
+    /// Evict cached searches for the types a committed transaction wrote
+    ///
+    /// Transactions only run resource hooks for conformance resources, so the
+    /// [`SearchCacheHook`](crate::hooks::search_cache::SearchCacheHook) does not see them.
+    pub fn set_search_cache(&mut self, cache: Arc<SearchCache>) {
+        self.search_cache = Some(cache);
+    }
+
     pub fn new_with_runtime_config(
         store: PostgresResourceStore,
         hooks: Vec<Arc<dyn ResourceHook>>,
@@ -250,6 +275,17 @@ impl TransactionService {
             tracing::warn!("Failed to apply inline transaction indexing: {}", e);
         }
 
+        if let Some(cache) = &self.search_cache {
+            let written_types: HashSet<&str> = original_requests
+                .iter()
+                .filter(|(method, _)| method != "GET" && method != "HEAD")
+                .filter_map(|(_, url)| url.split(['/', '?']).find(|s| !s.is_empty()))
+                .collect();
+            for resource_type in written_types {
+                cache.invalidate(resource_type);
+            }
+        }
+
         serde_json::to_value(response_bundle).map_err(|e| {
             crate::Error::Internal(format!(
                 "Failed to serialize transaction response bundle: {}",
@@ -300,7 +336,8 @@ impl TransactionService {
         tracing::debug!("process_transaction: Seeding non-POST mappings");
         url_rewriter.seed_non_post_mappings(&entries)?;
         tracing::debug!("process_transaction: Reserving POST IDs");
-        url_rewriter.reserve_post_ids(&entries, &post_indices)?;
+        let post_ids = self.assign_post_ids(&entries, &post_indices).await?;
+        url_rewriter.reserve_post_ids(&entries, post_ids);
         tracing::debug!("process_transaction: URL rewriter setup complete");
 
         let mut response_entries = vec![default_bundle_entry(); entries.len()];
@@ -333,6 +370,20 @@ impl TransactionService {
             }
         }
 
+        if let Err(err) = self
+            .resolve_conditional_creates(
+                &mut tx,
+                &entries,
+                &post_indices,
+                &mut url_rewriter,
+                options.base_url.as_deref(),
+            )
+            .await
+        {
+            let _ = tx.rollback().await;
+            return Err(err);
+        }
+
         for &index in &post_indices {
             match self
                 .process_entry(
@@ -456,10 +507,13 @@ impl TransactionService {
 
         let method = request.method.to_uppercase();
         let parsed_url = ParsedUrl::parse(&request.url);
-        let query_items = query_from_url(&request.url)
-            .map(parse_form_urlencoded)
-            .transpose()?
-            .unwrap_or_default();
+        // Conditional criteria may reference other entries by their fullUrl.
+        let query_items = url_rewriter.rewrite_query_items(
+            query_from_url(&request.url)
+                .map(parse_form_urlencoded)
+                .transpose()?
+                .unwrap_or_default(),
+        );
 
         match method.as_str() {
             "DELETE" => {
@@ -623,121 +677,62 @@ impl TransactionService {
                     .await?;
                 tracing::debug!("Resource rewrite complete for entry {}", index);
 
-                if let Some(if_none_exist_raw) = request.if_none_exist.as_deref() {
-                    tracing::debug!(
-                        "Processing conditional create for entry {} with if_none_exist: {}",
-                        index,
-                        if_none_exist_raw
-                    );
-                    let query = if_none_exist_raw.trim().trim_start_matches('?');
-                    let query_items = parse_form_urlencoded(query)?;
-                    if query_items.is_empty() {
-                        return Err(crate::Error::Validation(
-                            "Transaction conditional create requires If-None-Exist search parameters"
-                                .to_string(),
-                        ));
-                    }
-
-                    let search_params = build_conditional_search_params_from_items(&query_items)?;
-                    tracing::debug!(
-                        "Starting conditional search for entry {} with params: {:?}",
-                        index,
-                        search_params
-                    );
-                    let search_result = {
-                        let conn = tx.tx_mut()?;
-                        tracing::debug!(
-                            "Got transaction connection, calling search_with_connection for entry {}",
-                            index
-                        );
-                        let result = self
-                            .search_engine
-                            .search_with_connection(
-                                conn,
-                                Some(&resource_type),
-                                &search_params,
-                                base_url,
-                            )
-                            .await?;
-                        tracing::debug!(
-                            "Search completed for entry {}, found {} results",
-                            index,
-                            result.resources.len()
-                        );
-                        result
-                    };
-
-                    match self
-                        .conditional_service
-                        .conditional_create_from_matches(&search_result.resources)?
-                    {
-                        crate::services::conditional::ConditionalCreateResult::NoMatch => {
-                            /* proceed with create below */
+                // Conditional creates are resolved before any POST is processed (see
+                // `resolve_conditional_create`), so every entry sees the final ids.
+                if let Some(id) = url_rewriter.conditional_create_match(index) {
+                    let existing = tx.read(&resource_type, &id).await?.ok_or_else(|| {
+                        crate::Error::ResourceNotFound {
+                            resource_type: resource_type.clone(),
+                            id: id.clone(),
                         }
-                        crate::services::conditional::ConditionalCreateResult::MatchFound {
-                            id,
-                        } => {
-                            let existing =
-                                tx.read(&resource_type, &id).await?.ok_or_else(|| {
-                                    crate::Error::ResourceNotFound {
-                                        resource_type: resource_type.clone(),
-                                        id: id.clone(),
-                                    }
-                                })?;
-
-                            if let Some(full_url) = &entry.full_url {
-                                url_rewriter
-                                    .mapping
-                                    .insert(full_url.clone(), format!("{}/{}", resource_type, &id));
-                            }
+                    })?;
 
-                            let outcome = serde_json::json!({
-                                "resourceType": "OperationOutcome",
-                                "issue": [{
-                                    "severity": "information",
-                                    "code": "informational",
-                                    "diagnostics": format!(
-                                        "Resource matched existing resource with ID {}",
-                                        existing.id
-                                    )
-                                }]
-                            });
+                    let outcome = serde_json::json!({
+                        "resourceType": "OperationOutcome",
+                        "issue": [{
+                            "severity": "information",
+                            "code": "informational",
+                            "diagnostics": format!(
+                                "Resource matched existing resource with ID {}",
+                                existing.id
+                            )
+                        }]
+                    });
 
-                            return Ok(BundleEntry {
-                                full_url: entry.full_url.clone(),
-                                request: None,
-                                response: Some(BundleEntryResponse {
-                                    status: status_line(StatusCode::OK),
-                                    location: Some(format!(
-                                        "{}/{}/_history/{}",
-                                        resource_type, existing.id, existing.version_id
-                                    )),
-                                    etag: Some(format!("W/\"{}\"", existing.version_id)),
-                                    last_modified: Some(existing.last_updated.to_rfc3339()),
-                                    outcome: match prefer_return {
-                                        PreferReturn::OperationOutcome => Some(outcome),
-                                        _ => None,
-                                    },
-                                    extensions: HashMap::new(),
-                                }),
-                                resource: match prefer_return {
-                                    PreferReturn::Representation => Some(existing.resource),
-                                    _ => None,
-                                },
-                                search: None,
-                                extensions: HashMap::new(),
-                            });
-                        }
-                    }
+                    return Ok(BundleEntry {
+                        full_url: entry.full_url.clone(),
+                        request: None,
+                        response: Some(BundleEntryResponse {
+                            status: status_line(StatusCode::OK),
+                            location: Some(format!(
+                                "{}/{}/_history/{}",
+                                resource_type, existing.id, existing.version_id
+                            )),
+                            etag: Some(format!("W/\"{}\"", existing.version_id)),
+                            last_modified: Some(existing.last_updated.to_rfc3339()),
+                            outcome: match prefer_return {
+                                PreferReturn::OperationOutcome => Some(outcome),
+                                _ => None,
+                            },
+                            extensions: HashMap::new(),
+                        }),
+                        resource: match prefer_return {
+                            PreferReturn::Representation => Some(existing.resource),
+                            _ => None,
+                        },
+                        search: None,
+                        extensions: HashMap::new(),
+                    });
                 }
 
                 let id = url_rewriter.reserved_post_id(index).ok_or_else(|| {
                     crate::Error::Internal("Missing reserved POST id".to_string())
                 })?;
                 populate_meta(&mut resource, &id, 1, Utc::now());
+                self.generate_narrative.apply(&mut resource);
 
-                // Referential integrity check (strict mode)
-                if self.is_strict_referential_integrity() {
+                // Referential integrity check (logical/strict mode)
+                if self.checks_references_on_write() {
                     self.validate_references_in_transaction(&resource, &known_ids).await?;
                 }
 
@@ -860,7 +855,11 @@ impl TransactionService {
 
                     match resolution.target_id {
                         None => {
-                            let id = Uuid::new_v4().to_string();
+                            // No matches and no client id: create like a POST entry.
+                            let id = self
+                                .id_strategy
+                                .assign_id(&self.store, &resource_type, &resource)
+                                .await?;
                             populate_meta(&mut resource, &id, 1, Utc::now());
                             if let Some(obj) = resource.as_object_mut() {
                                 obj.insert("resourceType".to_string(), json!(resource_type));
@@ -872,8 +871,8 @@ impl TransactionService {
                                     .insert(full_url.clone(), format!("{}/{}", resource_type, &id));
                             }
 
-                            // Referential integrity check (strict mode)
-                            if self.is_strict_referential_integrity() {
+                            // Referential integrity check (logical/strict mode)
+                            if self.checks_references_on_write() {
                                 self.validate_references_in_transaction(&resource, &known_ids).await?;
                             }
 
@@ -956,8 +955,8 @@ impl TransactionService {
                                     .insert(full_url.clone(), format!("{}/{}", resource_type, &id));
                             }
 
-                            // Referential integrity check (strict mode)
-                            if self.is_strict_referential_integrity() {
+                            // Referential integrity check (logical/strict mode)
+                            if self.checks_references_on_write() {
                                 self.validate_references_in_transaction(&resource, &known_ids).await?;
                             }
 
@@ -1062,8 +1061,8 @@ impl TransactionService {
                     obj.insert("id".to_string(), json!(resource_id));
                 }
 
-                // Referential integrity check (strict mode)
-                if self.is_strict_referential_integrity() {
+                // Referential integrity check (logical/strict mode)
+                if self.checks_references_on_write() {
                     self.validate_references_in_transaction(&resource, &known_ids).await?;
                 }
 
@@ -1210,13 +1209,12 @@ impl TransactionService {
                     }
                 }
 
-                let mut patched = current.resource.clone();
-                json_patch::patch(&mut patched, &patch.0).map_err(|e| match e.kind {
-                    json_patch::PatchErrorKind::TestFailed => {
-                        crate::Error::UnprocessableEntity(e.to_string())
-                    }
-                    _ => crate::Error::InvalidResource(e.to_string()),
-                })?;
+                let mut patched = crate::services::crud::apply_json_patch(
+                    &current.resource,
+                    &patch,
+                    &resource_type,
+                    &resource_id,
+                )?;
 
                 url_rewriter.rewrite_resource(&mut patched)?;
                 self.resolve_conditional_references_in_transaction(tx, &mut patched, base_url)
@@ -1224,16 +1222,9 @@ impl TransactionService {
 
                 let new_version = current.version_id + 1;
                 populate_meta(&mut patched, &resource_id, new_version, Utc::now());
-                if let Some(obj) = patched.as_object_mut() {
-                    obj.insert("resourceType".to_string(), json!(resource_type));
-                    obj.insert("id".to_string(), json!(resource_id));
-                    // Narrative safety: PATCH changes data without updating narrative.
-                    // Drop it to avoid clinically unsafe narrative.
-                    obj.remove("text");
-                }
 
-                // Referential integrity check (strict mode)
-                if self.is_strict_referential_integrity() {
+                // Referential integrity check (logical/strict mode)
+                if self.checks_references_on_write() {
                     self.validate_references_in_transaction(&patched, &known_ids).await?;
                 }
 
@@ -1358,8 +1349,174 @@ impl TransactionService {
         }
     }
 
+    /// Resolve the `request.ifNoneExist` criteria of all POST entries before any POST is processed.
+    ///
+    /// Criteria may reference the `fullUrl` of another conditional create, which only has its
+    /// final id once that entry is resolved. Entries are therefore resolved in dependency order:
+    /// each round resolves (in bundle order) the entries whose criteria reference no unresolved
+    /// conditional create, until all are resolved. Criteria that depend on each other are rejected.
+    async fn resolve_conditional_creates(
+        &self,
+        tx: &mut PostgresTransactionContext,
+        entries: &[BundleEntry],
+        post_indices: &[usize],
+        url_rewriter: &mut UrlRewriter,
+        base_url: Option<&str>,
+    ) -> Result<()> {
+        let mut pending: Vec<usize> = post_indices
+            .iter()
+            .copied()
+            .filter(|&index| {
+                entries[index]
+                    .request
+                    .as_ref()
+                    .is_some_and(|request| request.if_none_exist.is_some())
+            })
+            .collect();
+
+        while !pending.is_empty() {
+            let unresolved_full_urls: Vec<&str> = pending
+                .iter()
+                .filter_map(|&index| entries[index].full_url.as_deref())
+                .collect();
+            let (ready, blocked): (Vec<usize>, Vec<usize>) =
+                pending.iter().copied().partition(|&index| {
+                    let entry = &entries[index];
+                    !if_none_exist_values(entry).iter().any(|value| {
+                        entry.full_url.as_deref() != Some(value.as_str())
+                            && unresolved_full_urls.contains(&value.as_str())
+                    })
+                });
+
+            if ready.is_empty() {
+                return Err(crate::Error::Validation(format!(
+                    "Transaction conditional creates at entries {} have circular ifNoneExist criteria",
+                    blocked
+                        .iter()
+                        .map(|index| index.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )));
+            }
+
+            for index in ready {
+                self.resolve_conditional_create(tx, &entries[index], index, url_rewriter, base_url)
+                    .await
+                    .map_err(|err| with_entry_context(err, index))?;
+            }
+            pending = blocked;
+        }
+        Ok(())
+    }
+
+    /// Resolve the `request.ifNoneExist` criteria of a single POST entry.
+    ///
+    /// A match replaces the id reserved for the entry, so references to its `fullUrl` from any
+    /// other entry are rewritten to the existing resource regardless of entry order.
+    async fn resolve_conditional_create(
+        &self,
+        tx: &mut PostgresTransactionContext,
+        entry: &BundleEntry,
+        index: usize,
+        url_rewriter: &mut UrlRewriter,
+        base_url: Option<&str>,
+    ) -> Result<()> {
+        let Some(request) = entry.request.as_ref() else {
+            return Ok(());
+        };
+        let Some(if_none_exist_raw) = request.if_none_exist.as_deref() else {
+            return Ok(());
+        };
+        tracing::debug!(
+            "Processing conditional create for entry {} with if_none_exist: {}",
+            index,
+            if_none_exist_raw
+        );
+
+        let resource_type = ParsedUrl::parse(&request.url)
+            .resource_type
+            .ok_or_else(|| {
+                crate::Error::InvalidResource(format!(
+                    "Transaction entry {} POST missing resource type in request.url",
+                    index
+                ))
+            })?;
+
+        let query = if_none_exist_raw.trim().trim_start_matches('?');
+        let query_items = url_rewriter.rewrite_query_items(parse_form_urlencoded(query)?);
+        if query_items.is_empty() {
+            return Err(crate::Error::Validation(
+                "Transaction conditional create requires If-None-Exist search parameters"
+                    .to_string(),
+            ));
+        }
+
+        let search_params = build_conditional_search_params_from_items(&query_items)?;
+        let search_result = {
+            let conn = tx.tx_mut()?;
+            self.search_engine
+                .search_with_connection(conn, Some(&resource_type), &search_params, base_url)
+                .await?
+        };
+
+        match self
+            .conditional_service
+            .conditional_create_from_matches(&search_result.resources)?
+        {
+            crate::services::conditional::ConditionalCreateResult::NoMatch => {}
+            crate::services::conditional::ConditionalCreateResult::MatchFound { id } => {
+                url_rewriter.record_conditional_create_match(
+                    index,
+                    entry.full_url.as_deref(),
+                    &resource_type,
+                    id,
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Pick ids for the POST entries with the configured id strategy, as
+    /// `(entry index, resource type, id)`.
+    async fn assign_post_ids(
+        &self,
+        entries: &[BundleEntry],
+        post_indices: &[usize],
+    ) -> Result<Vec<(usize, String, String)>> {
+        let mut post_ids = Vec::with_capacity(post_indices.len());
+        for &idx in post_indices {
+            let entry = &entries[idx];
+            let request = entry.request.as_ref().ok_or_else(|| {
+                crate::Error::InvalidResource(format!("Transaction entry {} missing request", idx))
+            })?;
+            let resource_type = ParsedUrl::parse(&request.url)
+                .resource_type
+                .ok_or_else(|| {
+                    crate::Error::InvalidResource(format!(
+                        "Transaction entry {} POST missing resource type in request.url",
+                        idx
+                    ))
+                })?;
+            let id = self
+                .id_strategy
+                .assign_id(
+                    &self.store,
+                    &resource_type,
+                    entry.resource.as_ref().unwrap_or(&JsonValue::Null),
+                )
+                .await?;
+            post_ids.push((idx, resource_type, id));
+        }
+        Ok(post_ids)
+    }
+
     fn is_strict_referential_integrity(&self) -> bool {
-        self.referential_integrity_mode == "strict"
+        self.referential_integrity_mode == ReferentialIntegrityMode::Strict
+    }
+
+    /// Whether writes check their references (logical and strict modes).
+    fn checks_references_on_write(&self) -> bool {
+        self.referential_integrity_mode != ReferentialIntegrityMode::Off
     }
 
     /// Validate references in a resource within a transaction context.
@@ -1409,14 +1566,7 @@ impl TransactionService {
             .map(|(rt, id)| format!("{}/{}", rt, id))
             .collect();
 
-        if !missing.is_empty() {
-            return Err(crate::Error::BusinessRule(format!(
-                "Referential integrity violation: the following referenced resources do not exist: {}",
-                missing.join(", ")
-            )));
-        }
-
-        Ok(())
+        report_missing_references(self.referential_integrity_mode, resource, &missing)
     }
 
     /// Check that no other resources reference this resource before deletion in a transaction.
@@ -1684,6 +1834,30 @@ fn check_identity_overlaps(
     Ok(())
 }
 
+/// Search values of an entry's `request.ifNoneExist` criteria, one per OR alternative.
+///
+/// Conditional creates depend on each other only through values that equal another entry's
+/// `fullUrl`; criteria that fail to parse are reported when the entry itself is resolved.
+fn if_none_exist_values(entry: &BundleEntry) -> Vec<String> {
+    let Some(criteria) = entry
+        .request
+        .as_ref()
+        .and_then(|request| request.if_none_exist.as_deref())
+    else {
+        return Vec::new();
+    };
+    parse_form_urlencoded(criteria.trim().trim_start_matches('?'))
+        .unwrap_or_default()
+        .into_iter()
+        .flat_map(|(_, value)| {
+            value
+                .split(',')
+                .map(|alternative| alternative.trim().to_string())
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
 fn with_entry_context(err: crate::Error, index: usize) -> crate::Error {
     match err {
         crate::Error::InvalidResource(msg) => {
@@ -1698,6 +1872,22 @@ fn with_entry_context(err: crate::Error, index: usize) -> crate::Error {
         crate::Error::PreconditionFailed(msg) => {
             crate::Error::PreconditionFailed(format!("Transaction entry {}: {}", index, msg))
         }
+        crate::Error::InvalidReference(msg) => {
+            crate::Error::InvalidReference(format!("Transaction entry {}: {}", index, msg))
+        }
+        crate::Error::UnprocessableEntity(msg) => {
+            crate::Error::UnprocessableEntity(format!("Transaction entry {}: {}", index, msg))
+        }
+        crate::Error::NotFound(msg) => {
+            crate::Error::NotFound(format!("Transaction entry {}: {}", index, msg))
+        }
+        // Structured variants are flattened to keep their status code while naming the entry.
+        err @ crate::Error::ResourceNotFound { .. } => {
+            crate::Error::NotFound(format!("Transaction entry {}: {}", index, err))
+        }
+        err @ crate::Error::VersionConflict { .. } => {
+            crate::Error::PreconditionFailed(format!("Transaction entry {}: {}", index, err))
+        }
         other => other,
     }
 }
@@ -1992,6 +2182,8 @@ struct UrlRewriter {
     fhir_context: Arc<dyn FhirContext>,
     mapping: HashMap<String, String>,
     reserved_post_ids: HashMap<usize, String>,
+    /// Existing resources matched by conditional creates, by entry index.
+    conditional_create_matches: HashMap<usize, String>,
     canonical_cache: HashMap<String, HashSet<String>>,
 }
 
@@ -2001,6 +2193,7 @@ impl UrlRewriter {
             fhir_context,
             mapping: HashMap::new(),
             reserved_post_ids: HashMap::new(),
+            conditional_create_matches: HashMap::new(),
             canonical_cache: HashMap::new(),
         }
     }
@@ -2026,35 +2219,54 @@ impl UrlRewriter {
         Ok(())
     }
 
-    fn reserve_post_ids(&mut self, entries: &[BundleEntry], post_indices: &[usize]) -> Result<()> {
-        for &idx in post_indices {
-            let entry = &entries[idx];
-            let request = entry.request.as_ref().ok_or_else(|| {
-                crate::Error::InvalidResource(format!("Transaction entry {} missing request", idx))
-            })?;
-            let parsed = ParsedUrl::parse(&request.url);
-            let resource_type = parsed.resource_type.clone().ok_or_else(|| {
-                crate::Error::InvalidResource(format!(
-                    "Transaction entry {} POST missing resource type in request.url",
-                    idx
-                ))
-            })?;
-
-            let id = Uuid::new_v4().to_string();
-            self.reserved_post_ids.insert(idx, id.clone());
-
-            if let Some(full_url) = &entry.full_url {
+    fn reserve_post_ids(
+        &mut self,
+        entries: &[BundleEntry],
+        post_ids: Vec<(usize, String, String)>,
+    ) {
+        for (idx, resource_type, id) in post_ids {
+            if let Some(full_url) = &entries[idx].full_url {
                 self.mapping
                     .insert(full_url.clone(), format!("{}/{}", resource_type, id));
             }
+            self.reserved_post_ids.insert(idx, id);
         }
-        Ok(())
     }
 
     fn reserved_post_id(&self, index: usize) -> Option<String> {
         self.reserved_post_ids.get(&index).cloned()
     }
 
+    /// Point the entry's `fullUrl` at the existing resource instead of its reserved id.
+    fn record_conditional_create_match(
+        &mut self,
+        index: usize,
+        full_url: Option<&str>,
+        resource_type: &str,
+        id: String,
+    ) {
+        if let Some(full_url) = full_url {
+            self.mapping
+                .insert(full_url.to_string(), format!("{}/{}", resource_type, id));
+        }
+        self.conditional_create_matches.insert(index, id);
+    }
+
+    fn conditional_create_match(&self, index: usize) -> Option<String> {
+        self.conditional_create_matches.get(&index).cloned()
+    }
+
+    /// Replace fullUrl references in search parameter values (conditional criteria).
+    fn rewrite_query_items(&self, items: Vec<(String, String)>) -> Vec<(String, String)> {
+        items
+            .into_iter()
+            .map(|(name, value)| {
+                let value = rewrite_string(&value, &self.mapping).unwrap_or(value);
+                (name, value)
+            })
+            .collect()
+    }
+
     fn rewrite_resource(&mut self, resource: &mut JsonValue) -> Result<()> {
         if self.mapping.is_empty() {
             return Ok(());
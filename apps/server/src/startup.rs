@@ -213,7 +213,9 @@ async fn install_public_packages(
     config: &Config,
 ) -> Result<()> {
     // Create registry client (uses ~/.fhir/packages cache by default)
-    let registry = Arc::new(RegistryClient::new(None));
+    let mut registry = RegistryClient::new(None);
+    registry.set_resumable_downloads(config.fhir.resumable_package_downloads);
+    let registry = Arc::new(registry);
 
     // Get default packages (core, extensions, terminology)
     let mut package_descriptors =
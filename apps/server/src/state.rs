@@ -8,20 +8,21 @@ use crate::{
         PostgresResourceStore, RuntimeConfigRepository,
     },
     hooks::{
-        compartment_definition::CompartmentDefinitionHook, search_parameter::SearchParameterHook,
-        terminology::TerminologyHook, ResourceHook,
+        compartment_definition::CompartmentDefinitionHook, search_cache::SearchCacheHook,
+        search_parameter::SearchParameterHook, terminology::TerminologyHook, ResourceHook,
     },
     queue::{InlineJobQueue, JobQueue, PostgresJobQueue},
     runtime_config::RuntimeConfigCache,
     services::{
-        AdminService, ConditionalReferenceResolver, CrudService, MetadataService, MetricsService,
-        OperationExecutor, OperationRegistry, PackageService, RuntimeConfigService, SearchService,
-        SystemService, TerminologyService,
+        search_cache::SearchCache, AdminService, ConditionalReferenceResolver, CrudService,
+        MetadataService, MetricsService, OperationExecutor, OperationRegistry, PackageService,
+        RuntimeConfigService, SearchService, SystemService, TerminologyService,
     },
     Result,
 };
 use sqlx::PgPool;
 use std::sync::Arc;
+use std::time::Duration;
 use ferrum_context::FhirContext;
 use ferrum_fhirpath::Engine as FhirPathEngine;
 
@@ -58,6 +59,7 @@ pub struct AppState {
     pub config: Arc<Config>,
     pub auth: Arc<crate::auth::AuthManager>,
     pub admin_auth: Arc<crate::admin_auth::AdminAuthManager>,
+    pub security_label_policy: Arc<dyn crate::security_labels::SecurityLabelPolicy>,
     pub db_pool: PgPool,
     pub job_queue: Arc<dyn JobQueue>,
     pub resource_hooks: Vec<Arc<dyn ResourceHook>>,
@@ -71,6 +73,9 @@ pub struct AppState {
     pub transaction_service: Arc<crate::services::TransactionService>,
     pub history_service: Arc<crate::services::HistoryService>,
     pub search_service: Arc<SearchService>,
+    /// Search result cache, when `fhir.search.cache.enabled`
+    pub search_cache: Option<Arc<SearchCache>>,
+    pub summary_filter: Arc<crate::services::SummaryFilter>,
     pub conditional_service: Arc<crate::services::conditional::ConditionalService>,
     pub conditional_reference_resolver: Arc<ConditionalReferenceResolver>,
     pub system_service: Arc<SystemService>,
@@ -213,8 +218,15 @@ impl AppState {
             runtime_config_cache.clone(),
         ));
 
+        let search_cache = config_arc.fhir.search.cache.enabled.then(|| {
+            Arc::new(SearchCache::new(
+                Duration::from_secs(config_arc.fhir.search.cache.ttl_seconds),
+                config_arc.fhir.search.cache.max_entries,
+            ))
+        });
+
         // Initialize resource hooks
-        let resource_hooks: Vec<Arc<dyn ResourceHook>> = vec![
+        let mut resource_hooks: Vec<Arc<dyn ResourceHook>> = vec![
             Arc::new(SearchParameterHook::new(
                 db_pool.clone(),
                 indexing_service.clone(),
@@ -228,6 +240,10 @@ impl AppState {
             Arc::new(TerminologyHook::new(db_pool.clone())),
             Arc::new(CompartmentDefinitionHook::new(db_pool.clone())),
         ];
+        // Last, so search definitions are updated before cached results are evicted.
+        if let Some(cache) = &search_cache {
+            resource_hooks.push(Arc::new(SearchCacheHook::new(cache.clone())));
+        }
         let mut crud_service_inner = CrudService::with_hooks_and_indexing_and_runtime_config(
             store.clone(),
             resource_hooks.clone(),
@@ -237,9 +253,10 @@ impl AppState {
             config_arc.fhir.hard_delete,
             runtime_config_cache.clone(),
         );
-        crud_service_inner.set_referential_integrity_mode(
-            config_arc.fhir.referential_integrity.mode.clone(),
-        );
+        crud_service_inner
+            .set_referential_integrity_mode(config_arc.fhir.referential_integrity.mode);
+        crud_service_inner.set_id_strategy(config_arc.fhir.id_strategy);
+        crud_service_inner.set_generate_narrative(config_arc.fhir.resources.generate_narrative);
         let crud_service = Arc::new(crud_service_inner);
 
         let conditional_service = Arc::new(crate::services::conditional::ConditionalService::new(
@@ -265,9 +282,10 @@ impl AppState {
             config_arc.fhir.hard_delete,
             runtime_config_cache.clone(),
         );
-        batch_service_inner.set_referential_integrity_mode(
-            config_arc.fhir.referential_integrity.mode.clone(),
-        );
+        batch_service_inner
+            .set_referential_integrity_mode(config_arc.fhir.referential_integrity.mode);
+        batch_service_inner.set_id_strategy(config_arc.fhir.id_strategy);
+        batch_service_inner.set_generate_narrative(config_arc.fhir.resources.generate_narrative);
         batch_service_inner.set_transaction_recorder(transaction_recorder.clone());
         let batch_service = Arc::new(batch_service_inner);
         let mut transaction_service_inner =
@@ -281,10 +299,15 @@ impl AppState {
                 config_arc.fhir.hard_delete,
                 runtime_config_cache.clone(),
             );
-        transaction_service_inner.set_referential_integrity_mode(
-            config_arc.fhir.referential_integrity.mode.clone(),
-        );
+        transaction_service_inner
+            .set_referential_integrity_mode(config_arc.fhir.referential_integrity.mode);
+        transaction_service_inner.set_id_strategy(config_arc.fhir.id_strategy);
+        transaction_service_inner
+            .set_generate_narrative(config_arc.fhir.resources.generate_narrative);
         transaction_service_inner.set_transaction_recorder(transaction_recorder);
+        if let Some(cache) = &search_cache {
+            transaction_service_inner.set_search_cache(cache.clone());
+        }
         let transaction_service = Arc::new(transaction_service_inner);
         let history_service = Arc::new(crate::services::HistoryService::new_with_runtime_config(
             store.clone(),
@@ -297,11 +320,15 @@ impl AppState {
 
         // Create search service with summary filtering
         let summary_filter = Arc::new(crate::services::SummaryFilter::new(fhir_context.clone()));
-        let search_service = Arc::new(SearchService::with_summary_filter(
+        let mut search_service_inner = SearchService::with_summary_filter(
             search_engine.clone(),
-            summary_filter,
+            summary_filter.clone(),
             runtime_config_cache.clone(),
-        ));
+        );
+        if let Some(cache) = &search_cache {
+            search_service_inner.set_search_cache(cache.clone());
+        }
+        let search_service = Arc::new(search_service_inner);
         let system_service = Arc::new(SystemService::new(
             search_engine.clone(),
             crud_service.clone(),
@@ -319,6 +346,8 @@ impl AppState {
             })?,
         );
         let admin_auth = Arc::new(crate::admin_auth::AdminAuthManager::new(config_arc.clone()));
+        let security_label_policy =
+            crate::security_labels::policy_from_config(&config_arc.auth.security_labels);
         let metadata_repo = crate::db::MetadataRepository::new(db_pool.clone());
         let metadata_service = Arc::new(MetadataService::new(config_arc.clone(), metadata_repo));
 
@@ -335,14 +364,23 @@ impl AppState {
 
         // Create operation services
         let operation_registry = Arc::new(OperationRegistry::new(Arc::new(store.clone())));
-        let operation_executor = Arc::new(OperationExecutor::with_services(
+        let mut operation_executor_inner = OperationExecutor::with_services(
             package_service.clone(),
             indexing_service.clone(),
             terminology_service,
             job_queue.clone(),
             search_engine.clone(),
             store.clone(),
-        ));
+            fhirpath_engine.clone(),
+        );
+        operation_executor_inner
+            .set_fhirpath_operation_enabled(config_arc.fhir.fhirpath.enable_operation);
+        operation_executor_inner.set_expunge_enabled(config_arc.fhir.enable_expunge);
+        operation_executor_inner.set_fhir_context(fhir_context.clone());
+        if let Some(cache) = &search_cache {
+            operation_executor_inner.set_search_cache(cache.clone());
+        }
+        let operation_executor = Arc::new(operation_executor_inner);
 
         // Load operation definitions from database (after packages are installed)
         if options.load_operation_definitions {
@@ -356,6 +394,7 @@ impl AppState {
             config: config_arc,
             auth,
             admin_auth,
+            security_label_policy,
             db_pool,
             job_queue,
             resource_hooks,
@@ -369,6 +408,8 @@ impl AppState {
             transaction_service,
             history_service,
             search_service,
+            search_cache,
+            summary_filter,
             conditional_service,
             conditional_reference_resolver,
             system_service,
@@ -0,0 +1,66 @@
+//! Bulk Data export worker
+
+use super::base::{Worker, WorkerConfig};
+use crate::{
+    db::PostgresResourceStore,
+    queue::{Job, JobQueue},
+    services::bulk_export::{BulkExportService, BULK_EXPORT_JOB_TYPE},
+    Result,
+};
+use async_trait::async_trait;
+use sqlx::PgPool;
+use std::sync::Arc;
+
+pub struct ExportWorker {
+    job_queue: Arc<dyn JobQueue>,
+    export_service: BulkExportService,
+    _config: WorkerConfig,
+}
+
+impl ExportWorker {
+    pub fn new(pool: PgPool, job_queue: Arc<dyn JobQueue>, config: WorkerConfig) -> Self {
+        Self {
+            job_queue,
+            export_service: BulkExportService::new(PostgresResourceStore::new(pool)),
+            _config: config,
+        }
+    }
+}
+
+#[async_trait]
+impl Worker for ExportWorker {
+    fn name(&self) -> &str {
+        "ExportWorker"
+    }
+
+    fn supported_job_types(&self) -> &[&str] {
+        &[BULK_EXPORT_JOB_TYPE]
+    }
+
+    async fn start(&self) -> Result<()> {
+        tracing::info!("{} starting...", self.name());
+        Ok(())
+    }
+
+    async fn stop(&self) -> Result<()> {
+        tracing::info!("{} stopping...", self.name());
+        Ok(())
+    }
+
+    async fn process_job(&self, job: Job) -> Result<()> {
+        let job_start = std::time::Instant::now();
+        tracing::info!("{} processing bulk_export job: {}", self.name(), job.id);
+
+        self.export_service
+            .run(self.job_queue.as_ref(), job.id)
+            .await?;
+
+        tracing::info!(
+            "{} completed bulk_export job: {} in {:?}",
+            self.name(),
+            job.id,
+            job_start.elapsed()
+        );
+        Ok(())
+    }
+}
@@ -151,6 +151,7 @@ impl IndexingWorker {
         } else {
             // Type-level reindex: cursor through all resources in batches
             let resource_type = params.resource_type.as_deref();
+            let started_at = chrono::Utc::now();
             let mut after_id: Option<String> = None;
 
             loop {
@@ -202,6 +203,12 @@ impl IndexingWorker {
                 // Advance cursor to the last ID in this page
                 after_id = page.last().map(|(_, id)| id.clone());
             }
+
+            // Every resource stored before the reindex started now carries index rows for
+            // the parameters that existed at that point.
+            self.indexing_service
+                .mark_search_parameters_indexed(resource_type, started_at)
+                .await?;
         }
 
         self.job_queue
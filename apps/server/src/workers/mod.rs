@@ -4,6 +4,7 @@
 //! Each worker type handles specific job types.
 
 mod base;
+mod export_worker;
 mod indexing_worker;
 mod package_worker;
 mod runner;
@@ -11,6 +12,7 @@ mod state;
 mod terminology_worker;
 
 pub use base::{Worker, WorkerConfig};
+pub use export_worker::ExportWorker;
 pub use indexing_worker::IndexingWorker;
 pub use package_worker::PackageWorker;
 pub use runner::{
@@ -24,7 +26,7 @@ use crate::Result;
 
 /// Create all configured workers using lightweight WorkerState
 pub fn create_workers(state: &WorkerState, config: WorkerConfig) -> Result<Vec<Box<dyn Worker>>> {
-    let mut workers: Vec<Box<dyn Worker>> = Vec::with_capacity(3);
+    let mut workers: Vec<Box<dyn Worker>> = Vec::with_capacity(4);
 
     // Package installation worker
     // Note: registry_url in config is the package registry URL (e.g., https://packages.fhir.org)
@@ -33,6 +35,7 @@ pub fn create_workers(state: &WorkerState, config: WorkerConfig) -> Result<Vec<B
         state.job_queue.clone(),
         state.indexing_service.clone(),
         None, // Use default cache directory (~/.fhir/packages)
+        state.config.fhir.resumable_package_downloads,
         state
             .config
             .fhir
@@ -56,5 +59,12 @@ pub fn create_workers(state: &WorkerState, config: WorkerConfig) -> Result<Vec<B
         config.clone(),
     )));
 
+    // Bulk Data export worker
+    workers.push(Box::new(ExportWorker::new(
+        state.db_pool.clone(),
+        state.job_queue.clone(),
+        config.clone(),
+    )));
+
     Ok(workers)
 }
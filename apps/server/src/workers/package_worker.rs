@@ -19,6 +19,7 @@ pub struct PackageWorker {
     job_queue: Arc<dyn JobQueue>,
     indexing_service: Arc<IndexingService>,
     registry_cache_dir: Option<std::path::PathBuf>,
+    resumable_downloads: bool,
     search_parameter_active_statuses: Vec<String>,
     _config: WorkerConfig,
 }
@@ -28,6 +29,7 @@ impl PackageWorker {
         job_queue: Arc<dyn JobQueue>,
         indexing_service: Arc<IndexingService>,
         registry_cache_dir: Option<std::path::PathBuf>,
+        resumable_downloads: bool,
         search_parameter_active_statuses: Vec<String>,
         config: WorkerConfig,
     ) -> Self {
@@ -35,6 +37,7 @@ impl PackageWorker {
             job_queue,
             indexing_service,
             registry_cache_dir,
+            resumable_downloads,
             search_parameter_active_statuses,
             _config: config,
         }
@@ -128,7 +131,8 @@ impl Worker for PackageWorker {
         );
 
         // Load package from registry
-        let registry = RegistryClient::new(self.registry_cache_dir.clone());
+        let mut registry = RegistryClient::new(self.registry_cache_dir.clone());
+        registry.set_resumable_downloads(self.resumable_downloads);
         let packages = if include_dependencies {
             registry
                 .load_package_with_dependencies(package_name, package_version.as_deref())
@@ -0,0 +1,348 @@
+#![allow(unused)]
+#[allow(unused)]
+mod support;
+
+use axum::{
+    body::Bytes,
+    http::{Method, StatusCode},
+};
+use serde_json::{json, Value};
+use support::*;
+
+/// Register `code` at every level so the operation router accepts `$code`.
+async fn register_async_operation(app: &TestApp, code: &str) -> anyhow::Result<()> {
+    register_operation(
+        app,
+        OperationFixture {
+            code,
+            system: true,
+            type_level: true,
+            instance: true,
+            affects_state: true,
+            ..Default::default()
+        },
+    )
+    .await
+}
+
+/// Strip scheme and host so absolute URLs returned by the server can be replayed in-process.
+fn local_path(url: &str) -> &str {
+    let start = url.find("/fhir/").expect("URL under /fhir");
+    &url[start..]
+}
+
+/// Poll the status endpoint until the request leaves the 202 "in progress" state.
+async fn poll_until_done(app: &TestApp, status_url: &str) -> anyhow::Result<(StatusCode, Bytes)> {
+    for _ in 0..100 {
+        let (status, _headers, body) = app
+            .request(Method::GET, local_path(status_url), None)
+            .await?;
+        if status != StatusCode::ACCEPTED {
+            return Ok((status, body));
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    anyhow::bail!("async request did not complete: {}", status_url)
+}
+
+#[tokio::test]
+async fn respond_async_operation_is_pollable_until_completed() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            register_async_operation(app, "reindex").await?;
+            let (status, _headers, _body) = app
+                .request(
+                    Method::POST,
+                    "/fhir/Patient",
+                    Some(to_json_body(&minimal_patient())?),
+                )
+                .await?;
+            assert_status(status, StatusCode::CREATED, "create Patient");
+
+            let (status, headers, body) = app
+                .request_with_extra_headers(
+                    Method::POST,
+                    "/fhir/Patient/$reindex",
+                    None,
+                    &[("prefer", "respond-async")],
+                )
+                .await?;
+            assert_status(status, StatusCode::ACCEPTED, "$reindex kick-off");
+            assert!(body.is_empty());
+            let status_url = headers
+                .get("content-location")
+                .and_then(|v| v.to_str().ok())
+                .expect("Content-Location header")
+                .to_string();
+            assert!(status_url.contains("$async-status?_jobId="));
+
+            // 202 while the operation runs, then 200 with the result
+            let (status, body) = poll_until_done(app, &status_url).await?;
+            assert_status(status, StatusCode::OK, "async request status");
+
+            let bundle: Value = serde_json::from_slice(&body)?;
+            assert_eq!(bundle["resourceType"], "Bundle");
+            assert_eq!(bundle["type"], "batch-response");
+            let entry = &bundle["entry"][0];
+            assert_eq!(entry["response"]["status"], "200 OK");
+            assert_eq!(entry["resource"]["resourceType"], "Parameters");
+
+            // The job records the request and where its result can be fetched
+            let job_id = status_url.rsplit('=').next().unwrap().parse()?;
+            let job = app
+                .state
+                .job_queue
+                .get_job(job_id)
+                .await?
+                .expect("async_operation job");
+            assert_eq!(job.job_type, "async_operation");
+            assert_eq!(job.parameters["operation"], "reindex");
+            assert_eq!(job.progress.unwrap()["location"], status_url.as_str());
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn respond_async_operation_errors_are_reported_in_the_result() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            register_async_operation(app, "install-package").await?;
+
+            // $install-package without its required `name` parameter
+            let (status, headers, _body) = app
+                .request_with_extra_headers(
+                    Method::POST,
+                    "/fhir/$install-package",
+                    None,
+                    &[("prefer", "respond-async")],
+                )
+                .await?;
+            assert_status(status, StatusCode::ACCEPTED, "$install-package kick-off");
+            let status_url = headers
+                .get("content-location")
+                .and_then(|v| v.to_str().ok())
+                .expect("Content-Location header")
+                .to_string();
+
+            let (status, body) = poll_until_done(app, &status_url).await?;
+            assert_status(status, StatusCode::OK, "async request status");
+
+            let bundle: Value = serde_json::from_slice(&body)?;
+            let response = &bundle["entry"][0]["response"];
+            assert_eq!(response["status"], "400 Bad Request");
+            assert_eq!(response["outcome"]["resourceType"], "OperationOutcome");
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+fn principal(subject: &str) -> ferrum::auth::Principal {
+    ferrum::auth::Principal {
+        subject: subject.to_string(),
+        scopes: vec![],
+        issuer: None,
+        audience: None,
+        client_id: None,
+        patient: None,
+        security_labels: vec![],
+    }
+}
+
+/// Send a request as `principal`, attached the way the auth middleware does.
+async fn request_as(
+    app: &TestApp,
+    method: Method,
+    path: &str,
+    extra_headers: &[(&str, &str)],
+    principal: Option<ferrum::auth::Principal>,
+) -> anyhow::Result<(StatusCode, axum::http::HeaderMap)> {
+    use tower::ServiceExt as _;
+
+    let mut request = axum::http::Request::builder()
+        .method(method)
+        .uri(path)
+        .header("host", "example.org")
+        .header("accept", "application/fhir+json");
+    for (name, value) in extra_headers {
+        request = request.header(*name, *value);
+    }
+    if let Some(principal) = principal {
+        request = request.extension(principal);
+    }
+    let response = app
+        .router
+        .clone()
+        .oneshot(request.body(axum::body::Body::empty())?)
+        .await?;
+    Ok((response.status(), response.headers().clone()))
+}
+
+#[tokio::test]
+async fn async_status_is_only_visible_to_the_initiating_principal() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            register_async_operation(app, "reindex").await?;
+
+            let (status, headers) = request_as(
+                app,
+                Method::POST,
+                "/fhir/Patient/$reindex",
+                &[("prefer", "respond-async")],
+                Some(principal("user-1")),
+            )
+            .await?;
+            assert_status(status, StatusCode::ACCEPTED, "$reindex kick-off");
+            let status_url = headers["content-location"].to_str()?.to_string();
+            let status_path = local_path(&status_url);
+
+            let (status, _) = request_as(
+                app,
+                Method::GET,
+                status_path,
+                &[],
+                Some(principal("user-2")),
+            )
+            .await?;
+            assert_status(status, StatusCode::NOT_FOUND, "status as another principal");
+            let (status, _) = request_as(app, Method::GET, status_path, &[], None).await?;
+            assert_status(status, StatusCode::NOT_FOUND, "status anonymously");
+
+            for _ in 0..100 {
+                let (status, _) = request_as(
+                    app,
+                    Method::GET,
+                    status_path,
+                    &[],
+                    Some(principal("user-1")),
+                )
+                .await?;
+                if status != StatusCode::ACCEPTED {
+                    assert_status(status, StatusCode::OK, "status as the initiating principal");
+                    return Ok(());
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            }
+            anyhow::bail!("async request did not complete: {}", status_url)
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn pending_async_operation_jobs_are_resumed() -> anyhow::Result<()> {
+    use ferrum::queue::{JobPriority, JobStatus};
+    use ferrum::services::async_operation;
+
+    with_test_app(|app| {
+        Box::pin(async move {
+            register_async_operation(app, "reindex").await?;
+
+            // A job enqueued before a restart, never claimed
+            let job_id = app
+                .state
+                .job_queue
+                .enqueue(
+                    async_operation::ASYNC_OPERATION_JOB_TYPE.to_string(),
+                    json!({
+                        "operation": "reindex",
+                        "request": "http://example.org/fhir/Patient/$reindex",
+                        "parameters": {"resourceType": "Parameters"},
+                        "resource_type": "Patient",
+                        "base_url": "http://example.org/fhir"
+                    }),
+                    JobPriority::Normal,
+                    None,
+                )
+                .await?;
+            let job = app.state.job_queue.get_job(job_id).await?.unwrap();
+            assert_eq!(job.status, JobStatus::Pending);
+
+            async_operation::run_pending(
+                app.state.job_queue.clone(),
+                app.state.operation_executor.clone(),
+            )
+            .await;
+
+            let job = app.state.job_queue.get_job(job_id).await?.unwrap();
+            assert_eq!(job.status, JobStatus::Completed);
+            assert_eq!(
+                job.progress.unwrap()["entry"]["response"]["status"],
+                "200 OK"
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn running_async_operation_jobs_are_failed_on_resume() -> anyhow::Result<()> {
+    use ferrum::queue::{JobPriority, JobStatus};
+    use ferrum::services::async_operation;
+
+    with_test_app(|app| {
+        Box::pin(async move {
+            register_async_operation(app, "reindex").await?;
+
+            let mut job_ids = Vec::new();
+            for _ in 0..2 {
+                let job_id = app
+                    .state
+                    .job_queue
+                    .enqueue(
+                        async_operation::ASYNC_OPERATION_JOB_TYPE.to_string(),
+                        json!({
+                            "operation": "reindex",
+                            "request": "http://example.org/fhir/Patient/$reindex",
+                            "parameters": {"resourceType": "Parameters"},
+                            "resource_type": "Patient",
+                            "base_url": "http://example.org/fhir"
+                        }),
+                        JobPriority::Normal,
+                        None,
+                    )
+                    .await?;
+                job_ids.push(job_id);
+            }
+
+            // The previous process claimed one job and stopped before completing it
+            let claimed = app
+                .state
+                .job_queue
+                .dequeue(
+                    &[async_operation::ASYNC_OPERATION_JOB_TYPE.to_string()],
+                    "api-previous",
+                )
+                .await?
+                .expect("pending job");
+
+            async_operation::resume(
+                app.state.job_queue.clone(),
+                app.state.operation_executor.clone(),
+            )
+            .await;
+
+            for job_id in job_ids {
+                let job = app.state.job_queue.get_job(job_id).await?.unwrap();
+                if job_id == claimed.id {
+                    assert_eq!(job.status, JobStatus::Failed);
+                    assert_eq!(
+                        job.error_message.as_deref(),
+                        Some("Interrupted by a server restart")
+                    );
+                } else {
+                    assert_eq!(job.status, JobStatus::Completed);
+                }
+            }
+
+            Ok(())
+        })
+    })
+    .await
+}
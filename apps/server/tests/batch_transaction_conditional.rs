@@ -262,6 +262,348 @@ async fn transaction_conditional_create_rewrites_references() -> anyhow::Result<
     .await
 }
 
+#[tokio::test]
+async fn transaction_rewrites_urn_uuid_placeholders_to_assigned_ids() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            // The Observation comes first: placeholders resolve regardless of entry order.
+            let bundle = json!({
+                "resourceType": "Bundle",
+                "type": "transaction",
+                "entry": [
+                    {
+                        "request": { "method": "POST", "url": "Observation" },
+                        "resource": {
+                            "resourceType": "Observation",
+                            "status": "final",
+                            "code": { "text": "test" },
+                            "subject": { "reference": "urn:uuid:0b7d5f3e-51f4-4d0c-9b0e-3c1f2a6d8e11" }
+                        }
+                    },
+                    {
+                        "fullUrl": "urn:uuid:0b7d5f3e-51f4-4d0c-9b0e-3c1f2a6d8e11",
+                        "request": { "method": "POST", "url": "Patient" },
+                        "resource": patient_with_mrn("Doe", "456")
+                    }
+                ]
+            });
+
+            let (status, _headers, body) = app
+                .request(Method::POST, "/fhir", Some(to_json_body(&bundle)?))
+                .await?;
+            assert_status(status, StatusCode::OK, "transaction");
+
+            let response: serde_json::Value = serde_json::from_slice(&body)?;
+            let patient_id = response["entry"][1]["resource"]["id"].as_str().unwrap();
+            let observation = &response["entry"][0]["resource"];
+            assert_eq!(
+                observation["subject"]["reference"].as_str().unwrap(),
+                format!("Patient/{}", patient_id)
+            );
+
+            let observation_id = observation["id"].as_str().unwrap();
+            let (status, _headers, body) = app
+                .request(
+                    Method::GET,
+                    &format!("/fhir/Observation/{}", observation_id),
+                    None,
+                )
+                .await?;
+            assert_status(status, StatusCode::OK, "read Observation");
+            let stored: serde_json::Value = serde_json::from_slice(&body)?;
+            assert_eq!(
+                stored["subject"]["reference"].as_str().unwrap(),
+                format!("Patient/{}", patient_id)
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn transaction_conditional_create_match_rewrites_earlier_entries_and_criteria(
+) -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            register_search_parameter(
+                &app.state.db_pool,
+                "identifier",
+                "Patient",
+                "token",
+                "Patient.identifier",
+                &[],
+            )
+            .await?;
+            register_search_parameter(
+                &app.state.db_pool,
+                "subject",
+                "Observation",
+                "reference",
+                "Observation.subject",
+                &[],
+            )
+            .await?;
+
+            let (_status, _headers, body) = app
+                .request(
+                    Method::POST,
+                    "/fhir/Patient",
+                    Some(to_json_body(&patient_with_mrn("Doe", "789"))?),
+                )
+                .await?;
+            let existing: serde_json::Value = serde_json::from_slice(&body)?;
+            let existing_id = existing["id"].as_str().unwrap().to_string();
+
+            let existing_observation = json!({
+                "resourceType": "Observation",
+                "status": "final",
+                "code": { "text": "existing" },
+                "subject": { "reference": format!("Patient/{}", existing_id) }
+            });
+            let (_status, _headers, body) = app
+                .request(
+                    Method::POST,
+                    "/fhir/Observation",
+                    Some(to_json_body(&existing_observation)?),
+                )
+                .await?;
+            let existing_observation: serde_json::Value = serde_json::from_slice(&body)?;
+
+            let bundle = json!({
+                "resourceType": "Bundle",
+                "type": "transaction",
+                "entry": [
+                    {
+                        "request": { "method": "POST", "url": "Observation" },
+                        "resource": {
+                            "resourceType": "Observation",
+                            "status": "final",
+                            "code": { "text": "new" },
+                            "subject": { "reference": "urn:uuid:pt1" }
+                        }
+                    },
+                    {
+                        "request": {
+                            "method": "POST",
+                            "url": "Observation",
+                            "ifNoneExist": "subject=urn:uuid:pt1"
+                        },
+                        "resource": {
+                            "resourceType": "Observation",
+                            "status": "final",
+                            "code": { "text": "duplicate" },
+                            "subject": { "reference": "urn:uuid:pt1" }
+                        }
+                    },
+                    {
+                        "fullUrl": "urn:uuid:pt1",
+                        "request": {
+                            "method": "POST",
+                            "url": "Patient",
+                            "ifNoneExist": "identifier=http://example.org/fhir/mrn|789"
+                        },
+                        "resource": patient_with_mrn("Other", "789")
+                    }
+                ]
+            });
+
+            let (status, _headers, body) = app
+                .request(Method::POST, "/fhir", Some(to_json_body(&bundle)?))
+                .await?;
+            assert_status(status, StatusCode::OK, "transaction");
+            let response: serde_json::Value = serde_json::from_slice(&body)?;
+
+            let new_observation = &response["entry"][0];
+            assert_eq!(
+                status_code_prefix(new_observation["response"]["status"].as_str().unwrap()),
+                "201"
+            );
+            assert_eq!(
+                new_observation["resource"]["subject"]["reference"]
+                    .as_str()
+                    .unwrap(),
+                format!("Patient/{}", existing_id)
+            );
+
+            // `subject=urn:uuid:pt1` was searched as the matched Patient.
+            let duplicate = &response["entry"][1];
+            assert_eq!(
+                status_code_prefix(duplicate["response"]["status"].as_str().unwrap()),
+                "200"
+            );
+            assert_resource_id(
+                &duplicate["resource"],
+                existing_observation["id"].as_str().unwrap(),
+            )?;
+
+            let patient_entry = &response["entry"][2];
+            assert_eq!(
+                status_code_prefix(patient_entry["response"]["status"].as_str().unwrap()),
+                "200"
+            );
+            assert_resource_id(&patient_entry["resource"], &existing_id)?;
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn transaction_conditional_creates_with_circular_criteria_are_rejected() -> anyhow::Result<()>
+{
+    with_test_app(|app| {
+        Box::pin(async move {
+            register_search_parameter(
+                &app.state.db_pool,
+                "subject",
+                "Observation",
+                "reference",
+                "Observation.subject",
+                &[],
+            )
+            .await?;
+            register_search_parameter(
+                &app.state.db_pool,
+                "link",
+                "Patient",
+                "reference",
+                "Patient.link.other",
+                &[],
+            )
+            .await?;
+
+            let bundle = json!({
+                "resourceType": "Bundle",
+                "type": "transaction",
+                "entry": [
+                    {
+                        "fullUrl": "urn:uuid:obs1",
+                        "request": {
+                            "method": "POST",
+                            "url": "Observation",
+                            "ifNoneExist": "subject=urn:uuid:pt1"
+                        },
+                        "resource": {
+                            "resourceType": "Observation",
+                            "status": "final",
+                            "code": { "text": "obs" },
+                            "subject": { "reference": "urn:uuid:pt1" }
+                        }
+                    },
+                    {
+                        "fullUrl": "urn:uuid:pt1",
+                        "request": {
+                            "method": "POST",
+                            "url": "Patient",
+                            "ifNoneExist": "link=urn:uuid:obs1"
+                        },
+                        "resource": { "resourceType": "Patient" }
+                    }
+                ]
+            });
+
+            let (status, _headers, body) = app
+                .request(Method::POST, "/fhir", Some(to_json_body(&bundle)?))
+                .await?;
+            assert_status(status, StatusCode::BAD_REQUEST, "circular transaction");
+            let outcome: serde_json::Value = serde_json::from_slice(&body)?;
+            assert!(
+                outcome.to_string().contains("circular"),
+                "unexpected outcome: {}",
+                outcome
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn transaction_conditional_create_dependencies_match_full_urls_exactly() -> anyhow::Result<()>
+{
+    with_test_app(|app| {
+        Box::pin(async move {
+            register_search_parameter(
+                &app.state.db_pool,
+                "subject",
+                "Observation",
+                "reference",
+                "Observation.subject",
+                &[],
+            )
+            .await?;
+            register_search_parameter(
+                &app.state.db_pool,
+                "link",
+                "Patient",
+                "reference",
+                "Patient.link.other",
+                &[],
+            )
+            .await?;
+
+            // `urn:uuid:pt10` only shares a prefix with the fullUrl of the second entry, and the
+            // third entry refers to itself; neither is a dependency cycle.
+            let bundle = json!({
+                "resourceType": "Bundle",
+                "type": "transaction",
+                "entry": [
+                    {
+                        "fullUrl": "urn:uuid:obs1",
+                        "request": {
+                            "method": "POST",
+                            "url": "Observation",
+                            "ifNoneExist": "subject=urn:uuid:pt10"
+                        },
+                        "resource": {
+                            "resourceType": "Observation",
+                            "status": "final",
+                            "code": { "text": "obs" }
+                        }
+                    },
+                    {
+                        "fullUrl": "urn:uuid:pt1",
+                        "request": {
+                            "method": "POST",
+                            "url": "Patient",
+                            "ifNoneExist": "link=urn:uuid:obs1"
+                        },
+                        "resource": { "resourceType": "Patient" }
+                    },
+                    {
+                        "fullUrl": "urn:uuid:pt2",
+                        "request": {
+                            "method": "POST",
+                            "url": "Patient",
+                            "ifNoneExist": "link=urn:uuid:pt2"
+                        },
+                        "resource": { "resourceType": "Patient" }
+                    }
+                ]
+            });
+
+            let (status, _headers, body) = app
+                .request(Method::POST, "/fhir", Some(to_json_body(&bundle)?))
+                .await?;
+            assert_status(status, StatusCode::OK, "transaction");
+            let response: serde_json::Value = serde_json::from_slice(&body)?;
+            for entry in response["entry"].as_array().unwrap() {
+                assert_eq!(
+                    status_code_prefix(entry["response"]["status"].as_str().unwrap()),
+                    "201"
+                );
+            }
+
+            Ok(())
+        })
+    })
+    .await
+}
+
 #[tokio::test]
 async fn batch_conditional_patch_updates() -> anyhow::Result<()> {
     with_test_app(|app| {
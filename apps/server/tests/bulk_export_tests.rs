@@ -0,0 +1,135 @@
+#![allow(unused)]
+#[allow(unused)]
+mod support;
+
+use axum::http::{Method, StatusCode};
+use serde_json::{json, Value};
+use support::*;
+
+fn parse_json(body: &[u8]) -> anyhow::Result<Value> {
+    Ok(serde_json::from_slice(body)?)
+}
+
+/// Strip scheme and host so absolute URLs returned by the server can be replayed in-process.
+fn local_path(url: &str) -> &str {
+    let start = url.find("/fhir/").expect("URL under /fhir");
+    &url[start..]
+}
+
+async fn create(app: &TestApp, resource_type: &str, resource: Value) -> anyhow::Result<()> {
+    let (status, _headers, _body) = app
+        .request(
+            Method::POST,
+            &format!("/fhir/{}", resource_type),
+            Some(to_json_body(&resource)?),
+        )
+        .await?;
+    assert_status(
+        status,
+        StatusCode::CREATED,
+        &format!("create {}", resource_type),
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn system_export_writes_one_ndjson_file_per_type() -> anyhow::Result<()> {
+    let output_dir = std::env::temp_dir().join(format!("ferrum-export-{}", uuid::Uuid::new_v4()));
+    let output_dir_str = output_dir.to_string_lossy().to_string();
+
+    let result = with_test_app_with_config(
+        |config| {
+            config.fhir.bulk_export.output_dir = output_dir_str;
+        },
+        |app| {
+            Box::pin(async move {
+                create(app, "Patient", minimal_patient()).await?;
+                create(app, "Patient", minimal_patient()).await?;
+                create(
+                    app,
+                    "Observation",
+                    json!({
+                        "resourceType": "Observation",
+                        "status": "final",
+                        "code": {"text": "heart rate"}
+                    }),
+                )
+                .await?;
+
+                let (status, headers, _body) = app
+                    .request_with_extra_headers(
+                        Method::GET,
+                        "/fhir/$export?_type=Patient,Observation,Patient",
+                        None,
+                        &[("prefer", "respond-async")],
+                    )
+                    .await?;
+                assert_status(status, StatusCode::ACCEPTED, "$export kick-off");
+                let status_url = headers
+                    .get("content-location")
+                    .and_then(|v| v.to_str().ok())
+                    .expect("Content-Location header")
+                    .to_string();
+                assert!(status_url.contains("$export-poll-status"));
+
+                let (status, _headers, body) = app
+                    .request(Method::GET, local_path(&status_url), None)
+                    .await?;
+                assert_status(status, StatusCode::OK, "$export status");
+                let manifest = parse_json(&body)?;
+                assert!(manifest["transactionTime"].is_string());
+                assert!(manifest["request"]
+                    .as_str()
+                    .unwrap()
+                    .ends_with("/$export?_type=Patient%2CObservation%2CPatient"));
+
+                let output = manifest["output"].as_array().unwrap();
+                assert_eq!(output.len(), 2, "one file per exported type");
+                let types: Vec<&str> = output.iter().map(|o| o["type"].as_str().unwrap()).collect();
+                assert_eq!(types, vec!["Patient", "Observation"], "in _type order");
+
+                for file in output {
+                    let url = file["url"].as_str().unwrap();
+                    let (status, headers, body) =
+                        app.request(Method::GET, local_path(url), None).await?;
+                    assert_status(status, StatusCode::OK, "download export file");
+                    assert_eq!(
+                        headers.get("content-type").unwrap(),
+                        "application/fhir+ndjson"
+                    );
+
+                    let lines: Vec<Value> = std::str::from_utf8(&body)?
+                        .lines()
+                        .map(serde_json::from_str)
+                        .collect::<Result<_, _>>()?;
+                    assert_eq!(lines.len() as u64, file["count"].as_u64().unwrap());
+                    assert!(lines.iter().all(|r| r["resourceType"] == file["type"]));
+                }
+                assert_eq!(
+                    output.iter().find(|o| o["type"] == "Patient").unwrap()["count"],
+                    2
+                );
+
+                Ok(())
+            })
+        },
+    )
+    .await;
+
+    let _ = std::fs::remove_dir_all(&output_dir);
+    result
+}
+
+#[tokio::test]
+async fn export_without_respond_async_is_rejected() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            let (status, _headers, _body) = app
+                .request(Method::GET, "/fhir/Patient/$export", None)
+                .await?;
+            assert_status(status, StatusCode::BAD_REQUEST, "$export without Prefer");
+            Ok(())
+        })
+    })
+    .await
+}
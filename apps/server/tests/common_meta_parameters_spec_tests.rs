@@ -248,3 +248,71 @@ async fn tag_and_security_token_semantics_match_system_only_and_code_only() -> a
     })
     .await
 }
+
+/// `_tag`, `_security` and `_profile` are built in: they are indexed from `meta` and
+/// searchable even when no SearchParameter resource defines them.
+#[tokio::test]
+async fn meta_params_are_searchable_without_search_parameter_resources() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            let tagged = json!({
+                "resourceType": "Patient",
+                "meta": {
+                    "profile": ["http://example.org/StructureDefinition/tagged-patient"],
+                    "security": [{
+                        "system": "http://terminology.hl7.org/CodeSystem/v3-Confidentiality",
+                        "code": "R"
+                    }],
+                    "tag": [{"system": "http://sys", "code": "code"}]
+                }
+            });
+            let other = json!({
+                "resourceType": "Patient",
+                "meta": {"tag": [{"system": "http://other", "code": "code"}]}
+            });
+
+            let (status, _headers, body) = app
+                .request(Method::POST, "/fhir/Patient", Some(to_json_body(&tagged)?))
+                .await?;
+            assert_status(status, StatusCode::CREATED, "create tagged Patient");
+            let id = parse_json(&body)?["id"].as_str().unwrap().to_string();
+
+            let (status, _headers, _body) = app
+                .request(Method::POST, "/fhir/Patient", Some(to_json_body(&other)?))
+                .await?;
+            assert_status(status, StatusCode::CREATED, "create other Patient");
+
+            for (query, label) in [
+                ("_tag=http://sys|code", "_tag system|code"),
+                (
+                    "_security=http://terminology.hl7.org/CodeSystem/v3-Confidentiality|R",
+                    "_security system|code",
+                ),
+                (
+                    "_profile=http://example.org/StructureDefinition/tagged-patient",
+                    "_profile",
+                ),
+            ] {
+                let (status, _headers, body) = app
+                    .request(Method::GET, &format!("/fhir/Patient?{}", query), None)
+                    .await?;
+                assert_status(status, StatusCode::OK, label);
+                let bundle = parse_json(&body)?;
+                let entries = bundle["entry"].as_array().context(label)?;
+                assert_eq!(entries.len(), 1, "{}", label);
+                assert_eq!(entries[0]["resource"]["id"], id, "{}", label);
+            }
+
+            // Code-only `_tag` matches both patients regardless of system.
+            let (status, _headers, body) = app
+                .request(Method::GET, "/fhir/Patient?_tag=code", None)
+                .await?;
+            assert_status(status, StatusCode::OK, "_tag code-only");
+            let bundle = parse_json(&body)?;
+            assert_eq!(bundle["entry"].as_array().map(Vec::len), Some(2));
+
+            Ok(())
+        })
+    })
+    .await
+}
@@ -187,11 +187,8 @@ async fn create_patient_as_json_get_as_xml() -> anyhow::Result<()> {
 async fn create_patient_as_xml_get_as_json() -> anyhow::Result<()> {
     with_test_app(|app| {
         Box::pin(async move {
-            // Build XML body.
-            // Note: The XML→JSON converter does structural mapping — single elements
-            // are NOT auto-wrapped in arrays. FHIR spec requires array knowledge from
-            // StructureDefinitions, so `name` appears as a single object, not an array.
-            // This test verifies the round-trip works (POST XML → stored → GET JSON).
+            // The XML→JSON converter knows element cardinalities, so a single
+            // `<name>` still becomes a one-element `name` array.
             let xml_body = r#"<?xml version="1.0" encoding="UTF-8"?>
 <Patient xmlns="http://hl7.org/fhir">
   <name>
@@ -215,14 +212,9 @@ async fn create_patient_as_xml_get_as_json() -> anyhow::Result<()> {
             assert_status(status, StatusCode::CREATED, "create Patient from XML");
             let created: Value = serde_json::from_slice(&body)?;
             assert_eq!(created["resourceType"], "Patient");
-            // Structural conversion: single `<name>` becomes an object, not an array
-            let name = &created["name"];
-            let family = if name.is_array() {
-                name[0]["family"].as_str()
-            } else {
-                name["family"].as_str()
-            };
-            assert_eq!(family, Some("Doe"), "expected family=Doe");
+            assert!(created["name"].is_array(), "name should be an array");
+            assert_eq!(created["name"][0]["family"], "Doe");
+            assert_eq!(created["name"][0]["given"], json!(["Jane"]));
             let id = created["id"].as_str().unwrap();
 
             // GET as JSON (default) — verify resource was persisted
@@ -232,12 +224,7 @@ async fn create_patient_as_xml_get_as_json() -> anyhow::Result<()> {
             assert_status(status, StatusCode::OK, "read Patient as JSON");
             let read: Value = serde_json::from_slice(&body)?;
             assert_eq!(read["resourceType"], "Patient");
-            let read_name = &read["name"];
-            let read_family = if read_name.is_array() {
-                read_name[0]["family"].as_str()
-            } else {
-                read_name["family"].as_str()
-            };
+            let read_family = read["name"][0]["family"].as_str();
             assert_eq!(read_family, Some("Doe"), "persisted family=Doe");
 
             Ok(())
@@ -246,6 +233,147 @@ async fn create_patient_as_xml_get_as_json() -> anyhow::Result<()> {
     .await
 }
 
+#[tokio::test]
+async fn xml_narrative_is_sanitized_when_configured() -> anyhow::Result<()> {
+    with_test_app_with_config(
+        |config| config.fhir.resources.sanitize_xml_narrative = true,
+        |app| {
+            Box::pin(async move {
+                let xml_body = r#"<Patient xmlns="http://hl7.org/fhir">
+  <text>
+    <status value="generated"/>
+    <div xmlns="http://www.w3.org/1999/xhtml"><p onclick="steal()">Jane</p><script>steal()</script></div>
+  </text>
+  <active value="true"/>
+</Patient>"#;
+
+                let (status, _headers, body) = app
+                    .request_with_extra_headers(
+                        Method::POST,
+                        "/fhir/Patient",
+                        Some(Bytes::from(xml_body)),
+                        &[("content-type", "application/fhir+xml")],
+                    )
+                    .await?;
+                assert_status(status, StatusCode::CREATED, "create Patient from XML");
+                let created: Value = serde_json::from_slice(&body)?;
+                let div = created["text"]["div"].as_str().unwrap_or_default();
+                assert!(div.contains("Jane"), "narrative text kept: {}", div);
+                assert!(!div.contains("script"), "script removed: {}", div);
+                assert!(!div.contains("onclick"), "handler removed: {}", div);
+
+                Ok(())
+            })
+        },
+    )
+    .await
+}
+
+#[tokio::test]
+async fn create_patient_as_xml_get_as_xml() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            let xml_body = r#"<Patient xmlns="http://hl7.org/fhir">
+  <active value="true"/>
+  <name>
+    <family value="Doe"/>
+    <given value="Jane"/>
+  </name>
+</Patient>"#;
+
+            let (status, headers, body) = app
+                .request_with_extra_headers(
+                    Method::POST,
+                    "/fhir/Patient",
+                    Some(Bytes::from(xml_body)),
+                    &[
+                        ("content-type", "application/fhir+xml"),
+                        ("accept", "application/fhir+xml"),
+                    ],
+                )
+                .await?;
+            assert_status(status, StatusCode::CREATED, "create Patient from XML");
+            let ct = headers
+                .get("content-type")
+                .and_then(|v| v.to_str().ok())
+                .unwrap_or("");
+            assert!(
+                ct.starts_with("application/fhir+xml"),
+                "expected fhir+xml content-type on create, got '{}'",
+                ct
+            );
+            let created = String::from_utf8_lossy(&body).to_string();
+            let id = created
+                .split("<id value=\"")
+                .nth(1)
+                .and_then(|rest| rest.split('"').next())
+                .expect("created Patient should carry an id")
+                .to_string();
+
+            let (status, headers, body) = app
+                .request_with_extra_headers(
+                    Method::GET,
+                    &format!("/fhir/Patient/{}", id),
+                    None,
+                    &[("accept", "application/fhir+xml")],
+                )
+                .await?;
+            assert_status(status, StatusCode::OK, "read Patient as XML");
+            let ct = headers
+                .get("content-type")
+                .and_then(|v| v.to_str().ok())
+                .unwrap_or("");
+            assert!(
+                ct.starts_with("application/fhir+xml"),
+                "expected fhir+xml content-type on read, got '{}'",
+                ct
+            );
+
+            let xml = String::from_utf8_lossy(&body);
+            assert!(xml.contains("<Patient xmlns=\"http://hl7.org/fhir\">"));
+            assert!(xml.contains(&format!("<id value=\"{}\"/>", id)));
+            assert!(xml.contains("<active value=\"true\"/>"));
+            assert!(xml.contains("<family value=\"Doe\"/>"));
+            assert!(xml.contains("<given value=\"Jane\"/>"));
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn create_with_malformed_xml_returns_operation_outcome() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            let xml_body =
+                r#"<Patient xmlns="http://hl7.org/fhir"><name><family value="Doe"/></Patient>"#;
+
+            let (status, _headers, body) = app
+                .request_with_extra_headers(
+                    Method::POST,
+                    "/fhir/Patient",
+                    Some(Bytes::from(xml_body)),
+                    &[("content-type", "application/fhir+xml")],
+                )
+                .await?;
+            assert_status(status, StatusCode::BAD_REQUEST, "create with malformed XML");
+
+            let outcome = parse_json(&body)?;
+            assert_eq!(outcome["resourceType"], "OperationOutcome");
+            let diagnostics = outcome["issue"][0]["diagnostics"].as_str().unwrap_or("");
+            assert!(
+                diagnostics.starts_with("Invalid resource: Invalid FHIR XML"),
+                "unexpected diagnostics: {}",
+                diagnostics
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
+
 #[tokio::test]
 async fn search_result_as_xml() -> anyhow::Result<()> {
     with_test_app(|app| {
@@ -288,3 +416,100 @@ async fn search_result_as_xml() -> anyhow::Result<()> {
     })
     .await
 }
+
+/// Turtle well-formedness check: the relative `<Type/id>` subjects the serializer emits are
+/// resolved against a placeholder base IRI.
+fn assert_well_formed_turtle(turtle: &str) {
+    let parser = oxttl::TurtleParser::new()
+        .with_base_iri("http://localhost/fhir/")
+        .expect("valid base IRI");
+    for triple in parser.parse_read(turtle.as_bytes()) {
+        if let Err(err) = triple {
+            panic!("malformed Turtle: {}\n{}", err, turtle);
+        }
+    }
+}
+
+#[tokio::test]
+async fn read_patient_as_turtle() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            let patient = json!({
+                "resourceType": "Patient",
+                "active": true,
+                "name": [{"family": "Turtle", "given": ["Tess"]}]
+            });
+            let (status, _headers, body) = app
+                .request(Method::POST, "/fhir/Patient", Some(to_json_body(&patient)?))
+                .await?;
+            assert_status(status, StatusCode::CREATED, "create Patient");
+            let created = parse_json(&body)?;
+            let id = created["id"].as_str().unwrap();
+
+            let (status, headers, body) = app
+                .request(
+                    Method::GET,
+                    &format!("/fhir/Patient/{}?_format=ttl", id),
+                    None,
+                )
+                .await?;
+            assert_status(status, StatusCode::OK, "read Patient as Turtle");
+
+            let ct = headers
+                .get("content-type")
+                .and_then(|v| v.to_str().ok())
+                .unwrap_or("");
+            assert!(
+                ct.starts_with("application/fhir+turtle"),
+                "expected fhir+turtle content-type, got '{}'",
+                ct
+            );
+
+            let turtle = String::from_utf8(body.to_vec())?;
+            assert_well_formed_turtle(&turtle);
+            assert!(
+                turtle.contains(&format!("<Patient/{}> a fhir:Patient", id)),
+                "{}",
+                turtle
+            );
+            assert!(
+                turtle.contains(&format!("fhir:Patient.id [ fhir:value \"{}\" ]", id)),
+                "{}",
+                turtle
+            );
+            assert!(
+                turtle.contains("fhir:Patient.name.family [ fhir:value \"Turtle\" ]"),
+                "{}",
+                turtle
+            );
+
+            // Search bundles carry their entries as nested resource nodes.
+            let (status, _headers, body) = app
+                .request_with_extra_headers(
+                    Method::GET,
+                    "/fhir/Patient",
+                    None,
+                    &[("accept", "application/fhir+turtle")],
+                )
+                .await?;
+            assert_status(status, StatusCode::OK, "search as Turtle");
+
+            let turtle = String::from_utf8(body.to_vec())?;
+            assert_well_formed_turtle(&turtle);
+            assert!(turtle.contains("[] a fhir:Bundle"), "{}", turtle);
+            assert!(
+                turtle.contains("fhir:Bundle.entry.resource [\n      a fhir:Patient ;"),
+                "{}",
+                turtle
+            );
+            assert!(
+                turtle.contains("fhir:Patient.name.family [ fhir:value \"Turtle\" ]"),
+                "{}",
+                turtle
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
@@ -15,6 +15,7 @@ use crate::support::{
     assert_resource_id, assert_status, assert_version_id, constants, minimal_patient,
     patient_with_mrn, register_search_parameter, to_json_body, with_test_app,
 };
+use axum::body::Bytes;
 use axum::http::{Method, StatusCode};
 use serde_json::json;
 
@@ -209,6 +210,42 @@ async fn create_rejects_missing_resource_type() -> anyhow::Result<()> {
     .await
 }
 
+#[tokio::test]
+async fn create_rejects_malformed_json_with_position() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            let truncated = "{\n  \"resourceType\": \"Patient\",\n  \"active\": tr";
+
+            let (status, headers, body) = app
+                .request(
+                    Method::POST,
+                    "/fhir/Patient",
+                    Some(Bytes::from_static(truncated.as_bytes())),
+                )
+                .await?;
+
+            assert_status(status, StatusCode::BAD_REQUEST, "malformed JSON");
+            assert!(headers
+                .get("content-type")
+                .and_then(|v| v.to_str().ok())
+                .is_some_and(|ct| ct.starts_with("application/fhir+json")));
+
+            let outcome: serde_json::Value = serde_json::from_slice(&body)?;
+            assert_eq!(outcome["resourceType"], "OperationOutcome");
+            assert_eq!(outcome["issue"][0]["code"], "invalid");
+            let diagnostics = outcome["issue"][0]["diagnostics"].as_str().unwrap();
+            assert!(
+                diagnostics.contains("at line 3 column 14 (byte offset 44)"),
+                "unexpected diagnostics: {}",
+                diagnostics
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
+
 #[tokio::test]
 async fn create_returns_location_header() -> anyhow::Result<()> {
     with_test_app(|app| {
@@ -0,0 +1,361 @@
+//! ID Generation Strategy Tests
+//!
+//! These tests verify the configurable `fhir.id_strategy` for POST creates:
+//! - "uuid" (default): server-assigned UUIDs, client ids ignored
+//! - "sequential": per-type counter ids
+//! - "client-allowed": valid client ids are kept, otherwise a UUID is generated
+
+use crate::support::{
+    assert_status, minimal_patient, register_search_parameter, to_json_body, with_test_app,
+    with_test_app_with_config, TestApp,
+};
+use axum::http::{Method, StatusCode};
+use ferrum::services::id_strategy::IdStrategy;
+use serde_json::{json, Value};
+
+async fn post(
+    app: &TestApp,
+    resource_type: &str,
+    resource: &Value,
+) -> anyhow::Result<(StatusCode, Value)> {
+    let (status, _headers, body) = app
+        .request(
+            Method::POST,
+            &format!("/fhir/{}", resource_type),
+            Some(to_json_body(resource)?),
+        )
+        .await?;
+    let value = if body.is_empty() {
+        Value::Null
+    } else {
+        serde_json::from_slice(&body)?
+    };
+    Ok((status, value))
+}
+
+async fn post_patient(app: &TestApp, resource: &Value) -> anyhow::Result<String> {
+    let (status, created) = post(app, "Patient", resource).await?;
+    assert_status(status, StatusCode::CREATED, "create Patient");
+    Ok(created["id"].as_str().unwrap().to_string())
+}
+
+// ============================================================================
+// UUID (default)
+// ============================================================================
+
+#[tokio::test]
+async fn uuid_strategy_assigns_uuid_and_ignores_client_id() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            let mut patient = minimal_patient();
+            patient["id"] = json!("client-chosen");
+
+            let id = post_patient(app, &patient).await?;
+            assert_ne!(id, "client-chosen");
+            assert!(
+                uuid::Uuid::parse_str(&id).is_ok(),
+                "expected a UUID id, got {}",
+                id
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+// ============================================================================
+// Sequential
+// ============================================================================
+
+#[tokio::test]
+async fn sequential_strategy_assigns_per_type_counter() -> anyhow::Result<()> {
+    with_test_app_with_config(
+        |config| {
+            config.fhir.id_strategy = IdStrategy::Sequential;
+        },
+        |app| {
+            Box::pin(async move {
+                let first = post_patient(app, &minimal_patient()).await?;
+                let second = post_patient(app, &minimal_patient()).await?;
+
+                let first: u64 = first.parse().expect("numeric id");
+                let second: u64 = second.parse().expect("numeric id");
+                assert_eq!(second, first + 1);
+
+                // Each type has its own counter.
+                let (status, org) = post(
+                    app,
+                    "Organization",
+                    &json!({"resourceType": "Organization", "name": "Acme"}),
+                )
+                .await?;
+                assert_status(status, StatusCode::CREATED, "create Organization");
+                assert_eq!(org["id"], "1");
+
+                Ok(())
+            })
+        },
+    )
+    .await
+}
+
+#[tokio::test]
+async fn sequential_strategy_skips_ids_taken_by_update_as_create() -> anyhow::Result<()> {
+    with_test_app_with_config(
+        |config| {
+            config.fhir.id_strategy = IdStrategy::Sequential;
+        },
+        |app| {
+            Box::pin(async move {
+                let mut patient = minimal_patient();
+                patient["id"] = json!("1");
+                let (status, _headers, _body) = app
+                    .request(
+                        Method::PUT,
+                        "/fhir/Patient/1",
+                        Some(to_json_body(&patient)?),
+                    )
+                    .await?;
+                assert_status(status, StatusCode::CREATED, "update-as-create Patient/1");
+
+                let id = post_patient(app, &minimal_patient()).await?;
+                assert_eq!(id, "2");
+
+                Ok(())
+            })
+        },
+    )
+    .await
+}
+
+// ============================================================================
+// Client-allowed
+// ============================================================================
+
+#[tokio::test]
+async fn client_allowed_strategy_keeps_client_id() -> anyhow::Result<()> {
+    with_test_app_with_config(
+        |config| {
+            config.fhir.id_strategy = IdStrategy::ClientAllowed;
+        },
+        |app| {
+            Box::pin(async move {
+                let mut patient = minimal_patient();
+                patient["id"] = json!("client-chosen.1");
+                let id = post_patient(app, &patient).await?;
+                assert_eq!(id, "client-chosen.1");
+
+                let (status, _headers, _body) = app
+                    .request(Method::GET, "/fhir/Patient/client-chosen.1", None)
+                    .await?;
+                assert_status(status, StatusCode::OK, "read client id");
+
+                // Without an id the server still generates one.
+                let generated = post_patient(app, &minimal_patient()).await?;
+                assert!(uuid::Uuid::parse_str(&generated).is_ok());
+
+                Ok(())
+            })
+        },
+    )
+    .await
+}
+
+#[tokio::test]
+async fn client_allowed_strategy_rejects_invalid_or_taken_ids() -> anyhow::Result<()> {
+    with_test_app_with_config(
+        |config| {
+            config.fhir.id_strategy = IdStrategy::ClientAllowed;
+        },
+        |app| {
+            Box::pin(async move {
+                let mut patient = minimal_patient();
+                patient["id"] = json!("not a valid id!");
+                let (status, _) = post(app, "Patient", &patient).await?;
+                assert_status(status, StatusCode::BAD_REQUEST, "invalid client id");
+
+                patient["id"] = json!("taken");
+                post_patient(app, &patient).await?;
+                let (status, _) = post(app, "Patient", &patient).await?;
+                assert_status(status, StatusCode::CONFLICT, "client id already in use");
+
+                Ok(())
+            })
+        },
+    )
+    .await
+}
+
+#[tokio::test]
+async fn client_allowed_strategy_rejects_concurrent_creates_of_one_id() -> anyhow::Result<()> {
+    with_test_app_with_config(
+        |config| {
+            config.fhir.id_strategy = IdStrategy::ClientAllowed;
+        },
+        |app| {
+            Box::pin(async move {
+                let mut patient = minimal_patient();
+                patient["id"] = json!("raced");
+                let (first, second) = tokio::join!(
+                    post(app, "Patient", &patient),
+                    post(app, "Patient", &patient)
+                );
+                let mut statuses = [first?.0, second?.0];
+                statuses.sort();
+                assert_eq!(statuses, [StatusCode::CREATED, StatusCode::CONFLICT]);
+
+                Ok(())
+            })
+        },
+    )
+    .await
+}
+
+// ============================================================================
+// Transactions
+// ============================================================================
+
+async fn post_transaction(
+    app: &TestApp,
+    entries: Vec<Value>,
+) -> anyhow::Result<(StatusCode, Value)> {
+    let bundle = json!({"resourceType": "Bundle", "type": "transaction", "entry": entries});
+    let (status, _headers, body) = app
+        .request(Method::POST, "/fhir", Some(to_json_body(&bundle)?))
+        .await?;
+    Ok((status, serde_json::from_slice(&body)?))
+}
+
+fn post_entry(full_url: &str, resource: Value) -> Value {
+    let resource_type = resource["resourceType"].as_str().unwrap().to_string();
+    json!({
+        "fullUrl": full_url,
+        "resource": resource,
+        "request": {"method": "POST", "url": resource_type}
+    })
+}
+
+#[tokio::test]
+async fn transaction_posts_use_id_strategy() -> anyhow::Result<()> {
+    with_test_app_with_config(
+        |config| {
+            config.fhir.id_strategy = IdStrategy::Sequential;
+        },
+        |app| {
+            Box::pin(async move {
+                let (status, response) = post_transaction(
+                    app,
+                    vec![
+                        post_entry("urn:uuid:patient", minimal_patient()),
+                        post_entry(
+                            "urn:uuid:observation",
+                            json!({
+                                "resourceType": "Observation",
+                                "status": "final",
+                                "code": {"text": "weight"},
+                                "subject": {"reference": "urn:uuid:patient"}
+                            }),
+                        ),
+                    ],
+                )
+                .await?;
+                assert_status(status, StatusCode::OK, "transaction");
+                assert_eq!(
+                    response["entry"][0]["response"]["location"],
+                    "Patient/1/_history/1"
+                );
+                assert_eq!(
+                    response["entry"][1]["response"]["location"],
+                    "Observation/1/_history/1"
+                );
+
+                let (status, _headers, body) = app
+                    .request(Method::GET, "/fhir/Observation/1", None)
+                    .await?;
+                assert_status(status, StatusCode::OK, "read Observation");
+                let observation: Value = serde_json::from_slice(&body)?;
+                assert_eq!(observation["subject"]["reference"], "Patient/1");
+
+                Ok(())
+            })
+        },
+    )
+    .await
+}
+
+#[tokio::test]
+async fn transaction_posts_keep_or_reject_client_ids() -> anyhow::Result<()> {
+    with_test_app_with_config(
+        |config| {
+            config.fhir.id_strategy = IdStrategy::ClientAllowed;
+        },
+        |app| {
+            Box::pin(async move {
+                let mut patient = minimal_patient();
+                patient["id"] = json!("tx-client");
+                let (status, response) =
+                    post_transaction(app, vec![post_entry("urn:uuid:p", patient.clone())]).await?;
+                assert_status(status, StatusCode::OK, "transaction");
+                assert_eq!(
+                    response["entry"][0]["response"]["location"],
+                    "Patient/tx-client/_history/1"
+                );
+
+                let (status, _) =
+                    post_transaction(app, vec![post_entry("urn:uuid:p", patient)]).await?;
+                assert_status(
+                    status,
+                    StatusCode::CONFLICT,
+                    "transaction reusing client id",
+                );
+
+                Ok(())
+            })
+        },
+    )
+    .await
+}
+
+#[tokio::test]
+async fn transaction_conditional_update_without_match_uses_id_strategy() -> anyhow::Result<()> {
+    with_test_app_with_config(
+        |config| {
+            config.fhir.id_strategy = IdStrategy::Sequential;
+        },
+        |app| {
+            Box::pin(async move {
+                register_search_parameter(
+                    &app.state.db_pool,
+                    "identifier",
+                    "Patient",
+                    "token",
+                    "Patient.identifier",
+                    &[],
+                )
+                .await?;
+
+                let (status, response) = post_transaction(
+                    app,
+                    vec![json!({
+                        "fullUrl": "urn:uuid:patient",
+                        "resource": minimal_patient(),
+                        "request": {
+                            "method": "PUT",
+                            "url": "Patient?identifier=http://example.org/fhir/mrn|404"
+                        }
+                    })],
+                )
+                .await?;
+                assert_status(status, StatusCode::OK, "transaction");
+                assert_eq!(
+                    response["entry"][0]["response"]["location"],
+                    "Patient/1/_history/1"
+                );
+
+                Ok(())
+            })
+        },
+    )
+    .await
+}
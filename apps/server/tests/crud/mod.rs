@@ -2,8 +2,11 @@ pub mod conditional_references;
 pub mod configurable_behaviors;
 pub mod create;
 pub mod delete;
+pub mod id_strategy;
+pub mod narrative;
 pub mod patch;
 pub mod read;
 pub mod referential_integrity;
+pub mod security_labels;
 pub mod spec_compliance;
 pub mod update;
@@ -0,0 +1,127 @@
+//! Narrative Generation Tests
+//!
+//! These tests verify the configurable `fhir.resources.generate_narrative` for POST creates,
+//! including POST entries of transaction bundles:
+//! - "off" (default): resources are stored as sent
+//! - "minimal": resources without `text.div` get a generated narrative; existing ones are kept
+
+use crate::support::{
+    assert_status, example_patient, to_json_body, with_test_app, with_test_app_with_config, TestApp,
+};
+use axum::http::{Method, StatusCode};
+use ferrum::services::narrative::NarrativeMode;
+use serde_json::{json, Value};
+
+async fn create_and_read_patient(app: &TestApp, patient: &Value) -> anyhow::Result<Value> {
+    let (status, _headers, body) = app
+        .request(Method::POST, "/fhir/Patient", Some(to_json_body(patient)?))
+        .await?;
+    assert_status(status, StatusCode::CREATED, "create Patient");
+    let created: Value = serde_json::from_slice(&body)?;
+    let id = created["id"].as_str().unwrap();
+
+    let (status, _headers, body) = app
+        .request(Method::GET, &format!("/fhir/Patient/{}", id), None)
+        .await?;
+    assert_status(status, StatusCode::OK, "read Patient");
+    Ok(serde_json::from_slice(&body)?)
+}
+
+fn enable_minimal_narrative(config: &mut ferrum::Config) {
+    config.fhir.resources.generate_narrative = NarrativeMode::Minimal;
+}
+
+#[tokio::test]
+async fn minimal_narrative_is_generated_for_patient_without_text() -> anyhow::Result<()> {
+    with_test_app_with_config(enable_minimal_narrative, |app| {
+        Box::pin(async move {
+            let patient = create_and_read_patient(app, &example_patient("Doe", "Jane")).await?;
+            let id = patient["id"].as_str().unwrap();
+
+            assert_eq!(patient["text"]["status"], "generated");
+            let div = patient["text"]["div"].as_str().expect("text.div");
+            assert!(
+                div.starts_with("<div xmlns=\"http://www.w3.org/1999/xhtml\">"),
+                "{}",
+                div
+            );
+            assert!(div.contains(&format!("Patient {}", id)), "{}", div);
+            assert!(div.contains("Jane Doe"), "{}", div);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn minimal_narrative_keeps_existing_text() -> anyhow::Result<()> {
+    with_test_app_with_config(enable_minimal_narrative, |app| {
+        Box::pin(async move {
+            let text = json!({
+                "status": "additional",
+                "div": "<div xmlns=\"http://www.w3.org/1999/xhtml\"><p>Written by the client</p></div>"
+            });
+            let mut patient = example_patient("Doe", "Jane");
+            patient["text"] = text.clone();
+
+            let patient = create_and_read_patient(app, &patient).await?;
+            assert_eq!(patient["text"], text);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn minimal_narrative_is_generated_for_transaction_posts() -> anyhow::Result<()> {
+    with_test_app_with_config(enable_minimal_narrative, |app| {
+        Box::pin(async move {
+            let bundle = json!({
+                "resourceType": "Bundle",
+                "type": "transaction",
+                "entry": [{
+                    "fullUrl": "urn:uuid:patient",
+                    "resource": example_patient("Doe", "Jane"),
+                    "request": {"method": "POST", "url": "Patient"}
+                }]
+            });
+            let (status, _headers, body) = app
+                .request(Method::POST, "/fhir", Some(to_json_body(&bundle)?))
+                .await?;
+            assert_status(status, StatusCode::OK, "transaction");
+            let response: Value = serde_json::from_slice(&body)?;
+            let location = response["entry"][0]["response"]["location"]
+                .as_str()
+                .expect("location");
+            let id = location.split('/').nth(1).unwrap();
+
+            let (status, _headers, body) = app
+                .request(Method::GET, &format!("/fhir/Patient/{}", id), None)
+                .await?;
+            assert_status(status, StatusCode::OK, "read Patient");
+            let patient: Value = serde_json::from_slice(&body)?;
+            assert_eq!(patient["text"]["status"], "generated");
+            let div = patient["text"]["div"].as_str().expect("text.div");
+            assert!(div.contains(&format!("Patient {}", id)), "{}", div);
+            assert!(div.contains("Jane Doe"), "{}", div);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn narrative_generation_is_off_by_default() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            let patient = create_and_read_patient(app, &example_patient("Doe", "Jane")).await?;
+            assert!(patient.get("text").is_none(), "{}", patient);
+
+            Ok(())
+        })
+    })
+    .await
+}
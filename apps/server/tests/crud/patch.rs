@@ -7,6 +7,8 @@
 //! - Conditional patch resolution (0/1/many matches)
 //! - 422 Unprocessable Entity on failing JSON Patch test op
 //! - Narrative safety behavior (narrative removed after patch)
+//! - Version contention via If-Match (412 on stale version)
+//! - Rejection of patches that change id or resourceType
 
 use crate::support::{
     assert_resource_id, assert_status, assert_version_id, constants, patient_with_mrn,
@@ -213,3 +215,115 @@ async fn conditional_patch_returns_422_on_failed_test_op() -> anyhow::Result<()>
     })
     .await
 }
+
+async fn create_patient_for_patch(app: &crate::support::TestApp) -> anyhow::Result<String> {
+    let patient = json!({ "resourceType": "Patient", "active": true });
+    let (status, _headers, body) = app
+        .request(Method::POST, "/fhir/Patient", Some(to_json_body(&patient)?))
+        .await?;
+    assert_status(status, StatusCode::CREATED, "create");
+    let created: serde_json::Value = serde_json::from_slice(&body)?;
+    Ok(created["id"].as_str().unwrap().to_string())
+}
+
+#[tokio::test]
+async fn patch_with_matching_if_match_applies() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            let id = create_patient_for_patch(app).await?;
+
+            let patch = json!([
+                { "op": "replace", "path": "/active", "value": false }
+            ]);
+            let (status, headers, body) = app
+                .request_with_extra_headers(
+                    Method::PATCH,
+                    &format!("/fhir/Patient/{id}"),
+                    Some(to_json_body(&patch)?),
+                    &[
+                        ("content-type", "application/json-patch+json"),
+                        ("if-match", "W/\"1\""),
+                    ],
+                )
+                .await?;
+            assert_status(status, StatusCode::OK, "patch with current If-Match");
+            assert_eq!(
+                headers.get("etag").and_then(|v| v.to_str().ok()),
+                Some("W/\"2\"")
+            );
+            let patched: serde_json::Value = serde_json::from_slice(&body)?;
+            assert_version_id(&patched, "2")?;
+            assert_eq!(patched["active"], false);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn patch_with_stale_if_match_returns_412() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            let id = create_patient_for_patch(app).await?;
+
+            let patch = json!([
+                { "op": "replace", "path": "/active", "value": false }
+            ]);
+            let (status, _headers, _body) = app
+                .request_with_extra_headers(
+                    Method::PATCH,
+                    &format!("/fhir/Patient/{id}"),
+                    Some(to_json_body(&patch)?),
+                    &[
+                        ("content-type", "application/json-patch+json"),
+                        ("if-match", "W/\"7\""),
+                    ],
+                )
+                .await?;
+            assert_status(
+                status,
+                StatusCode::PRECONDITION_FAILED,
+                "patch with stale If-Match",
+            );
+
+            let (status, _headers, body) = app
+                .request(Method::GET, &format!("/fhir/Patient/{id}"), None)
+                .await?;
+            assert_status(status, StatusCode::OK, "read");
+            let current: serde_json::Value = serde_json::from_slice(&body)?;
+            assert_version_id(&current, "1")?;
+            assert_eq!(current["active"], true, "stale patch must not be applied");
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn patch_rejects_identity_changes() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            let id = create_patient_for_patch(app).await?;
+
+            for patch in [
+                json!([{ "op": "replace", "path": "/id", "value": "other-id" }]),
+                json!([{ "op": "replace", "path": "/resourceType", "value": "Person" }]),
+            ] {
+                let (status, _headers, _body) = app
+                    .request_with_extra_headers(
+                        Method::PATCH,
+                        &format!("/fhir/Patient/{id}"),
+                        Some(to_json_body(&patch)?),
+                        &[("content-type", "application/json-patch+json")],
+                    )
+                    .await?;
+                assert_status(status, StatusCode::BAD_REQUEST, "identity-changing patch");
+            }
+
+            Ok(())
+        })
+    })
+    .await
+}
@@ -8,6 +8,7 @@
 //! - 410 Gone for deleted resources
 //! - Reading returns current version only
 //! - ETag header with version ID
+//! - _summary=text on read
 
 use crate::support::{
     assert_resource_id, assert_status, minimal_patient, to_json_body, with_test_app,
@@ -219,3 +220,105 @@ async fn read_preserves_all_resource_data() -> anyhow::Result<()> {
     })
     .await
 }
+
+#[tokio::test]
+async fn read_with_summary_text_keeps_only_text_id_meta_and_mandatory() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            use serde_json::json;
+
+            let observation = json!({
+                "resourceType": "Observation",
+                "text": {
+                    "status": "generated",
+                    "div": "<div xmlns=\"http://www.w3.org/1999/xhtml\">Heart rate 72</div>"
+                },
+                "status": "final",
+                "code": { "text": "Heart rate" },
+                "subject": { "reference": "Patient/example" },
+                "valueQuantity": { "value": 72, "unit": "beats/minute" }
+            });
+
+            let (status, _headers, body) = app
+                .request(
+                    Method::POST,
+                    "/fhir/Observation",
+                    Some(to_json_body(&observation)?),
+                )
+                .await?;
+            assert_status(status, StatusCode::CREATED, "create");
+
+            let created: serde_json::Value = serde_json::from_slice(&body)?;
+            let id = created["id"].as_str().unwrap();
+
+            let (status, _headers, body) = app
+                .request(
+                    Method::GET,
+                    &format!("/fhir/Observation/{id}?_summary=text"),
+                    None,
+                )
+                .await?;
+            assert_status(status, StatusCode::OK, "read _summary=text");
+
+            let read: serde_json::Value = serde_json::from_slice(&body)?;
+            assert_resource_id(&read, id)?;
+            assert_eq!(read["text"]["status"], "generated");
+            assert!(read.get("valueQuantity").is_none());
+            assert!(read.get("subject").is_none());
+            // status and code are mandatory (min = 1) in the base definition
+            assert_eq!(read["status"], "final");
+            assert_eq!(read["code"]["text"], "Heart rate");
+
+            let tags = read["meta"]["tag"].as_array().expect("meta.tag");
+            assert!(
+                tags.iter().any(|t| t["code"] == "SUBSETTED"),
+                "SUBSETTED tag should be added: {tags:?}"
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn read_with_summary_text_without_narrative_keeps_id_and_meta() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            use serde_json::json;
+
+            let patient = json!({
+                "resourceType": "Patient",
+                "active": true,
+                "name": [{ "family": "Smith" }]
+            });
+
+            let (status, _headers, body) = app
+                .request(Method::POST, "/fhir/Patient", Some(to_json_body(&patient)?))
+                .await?;
+            assert_status(status, StatusCode::CREATED, "create");
+
+            let created: serde_json::Value = serde_json::from_slice(&body)?;
+            let id = created["id"].as_str().unwrap();
+
+            let (status, _headers, body) = app
+                .request(
+                    Method::GET,
+                    &format!("/fhir/Patient/{id}?_summary=text"),
+                    None,
+                )
+                .await?;
+            assert_status(status, StatusCode::OK, "read _summary=text");
+
+            let read: serde_json::Value = serde_json::from_slice(&body)?;
+            assert_resource_id(&read, id)?;
+            assert_eq!(read["meta"]["versionId"], "1");
+            assert!(read.get("text").is_none());
+            assert!(read.get("name").is_none());
+            assert!(read.get("active").is_none());
+
+            Ok(())
+        })
+    })
+    .await
+}
@@ -1,14 +1,16 @@
 //! Referential Integrity Tests
 //!
 //! These tests verify that the configurable referential integrity modes work:
-//! - "lenient" (default): no reference checking, dangling refs allowed
-//! - "strict": rejects writes with broken references, blocks deletes of referenced resources
+//! - "off" / "lenient" (default): no reference checking, dangling refs allowed
+//! - "logical": dangling refs are logged but the write is accepted
+//! - "strict": rejects writes with broken references (422), blocks deletes of referenced resources
 
 use crate::support::{
     assert_status, minimal_patient, register_search_parameter, to_json_body, with_test_app,
     with_test_app_with_config, ObservationBuilder,
 };
 use axum::http::{Method, StatusCode};
+use ferrum::services::referential_integrity::ReferentialIntegrityMode;
 use serde_json::json;
 
 // ============================================================================
@@ -40,6 +42,85 @@ async fn lenient_allows_dangling_reference() -> anyhow::Result<()> {
     .await
 }
 
+#[tokio::test]
+async fn off_allows_dangling_reference() -> anyhow::Result<()> {
+    with_test_app_with_config(
+        |config| {
+            config.fhir.referential_integrity.mode = ReferentialIntegrityMode::Off;
+        },
+        |app| {
+            Box::pin(async move {
+                let obs = ObservationBuilder::new()
+                    .code_text("Weight")
+                    .subject("Patient/nonexistent-999")
+                    .build();
+
+                let (status, _headers, _body) = app
+                    .request(
+                        Method::POST,
+                        "/fhir/Observation",
+                        Some(to_json_body(&obs)?),
+                    )
+                    .await?;
+
+                assert_status(status, StatusCode::CREATED, "off allows dangling ref");
+                Ok(())
+            })
+        },
+    )
+    .await
+}
+
+// ============================================================================
+// Logical Mode
+// ============================================================================
+
+#[tokio::test]
+async fn logical_accepts_dangling_reference_on_create_and_update() -> anyhow::Result<()> {
+    with_test_app_with_config(
+        |config| {
+            config.fhir.referential_integrity.mode = ReferentialIntegrityMode::Logical;
+        },
+        |app| {
+            Box::pin(async move {
+                let obs = ObservationBuilder::new()
+                    .code_text("Weight")
+                    .subject("Patient/nonexistent-999")
+                    .build();
+
+                let (status, _headers, body) = app
+                    .request(
+                        Method::POST,
+                        "/fhir/Observation",
+                        Some(to_json_body(&obs)?),
+                    )
+                    .await?;
+                assert_status(status, StatusCode::CREATED, "logical accepts dangling ref");
+
+                let mut created: serde_json::Value = serde_json::from_slice(&body)?;
+                let obs_id = created["id"].as_str().unwrap().to_string();
+                created["subject"] = json!({ "reference": "Patient/nonexistent-1000" });
+
+                let (status, _headers, _body) = app
+                    .request(
+                        Method::PUT,
+                        &format!("/fhir/Observation/{}", obs_id),
+                        Some(to_json_body(&created)?),
+                    )
+                    .await?;
+                assert_status(
+                    status,
+                    StatusCode::OK,
+                    "logical accepts dangling ref on update",
+                );
+
+                Ok(())
+            })
+        },
+    )
+    .await
+}
+
 // ============================================================================
 // Strict Mode — Create
 // ============================================================================
@@ -48,7 +129,7 @@ async fn lenient_allows_dangling_reference() -> anyhow::Result<()> {
 async fn strict_rejects_dangling_reference_on_create() -> anyhow::Result<()> {
     with_test_app_with_config(
         |config| {
-            config.fhir.referential_integrity.mode = "strict".to_string();
+            config.fhir.referential_integrity.mode = ReferentialIntegrityMode::Strict;
         },
         |app| {
             Box::pin(async move {
@@ -67,12 +148,18 @@ async fn strict_rejects_dangling_reference_on_create() -> anyhow::Result<()> {
 
                 assert_status(
                     status,
-                    StatusCode::CONFLICT,
+                    StatusCode::UNPROCESSABLE_ENTITY,
                     "strict rejects dangling ref on create",
                 );
 
                 let outcome: serde_json::Value = serde_json::from_slice(&body)?;
                 assert_eq!(outcome["resourceType"], "OperationOutcome");
+                let diagnostics = outcome["issue"][0]["diagnostics"].as_str().unwrap_or("");
+                assert!(
+                    diagnostics.contains("Patient/nonexistent-999"),
+                    "diagnostics should list the broken reference: {}",
+                    diagnostics
+                );
 
                 Ok(())
             })
@@ -85,7 +172,7 @@ async fn strict_rejects_dangling_reference_on_create() -> anyhow::Result<()> {
 async fn strict_allows_valid_reference_on_create() -> anyhow::Result<()> {
     with_test_app_with_config(
         |config| {
-            config.fhir.referential_integrity.mode = "strict".to_string();
+            config.fhir.referential_integrity.mode = ReferentialIntegrityMode::Strict;
         },
         |app| {
             Box::pin(async move {
@@ -134,7 +221,7 @@ async fn strict_allows_valid_reference_on_create() -> anyhow::Result<()> {
 async fn strict_rejects_dangling_reference_on_update() -> anyhow::Result<()> {
     with_test_app_with_config(
         |config| {
-            config.fhir.referential_integrity.mode = "strict".to_string();
+            config.fhir.referential_integrity.mode = ReferentialIntegrityMode::Strict;
         },
         |app| {
             Box::pin(async move {
@@ -181,7 +268,7 @@ async fn strict_rejects_dangling_reference_on_update() -> anyhow::Result<()> {
 
                 assert_status(
                     status,
-                    StatusCode::CONFLICT,
+                    StatusCode::UNPROCESSABLE_ENTITY,
                     "strict rejects dangling ref on update",
                 );
 
@@ -200,7 +287,7 @@ async fn strict_rejects_dangling_reference_on_update() -> anyhow::Result<()> {
 async fn strict_blocks_delete_when_referenced() -> anyhow::Result<()> {
     with_test_app_with_config(
         |config| {
-            config.fhir.referential_integrity.mode = "strict".to_string();
+            config.fhir.referential_integrity.mode = ReferentialIntegrityMode::Strict;
         },
         |app| {
             Box::pin(async move {
@@ -269,7 +356,7 @@ async fn strict_blocks_delete_when_referenced() -> anyhow::Result<()> {
 async fn strict_allows_delete_when_unreferenced() -> anyhow::Result<()> {
     with_test_app_with_config(
         |config| {
-            config.fhir.referential_integrity.mode = "strict".to_string();
+            config.fhir.referential_integrity.mode = ReferentialIntegrityMode::Strict;
         },
         |app| {
             Box::pin(async move {
@@ -313,7 +400,7 @@ async fn strict_allows_delete_when_unreferenced() -> anyhow::Result<()> {
 async fn strict_ignores_fragment_and_absolute_refs() -> anyhow::Result<()> {
     with_test_app_with_config(
         |config| {
-            config.fhir.referential_integrity.mode = "strict".to_string();
+            config.fhir.referential_integrity.mode = ReferentialIntegrityMode::Strict;
         },
         |app| {
             Box::pin(async move {
@@ -357,7 +444,7 @@ async fn strict_ignores_fragment_and_absolute_refs() -> anyhow::Result<()> {
 async fn strict_allows_self_reference() -> anyhow::Result<()> {
     with_test_app_with_config(
         |config| {
-            config.fhir.referential_integrity.mode = "strict".to_string();
+            config.fhir.referential_integrity.mode = ReferentialIntegrityMode::Strict;
         },
         |app| {
             Box::pin(async move {
@@ -0,0 +1,497 @@
+//! Security-label access filtering tests (`auth.security_labels`)
+//!
+//! A principal only sees resources whose `meta.security` labels are all granted to it:
+//! - Read of a resource with a withheld label is 404
+//! - Search silently drops resources with a withheld label
+//! - Unlabeled resources stay visible
+//! - History, batch/transaction reads, operations, bulk export and conditional writes apply the
+//!   same filter
+
+use crate::support::{
+    assert_status, extract_resource_ids, minimal_patient, register_operation, to_json_body,
+    with_test_app, with_test_app_with_config, OperationFixture, TestApp,
+};
+use anyhow::Context as _;
+use axum::{
+    body::Body,
+    http::{Method, Request, StatusCode},
+};
+use serde_json::{json, Value};
+use tower::ServiceExt as _;
+
+const CONFIDENTIALITY: &str = "http://terminology.hl7.org/CodeSystem/v3-Confidentiality";
+
+fn principal(labels: &[&str]) -> ferrum::auth::Principal {
+    ferrum::auth::Principal {
+        subject: "user-1".to_string(),
+        scopes: vec![],
+        issuer: None,
+        audience: None,
+        client_id: None,
+        patient: None,
+        security_labels: labels.iter().map(|l| l.to_string()).collect(),
+    }
+}
+
+/// Send a request as `principal`, attached the way the auth middleware does.
+async fn request_as(
+    app: &TestApp,
+    method: Method,
+    path: &str,
+    body: Option<&Value>,
+    principal: ferrum::auth::Principal,
+) -> anyhow::Result<(StatusCode, Value)> {
+    let mut request = Request::builder()
+        .method(method)
+        .uri(path)
+        .header("host", "example.org")
+        .header("accept", "application/fhir+json")
+        .extension(principal);
+    if let Some(body) = body {
+        request = request.header("content-type", content_type(body));
+    }
+    let request = request
+        .body(match body {
+            Some(body) => Body::from(to_json_body(body)?),
+            None => Body::empty(),
+        })
+        .context("build request")?;
+    let response = app
+        .router
+        .clone()
+        .oneshot(request)
+        .await
+        .context("dispatch request")?;
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+    let value = if body.is_empty() {
+        Value::Null
+    } else {
+        serde_json::from_slice(&body)?
+    };
+    Ok((status, value))
+}
+
+/// JSON Patch documents are arrays; everything else is a FHIR resource.
+fn content_type(body: &Value) -> &'static str {
+    if body.is_array() {
+        "application/json-patch+json"
+    } else {
+        "application/fhir+json"
+    }
+}
+
+/// GET as `principal`.
+async fn get_as(
+    app: &TestApp,
+    path: &str,
+    principal: ferrum::auth::Principal,
+) -> anyhow::Result<(StatusCode, Value)> {
+    request_as(app, Method::GET, path, None, principal).await
+}
+
+fn labeled(mut resource: Value, labels: &[&str]) -> Value {
+    if !labels.is_empty() {
+        let security: Vec<Value> = labels
+            .iter()
+            .map(|code| json!({"system": CONFIDENTIALITY, "code": code}))
+            .collect();
+        resource["meta"] = json!({"security": security});
+    }
+    resource
+}
+
+/// Create `resource` anonymously; creates are not label-checked.
+async fn create_labeled(app: &TestApp, resource: Value, labels: &[&str]) -> anyhow::Result<String> {
+    let resource_type = resource["resourceType"].as_str().unwrap().to_string();
+    let (status, _headers, body) = app
+        .request(
+            Method::POST,
+            &format!("/fhir/{}", resource_type),
+            Some(to_json_body(&labeled(resource, labels))?),
+        )
+        .await?;
+    assert_status(
+        status,
+        StatusCode::CREATED,
+        &format!("create {}", resource_type),
+    );
+    let created: Value = serde_json::from_slice(&body)?;
+    Ok(created["id"].as_str().unwrap().to_string())
+}
+
+async fn create_patient(app: &TestApp, labels: &[&str]) -> anyhow::Result<String> {
+    create_labeled(app, minimal_patient(), labels).await
+}
+
+fn entry_ids(bundle: &Value) -> Vec<String> {
+    bundle["entry"]
+        .as_array()
+        .map(|entries| {
+            entries
+                .iter()
+                .filter_map(|e| e["resource"]["id"].as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn with_labels_enabled(config: &mut ferrum::Config) {
+    config.auth.security_labels.enabled = true;
+}
+
+#[tokio::test]
+async fn principal_without_label_cannot_read_or_find_labeled_resource() -> anyhow::Result<()> {
+    with_test_app_with_config(
+        |config| {
+            config.auth.security_labels.enabled = true;
+        },
+        |app| {
+            Box::pin(async move {
+                let high = create_patient(app, &["HIGH"]).await?;
+                let restricted = create_patient(app, &["R"]).await?;
+                let unlabeled = create_patient(app, &[]).await?;
+
+                let (status, _) =
+                    get_as(app, &format!("/fhir/Patient/{}", high), principal(&["R"])).await?;
+                assert_status(status, StatusCode::NOT_FOUND, "read HIGH as R");
+
+                let (status, _) = get_as(
+                    app,
+                    &format!("/fhir/Patient/{}/_history/1", high),
+                    principal(&["R"]),
+                )
+                .await?;
+                assert_status(status, StatusCode::NOT_FOUND, "vread HIGH as R");
+
+                let (status, body) = get_as(
+                    app,
+                    &format!("/fhir/Patient/{}", restricted),
+                    principal(&["R"]),
+                )
+                .await?;
+                assert_status(status, StatusCode::OK, "read R as R");
+                assert_eq!(body["id"], restricted.as_str());
+
+                let (status, bundle) = get_as(app, "/fhir/Patient", principal(&["R"])).await?;
+                assert_status(status, StatusCode::OK, "search as R");
+                let mut ids = extract_resource_ids(&bundle, "Patient")?;
+                ids.sort();
+                let mut expected = vec![restricted.clone(), unlabeled.clone()];
+                expected.sort();
+                assert_eq!(ids, expected);
+
+                let (status, bundle) =
+                    get_as(app, "/fhir/Patient", principal(&["R", "HIGH"])).await?;
+                assert_status(status, StatusCode::OK, "search as R+HIGH");
+                assert_eq!(extract_resource_ids(&bundle, "Patient")?.len(), 3);
+
+                Ok(())
+            })
+        },
+    )
+    .await
+}
+
+#[tokio::test]
+async fn labels_are_not_enforced_when_disabled() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            let high = create_patient(app, &["HIGH"]).await?;
+
+            let (status, _) =
+                get_as(app, &format!("/fhir/Patient/{}", high), principal(&["R"])).await?;
+            assert_status(status, StatusCode::OK, "read HIGH with filtering disabled");
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn labels_match_on_system_and_code() -> anyhow::Result<()> {
+    with_test_app_with_config(with_labels_enabled, |app| {
+        Box::pin(async move {
+            let restricted = create_patient(app, &["R"]).await?;
+            let path = format!("/fhir/Patient/{}", restricted);
+
+            let (status, _) =
+                get_as(app, &path, principal(&["http://example.org/labels|R"])).await?;
+            assert_status(
+                status,
+                StatusCode::NOT_FOUND,
+                "read R with a foreign system",
+            );
+
+            let grant = format!("{}|R", CONFIDENTIALITY);
+            let (status, _) = get_as(app, &path, principal(&[grant.as_str()])).await?;
+            assert_status(status, StatusCode::OK, "read R with system|code grant");
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn history_hides_labeled_resources() -> anyhow::Result<()> {
+    with_test_app_with_config(with_labels_enabled, |app| {
+        Box::pin(async move {
+            let high = create_patient(app, &["HIGH"]).await?;
+            let unlabeled = create_patient(app, &[]).await?;
+
+            let (status, bundle) = get_as(
+                app,
+                &format!("/fhir/Patient/{}/_history", high),
+                principal(&["R"]),
+            )
+            .await?;
+            assert!(
+                status == StatusCode::NOT_FOUND || entry_ids(&bundle).is_empty(),
+                "instance history of HIGH as R: {} {}",
+                status,
+                bundle
+            );
+
+            for path in ["/fhir/Patient/_history", "/fhir/_history"] {
+                let (status, bundle) = get_as(app, path, principal(&["R"])).await?;
+                assert_status(status, StatusCode::OK, path);
+                assert_eq!(entry_ids(&bundle), vec![unlabeled.clone()], "{}", path);
+            }
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn batch_and_transaction_reads_hide_labeled_resources() -> anyhow::Result<()> {
+    with_test_app_with_config(with_labels_enabled, |app| {
+        Box::pin(async move {
+            let high = create_patient(app, &["HIGH"]).await?;
+
+            for bundle_type in ["batch", "transaction"] {
+                let bundle = json!({
+                    "resourceType": "Bundle",
+                    "type": bundle_type,
+                    "entry": [{
+                        "request": {"method": "GET", "url": format!("Patient/{}", high)}
+                    }]
+                });
+                let (status, response) =
+                    request_as(app, Method::POST, "/fhir", Some(&bundle), principal(&["R"]))
+                        .await?;
+                if bundle_type == "batch" {
+                    assert_status(status, StatusCode::OK, "batch");
+                    let entry_status = response["entry"][0]["response"]["status"]
+                        .as_str()
+                        .unwrap_or_default();
+                    assert!(entry_status.starts_with("404"), "batch GET: {}", response);
+                } else {
+                    assert_status(status, StatusCode::NOT_FOUND, "transaction GET");
+                }
+            }
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn instance_operations_hide_labeled_resources() -> anyhow::Result<()> {
+    with_test_app_with_config(
+        |config| {
+            with_labels_enabled(config);
+            config.fhir.fhirpath.enable_operation = true;
+        },
+        |app| {
+            Box::pin(async move {
+                for (code, resource) in [
+                    ("everything", "Patient"),
+                    ("fhirpath", "Patient"),
+                    ("snapshot", "StructureDefinition"),
+                ] {
+                    register_operation(
+                        app,
+                        OperationFixture {
+                            code,
+                            resource: &[resource],
+                            instance: true,
+                            ..Default::default()
+                        },
+                    )
+                    .await?;
+                }
+                let high = create_patient(app, &["HIGH"]).await?;
+                let profile = create_labeled(
+                    app,
+                    json!({
+                        "resourceType": "StructureDefinition",
+                        "url": "http://example.org/StructureDefinition/labeled-patient",
+                        "name": "LabeledPatient",
+                        "status": "draft",
+                        "kind": "resource",
+                        "abstract": false,
+                        "type": "Patient",
+                        "baseDefinition": "http://hl7.org/fhir/StructureDefinition/Patient",
+                        "derivation": "constraint",
+                        "differential": {"element": [{"id": "Patient", "path": "Patient"}]}
+                    }),
+                    &["HIGH"],
+                )
+                .await?;
+
+                let (status, _) = get_as(
+                    app,
+                    &format!("/fhir/Patient/{}/$everything", high),
+                    principal(&["R"]),
+                )
+                .await?;
+                assert_status(status, StatusCode::NOT_FOUND, "$everything on HIGH as R");
+
+                let expression = json!({
+                    "resourceType": "Parameters",
+                    "parameter": [{"name": "expression", "valueString": "Patient.id"}]
+                });
+                let (status, _) = request_as(
+                    app,
+                    Method::POST,
+                    &format!("/fhir/Patient/{}/$fhirpath", high),
+                    Some(&expression),
+                    principal(&["R"]),
+                )
+                .await?;
+                assert_status(status, StatusCode::NOT_FOUND, "$fhirpath on HIGH as R");
+
+                let (status, _) = get_as(
+                    app,
+                    &format!("/fhir/StructureDefinition/{}/$snapshot", profile),
+                    principal(&["R"]),
+                )
+                .await?;
+                assert_status(status, StatusCode::NOT_FOUND, "$snapshot on HIGH as R");
+
+                Ok(())
+            })
+        },
+    )
+    .await
+}
+
+#[tokio::test]
+async fn bulk_export_hides_labeled_resources() -> anyhow::Result<()> {
+    let output_dir = std::env::temp_dir().join(format!("ferrum-export-{}", uuid::Uuid::new_v4()));
+    let output_dir_str = output_dir.to_string_lossy().to_string();
+
+    let result = with_test_app_with_config(
+        |config| {
+            with_labels_enabled(config);
+            config.fhir.bulk_export.output_dir = output_dir_str;
+        },
+        |app| {
+            Box::pin(async move {
+                create_patient(app, &["HIGH"]).await?;
+                let unlabeled = create_patient(app, &[]).await?;
+
+                let request = Request::builder()
+                    .method(Method::GET)
+                    .uri("/fhir/Patient/$export")
+                    .header("host", "example.org")
+                    .header("accept", "application/fhir+json")
+                    .header("prefer", "respond-async")
+                    .extension(principal(&["R"]))
+                    .body(Body::empty())?;
+                let response = app.router.clone().oneshot(request).await?;
+                assert_status(response.status(), StatusCode::ACCEPTED, "$export kick-off");
+                let status_url = response.headers()["content-location"].to_str()?.to_string();
+                let status_path = &status_url[status_url.find("/fhir/").unwrap()..];
+
+                let (status, manifest) = get_as(app, status_path, principal(&["R"])).await?;
+                assert_status(status, StatusCode::OK, "$export status");
+                let file_url = manifest["output"][0]["url"].as_str().unwrap();
+                let (status, _headers, body) = app
+                    .request(
+                        Method::GET,
+                        &file_url[file_url.find("/fhir/").unwrap()..],
+                        None,
+                    )
+                    .await?;
+                assert_status(status, StatusCode::OK, "download export file");
+                let ids: Vec<String> = std::str::from_utf8(&body)?
+                    .lines()
+                    .map(|line| serde_json::from_str::<Value>(line).map(|r| r["id"].to_string()))
+                    .collect::<Result<_, _>>()?;
+                assert_eq!(ids, vec![format!("\"{}\"", unlabeled)]);
+
+                Ok(())
+            })
+        },
+    )
+    .await;
+
+    let _ = std::fs::remove_dir_all(&output_dir);
+    result
+}
+
+#[tokio::test]
+async fn conditional_writes_do_not_match_labeled_resources() -> anyhow::Result<()> {
+    with_test_app_with_config(with_labels_enabled, |app| {
+        Box::pin(async move {
+            let identifier = json!([{"system": "http://example.org/mrn", "value": "hidden-1"}]);
+            let mut patient = minimal_patient();
+            patient["identifier"] = identifier.clone();
+            let high = create_labeled(app, patient.clone(), &["HIGH"]).await?;
+            let criteria = "/fhir/Patient?identifier=http://example.org/mrn|hidden-1";
+
+            let (status, _) = request_as(
+                app,
+                Method::PATCH,
+                criteria,
+                Some(&json!([{"op": "add", "path": "/active", "value": false}])),
+                principal(&["R"]),
+            )
+            .await?;
+            assert_status(status, StatusCode::NOT_FOUND, "conditional patch as R");
+
+            let (status, _) =
+                request_as(app, Method::DELETE, criteria, None, principal(&["R"])).await?;
+            assert_status(status, StatusCode::NOT_FOUND, "conditional delete as R");
+
+            let (status, updated) = request_as(
+                app,
+                Method::PUT,
+                criteria,
+                Some(&patient),
+                principal(&["R"]),
+            )
+            .await?;
+            assert_status(status, StatusCode::CREATED, "conditional update as R");
+            assert_ne!(updated["id"], high.as_str());
+
+            let (status, _) = request_as(
+                app,
+                Method::PUT,
+                &format!("/fhir/Patient/{}", high),
+                Some(&json!({"resourceType": "Patient", "id": high})),
+                principal(&["R"]),
+            )
+            .await?;
+            assert_status(status, StatusCode::NOT_FOUND, "update HIGH by id as R");
+
+            let (status, body) = get_as(
+                app,
+                &format!("/fhir/Patient/{}", high),
+                principal(&["HIGH"]),
+            )
+            .await?;
+            assert_status(status, StatusCode::OK, "HIGH is untouched");
+            assert_eq!(body["meta"]["versionId"], "1");
+
+            Ok(())
+        })
+    })
+    .await
+}
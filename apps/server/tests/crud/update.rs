@@ -8,6 +8,7 @@
 //! - meta.lastUpdated changes on update
 //! - Update-as-create (client-defined IDs)
 //! - Conditional update (If-Match)
+//! - Conditional update by search criteria (zero/one/many matches)
 //! - ID validation (body must match URL)
 //! - Resource type validation
 
@@ -247,6 +248,65 @@ async fn conditional_update_creates_when_no_match_and_no_id() -> anyhow::Result<
     .await
 }
 
+#[tokio::test]
+async fn conditional_update_no_match_assigns_fresh_server_id() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            register_search_parameter(
+                &app.state.db_pool,
+                "identifier",
+                "Patient",
+                "token",
+                "Patient.identifier",
+                &[],
+            )
+            .await?;
+
+            let existing = patient_with_mrn("Doe", "123");
+            let (status, _headers, body) = app
+                .request(
+                    Method::POST,
+                    "/fhir/Patient",
+                    Some(to_json_body(&existing)?),
+                )
+                .await?;
+            assert_status(status, StatusCode::CREATED, "create");
+            let existing: serde_json::Value = serde_json::from_slice(&body)?;
+            let existing_id = existing["id"].as_str().unwrap().to_string();
+
+            let patient = patient_with_mrn("Roe", "456");
+            let (status, _headers, body) = app
+                .request(
+                    Method::PUT,
+                    "/fhir/Patient?identifier=http://example.org/fhir/mrn|456",
+                    Some(to_json_body(&patient)?),
+                )
+                .await?;
+            assert_status(status, StatusCode::CREATED, "conditional update create");
+            let created: serde_json::Value = serde_json::from_slice(&body)?;
+            let created_id = created["id"].as_str().unwrap().to_string();
+            assert_ne!(created_id, existing_id);
+            assert_version_id(&created, "1")?;
+
+            // The same criteria now resolve to the created resource and update it in place.
+            let (status, _headers, body) = app
+                .request(
+                    Method::PUT,
+                    "/fhir/Patient?identifier=http://example.org/fhir/mrn|456",
+                    Some(to_json_body(&patient)?),
+                )
+                .await?;
+            assert_status(status, StatusCode::OK, "conditional update");
+            let updated: serde_json::Value = serde_json::from_slice(&body)?;
+            assert_resource_id(&updated, &created_id)?;
+            assert_version_id(&updated, "2")?;
+
+            Ok(())
+        })
+    })
+    .await
+}
+
 #[tokio::test]
 async fn conditional_update_updates_when_one_match() -> anyhow::Result<()> {
     with_test_app(|app| {
@@ -0,0 +1,315 @@
+#![allow(unused)]
+#[allow(unused)]
+mod support;
+
+use axum::http::{Method, StatusCode};
+use serde_json::{json, Value};
+use support::*;
+
+/// Register the $expunge OperationDefinition so the operation router accepts it.
+async fn setup_expunge(app: &TestApp) -> anyhow::Result<()> {
+    register_operation(
+        app,
+        OperationFixture {
+            code: "expunge",
+            type_level: true,
+            instance: true,
+            affects_state: true,
+            ..Default::default()
+        },
+    )
+    .await
+}
+
+fn enable_expunge(config: &mut ferrum::Config) {
+    config.fhir.enable_expunge = true;
+}
+
+fn parse_json(body: &[u8]) -> anyhow::Result<Value> {
+    Ok(serde_json::from_slice(body)?)
+}
+
+fn expunge_params(deleted_resources: bool, previous_versions: bool) -> Value {
+    json!({
+        "resourceType": "Parameters",
+        "parameter": [
+            {"name": "expungeDeletedResources", "valueBoolean": deleted_resources},
+            {"name": "expungePreviousVersions", "valueBoolean": previous_versions}
+        ]
+    })
+}
+
+fn expunge_params_with_limit(
+    deleted_resources: bool,
+    previous_versions: bool,
+    limit: u64,
+) -> Value {
+    let mut params = expunge_params(deleted_resources, previous_versions);
+    params["parameter"]
+        .as_array_mut()
+        .unwrap()
+        .push(json!({"name": "limit", "valuePositiveInt": limit}));
+    params
+}
+
+fn expunged_count(result: &Value) -> i64 {
+    result["parameter"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|p| p["name"] == "count")
+        .and_then(|p| p["valueInteger"].as_i64())
+        .unwrap_or(-1)
+}
+
+async fn create_patient_with_versions(app: &TestApp, versions: usize) -> anyhow::Result<String> {
+    let (status, _headers, body) = app
+        .request(
+            Method::POST,
+            "/fhir/Patient",
+            Some(to_json_body(&minimal_patient())?),
+        )
+        .await?;
+    assert_status(status, StatusCode::CREATED, "create Patient");
+    let mut patient = parse_json(&body)?;
+    let id = patient["id"].as_str().unwrap().to_string();
+
+    for n in 1..versions {
+        patient["active"] = json!(n % 2 == 0);
+        let (status, _headers, body) = app
+            .request(
+                Method::PUT,
+                &format!("/fhir/Patient/{}", id),
+                Some(to_json_body(&patient)?),
+            )
+            .await?;
+        assert_status(status, StatusCode::OK, "update Patient");
+        patient = parse_json(&body)?;
+    }
+
+    Ok(id)
+}
+
+#[tokio::test]
+async fn expunge_previous_versions_keeps_current_version() -> anyhow::Result<()> {
+    with_test_app_with_config(enable_expunge, |app| {
+        Box::pin(async move {
+            setup_expunge(app).await?;
+            let id = create_patient_with_versions(app, 3).await?;
+
+            let (status, _headers, body) = app
+                .request(
+                    Method::POST,
+                    &format!("/fhir/Patient/{}/$expunge", id),
+                    Some(to_json_body(&expunge_params(false, true))?),
+                )
+                .await?;
+            assert_status(status, StatusCode::OK, "$expunge previous versions");
+            assert_eq!(expunged_count(&parse_json(&body)?), 2);
+
+            let (status, _headers, body) = app
+                .request(Method::GET, &format!("/fhir/Patient/{}/_history", id), None)
+                .await?;
+            assert_status(status, StatusCode::OK, "history");
+            let history = parse_json(&body)?;
+            let entries = history["entry"].as_array().cloned().unwrap_or_default();
+            assert_eq!(entries.len(), 1, "only the current version should remain");
+            assert_eq!(entries[0]["resource"]["meta"]["versionId"], "3");
+
+            let (status, _headers, _body) = app
+                .request(
+                    Method::GET,
+                    &format!("/fhir/Patient/{}/_history/1", id),
+                    None,
+                )
+                .await?;
+            assert_eq!(
+                status,
+                StatusCode::NOT_FOUND,
+                "expunged version must be gone"
+            );
+
+            let (status, _headers, _body) = app
+                .request(Method::GET, &format!("/fhir/Patient/{}", id), None)
+                .await?;
+            assert_status(status, StatusCode::OK, "current version still readable");
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn expunge_deleted_resource_removes_all_versions_and_indexes() -> anyhow::Result<()> {
+    with_test_app_with_config(enable_expunge, |app| {
+        Box::pin(async move {
+            setup_expunge(app).await?;
+            let id = create_patient_with_versions(app, 2).await?;
+
+            let (status, _headers, _body) = app
+                .request(Method::DELETE, &format!("/fhir/Patient/{}", id), None)
+                .await?;
+            assert!(status.is_success(), "delete Patient: {status}");
+
+            let (status, _headers, body) = app
+                .request(
+                    Method::POST,
+                    "/fhir/Patient/$expunge",
+                    Some(to_json_body(&expunge_params(true, false))?),
+                )
+                .await?;
+            assert_status(status, StatusCode::OK, "$expunge deleted resources");
+            assert_eq!(expunged_count(&parse_json(&body)?), 3);
+
+            let rows: i64 = sqlx::query_scalar(
+                "SELECT COUNT(*) FROM resources WHERE resource_type = 'Patient' AND id = $1",
+            )
+            .bind(&id)
+            .fetch_one(&app.state.db_pool)
+            .await?;
+            assert_eq!(rows, 0);
+
+            let index_rows: i64 = sqlx::query_scalar(
+                "SELECT COUNT(*) FROM search_token WHERE resource_type = 'Patient' AND resource_id = $1",
+            )
+            .bind(&id)
+            .fetch_one(&app.state.db_pool)
+            .await?;
+            assert_eq!(index_rows, 0);
+
+            let (status, _headers, _body) = app
+                .request(Method::GET, &format!("/fhir/Patient/{}", id), None)
+                .await?;
+            assert_eq!(status, StatusCode::NOT_FOUND, "expunged resource is unknown");
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn expunge_refuses_current_version_of_live_resource() -> anyhow::Result<()> {
+    with_test_app_with_config(enable_expunge, |app| {
+        Box::pin(async move {
+            setup_expunge(app).await?;
+            let id = create_patient_with_versions(app, 1).await?;
+
+            let (status, _headers, _body) = app
+                .request(
+                    Method::POST,
+                    &format!("/fhir/Patient/{}/$expunge", id),
+                    Some(to_json_body(&expunge_params(true, false))?),
+                )
+                .await?;
+            assert_status(status, StatusCode::BAD_REQUEST, "$expunge live resource");
+
+            let (status, _headers, _body) = app
+                .request(Method::GET, &format!("/fhir/Patient/{}", id), None)
+                .await?;
+            assert_status(status, StatusCode::OK, "resource untouched");
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn expunge_with_both_flags_removes_history_of_live_resource() -> anyhow::Result<()> {
+    with_test_app_with_config(enable_expunge, |app| {
+        Box::pin(async move {
+            setup_expunge(app).await?;
+            let id = create_patient_with_versions(app, 3).await?;
+
+            let (status, _headers, body) = app
+                .request(
+                    Method::POST,
+                    &format!("/fhir/Patient/{}/$expunge", id),
+                    Some(to_json_body(&expunge_params(true, true))?),
+                )
+                .await?;
+            assert_status(status, StatusCode::OK, "$expunge with both flags");
+            assert_eq!(expunged_count(&parse_json(&body)?), 2);
+
+            let (status, _headers, _body) = app
+                .request(Method::GET, &format!("/fhir/Patient/{}", id), None)
+                .await?;
+            assert_status(status, StatusCode::OK, "current version kept");
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn expunge_limit_caps_removed_versions() -> anyhow::Result<()> {
+    with_test_app_with_config(enable_expunge, |app| {
+        Box::pin(async move {
+            setup_expunge(app).await?;
+            let deleted = create_patient_with_versions(app, 2).await?;
+            let (status, _headers, _body) = app
+                .request(Method::DELETE, &format!("/fhir/Patient/{}", deleted), None)
+                .await?;
+            assert!(status.is_success(), "delete Patient: {status}");
+            let live = create_patient_with_versions(app, 4).await?;
+
+            // The deleted patient (3 versions) fits; one old version of the live one follows.
+            let (status, _headers, body) = app
+                .request(
+                    Method::POST,
+                    "/fhir/Patient/$expunge",
+                    Some(to_json_body(&expunge_params_with_limit(true, true, 4))?),
+                )
+                .await?;
+            assert_status(status, StatusCode::OK, "$expunge with limit");
+            assert_eq!(expunged_count(&parse_json(&body)?), 4);
+
+            let versions: Vec<i32> = sqlx::query_scalar(
+                "SELECT version_id FROM resources
+                 WHERE resource_type = 'Patient' AND id = ANY($1)
+                 ORDER BY version_id",
+            )
+            .bind(vec![deleted, live])
+            .fetch_all(&app.state.db_pool)
+            .await?;
+            assert_eq!(versions, vec![2, 3, 4]);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn expunge_is_disabled_by_default() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            setup_expunge(app).await?;
+            let id = create_patient_with_versions(app, 2).await?;
+
+            let (status, _headers, _body) = app
+                .request(
+                    Method::POST,
+                    &format!("/fhir/Patient/{}/$expunge", id),
+                    Some(to_json_body(&expunge_params(false, true))?),
+                )
+                .await?;
+            assert_status(status, StatusCode::METHOD_NOT_ALLOWED, "$expunge disabled");
+
+            let (status, _headers, _body) = app
+                .request(
+                    Method::GET,
+                    &format!("/fhir/Patient/{}/_history/1", id),
+                    None,
+                )
+                .await?;
+            assert_status(status, StatusCode::OK, "history untouched");
+
+            Ok(())
+        })
+    })
+    .await
+}
@@ -0,0 +1,105 @@
+#![allow(unused)]
+#[allow(unused)]
+mod support;
+
+use axum::http::{Method, StatusCode};
+use serde_json::{json, Value};
+use support::*;
+
+/// Register the $fhirpath OperationDefinition so the operation router accepts it.
+async fn setup_fhirpath_operation(app: &TestApp) -> anyhow::Result<()> {
+    register_operation(
+        app,
+        OperationFixture {
+            code: "fhirpath",
+            instance: true,
+            ..Default::default()
+        },
+    )
+    .await
+}
+
+fn parse_json(body: &[u8]) -> anyhow::Result<Value> {
+    Ok(serde_json::from_slice(body)?)
+}
+
+fn expression_params(expression: &str) -> Value {
+    json!({
+        "resourceType": "Parameters",
+        "parameter": [{"name": "expression", "valueString": expression}]
+    })
+}
+
+async fn create_patient(app: &TestApp) -> anyhow::Result<String> {
+    let patient = json!({
+        "resourceType": "Patient",
+        "name": [
+            {"family": "Doe", "given": ["John", "Quincy"]},
+            {"family": "Roe", "given": ["Jane"]}
+        ]
+    });
+    let (status, _headers, body) = app
+        .request(Method::POST, "/fhir/Patient", Some(to_json_body(&patient)?))
+        .await?;
+    assert_status(status, StatusCode::CREATED, "create Patient");
+    Ok(parse_json(&body)?["id"].as_str().unwrap().to_string())
+}
+
+#[tokio::test]
+async fn fhirpath_evaluates_expression_against_stored_resource() -> anyhow::Result<()> {
+    with_test_app_with_config(
+        |config| {
+            config.fhir.fhirpath.enable_operation = true;
+        },
+        |app| {
+            Box::pin(async move {
+                setup_fhirpath_operation(app).await?;
+                let id = create_patient(app).await?;
+
+                let (status, _headers, body) = app
+                    .request(
+                        Method::POST,
+                        &format!("/fhir/Patient/{}/$fhirpath", id),
+                        Some(to_json_body(&expression_params("Patient.name.given"))?),
+                    )
+                    .await?;
+                assert_status(status, StatusCode::OK, "$fhirpath");
+
+                let result = parse_json(&body)?;
+                assert_eq!(result["resourceType"], "Parameters");
+                let params = result["parameter"].as_array().unwrap();
+                let given: Vec<&str> = params
+                    .iter()
+                    .filter(|p| p["name"] == "result")
+                    .map(|p| p["valueString"].as_str().unwrap())
+                    .collect();
+                assert_eq!(given, vec!["John", "Quincy", "Jane"]);
+
+                Ok(())
+            })
+        },
+    )
+    .await
+}
+
+#[tokio::test]
+async fn fhirpath_is_disabled_by_default() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            setup_fhirpath_operation(app).await?;
+            let id = create_patient(app).await?;
+
+            let (status, _headers, _body) = app
+                .request(
+                    Method::POST,
+                    &format!("/fhir/Patient/{}/$fhirpath", id),
+                    Some(to_json_body(&expression_params("Patient.name.given"))?),
+                )
+                .await?;
+            assert_status(status, StatusCode::METHOD_NOT_ALLOWED, "$fhirpath disabled");
+
+            Ok(())
+        })
+    })
+    .await
+}
@@ -437,3 +437,206 @@ async fn history_at_rejects_combined_with_since() -> anyhow::Result<()> {
     })
     .await
 }
+
+fn entry_types(bundle: &Value) -> Vec<String> {
+    entries(bundle)
+        .iter()
+        .filter_map(|e| e["request"]["url"].as_str())
+        .map(|url| url.split('/').next().unwrap_or("").to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn history_system_type_filter_narrows_results() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            let (status, _headers, body) = app
+                .request(
+                    Method::POST,
+                    "/fhir/Patient",
+                    Some(to_json_body(&minimal_patient())?),
+                )
+                .await?;
+            assert_status(status, StatusCode::CREATED, "create Patient");
+            let patient_id = parse_json(&body)?["id"].as_str().unwrap().to_string();
+
+            let obs = minimal_observation(&patient_id);
+            let (status, _headers, _body) = app
+                .request(Method::POST, "/fhir/Observation", Some(to_json_body(&obs)?))
+                .await?;
+            assert_status(status, StatusCode::CREATED, "create Observation");
+
+            let condition = condition_with_snomed(&patient_id, "38341003", "Hypertension");
+            let (status, _headers, _body) = app
+                .request(
+                    Method::POST,
+                    "/fhir/Condition",
+                    Some(to_json_body(&condition)?),
+                )
+                .await?;
+            assert_status(status, StatusCode::CREATED, "create Condition");
+
+            // Unfiltered system history sees all three types
+            let (status, _headers, body) = app.request(Method::GET, "/fhir/_history", None).await?;
+            assert_status(status, StatusCode::OK, "system history");
+            let types = entry_types(&parse_json(&body)?);
+            assert!(types.iter().any(|t| t == "Condition"));
+
+            let (status, _headers, body) = app
+                .request(
+                    Method::GET,
+                    "/fhir/_history?_type=Patient,Observation",
+                    None,
+                )
+                .await?;
+            assert_status(status, StatusCode::OK, "system history with _type");
+            let bundle = parse_json(&body)?;
+            let types = entry_types(&bundle);
+            assert!(types.iter().any(|t| t == "Patient"));
+            assert!(types.iter().any(|t| t == "Observation"));
+            assert!(
+                types.iter().all(|t| t == "Patient" || t == "Observation"),
+                "unexpected types in filtered history: {:?}",
+                types
+            );
+
+            let (status, _headers, body) = app
+                .request(Method::GET, "/fhir/_history?_type=Condition", None)
+                .await?;
+            assert_status(status, StatusCode::OK, "system history with single _type");
+            let types = entry_types(&parse_json(&body)?);
+            assert!(!types.is_empty());
+            assert!(types.iter().all(|t| t == "Condition"));
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn history_system_type_filter_rejects_unknown_types() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            let (status, _headers, body) = app
+                .request(Method::GET, "/fhir/_history?_type=Patient,NotAType", None)
+                .await?;
+            assert_status(status, StatusCode::BAD_REQUEST, "unknown _type");
+            let outcome = parse_json(&body)?;
+            assert_eq!(outcome["resourceType"], "OperationOutcome");
+
+            // _type is only defined for system-level history
+            let (status, _headers, _body) = app
+                .request(Method::GET, "/fhir/Patient/_history?_type=Patient", None)
+                .await?;
+            assert_status(status, StatusCode::BAD_REQUEST, "_type on type history");
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+fn next_link(bundle: &Value) -> Option<String> {
+    bundle["link"]
+        .as_array()?
+        .iter()
+        .find(|l| l["relation"] == "next")
+        .and_then(|l| l["url"].as_str())
+        .map(|url| url[url.find("/fhir/").expect("URL under /fhir")..].to_string())
+}
+
+#[tokio::test]
+async fn history_pages_with_count_and_next_links() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            let (status, _headers, body) = app
+                .request(
+                    Method::POST,
+                    "/fhir/Patient",
+                    Some(to_json_body(&minimal_patient())?),
+                )
+                .await?;
+            assert_status(status, StatusCode::CREATED, "create Patient");
+            let id = parse_json(&body)?["id"].as_str().unwrap().to_string();
+
+            for family in ["Two", "Three", "Four", "Five"] {
+                let mut patient = example_patient(family, "Pat");
+                patient["id"] = Value::String(id.clone());
+                let (status, _headers, _body) = app
+                    .request(
+                        Method::PUT,
+                        &format!("/fhir/Patient/{}", id),
+                        Some(to_json_body(&patient)?),
+                    )
+                    .await?;
+                assert_status(status, StatusCode::OK, "update Patient");
+            }
+
+            let mut path = Some(format!("/fhir/Patient/{}/_history?_count=2", id));
+            let mut versions = Vec::new();
+            let mut pages = 0;
+            while let Some(current) = path {
+                let (status, _headers, body) = app.request(Method::GET, &current, None).await?;
+                assert_status(status, StatusCode::OK, "history page");
+                let bundle = parse_json(&body)?;
+                let es = entries(&bundle);
+                assert!(es.len() <= 2, "page exceeds _count");
+                versions.extend(es.iter().map(|e| {
+                    e["resource"]["meta"]["versionId"]
+                        .as_str()
+                        .unwrap()
+                        .to_string()
+                }));
+                pages += 1;
+                path = next_link(&bundle);
+                if let Some(next) = &path {
+                    assert!(next.contains("_count=2"), "next link keeps _count");
+                }
+            }
+
+            assert_eq!(pages, 3);
+            assert_eq!(versions, vec!["5", "4", "3", "2", "1"]);
+
+            // Type-level history pages the same way
+            let mut path = Some("/fhir/Patient/_history?_count=2&_sort=_lastUpdated".to_string());
+            let mut type_versions = Vec::new();
+            let request_url = format!("Patient/{}", id);
+            while let Some(current) = path {
+                let (status, _headers, body) = app.request(Method::GET, &current, None).await?;
+                assert_status(status, StatusCode::OK, "type history page");
+                let bundle = parse_json(&body)?;
+                type_versions.extend(
+                    entries(&bundle)
+                        .iter()
+                        .filter(|e| e["request"]["url"].as_str() == Some(request_url.as_str()))
+                        .map(|e| {
+                            e["resource"]["meta"]["versionId"]
+                                .as_str()
+                                .unwrap()
+                                .to_string()
+                        }),
+                );
+                path = next_link(&bundle);
+            }
+            assert_eq!(type_versions, vec!["1", "2", "3", "4", "5"]);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn history_rejects_malformed_cursor() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            let (status, _headers, _body) = app
+                .request(Method::GET, "/fhir/_history?_cursor=not-a-cursor", None)
+                .await?;
+            assert_status(status, StatusCode::BAD_REQUEST, "malformed _cursor");
+            Ok(())
+        })
+    })
+    .await
+}
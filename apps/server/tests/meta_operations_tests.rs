@@ -0,0 +1,296 @@
+#![allow(unused)]
+#[allow(unused)]
+mod support;
+
+use axum::http::{Method, StatusCode};
+use serde_json::{json, Value};
+use support::*;
+
+const TAG_SYSTEM: &str = "http://example.org/tags";
+
+/// Register the $meta-add / $meta-delete OperationDefinitions so the operation router accepts them.
+async fn setup_meta_operations(app: &TestApp) -> anyhow::Result<()> {
+    for code in ["meta-add", "meta-delete"] {
+        register_operation(
+            app,
+            OperationFixture {
+                code,
+                instance: true,
+                affects_state: true,
+                ..Default::default()
+            },
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+fn parse_json(body: &[u8]) -> anyhow::Result<Value> {
+    Ok(serde_json::from_slice(body)?)
+}
+
+fn meta_params(meta: Value) -> Value {
+    json!({
+        "resourceType": "Parameters",
+        "parameter": [{"name": "meta", "valueMeta": meta}]
+    })
+}
+
+fn returned_meta(result: &Value) -> Value {
+    result["parameter"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|p| p["name"] == "return")
+        .map(|p| p["valueMeta"].clone())
+        .unwrap_or(Value::Null)
+}
+
+async fn create_patient(app: &TestApp) -> anyhow::Result<String> {
+    let (status, _headers, body) = app
+        .request(
+            Method::POST,
+            "/fhir/Patient",
+            Some(to_json_body(&minimal_patient())?),
+        )
+        .await?;
+    assert_status(status, StatusCode::CREATED, "create Patient");
+    Ok(parse_json(&body)?["id"].as_str().unwrap().to_string())
+}
+
+async fn search_by_tag(app: &TestApp, code: &str) -> anyhow::Result<Vec<String>> {
+    let (status, _headers, body) = app
+        .request(
+            Method::GET,
+            &format!("/fhir/Patient?_tag={}|{}", TAG_SYSTEM, code),
+            None,
+        )
+        .await?;
+    assert_status(status, StatusCode::OK, "search by _tag");
+    extract_resource_ids(&parse_json(&body)?, "Patient")
+}
+
+#[tokio::test]
+async fn meta_add_then_delete_updates_tag_search() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            setup_meta_operations(app).await?;
+            let id = create_patient(app).await?;
+            assert!(search_by_tag(app, "vip").await?.is_empty());
+
+            let (status, _headers, body) = app
+                .request(
+                    Method::POST,
+                    &format!("/fhir/Patient/{}/$meta-add", id),
+                    Some(to_json_body(&meta_params(json!({
+                        "tag": [{"system": TAG_SYSTEM, "code": "vip"}]
+                    })))?),
+                )
+                .await?;
+            assert_status(status, StatusCode::OK, "$meta-add");
+            let meta = returned_meta(&parse_json(&body)?);
+            assert_eq!(meta["tag"][0]["code"], "vip");
+            assert_eq!(meta["versionId"], "1", "meta-only change keeps the version");
+
+            assert_eq!(search_by_tag(app, "vip").await?, vec![id.clone()]);
+
+            let (status, _headers, body) = app
+                .request(
+                    Method::POST,
+                    &format!("/fhir/Patient/{}/$meta-delete", id),
+                    Some(to_json_body(&meta_params(json!({
+                        "tag": [{"system": TAG_SYSTEM, "code": "vip"}]
+                    })))?),
+                )
+                .await?;
+            assert_status(status, StatusCode::OK, "$meta-delete");
+            assert!(returned_meta(&parse_json(&body)?).get("tag").is_none());
+
+            assert!(search_by_tag(app, "vip").await?.is_empty());
+
+            let (status, _headers, body) = app
+                .request(Method::GET, &format!("/fhir/Patient/{}/_history", id), None)
+                .await?;
+            assert_status(status, StatusCode::OK, "history");
+            let history = parse_json(&body)?;
+            assert_eq!(
+                history["entry"].as_array().map(|e| e.len()),
+                Some(1),
+                "meta operations must not create new versions"
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn meta_update_reindexes_in_the_same_transaction() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            let id = create_patient(app).await?;
+
+            // No indexing job is queued: the store rebuilds the index rows itself.
+            let store = ferrum::db::PostgresResourceStore::new(app.state.db_pool.clone());
+            store
+                .update_current_meta("Patient", &id, &app.state.indexing_service, |meta| {
+                    meta.insert(
+                        "tag".to_string(),
+                        json!([{"system": TAG_SYSTEM, "code": "vip"}]),
+                    );
+                })
+                .await?;
+            assert_eq!(search_by_tag(app, "vip").await?, vec![id.clone()]);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn meta_add_keeps_existing_index_entries_searchable() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            setup_meta_operations(app).await?;
+            let mut patient = minimal_patient();
+            patient["meta"] = json!({"tag": [{"system": TAG_SYSTEM, "code": "existing"}]});
+            let (status, _headers, body) = app
+                .request(Method::POST, "/fhir/Patient", Some(to_json_body(&patient)?))
+                .await?;
+            assert_status(status, StatusCode::CREATED, "create Patient");
+            let id = parse_json(&body)?["id"].as_str().unwrap().to_string();
+
+            let (status, _headers, _body) = app
+                .request(
+                    Method::POST,
+                    &format!("/fhir/Patient/{}/$meta-add", id),
+                    Some(to_json_body(&meta_params(json!({
+                        "tag": [{"system": TAG_SYSTEM, "code": "vip"}]
+                    })))?),
+                )
+                .await?;
+            assert_status(status, StatusCode::OK, "$meta-add");
+
+            assert_eq!(search_by_tag(app, "vip").await?, vec![id.clone()]);
+            assert_eq!(search_by_tag(app, "existing").await?, vec![id.clone()]);
+            let (status, _headers, body) = app
+                .request(Method::GET, "/fhir/Patient?family=Doe", None)
+                .await?;
+            assert_status(status, StatusCode::OK, "search by family");
+            assert_eq!(
+                extract_resource_ids(&parse_json(&body)?, "Patient")?,
+                vec![id.clone()]
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn meta_changes_evict_cached_searches() -> anyhow::Result<()> {
+    with_test_app_with_config(
+        |config| {
+            config.fhir.search.cache.enabled = true;
+        },
+        |app| {
+            Box::pin(async move {
+                setup_meta_operations(app).await?;
+                let id = create_patient(app).await?;
+                assert!(search_by_tag(app, "vip").await?.is_empty());
+
+                let (status, _headers, _body) = app
+                    .request(
+                        Method::POST,
+                        &format!("/fhir/Patient/{}/$meta-add", id),
+                        Some(to_json_body(&meta_params(json!({
+                            "tag": [{"system": TAG_SYSTEM, "code": "vip"}]
+                        })))?),
+                    )
+                    .await?;
+                assert_status(status, StatusCode::OK, "$meta-add");
+                assert_eq!(search_by_tag(app, "vip").await?, vec![id.clone()]);
+
+                let (status, _headers, _body) = app
+                    .request(
+                        Method::POST,
+                        &format!("/fhir/Patient/{}/$meta-delete", id),
+                        Some(to_json_body(&meta_params(json!({
+                            "tag": [{"system": TAG_SYSTEM, "code": "vip"}]
+                        })))?),
+                    )
+                    .await?;
+                assert_status(status, StatusCode::OK, "$meta-delete");
+                assert!(search_by_tag(app, "vip").await?.is_empty());
+
+                Ok(())
+            })
+        },
+    )
+    .await
+}
+
+#[tokio::test]
+async fn meta_add_does_not_duplicate_existing_tags() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            setup_meta_operations(app).await?;
+            let id = create_patient(app).await?;
+
+            let params = meta_params(json!({
+                "tag": [{"system": TAG_SYSTEM, "code": "vip", "display": "VIP"}],
+                "profile": ["http://example.org/StructureDefinition/custom-patient"]
+            }));
+            for _ in 0..2 {
+                let (status, _headers, _body) = app
+                    .request(
+                        Method::POST,
+                        &format!("/fhir/Patient/{}/$meta-add", id),
+                        Some(to_json_body(&params)?),
+                    )
+                    .await?;
+                assert_status(status, StatusCode::OK, "$meta-add");
+            }
+
+            let (status, _headers, body) = app
+                .request(Method::GET, &format!("/fhir/Patient/{}", id), None)
+                .await?;
+            assert_status(status, StatusCode::OK, "read Patient");
+            let patient = parse_json(&body)?;
+            assert_eq!(patient["meta"]["tag"].as_array().map(|t| t.len()), Some(1));
+            assert_eq!(
+                patient["meta"]["profile"].as_array().map(|p| p.len()),
+                Some(1)
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn meta_add_on_unknown_resource_is_not_found() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            setup_meta_operations(app).await?;
+
+            let (status, _headers, _body) = app
+                .request(
+                    Method::POST,
+                    "/fhir/Patient/does-not-exist/$meta-add",
+                    Some(to_json_body(&meta_params(json!({
+                        "tag": [{"system": TAG_SYSTEM, "code": "vip"}]
+                    })))?),
+                )
+                .await?;
+            assert_status(status, StatusCode::NOT_FOUND, "$meta-add unknown resource");
+
+            Ok(())
+        })
+    })
+    .await
+}
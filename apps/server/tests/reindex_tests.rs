@@ -257,3 +257,292 @@ async fn reindex_rebuilds_search_indexes() -> anyhow::Result<()> {
     })
     .await
 }
+
+#[tokio::test]
+async fn admin_reindex_resource_type_repopulates_search_tables() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            setup_search_param(app).await?;
+
+            for family in ["Alpha", "Beta"] {
+                let patient = json!({
+                    "resourceType": "Patient",
+                    "name": [{"family": family}]
+                });
+                let (status, _headers, _body) = app
+                    .request(Method::POST, "/fhir/Patient", Some(to_json_body(&patient)?))
+                    .await?;
+                assert_status(status, StatusCode::CREATED, "create Patient");
+            }
+
+            sqlx::query("DELETE FROM search_string WHERE resource_type = 'Patient'")
+                .execute(&app.state.db_pool)
+                .await?;
+
+            let (status, _headers, body) = app
+                .request(Method::POST, "/admin/resources/Patient/reindex", None)
+                .await?;
+            assert_status(status, StatusCode::ACCEPTED, "admin reindex");
+
+            let result = parse_json(&body)?;
+            assert_eq!(result["resourceType"], "Patient");
+            let job_id = result["jobId"].as_str().unwrap().to_string();
+
+            // Progress is visible through the jobs API (InlineJobQueue runs it synchronously)
+            let (status, _headers, body) = app
+                .request(Method::GET, &format!("/admin/jobs/{}", job_id), None)
+                .await?;
+            assert_status(status, StatusCode::OK, "get job");
+            let job = parse_json(&body)?;
+            assert_eq!(job["jobType"], "reindex");
+            assert_eq!(job["status"], "Completed");
+            assert_eq!(job["processedItems"], 2);
+
+            let count: i64 = sqlx::query_scalar(
+                "SELECT COUNT(DISTINCT resource_id) FROM search_string WHERE resource_type = 'Patient' AND parameter_name = 'family'",
+            )
+            .fetch_one(&app.state.db_pool)
+            .await?;
+            assert_eq!(count, 2, "both patients should be reindexed");
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn reindex_jobs_for_same_type_are_coalesced() -> anyhow::Result<()> {
+    use ferrum::queue::{JobPriority, JobQueue, PostgresJobQueue};
+
+    with_test_app(|app| {
+        Box::pin(async move {
+            let queue = PostgresJobQueue::new(app.state.db_pool.clone(), 1);
+            let params = json!({"resource_type": "Patient", "resource_id": null});
+
+            let (first, created) = queue
+                .enqueue_unique(
+                    "reindex".to_string(),
+                    params.clone(),
+                    JobPriority::Normal,
+                    None,
+                )
+                .await?;
+            assert!(created);
+
+            let (second, created) = queue
+                .enqueue_unique("reindex".to_string(), params, JobPriority::Normal, None)
+                .await?;
+            assert!(!created, "second request should coalesce");
+            assert_eq!(first, second);
+
+            let (other, created) = queue
+                .enqueue_unique(
+                    "reindex".to_string(),
+                    json!({"resource_type": "Observation", "resource_id": null}),
+                    JobPriority::Normal,
+                    None,
+                )
+                .await?;
+            assert!(created, "a different type gets its own job");
+            assert_ne!(first, other);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn reindex_request_is_not_coalesced_into_running_job() -> anyhow::Result<()> {
+    use ferrum::queue::{JobPriority, JobQueue, PostgresJobQueue};
+
+    with_test_app(|app| {
+        Box::pin(async move {
+            let queue = PostgresJobQueue::new(app.state.db_pool.clone(), 1);
+            let params = json!({"resource_type": "Patient", "resource_id": null});
+
+            let (first, created) = queue
+                .enqueue_unique(
+                    "reindex".to_string(),
+                    params.clone(),
+                    JobPriority::Normal,
+                    None,
+                )
+                .await?;
+            assert!(created);
+
+            let running = queue
+                .dequeue(&["reindex".to_string()], "test-worker")
+                .await?
+                .expect("pending reindex job");
+            assert_eq!(running.id, first);
+
+            let (second, created) = queue
+                .enqueue_unique(
+                    "reindex".to_string(),
+                    params.clone(),
+                    JobPriority::Normal,
+                    None,
+                )
+                .await?;
+            assert!(created, "a running job must not absorb a new request");
+            assert_ne!(first, second);
+
+            let (third, created) = queue
+                .enqueue_unique("reindex".to_string(), params, JobPriority::Normal, None)
+                .await?;
+            assert!(!created, "the new pending job still coalesces");
+            assert_eq!(second, third);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn search_warns_about_unindexed_parameter_until_reindex() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            setup_reindex(app).await?;
+
+            // The Patient is stored before the search parameter exists, so it has no index rows.
+            let patient = minimal_patient();
+            let (status, _headers, body) = app
+                .request(Method::POST, "/fhir/Patient", Some(to_json_body(&patient)?))
+                .await?;
+            assert_status(status, StatusCode::CREATED, "create Patient");
+            let patient_id = parse_json(&body)?["id"].as_str().unwrap().to_string();
+
+            setup_search_param(app).await?;
+            app.state.indexing_service.invalidate_cache(Some("Patient"));
+
+            let (status, _headers, body) = app
+                .request_with_extra_headers(
+                    Method::GET,
+                    "/fhir/Patient?family=Doe",
+                    None,
+                    &[("prefer", "handling=strict")],
+                )
+                .await?;
+            assert_status(status, StatusCode::OK, "search before reindex");
+            let bundle = parse_json(&body)?;
+            let outcome = get_bundle_entries(&bundle)?
+                .iter()
+                .find(|e| e["search"]["mode"] == "outcome")
+                .expect("outcome entry for unindexed parameter")
+                .clone();
+            assert_eq!(outcome["resource"]["issue"][0]["severity"], "warning");
+            assert_eq!(outcome["resource"]["issue"][0]["code"], "incomplete");
+            let diagnostics = outcome["resource"]["issue"][0]["diagnostics"]
+                .as_str()
+                .unwrap_or_default();
+            assert!(diagnostics.contains("family"), "{diagnostics}");
+
+            // Lenient handling does not report it.
+            let (status, _headers, body) = app
+                .request(Method::GET, "/fhir/Patient?family=Doe", None)
+                .await?;
+            assert_status(status, StatusCode::OK, "lenient search before reindex");
+            let bundle = parse_json(&body)?;
+            assert!(get_bundle_entries(&bundle)?
+                .iter()
+                .all(|e| e["search"]["mode"] != "outcome"));
+
+            let (status, _headers, _body) = app
+                .request(Method::POST, "/fhir/Patient/$reindex", None)
+                .await?;
+            assert_status(status, StatusCode::OK, "$reindex type-level");
+
+            let (status, _headers, body) = app
+                .request_with_extra_headers(
+                    Method::GET,
+                    "/fhir/Patient?family=Doe",
+                    None,
+                    &[("prefer", "handling=strict")],
+                )
+                .await?;
+            assert_status(status, StatusCode::OK, "search after reindex");
+            let bundle = parse_json(&body)?;
+            assert_bundle_contains_id(&bundle, "Patient", &patient_id)?;
+            assert!(
+                get_bundle_entries(&bundle)?
+                    .iter()
+                    .all(|e| e["search"]["mode"] != "outcome"),
+                "no warning once the parameter is reindexed"
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+/// Strict search on `path`, returning whether it reports an unindexed parameter.
+async fn warns_unindexed(app: &TestApp, path: &str) -> anyhow::Result<bool> {
+    let (status, _headers, body) = app
+        .request_with_extra_headers(Method::GET, path, None, &[("prefer", "handling=strict")])
+        .await?;
+    assert_status(status, StatusCode::OK, path);
+    let bundle = parse_json(&body)?;
+    Ok(get_bundle_entries(&bundle)?
+        .iter()
+        .any(|e| e["search"]["mode"] == "outcome"))
+}
+
+#[tokio::test]
+async fn type_level_reindex_covers_resource_parameters_for_that_type_only() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            setup_reindex(app).await?;
+
+            let mut patient = minimal_patient();
+            patient["language"] = json!("en");
+            let (status, _headers, body) = app
+                .request(Method::POST, "/fhir/Patient", Some(to_json_body(&patient)?))
+                .await?;
+            assert_status(status, StatusCode::CREATED, "create Patient");
+            let patient_id = parse_json(&body)?["id"].as_str().unwrap().to_string();
+            let mut observation = minimal_observation(&patient_id);
+            observation["language"] = json!("en");
+            let (status, _headers, _body) = app
+                .request(
+                    Method::POST,
+                    "/fhir/Observation",
+                    Some(to_json_body(&observation)?),
+                )
+                .await?;
+            assert_status(status, StatusCode::CREATED, "create Observation");
+
+            // A parameter on Resource applies to every type stored before it existed.
+            register_search_parameter(
+                &app.state.db_pool,
+                "lang",
+                "Resource",
+                "token",
+                "Resource.language",
+                &[],
+            )
+            .await?;
+            app.state.search_engine.invalidate_param_cache();
+            app.state.indexing_service.invalidate_cache(None);
+            assert!(warns_unindexed(app, "/fhir/Patient?lang=en").await?);
+            assert!(warns_unindexed(app, "/fhir/Observation?lang=en").await?);
+
+            let (status, _headers, _body) = app
+                .request(Method::POST, "/fhir/Patient/$reindex", None)
+                .await?;
+            assert_status(status, StatusCode::OK, "$reindex type-level");
+
+            assert!(!warns_unindexed(app, "/fhir/Patient?lang=en").await?);
+            assert!(
+                warns_unindexed(app, "/fhir/Observation?lang=en").await?,
+                "Observations were not reindexed"
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
@@ -0,0 +1,221 @@
+//! Search result cache validators and server-side result cache
+//!
+//! Search responses carry a weak ETag derived from the query, the response format and the
+//! matched entries; `If-None-Match` with that ETag returns 304 Not Modified.
+//! With `fhir.search.cache.enabled`, identical searches are served from memory until a
+//! resource of a searched type is written.
+
+use crate::support::*;
+use axum::http::{Method, StatusCode};
+use serde_json::json;
+
+async fn search_etag(app: &TestApp, path: &str) -> anyhow::Result<String> {
+    let (status, headers, _body) = app.request(Method::GET, path, None).await?;
+    assert_status(status, StatusCode::OK, "search");
+    let etag = headers
+        .get("etag")
+        .and_then(|v| v.to_str().ok())
+        .expect("search response has an ETag")
+        .to_string();
+    assert!(etag.starts_with("W/\""), "expected weak ETag, got {}", etag);
+    Ok(etag)
+}
+
+async fn conditional_search_status(
+    app: &TestApp,
+    path: &str,
+    etag: &str,
+) -> anyhow::Result<StatusCode> {
+    let (status, _headers, _body) = app
+        .request_with_extra_headers(Method::GET, path, None, &[("if-none-match", etag)])
+        .await?;
+    Ok(status)
+}
+
+#[tokio::test]
+async fn repeated_search_with_etag_returns_not_modified() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            let patient = PatientBuilder::new().family("Cached").build();
+            let (status, _, _) = app
+                .request(Method::POST, "/fhir/Patient", Some(to_json_body(&patient)?))
+                .await?;
+            assert_status(status, StatusCode::CREATED, "create patient");
+
+            let etag = search_etag(app, "/fhir/Patient").await?;
+            assert_eq!(search_etag(app, "/fhir/Patient").await?, etag);
+            assert_eq!(
+                conditional_search_status(app, "/fhir/Patient", &etag).await?,
+                StatusCode::NOT_MODIFIED
+            );
+
+            // A new matching resource invalidates the validator.
+            let (status, _, _) = app
+                .request(Method::POST, "/fhir/Patient", Some(to_json_body(&patient)?))
+                .await?;
+            assert_status(status, StatusCode::CREATED, "create second patient");
+            assert_eq!(
+                conditional_search_status(app, "/fhir/Patient", &etag).await?,
+                StatusCode::OK
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn empty_search_has_stable_etag() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            let etag = search_etag(app, "/fhir/Patient").await?;
+            assert_eq!(search_etag(app, "/fhir/Patient").await?, etag);
+            assert_eq!(
+                conditional_search_status(app, "/fhir/Patient", &etag).await?,
+                StatusCode::NOT_MODIFIED
+            );
+
+            // Different queries get different validators.
+            assert_ne!(search_etag(app, "/fhir/Patient?_count=5").await?, etag);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn etag_covers_meta_changes_and_response_format() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            register_operation(
+                app,
+                OperationFixture {
+                    code: "meta-add",
+                    instance: true,
+                    affects_state: true,
+                    ..Default::default()
+                },
+            )
+            .await?;
+            let patient = PatientBuilder::new().family("Tagged").build();
+            let (status, _, body) = app
+                .request(Method::POST, "/fhir/Patient", Some(to_json_body(&patient)?))
+                .await?;
+            assert_status(status, StatusCode::CREATED, "create patient");
+            let created: serde_json::Value = serde_json::from_slice(&body)?;
+            let id = created["id"].as_str().unwrap().to_string();
+
+            let etag = search_etag(app, "/fhir/Patient").await?;
+
+            let (_status, headers, _body) = app
+                .request_with_extra_headers(
+                    Method::GET,
+                    "/fhir/Patient",
+                    None,
+                    &[("accept", "application/fhir+xml")],
+                )
+                .await?;
+            let xml_etag = headers.get("etag").and_then(|v| v.to_str().ok());
+            assert_ne!(xml_etag, Some(etag.as_str()), "format is part of the ETag");
+
+            // $meta-add keeps versionId and lastUpdated but still changes the result.
+            let params = json!({
+                "resourceType": "Parameters",
+                "parameter": [{
+                    "name": "meta",
+                    "valueMeta": {"tag": [{"system": "http://example.org/tags", "code": "vip"}]}
+                }]
+            });
+            let (status, _, _) = app
+                .request(
+                    Method::POST,
+                    &format!("/fhir/Patient/{}/$meta-add", id),
+                    Some(to_json_body(&params)?),
+                )
+                .await?;
+            assert_status(status, StatusCode::OK, "$meta-add");
+            assert_eq!(
+                conditional_search_status(app, "/fhir/Patient", &etag).await?,
+                StatusCode::OK
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+async fn search_total(app: &TestApp, path: &str) -> anyhow::Result<u64> {
+    let (status, _headers, body) = app.request(Method::GET, path, None).await?;
+    assert_status(status, StatusCode::OK, "search");
+    let bundle: serde_json::Value = serde_json::from_slice(&body)?;
+    Ok(bundle["total"].as_u64().expect("search bundle has a total"))
+}
+
+#[tokio::test]
+async fn repeated_search_is_served_from_cache_until_a_write() -> anyhow::Result<()> {
+    with_test_app_with_config(
+        |config| {
+            config.fhir.search.cache.enabled = true;
+        },
+        |app| {
+            Box::pin(async move {
+                let cache = app
+                    .state
+                    .search_cache
+                    .clone()
+                    .expect("search cache enabled");
+                register_search_parameter(
+                    &app.state.db_pool,
+                    "family",
+                    "Patient",
+                    "string",
+                    "Patient.name.family",
+                    &[],
+                )
+                .await?;
+
+                let patient = PatientBuilder::new().family("Cached").build();
+                let (status, _, _) = app
+                    .request(Method::POST, "/fhir/Patient", Some(to_json_body(&patient)?))
+                    .await?;
+                assert_status(status, StatusCode::CREATED, "create patient");
+
+                let path = "/fhir/Patient?family=Cached&_total=accurate";
+                assert_eq!(search_total(app, path).await?, 1);
+                assert_eq!(search_total(app, path).await?, 1);
+                assert_eq!((cache.hits(), cache.misses()), (1, 1));
+
+                // Writes to other types keep the entry.
+                let observation = serde_json::json!({
+                    "resourceType": "Observation",
+                    "status": "final",
+                    "code": {"text": "unrelated"}
+                });
+                let (status, _, _) = app
+                    .request(
+                        Method::POST,
+                        "/fhir/Observation",
+                        Some(to_json_body(&observation)?),
+                    )
+                    .await?;
+                assert_status(status, StatusCode::CREATED, "create observation");
+                assert_eq!(search_total(app, path).await?, 1);
+                assert_eq!((cache.hits(), cache.misses()), (2, 1));
+
+                // A Patient write evicts it and the next search sees the new resource.
+                let (status, _, _) = app
+                    .request(Method::POST, "/fhir/Patient", Some(to_json_body(&patient)?))
+                    .await?;
+                assert_status(status, StatusCode::CREATED, "create second patient");
+                assert_eq!(search_total(app, path).await?, 2);
+                assert_eq!((cache.hits(), cache.misses()), (2, 2));
+
+                Ok(())
+            })
+        },
+    )
+    .await
+}
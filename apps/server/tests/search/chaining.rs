@@ -742,3 +742,87 @@ async fn chain_with_date_parameter() -> anyhow::Result<()> {
     })
     .await
 }
+
+#[tokio::test]
+async fn chain_uses_declared_reference_targets() -> anyhow::Result<()> {
+    // Test: Observation?recorded-by-device.manufacturer=Acme
+    // The custom parameter declares Device as its only target, which the name-based
+    // heuristic (Patient/Practitioner/Organization) would never guess.
+    with_test_app(|app| {
+        Box::pin(async move {
+            register_reference_search_parameter(
+                &app.state.db_pool,
+                "recorded-by-device",
+                "Observation",
+                "Observation.device",
+                &["Device"],
+            )
+            .await?;
+
+            register_search_parameter(
+                &app.state.db_pool,
+                "manufacturer",
+                "Device",
+                "string",
+                "Device.manufacturer",
+                &[],
+            )
+            .await?;
+
+            let mut observation_ids = Vec::new();
+            for manufacturer in ["Acme", "Globex"] {
+                let device = json!({
+                    "resourceType": "Device",
+                    "manufacturer": manufacturer
+                });
+                let (status, _, body) = app
+                    .request(Method::POST, "/fhir/Device", Some(to_json_body(&device)?))
+                    .await?;
+                assert_status(status, StatusCode::CREATED, "create device");
+                let device_id = serde_json::from_slice::<serde_json::Value>(&body)?["id"]
+                    .as_str()
+                    .unwrap()
+                    .to_string();
+
+                let observation = json!({
+                    "resourceType": "Observation",
+                    "status": "final",
+                    "code": {"text": "Reading"},
+                    "device": {"reference": format!("Device/{}", device_id)}
+                });
+                let (status, _, body) = app
+                    .request(
+                        Method::POST,
+                        "/fhir/Observation",
+                        Some(to_json_body(&observation)?),
+                    )
+                    .await?;
+                assert_status(status, StatusCode::CREATED, "create observation");
+                observation_ids.push(
+                    serde_json::from_slice::<serde_json::Value>(&body)?["id"]
+                        .as_str()
+                        .unwrap()
+                        .to_string(),
+                );
+            }
+
+            // Strict handling ensures the chain is resolved rather than silently ignored.
+            let (status, _, body) = app
+                .request_with_extra_headers(
+                    Method::GET,
+                    "/fhir/Observation?recorded-by-device.manufacturer=Acme",
+                    None,
+                    &[("prefer", "handling=strict")],
+                )
+                .await?;
+            assert_status(status, StatusCode::OK, "search with declared-target chain");
+
+            let bundle: serde_json::Value = serde_json::from_slice(&body)?;
+            let ids = extract_resource_ids(&bundle, "Observation")?;
+            assert_eq!(ids, vec![observation_ids[0].clone()]);
+
+            Ok(())
+        })
+    })
+    .await
+}
@@ -0,0 +1,72 @@
+//! Admin search SQL preview (`GET /admin/search/explain`)
+//!
+//! Returns the parameterized SQL and bind values for a search without running it.
+
+use crate::support::*;
+use axum::http::{Method, StatusCode};
+
+#[tokio::test]
+async fn explain_token_search_returns_sql_with_redacted_values() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            register_search_parameter(
+                &app.state.db_pool,
+                "code",
+                "Observation",
+                "token",
+                "Observation.code",
+                &[],
+            )
+            .await?;
+
+            let (status, _headers, body) = app
+                .request(
+                    Method::GET,
+                    "/admin/search/explain?type=Observation&code=http://loinc.org%7C1234-5",
+                    None,
+                )
+                .await?;
+            assert_status(status, StatusCode::OK, "explain search");
+            let explanation: serde_json::Value = serde_json::from_slice(&body)?;
+
+            assert_eq!(explanation["resourceType"], "Observation");
+            let sql = explanation["sql"].as_str().unwrap();
+            assert!(
+                sql.starts_with("SELECT r.resource FROM resources r"),
+                "{sql}"
+            );
+            assert!(sql.contains("r.resource_type = $1"), "{sql}");
+            assert!(sql.contains("FROM search_token sp"), "{sql}");
+            assert!(sql.contains("sp.parameter_name = $"), "{sql}");
+
+            let binds = explanation["bindValues"].as_array().unwrap();
+            assert_eq!(binds[0], "Observation");
+            assert!(binds.iter().any(|b| b == "code"));
+            assert!(binds.iter().any(|b| b == "<redacted>"));
+            assert!(!explanation.to_string().contains("1234-5"));
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn explain_requires_known_type() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            let (status, _headers, _body) = app
+                .request(Method::GET, "/admin/search/explain?code=x", None)
+                .await?;
+            assert_status(status, StatusCode::BAD_REQUEST, "missing type");
+
+            let (status, _headers, _body) = app
+                .request(Method::GET, "/admin/search/explain?type=NotAType", None)
+                .await?;
+            assert_status(status, StatusCode::BAD_REQUEST, "unknown type");
+
+            Ok(())
+        })
+    })
+    .await
+}
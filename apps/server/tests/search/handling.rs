@@ -0,0 +1,82 @@
+//! Prefer: handling=strict|lenient tests
+//!
+//! FHIR Spec: 3.2.1.1 - Handling errors (unknown/unsupported parameters)
+
+use crate::support::*;
+use axum::http::{Method, StatusCode};
+use serde_json::Value;
+
+#[tokio::test]
+async fn strict_handling_rejects_unknown_parameter() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            let (status, _, body) = app
+                .request_with_extra_headers(
+                    Method::GET,
+                    "/fhir/Patient?bogus-param=xyz",
+                    None,
+                    &[("prefer", "handling=strict")],
+                )
+                .await?;
+            assert_status(status, StatusCode::BAD_REQUEST, "strict search");
+
+            let outcome: Value = serde_json::from_slice(&body)?;
+            assert_eq!(outcome["resourceType"], "OperationOutcome");
+            let diagnostics = outcome["issue"][0]["diagnostics"]
+                .as_str()
+                .unwrap_or_default();
+            assert!(
+                diagnostics.contains("bogus-param"),
+                "diagnostics should name the unknown parameter: {diagnostics}"
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn lenient_handling_ignores_unknown_parameter_with_warning() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            let patient = PatientBuilder::new().family("Lenient").build();
+            let (status, _, body) = app
+                .request(Method::POST, "/fhir/Patient", Some(to_json_body(&patient)?))
+                .await?;
+            assert_status(status, StatusCode::CREATED, "create patient");
+            let patient_id = serde_json::from_slice::<Value>(&body)?["id"]
+                .as_str()
+                .unwrap()
+                .to_string();
+
+            let (status, _, body) = app
+                .request_with_extra_headers(
+                    Method::GET,
+                    "/fhir/Patient?bogus-param=xyz",
+                    None,
+                    &[("prefer", "handling=lenient")],
+                )
+                .await?;
+            assert_status(status, StatusCode::OK, "lenient search");
+
+            let bundle: Value = serde_json::from_slice(&body)?;
+            assert_bundle_type(&bundle, "searchset")?;
+            assert_bundle_contains_id(&bundle, "Patient", &patient_id)?;
+
+            let outcome = get_bundle_entries(&bundle)?
+                .iter()
+                .find(|e| e["search"]["mode"] == "outcome")
+                .expect("outcome entry for ignored parameter");
+            assert_eq!(outcome["resource"]["resourceType"], "OperationOutcome");
+            assert_eq!(outcome["resource"]["issue"][0]["severity"], "warning");
+            let diagnostics = outcome["resource"]["issue"][0]["diagnostics"]
+                .as_str()
+                .unwrap_or_default();
+            assert!(diagnostics.contains("bogus-param"), "{diagnostics}");
+
+            Ok(())
+        })
+    })
+    .await
+}
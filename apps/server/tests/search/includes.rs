@@ -113,6 +113,144 @@ async fn revinclude_filters_by_source_type() -> anyhow::Result<()> {
     .await
 }
 
+#[tokio::test]
+async fn revinclude_wildcard_returns_all_referencing_resources() -> anyhow::Result<()> {
+    // Patient?_revinclude=* should pull in every resource referencing the matched Patient,
+    // whichever reference parameter links them. Adding an explicit directive on top of the
+    // wildcard must not duplicate entries.
+    with_test_app(|app| {
+        Box::pin(async move {
+            let pool = &app.state.db_pool;
+
+            register_search_parameter(pool, "subject", "Condition", "reference", "Condition.subject", &["Patient"]).await?;
+            register_search_parameter(pool, "subject", "Observation", "reference", "Observation.subject", &["Patient"]).await?;
+
+            let patient = json!({"resourceType": "Patient", "name": [{"family": "Doe"}]});
+            let (status, _, body) = app.request(Method::POST, "/fhir/Patient", Some(to_json_body(&patient)?)).await?;
+            assert_status(status, StatusCode::CREATED, "create patient");
+            let patient_id = serde_json::from_slice::<serde_json::Value>(&body)?["id"].as_str().unwrap().to_string();
+
+            let condition = json!({
+                "resourceType": "Condition",
+                "subject": {"reference": format!("Patient/{}", patient_id)},
+                "code": {"text": "Headache"}
+            });
+            let (status, _, body) = app.request(Method::POST, "/fhir/Condition", Some(to_json_body(&condition)?)).await?;
+            assert_status(status, StatusCode::CREATED, "create condition");
+            let cond_id = serde_json::from_slice::<serde_json::Value>(&body)?["id"].as_str().unwrap().to_string();
+
+            let observation = json!({
+                "resourceType": "Observation",
+                "status": "final",
+                "code": {"text": "BP"},
+                "subject": {"reference": format!("Patient/{}", patient_id)}
+            });
+            let (status, _, body) = app.request(Method::POST, "/fhir/Observation", Some(to_json_body(&observation)?)).await?;
+            assert_status(status, StatusCode::CREATED, "create observation");
+            let obs_id = serde_json::from_slice::<serde_json::Value>(&body)?["id"].as_str().unwrap().to_string();
+
+            for query in ["_revinclude=*", "_revinclude=*&_revinclude=Condition:subject"] {
+                let (status, _, body) = app.request(Method::GET, &format!("/fhir/Patient?{}", query), None).await?;
+                assert_status(status, StatusCode::OK, query);
+
+                let bundle: serde_json::Value = serde_json::from_slice(&body)?;
+                assert_bundle(&bundle)?;
+
+                let include_cond_ids = extract_resource_ids_by_mode(&bundle, "Condition", "include")?;
+                assert_eq!(include_cond_ids, vec![cond_id.clone()], "Condition included once for {}", query);
+
+                let include_obs_ids = extract_resource_ids_by_mode(&bundle, "Observation", "include")?;
+                assert_eq!(include_obs_ids, vec![obs_id.clone()], "Observation included once for {}", query);
+            }
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn revinclude_wildcard_respects_expansion_limit() -> anyhow::Result<()> {
+    // A wildcard linking more reference parameters than allowed is rejected as too costly.
+    with_test_app_with_config(
+        |config| {
+            config.fhir.search.max_wildcard_include_params = 1;
+        },
+        |app| {
+            Box::pin(async move {
+                let pool = &app.state.db_pool;
+
+                register_search_parameter(pool, "subject", "Condition", "reference", "Condition.subject", &["Patient"]).await?;
+                register_search_parameter(pool, "subject", "Observation", "reference", "Observation.subject", &["Patient"]).await?;
+
+                let patient = json!({"resourceType": "Patient", "name": [{"family": "Doe"}]});
+                let (status, _, body) = app.request(Method::POST, "/fhir/Patient", Some(to_json_body(&patient)?)).await?;
+                assert_status(status, StatusCode::CREATED, "create patient");
+                let patient_id = serde_json::from_slice::<serde_json::Value>(&body)?["id"].as_str().unwrap().to_string();
+
+                let condition = json!({
+                    "resourceType": "Condition",
+                    "subject": {"reference": format!("Patient/{}", patient_id)},
+                    "code": {"text": "Headache"}
+                });
+                let (status, _, _body) = app.request(Method::POST, "/fhir/Condition", Some(to_json_body(&condition)?)).await?;
+                assert_status(status, StatusCode::CREATED, "create condition");
+
+                let observation = json!({
+                    "resourceType": "Observation",
+                    "status": "final",
+                    "code": {"text": "BP"},
+                    "subject": {"reference": format!("Patient/{}", patient_id)}
+                });
+                let (status, _, _body) = app.request(Method::POST, "/fhir/Observation", Some(to_json_body(&observation)?)).await?;
+                assert_status(status, StatusCode::CREATED, "create observation");
+
+                let (status, _, _body) = app.request(Method::GET, "/fhir/Patient?_revinclude=*", None).await?;
+                assert_status(status, StatusCode::FORBIDDEN, "wildcard over limit");
+
+                Ok(())
+            })
+        },
+    )
+    .await
+}
+
+#[tokio::test]
+async fn revinclude_wildcard_limit_is_checked_before_querying() -> anyhow::Result<()> {
+    // The expansion comes from the reference parameter definitions, so the limit applies even
+    // when no stored resource references the Patient yet.
+    with_test_app_with_config(
+        |config| {
+            config.fhir.search.max_wildcard_include_params = 2;
+        },
+        |app| {
+            Box::pin(async move {
+                let pool = &app.state.db_pool;
+
+                register_reference_search_parameter(pool, "subject", "Condition", "Condition.subject", &["Patient"]).await?;
+                register_reference_search_parameter(pool, "subject", "Observation", "Observation.subject", &["Patient"]).await?;
+                register_reference_search_parameter(pool, "performer", "Observation", "Observation.performer", &["Practitioner"]).await?;
+                app.state.search_engine.invalidate_param_cache();
+
+                let patient = json!({"resourceType": "Patient", "name": [{"family": "Doe"}]});
+                let (status, _, _body) = app.request(Method::POST, "/fhir/Patient", Some(to_json_body(&patient)?)).await?;
+                assert_status(status, StatusCode::CREATED, "create patient");
+
+                let (status, _, _body) = app.request(Method::GET, "/fhir/Patient?_revinclude=*", None).await?;
+                assert_status(status, StatusCode::FORBIDDEN, "wildcard over limit");
+
+                // Narrowed to Observation: `subject` and the inherited `_profile` may target a
+                // Patient, `performer` cannot.
+                let (status, _, _body) = app.request(Method::GET, "/fhir/Patient?_revinclude=Observation:*", None).await?;
+                assert_status(status, StatusCode::OK, "wildcard within limit");
+
+                Ok(())
+            })
+        },
+    )
+    .await
+}
+
 // ============================================================================
 // _include
 // ============================================================================
@@ -162,3 +300,76 @@ async fn include_basic() -> anyhow::Result<()> {
     })
     .await
 }
+
+// ============================================================================
+// _include:iterate
+// ============================================================================
+
+#[tokio::test]
+async fn include_iterate_follows_chain_once() -> anyhow::Result<()> {
+    // Observation?_include=Observation:subject&_include:iterate=Patient:organization
+    // should return the Observation, its Patient and the Patient's Organization,
+    // each exactly once.
+    with_test_app(|app| {
+        Box::pin(async move {
+            let pool = &app.state.db_pool;
+
+            register_search_parameter(pool, "subject", "Observation", "reference", "Observation.subject", &[]).await?;
+            register_search_parameter(pool, "organization", "Patient", "reference", "Patient.managingOrganization", &[]).await?;
+
+            // Create Organization
+            let org = json!({"resourceType": "Organization", "name": "Acme Health"});
+            let (status, _, body) = app.request(Method::POST, "/fhir/Organization", Some(to_json_body(&org)?)).await?;
+            assert_status(status, StatusCode::CREATED, "create organization");
+            let org_id = serde_json::from_slice::<serde_json::Value>(&body)?["id"].as_str().unwrap().to_string();
+
+            // Create Patient managed by that Organization
+            let patient = json!({
+                "resourceType": "Patient",
+                "name": [{"family": "Doe"}],
+                "managingOrganization": {"reference": format!("Organization/{}", org_id)}
+            });
+            let (status, _, body) = app.request(Method::POST, "/fhir/Patient", Some(to_json_body(&patient)?)).await?;
+            assert_status(status, StatusCode::CREATED, "create patient");
+            let patient_id = serde_json::from_slice::<serde_json::Value>(&body)?["id"].as_str().unwrap().to_string();
+
+            // Create Observation referencing Patient
+            let observation = json!({
+                "resourceType": "Observation",
+                "status": "final",
+                "code": {"text": "Heart rate"},
+                "subject": {"reference": format!("Patient/{}", patient_id)}
+            });
+            let (status, _, body) = app.request(Method::POST, "/fhir/Observation", Some(to_json_body(&observation)?)).await?;
+            assert_status(status, StatusCode::CREATED, "create observation");
+            let obs_id = serde_json::from_slice::<serde_json::Value>(&body)?["id"].as_str().unwrap().to_string();
+
+            // Search
+            let (status, _, body) = app
+                .request(
+                    Method::GET,
+                    "/fhir/Observation?_include=Observation:subject&_include:iterate=Patient:organization",
+                    None,
+                )
+                .await?;
+            assert_status(status, StatusCode::OK, "search");
+
+            let bundle: serde_json::Value = serde_json::from_slice(&body)?;
+            assert_bundle(&bundle)?;
+
+            let match_obs = extract_resource_ids_by_mode(&bundle, "Observation", "match")?;
+            assert_eq!(match_obs, vec![obs_id], "Observation should be the only match");
+
+            let include_patients = extract_resource_ids_by_mode(&bundle, "Patient", "include")?;
+            assert_eq!(include_patients, vec![patient_id], "Patient should be included once");
+
+            let include_orgs = extract_resource_ids_by_mode(&bundle, "Organization", "include")?;
+            assert_eq!(include_orgs, vec![org_id], "Organization should be included once via :iterate");
+
+            assert_eq!(get_bundle_entries(&bundle)?.len(), 3, "each linked resource appears once");
+
+            Ok(())
+        })
+    })
+    .await
+}
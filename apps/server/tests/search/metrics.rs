@@ -0,0 +1,71 @@
+//! Per-search-parameter timing metrics
+//!
+//! Every search records its resolution and SQL time under each parameter it used in
+//! `fhir_search_parameter_duration_seconds`, exposed on `/metrics`.
+
+use crate::support::*;
+use axum::http::{Method, StatusCode};
+
+async fn has_parameter_sample(
+    app: &TestApp,
+    resource_type: &str,
+    parameter: &str,
+) -> anyhow::Result<bool> {
+    let (status, _headers, body) = app.request(Method::GET, "/metrics", None).await?;
+    assert_status(status, StatusCode::OK, "metrics");
+    let text = String::from_utf8(body.to_vec())?;
+    let resource_label = format!("resource_type=\"{}\"", resource_type);
+    let parameter_label = format!("parameter=\"{}\"", parameter);
+    Ok(text.lines().any(|line| {
+        line.starts_with("fhir_search_parameter_duration_seconds_count{")
+            && line.contains(&resource_label)
+            && line.contains(&parameter_label)
+    }))
+}
+
+#[tokio::test]
+async fn search_records_duration_per_parameter() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            register_search_parameter(
+                &app.state.db_pool,
+                "subject",
+                "Observation",
+                "reference",
+                "Observation.subject",
+                &["Patient"],
+            )
+            .await?;
+            register_search_parameter(
+                &app.state.db_pool,
+                "family",
+                "Patient",
+                "string",
+                "Patient.name.family",
+                &[],
+            )
+            .await?;
+
+            let (status, _, _) = app
+                .request(Method::GET, "/fhir/Patient?family=Timed", None)
+                .await?;
+            assert_status(status, StatusCode::OK, "search by family");
+            assert!(has_parameter_sample(app, "Patient", "family").await?);
+
+            // Chained parameters are attributed to their top-level code.
+            let (status, _, _) = app
+                .request(
+                    Method::GET,
+                    "/fhir/Observation?subject:Patient.family=Timed",
+                    None,
+                )
+                .await?;
+            assert_status(status, StatusCode::OK, "chained search");
+            assert!(has_parameter_sample(app, "Observation", "subject").await?);
+            assert!(!has_parameter_sample(app, "Observation", "subject:Patient.family").await?);
+
+            Ok(())
+        })
+    })
+    .await
+}
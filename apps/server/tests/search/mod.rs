@@ -1,6 +1,13 @@
+pub mod caching;
 pub mod chaining;
+pub mod explain;
+pub mod handling;
 pub mod includes;
+pub mod metrics;
 pub mod paging;
+pub mod parameter_status;
 pub mod parameters;
+pub mod post_search;
+pub mod validate_search;
 // pub mod modifiers;
 // pub mod result_params;
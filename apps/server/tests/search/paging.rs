@@ -131,3 +131,115 @@ async fn paging_links_include_prev_first_last() -> anyhow::Result<()> {
     })
     .await
 }
+
+#[tokio::test]
+async fn oversized_count_is_clamped_to_max_page_size() -> anyhow::Result<()> {
+    with_test_app_with_config(
+        |config| {
+            config.fhir.search.max_page_size = 2;
+        },
+        |app| {
+            Box::pin(async move {
+                create_patient(app, "Alpha").await?;
+                create_patient(app, "Beta").await?;
+                create_patient(app, "Gamma").await?;
+
+                let (status, _headers, body) = app
+                    .request(Method::GET, "/fhir/Patient?_count=100000", None)
+                    .await?;
+                assert_status(status, StatusCode::OK, "oversized _count");
+
+                let bundle: Value = serde_json::from_slice(&body)?;
+                let ids = extract_resource_ids_by_mode(&bundle, "Patient", "match")?;
+                assert_eq!(ids.len(), 2, "page size should be capped");
+
+                let next_url = link_url(&bundle, "next").context("next link")?;
+                assert_eq!(query_param(&next_url, "_count").as_deref(), Some("2"));
+
+                let warning = bundle["entry"]
+                    .as_array()
+                    .context("entries")?
+                    .iter()
+                    .filter(|e| e["search"]["mode"] == "outcome")
+                    .flat_map(|e| {
+                        e["resource"]["issue"]
+                            .as_array()
+                            .cloned()
+                            .unwrap_or_default()
+                    })
+                    .find(|issue| issue["severity"] == "warning" && issue["code"] == "too-costly");
+                assert!(warning.is_some(), "expected a clamping warning: {bundle}");
+
+                // Following the next link continues with the capped page size.
+                let (status, _headers, body) = app
+                    .request(Method::GET, &path_and_query(&next_url)?, None)
+                    .await?;
+                assert_status(status, StatusCode::OK, "next page");
+                let bundle: Value = serde_json::from_slice(&body)?;
+                let next_ids = extract_resource_ids_by_mode(&bundle, "Patient", "match")?;
+                assert_eq!(next_ids.len(), 1);
+                assert!(!ids.contains(&next_ids[0]));
+
+                Ok(())
+            })
+        },
+    )
+    .await
+}
+
+#[tokio::test]
+async fn oversized_count_is_clamped_to_max_count_below_page_size() -> anyhow::Result<()> {
+    with_test_app_with_config(
+        |config| {
+            config.fhir.search.max_page_size = 1000;
+            config.fhir.search.max_count = 2;
+        },
+        |app| {
+            Box::pin(async move {
+                create_patient(app, "Alpha").await?;
+                create_patient(app, "Beta").await?;
+                create_patient(app, "Gamma").await?;
+
+                let (status, _headers, body) = app
+                    .request(Method::GET, "/fhir/Patient?_count=100000", None)
+                    .await?;
+                assert_status(status, StatusCode::OK, "_count above max_count");
+
+                let bundle: Value = serde_json::from_slice(&body)?;
+                let ids = extract_resource_ids_by_mode(&bundle, "Patient", "match")?;
+                assert_eq!(ids.len(), 2, "page size should be capped at max_count");
+                let next_url = link_url(&bundle, "next").context("next link")?;
+                assert_eq!(query_param(&next_url, "_count").as_deref(), Some("2"));
+
+                Ok(())
+            })
+        },
+    )
+    .await
+}
+
+#[tokio::test]
+async fn count_zero_returns_total_without_entries() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            create_patient(app, "Alpha").await?;
+            create_patient(app, "Beta").await?;
+
+            // `_summary` after `_count=0` must not bring entries back.
+            for query in ["_count=0", "_count=0&_summary=true"] {
+                let (status, _headers, body) = app
+                    .request(Method::GET, &format!("/fhir/Patient?{}", query), None)
+                    .await?;
+                assert_status(status, StatusCode::OK, query);
+
+                let bundle: Value = serde_json::from_slice(&body)?;
+                assert_eq!(bundle["type"], "searchset", "{}", query);
+                assert_eq!(bundle["total"], 2, "{}", query);
+                assert!(bundle.get("entry").is_none(), "{}: {bundle}", query);
+            }
+
+            Ok(())
+        })
+    })
+    .await
+}
@@ -0,0 +1,155 @@
+//! Warnings for searches on retired or experimental search parameters
+//!
+//! The search still succeeds; the client is told through HTTP `Warning` headers and an
+//! `information` issue in an OperationOutcome entry (search.mode = "outcome").
+
+use crate::support::*;
+use axum::http::{HeaderMap, Method, StatusCode};
+use serde_json::{json, Value};
+
+/// Register `Patient?nickname` from a SearchParameter with the given status flags and create a
+/// patient it matches; returns the patient id.
+async fn setup_nickname_parameter(
+    app: &TestApp,
+    status: &str,
+    experimental: bool,
+) -> anyhow::Result<String> {
+    let search_param = json!({
+        "resourceType": "SearchParameter",
+        "url": "http://example.org/SearchParameter/Patient-nickname",
+        "status": status,
+        "experimental": experimental,
+        "code": "nickname",
+        "base": ["Patient"],
+        "type": "string",
+        "expression": "Patient.name.given"
+    });
+    let (status, _, _) = app
+        .request(
+            Method::POST,
+            "/fhir/SearchParameter",
+            Some(to_json_body(&search_param)?),
+        )
+        .await?;
+    assert_status(status, StatusCode::CREATED, "create SearchParameter");
+
+    let patient = PatientBuilder::new().family("Smith").given("Bob").build();
+    let (status, _, body) = app
+        .request(Method::POST, "/fhir/Patient", Some(to_json_body(&patient)?))
+        .await?;
+    assert_status(status, StatusCode::CREATED, "create patient");
+    Ok(serde_json::from_slice::<Value>(&body)?["id"]
+        .as_str()
+        .unwrap()
+        .to_string())
+}
+
+/// Assert that the response warns that `nickname` is `flag`, in both places.
+fn assert_nickname_warning(headers: &HeaderMap, bundle: &Value, flag: &str) -> anyhow::Result<()> {
+    let warning = headers
+        .get("warning")
+        .and_then(|v| v.to_str().ok())
+        .expect("Warning header");
+    assert!(warning.starts_with("299 "), "{warning}");
+    assert!(warning.contains("nickname"), "{warning}");
+    assert!(warning.contains(flag), "{warning}");
+
+    let outcome = get_bundle_entries(bundle)?
+        .iter()
+        .find(|e| e["search"]["mode"] == "outcome")
+        .expect("outcome entry for flagged parameter");
+    let issue = &outcome["resource"]["issue"][0];
+    assert_eq!(issue["severity"], "information");
+    assert_eq!(issue["code"], "informational");
+    let diagnostics = issue["diagnostics"].as_str().unwrap_or_default();
+    assert!(diagnostics.contains("nickname"), "{diagnostics}");
+    assert!(diagnostics.contains(flag), "{diagnostics}");
+    Ok(())
+}
+
+#[tokio::test]
+async fn experimental_parameter_search_carries_warning() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            let patient_id = setup_nickname_parameter(app, "active", true).await?;
+
+            let (status, headers, body) = app
+                .request(Method::GET, "/fhir/Patient?nickname=Bob", None)
+                .await?;
+            assert_status(status, StatusCode::OK, "search on experimental parameter");
+            let bundle: Value = serde_json::from_slice(&body)?;
+            assert_bundle_contains_id(&bundle, "Patient", &patient_id)?;
+            assert_nickname_warning(&headers, &bundle, "experimental")?;
+
+            // Parameters from regular definitions do not warn.
+            let (status, headers, _) = app
+                .request(
+                    Method::GET,
+                    &format!("/fhir/Patient?_id={}", patient_id),
+                    None,
+                )
+                .await?;
+            assert_status(status, StatusCode::OK, "search on _id");
+            assert!(headers.get("warning").is_none());
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn retired_parameter_search_carries_warning_when_searchable() -> anyhow::Result<()> {
+    with_test_app_with_config(
+        |config| {
+            config.fhir.search.search_parameter_active_statuses =
+                vec!["active".to_string(), "retired".to_string()];
+        },
+        |app| {
+            Box::pin(async move {
+                let patient_id = setup_nickname_parameter(app, "retired", false).await?;
+
+                let (status, headers, body) = app
+                    .request(Method::GET, "/fhir/Patient?nickname=Bob", None)
+                    .await?;
+                assert_status(status, StatusCode::OK, "search on retired parameter");
+                let bundle: Value = serde_json::from_slice(&body)?;
+                assert_bundle_contains_id(&bundle, "Patient", &patient_id)?;
+                assert_nickname_warning(&headers, &bundle, "retired")?;
+
+                Ok(())
+            })
+        },
+    )
+    .await
+}
+
+#[tokio::test]
+async fn retired_parameter_is_not_searchable_by_default() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            setup_nickname_parameter(app, "retired", false).await?;
+
+            let (status, _, body) = app
+                .request(Method::GET, "/fhir/Patient?nickname=Bob", None)
+                .await?;
+            assert_status(status, StatusCode::OK, "search on retired parameter");
+            let bundle: Value = serde_json::from_slice(&body)?;
+            let outcome = get_bundle_entries(&bundle)?
+                .iter()
+                .find(|e| e["search"]["mode"] == "outcome")
+                .cloned()
+                .expect("outcome entry for unknown parameter");
+            // Reported as unsupported, not as a searchable parameter with a warning.
+            let issue = &outcome["resource"]["issue"][0];
+            assert_eq!(issue["code"], "not-supported");
+            assert!(issue["diagnostics"]
+                .as_str()
+                .unwrap_or_default()
+                .contains("nickname"));
+
+            Ok(())
+        })
+    })
+    .await
+}
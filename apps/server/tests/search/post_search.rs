@@ -0,0 +1,124 @@
+//! POST-based search (`POST [base]/[type]/_search`)
+//!
+//! FHIR Spec: 3.2.1.2 - Parameters may be sent as an `application/x-www-form-urlencoded`
+//! body; query-string parameters on the same request are combined with them.
+
+use crate::support::*;
+use axum::body::Bytes;
+use axum::http::{Method, StatusCode};
+use serde_json::{json, Value};
+
+const FORM: (&str, &str) = ("content-type", "application/x-www-form-urlencoded");
+
+async fn setup(app: &TestApp) -> anyhow::Result<()> {
+    register_search_parameter(
+        &app.state.db_pool,
+        "name",
+        "Patient",
+        "string",
+        "Patient.name",
+        &[],
+    )
+    .await?;
+    register_search_parameter(
+        &app.state.db_pool,
+        "gender",
+        "Patient",
+        "token",
+        "Patient.gender",
+        &[],
+    )
+    .await?;
+    Ok(())
+}
+
+async fn create_patient(app: &TestApp, family: &str, gender: &str) -> anyhow::Result<String> {
+    let patient = json!({
+        "resourceType": "Patient",
+        "name": [{"family": family}],
+        "gender": gender
+    });
+    let (status, _headers, body) = app
+        .request(Method::POST, "/fhir/Patient", Some(to_json_body(&patient)?))
+        .await?;
+    assert_status(status, StatusCode::CREATED, "create Patient");
+    let created: Value = serde_json::from_slice(&body)?;
+    Ok(created["id"].as_str().unwrap().to_string())
+}
+
+async fn post_search(app: &TestApp, path: &str, form: &'static str) -> anyhow::Result<Value> {
+    let (status, _headers, body) = app
+        .request_with_extra_headers(Method::POST, path, Some(Bytes::from(form)), &[FORM])
+        .await?;
+    assert_status(status, StatusCode::OK, path);
+    Ok(serde_json::from_slice(&body)?)
+}
+
+#[tokio::test]
+async fn post_search_reads_parameters_from_form_body() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            setup(app).await?;
+            let smith = create_patient(app, "Smith", "male").await?;
+            create_patient(app, "Jones", "male").await?;
+
+            let bundle = post_search(app, "/fhir/Patient/_search", "name=smith").await?;
+            assert_eq!(bundle["type"], "searchset");
+            let ids = extract_resource_ids_by_mode(&bundle, "Patient", "match")?;
+            assert_eq!(ids, vec![smith]);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn post_search_combines_query_and_body_parameters() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            setup(app).await?;
+            let smith_male = create_patient(app, "Smith", "male").await?;
+            create_patient(app, "Smith", "female").await?;
+            create_patient(app, "Jones", "male").await?;
+
+            // Different parameters in the URL and the body.
+            let bundle =
+                post_search(app, "/fhir/Patient/_search?gender=male", "name=smith").await?;
+            let ids = extract_resource_ids_by_mode(&bundle, "Patient", "match")?;
+            assert_eq!(ids, vec![smith_male]);
+
+            // The same parameter in both places is ANDed, not ORed.
+            let bundle = post_search(app, "/fhir/Patient/_search?name=jones", "name=smith").await?;
+            let ids = extract_resource_ids_by_mode(&bundle, "Patient", "match")?;
+            assert!(ids.is_empty(), "expected no match, got {:?}", ids);
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn post_search_rejects_non_form_body() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            let (status, _headers, _body) = app
+                .request_with_extra_headers(
+                    Method::POST,
+                    "/fhir/Patient/_search",
+                    Some(Bytes::from_static(b"{\"name\":\"smith\"}")),
+                    &[("content-type", "application/json")],
+                )
+                .await?;
+            assert_status(
+                status,
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                "POST search with JSON body",
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
@@ -0,0 +1,138 @@
+//! $validate-search dry-run tests
+//!
+//! Resolves search parameters without executing the search and reports each parameter's status.
+
+use crate::support::*;
+use axum::http::{Method, StatusCode};
+use serde_json::Value;
+
+fn issue_for<'a>(outcome: &'a Value, name: &str) -> &'a Value {
+    outcome["issue"]
+        .as_array()
+        .and_then(|issues| issues.iter().find(|i| i["expression"][0] == name))
+        .unwrap_or_else(|| panic!("no issue reported for {name}: {outcome}"))
+}
+
+#[tokio::test]
+async fn validate_search_reports_mixed_parameters() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            for (code, expression) in [("family", "Patient.name.family"), ("name", "Patient.name")] {
+                register_search_parameter(
+                    &app.state.db_pool,
+                    code,
+                    "Patient",
+                    "string",
+                    expression,
+                    &[],
+                )
+                .await?;
+            }
+            app.state.search_engine.invalidate_param_cache();
+
+            let (status, _, body) = app
+                .request(
+                    Method::GET,
+                    "/fhir/Patient/$validate-search?family=Smith&_count=10&bogus-param=xyz&name:fuzzy=Jo",
+                    None,
+                )
+                .await?;
+            assert_status(status, StatusCode::OK, "validate-search");
+
+            let outcome: Value = serde_json::from_slice(&body)?;
+            assert_eq!(outcome["resourceType"], "OperationOutcome");
+            assert_eq!(outcome["issue"].as_array().map(|a| a.len()), Some(4));
+
+            assert_eq!(issue_for(&outcome, "family")["severity"], "information");
+            assert_eq!(issue_for(&outcome, "_count")["severity"], "information");
+
+            let bogus = issue_for(&outcome, "bogus-param");
+            assert_eq!(bogus["severity"], "error");
+            assert_eq!(bogus["code"], "not-supported");
+            let diagnostics = bogus["diagnostics"].as_str().unwrap_or_default();
+            assert!(diagnostics.contains("Unknown search parameter"), "{diagnostics}");
+
+            let modifier = issue_for(&outcome, "name:fuzzy");
+            assert_eq!(modifier["severity"], "error");
+            let diagnostics = modifier["diagnostics"].as_str().unwrap_or_default();
+            assert!(diagnostics.contains("fuzzy"), "{diagnostics}");
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn validate_search_reports_the_cardinality_that_was_violated() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            register_search_parameter(
+                &app.state.db_pool,
+                "gender",
+                "Patient",
+                "token",
+                "Patient.gender",
+                &[],
+            )
+            .await?;
+            register_search_parameter(
+                &app.state.db_pool,
+                "birthdate",
+                "Patient",
+                "date",
+                "Patient.birthDate",
+                &[],
+            )
+            .await?;
+            sqlx::query(
+                "UPDATE search_parameters SET multiple_or = FALSE WHERE resource_type = 'Patient' AND code = 'gender'",
+            )
+            .execute(&app.state.db_pool)
+            .await?;
+            sqlx::query(
+                "UPDATE search_parameters SET multiple_and = FALSE WHERE resource_type = 'Patient' AND code = 'birthdate'",
+            )
+            .execute(&app.state.db_pool)
+            .await?;
+            app.state.search_engine.invalidate_param_cache();
+
+            // `gender` may repeat but not take comma-separated values; `birthdate` the reverse.
+            let (status, _, body) = app
+                .request(
+                    Method::GET,
+                    "/fhir/Patient/$validate-search?gender=male,female&gender=other&birthdate=ge2000&birthdate=le2010",
+                    None,
+                )
+                .await?;
+            assert_status(status, StatusCode::OK, "validate-search");
+
+            let outcome: Value = serde_json::from_slice(&body)?;
+            let issues = outcome["issue"].as_array().cloned().unwrap_or_default();
+            let diagnostics = |name: &str| -> Vec<String> {
+                issues
+                    .iter()
+                    .filter(|i| i["expression"][0] == name)
+                    .filter_map(|i| i["diagnostics"].as_str().map(str::to_string))
+                    .collect()
+            };
+
+            let gender = diagnostics("gender");
+            assert!(
+                gender.iter().any(|d| d.contains("comma-separated values (OR)")),
+                "{outcome}"
+            );
+            assert!(!gender.iter().any(|d| d.contains("(AND)")), "{outcome}");
+
+            let birthdate = diagnostics("birthdate");
+            assert_eq!(birthdate.len(), 2, "{outcome}");
+            assert!(
+                birthdate.iter().all(|d| d.contains("cannot be repeated (AND)")),
+                "{outcome}"
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
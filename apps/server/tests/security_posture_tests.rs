@@ -56,3 +56,69 @@ async fn cors_is_not_permissive_by_default() -> anyhow::Result<()> {
     })
     .await
 }
+
+#[tokio::test]
+async fn spoofed_forwarded_host_falls_back_to_trusted_base_url() -> anyhow::Result<()> {
+    with_test_app_with_config(
+        |config| {
+            config.server.base_url = Some("https://fhir.example.org/fhir".to_string());
+            config.server.trusted_base_urls = vec!["https://tenant-a.example.org/fhir".to_string()];
+        },
+        |app| {
+            Box::pin(async move {
+                let patient = minimal_patient();
+                let (status, _headers, _body) = app
+                    .request(Method::POST, "/fhir/Patient", Some(to_json_body(&patient)?))
+                    .await?;
+                assert_status(status, StatusCode::CREATED, "create patient");
+
+                let search_urls = |host: &'static str| async move {
+                    let (status, _headers, body) = app
+                        .request_with_extra_headers(
+                            Method::GET,
+                            "/fhir/Patient",
+                            None,
+                            &[("x-forwarded-proto", "https"), ("x-forwarded-host", host)],
+                        )
+                        .await?;
+                    assert_status(status, StatusCode::OK, "search patients");
+                    let bundle: serde_json::Value = serde_json::from_slice(&body)?;
+                    let mut urls: Vec<String> = get_bundle_entries(&bundle)?
+                        .iter()
+                        .filter_map(|e| e["fullUrl"].as_str().map(str::to_string))
+                        .collect();
+                    urls.extend(
+                        bundle["link"]
+                            .as_array()
+                            .into_iter()
+                            .flatten()
+                            .filter_map(|l| l["url"].as_str().map(str::to_string)),
+                    );
+                    assert!(!urls.is_empty(), "expected fullUrl and link values");
+                    anyhow::Ok(urls)
+                };
+
+                // A spoofed host must not leak into links; the canonical base is used instead.
+                for url in search_urls("attacker.example.com").await? {
+                    assert!(
+                        url.starts_with("https://fhir.example.org/fhir/"),
+                        "expected canonical base, got {}",
+                        url
+                    );
+                }
+
+                // A trusted tenant host is honored.
+                for url in search_urls("tenant-a.example.org").await? {
+                    assert!(
+                        url.starts_with("https://tenant-a.example.org/fhir/"),
+                        "expected tenant base, got {}",
+                        url
+                    );
+                }
+
+                Ok(())
+            })
+        },
+    )
+    .await
+}
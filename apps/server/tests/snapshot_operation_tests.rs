@@ -0,0 +1,134 @@
+#![allow(unused)]
+#[allow(unused)]
+mod support;
+
+use axum::http::{Method, StatusCode};
+use serde_json::{json, Value};
+use support::*;
+
+/// Register the $snapshot OperationDefinition so the operation router accepts it.
+async fn setup_snapshot_operation(app: &TestApp) -> anyhow::Result<()> {
+    register_operation(
+        app,
+        OperationFixture {
+            code: "snapshot",
+            resource: &["StructureDefinition"],
+            instance: true,
+            ..Default::default()
+        },
+    )
+    .await
+}
+
+fn parse_json(body: &[u8]) -> anyhow::Result<Value> {
+    Ok(serde_json::from_slice(body)?)
+}
+
+/// Differential-only Patient profile requiring at least one name.
+fn named_patient_profile(base_definition: &str) -> Value {
+    json!({
+        "resourceType": "StructureDefinition",
+        "url": "http://example.org/fhir/StructureDefinition/named-patient",
+        "name": "NamedPatient",
+        "status": "active",
+        "kind": "resource",
+        "abstract": false,
+        "type": "Patient",
+        "baseDefinition": base_definition,
+        "derivation": "constraint",
+        "differential": {
+            "element": [
+                {"id": "Patient", "path": "Patient"},
+                {"id": "Patient.name", "path": "Patient.name", "min": 1}
+            ]
+        }
+    })
+}
+
+async fn create_profile(app: &TestApp, profile: &Value) -> anyhow::Result<String> {
+    let (status, _headers, body) = app
+        .request(
+            Method::POST,
+            "/fhir/StructureDefinition",
+            Some(to_json_body(profile)?),
+        )
+        .await?;
+    assert_status(status, StatusCode::CREATED, "create StructureDefinition");
+    Ok(parse_json(&body)?["id"].as_str().unwrap().to_string())
+}
+
+#[tokio::test]
+async fn snapshot_generates_snapshot_from_differential() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            setup_snapshot_operation(app).await?;
+            let id = create_profile(
+                app,
+                &named_patient_profile("http://hl7.org/fhir/StructureDefinition/Patient"),
+            )
+            .await?;
+
+            let (status, _headers, body) = app
+                .request(
+                    Method::POST,
+                    &format!("/fhir/StructureDefinition/{}/$snapshot", id),
+                    None,
+                )
+                .await?;
+            assert_status(status, StatusCode::OK, "$snapshot");
+
+            let profile = parse_json(&body)?;
+            assert_eq!(profile["resourceType"], "StructureDefinition");
+            assert_eq!(profile["id"], id.as_str());
+            let elements = profile["snapshot"]["element"]
+                .as_array()
+                .expect("snapshot.element");
+            assert!(elements.len() > 2, "snapshot includes base elements");
+            let name = elements
+                .iter()
+                .find(|e| e["path"] == "Patient.name")
+                .expect("Patient.name in snapshot");
+            assert_eq!(name["min"], 1);
+            assert_eq!(name["max"], "*");
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn snapshot_reports_unresolvable_base() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            setup_snapshot_operation(app).await?;
+            let id = create_profile(
+                app,
+                &named_patient_profile("http://example.org/fhir/StructureDefinition/missing"),
+            )
+            .await?;
+
+            let (status, _headers, body) = app
+                .request(
+                    Method::POST,
+                    &format!("/fhir/StructureDefinition/{}/$snapshot", id),
+                    None,
+                )
+                .await?;
+            assert_status(status, StatusCode::UNPROCESSABLE_ENTITY, "$snapshot");
+
+            let outcome = parse_json(&body)?;
+            assert_eq!(outcome["resourceType"], "OperationOutcome");
+            let diagnostics = outcome["issue"][0]["diagnostics"]
+                .as_str()
+                .unwrap_or_default();
+            assert!(
+                diagnostics.contains("http://example.org/fhir/StructureDefinition/missing"),
+                "{diagnostics}"
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
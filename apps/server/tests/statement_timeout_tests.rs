@@ -0,0 +1,70 @@
+#![allow(unused)]
+#[allow(unused)]
+mod support;
+
+use axum::http::{header, StatusCode};
+use axum::response::IntoResponse;
+use serde_json::Value;
+use support::*;
+
+#[tokio::test]
+async fn statement_timeout_maps_to_503_timeout_outcome() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            // `SET LOCAL` keeps the tiny timeout scoped to this transaction, so the pooled
+            // connection is unaffected once it is rolled back.
+            let mut tx = app.state.db_pool.begin().await?;
+            sqlx::query("SET LOCAL statement_timeout = '10ms'")
+                .execute(&mut *tx)
+                .await?;
+            let err = sqlx::query("SELECT pg_sleep(1)")
+                .execute(&mut *tx)
+                .await
+                .expect_err("pg_sleep should exceed the statement timeout");
+            drop(tx);
+
+            let err = ferrum::Error::Database(err);
+            assert!(err.is_statement_timeout(), "unexpected error: {}", err);
+
+            let response = err.into_response();
+            assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+            assert_eq!(
+                response
+                    .headers()
+                    .get(header::RETRY_AFTER)
+                    .and_then(|v| v.to_str().ok()),
+                Some("5")
+            );
+
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+            let outcome: Value = serde_json::from_slice(&body)?;
+            assert_eq!(outcome["resourceType"], "OperationOutcome");
+            assert_eq!(outcome["issue"][0]["code"], "timeout");
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn other_database_errors_stay_internal() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            let err = sqlx::query("SELECT * FROM table_that_does_not_exist")
+                .execute(&app.state.db_pool)
+                .await
+                .expect_err("query should fail");
+
+            let err = ferrum::Error::Database(err);
+            assert!(!err.is_statement_timeout());
+            assert_eq!(
+                err.into_response().status(),
+                StatusCode::INTERNAL_SERVER_ERROR
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
@@ -1,6 +1,7 @@
 pub mod assertions;
 pub mod builders;
 pub mod fixtures;
+pub mod operations;
 pub mod search_helpers;
 pub mod shared;
 
@@ -25,6 +26,7 @@ use uuid::Uuid;
 pub use assertions::*;
 pub use builders::*;
 pub use fixtures::*;
+pub use operations::*;
 pub use search_helpers::*;
 
 pub struct TestApp {
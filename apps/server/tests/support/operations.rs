@@ -0,0 +1,48 @@
+use super::{assert_status, to_json_body, TestApp};
+use axum::http::{Method, StatusCode};
+use serde_json::json;
+
+/// OperationDefinition registered by [`register_operation`].
+///
+/// Only `code` and the invocation levels matter to the operation router; everything else is
+/// derived from the code.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OperationFixture<'a> {
+    pub code: &'a str,
+    /// Resource types the operation applies to (empty = any).
+    pub resource: &'a [&'a str],
+    pub system: bool,
+    pub type_level: bool,
+    pub instance: bool,
+    pub affects_state: bool,
+}
+
+/// Store the OperationDefinition for `op` and reload the registry so the router accepts it.
+pub async fn register_operation(app: &TestApp, op: OperationFixture<'_>) -> anyhow::Result<()> {
+    let mut op_def = json!({
+        "resourceType": "OperationDefinition",
+        "id": op.code,
+        "url": format!("http://ferrum.fhir.server/OperationDefinition/{}", op.code),
+        "status": "active",
+        "kind": "operation",
+        "code": op.code,
+        "system": op.system,
+        "type": op.type_level,
+        "instance": op.instance,
+        "affectsState": op.affects_state
+    });
+    if !op.resource.is_empty() {
+        op_def["resource"] = json!(op.resource);
+    }
+    let (status, _headers, _body) = app
+        .request(
+            Method::POST,
+            "/fhir/OperationDefinition",
+            Some(to_json_body(&op_def)?),
+        )
+        .await?;
+    assert_status(status, StatusCode::CREATED, "create OperationDefinition");
+
+    app.state.operation_registry.load_definitions().await?;
+    Ok(())
+}
@@ -453,3 +453,41 @@ pub async fn register_search_parameter(
 
     Ok(())
 }
+
+/// Helper to register a reference search parameter with declared target types for testing
+pub async fn register_reference_search_parameter(
+    pool: &PgPool,
+    code: &str,
+    resource_type: &str,
+    expression: &str,
+    targets: &[&str],
+) -> anyhow::Result<()> {
+    let targets_array: Vec<String> = targets.iter().map(|s| s.to_string()).collect();
+
+    sqlx::query(
+        r#"
+        INSERT INTO search_parameters (
+            code, resource_type, type, expression, description,
+            targets, multiple_or, multiple_and
+        )
+        VALUES ($1, $2, 'reference', $3, $4, $5, TRUE, TRUE)
+        ON CONFLICT (code, resource_type) DO UPDATE
+        SET type = EXCLUDED.type,
+            expression = EXCLUDED.expression,
+            description = EXCLUDED.description,
+            targets = EXCLUDED.targets,
+            multiple_or = EXCLUDED.multiple_or,
+            multiple_and = EXCLUDED.multiple_and,
+            active = TRUE
+        "#,
+    )
+    .bind(code)
+    .bind(resource_type)
+    .bind(expression)
+    .bind(format!("Test search parameter: {}", code))
+    .bind(&targets_array)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
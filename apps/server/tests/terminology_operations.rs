@@ -286,3 +286,424 @@ async fn terminology_operations_smoke_test() -> anyhow::Result<()> {
     })
     .await
 }
+
+/// `match` parameters of a $translate result as (relationship, system, code) triples.
+fn translate_matches(result: &Value) -> Vec<(String, String, String)> {
+    result["parameter"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|p| p["name"] == "match")
+        .map(|m| {
+            let parts = m["part"].as_array().unwrap();
+            let relationship = parts
+                .iter()
+                .find(|p| p["name"] == "equivalence" || p["name"] == "relationship")
+                .and_then(|p| p["valueCode"].as_str())
+                .unwrap_or_default()
+                .to_string();
+            let concept = parts
+                .iter()
+                .find(|p| p["name"] == "concept")
+                .map(|p| &p["valueCoding"]);
+            let field = |name: &str| {
+                concept
+                    .and_then(|c| c[name].as_str())
+                    .unwrap_or_default()
+                    .to_string()
+            };
+            (relationship, field("system"), field("code"))
+        })
+        .collect()
+}
+
+fn translate_result(result: &Value) -> Option<bool> {
+    result["parameter"]
+        .as_array()?
+        .iter()
+        .find(|p| p["name"] == "result")?
+        .get("valueBoolean")?
+        .as_bool()
+}
+
+#[tokio::test]
+async fn translate_uses_stored_concept_map() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            create_operation_definition(
+                app,
+                json!({
+                    "resourceType": "OperationDefinition",
+                    "status": "active",
+                    "kind": "operation",
+                    "code": "translate",
+                    "resource": ["ConceptMap"],
+                    "system": false,
+                    "type": true,
+                    "instance": true,
+                    "affectsState": false
+                }),
+            )
+            .await?;
+            app.state.operation_registry.load_definitions().await?;
+
+            // Two versions of the same map; the unmapped fallback only exists in version 2.
+            for (version, unmapped) in [
+                ("1", None),
+                (
+                    "2",
+                    Some(json!({ "mode": "fixed", "code": "unknown", "display": "Unknown" })),
+                ),
+            ] {
+                let mut group = json!({
+                    "source": "http://example.org/CodeSystem/local",
+                    "target": "http://loinc.org",
+                    "element": [
+                        {
+                            "code": "hb",
+                            "target": [{
+                                "code": "718-7",
+                                "display": "Hemoglobin",
+                                "equivalence": "equivalent"
+                            }]
+                        },
+                        {
+                            "code": "misc",
+                            "target": [{ "equivalence": "unmatched" }]
+                        }
+                    ]
+                });
+                if let Some(unmapped) = unmapped {
+                    group["unmapped"] = unmapped;
+                }
+                let cm = json!({
+                    "resourceType": "ConceptMap",
+                    "url": "http://example.org/ConceptMap/local-to-loinc",
+                    "version": version,
+                    "status": "active",
+                    "group": [group]
+                });
+                let (status, _headers, _body) = app
+                    .request(Method::POST, "/fhir/ConceptMap", Some(to_json_body(&cm)?))
+                    .await?;
+                assert_status(status, StatusCode::CREATED, "create ConceptMap");
+            }
+
+            let translate = |code: &str, version: &str| {
+                format!(
+                    "/fhir/ConceptMap/$translate?url={}&conceptMapVersion={}&system={}&code={}",
+                    "http://example.org/ConceptMap/local-to-loinc",
+                    version,
+                    "http://example.org/CodeSystem/local",
+                    code
+                )
+            };
+
+            // Mapped source code
+            let (status, _headers, body) = app
+                .request(Method::GET, &translate("hb", "1"), None)
+                .await?;
+            assert_status(status, StatusCode::OK, "$translate hb");
+            let result: Value = serde_json::from_slice(&body)?;
+            assert_eq!(translate_result(&result), Some(true));
+            assert_eq!(
+                translate_matches(&result),
+                vec![(
+                    "equivalent".to_string(),
+                    "http://loinc.org".to_string(),
+                    "718-7".to_string()
+                )]
+            );
+
+            // Explicitly unmatched code: reported, but not a successful translation
+            let (status, _headers, body) = app
+                .request(Method::GET, &translate("misc", "2"), None)
+                .await?;
+            assert_status(status, StatusCode::OK, "$translate misc");
+            let result: Value = serde_json::from_slice(&body)?;
+            assert_eq!(translate_result(&result), Some(false));
+            assert_eq!(
+                translate_matches(&result),
+                vec![("unmatched".to_string(), String::new(), String::new())]
+            );
+
+            // Unlisted code: no match in version 1, the fixed fallback in version 2
+            let (status, _headers, body) = app
+                .request(Method::GET, &translate("other", "1"), None)
+                .await?;
+            assert_status(status, StatusCode::OK, "$translate other v1");
+            let result: Value = serde_json::from_slice(&body)?;
+            assert_eq!(translate_result(&result), Some(false));
+            assert!(translate_matches(&result).is_empty());
+
+            let (status, _headers, body) = app
+                .request(Method::GET, &translate("other", "2"), None)
+                .await?;
+            assert_status(status, StatusCode::OK, "$translate other v2");
+            let result: Value = serde_json::from_slice(&body)?;
+            assert_eq!(translate_result(&result), Some(true));
+            assert_eq!(
+                translate_matches(&result),
+                vec![(
+                    "inexact".to_string(),
+                    "http://loinc.org".to_string(),
+                    "unknown".to_string()
+                )]
+            );
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn lookup_returns_concept_details() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            create_operation_definition(
+                app,
+                json!({
+                    "resourceType": "OperationDefinition",
+                    "status": "active",
+                    "kind": "operation",
+                    "code": "lookup",
+                    "resource": ["CodeSystem"],
+                    "system": false,
+                    "type": true,
+                    "instance": true,
+                    "affectsState": false
+                }),
+            )
+            .await?;
+            app.state.operation_registry.load_definitions().await?;
+
+            let cs = json!({
+                "resourceType": "CodeSystem",
+                "url": "http://example.org/CodeSystem/lookup",
+                "version": "2.0",
+                "name": "LookupTest",
+                "status": "active",
+                "content": "complete",
+                "property": [{ "code": "status", "type": "code" }],
+                "concept": [{
+                    "code": "parent",
+                    "display": "Parent",
+                    "concept": [{
+                        "code": "child",
+                        "display": "Child concept",
+                        "definition": "A concept nested below parent",
+                        "designation": [{ "language": "de", "value": "Kindkonzept" }],
+                        "property": [{ "code": "status", "valueCode": "retired" }]
+                    }]
+                }]
+            });
+            let (status, _headers, _body) = app
+                .request(Method::POST, "/fhir/CodeSystem", Some(to_json_body(&cs)?))
+                .await?;
+            assert_status(status, StatusCode::CREATED, "create CodeSystem");
+
+            let (status, _headers, body) = app
+                .request(
+                    Method::GET,
+                    "/fhir/CodeSystem/$lookup?system=http://example.org/CodeSystem/lookup&code=child",
+                    None,
+                )
+                .await?;
+            assert_status(status, StatusCode::OK, "$lookup");
+            let result: Value = serde_json::from_slice(&body)?;
+            assert_eq!(result["resourceType"], "Parameters");
+            let params = result["parameter"].as_array().unwrap();
+            let value_string = |name: &str| {
+                params
+                    .iter()
+                    .find(|p| p["name"] == name)
+                    .and_then(|p| p["valueString"].as_str())
+                    .map(|s| s.to_string())
+            };
+            assert_eq!(value_string("name").as_deref(), Some("LookupTest"));
+            assert_eq!(value_string("version").as_deref(), Some("2.0"));
+            assert_eq!(value_string("display").as_deref(), Some("Child concept"));
+            assert_eq!(
+                value_string("definition").as_deref(),
+                Some("A concept nested below parent")
+            );
+
+            let property = params
+                .iter()
+                .find(|p| p["name"] == "property")
+                .expect("property parameter");
+            let parts = property["part"].as_array().unwrap();
+            assert!(parts
+                .iter()
+                .any(|p| p["name"] == "code" && p["valueCode"] == "status"));
+            assert!(parts
+                .iter()
+                .any(|p| p["name"] == "value" && p["valueCode"] == "retired"));
+            assert!(params.iter().any(|p| p["name"] == "designation"));
+
+            // Unknown code
+            let (status, _headers, body) = app
+                .request(
+                    Method::GET,
+                    "/fhir/CodeSystem/$lookup?system=http://example.org/CodeSystem/lookup&code=missing",
+                    None,
+                )
+                .await?;
+            assert_status(status, StatusCode::NOT_FOUND, "$lookup unknown code");
+            let outcome: Value = serde_json::from_slice(&body)?;
+            assert_eq!(outcome["resourceType"], "OperationOutcome");
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+/// `result` and `display` of a $validate-code response.
+fn validate_code_outcome(result: &Value) -> (Option<bool>, Option<String>) {
+    let params = result["parameter"].as_array().unwrap();
+    let find = |name: &str| params.iter().find(|p| p["name"] == name);
+    (
+        find("result").and_then(|p| p["valueBoolean"].as_bool()),
+        find("display").and_then(|p| p["valueString"].as_str().map(|s| s.to_string())),
+    )
+}
+
+#[tokio::test]
+async fn validate_code_checks_valueset_membership() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            create_operation_definition(
+                app,
+                json!({
+                    "resourceType": "OperationDefinition",
+                    "status": "active",
+                    "kind": "operation",
+                    "code": "validate-code",
+                    "resource": ["ValueSet"],
+                    "system": false,
+                    "type": true,
+                    "instance": true,
+                    "affectsState": false
+                }),
+            )
+            .await?;
+            app.state.operation_registry.load_definitions().await?;
+
+            let cs = json!({
+                "resourceType": "CodeSystem",
+                "url": "http://example.org/CodeSystem/letters",
+                "status": "active",
+                "content": "complete",
+                "concept": [
+                    { "code": "a", "display": "Alpha" },
+                    { "code": "b", "display": "Bravo" }
+                ]
+            });
+            let (status, _headers, _body) = app
+                .request(Method::POST, "/fhir/CodeSystem", Some(to_json_body(&cs)?))
+                .await?;
+            assert_status(status, StatusCode::CREATED, "create CodeSystem");
+
+            // Enumerated include without displays
+            let composed = json!({
+                "resourceType": "ValueSet",
+                "url": "http://example.org/ValueSet/only-a",
+                "status": "active",
+                "compose": {
+                    "include": [{
+                        "system": "http://example.org/CodeSystem/letters",
+                        "concept": [{ "code": "a" }]
+                    }]
+                }
+            });
+            let (status, _headers, body) = app
+                .request(
+                    Method::POST,
+                    "/fhir/ValueSet",
+                    Some(to_json_body(&composed)?),
+                )
+                .await?;
+            assert_status(status, StatusCode::CREATED, "create ValueSet");
+            let composed_id = serde_json::from_slice::<Value>(&body)?["id"]
+                .as_str()
+                .unwrap()
+                .to_string();
+
+            // Pre-expanded value set without a compose
+            let expanded = json!({
+                "resourceType": "ValueSet",
+                "url": "http://example.org/ValueSet/only-b",
+                "status": "active",
+                "expansion": {
+                    "timestamp": "2024-01-01T00:00:00Z",
+                    "total": 1,
+                    "contains": [{
+                        "system": "http://example.org/CodeSystem/letters",
+                        "code": "b",
+                        "display": "Bravo"
+                    }]
+                }
+            });
+            let (status, _headers, _body) = app
+                .request(
+                    Method::POST,
+                    "/fhir/ValueSet",
+                    Some(to_json_body(&expanded)?),
+                )
+                .await?;
+            assert_status(status, StatusCode::CREATED, "create expanded ValueSet");
+
+            let system = "http://example.org/CodeSystem/letters";
+            let cases = [
+                (
+                    format!(
+                        "/fhir/ValueSet/{}/$validate-code?system={}&code=a",
+                        composed_id, system
+                    ),
+                    Some(true),
+                    Some("Alpha".to_string()),
+                ),
+                (
+                    format!(
+                        "/fhir/ValueSet/{}/$validate-code?system={}&code=b",
+                        composed_id, system
+                    ),
+                    Some(false),
+                    None,
+                ),
+                (
+                    format!(
+                        "/fhir/ValueSet/$validate-code?url={}&system={}&code=b",
+                        "http://example.org/ValueSet/only-b", system
+                    ),
+                    Some(true),
+                    Some("Bravo".to_string()),
+                ),
+                (
+                    format!(
+                        "/fhir/ValueSet/$validate-code?url={}&system={}&code=a",
+                        "http://example.org/ValueSet/only-b", system
+                    ),
+                    Some(false),
+                    None,
+                ),
+            ];
+            for (path, expected_result, expected_display) in cases {
+                let (status, _headers, body) = app.request(Method::GET, &path, None).await?;
+                assert_status(status, StatusCode::OK, &path);
+                let result: Value = serde_json::from_slice(&body)?;
+                assert_eq!(result["resourceType"], "Parameters");
+                assert_eq!(
+                    validate_code_outcome(&result),
+                    (expected_result, expected_display),
+                    "{path}"
+                );
+            }
+
+            Ok(())
+        })
+    })
+    .await
+}
@@ -0,0 +1,140 @@
+#![allow(unused)]
+#[allow(unused)]
+mod support;
+
+use axum::http::{Method, StatusCode};
+use serde_json::{json, Value};
+use support::*;
+
+async fn count_current(app: &TestApp, resource_type: &str) -> anyhow::Result<i64> {
+    let count = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM resources WHERE resource_type = $1 AND is_current AND NOT deleted",
+    )
+    .bind(resource_type)
+    .fetch_one(&app.state.db_pool)
+    .await?;
+    Ok(count)
+}
+
+/// Three entries where the second (a PUT with a stale `ifMatch`) fails. The POSTs are
+/// processed before the PUT, so both have already been written when the failure occurs.
+fn bundle_with_failing_second_entry(bundle_type: &str, patient_id: &str) -> Value {
+    json!({
+        "resourceType": "Bundle",
+        "type": bundle_type,
+        "entry": [
+            {
+                "fullUrl": "urn:uuid:6f1c3b9e-0d7a-4a43-9a57-1b3a8b2f0c01",
+                "resource": {"resourceType": "Patient", "name": [{"family": "First"}]},
+                "request": {"method": "POST", "url": "Patient"}
+            },
+            {
+                "resource": {
+                    "resourceType": "Patient",
+                    "id": patient_id,
+                    "name": [{"family": "Changed"}]
+                },
+                "request": {
+                    "method": "PUT",
+                    "url": format!("Patient/{}", patient_id),
+                    "ifMatch": "W/\"7\""
+                }
+            },
+            {
+                "fullUrl": "urn:uuid:6f1c3b9e-0d7a-4a43-9a57-1b3a8b2f0c03",
+                "resource": {
+                    "resourceType": "Observation",
+                    "status": "final",
+                    "code": {"text": "heart rate"}
+                },
+                "request": {"method": "POST", "url": "Observation"}
+            }
+        ]
+    })
+}
+
+async fn create_existing_patient(app: &TestApp) -> anyhow::Result<String> {
+    let (status, _headers, body) = app
+        .request(
+            Method::POST,
+            "/fhir/Patient",
+            Some(to_json_body(&minimal_patient())?),
+        )
+        .await?;
+    assert_status(status, StatusCode::CREATED, "create Patient");
+    let created: Value = serde_json::from_slice(&body)?;
+    Ok(created["id"].as_str().unwrap().to_string())
+}
+
+#[tokio::test]
+async fn failing_transaction_entry_rolls_back_all_entries() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            let patient_id = create_existing_patient(app).await?;
+
+            let bundle = bundle_with_failing_second_entry("transaction", &patient_id);
+            let (status, _headers, body) = app
+                .request(Method::POST, "/fhir", Some(to_json_body(&bundle)?))
+                .await?;
+            assert_status(status, StatusCode::PRECONDITION_FAILED, "transaction");
+
+            let outcome: Value = serde_json::from_slice(&body)?;
+            assert_eq!(outcome["resourceType"], "OperationOutcome");
+            let diagnostics = outcome["issue"][0]["diagnostics"].as_str().unwrap_or("");
+            assert!(
+                diagnostics.contains("Transaction entry 1:"),
+                "failing entry index missing from: {}",
+                diagnostics
+            );
+
+            // Neither POST was committed and the existing Patient is unchanged.
+            assert_eq!(count_current(app, "Patient").await?, 1);
+            assert_eq!(count_current(app, "Observation").await?, 0);
+
+            let (status, _headers, body) = app
+                .request(Method::GET, &format!("/fhir/Patient/{}", patient_id), None)
+                .await?;
+            assert_status(status, StatusCode::OK, "read existing Patient");
+            let patient: Value = serde_json::from_slice(&body)?;
+            assert_eq!(patient["meta"]["versionId"], "1");
+
+            Ok(())
+        })
+    })
+    .await
+}
+
+#[tokio::test]
+async fn failing_batch_entry_keeps_other_entries() -> anyhow::Result<()> {
+    with_test_app(|app| {
+        Box::pin(async move {
+            let patient_id = create_existing_patient(app).await?;
+
+            let bundle = bundle_with_failing_second_entry("batch", &patient_id);
+            let (status, _headers, body) = app
+                .request(Method::POST, "/fhir", Some(to_json_body(&bundle)?))
+                .await?;
+            assert_status(status, StatusCode::OK, "batch");
+
+            let response: Value = serde_json::from_slice(&body)?;
+            let statuses: Vec<&str> = response["entry"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|e| {
+                    e["response"]["status"]
+                        .as_str()
+                        .and_then(|s| s.split_whitespace().next())
+                        .unwrap_or("")
+                })
+                .collect();
+            assert_eq!(statuses, vec!["201", "412", "201"]);
+
+            assert_eq!(count_current(app, "Patient").await?, 2);
+            assert_eq!(count_current(app, "Observation").await?, 1);
+
+            Ok(())
+        })
+    })
+    .await
+}
@@ -23,6 +23,13 @@ pub struct GeneratorConfig {
     pub generate_docs: bool,
     /// Whether to generate serde derive macros (for serialization)
     pub generate_serde: bool,
+    /// Whether to generate `TryFrom<T> for serde_json::Value` and `TryFrom<serde_json::Value>`
+    /// impls per type (requires `generate_serde`)
+    pub generate_value_conversions: bool,
+    /// Additional derives appended to every generated struct (e.g., "Default", "Hash")
+    pub extra_derives: Vec<String>,
+    /// Whether to generate a `{Type}Builder` per struct
+    pub generate_builders: bool,
     /// Custom module path prefix
     pub module_prefix: Option<String>,
 }
@@ -32,6 +39,9 @@ impl Default for GeneratorConfig {
         Self {
             generate_docs: true,
             generate_serde: true,
+            generate_value_conversions: false,
+            extra_derives: Vec::new(),
+            generate_builders: false,
             module_prefix: None,
         }
     }
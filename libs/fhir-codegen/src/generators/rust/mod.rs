@@ -56,6 +56,13 @@ impl Generator for RustGenerator {
             }
         }
 
+        // Generate the AnyResource enum over all concrete resources
+        let resources = concrete_resources(registry);
+        if !resources.is_empty() {
+            let code = self.generate_any_resource_module(&resources);
+            modules.insert("any_resource.rs".to_string(), code);
+        }
+
         // Generate mod.rs that exports all modules
         let mod_rs = self.generate_mod_rs(registry);
         modules.insert("mod.rs".to_string(), mod_rs);
@@ -170,11 +177,7 @@ impl RustGenerator {
             }
 
             // Generate derive macros
-            code.push_str("#[derive(Debug, Clone, PartialEq");
-            if self.config.generate_serde {
-                code.push_str(", Serialize, Deserialize");
-            }
-            code.push_str(")]\n");
+            code.push_str(&types::generate_derives(&self.config));
 
             // Add serde rename_all for camelCase
             if self.config.generate_serde {
@@ -194,7 +197,44 @@ impl RustGenerator {
             }
 
             code.push('}');
+
+            if self.config.generate_serde && self.config.generate_value_conversions {
+                code.push_str("\n\n");
+                code.push_str(&types::generate_value_conversions(&backbone.name));
+            }
+
+            if self.config.generate_builders {
+                code.push_str("\n\n");
+                code.push_str(&types::generate_builder(
+                    &backbone.name,
+                    &backbone.properties,
+                    registry,
+                ));
+            }
+        }
+
+        code
+    }
+
+    /// Generate the module holding the `AnyResource` enum
+    fn generate_any_resource_module(&self, resources: &[&TypeDefinition]) -> String {
+        let mut code = String::new();
+
+        code.push_str("//! AnyResource enum over all generated resources\n\n");
+
+        if self.config.generate_serde {
+            code.push_str("use serde::{Deserialize, Serialize};\n");
+        }
+        for resource in resources {
+            code.push_str(&format!(
+                "use super::{}::{};\n",
+                resource.name.to_snake_case(),
+                resource.name
+            ));
         }
+        code.push('\n');
+
+        code.push_str(&types::generate_any_resource_enum(resources, &self.config));
 
         code
     }
@@ -234,17 +274,17 @@ impl RustGenerator {
         }
 
         // Declare all resource modules
-        let mut resources: Vec<_> = registry
-            .resource_types()
-            .filter(|t| !t.is_abstract)
-            .collect();
-        resources.sort_by(|a, b| a.name.cmp(&b.name));
+        let resources = concrete_resources(registry);
 
-        for type_def in resources {
+        for type_def in &resources {
             let module_name = type_def.name.to_snake_case();
             code.push_str(&format!("pub mod {};\n", module_name));
         }
 
+        if !resources.is_empty() {
+            code.push_str("pub mod any_resource;\n");
+        }
+
         code.push_str("\n// Re-export all types\n");
         code.push_str("pub use primitives::*;\n");
 
@@ -260,6 +300,328 @@ impl RustGenerator {
             }
         }
 
+        if !resources.is_empty() {
+            code.push_str("pub use any_resource::AnyResource;\n");
+        }
+
         code
     }
 }
+
+/// Non-abstract resources in the registry, sorted by name
+fn concrete_resources(registry: &TypeRegistry) -> Vec<&TypeDefinition> {
+    let mut resources: Vec<_> = registry
+        .resource_types()
+        .filter(|t| !t.is_abstract)
+        .collect();
+    resources.sort_by(|a, b| a.name.cmp(&b.name));
+    resources
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::{Cardinality, Property, PropertyType, TypeKind};
+
+    fn property(name: &str, code: &str, min: u32) -> Property {
+        Property {
+            name: name.to_string(),
+            path: format!("Patient.{}", name),
+            description: None,
+            types: vec![PropertyType {
+                code: code.to_string(),
+                profile: None,
+                target_profiles: Vec::new(),
+                content_reference: None,
+            }],
+            cardinality: Cardinality::new(min, Some(1)),
+            is_required: min > 0,
+            is_modifier: false,
+            must_support: false,
+        }
+    }
+
+    fn resource(name: &str, properties: Vec<Property>) -> TypeDefinition {
+        TypeDefinition {
+            name: name.to_string(),
+            url: Some(format!("http://hl7.org/fhir/StructureDefinition/{}", name)),
+            description: None,
+            kind: TypeKind::Resource,
+            base_type: None,
+            properties: properties
+                .into_iter()
+                .map(|p| Property {
+                    path: format!("{}.{}", name, p.name),
+                    ..p
+                })
+                .collect(),
+            is_abstract: false,
+            backbone_elements: Vec::new(),
+            parent_type: None,
+        }
+    }
+
+    fn add_type(registry: &mut TypeRegistry, type_def: TypeDefinition) {
+        registry.add_type(type_def.url.clone().unwrap(), type_def);
+    }
+
+    fn patient_registry() -> TypeRegistry {
+        let mut registry = TypeRegistry::new();
+        add_type(
+            &mut registry,
+            resource(
+                "Patient",
+                vec![
+                    property("active", "boolean", 0),
+                    property("gender", "code", 1),
+                ],
+            ),
+        );
+        registry
+    }
+
+    #[test]
+    fn test_value_conversions_are_opt_in() {
+        let registry = patient_registry();
+
+        let output = RustGenerator::new_default().generate(&registry).unwrap();
+        assert!(!output.modules["patient.rs"].contains("serde_json::Value"));
+
+        let config = GeneratorConfig {
+            generate_value_conversions: true,
+            ..GeneratorConfig::default()
+        };
+        let output = RustGenerator::new(config).generate(&registry).unwrap();
+        compile_and_run(
+            "value-conversions",
+            &output,
+            "    let patient = Patient {
+        active: Some(true),
+        gender: \"female\".to_string(),
+    };
+    let value = serde_json::Value::try_from(patient.clone()).unwrap();
+    assert_eq!(value, serde_json::json!({\"active\": true, \"gender\": \"female\"}));
+    assert_eq!(Patient::try_from(value).unwrap(), patient);
+
+    assert!(Patient::try_from(serde_json::json!({\"active\": \"yes\"})).is_err());",
+        );
+    }
+
+    #[test]
+    fn test_extra_derives_are_appended() {
+        let config = GeneratorConfig {
+            extra_derives: vec!["Default".to_string(), "Debug".to_string()],
+            ..GeneratorConfig::default()
+        };
+        let output = RustGenerator::new(config)
+            .generate(&patient_registry())
+            .unwrap();
+
+        assert!(output.modules["patient.rs"]
+            .contains("#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]"));
+    }
+
+    #[test]
+    fn test_generated_builder_compiles_and_checks_required_fields() {
+        let config = GeneratorConfig {
+            generate_serde: false,
+            generate_builders: true,
+            ..GeneratorConfig::default()
+        };
+        let output = RustGenerator::new(config)
+            .generate(&patient_registry())
+            .unwrap();
+        let code = &output.modules["patient.rs"];
+        assert!(code.contains("pub struct PatientBuilder {"));
+        assert!(code.contains("pub fn build(self) -> Result<Patient, String> {"));
+
+        compile_and_run(
+            "builder",
+            &output,
+            "    let patient = Patient::builder()
+        .gender(\"female\".to_string())
+        .active(true)
+        .build()
+        .unwrap();
+    assert_eq!(patient.active, Some(true));
+    assert_eq!(patient.gender, \"female\");
+
+    let err = Patient::builder().active(false).build().unwrap_err();
+    assert_eq!(err, \"missing required field Patient.gender\");",
+        );
+    }
+
+    #[test]
+    fn test_any_resource_round_trips_by_resource_type() {
+        let mut registry = patient_registry();
+        add_type(
+            &mut registry,
+            resource("Observation", vec![property("status", "code", 1)]),
+        );
+        add_type(
+            &mut registry,
+            TypeDefinition {
+                is_abstract: true,
+                ..resource("DomainResource", Vec::new())
+            },
+        );
+
+        let output = RustGenerator::new_default().generate(&registry).unwrap();
+        let code = &output.modules["any_resource.rs"];
+        assert!(code.contains("#[serde(tag = \"resourceType\")]"));
+        assert!(code.contains("    Observation(Observation),\n    Patient(Patient),\n"));
+        assert!(
+            !code.contains("DomainResource"),
+            "abstract resources get no variant"
+        );
+        assert!(output.modules["mod.rs"].contains("pub use any_resource::AnyResource;"));
+
+        compile_and_run(
+            "any-resource",
+            &output,
+            "    let value = serde_json::json!({\"resourceType\": \"Patient\", \"active\": true, \"gender\": \"female\"});
+    let resource: AnyResource = serde_json::from_value(value.clone()).unwrap();
+    assert_eq!(resource.resource_type(), \"Patient\");
+    assert_eq!(
+        resource,
+        AnyResource::Patient(Patient {
+            active: Some(true),
+            gender: \"female\".to_string(),
+        })
+    );
+    assert_eq!(serde_json::to_value(&resource).unwrap(), value);
+
+    let resource: AnyResource =
+        serde_json::from_value(serde_json::json!({\"resourceType\": \"Observation\", \"status\": \"final\"}))
+            .unwrap();
+    assert_eq!(resource.resource_type(), \"Observation\");
+    assert!(matches!(resource, AnyResource::Observation(Observation { .. })));
+
+    assert!(serde_json::from_value::<AnyResource>(serde_json::json!({\"resourceType\": \"Encounter\"})).is_err());",
+        );
+    }
+
+    #[test]
+    fn test_keyword_and_invalid_field_names_are_renamed() {
+        let mut registry = patient_registry();
+        let mut patient = registry.get_type_by_name("Patient").unwrap().clone();
+        for name in ["type", "use", "abstract", "ref", "self", "link-type", "1st"] {
+            patient.properties.push(property(name, "string", 0));
+        }
+        registry.add_type(patient.url.clone().unwrap(), patient);
+
+        let output = RustGenerator::new_default().generate(&registry).unwrap();
+        let code = &output.modules["patient.rs"];
+        for (field, wire) in [
+            ("r#type", "type"),
+            ("r#use", "use"),
+            ("r#abstract", "abstract"),
+            ("r#ref", "ref"),
+            ("self_", "self"),
+            ("link_type", "link-type"),
+        ] {
+            assert!(
+                code.contains(&format!(
+                    "    #[serde(rename = \"{}\")]\n    pub {}: Option<String>,",
+                    wire, field
+                )),
+                "missing renamed field {} in:\n{}",
+                field,
+                code
+            );
+        }
+        // Names that round-trip through `rename_all = "camelCase"` need no rename
+        assert!(!code.contains("rename = \"active\""));
+        assert!(!code.contains("rename = \"1st\""));
+        assert!(code.contains("    pub _1st: Option<String>,"));
+
+        let config = GeneratorConfig {
+            generate_serde: false,
+            ..GeneratorConfig::default()
+        };
+        let output = RustGenerator::new(config).generate(&registry).unwrap();
+        compile_and_run(
+            "field-names",
+            &output,
+            "    let patient = Patient {
+        active: None,
+        gender: String::new(),
+        r#type: Some(\"type\".to_string()),
+        r#use: None,
+        r#abstract: None,
+        r#ref: None,
+        self_: None,
+        link_type: None,
+        _1st: None,
+    };
+    assert_eq!(patient.r#type.as_deref(), Some(\"type\"));",
+        );
+    }
+
+    /// Build the generated modules together with a `main` running `body` as a cargo package
+    /// depending on `serde` and `serde_json`, and run it.
+    ///
+    /// The modules live under `generated` and are glob-imported into `main`. The workspace
+    /// lockfile pins the dependencies so the build works offline; the target directory is
+    /// shared between tests so they are only compiled once. Skipped when no cargo toolchain
+    /// is available.
+    fn compile_and_run(name: &str, output: &RustOutput, body: &str) {
+        let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+        let toolchain = std::process::Command::new(&cargo).arg("--version").output();
+        if !toolchain.is_ok_and(|version| version.status.success()) {
+            eprintln!("skipping {}: no cargo toolchain available", name);
+            return;
+        }
+
+        let dir =
+            std::env::temp_dir().join(format!("ferrum-codegen-{}-{}", name, std::process::id()));
+        std::fs::create_dir_all(dir.join("src/generated")).unwrap();
+        std::fs::write(
+            dir.join("Cargo.toml"),
+            format!(
+                "[package]
+name = \"ferrum-codegen-{name}\"
+version = \"0.0.0\"
+edition = \"2021\"
+
+[dependencies]
+serde = {{ version = \"1\", features = [\"derive\"] }}
+serde_json = \"1\"
+
+[workspace]
+"
+            ),
+        )
+        .unwrap();
+        std::fs::copy(
+            concat!(env!("CARGO_MANIFEST_DIR"), "/../../Cargo.lock"),
+            dir.join("Cargo.lock"),
+        )
+        .unwrap();
+        for (file_name, code) in &output.modules {
+            std::fs::write(dir.join("src/generated").join(file_name), code).unwrap();
+        }
+        std::fs::write(
+            dir.join("src/main.rs"),
+            format!("mod generated;\n\nuse generated::*;\n\nfn main() {{\n{body}\n}}\n"),
+        )
+        .unwrap();
+
+        let output = std::process::Command::new(cargo)
+            .args(["run", "--offline", "--quiet", "--manifest-path"])
+            .arg(dir.join("Cargo.toml"))
+            .env(
+                "CARGO_TARGET_DIR",
+                std::env::temp_dir().join("ferrum-codegen-target"),
+            )
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "generated code failed to build or run:\n{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
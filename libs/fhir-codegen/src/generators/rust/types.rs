@@ -30,11 +30,7 @@ pub fn generate_struct(
     }
 
     // Generate derive macros
-    code.push_str("#[derive(Debug, Clone, PartialEq");
-    if config.generate_serde {
-        code.push_str(", Serialize, Deserialize");
-    }
-    code.push_str(")]\n");
+    code.push_str(&generate_derives(config));
 
     // Add serde rename_all for camelCase
     if config.generate_serde {
@@ -51,6 +47,165 @@ pub fn generate_struct(
 
     code.push('}');
 
+    if config.generate_serde && config.generate_value_conversions {
+        code.push_str("\n\n");
+        code.push_str(&generate_value_conversions(&type_def.name));
+    }
+
+    if config.generate_builders {
+        code.push_str("\n\n");
+        code.push_str(&generate_builder(
+            &type_def.name,
+            &type_def.properties,
+            registry,
+        ));
+    }
+
+    code
+}
+
+/// Generate the derive attribute for a struct
+pub fn generate_derives(config: &GeneratorConfig) -> String {
+    let mut derives = vec!["Debug", "Clone", "PartialEq"];
+    if config.generate_serde {
+        derives.extend(["Serialize", "Deserialize"]);
+    }
+    for extra in &config.extra_derives {
+        if !derives.contains(&extra.as_str()) {
+            derives.push(extra);
+        }
+    }
+    format!("#[derive({})]\n", derives.join(", "))
+}
+
+/// Generate a `{Type}Builder` with chainable setters
+///
+/// `build()` fails if a required field was not set.
+pub fn generate_builder(
+    type_name: &str,
+    properties: &[Property],
+    registry: &TypeRegistry,
+) -> String {
+    let builder = format!("{}Builder", type_name);
+    let fields: Vec<(String, String, &Property)> = properties
+        .iter()
+        .map(|p| {
+            (
+                sanitize_field_name(&p.name),
+                generate_inner_field_type(p, registry),
+                p,
+            )
+        })
+        .collect();
+
+    let mut code = String::new();
+    code.push_str(&format!("/// Builder for [`{}`]\n", type_name));
+    code.push_str("#[derive(Debug, Clone, Default)]\n");
+    code.push_str(&format!("pub struct {} {{\n", builder));
+    for (field, ty, _) in &fields {
+        code.push_str(&format!("    {}: Option<{}>,\n", field, ty));
+    }
+    code.push_str("}\n\n");
+
+    code.push_str(&format!("impl {} {{\n", type_name));
+    code.push_str(&format!("    pub fn builder() -> {} {{\n", builder));
+    code.push_str(&format!("        {}::default()\n", builder));
+    code.push_str("    }\n}\n\n");
+
+    code.push_str(&format!("impl {} {{\n", builder));
+    for (field, ty, _) in &fields {
+        code.push_str(&format!(
+            "    pub fn {}(mut self, value: {}) -> Self {{\n",
+            field, ty
+        ));
+        code.push_str(&format!("        self.{} = Some(value);\n", field));
+        code.push_str("        self\n    }\n\n");
+    }
+
+    code.push_str(&format!(
+        "    pub fn build(self) -> Result<{}, String> {{\n",
+        type_name
+    ));
+    code.push_str(&format!("        Ok({} {{\n", type_name));
+    for (field, _, property) in &fields {
+        if property.cardinality.is_optional() {
+            code.push_str(&format!("            {}: self.{},\n", field, field));
+        } else {
+            code.push_str(&format!(
+                "            {}: self.{}.ok_or(\"missing required field {}\")?,\n",
+                field, field, property.path
+            ));
+        }
+    }
+    code.push_str("        })\n    }\n}");
+
+    code
+}
+
+/// Generate `serde_json::Value` conversions for a struct
+pub fn generate_value_conversions(type_name: &str) -> String {
+    format!(
+        "impl TryFrom<{name}> for serde_json::Value {{
+    type Error = serde_json::Error;
+
+    fn try_from(value: {name}) -> Result<Self, Self::Error> {{
+        serde_json::to_value(value)
+    }}
+}}
+
+impl TryFrom<serde_json::Value> for {name} {{
+    type Error = serde_json::Error;
+
+    fn try_from(value: serde_json::Value) -> Result<Self, Self::Error> {{
+        serde_json::from_value(value)
+    }}
+}}",
+        name = type_name
+    )
+}
+
+/// Generate the `AnyResource` enum with one variant per concrete resource
+///
+/// With serde enabled the enum is internally tagged by `resourceType`.
+pub fn generate_any_resource_enum(
+    resources: &[&TypeDefinition],
+    config: &GeneratorConfig,
+) -> String {
+    let mut code = String::new();
+
+    if config.generate_docs {
+        code.push_str("/// Any concrete FHIR resource, discriminated by `resourceType`\n");
+    }
+    // `extra_derives` (e.g. `Default`) target structs and may not apply to an enum
+    if config.generate_serde {
+        code.push_str("#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]\n");
+        code.push_str("#[serde(tag = \"resourceType\")]\n");
+    } else {
+        code.push_str("#[derive(Debug, Clone, PartialEq)]\n");
+    }
+
+    code.push_str("pub enum AnyResource {\n");
+    for resource in resources {
+        code.push_str(&format!("    {name}({name}),\n", name = resource.name));
+    }
+    code.push_str("}\n\n");
+
+    code.push_str("impl AnyResource {\n");
+    if config.generate_docs {
+        code.push_str("    /// The `resourceType` of the contained resource\n");
+    }
+    code.push_str("    pub fn resource_type(&self) -> &str {\n");
+    code.push_str("        match self {\n");
+    for resource in resources {
+        code.push_str(&format!(
+            "            Self::{name}(_) => \"{name}\",\n",
+            name = resource.name
+        ));
+    }
+    code.push_str("        }\n");
+    code.push_str("    }\n");
+    code.push('}');
+
     code
 }
 
@@ -94,6 +249,9 @@ fn generate_field(
         }
     }
 
+    // Field name (convert to snake_case and handle keywords)
+    let field_name = sanitize_field_name(&property.name);
+
     // Serde attributes
     if config.generate_serde {
         // Handle optional fields
@@ -101,15 +259,13 @@ fn generate_field(
             code.push_str("    #[serde(skip_serializing_if = \"Option::is_none\")]\n");
         }
 
-        // Handle special renames (e.g., 'type' is a Rust keyword)
-        if is_rust_keyword(&property.name) {
+        // Keep the wire name when the field had to be renamed (e.g., 'type' is a Rust
+        // keyword) or `rename_all = "camelCase"` would not map it back to the FHIR name
+        if is_rust_keyword(&property.name) || serde_camel_case(&field_name) != property.name {
             code.push_str(&format!("    #[serde(rename = \"{}\")]\n", property.name));
         }
     }
 
-    // Field name (convert to snake_case and handle keywords)
-    let field_name = sanitize_field_name(&property.name);
-
     // Field type
     let field_type = generate_field_type(property, registry);
 
@@ -120,9 +276,30 @@ fn generate_field(
 
 /// Generate the Rust type for a property
 fn generate_field_type(property: &Property, registry: &TypeRegistry) -> String {
+    let base_type = generate_inner_field_type(property, registry);
+
+    // Wrap in Option if optional
+    if property.cardinality.is_optional() {
+        format!("Option<{}>", base_type)
+    } else {
+        base_type
+    }
+}
+
+/// Generate the Rust type for a property, without the `Option` wrapper for optional fields
+fn generate_inner_field_type(property: &Property, registry: &TypeRegistry) -> String {
     // Handle multiple types (use an enum or Box<dyn> in practice, simplified here)
     let base_type = if property.types.is_empty() {
         "serde_json::Value".to_string()
+    } else if property.types[0].content_reference.is_some() {
+        // Content references may point back at the enclosing backbone element, so a
+        // single value is boxed to break the recursion (Vec already provides indirection)
+        let backbone = property.types[0].code.clone();
+        if property.cardinality.is_array() {
+            backbone
+        } else {
+            format!("Box<{}>", backbone)
+        }
     } else if property.types.len() == 1 {
         map_fhir_type_to_rust(&property.types[0], registry)
     } else {
@@ -131,17 +308,10 @@ fn generate_field_type(property: &Property, registry: &TypeRegistry) -> String {
     };
 
     // Wrap in Vec if array
-    let base_type = if property.cardinality.is_array() {
+    if property.cardinality.is_array() {
         format!("Vec<{}>", base_type)
     } else {
         base_type
-    };
-
-    // Wrap in Option if optional
-    if property.cardinality.is_optional() {
-        format!("Option<{}>", base_type)
-    } else {
-        base_type
     }
 }
 
@@ -201,13 +371,43 @@ fn map_fhir_type_to_rust(property_type: &PropertyType, registry: &TypeRegistry)
 
 /// Sanitize a field name to be a valid Rust identifier
 fn sanitize_field_name(name: &str) -> String {
-    let snake = name.to_snake_case();
+    // Characters that cannot appear in an identifier become underscores
+    let mut snake: String = name
+        .to_snake_case()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+
+    if snake.is_empty() {
+        snake.push_str("field");
+    } else if snake.starts_with(|c: char| c.is_ascii_digit()) {
+        snake.insert(0, '_');
+    }
 
-    if is_rust_keyword(&snake) {
-        format!("r#{}", snake)
-    } else {
-        snake
+    match snake.as_str() {
+        // Keywords that cannot be raw identifiers
+        "self" | "Self" | "super" | "crate" => format!("{}_", snake),
+        s if is_rust_keyword(s) => format!("r#{}", snake),
+        _ => snake,
+    }
+}
+
+/// The name serde uses for a field under `rename_all = "camelCase"`
+fn serde_camel_case(field_name: &str) -> String {
+    let field_name = field_name.strip_prefix("r#").unwrap_or(field_name);
+    let mut camel = String::with_capacity(field_name.len());
+    let mut capitalize = false;
+    for c in field_name.chars() {
+        if c == '_' {
+            capitalize = true;
+        } else if capitalize {
+            camel.push(c.to_ascii_uppercase());
+            capitalize = false;
+        } else {
+            camel.push(c);
+        }
     }
+    camel
 }
 
 /// Check if a string is a Rust keyword
@@ -196,6 +196,10 @@ pub struct PropertyType {
     pub profile: Option<String>,
     /// Target resource types (for Reference properties)
     pub target_profiles: Vec<String>,
+    /// Element path this type was resolved from via `contentReference`
+    /// (e.g., "Questionnaire.item"); `code` is then the backbone element name
+    #[serde(default)]
+    pub content_reference: Option<String>,
 }
 
 /// Cardinality of a property (min..max)
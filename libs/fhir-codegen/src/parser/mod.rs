@@ -270,12 +270,14 @@ fn parse_element(element: &Value, type_name: &str) -> Result<Property> {
     let cardinality = Cardinality::new(min, max);
     let is_required = cardinality.is_required();
 
-    // Parse types
+    // Parse types; elements using contentReference reuse the referenced backbone element
     let types = if let Some(type_array) = element.get("type").and_then(|v| v.as_array()) {
         type_array
             .iter()
             .filter_map(|t| parse_element_type(t).ok())
             .collect()
+    } else if let Some(reference) = element.get("contentReference").and_then(|v| v.as_str()) {
+        parse_content_reference(reference).into_iter().collect()
     } else {
         Vec::new()
     };
@@ -331,6 +333,29 @@ fn parse_element_type(type_spec: &Value) -> Result<PropertyType> {
         code,
         profile,
         target_profiles,
+        content_reference: None,
+    })
+}
+
+/// Resolve a `contentReference` (e.g., "#Questionnaire.item") to the backbone element
+/// it points at.
+///
+/// Only top-level backbone elements are generated, so references to deeper paths
+/// (e.g., "#ValueSet.compose.include") are not resolved.
+fn parse_content_reference(reference: &str) -> Option<PropertyType> {
+    let path = reference
+        .rsplit_once('#')
+        .map_or(reference, |(_, path)| path);
+    let (_, backbone) = path.split_once('.')?;
+    if backbone.is_empty() || backbone.contains('.') {
+        return None;
+    }
+
+    Some(PropertyType {
+        code: capitalize_first(backbone),
+        profile: None,
+        target_profiles: Vec::new(),
+        content_reference: Some(path.to_string()),
     })
 }
 
@@ -352,4 +377,80 @@ mod tests {
         );
         assert_eq!(extract_type_name_from_url("Patient"), "Patient");
     }
+
+    #[test]
+    fn test_content_reference_reuses_backbone_type() {
+        use crate::generators::rust::RustGenerator;
+        use crate::generators::Generator;
+        use serde_json::json;
+
+        let sd = json!({
+            "resourceType": "StructureDefinition",
+            "name": "Questionnaire",
+            "url": "http://hl7.org/fhir/StructureDefinition/Questionnaire",
+            "kind": "resource",
+            "snapshot": {
+                "element": [
+                    { "path": "Questionnaire" },
+                    {
+                        "path": "Questionnaire.item",
+                        "min": 0,
+                        "max": "*",
+                        "type": [{ "code": "BackboneElement" }]
+                    },
+                    {
+                        "path": "Questionnaire.item.linkId",
+                        "min": 1,
+                        "max": "1",
+                        "type": [{ "code": "string" }]
+                    },
+                    {
+                        "path": "Questionnaire.item.item",
+                        "min": 0,
+                        "max": "*",
+                        "contentReference": "#Questionnaire.item"
+                    },
+                    {
+                        "path": "Questionnaire.item.parent",
+                        "min": 0,
+                        "max": "1",
+                        "contentReference": "http://hl7.org/fhir/StructureDefinition/Questionnaire#Questionnaire.item"
+                    },
+                    {
+                        "path": "Questionnaire.item.nested",
+                        "min": 0,
+                        "max": "1",
+                        "contentReference": "#Questionnaire.item.answerOption"
+                    }
+                ]
+            }
+        });
+
+        let type_def = parse_structure_definition(&sd).unwrap();
+        let item = &type_def.backbone_elements[0];
+        let item_type = &item.properties[1].types[0];
+        assert_eq!(item_type.code, "Item");
+        assert_eq!(
+            item_type.content_reference.as_deref(),
+            Some("Questionnaire.item")
+        );
+        assert_eq!(item.properties[2].types[0].code, "Item");
+        assert!(item.properties[3].types.is_empty());
+
+        let mut registry = TypeRegistry::new();
+        registry.add_type(type_def.url.clone().unwrap(), type_def);
+        let output = RustGenerator::new_default().generate(&registry).unwrap();
+        let code = output
+            .modules
+            .values()
+            .find(|code| code.contains("pub struct Item"))
+            .expect("backbone struct generated");
+
+        assert!(code.contains("pub item: Option<Vec<Item>>,"), "{code}");
+        assert!(code.contains("pub parent: Option<Box<Item>>,"), "{code}");
+        assert!(
+            code.contains("pub nested: Option<serde_json::Value>,"),
+            "{code}"
+        );
+    }
 }
@@ -1,6 +1,8 @@
 use crate::error::{Error, Result};
 use crate::loader::PackageLoader;
-use crate::version::{extract_version_algorithm, select_from_version_index, VersionKey};
+use crate::version::{
+    extract_version_algorithm, select_from_version_index, VersionAlgorithm, VersionKey,
+};
 use async_trait::async_trait;
 use lru::LruCache;
 use serde::{Deserialize, Serialize};
@@ -10,12 +12,12 @@ use std::fs;
 use std::future::Future;
 use std::num::NonZeroUsize;
 use std::path::Path;
-use std::sync::atomic::{AtomicU64, Ordering as AtomicOrdering};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering as AtomicOrdering};
 use std::sync::Arc;
-use std::sync::Mutex;
+use std::sync::{Mutex, OnceLock};
 use std::time::{Duration, Instant};
 use ferrum_models::{ElementTypeInfo, StructureDefinition};
-use ferrum_package::FhirPackage;
+use ferrum_package::{FhirPackage, PackageManifest, RawFhirPackage};
 use tokio::runtime::Handle;
 
 #[async_trait]
@@ -36,52 +38,206 @@ pub trait ConformanceResourceProvider: Send + Sync {
         version: &str,
     ) -> Result<Option<Arc<Value>>> {
         let resources = self.list_by_canonical(canonical_url).await?;
-        let mut versions: BTreeMap<VersionKey, Arc<Value>> = BTreeMap::new();
-        for resource in resources {
-            let Some(url) = resource.get("url").and_then(|v| v.as_str()) else {
-                continue;
-            };
-            if url != canonical_url {
-                continue;
-            }
+        let versions = build_version_index(canonical_url, resources);
+        Ok(select_from_version_index(&versions, Some(version)).cloned())
+    }
 
-            let version_str = resource
-                .get("version")
-                .and_then(|v| v.as_str())
-                .unwrap_or("0");
+    /// Resolve a canonical reference of the form `url` or `url|version`.
+    ///
+    /// Returns the exact business version when one is given, otherwise the highest known
+    /// version (stable releases preferred). URLs are matched exactly; providers that index
+    /// canonicals case-insensitively (like [`DefaultFhirContext`]) override this.
+    async fn resolve_canonical(&self, canonical: &str) -> Result<Option<Arc<Value>>> {
+        let (canonical_url, version) = split_canonical(canonical);
+        let resources = self.list_by_canonical(canonical_url).await?;
+        let versions = build_version_index(canonical_url, resources);
 
-            let algorithm = extract_version_algorithm(resource.as_ref());
-            versions.insert(VersionKey::new(version_str, algorithm), resource);
+        Ok(select_from_version_index(&versions, version).cloned())
+    }
+}
+
+/// Split a canonical reference into its URL and optional `|version` suffix.
+fn split_canonical(canonical: &str) -> (&str, Option<&str>) {
+    match canonical.split_once('|') {
+        Some((url, version)) if !version.is_empty() => (url, Some(version)),
+        Some((url, _)) => (url, None),
+        None => (canonical, None),
+    }
+}
+
+/// Index resources whose `url` matches `canonical_url` by business version.
+fn build_version_index(
+    canonical_url: &str,
+    resources: Vec<Arc<Value>>,
+) -> BTreeMap<VersionKey, Arc<Value>> {
+    let mut versions: BTreeMap<VersionKey, Arc<Value>> = BTreeMap::new();
+    for resource in resources {
+        let Some(url) = resource.get("url").and_then(|v| v.as_str()) else {
+            continue;
+        };
+        if url != canonical_url {
+            continue;
         }
 
-        Ok(select_from_version_index(&versions, Some(version)).cloned())
+        let version_str = resource
+            .get("version")
+            .and_then(|v| v.as_str())
+            .unwrap_or("0");
+
+        let algorithm = extract_version_algorithm(resource.as_ref());
+        versions.insert(VersionKey::new(version_str, algorithm), resource);
     }
+    versions
 }
 
+/// Resolves through a chain of providers, in order
+///
+/// The first provider with an answer for a canonical wins. A provider error is only
+/// surfaced when the last provider in the chain fails too (the first error is returned).
+/// With [`Self::with_answer_tracking`], which provider answered each canonical is recorded
+/// and reported by [`Self::introspection`], e.g. to see whether an in-memory layer shadowed
+/// the package-backed definitions.
 pub struct FallbackConformanceProvider {
-    primary: Arc<dyn ConformanceResourceProvider>,
-    fallback: Arc<dyn ConformanceResourceProvider>,
+    providers: Vec<(String, Arc<dyn ConformanceResourceProvider>)>,
+    /// Latest answer per canonical URL, for the most recently answered canonicals
+    answered_by: Option<Mutex<LruCache<String, ProviderAnswer>>>,
+}
+
+/// Which provider in the chain answered a canonical, and the ids and types it returned
+struct ProviderAnswer {
+    provider: usize,
+    resource_ids: Vec<String>,
+    resource_types: Vec<String>,
 }
 
 impl FallbackConformanceProvider {
-    pub fn new(
-        primary: Arc<dyn ConformanceResourceProvider>,
-        fallback: Arc<dyn ConformanceResourceProvider>,
-    ) -> Self {
-        Self { primary, fallback }
+    /// Chain `providers` in order; they are named `provider-0`, `provider-1`, ...
+    pub fn new(providers: Vec<Arc<dyn ConformanceResourceProvider>>) -> Self {
+        Self::with_names(
+            providers
+                .into_iter()
+                .enumerate()
+                .map(|(i, provider)| (format!("provider-{}", i), provider))
+                .collect(),
+        )
+    }
+
+    /// Chain named providers in order; names are reported by [`Self::introspection`]
+    pub fn with_names(providers: Vec<(String, Arc<dyn ConformanceResourceProvider>)>) -> Self {
+        Self {
+            providers,
+            answered_by: None,
+        }
+    }
+
+    /// Record which provider answered the `capacity` most recently resolved canonicals
+    ///
+    /// Off by default; meant for diagnostics via [`Self::introspection`].
+    pub fn with_answer_tracking(mut self, capacity: usize) -> Self {
+        let capacity = NonZeroUsize::new(capacity.max(1)).unwrap();
+        self.answered_by = Some(Mutex::new(LruCache::new(capacity)));
+        self
+    }
+
+    /// One entry per provider in chain order, listing the tracked canonicals it answered
+    ///
+    /// `name` is the provider name and `version` is empty; the remaining fields describe
+    /// the resources this provider returned. Without [`Self::with_answer_tracking`] only the
+    /// names are filled in.
+    pub fn introspection(&self) -> Vec<PackageIntrospection> {
+        let answered_by = self
+            .answered_by
+            .as_ref()
+            .map(|answered_by| answered_by.lock().unwrap_or_else(|e| e.into_inner()));
+
+        self.providers
+            .iter()
+            .enumerate()
+            .map(|(index, (name, _))| {
+                let answered: Vec<(&String, &ProviderAnswer)> = answered_by
+                    .iter()
+                    .flat_map(|answered_by| answered_by.iter())
+                    .filter(|(_, answer)| answer.provider == index)
+                    .collect();
+
+                let mut canonical_urls: Vec<String> =
+                    answered.iter().map(|(url, _)| url.to_string()).collect();
+                canonical_urls.sort();
+
+                let mut resource_ids: Vec<String> = answered
+                    .iter()
+                    .flat_map(|(_, answer)| answer.resource_ids.iter().cloned())
+                    .collect();
+                let mut resource_counts_by_type: HashMap<String, usize> = HashMap::new();
+                for resource_type in answered
+                    .iter()
+                    .flat_map(|(_, answer)| &answer.resource_types)
+                {
+                    *resource_counts_by_type
+                        .entry(resource_type.clone())
+                        .or_insert(0) += 1;
+                }
+                resource_ids.sort();
+                resource_ids.dedup();
+
+                PackageIntrospection {
+                    name: name.clone(),
+                    version: String::new(),
+                    canonical: None,
+                    dependencies: None,
+                    resource_ids,
+                    canonical_urls,
+                    resource_counts_by_type,
+                }
+            })
+            .collect()
+    }
+
+    fn record_answer(&self, canonical_url: &str, provider: usize, resources: &[Arc<Value>]) {
+        let Some(answered_by) = &self.answered_by else {
+            return;
+        };
+        let field = |name: &str| -> Vec<String> {
+            resources
+                .iter()
+                .filter_map(|r| r.get(name).and_then(|v| v.as_str()).map(String::from))
+                .collect()
+        };
+        let answer = ProviderAnswer {
+            provider,
+            resource_ids: field("id"),
+            resource_types: field("resourceType"),
+        };
+        answered_by
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .put(canonical_url.to_string(), answer);
     }
 }
 
 #[async_trait]
 impl ConformanceResourceProvider for FallbackConformanceProvider {
     async fn list_by_canonical(&self, canonical_url: &str) -> Result<Vec<Arc<Value>>> {
-        match self.primary.list_by_canonical(canonical_url).await {
-            Ok(primary) if !primary.is_empty() => Ok(primary),
-            Ok(_) => self.fallback.list_by_canonical(canonical_url).await,
-            Err(primary_err) => match self.fallback.list_by_canonical(canonical_url).await {
-                Ok(v) => Ok(v),
-                Err(_) => Err(primary_err),
-            },
+        let mut first_error = None;
+        let mut last_failed = false;
+
+        for (index, (_, provider)) in self.providers.iter().enumerate() {
+            match provider.list_by_canonical(canonical_url).await {
+                Ok(resources) if !resources.is_empty() => {
+                    self.record_answer(canonical_url, index, &resources);
+                    return Ok(resources);
+                }
+                Ok(_) => last_failed = false,
+                Err(err) => {
+                    first_error.get_or_insert(err);
+                    last_failed = true;
+                }
+            }
+        }
+
+        match first_error {
+            Some(err) if last_failed => Err(err),
+            _ => Ok(Vec::new()),
         }
     }
 
@@ -90,25 +246,29 @@ impl ConformanceResourceProvider for FallbackConformanceProvider {
         canonical_url: &str,
         version: &str,
     ) -> Result<Option<Arc<Value>>> {
-        match self
-            .primary
-            .get_by_canonical_and_version(canonical_url, version)
-            .await
-        {
-            Ok(Some(resource)) => Ok(Some(resource)),
-            Ok(None) => {
-                self.fallback
-                    .get_by_canonical_and_version(canonical_url, version)
-                    .await
-            }
-            Err(primary_err) => match self
-                .fallback
+        let mut first_error = None;
+        let mut last_failed = false;
+
+        for (index, (_, provider)) in self.providers.iter().enumerate() {
+            match provider
                 .get_by_canonical_and_version(canonical_url, version)
                 .await
             {
-                Ok(v) => Ok(v),
-                Err(_) => Err(primary_err),
-            },
+                Ok(Some(resource)) => {
+                    self.record_answer(canonical_url, index, std::slice::from_ref(&resource));
+                    return Ok(Some(resource));
+                }
+                Ok(None) => last_failed = false,
+                Err(err) => {
+                    first_error.get_or_insert(err);
+                    last_failed = true;
+                }
+            }
+        }
+
+        match first_error {
+            Some(err) if last_failed => Err(err),
+            _ => Ok(None),
         }
     }
 }
@@ -410,6 +570,51 @@ pub trait FhirContext: Send + Sync {
         self.get_structure_definition(&canonical_url)
     }
 
+    /// Follow `baseDefinition` links from a StructureDefinition up to its root
+    ///
+    /// Returns the chain starting with `canonical_url` itself and ending with the
+    /// definition that has no base (e.g. `Resource` or `Element`). Base URLs may carry a
+    /// `|version` suffix. Fails if any link in the chain cannot be resolved or loops.
+    fn base_chain(&self, canonical_url: &str) -> Result<Vec<Arc<StructureDefinition>>> {
+        let mut chain: Vec<Arc<StructureDefinition>> = Vec::new();
+        let mut next = Some(canonical_url.to_string());
+
+        while let Some(url) = next.take() {
+            let sd = match url.split_once('|') {
+                Some((base_url, version)) => self
+                    .get_resource_by_url(base_url, Some(version))?
+                    .map(|resource| {
+                        serde_json::from_value::<StructureDefinition>(Arc::unwrap_or_clone(
+                            resource,
+                        ))
+                    })
+                    .transpose()?
+                    .map(Arc::new),
+                None => self.get_structure_definition(&url)?,
+            };
+
+            let sd = sd.ok_or_else(|| match chain.last() {
+                Some(child) => Error::StructureDefinitionNotFound(format!(
+                    "{} (baseDefinition of {})",
+                    url, child.url
+                )),
+                None => Error::StructureDefinitionNotFound(url.clone()),
+            })?;
+
+            if chain.iter().any(|seen| seen.url == sd.url) {
+                return Err(Error::InvalidStructureDefinition(format!(
+                    "Circular baseDefinition chain at {}",
+                    sd.url
+                )));
+            }
+
+            next = sd.base_definition.clone();
+            chain.push(sd);
+        }
+
+        Ok(chain)
+    }
+
     /// Get a StructureDefinition from a resource (checks meta.profile or resourceType)
     fn get_structure_definition_from_resource(
         &self,
@@ -683,12 +888,24 @@ pub struct LockedPackage {
 impl PackageLock {
     /// Create a new lock file from loaded packages
     pub fn from_packages(root_name: &str, root_version: &str, packages: &[FhirPackage]) -> Self {
-        let packages: Vec<LockedPackage> = packages
-            .iter()
-            .map(|pkg| LockedPackage {
-                name: pkg.manifest.name.clone(),
-                version: pkg.manifest.version.clone(),
-                canonical: pkg.manifest.canonical.clone(),
+        Self::from_manifests(
+            root_name,
+            root_version,
+            packages.iter().map(|pkg| &pkg.manifest),
+        )
+    }
+
+    fn from_manifests<'a>(
+        root_name: &str,
+        root_version: &str,
+        manifests: impl IntoIterator<Item = &'a PackageManifest>,
+    ) -> Self {
+        let packages: Vec<LockedPackage> = manifests
+            .into_iter()
+            .map(|manifest| LockedPackage {
+                name: manifest.name.clone(),
+                version: manifest.version.clone(),
+                canonical: manifest.canonical.clone(),
             })
             .collect();
 
@@ -748,152 +965,371 @@ impl PackageLock {
     }
 }
 
+/// How to pick between packages that define the same canonical URL
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConflictPolicy {
+    /// The first package (in load order) defining a canonical owns it; later ones are ignored.
+    FirstWins,
+    /// The last package (in load order) defining a canonical replaces earlier definitions.
+    LastWins,
+    /// All versions are kept and unversioned lookups return the highest one. Identical
+    /// versions defined by several packages resolve to the later package.
+    #[default]
+    HighestVersion,
+}
+
 /// Default implementation using pinned packages (exact versions)
+///
+/// In eager mode (the default) the context is built from parsed [`FhirPackage`]s and every
+/// resource with a canonical URL is copied into the index at construction. In deferred mode it
+/// is built from [`RawFhirPackage`]s: only the resource headers are read up front, and a
+/// resource's JSON is parsed the first time it is looked up.
+///
+/// All indices are built at construction and every lookup takes `&self`: copied resources
+/// are memoized per index entry in a `OnceLock`, and parsed StructureDefinitions are kept in a
+/// bounded LRU whose lock is never held while parsing. A context can therefore be shared across
+/// threads and async tasks behind an `Arc`. Only [`Self::add_resource`] mutates the index and
+/// needs `&mut self`.
 pub struct DefaultFhirContext {
-    _packages: Vec<Arc<FhirPackage>>,
-    resources_by_canonical: HashMap<String, BTreeMap<VersionKey, Arc<Value>>>,
+    _packages: Vec<IndexedPackage>,
+    resources_by_canonical: HashMap<String, BTreeMap<VersionKey, IndexedResource>>,
+    /// Lowercased canonical URL -> key in `resources_by_canonical`, for case-insensitive lookups
+    canonical_by_lowercase: HashMap<String, String>,
     structure_definition_cache: Mutex<LruCache<String, Arc<StructureDefinition>>>,
+    counters: LoadCounters,
 }
 
-impl DefaultFhirContext {
-    /// Create a new context with a loaded package
-    pub fn new(package: FhirPackage) -> Self {
-        Self::from_packages(vec![package])
-    }
+/// Resources taken out of their packages so far
+#[derive(Default)]
+struct LoadCounters {
+    materialized: AtomicUsize,
+    /// Raw resources whose JSON was parsed
+    parsed: AtomicUsize,
+}
 
-    /// Create a context from already loaded packages (no client required)
-    pub fn from_packages(packages: Vec<FhirPackage>) -> Self {
-        let packages: Vec<Arc<FhirPackage>> = packages.into_iter().map(Arc::new).collect();
-        let mut resources_by_canonical: HashMap<String, BTreeMap<VersionKey, Arc<Value>>> =
-            HashMap::new();
+/// A package indexed by a [`DefaultFhirContext`]
+#[derive(Clone)]
+enum IndexedPackage {
+    Parsed(Arc<FhirPackage>),
+    Raw(Arc<RawFhirPackage>),
+}
 
-        for package in &packages {
-            // Index all resources (conformance + examples)
-            for resource in package.resources.iter().chain(package.examples.iter()) {
-                if let Some(canonical_url) = resource.get("url").and_then(|v: &Value| v.as_str()) {
-                    let version = resource
-                        .get("version")
-                        .and_then(|v: &Value| v.as_str())
-                        .unwrap_or(&package.manifest.version);
+/// What the canonical index needs to know about a package resource
+struct ResourceSummary<'a> {
+    location: ResourceLocation,
+    resource_type: Option<&'a str>,
+    id: Option<&'a str>,
+    url: Option<&'a str>,
+    version: Option<&'a str>,
+    algorithm: Option<VersionAlgorithm>,
+}
 
-                    let algorithm = extract_version_algorithm(resource);
+impl IndexedPackage {
+    fn manifest(&self) -> &PackageManifest {
+        match self {
+            Self::Parsed(package) => &package.manifest,
+            Self::Raw(package) => &package.manifest,
+        }
+    }
 
-                    resources_by_canonical
-                        .entry(canonical_url.to_string())
-                        .or_default()
-                        .insert(
-                            VersionKey::new(version, algorithm),
-                            Arc::new(resource.clone()),
-                        );
-                }
+    /// Conformance resources followed by examples, read from the parsed resources or from the
+    /// raw resource headers
+    fn summaries(&self) -> Vec<ResourceSummary<'_>> {
+        fn located<'a, T>(
+            conformance: &'a [T],
+            examples: &'a [T],
+        ) -> impl Iterator<Item = (ResourceLocation, &'a T)> {
+            let conformance = conformance
+                .iter()
+                .enumerate()
+                .map(|(i, r)| (ResourceLocation::Conformance(i), r));
+            let examples = examples
+                .iter()
+                .enumerate()
+                .map(|(i, r)| (ResourceLocation::Example(i), r));
+            conformance.chain(examples)
+        }
+
+        match self {
+            Self::Parsed(package) => {
+                located(package.conformance_resources(), package.example_resources())
+                    .map(|(location, resource)| ResourceSummary {
+                        location,
+                        resource_type: resource.get("resourceType").and_then(Value::as_str),
+                        id: resource.get("id").and_then(Value::as_str),
+                        url: resource.get("url").and_then(Value::as_str),
+                        version: resource.get("version").and_then(Value::as_str),
+                        algorithm: extract_version_algorithm(resource),
+                    })
+                    .collect()
             }
+            Self::Raw(package) => {
+                located(package.conformance_resources(), package.example_resources())
+                    .map(|(location, resource)| {
+                        let header = resource.header();
+                        ResourceSummary {
+                            location,
+                            resource_type: header.resource_type.as_deref(),
+                            id: header.id.as_deref(),
+                            url: header.url.as_deref(),
+                            version: header.version.as_deref(),
+                            algorithm: header
+                                .version_algorithm()
+                                .and_then(VersionAlgorithm::from_str),
+                        }
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// A resource in the canonical index, materialized from its package on first access.
+struct IndexedResource {
+    value: OnceLock<Arc<Value>>,
+    source: Option<(IndexedPackage, ResourceLocation)>,
+}
+
+#[derive(Clone, Copy)]
+enum ResourceLocation {
+    Conformance(usize),
+    Example(usize),
+}
+
+impl IndexedResource {
+    fn loaded(value: Arc<Value>) -> Self {
+        Self {
+            value: OnceLock::from(value),
+            source: None,
         }
+    }
 
+    fn deferred(package: IndexedPackage, location: ResourceLocation) -> Self {
         Self {
-            _packages: packages,
-            resources_by_canonical,
-            structure_definition_cache: Mutex::new(LruCache::new(NonZeroUsize::new(4096).unwrap())),
+            value: OnceLock::new(),
+            source: Some((package, location)),
         }
     }
 
+    fn get(&self, counters: &LoadCounters) -> Arc<Value> {
+        self.value
+            .get_or_init(|| {
+                let (package, location) = self
+                    .source
+                    .as_ref()
+                    .expect("deferred resources always have a source");
+                let resource = match (package, *location) {
+                    (IndexedPackage::Parsed(package), ResourceLocation::Conformance(i)) => {
+                        package.conformance_resources()[i].clone()
+                    }
+                    (IndexedPackage::Parsed(package), ResourceLocation::Example(i)) => {
+                        package.example_resources()[i].clone()
+                    }
+                    (IndexedPackage::Raw(package), location) => {
+                        let raw = match location {
+                            ResourceLocation::Conformance(i) => &package.conformance_resources()[i],
+                            ResourceLocation::Example(i) => &package.example_resources()[i],
+                        };
+                        counters.parsed.fetch_add(1, AtomicOrdering::Relaxed);
+                        // Reading the header already validated the JSON.
+                        raw.parse().expect("raw package resources are valid JSON")
+                    }
+                };
+                counters.materialized.fetch_add(1, AtomicOrdering::Relaxed);
+                Arc::new(resource)
+            })
+            .clone()
+    }
+}
+
+impl DefaultFhirContext {
+    /// Create a new context with a loaded package
+    pub fn new(package: FhirPackage) -> Self {
+        Self::from_packages(vec![package], ConflictPolicy::default())
+    }
+
+    /// Create a context from already loaded packages (no client required)
+    ///
+    /// Packages are indexed in order; `policy` decides which definition wins when several
+    /// packages share a canonical URL.
+    pub fn from_packages(packages: Vec<FhirPackage>, policy: ConflictPolicy) -> Self {
+        Self::from_arc_packages(packages.into_iter().map(Arc::new).collect(), policy)
+    }
+
     /// Create a context from already loaded, shared packages.
     ///
     /// This avoids cloning large `FhirPackage` instances when the caller needs
     /// to reuse the same package set elsewhere (e.g., DB installation, diagnostics).
-    pub fn from_arc_packages(packages: Vec<Arc<FhirPackage>>) -> Self {
-        let mut resources_by_canonical: HashMap<String, BTreeMap<VersionKey, Arc<Value>>> =
+    pub fn from_arc_packages(packages: Vec<Arc<FhirPackage>>, policy: ConflictPolicy) -> Self {
+        let packages = packages.into_iter().map(IndexedPackage::Parsed).collect();
+        Self::index_packages(packages, policy, false)
+    }
+
+    /// Create a deferred context from unparsed packages
+    ///
+    /// Only resource headers are read here; each resource is parsed when first resolved by
+    /// canonical URL.
+    pub fn from_packages_deferred(packages: Vec<RawFhirPackage>, policy: ConflictPolicy) -> Self {
+        Self::from_arc_packages_deferred(packages.into_iter().map(Arc::new).collect(), policy)
+    }
+
+    /// Create a deferred context from unparsed, shared packages
+    pub fn from_arc_packages_deferred(
+        packages: Vec<Arc<RawFhirPackage>>,
+        policy: ConflictPolicy,
+    ) -> Self {
+        let packages = packages.into_iter().map(IndexedPackage::Raw).collect();
+        Self::index_packages(packages, policy, true)
+    }
+
+    fn index_packages(
+        packages: Vec<IndexedPackage>,
+        policy: ConflictPolicy,
+        deferred: bool,
+    ) -> Self {
+        let mut resources_by_canonical: HashMap<String, BTreeMap<VersionKey, IndexedResource>> =
             HashMap::new();
 
         for package in &packages {
             // Index all resources (conformance + examples)
-            for resource in package.resources.iter().chain(package.examples.iter()) {
-                if let Some(canonical_url) = resource.get("url").and_then(|v: &Value| v.as_str()) {
-                    let version = resource
-                        .get("version")
-                        .and_then(|v: &Value| v.as_str())
-                        .unwrap_or(&package.manifest.version);
+            let mut package_index: HashMap<String, BTreeMap<VersionKey, IndexedResource>> =
+                HashMap::new();
 
-                    let algorithm = extract_version_algorithm(resource);
+            for summary in package.summaries() {
+                if let Some(canonical_url) = summary.url {
+                    let version = summary.version.unwrap_or(&package.manifest().version);
 
-                    resources_by_canonical
+                    package_index
                         .entry(canonical_url.to_string())
                         .or_default()
                         .insert(
-                            VersionKey::new(version, algorithm),
-                            Arc::new(resource.clone()),
+                            VersionKey::new(version, summary.algorithm),
+                            IndexedResource::deferred(package.clone(), summary.location),
                         );
                 }
             }
+
+            for (canonical_url, versions) in package_index {
+                match policy {
+                    ConflictPolicy::FirstWins => {
+                        resources_by_canonical
+                            .entry(canonical_url)
+                            .or_insert(versions);
+                    }
+                    ConflictPolicy::LastWins => {
+                        resources_by_canonical.insert(canonical_url, versions);
+                    }
+                    ConflictPolicy::HighestVersion => {
+                        resources_by_canonical
+                            .entry(canonical_url)
+                            .or_default()
+                            .extend(versions);
+                    }
+                }
+            }
         }
 
-        Self {
+        let canonical_by_lowercase = resources_by_canonical
+            .keys()
+            .map(|url| (url.to_ascii_lowercase(), url.clone()))
+            .collect();
+
+        let context = Self {
             _packages: packages,
             resources_by_canonical,
+            canonical_by_lowercase,
             structure_definition_cache: Mutex::new(LruCache::new(NonZeroUsize::new(4096).unwrap())),
+            counters: LoadCounters::default(),
+        };
+
+        if !deferred {
+            for entry in context
+                .resources_by_canonical
+                .values()
+                .flat_map(|v| v.values())
+            {
+                entry.get(&context.counters);
+            }
         }
+
+        context
+    }
+
+    /// Manifest of the package a resolved resource was indexed from
+    ///
+    /// Returns `None` if the canonical (or requested version) is unknown, or the resource
+    /// was added directly via [`Self::add_resource`].
+    pub fn source_package(
+        &self,
+        canonical_url: &str,
+        version: Option<&str>,
+    ) -> Option<&PackageManifest> {
+        let versions = self.resources_by_canonical.get(canonical_url)?;
+        let entry = select_from_version_index(versions, version)?;
+        entry.source.as_ref().map(|(package, _)| package.manifest())
+    }
+
+    /// Number of indexed resources copied out of their packages so far
+    ///
+    /// Equals the number of canonical resources in eager mode; grows on demand in deferred mode.
+    pub fn materialized_resource_count(&self) -> usize {
+        self.counters.materialized.load(AtomicOrdering::Relaxed)
+    }
+
+    /// Number of resources parsed from raw package JSON so far
+    ///
+    /// Always zero in eager mode, where packages arrive parsed.
+    pub fn parsed_resource_count(&self) -> usize {
+        self.counters.parsed.load(AtomicOrdering::Relaxed)
     }
 
     /// Expose loaded packages and indexed resources for diagnostics
     pub fn package_introspection(&self) -> Vec<PackageIntrospection> {
         self._packages
             .iter()
-            .map(|pkg| {
+            .map(|indexed| {
+                let manifest = indexed.manifest();
+                let summaries = indexed.summaries();
+
                 // Collect resource IDs
-                let mut resource_ids: Vec<String> = pkg
-                    .resources
+                let mut resource_ids: Vec<String> = summaries
                     .iter()
-                    .chain(pkg.examples.iter())
-                    .filter_map(|r: &Value| {
-                        r.get("id")
-                            .and_then(|v: &Value| v.as_str())
-                            .map(String::from)
-                    })
+                    .filter_map(|r| r.id.map(String::from))
                     .collect();
                 resource_ids.sort();
                 resource_ids.dedup();
 
                 // Collect canonical URLs
-                let mut canonical_urls: Vec<String> = pkg
-                    .resources
+                let mut canonical_urls: Vec<String> = summaries
                     .iter()
-                    .chain(pkg.examples.iter())
-                    .filter_map(|r: &Value| {
-                        r.get("url")
-                            .and_then(|v: &Value| v.as_str())
-                            .map(String::from)
-                    })
+                    .filter_map(|r| r.url.map(String::from))
                     .collect();
                 canonical_urls.sort();
                 canonical_urls.dedup();
 
                 // Count resources by type
                 let mut resource_counts_by_type: HashMap<String, usize> = HashMap::new();
-                for resource in pkg.resources.iter().chain(pkg.examples.iter()) {
-                    if let Some(resource_type) = resource
-                        .get("resourceType")
-                        .and_then(|v: &Value| v.as_str())
-                    {
-                        *resource_counts_by_type
-                            .entry(resource_type.to_string())
-                            .or_insert(0) += 1;
-                    }
+                for resource_type in summaries.iter().filter_map(|r| r.resource_type) {
+                    *resource_counts_by_type
+                        .entry(resource_type.to_string())
+                        .or_insert(0) += 1;
                 }
 
                 // Convert dependencies HashMap to serde_json::Map
-                let dependencies = if pkg.manifest.dependencies.is_empty() {
+                let dependencies = if manifest.dependencies.is_empty() {
                     None
                 } else {
                     let mut map = serde_json::Map::new();
-                    for (k, v) in &pkg.manifest.dependencies {
+                    for (k, v) in &manifest.dependencies {
                         map.insert(k.clone(), serde_json::Value::String(v.clone()));
                     }
                     Some(map)
                 };
 
                 PackageIntrospection {
-                    name: pkg.manifest.name.clone(),
-                    version: pkg.manifest.version.clone(),
-                    canonical: pkg.manifest.canonical.clone(),
+                    name: manifest.name.clone(),
+                    version: manifest.version.clone(),
+                    canonical: manifest.canonical.clone(),
                     dependencies,
                     resource_ids,
                     canonical_urls,
@@ -904,6 +1340,8 @@ impl DefaultFhirContext {
     }
 
     /// Return the latest version of all StructureDefinitions known to this context.
+    ///
+    /// In deferred mode this copies the latest version of every indexed resource.
     pub fn all_structure_definitions(&self) -> Vec<Arc<Value>> {
         self.resources_by_canonical
             .keys()
@@ -940,7 +1378,7 @@ impl DefaultFhirContext {
         let packages = loader
             .load_package_with_dependencies(package_name, Some(package_version))
             .await?;
-        Ok(Self::from_packages(packages))
+        Ok(Self::from_packages(packages, ConflictPolicy::default()))
     }
 
     /// Create from async registry client and package name with optional version
@@ -955,7 +1393,7 @@ impl DefaultFhirContext {
         let packages = loader
             .load_package_with_dependencies(package_name, version)
             .await?;
-        Ok(Self::from_packages(packages))
+        Ok(Self::from_packages(packages, ConflictPolicy::default()))
     }
 
     /// Create from FHIR version (R4, R4B, or R5) using an async package loader
@@ -978,11 +1416,48 @@ impl DefaultFhirContext {
         loader: Option<Arc<dyn PackageLoader>>,
         fhir_version: &str,
     ) -> Result<Self> {
-        let loader = match loader {
-            Some(loader) => loader,
-            None => crate::loader::default_package_loader()?,
-        };
+        let packages = Self::load_core_packages(loader, fhir_version).await?;
+        Ok(Self::from_packages(packages, ConflictPolicy::default()))
+    }
+
+    /// Like [`Self::from_fhir_version_async`], but builds a deferred context
+    ///
+    /// The core packages are loaded unparsed (see [`PackageLoader::load_raw_package_with_dependencies`])
+    /// and each resource is parsed on first lookup. Useful for tests and tools that resolve
+    /// only a few definitions.
+    pub async fn from_fhir_version_deferred_async(
+        loader: Option<Arc<dyn PackageLoader>>,
+        fhir_version: &str,
+    ) -> Result<Self> {
+        let (package_name, package_version) = Self::core_package(fhir_version)?;
+        let packages = Self::loader_or_default(loader)?
+            .load_raw_package_with_dependencies(package_name, Some(package_version))
+            .await?;
+        Ok(Self::from_packages_deferred(
+            packages,
+            ConflictPolicy::default(),
+        ))
+    }
 
+    async fn load_core_packages(
+        loader: Option<Arc<dyn PackageLoader>>,
+        fhir_version: &str,
+    ) -> Result<Vec<FhirPackage>> {
+        let (package_name, package_version) = Self::core_package(fhir_version)?;
+        Self::loader_or_default(loader)?
+            .load_package_with_dependencies(package_name, Some(package_version))
+            .await
+    }
+
+    fn loader_or_default(loader: Option<Arc<dyn PackageLoader>>) -> Result<Arc<dyn PackageLoader>> {
+        match loader {
+            Some(loader) => Ok(loader),
+            None => crate::loader::default_package_loader(),
+        }
+    }
+
+    /// Core package name and version for a FHIR version
+    fn core_package(fhir_version: &str) -> Result<(&'static str, &'static str)> {
         let (package_name, package_version) = match fhir_version {
             "R4" => ("hl7.fhir.r4.core", "4.0.1"),
             "R4B" => ("hl7.fhir.r4b.core", "4.3.0"),
@@ -995,7 +1470,7 @@ impl DefaultFhirContext {
             }
         };
 
-        Self::from_package_async(loader, package_name, package_version).await
+        Ok((package_name, package_version))
     }
 
     /// Create from lock file using an async package loader
@@ -1035,7 +1510,7 @@ impl DefaultFhirContext {
         // Validate that all loaded packages match the lock file
         lock.validate_packages(&packages)?;
 
-        Ok(Self::from_packages(packages))
+        Ok(Self::from_packages(packages, ConflictPolicy::default()))
     }
 
     /// Create from lock file path using async registry client
@@ -1067,19 +1542,11 @@ impl DefaultFhirContext {
     /// # }
     /// ```
     pub fn generate_lock_file(&self, root_name: &str, root_version: &str) -> PackageLock {
-        let packages: Vec<FhirPackage> = self
-            ._packages
-            .iter()
-            .map(|arc_pkg| {
-                // Clone the package data
-                FhirPackage::new(
-                    arc_pkg.manifest.clone(),
-                    arc_pkg.resources.clone(),
-                    arc_pkg.examples.clone(),
-                )
-            })
-            .collect();
-        PackageLock::from_packages(root_name, root_version, &packages)
+        PackageLock::from_manifests(
+            root_name,
+            root_version,
+            self._packages.iter().map(IndexedPackage::manifest),
+        )
     }
 
     /// Insert an additional resource into this context's canonical index.
@@ -1097,30 +1564,29 @@ impl DefaultFhirContext {
             .unwrap_or("0")
             .to_string();
         let algorithm = extract_version_algorithm(&resource);
+        self.canonical_by_lowercase
+            .entry(canonical_url.to_ascii_lowercase())
+            .or_insert_with(|| canonical_url.clone());
         self.resources_by_canonical
             .entry(canonical_url.clone())
             .or_default()
-            .insert(VersionKey::new(&version_str, algorithm), Arc::new(resource));
-        // Invalidate the SD cache for this canonical URL so the new resource is picked up.
-        let mut cache = self
-            .structure_definition_cache
-            .lock()
-            .unwrap_or_else(|e| e.into_inner());
-        cache.pop(&canonical_url);
+            .insert(
+                VersionKey::new(&version_str, algorithm),
+                IndexedResource::loaded(Arc::new(resource)),
+            );
+        self.counters
+            .materialized
+            .fetch_add(1, AtomicOrdering::Relaxed);
+        // Drop the parsed StructureDefinition so the new resource is picked up.
+        self.structure_definition_cache
+            .get_mut()
+            .unwrap_or_else(|e| e.into_inner())
+            .pop(&canonical_url);
     }
 
     fn get_from_index(&self, canonical_url: &str, version: Option<&str>) -> Option<Arc<Value>> {
-        self.get_from_index_ref(canonical_url, version)
-            .map(Arc::clone)
-    }
-
-    fn get_from_index_ref(
-        &self,
-        canonical_url: &str,
-        version: Option<&str>,
-    ) -> Option<&Arc<Value>> {
         let versions = self.resources_by_canonical.get(canonical_url)?;
-        select_from_version_index(versions, version)
+        select_from_version_index(versions, version).map(|entry| entry.get(&self.counters))
     }
 }
 
@@ -1151,17 +1617,17 @@ impl FhirContext for DefaultFhirContext {
             return Ok(None);
         };
         let sd: StructureDefinition = serde_json::from_value(Arc::unwrap_or_clone(resource))?;
-        let sd = Arc::new(sd);
-
-        {
-            let mut cache = self
-                .structure_definition_cache
-                .lock()
-                .unwrap_or_else(|e| e.into_inner());
-            cache.put(canonical_url.to_string(), sd.clone());
-        }
 
-        Ok(Some(sd))
+        // A concurrent lookup may have parsed the same resource first; keep its copy.
+        let mut cache = self
+            .structure_definition_cache
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        Ok(Some(
+            cache
+                .get_or_insert(canonical_url.to_string(), || Arc::new(sd))
+                .clone(),
+        ))
     }
 
     fn get_core_structure_definition_by_type(
@@ -1180,7 +1646,10 @@ impl ConformanceResourceProvider for DefaultFhirContext {
             return Ok(vec![]);
         };
 
-        Ok(versions.values().cloned().collect())
+        Ok(versions
+            .values()
+            .map(|entry| entry.get(&self.counters))
+            .collect())
     }
 
     async fn get_by_canonical_and_version(
@@ -1190,6 +1659,19 @@ impl ConformanceResourceProvider for DefaultFhirContext {
     ) -> Result<Option<Arc<Value>>> {
         Ok(self.get_from_index(canonical_url, Some(version)))
     }
+
+    async fn resolve_canonical(&self, canonical: &str) -> Result<Option<Arc<Value>>> {
+        let (canonical_url, version) = split_canonical(canonical);
+        let versions = self.resources_by_canonical.get(canonical_url).or_else(|| {
+            self.canonical_by_lowercase
+                .get(&canonical_url.to_ascii_lowercase())
+                .and_then(|url| self.resources_by_canonical.get(url))
+        });
+
+        Ok(versions
+            .and_then(|versions| select_from_version_index(versions, version))
+            .map(|entry| entry.get(&self.counters)))
+    }
 }
 
 impl FhirContext for FlexibleFhirContext {
@@ -1511,6 +1993,152 @@ mod tests {
         FhirPackage::new(manifest, resources, vec![])
     }
 
+    fn make_chain_sd(url: &str, type_: &str, base: Option<&str>) -> Value {
+        let name = url.rsplit('/').next().unwrap();
+        let mut sd = json!({
+            "resourceType": "StructureDefinition",
+            "id": name,
+            "url": url,
+            "name": name,
+            "status": "active",
+            "kind": "resource",
+            "abstract": false,
+            "type": type_
+        });
+        if let Some(base) = base {
+            sd["baseDefinition"] = json!(base);
+            sd["derivation"] = json!(if type_ == name {
+                "specialization"
+            } else {
+                "constraint"
+            });
+        }
+        sd
+    }
+
+    const US_CORE_PATIENT: &str = "http://hl7.org/fhir/us/core/StructureDefinition/us-core-patient";
+
+    fn create_chain_context(include_domain_resource: bool) -> DefaultFhirContext {
+        let mut context = DefaultFhirContext::from_packages(vec![], ConflictPolicy::default());
+        context.add_resource(make_chain_sd(
+            US_CORE_PATIENT,
+            "Patient",
+            Some("http://hl7.org/fhir/StructureDefinition/Patient"),
+        ));
+        context.add_resource(make_chain_sd(
+            "http://hl7.org/fhir/StructureDefinition/Patient",
+            "Patient",
+            Some("http://hl7.org/fhir/StructureDefinition/DomainResource"),
+        ));
+        if include_domain_resource {
+            context.add_resource(make_chain_sd(
+                "http://hl7.org/fhir/StructureDefinition/DomainResource",
+                "DomainResource",
+                Some("http://hl7.org/fhir/StructureDefinition/Resource"),
+            ));
+        }
+        context.add_resource(make_chain_sd(
+            "http://hl7.org/fhir/StructureDefinition/Resource",
+            "Resource",
+            None,
+        ));
+        context
+    }
+
+    #[test]
+    fn base_chain_walks_profile_to_root() {
+        let context = create_chain_context(true);
+
+        let chain = context.base_chain(US_CORE_PATIENT).unwrap();
+        let names: Vec<&str> = chain.iter().map(|sd| sd.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["us-core-patient", "Patient", "DomainResource", "Resource"]
+        );
+
+        // Starting from a core type already in the context works the same way
+        let chain = context
+            .base_chain("http://hl7.org/fhir/StructureDefinition/Patient")
+            .unwrap();
+        assert_eq!(chain.len(), 3);
+        assert_eq!(chain[0].name, "Patient");
+    }
+
+    #[test]
+    fn base_chain_fails_on_unresolvable_link() {
+        let context = create_chain_context(false);
+
+        let err = context.base_chain(US_CORE_PATIENT).unwrap_err();
+        match err {
+            Error::StructureDefinitionNotFound(msg) => {
+                assert!(msg.contains("DomainResource"), "{msg}");
+                assert!(msg.contains("StructureDefinition/Patient"), "{msg}");
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+
+        assert!(matches!(
+            context.base_chain("http://example.org/StructureDefinition/missing"),
+            Err(Error::StructureDefinitionNotFound(_))
+        ));
+    }
+
+    fn create_large_mock_package() -> FhirPackage {
+        let mut package = create_mock_package();
+        for i in 0..50 {
+            package.add_resource(json!({
+                "resourceType": "StructureDefinition",
+                "id": format!("Profile{}", i),
+                "url": format!("http://example.org/StructureDefinition/Profile{}", i),
+                "name": format!("Profile{}", i),
+                "status": "active",
+                "kind": "resource",
+                "abstract": false,
+                "type": "Patient",
+                "baseDefinition": "http://hl7.org/fhir/StructureDefinition/Patient"
+            }));
+        }
+        package
+    }
+
+    #[test]
+    fn deferred_context_parses_only_resolved_resources() {
+        let eager = DefaultFhirContext::from_packages(
+            vec![create_large_mock_package()],
+            ConflictPolicy::default(),
+        );
+        let deferred = DefaultFhirContext::from_packages_deferred(
+            vec![RawFhirPackage::from_package(&create_large_mock_package())],
+            ConflictPolicy::default(),
+        );
+
+        assert_eq!(eager.materialized_resource_count(), 53);
+        assert_eq!(eager.parsed_resource_count(), 0);
+        assert_eq!(deferred.parsed_resource_count(), 0);
+
+        // Introspection reads the resource headers only
+        assert_eq!(deferred.package_introspection()[0].canonical_urls.len(), 53);
+        assert_eq!(deferred.parsed_resource_count(), 0);
+
+        let url = "http://example.org/StructureDefinition/Profile7";
+        let from_deferred = FhirContext::get_structure_definition(&deferred, url)
+            .unwrap()
+            .unwrap();
+        let from_eager = FhirContext::get_structure_definition(&eager, url)
+            .unwrap()
+            .unwrap();
+        assert_eq!(from_deferred.name, from_eager.name);
+        assert_eq!(deferred.parsed_resource_count(), 1);
+
+        // Repeated lookups reuse the parsed resource
+        deferred.get_resource_by_url(url, None).unwrap().unwrap();
+        assert_eq!(deferred.parsed_resource_count(), 1);
+
+        // Enumerating all definitions forces a full load
+        assert_eq!(deferred.all_structure_definitions().len(), 53);
+        assert_eq!(deferred.parsed_resource_count(), 53);
+    }
+
     #[test]
     fn test_get_structure_definition_by_url() {
         let package = create_mock_package();
@@ -1575,12 +2203,112 @@ mod tests {
         FhirPackage::new(manifest, resources, vec![])
     }
 
+    const SHARED_VALUE_SET: &str = "http://example.org/ValueSet/shared";
+
+    fn make_value_set_pkg(name: &str, vs_version: &str) -> FhirPackage {
+        let package = make_pkg(name, "1.0.0", "1.0.0");
+        FhirPackage::new(
+            package.manifest,
+            vec![json!({
+                "resourceType": "ValueSet",
+                "url": SHARED_VALUE_SET,
+                "version": vs_version,
+                "status": "active"
+            })],
+            vec![],
+        )
+    }
+
+    fn resolve_shared(context: &DefaultFhirContext) -> (String, String) {
+        let resource = context
+            .get_resource_by_url(SHARED_VALUE_SET, None)
+            .unwrap()
+            .unwrap();
+        let package = context.source_package(SHARED_VALUE_SET, None).unwrap();
+        (
+            resource["version"].as_str().unwrap().to_string(),
+            package.name.clone(),
+        )
+    }
+
+    #[test]
+    fn conflict_policy_selects_expected_package() {
+        let packages = || {
+            vec![
+                make_value_set_pkg("ig.newer", "2.0.0"),
+                make_value_set_pkg("ig.older", "1.0.0"),
+            ]
+        };
+
+        let first = DefaultFhirContext::from_packages(packages(), ConflictPolicy::FirstWins);
+        assert_eq!(
+            resolve_shared(&first),
+            ("2.0.0".to_string(), "ig.newer".to_string())
+        );
+        assert!(first
+            .get_resource_by_url(SHARED_VALUE_SET, Some("1.0.0"))
+            .unwrap()
+            .is_none());
+
+        let last = DefaultFhirContext::from_packages(packages(), ConflictPolicy::LastWins);
+        assert_eq!(
+            resolve_shared(&last),
+            ("1.0.0".to_string(), "ig.older".to_string())
+        );
+        assert!(last
+            .get_resource_by_url(SHARED_VALUE_SET, Some("2.0.0"))
+            .unwrap()
+            .is_none());
+
+        let highest = DefaultFhirContext::from_packages(packages(), ConflictPolicy::HighestVersion);
+        assert_eq!(
+            resolve_shared(&highest),
+            ("2.0.0".to_string(), "ig.newer".to_string())
+        );
+        assert_eq!(
+            highest
+                .source_package(SHARED_VALUE_SET, Some("1.0.0"))
+                .map(|m| m.name.as_str()),
+            Some("ig.older")
+        );
+    }
+
+    #[test]
+    fn highest_version_policy_ignores_load_order() {
+        let context = DefaultFhirContext::from_packages(
+            vec![
+                make_value_set_pkg("ig.older", "1.0.0"),
+                make_value_set_pkg("ig.newer", "2.0.0"),
+            ],
+            ConflictPolicy::HighestVersion,
+        );
+        assert_eq!(
+            resolve_shared(&context),
+            ("2.0.0".to_string(), "ig.newer".to_string())
+        );
+
+        let context = DefaultFhirContext::from_packages(
+            vec![
+                make_value_set_pkg("ig.older", "1.0.0"),
+                make_value_set_pkg("ig.newer", "2.0.0"),
+            ],
+            ConflictPolicy::FirstWins,
+        );
+        assert_eq!(
+            resolve_shared(&context),
+            ("1.0.0".to_string(), "ig.older".to_string())
+        );
+    }
+
     #[test]
     fn prefers_release_over_prerelease_when_latest() {
         let release_pkg = make_pkg("test-release", "1.0.0", "1.0.0");
         let ballot_pkg = make_pkg("test-ballot", "1.0.0-ballot", "1.0.0-ballot");
 
-        let context = DefaultFhirContext::from_packages(vec![ballot_pkg, release_pkg]);
+        let context = DefaultFhirContext::from_packages(
+            vec![ballot_pkg, release_pkg],
+            ConflictPolicy::default(),
+        );
         let sd = FhirContext::get_structure_definition(
             &context,
             "http://hl7.org/fhir/StructureDefinition/Patient",
@@ -1831,6 +2559,136 @@ mod tests {
         }
     }
 
+    fn value_set_version(url: &str, version: &str) -> Value {
+        json!({
+            "resourceType": "ValueSet",
+            "url": url,
+            "version": version,
+            "status": "active"
+        })
+    }
+
+    fn resolved_version(resource: Option<Arc<Value>>) -> Option<String> {
+        resource.and_then(|r| r.get("version").and_then(|v| v.as_str()).map(String::from))
+    }
+
+    #[test]
+    fn resolve_canonical_with_version_suffix() {
+        let url = "http://hl7.org/fhir/ValueSet/x";
+        let mut context = DefaultFhirContext::from_packages(vec![], ConflictPolicy::default());
+        for version in ["1.0.0", "2.0.0", "1.5.0"] {
+            context.add_resource(value_set_version(url, version));
+        }
+
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let exact = rt
+            .block_on(context.resolve_canonical("http://hl7.org/fhir/ValueSet/x|2.0.0"))
+            .unwrap();
+        assert_eq!(resolved_version(exact).as_deref(), Some("2.0.0"));
+
+        let older = rt
+            .block_on(context.resolve_canonical("http://hl7.org/fhir/ValueSet/x|1.0.0"))
+            .unwrap();
+        assert_eq!(resolved_version(older).as_deref(), Some("1.0.0"));
+
+        let missing = rt
+            .block_on(context.resolve_canonical("http://hl7.org/fhir/ValueSet/x|3.0.0"))
+            .unwrap();
+        assert!(missing.is_none());
+    }
+
+    #[test]
+    fn resolve_canonical_without_version_returns_newest() {
+        let url = "http://hl7.org/fhir/ValueSet/x";
+        let mut context = DefaultFhirContext::from_packages(vec![], ConflictPolicy::default());
+        for version in ["1.0.0", "2.0.0", "1.5.0"] {
+            context.add_resource(value_set_version(url, version));
+        }
+
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let newest = rt.block_on(context.resolve_canonical(url)).unwrap();
+        assert_eq!(resolved_version(newest).as_deref(), Some("2.0.0"));
+
+        let mixed_case = rt
+            .block_on(context.resolve_canonical("http://HL7.org/fhir/ValueSet/x"))
+            .unwrap();
+        assert_eq!(resolved_version(mixed_case).as_deref(), Some("2.0.0"));
+    }
+
+    #[test]
+    fn shared_context_resolves_concurrently() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<DefaultFhirContext>();
+
+        // Deferred, so tasks race to parse the same index entries
+        let context = Arc::new(DefaultFhirContext::from_packages_deferred(
+            vec![RawFhirPackage::from_package(&create_mock_package())],
+            ConflictPolicy::default(),
+        ));
+        let urls = [
+            "http://hl7.org/fhir/StructureDefinition/Patient",
+            "http://hl7.org/fhir/StructureDefinition/Observation",
+            "http://hl7.org/fhir/StructureDefinition/HumanName",
+        ];
+
+        let rt = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(4)
+            .build()
+            .unwrap();
+        let tasks: Vec<_> = (0..64)
+            .map(|i| {
+                let context = context.clone();
+                let url = urls[i % urls.len()];
+                rt.spawn(async move {
+                    let sd = context.get_structure_definition(url).unwrap().unwrap();
+                    assert_eq!(sd.url, url);
+                    let resolved = context
+                        .resolve_canonical(&url.to_uppercase())
+                        .await
+                        .unwrap()
+                        .unwrap();
+                    assert_eq!(resolved["url"], url);
+                    sd
+                })
+            })
+            .collect();
+        let resolved: Vec<_> = rt.block_on(async {
+            let mut resolved = Vec::new();
+            for task in tasks {
+                resolved.push(task.await.unwrap());
+            }
+            resolved
+        });
+
+        // Every task got the single memoized definition for its URL
+        for (i, sd) in resolved.iter().enumerate() {
+            assert!(Arc::ptr_eq(sd, &resolved[i % urls.len()]));
+        }
+        assert_eq!(context.parsed_resource_count(), urls.len());
+    }
+
+    #[test]
+    fn default_resolve_canonical_uses_listed_versions() {
+        let url = "http://example.org/ValueSet/y";
+        let provider = StaticProvider::with(
+            url,
+            vec![
+                value_set_version(url, "2.0.0"),
+                value_set_version(url, "10.0.0"),
+                value_set_version(url, "9.1.0"),
+            ],
+        );
+
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let newest = rt.block_on(provider.resolve_canonical(url)).unwrap();
+        assert_eq!(resolved_version(newest).as_deref(), Some("10.0.0"));
+
+        let pinned = rt
+            .block_on(provider.resolve_canonical(&format!("{}|9.1.0", url)))
+            .unwrap();
+        assert_eq!(resolved_version(pinned).as_deref(), Some("9.1.0"));
+    }
+
     struct FailingProvider;
 
     #[async_trait]
@@ -1851,7 +2709,7 @@ mod tests {
             url,
             vec![json!({"url": url, "version": "2.0.0"})],
         ));
-        let provider = FallbackConformanceProvider::new(primary, fallback);
+        let provider = FallbackConformanceProvider::new(vec![primary, fallback]);
 
         let rt = tokio::runtime::Runtime::new().unwrap();
         let result = rt.block_on(provider.list_by_canonical(url)).unwrap();
@@ -1870,7 +2728,7 @@ mod tests {
             url,
             vec![json!({"url": url, "version": "2.0.0"})],
         ));
-        let provider = FallbackConformanceProvider::new(primary, fallback);
+        let provider = FallbackConformanceProvider::new(vec![primary, fallback]);
 
         let rt = tokio::runtime::Runtime::new().unwrap();
         let result = rt.block_on(provider.list_by_canonical(url)).unwrap();
@@ -1889,7 +2747,7 @@ mod tests {
             url,
             vec![json!({"url": url, "version": "3.0.0"})],
         ));
-        let provider = FallbackConformanceProvider::new(primary, fallback);
+        let provider = FallbackConformanceProvider::new(vec![primary, fallback]);
 
         let rt = tokio::runtime::Runtime::new().unwrap();
         let result = rt.block_on(provider.list_by_canonical(url)).unwrap();
@@ -1900,7 +2758,7 @@ mod tests {
     fn fallback_provider_returns_primary_error_when_both_fail() {
         let primary: Arc<dyn ConformanceResourceProvider> = Arc::new(FailingProvider);
         let fallback: Arc<dyn ConformanceResourceProvider> = Arc::new(FailingProvider);
-        let provider = FallbackConformanceProvider::new(primary, fallback);
+        let provider = FallbackConformanceProvider::new(vec![primary, fallback]);
 
         let rt = tokio::runtime::Runtime::new().unwrap();
         let result = rt.block_on(provider.list_by_canonical("http://example.org/missing"));
@@ -1916,7 +2774,7 @@ mod tests {
             url,
             vec![json!({"url": url, "version": "4.0.0"})],
         ));
-        let provider = FallbackConformanceProvider::new(primary, fallback);
+        let provider = FallbackConformanceProvider::new(vec![primary, fallback]);
 
         let rt = tokio::runtime::Runtime::new().unwrap();
         let result = rt
@@ -1929,6 +2787,117 @@ mod tests {
         );
     }
 
+    #[test]
+    fn fallback_chain_reports_answering_provider() {
+        let patient = "http://hl7.org/fhir/StructureDefinition/Patient";
+        let custom = "http://example.org/SD/Custom";
+        let memory = Arc::new(StaticProvider::with(
+            custom,
+            vec![json!({"resourceType": "StructureDefinition", "id": "custom", "url": custom})],
+        ));
+        let packages = Arc::new(DefaultFhirContext::new(create_mock_package()));
+        let provider = FallbackConformanceProvider::with_names(vec![
+            (
+                "memory".to_string(),
+                memory as Arc<dyn ConformanceResourceProvider>,
+            ),
+            ("packages".to_string(), packages),
+        ])
+        .with_answer_tracking(16);
+
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let resolved = rt.block_on(provider.resolve_canonical(patient)).unwrap();
+        assert_eq!(resolved.unwrap()["id"], "Patient");
+        assert!(rt
+            .block_on(provider.resolve_canonical(custom))
+            .unwrap()
+            .is_some());
+
+        let introspection = provider.introspection();
+        let names: Vec<&str> = introspection.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["memory", "packages"]);
+        assert_eq!(introspection[0].canonical_urls, vec![custom.to_string()]);
+        assert_eq!(introspection[1].canonical_urls, vec![patient.to_string()]);
+        assert_eq!(introspection[1].resource_ids, vec!["Patient".to_string()]);
+        assert_eq!(
+            introspection[1]
+                .resource_counts_by_type
+                .get("StructureDefinition"),
+            Some(&1)
+        );
+    }
+
+    #[test]
+    fn fallback_chain_tries_every_provider_in_order() {
+        let url = "http://example.org/SD/Third";
+        let provider = FallbackConformanceProvider::new(vec![
+            Arc::new(FailingProvider),
+            Arc::new(StaticProvider::empty()),
+            Arc::new(StaticProvider::with(
+                url,
+                vec![json!({"url": url, "version": "1.0.0"})],
+            )),
+        ])
+        .with_answer_tracking(16);
+
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let result = rt.block_on(provider.list_by_canonical(url)).unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(
+            provider.introspection()[2].canonical_urls,
+            vec![url.to_string()]
+        );
+
+        // An earlier failure is not surfaced when a later provider succeeds, even without a result
+        let missing = rt
+            .block_on(provider.list_by_canonical("http://example.org/missing"))
+            .unwrap();
+        assert!(missing.is_empty());
+    }
+
+    #[test]
+    fn fallback_chain_answer_tracking_is_opt_in_and_bounded() {
+        let urls = [
+            "http://example.org/SD/A",
+            "http://example.org/SD/B",
+            "http://example.org/SD/C",
+        ];
+        let providers = || -> Vec<Arc<dyn ConformanceResourceProvider>> {
+            urls.iter()
+                .map(|url| {
+                    Arc::new(StaticProvider::with(url, vec![json!({"url": url})]))
+                        as Arc<dyn ConformanceResourceProvider>
+                })
+                .collect()
+        };
+        let untracked = FallbackConformanceProvider::new(providers());
+        let tracked = FallbackConformanceProvider::new(providers()).with_answer_tracking(2);
+
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        for url in urls {
+            assert!(rt
+                .block_on(untracked.resolve_canonical(url))
+                .unwrap()
+                .is_some());
+            assert!(rt
+                .block_on(tracked.resolve_canonical(url))
+                .unwrap()
+                .is_some());
+        }
+
+        assert!(untracked
+            .introspection()
+            .iter()
+            .all(|p| p.canonical_urls.is_empty()));
+        // Only the two most recently answered canonicals are kept
+        let answered: Vec<usize> = tracked
+            .introspection()
+            .iter()
+            .map(|p| p.canonical_urls.len())
+            .collect();
+        assert_eq!(answered, vec![0, 1, 1]);
+    }
+
     // --- PackageLock tests ---
 
     #[test]
@@ -2108,6 +3077,34 @@ mod tests {
         assert!(result.is_some());
     }
 
+    #[test]
+    fn add_resource_replaces_cached_structure_definition() {
+        let package = create_mock_package();
+        let mut context = DefaultFhirContext::new(package);
+        let url = "http://example.org/fhir/StructureDefinition/Custom";
+        let custom = |version: &str| {
+            json!({
+                "resourceType": "StructureDefinition",
+                "url": url,
+                "version": version,
+                "name": "Custom",
+                "type": "Custom",
+                "kind": "resource",
+                "abstract": false,
+                "status": "active",
+                "snapshot": {"element": []}
+            })
+        };
+
+        context.add_resource(custom("1.0.0"));
+        let sd = context.get_structure_definition(url).unwrap().unwrap();
+        assert_eq!(sd.version.as_deref(), Some("1.0.0"));
+
+        context.add_resource(custom("2.0.0"));
+        let sd = context.get_structure_definition(url).unwrap().unwrap();
+        assert_eq!(sd.version.as_deref(), Some("2.0.0"));
+    }
+
     // --- DefaultFhirContext.all_structure_definitions ---
 
     #[test]
@@ -9,8 +9,8 @@ pub mod loader;
 pub mod version;
 
 pub use context::{
-    ConformanceResourceProvider, DefaultFhirContext, FallbackConformanceProvider, FhirContext,
-    FlexibleFhirContext, LockedPackage, PackageIntrospection, PackageLock,
+    ConflictPolicy, ConformanceResourceProvider, DefaultFhirContext, FallbackConformanceProvider,
+    FhirContext, FlexibleFhirContext, LockedPackage, PackageIntrospection, PackageLock,
 };
 pub use error::{Error, Result};
 pub use loader::PackageLoader;
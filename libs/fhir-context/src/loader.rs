@@ -1,7 +1,7 @@
 use crate::error::{Error, Result};
 use async_trait::async_trait;
 use std::sync::Arc;
-use ferrum_package::FhirPackage;
+use ferrum_package::{FhirPackage, RawFhirPackage};
 
 #[async_trait]
 pub trait PackageLoader: Send + Sync {
@@ -16,6 +16,23 @@ pub trait PackageLoader: Send + Sync {
         package_name: &str,
         version: &str,
     ) -> Result<FhirPackage>;
+
+    /// Load a package with its dependencies, keeping resources unparsed.
+    ///
+    /// The default implementation loads parsed packages and re-serializes them; loaders that
+    /// can read package files directly should override it.
+    async fn load_raw_package_with_dependencies(
+        &self,
+        package_name: &str,
+        version: Option<&str>,
+    ) -> Result<Vec<RawFhirPackage>> {
+        Ok(self
+            .load_package_with_dependencies(package_name, version)
+            .await?
+            .iter()
+            .map(RawFhirPackage::from_package)
+            .collect())
+    }
 }
 
 #[cfg(feature = "registry-loader")]
@@ -51,6 +68,20 @@ where
         .await
         .map_err(|e| Error::PackageLoader(e.to_string()))
     }
+
+    async fn load_raw_package_with_dependencies(
+        &self,
+        package_name: &str,
+        version: Option<&str>,
+    ) -> Result<Vec<RawFhirPackage>> {
+        ferrum_registry_client::RegistryClient::load_raw_package_with_dependencies(
+            self,
+            package_name,
+            version,
+        )
+        .await
+        .map_err(|e| Error::PackageLoader(e.to_string()))
+    }
 }
 
 pub fn default_package_loader() -> Result<Arc<dyn PackageLoader>> {
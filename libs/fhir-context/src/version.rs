@@ -2,7 +2,6 @@ use semver::Version;
 use serde_json::Value;
 use std::cmp::Ordering;
 use std::collections::BTreeMap;
-use std::sync::Arc;
 
 /// Version algorithm types as defined in FHIR spec
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -194,10 +193,10 @@ impl PartialOrd for VersionKey {
     }
 }
 
-pub(crate) fn select_from_version_index<'a>(
-    versions: &'a BTreeMap<VersionKey, Arc<Value>>,
+pub(crate) fn select_from_version_index<'a, V>(
+    versions: &'a BTreeMap<VersionKey, V>,
     version: Option<&str>,
-) -> Option<&'a Arc<Value>> {
+) -> Option<&'a V> {
     match version {
         Some(v) => {
             // For exact version lookup, find by version string regardless of algorithm.
@@ -223,6 +222,7 @@ pub(crate) fn select_from_version_index<'a>(
 mod tests {
     use super::*;
     use serde_json::json;
+    use std::sync::Arc;
 
     // --- VersionAlgorithm::from_str ---
 
@@ -5,11 +5,11 @@
 //! XML → JSON conversion. Metadata is embedded at compile time from
 //! `fhir_type_metadata.json` (generated via `ferrum-cli gen-format-metadata`).
 
-use quick_xml::events::{BytesEnd, BytesStart, Event};
+use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
 use quick_xml::Writer;
 use roxmltree::Document;
 use serde_json::{Map, Value};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::io::Cursor;
 use std::sync::LazyLock;
 use thiserror::Error;
@@ -76,8 +76,15 @@ pub enum FormatError {
     XmlWrite(#[from] quick_xml::Error),
 }
 
-/// Convert a FHIR JSON payload into its XML representation.
+/// Convert a FHIR JSON payload into its XML representation, indented by two spaces.
 pub fn json_to_xml(input: &str) -> Result<String, FormatError> {
+    json_to_xml_with(input, 2)
+}
+
+/// Convert a FHIR JSON payload into XML indented by `indent` spaces per level.
+///
+/// An `indent` of 0 writes the document on a single line.
+pub fn json_to_xml_with(input: &str, indent: usize) -> Result<String, FormatError> {
     let value: Value = serde_json::from_str(input)?;
     let obj = value.as_object().ok_or(FormatError::ExpectedObject)?;
     let resource_type = obj
@@ -85,42 +92,45 @@ pub fn json_to_xml(input: &str) -> Result<String, FormatError> {
         .and_then(Value::as_str)
         .ok_or(FormatError::MissingResourceType)?;
 
-    let mut writer = Writer::new_with_indent(Cursor::new(Vec::new()), b' ', 2);
+    let mut writer = if indent == 0 {
+        Writer::new(Cursor::new(Vec::new()))
+    } else {
+        Writer::new_with_indent(Cursor::new(Vec::new()), b' ', indent)
+    };
     let mut root = BytesStart::new(resource_type);
     root.push_attribute(("xmlns", FHIR_NS));
     writer.write_event(Event::Start(root.clone()))?;
-
-    let mut meta = HashMap::new();
-    for (k, v) in obj {
-        if k.starts_with('_') {
-            meta.insert(k.trim_start_matches('_').to_string(), v.clone());
-        }
-    }
-
-    for (k, v) in obj {
-        if k == "resourceType" || k.starts_with('_') {
-            continue;
-        }
-        let meta_entry = meta.get(k);
-        write_json_value(&mut writer, k, v, meta_entry)?;
-    }
-
-    // Handle metadata fields that don't have a corresponding value field
-    // (e.g., _active with extensions but no active field)
-    for (k, v) in &meta {
-        if !obj.contains_key(k) {
-            // This metadata has no corresponding value, write it as a primitive with no value
-            write_json_value(&mut writer, k, &Value::Null, Some(v))?;
-        }
-    }
-
+    write_children(&mut writer, obj, &["resourceType"])?;
     writer.write_event(Event::End(BytesEnd::new(resource_type)))?;
     let bytes = writer.into_inner().into_inner();
     Ok(String::from_utf8(bytes)?)
 }
 
-/// Convert a FHIR XML payload into its JSON representation.
+/// Options for [`xml_to_json_with`]. The default writes compact JSON and copies narratives
+/// verbatim.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct XmlToJsonOptions {
+    /// Pretty-print the JSON instead of writing a single line.
+    pub pretty: bool,
+    /// Strip active content from narrative XHTML: script, style and embedding elements
+    /// (`iframe`, `object`, `embed`, ...), event handler (`on*`) and `style` attributes, and
+    /// URLs with a `javascript:`, `vbscript:` or `data:` scheme.
+    pub sanitize_narrative: bool,
+}
+
+/// Convert a FHIR XML payload into its pretty-printed JSON representation.
 pub fn xml_to_json(input: &str) -> Result<String, FormatError> {
+    xml_to_json_with(
+        input,
+        XmlToJsonOptions {
+            pretty: true,
+            ..Default::default()
+        },
+    )
+}
+
+/// Convert a FHIR XML payload into JSON as configured by `options`.
+pub fn xml_to_json_with(input: &str, options: XmlToJsonOptions) -> Result<String, FormatError> {
     let doc = Document::parse(input)?;
     let root = doc.root_element();
 
@@ -134,12 +144,22 @@ pub fn xml_to_json(input: &str) -> Result<String, FormatError> {
 
     let mut accumulator = Map::new();
     for child in root.children().filter(|n| n.is_element()) {
-        process_xml_child(input, &mut accumulator, &child, Some(&resource_type))?;
+        process_xml_child(
+            input,
+            options.sanitize_narrative,
+            &mut accumulator,
+            &child,
+            Some(&resource_type),
+        )?;
     }
 
     map.extend(accumulator);
     let json = Value::Object(map);
-    Ok(serde_json::to_string_pretty(&json)?)
+    if options.pretty {
+        Ok(serde_json::to_string_pretty(&json)?)
+    } else {
+        Ok(serde_json::to_string(&json)?)
+    }
 }
 
 fn write_json_value(
@@ -157,6 +177,8 @@ fn write_json_value(
             }
         }
         Value::Object(obj) => write_complex(writer, name, obj)?,
+        // A null with metadata is a primitive that only carries an id or extensions
+        Value::Null if meta.is_some() => write_primitive(writer, name, value, meta)?,
         Value::Null => {}
         primitive => write_primitive(writer, name, primitive, meta)?,
     }
@@ -168,32 +190,71 @@ fn write_complex(
     name: &str,
     obj: &Map<String, Value>,
 ) -> Result<(), FormatError> {
-    let mut meta = HashMap::new();
-    for (k, v) in obj {
-        if k.starts_with('_') {
-            meta.insert(k.trim_start_matches('_').to_string(), v.clone());
-        }
-    }
-
     let mut start = BytesStart::new(name);
     if let Some(Value::String(id)) = obj.get("id") {
         start.push_attribute(("id", id.as_str()));
     }
 
     writer.write_event(Event::Start(start))?;
+    write_children(writer, obj, &["id"])?;
+    writer.write_event(Event::End(BytesEnd::new(name)))?;
+    Ok(())
+}
 
-    for (k, v) in obj {
-        if k.starts_with('_') || k == "id" {
+/// Write the properties of a JSON object as child elements.
+///
+/// Elements are emitted in the object's iteration order, so output is stable for a given
+/// input: keys are sorted unless serde_json's `preserve_order` feature is enabled, in which
+/// case the input order is kept. A `_name` entry is written together with `name`, or on its
+/// own at its first position when the object has no `name` value. Keys in `skip` are
+/// handled by the caller.
+fn write_children(
+    writer: &mut Writer<Cursor<Vec<u8>>>,
+    obj: &Map<String, Value>,
+    skip: &[&str],
+) -> Result<(), FormatError> {
+    let mut written: HashSet<&str> = HashSet::new();
+    for key in obj.keys() {
+        let name = key.strip_prefix('_').unwrap_or(key);
+        if skip.contains(&name) || !written.insert(name) {
             continue;
         }
-        let meta_entry = meta.get(k);
-        write_json_value(writer, k, v, meta_entry)?;
+        let meta = obj.get(&format!("_{}", name));
+        let value = obj.get(name).unwrap_or(&Value::Null);
+        write_json_value(writer, name, value, meta)?;
     }
-
-    writer.write_event(Event::End(BytesEnd::new(name)))?;
     Ok(())
 }
 
+/// Convert a FHIR JSON payload to XML and back, returning the resulting JSON.
+///
+/// Useful for checking conversion fidelity: a faithful round trip returns a value equal
+/// to the parsed input.
+pub fn round_trip_json(input: &str) -> Result<Value, FormatError> {
+    let xml = json_to_xml(input)?;
+    let json = xml_to_json_with(&xml, XmlToJsonOptions::default())?;
+    Ok(serde_json::from_str(&json)?)
+}
+
+/// Build a narrative `<div>` holding one `<p>` per entry of `paragraphs`.
+///
+/// Paragraph text is escaped, so the result is always well-formed XHTML suitable for
+/// `Narrative.div`.
+pub fn narrative_div<S: AsRef<str>>(paragraphs: &[S]) -> Result<String, FormatError> {
+    let mut writer = Writer::new(Cursor::new(Vec::new()));
+    let mut div = BytesStart::new("div");
+    div.push_attribute(("xmlns", XHTML_NS));
+    writer.write_event(Event::Start(div))?;
+    for paragraph in paragraphs {
+        writer.write_event(Event::Start(BytesStart::new("p")))?;
+        writer.write_event(Event::Text(BytesText::new(paragraph.as_ref())))?;
+        writer.write_event(Event::End(BytesEnd::new("p")))?;
+    }
+    writer.write_event(Event::End(BytesEnd::new("div")))?;
+    let bytes = writer.into_inner().into_inner();
+    Ok(String::from_utf8(bytes)?)
+}
+
 fn write_primitive(
     writer: &mut Writer<Cursor<Vec<u8>>>,
     name: &str,
@@ -249,6 +310,7 @@ fn primitive_to_string(value: &Value) -> String {
 
 fn process_xml_child(
     source: &str,
+    sanitize_narrative: bool,
     target: &mut Map<String, Value>,
     node: &roxmltree::Node,
     parent_type: Option<&str>,
@@ -260,7 +322,7 @@ fn process_xml_child(
     let force_array = prop_meta.map(|m| m.multiple).unwrap_or(false);
     let element_type = prop_meta.map(|m| m.type_name.as_str());
 
-    let (value, meta) = xml_element_to_value(source, node, element_type)?;
+    let (value, meta) = xml_element_to_value(source, sanitize_narrative, node, element_type)?;
 
     insert_json_property(target, &name, value, meta, force_array);
     Ok(())
@@ -268,12 +330,17 @@ fn process_xml_child(
 
 fn xml_element_to_value(
     source: &str,
+    sanitize_narrative: bool,
     node: &roxmltree::Node,
     element_type: Option<&str>,
 ) -> Result<(Value, Option<Value>), FormatError> {
     if node.tag_name().namespace().is_some_and(|ns| ns == XHTML_NS) {
-        let snippet = &source[node.range()];
-        return Ok((Value::String(snippet.to_string()), None));
+        let xhtml = if sanitize_narrative {
+            sanitize_xhtml(source, node)
+        } else {
+            source[node.range()].to_string()
+        };
+        return Ok((Value::String(xhtml), None));
     }
 
     let mut meta_map = Map::new();
@@ -281,19 +348,23 @@ fn xml_element_to_value(
         meta_map.insert("id".to_string(), Value::String(id.to_string()));
     }
 
-    if let Some(val) = node.attribute("value") {
+    // Primitives may carry only an id or extensions, without a value attribute.
+    let value_attr = node.attribute("value");
+    let is_primitive =
+        element_type.is_some_and(|t| t.starts_with(|c: char| c.is_ascii_lowercase()));
+    if value_attr.is_some() || is_primitive {
         let mut extensions = Vec::new();
         for child in node.children().filter(|c| c.is_element()) {
             if child.tag_name().name() == "extension" {
                 let (ext_val, _ext_meta) =
-                    xml_element_to_value(source, &child, Some("Extension"))?;
+                    xml_element_to_value(source, sanitize_narrative, &child, Some("Extension"))?;
                 extensions.push(ext_val);
             }
         }
         if !extensions.is_empty() {
             meta_map.insert("extension".to_string(), Value::Array(extensions));
         }
-        let prim = parse_primitive(val, element_type);
+        let prim = value_attr.map_or(Value::Null, |val| parse_primitive(val, element_type));
         let meta = if meta_map.is_empty() {
             None
         } else {
@@ -308,12 +379,97 @@ fn xml_element_to_value(
     }
 
     for child in node.children().filter(|c| c.is_element()) {
-        process_xml_child(source, &mut obj, &child, element_type)?;
+        process_xml_child(source, sanitize_narrative, &mut obj, &child, element_type)?;
     }
 
     Ok((Value::Object(obj), None))
 }
 
+/// XHTML elements dropped with their content by [`sanitize_xhtml`].
+const UNSAFE_XHTML_ELEMENTS: &[&str] = &[
+    "script", "style", "iframe", "frame", "frameset", "object", "embed", "applet", "base", "link",
+    "meta",
+];
+
+/// Attributes holding URLs; [`sanitize_xhtml`] drops them when the URL has an unsafe scheme.
+const XHTML_URL_ATTRIBUTES: &[&str] = &[
+    "href",
+    "src",
+    "action",
+    "formaction",
+    "data",
+    "codebase",
+    "cite",
+    "background",
+    "poster",
+    "longdesc",
+    "usemap",
+];
+
+/// URL schemes whose attributes [`sanitize_xhtml`] drops.
+const UNSAFE_URL_SCHEMES: &[&str] = &["javascript:", "vbscript:", "data:"];
+
+/// Copy an XHTML element from `source` without active content (see
+/// [`XmlToJsonOptions::sanitize_narrative`]).
+fn sanitize_xhtml(source: &str, node: &roxmltree::Node) -> String {
+    let mut removed: Vec<std::ops::Range<usize>> = Vec::new();
+    for element in node.descendants().filter(|n| n.is_element()) {
+        if removed.iter().any(|r| r.contains(&element.range().start)) {
+            continue;
+        }
+        let tag = element.tag_name().name();
+        if UNSAFE_XHTML_ELEMENTS
+            .iter()
+            .any(|unsafe_tag| tag.eq_ignore_ascii_case(unsafe_tag))
+        {
+            removed.push(element.range());
+            continue;
+        }
+        for attr in element.attributes() {
+            let name = attr.name();
+            let is_handler = name.len() > 2
+                && name
+                    .get(..2)
+                    .is_some_and(|prefix| prefix.eq_ignore_ascii_case("on"));
+            let is_unsafe_url = XHTML_URL_ATTRIBUTES
+                .iter()
+                .any(|url_attr| name.eq_ignore_ascii_case(url_attr))
+                && has_unsafe_scheme(attr.value());
+            if is_handler || is_unsafe_url || name.eq_ignore_ascii_case("style") {
+                // Take the whitespace separating the attribute from its predecessor too.
+                let range = attr.range();
+                let start = source[..range.start].trim_end().len();
+                removed.push(start..range.end);
+            }
+        }
+    }
+
+    let node_range = node.range();
+    let mut out = String::with_capacity(node_range.len());
+    let mut pos = node_range.start;
+    removed.sort_by_key(|r| r.start);
+    for range in removed {
+        out.push_str(&source[pos..range.start]);
+        pos = range.end;
+    }
+    out.push_str(&source[pos..node_range.end]);
+    out
+}
+
+/// Whether `value` is a URL with an unsafe scheme, as a browser would read it: whitespace and
+/// control characters (e.g. `java\tscript:`) are ignored and the scheme is case-insensitive.
+fn has_unsafe_scheme(value: &str) -> bool {
+    let normalized: String = value
+        .chars()
+        .filter(|c| !c.is_whitespace() && !c.is_control())
+        .take(16)
+        .collect::<String>()
+        .to_ascii_lowercase();
+    UNSAFE_URL_SCHEMES
+        .iter()
+        .any(|scheme| normalized.starts_with(scheme))
+}
+
 fn insert_json_property(
     map: &mut Map<String, Value>,
     name: &str,
@@ -321,8 +477,11 @@ fn insert_json_property(
     meta: Option<Value>,
     force_array: bool,
 ) {
+    // A primitive without a value is only represented by its `_name` metadata.
+    let skip_value = value.is_null() && !force_array && !map.contains_key(name);
     let entry = map.entry(name.to_string());
     match entry {
+        serde_json::map::Entry::Vacant(_) if skip_value => {}
         serde_json::map::Entry::Vacant(v) => {
             if force_array {
                 v.insert(Value::Array(vec![value]));
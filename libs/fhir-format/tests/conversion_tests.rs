@@ -1,7 +1,10 @@
 use serde_json::Value;
 use std::fs;
 use std::path::PathBuf;
-use ferrum_format::{json_to_xml, xml_to_json};
+use ferrum_format::{
+    json_to_xml, json_to_xml_with, narrative_div, round_trip_json, xml_to_json, xml_to_json_with,
+    XmlToJsonOptions,
+};
 
 /// Helper to normalize JSON for comparison (ignoring formatting/whitespace differences)
 fn normalize_json(json_str: &str) -> Value {
@@ -396,3 +399,121 @@ fn test_all_preserve_contained_resources() {
         );
     }
 }
+
+#[test]
+fn test_compact_json_output() {
+    let xml = r#"<Patient xmlns="http://hl7.org/fhir"><id value="p1"/><active value="true"/><name><family value="Doe"/></name></Patient>"#;
+
+    let compact = xml_to_json_with(xml, XmlToJsonOptions::default()).expect("converts");
+    assert!(
+        !compact.contains('\n'),
+        "compact JSON has no newlines: {compact}"
+    );
+
+    let pretty = xml_to_json(xml).expect("converts");
+    assert!(pretty.contains('\n'));
+    assert_eq!(normalize_json(&compact), normalize_json(&pretty));
+}
+
+/// Narrative `div` of `xml` converted with `sanitize_narrative` set as given.
+fn converted_div(xml: &str, sanitize_narrative: bool) -> String {
+    let options = XmlToJsonOptions {
+        sanitize_narrative,
+        ..Default::default()
+    };
+    let json: serde_json::Value =
+        serde_json::from_str(&xml_to_json_with(xml, options).expect("converts")).unwrap();
+    json["text"]["div"].as_str().unwrap().to_string()
+}
+
+#[test]
+fn test_narrative_sanitization() {
+    let xml = r#"<Patient xmlns="http://hl7.org/fhir"><text><status value="generated"/><div xmlns="http://www.w3.org/1999/xhtml"><p onclick="steal()">Hi<script>alert(1)</script></p><a href=" JavaScript:alert(2)">x</a><a href="http://example.org">ok</a></div></text></Patient>"#;
+
+    let div = converted_div(xml, false);
+    assert!(div.contains("<script>alert(1)</script>"), "{div}");
+
+    assert_eq!(
+        converted_div(xml, true),
+        r#"<div xmlns="http://www.w3.org/1999/xhtml"><p>Hi</p><a>x</a><a href="http://example.org">ok</a></div>"#
+    );
+}
+
+#[test]
+fn test_narrative_sanitization_of_obfuscated_and_embedded_content() {
+    let xml = r##"<Patient xmlns="http://hl7.org/fhir"><text><status value="generated"/><div xmlns="http://www.w3.org/1999/xhtml"><a href="java&#9;script:alert(1)">tab</a><a href="java&#10;script:alert(2)">newline</a><a href="DATA:text/html,&lt;script&gt;alert(3)&lt;/script&gt;">data</a><p style="background:url(javascript:alert(4))" title="Data: kept">styled</p><iframe src="http://example.org"></iframe><object data="x.swf"><embed src="x.swf"/></object><img src="#photo" alt="ok"/></div></text></Patient>"##;
+
+    assert_eq!(
+        converted_div(xml, true),
+        r##"<div xmlns="http://www.w3.org/1999/xhtml"><a>tab</a><a>newline</a><a>data</a><p title="Data: kept">styled</p><img src="#photo" alt="ok"/></div>"##
+    );
+}
+
+#[test]
+fn test_narrative_div_escapes_text() {
+    let div = narrative_div(&["Patient p1", "Name: <b>Doe</b> & Co"]).expect("builds");
+    assert_eq!(
+        div,
+        r#"<div xmlns="http://www.w3.org/1999/xhtml"><p>Patient p1</p><p>Name: &lt;b&gt;Doe&lt;/b&gt; &amp; Co</p></div>"#
+    );
+    assert!(roxmltree::Document::parse(&div).is_ok());
+}
+
+#[test]
+fn test_xml_indent_width() {
+    let json = r#"{"resourceType":"Patient","id":"p1","name":[{"family":"Doe"}]}"#;
+
+    let xml = json_to_xml_with(json, 4).expect("converts");
+    assert!(xml.contains("\n    <id value=\"p1\"/>"), "{xml}");
+    assert!(xml.contains("\n        <family value=\"Doe\"/>"), "{xml}");
+
+    let default = json_to_xml(json).expect("converts");
+    assert!(default.contains("\n  <id value=\"p1\"/>"), "{default}");
+
+    let compact = json_to_xml_with(json, 0).expect("converts");
+    assert!(!compact.contains('\n'), "{compact}");
+}
+
+#[test]
+fn test_round_trip_repeated_and_nested_elements() {
+    let input = r#"{
+        "resourceType": "Patient",
+        "id": "rt",
+        "extension": [
+            {"url": "http://example.org/a", "valueString": "one"},
+            {"url": "http://example.org/b", "valueBoolean": true}
+        ],
+        "identifier": [
+            {"system": "http://example.org/mrn", "value": "123"},
+            {"system": "http://example.org/ssn", "value": "456"}
+        ],
+        "_active": {"extension": [{"url": "http://example.org/why", "valueString": "unknown"}]},
+        "name": [
+            {"use": "official", "family": "Doe", "given": ["Jane", "Q"]},
+            {
+                "use": "nickname",
+                "given": ["JJ"],
+                "_given": [{"id": "g1"}],
+                "period": {"start": "2020-01-01"}
+            }
+        ],
+        "telecom": [
+            {"system": "phone", "value": "555-0100", "rank": 1},
+            {"system": "email", "value": "jane@example.org"}
+        ],
+        "contact": [
+            {
+                "relationship": [{"coding": [{"system": "http://example.org/rel", "code": "N"}]}],
+                "name": {"family": "Roe", "given": ["Sam"]}
+            }
+        ]
+    }"#;
+
+    let expected = normalize_json(input);
+    assert_eq!(round_trip_json(input).expect("round trips"), expected);
+
+    // Element order only depends on the input, so repeated conversions are byte-identical.
+    let xml = json_to_xml(input).expect("converts");
+    assert_eq!(json_to_xml(input).expect("converts"), xml);
+    assert!(xml.contains("<active>"), "{xml}");
+}
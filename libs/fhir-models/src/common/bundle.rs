@@ -264,6 +264,29 @@ impl Bundle {
         self.entry.as_deref_mut().unwrap_or(&mut [])
     }
 
+    /// Iterate over the resources of all entries (`entry.resource`), skipping entries
+    /// without one
+    pub fn resources(&self) -> impl Iterator<Item = &Value> {
+        self.entries().iter().filter_map(|e| e.resource.as_ref())
+    }
+
+    /// Iterate over the entries whose resource is of the given type
+    pub fn entries_of_type<'a>(
+        &'a self,
+        resource_type: &'a str,
+    ) -> impl Iterator<Item = &'a BundleEntry> {
+        self.entries()
+            .iter()
+            .filter(move |e| e.resource_type() == Some(resource_type))
+    }
+
+    /// Find the entry with the given `fullUrl`
+    pub fn find_by_full_url(&self, url: &str) -> Option<&BundleEntry> {
+        self.entries()
+            .iter()
+            .find(|e| e.full_url.as_deref() == Some(url))
+    }
+
     /// Add an entry to the bundle
     pub fn add_entry(&mut self, entry: BundleEntry) {
         if self.entry.is_none() {
@@ -288,6 +311,16 @@ impl Bundle {
     }
 }
 
+impl BundleEntry {
+    /// Resource type of the entry's resource, if it has one
+    pub fn resource_type(&self) -> Option<&str> {
+        self.resource
+            .as_ref()
+            .and_then(|r| r.get("resourceType"))
+            .and_then(|v| v.as_str())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -374,6 +407,81 @@ mod tests {
         assert_eq!(bundle.link.as_ref().unwrap()[0].relation, "self");
     }
 
+    fn mixed_searchset() -> Bundle {
+        Bundle::from_value(&json!({
+            "resourceType": "Bundle",
+            "type": "searchset",
+            "entry": [
+                {
+                    "fullUrl": "http://example.org/fhir/Patient/1",
+                    "resource": {"resourceType": "Patient", "id": "1"},
+                    "search": {"mode": "match"}
+                },
+                {
+                    "fullUrl": "http://example.org/fhir/Observation/2",
+                    "resource": {"resourceType": "Observation", "id": "2"},
+                    "search": {"mode": "match"}
+                },
+                {
+                    "fullUrl": "http://example.org/fhir/Patient/3",
+                    "resource": {"resourceType": "Patient", "id": "3"},
+                    "search": {"mode": "include"}
+                },
+                {
+                    "resource": {"resourceType": "OperationOutcome"},
+                    "search": {"mode": "outcome"}
+                },
+                {
+                    "fullUrl": "urn:uuid:0b8e6f2a-5c1d-4e3f-9a7b-2d4c6e8f0a1b"
+                }
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_resources() {
+        let bundle = mixed_searchset();
+        let types: Vec<&str> = bundle
+            .resources()
+            .map(|r| r["resourceType"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            types,
+            vec!["Patient", "Observation", "Patient", "OperationOutcome"]
+        );
+    }
+
+    #[test]
+    fn test_entries_of_type() {
+        let bundle = mixed_searchset();
+        let patient_ids: Vec<&str> = bundle
+            .entries_of_type("Patient")
+            .map(|e| e.resource.as_ref().unwrap()["id"].as_str().unwrap())
+            .collect();
+        assert_eq!(patient_ids, vec!["1", "3"]);
+        assert_eq!(bundle.entries_of_type("Observation").count(), 1);
+        assert_eq!(bundle.entries_of_type("Encounter").count(), 0);
+    }
+
+    #[test]
+    fn test_find_by_full_url() {
+        let bundle = mixed_searchset();
+        let entry = bundle
+            .find_by_full_url("http://example.org/fhir/Observation/2")
+            .unwrap();
+        assert_eq!(entry.resource_type(), Some("Observation"));
+
+        let entry = bundle
+            .find_by_full_url("urn:uuid:0b8e6f2a-5c1d-4e3f-9a7b-2d4c6e8f0a1b")
+            .unwrap();
+        assert!(entry.resource.is_none());
+
+        assert!(bundle
+            .find_by_full_url("http://example.org/fhir/Patient/2")
+            .is_none());
+    }
+
     #[test]
     fn test_bundle_entry_request() {
         let request = BundleEntryRequest {
@@ -250,4 +250,101 @@ impl CodeSystem {
             extensions: HashMap::new(),
         }
     }
+
+    /// Find a concept by code anywhere in the concept hierarchy
+    pub fn find_concept(&self, code: &str) -> Option<&CodeSystemConcept> {
+        find_in(self.concept.as_deref().unwrap_or_default(), code)
+    }
+
+    /// All concepts below `code` in the hierarchy (depth-first, excluding `code` itself)
+    ///
+    /// Returns an empty list if the code is unknown or has no children.
+    pub fn descendants(&self, code: &str) -> Vec<&CodeSystemConcept> {
+        let mut out = Vec::new();
+        if let Some(concept) = self.find_concept(code) {
+            collect_descendants(concept, &mut out);
+        }
+        out
+    }
+}
+
+fn find_in<'a>(concepts: &'a [CodeSystemConcept], code: &str) -> Option<&'a CodeSystemConcept> {
+    concepts.iter().find_map(|concept| {
+        if concept.code == code {
+            Some(concept)
+        } else {
+            find_in(concept.concept.as_deref().unwrap_or_default(), code)
+        }
+    })
+}
+
+fn collect_descendants<'a>(concept: &'a CodeSystemConcept, out: &mut Vec<&'a CodeSystemConcept>) {
+    for child in concept.concept.as_deref().unwrap_or_default() {
+        out.push(child);
+        collect_descendants(child, out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn hierarchical_code_system() -> CodeSystem {
+        serde_json::from_value(json!({
+            "resourceType": "CodeSystem",
+            "url": "http://example.org/CodeSystem/body-site",
+            "status": "active",
+            "content": "complete",
+            "hierarchyMeaning": "is-a",
+            "concept": [
+                {
+                    "code": "limb",
+                    "concept": [
+                        {
+                            "code": "arm",
+                            "concept": [
+                                {"code": "hand"},
+                                {"code": "elbow"}
+                            ]
+                        },
+                        {
+                            "code": "leg",
+                            "concept": [{"code": "foot"}]
+                        }
+                    ]
+                },
+                {"code": "head"}
+            ]
+        }))
+        .unwrap()
+    }
+
+    fn codes<'a>(concepts: &[&'a CodeSystemConcept]) -> Vec<&'a str> {
+        concepts.iter().map(|c| c.code.as_str()).collect()
+    }
+
+    #[test]
+    fn test_find_concept_at_any_depth() {
+        let cs = hierarchical_code_system();
+
+        assert_eq!(cs.find_concept("limb").unwrap().code, "limb");
+        assert_eq!(cs.find_concept("leg").unwrap().code, "leg");
+        assert_eq!(cs.find_concept("elbow").unwrap().code, "elbow");
+        assert!(cs.find_concept("tail").is_none());
+    }
+
+    #[test]
+    fn test_descendants() {
+        let cs = hierarchical_code_system();
+
+        assert_eq!(
+            codes(&cs.descendants("limb")),
+            vec!["arm", "hand", "elbow", "leg", "foot"]
+        );
+        assert_eq!(codes(&cs.descendants("arm")), vec!["hand", "elbow"]);
+        assert!(cs.descendants("hand").is_empty());
+        assert!(cs.descendants("head").is_empty());
+        assert!(cs.descendants("tail").is_empty());
+    }
 }
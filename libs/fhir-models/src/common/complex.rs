@@ -88,3 +88,336 @@ pub struct UsageContext {
     #[serde(flatten)]
     pub value: serde_json::Value, // Can be CodeableConcept, Quantity, Range, or Reference
 }
+
+/// Quantity - a measured amount
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Quantity {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<f64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comparator: Option<String>, // < | <= | >= | >
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+}
+
+/// CodeableConcept - a concept given by codings and/or text
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CodeableConcept {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coding: Option<Vec<Coding>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+
+/// Period - a time range defined by start and end date/times
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Period {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end: Option<String>,
+}
+
+/// Ratio - a ratio of two Quantity values
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Ratio {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub numerator: Option<Quantity>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub denominator: Option<Quantity>,
+}
+
+/// SampledData - a series of measurements taken by a device
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SampledData {
+    pub origin: Quantity,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub period: Option<f64>, // R4/R4B; R5 uses interval + intervalUnit
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub factor: Option<f64>,
+
+    #[serde(rename = "lowerLimit", skip_serializing_if = "Option::is_none")]
+    pub lower_limit: Option<f64>,
+
+    #[serde(rename = "upperLimit", skip_serializing_if = "Option::is_none")]
+    pub upper_limit: Option<f64>,
+
+    pub dimensions: u32,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<String>,
+
+    /// Version-specific fields (e.g. R5 `interval`, `intervalUnit`, `codeMap`)
+    #[serde(flatten)]
+    pub extensions: std::collections::HashMap<String, serde_json::Value>,
+}
+
+/// Identifier - a business identifier for a resource
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Identifier {
+    #[serde(rename = "use", skip_serializing_if = "Option::is_none")]
+    pub use_: Option<String>, // usual | official | temp | secondary | old
+
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub type_: Option<CodeableConcept>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub period: Option<Period>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assigner: Option<Box<Reference>>,
+}
+
+/// Reference - a reference from one resource to another
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Reference {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
+
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub type_: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identifier: Option<Identifier>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display: Option<String>,
+}
+
+impl Reference {
+    /// Resource type and id of a literal `[base/]Type/id[/_history/vid]` reference
+    ///
+    /// Works for both relative and absolute URLs. Returns `None` for contained (`#id`)
+    /// references, URNs, and logical (identifier-only) references.
+    pub fn relative_parts(&self) -> Option<(String, String)> {
+        let reference = self.reference.as_deref()?;
+        if reference.starts_with('#') || reference.starts_with("urn:") {
+            return None;
+        }
+
+        let path = reference.split(['?', '#']).next().unwrap_or(reference);
+        let mut segments: Vec<&str> = path.trim_end_matches('/').split('/').collect();
+        if segments.len() >= 4 && segments[segments.len() - 2] == "_history" {
+            segments.truncate(segments.len() - 2);
+        }
+        if segments.len() < 2 {
+            return None;
+        }
+
+        let id = segments[segments.len() - 1];
+        let resource_type = segments[segments.len() - 2];
+        let type_is_valid = resource_type
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_uppercase())
+            && resource_type.chars().all(|c| c.is_ascii_alphanumeric());
+        if !type_is_valid || id.is_empty() {
+            return None;
+        }
+
+        Some((resource_type.to_string(), id.to_string()))
+    }
+
+    /// Whether the literal reference is an absolute URL or URN
+    pub fn is_absolute(&self) -> bool {
+        self.reference
+            .as_deref()
+            .is_some_and(|reference| reference.starts_with("urn:") || reference.contains("://"))
+    }
+}
+
+/// Polymorphic `value[x]` element (e.g. Observation.value[x])
+///
+/// Serializes as a single `value{Type}` entry, so the variant is selected by field name
+/// rather than by inspecting the JSON shape.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ValueX {
+    #[serde(rename = "valueQuantity")]
+    Quantity(Quantity),
+    #[serde(rename = "valueCodeableConcept")]
+    CodeableConcept(CodeableConcept),
+    #[serde(rename = "valueString")]
+    String(String),
+    #[serde(rename = "valueBoolean")]
+    Boolean(bool),
+    #[serde(rename = "valueInteger")]
+    Integer(i64),
+    #[serde(rename = "valueDateTime")]
+    DateTime(String),
+    #[serde(rename = "valuePeriod")]
+    Period(Period),
+    #[serde(rename = "valueRatio")]
+    Ratio(Ratio),
+    #[serde(rename = "valueSampledData")]
+    SampledData(SampledData),
+}
+
+impl ValueX {
+    /// JSON field name of this choice (e.g. `valueQuantity`)
+    pub fn field_name(&self) -> &'static str {
+        match self {
+            ValueX::Quantity(_) => "valueQuantity",
+            ValueX::CodeableConcept(_) => "valueCodeableConcept",
+            ValueX::String(_) => "valueString",
+            ValueX::Boolean(_) => "valueBoolean",
+            ValueX::Integer(_) => "valueInteger",
+            ValueX::DateTime(_) => "valueDateTime",
+            ValueX::Period(_) => "valuePeriod",
+            ValueX::Ratio(_) => "valueRatio",
+            ValueX::SampledData(_) => "valueSampledData",
+        }
+    }
+
+    /// Find and parse the `value[x]` entry of a JSON object
+    ///
+    /// Returns `None` if the object has no supported `value{Type}` field or its content
+    /// does not match the type named by the field.
+    pub fn from_object(object: &serde_json::Map<String, serde_json::Value>) -> Option<ValueX> {
+        object
+            .iter()
+            .filter(|(key, _)| {
+                key.strip_prefix("value")
+                    .and_then(|suffix| suffix.chars().next())
+                    .is_some_and(|c| c.is_ascii_uppercase())
+            })
+            .find_map(|(key, value)| {
+                let mut entry = serde_json::Map::with_capacity(1);
+                entry.insert(key.clone(), value.clone());
+                serde_json::from_value(serde_json::Value::Object(entry)).ok()
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_value_quantity_round_trip() {
+        let observation = json!({
+            "resourceType": "Observation",
+            "status": "final",
+            "valueQuantity": {
+                "value": 72.5,
+                "unit": "kg",
+                "system": "http://unitsofmeasure.org",
+                "code": "kg"
+            }
+        });
+
+        let value = ValueX::from_object(observation.as_object().unwrap()).unwrap();
+        match &value {
+            ValueX::Quantity(q) => {
+                assert_eq!(q.value, Some(72.5));
+                assert_eq!(q.code.as_deref(), Some("kg"));
+            }
+            other => panic!("expected Quantity, got {:?}", other),
+        }
+        assert_eq!(value.field_name(), "valueQuantity");
+
+        let json = serde_json::to_value(&value).unwrap();
+        assert_eq!(
+            json,
+            json!({ "valueQuantity": observation["valueQuantity"] })
+        );
+        assert_eq!(serde_json::from_value::<ValueX>(json).unwrap(), value);
+    }
+
+    #[test]
+    fn test_value_string_round_trip() {
+        let observation = json!({
+            "resourceType": "Observation",
+            "status": "final",
+            "valueString": "positive"
+        });
+
+        let value = ValueX::from_object(observation.as_object().unwrap()).unwrap();
+        assert_eq!(value, ValueX::String("positive".to_string()));
+
+        let json = serde_json::to_value(&value).unwrap();
+        assert_eq!(json, json!({ "valueString": "positive" }));
+        assert_eq!(serde_json::from_value::<ValueX>(json).unwrap(), value);
+    }
+
+    #[test]
+    fn test_from_object_without_value() {
+        let observation = json!({
+            "resourceType": "Observation",
+            "status": "final",
+            "valueSet": "not a choice element"
+        });
+
+        assert_eq!(ValueX::from_object(observation.as_object().unwrap()), None);
+    }
+
+    #[test]
+    fn test_reference_relative() {
+        let reference: Reference =
+            serde_json::from_value(json!({"reference": "Patient/123"})).unwrap();
+
+        assert!(!reference.is_absolute());
+        assert_eq!(
+            reference.relative_parts(),
+            Some(("Patient".to_string(), "123".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_reference_absolute() {
+        let reference: Reference = serde_json::from_value(json!({
+            "reference": "http://ex.org/Patient/123/_history/2"
+        }))
+        .unwrap();
+
+        assert!(reference.is_absolute());
+        assert_eq!(
+            reference.relative_parts(),
+            Some(("Patient".to_string(), "123".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_reference_logical_identifier_only() {
+        let reference: Reference = serde_json::from_value(json!({
+            "type": "Patient",
+            "identifier": {"system": "http://ex.org/mrn", "value": "12345"},
+            "display": "Jane Doe"
+        }))
+        .unwrap();
+
+        assert!(!reference.is_absolute());
+        assert_eq!(reference.relative_parts(), None);
+        assert_eq!(reference.type_.as_deref(), Some("Patient"));
+        assert_eq!(
+            reference
+                .identifier
+                .as_ref()
+                .and_then(|i| i.value.as_deref()),
+            Some("12345")
+        );
+
+        let json = serde_json::to_value(&reference).unwrap();
+        assert_eq!(json["type"], "Patient");
+        assert_eq!(json["identifier"]["system"], "http://ex.org/mrn");
+    }
+}
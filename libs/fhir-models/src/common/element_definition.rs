@@ -387,6 +387,16 @@ impl ElementDefinition {
         self.slice_name.is_some()
     }
 
+    /// Name of the slice this element defines, if any
+    pub fn slice_name(&self) -> Option<&str> {
+        self.slice_name.as_deref()
+    }
+
+    /// Check if this element is sliced (declares slicing for its slices to follow)
+    pub fn is_sliced(&self) -> bool {
+        self.slicing.is_some()
+    }
+
     /// Get the parent path (everything before the last '.')
     pub fn parent_path(&self) -> Option<String> {
         self.path.rfind('.').map(|pos| self.path[..pos].to_string())
@@ -405,10 +415,10 @@ impl ElementDefinition {
     }
 
     /// Get type codes for this element
-    pub fn type_codes(&self) -> Vec<String> {
+    pub fn type_codes(&self) -> Vec<&str> {
         self.types
             .as_ref()
-            .map(|types| types.iter().map(|t| t.code.clone()).collect())
+            .map(|types| types.iter().map(|t| t.code.as_str()).collect())
             .unwrap_or_default()
     }
 
@@ -425,6 +435,19 @@ impl ElementDefinition {
             .unwrap_or(false)
     }
 
+    /// Get the effective cardinality as `(min, max)`
+    ///
+    /// A missing `min` counts as 0. `max` is `None` when unbounded ("*" or missing).
+    pub fn cardinality(&self) -> (u32, Option<u32>) {
+        let min = self.min.unwrap_or(0);
+        let max = self
+            .max
+            .as_deref()
+            .filter(|m| *m != "*")
+            .and_then(|m| m.parse::<u32>().ok());
+        (min, max)
+    }
+
     /// Get the cardinality as a string (e.g., "0..1", "1..*")
     pub fn cardinality_string(&self) -> String {
         let min = self.min.unwrap_or(0);
@@ -434,7 +457,7 @@ impl ElementDefinition {
 
     /// Extract type information into an ElementTypeInfo struct
     pub fn to_type_info(&self) -> Option<ElementTypeInfo> {
-        let type_codes = self.type_codes();
+        let type_codes: Vec<String> = self.type_codes().into_iter().map(String::from).collect();
 
         if type_codes.is_empty() {
             return None;
@@ -442,16 +465,7 @@ impl ElementDefinition {
 
         let is_choice = self.is_choice_type();
         let is_array = self.is_array();
-        let min = self.min.unwrap_or(0);
-
-        // Parse max: "*" becomes None, otherwise parse as integer
-        let max = self.max.as_ref().and_then(|m| {
-            if m == "*" {
-                None
-            } else {
-                m.parse::<u32>().ok()
-            }
-        });
+        let (min, max) = self.cardinality();
 
         Some(ElementTypeInfo {
             path: self.path.clone(),
@@ -692,4 +706,58 @@ mod tests {
         assert!(elem.is_required());
         assert!(elem.is_array());
     }
+
+    fn element(path: &str) -> ElementDefinition {
+        serde_json::from_value(serde_json::json!({ "path": path })).unwrap()
+    }
+
+    fn element_type(code: &str) -> ElementDefinitionType {
+        ElementDefinitionType {
+            code: code.to_string(),
+            profile: None,
+            target_profile: None,
+            aggregation: None,
+            versioning: None,
+        }
+    }
+
+    #[test]
+    fn test_accessors_for_sliced_array() {
+        let mut elem = element("Observation.component");
+        elem.min = Some(0);
+        elem.max = Some("*".to_string());
+        elem.types = Some(vec![element_type("BackboneElement")]);
+        elem.slicing = Some(ElementDefinitionSlicing {
+            discriminator: None,
+            description: None,
+            ordered: None,
+            rules: SlicingRules::Open,
+        });
+
+        assert_eq!(elem.cardinality(), (0, None));
+        assert_eq!(elem.type_codes(), vec!["BackboneElement"]);
+        assert!(elem.is_sliced());
+        assert_eq!(elem.slice_name(), None);
+    }
+
+    #[test]
+    fn test_accessors_for_named_slice() {
+        let mut elem = element("Observation.value[x]");
+        elem.slice_name = Some("valueQuantity".to_string());
+        elem.min = Some(1);
+        elem.max = Some("1".to_string());
+        elem.types = Some(vec![element_type("Quantity"), element_type("string")]);
+
+        assert_eq!(elem.cardinality(), (1, Some(1)));
+        assert_eq!(elem.type_codes(), vec!["Quantity", "string"]);
+        assert!(!elem.is_sliced());
+        assert_eq!(elem.slice_name(), Some("valueQuantity"));
+    }
+
+    #[test]
+    fn test_cardinality_defaults() {
+        let elem = element("Patient.name");
+        assert_eq!(elem.cardinality(), (0, None));
+        assert!(elem.type_codes().is_empty());
+    }
 }
@@ -256,4 +256,123 @@ impl ValueSet {
             extensions: HashMap::new(),
         }
     }
+
+    /// All `(system, code)` pairs in the expansion, flattening nested `contains`
+    ///
+    /// Grouping entries without a system or code are traversed but not returned.
+    pub fn expansion_codes(&self) -> Vec<(String, String)> {
+        let mut out = Vec::new();
+        if let Some(expansion) = &self.expansion {
+            collect_codes(expansion.contains.as_deref().unwrap_or_default(), &mut out);
+        }
+        out
+    }
+
+    /// Whether the expansion contains `system|code` at any nesting level
+    pub fn contains_code(&self, system: &str, code: &str) -> bool {
+        self.expansion.as_ref().is_some_and(|expansion| {
+            contains_in(
+                expansion.contains.as_deref().unwrap_or_default(),
+                system,
+                code,
+            )
+        })
+    }
+}
+
+fn collect_codes(contains: &[ValueSetExpansionContains], out: &mut Vec<(String, String)>) {
+    for entry in contains {
+        if let (Some(system), Some(code)) = (&entry.system, &entry.code) {
+            out.push((system.clone(), code.clone()));
+        }
+        collect_codes(entry.contains.as_deref().unwrap_or_default(), out);
+    }
+}
+
+fn contains_in(contains: &[ValueSetExpansionContains], system: &str, code: &str) -> bool {
+    contains.iter().any(|entry| {
+        (entry.system.as_deref() == Some(system) && entry.code.as_deref() == Some(code))
+            || contains_in(entry.contains.as_deref().unwrap_or_default(), system, code)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const LOINC: &str = "http://loinc.org";
+    const SNOMED: &str = "http://snomed.info/sct";
+
+    fn value_set_with_expansion(contains: serde_json::Value) -> ValueSet {
+        serde_json::from_value(json!({
+            "resourceType": "ValueSet",
+            "url": "http://example.org/ValueSet/test",
+            "status": "active",
+            "expansion": {
+                "timestamp": "2024-01-01T00:00:00Z",
+                "contains": contains
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_flat_expansion() {
+        let vs = value_set_with_expansion(json!([
+            {"system": LOINC, "code": "8867-4"},
+            {"system": LOINC, "code": "8310-5"}
+        ]));
+
+        assert_eq!(
+            vs.expansion_codes(),
+            vec![
+                (LOINC.to_string(), "8867-4".to_string()),
+                (LOINC.to_string(), "8310-5".to_string()),
+            ]
+        );
+        assert!(vs.contains_code(LOINC, "8310-5"));
+        assert!(!vs.contains_code(SNOMED, "8310-5"));
+    }
+
+    #[test]
+    fn test_grouped_expansion() {
+        let vs = value_set_with_expansion(json!([
+            {
+                "display": "Vital signs",
+                "contains": [
+                    {"system": LOINC, "code": "8867-4"},
+                    {
+                        "system": SNOMED,
+                        "code": "271649006",
+                        "contains": [{"system": SNOMED, "code": "271650006"}]
+                    }
+                ]
+            },
+            {"system": LOINC, "code": "8310-5"}
+        ]));
+
+        assert_eq!(
+            vs.expansion_codes(),
+            vec![
+                (LOINC.to_string(), "8867-4".to_string()),
+                (SNOMED.to_string(), "271649006".to_string()),
+                (SNOMED.to_string(), "271650006".to_string()),
+                (LOINC.to_string(), "8310-5".to_string()),
+            ]
+        );
+        assert!(vs.contains_code(SNOMED, "271650006"));
+        assert!(!vs.contains_code(LOINC, "271650006"));
+    }
+
+    #[test]
+    fn test_no_expansion() {
+        let vs = ValueSet::new(
+            "http://example.org/ValueSet/empty",
+            PublicationStatus::Active,
+        );
+
+        assert!(vs.expansion_codes().is_empty());
+        assert!(!vs.contains_code(LOINC, "8867-4"));
+    }
 }
@@ -6,6 +6,7 @@
 use flate2::read::GzDecoder;
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use std::fs;
 use std::io::Read;
@@ -58,6 +59,52 @@ pub fn validate_version_format(version: &str) -> Result<(), PackageError> {
     Ok(())
 }
 
+/// Validate that a manifest URL field is absolute (has a scheme and a host). Structural only.
+fn validate_absolute_url(field: &str, url: &str) -> Result<(), PackageError> {
+    let invalid = || {
+        PackageError::ValidationError(format!(
+            "Field '{}' must be an absolute URL, got '{}'",
+            field, url
+        ))
+    };
+
+    let (scheme, rest) = url.split_once("://").ok_or_else(invalid)?;
+    let valid_scheme = scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    let host = rest.split(['/', '?', '#']).next().unwrap_or_default();
+
+    if !valid_scheme || host.is_empty() || url.chars().any(char::is_whitespace) {
+        return Err(invalid());
+    }
+    Ok(())
+}
+
+/// Validate a dependency version reference: an exact version or a patch wildcard (`1.2.x`).
+///
+/// npm range syntaxes (`^1.2`, `~1.2.3`, `>=1.0.0`, `1.x || 2.x`) are rejected because
+/// [`version_matches`] cannot resolve them. Errors name the dependency.
+fn validate_dependency_reference(name: &str, reference: &str) -> Result<(), PackageError> {
+    let is_range = reference.starts_with(['^', '~', '>', '<', '='])
+        || reference.contains(['*', '|', ' '])
+        || reference == "x";
+    if is_range {
+        return Err(PackageError::ValidationError(format!(
+            "Dependency '{}' uses unsupported version range '{}' (only exact versions and patch wildcards like '1.2.x' are supported)",
+            name, reference
+        )));
+    }
+
+    let version = reference.strip_suffix(".x").unwrap_or(reference);
+    validate_version_format(version).map_err(|e| match e {
+        PackageError::ValidationError(msg) => {
+            PackageError::ValidationError(format!("Dependency '{}': {}", name, msg))
+        }
+        other => other,
+    })
+}
+
 /// Parse version into base and optional label (e.g., "1.2.3-release" → ("1.2.3", Some("release"))).
 pub fn parse_version(version: &str) -> (String, Option<String>) {
     if let Some((base, label)) = version.split_once('-') {
@@ -234,9 +281,20 @@ impl PackageManifest {
         if strict {
             validate_version_format(&self.version)?;
 
-            for dep_version in self.dependencies.values() {
-                let version_to_validate = dep_version.strip_suffix(".x").unwrap_or(dep_version);
-                validate_version_format(version_to_validate)?;
+            for (field, url) in [
+                ("canonical", &self.canonical),
+                ("url", &self.url),
+                ("homepage", &self.homepage),
+            ] {
+                if let Some(url) = url {
+                    validate_absolute_url(field, url)?;
+                }
+            }
+
+            let mut dependencies: Vec<_> = self.dependencies.iter().collect();
+            dependencies.sort();
+            for (name, reference) in dependencies {
+                validate_dependency_reference(name, reference)?;
             }
         }
 
@@ -249,6 +307,41 @@ impl PackageManifest {
             name == "hl7.fhir.core" || (name.starts_with("hl7.fhir.r") && name.ends_with(".core"))
         })
     }
+
+    /// FHIR versions this package targets.
+    ///
+    /// Returns `fhirVersions` when present; otherwise infers them from core package
+    /// dependencies (e.g. `hl7.fhir.r4.core` -> `4.0.1`). The legacy `hl7.fhir.core`
+    /// package is versioned by FHIR release, so its dependency version is used as-is.
+    pub fn effective_fhir_versions(&self) -> Vec<String> {
+        if !self.fhir_versions.is_empty() {
+            return self.fhir_versions.clone();
+        }
+
+        let mut versions: Vec<String> = self
+            .dependencies
+            .iter()
+            .filter_map(|(name, version)| match name.as_str() {
+                "hl7.fhir.core" => Some(version.clone()),
+                _ => core_package_fhir_version(name).map(str::to_string),
+            })
+            .collect();
+        versions.sort();
+        versions.dedup();
+        versions
+    }
+}
+
+/// FHIR version of a release-specific core package.
+fn core_package_fhir_version(name: &str) -> Option<&'static str> {
+    match name {
+        "hl7.fhir.r2.core" => Some("1.0.2"),
+        "hl7.fhir.r3.core" => Some("3.0.2"),
+        "hl7.fhir.r4.core" => Some("4.0.1"),
+        "hl7.fhir.r4b.core" => Some("4.3.0"),
+        "hl7.fhir.r5.core" => Some("5.0.0"),
+        _ => None,
+    }
 }
 
 /// Package index (`.index.json`).
@@ -261,12 +354,35 @@ pub struct PackageIndex {
     pub extra: Map<String, Value>,
 }
 
+impl PackageIndex {
+    /// `index-version` values this crate understands.
+    pub const SUPPORTED_VERSIONS: &'static [u8] = &[1, 2];
+
+    /// Reject indexes with an unknown `index-version`.
+    pub fn validate(&self) -> PackageResult<()> {
+        if !Self::SUPPORTED_VERSIONS.contains(&self.index_version) {
+            return Err(PackageError::InvalidStructure(format!(
+                "Unsupported .index.json index-version {} (expected 1 or 2)",
+                self.index_version
+            )));
+        }
+        Ok(())
+    }
+}
+
 /// File entry in package index.
+///
+/// Version-1 indexes written by older tooling may omit `resourceType`; `valueSet` and
+/// `derivation` are only written by index-version 2.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct IndexedFile {
     pub filename: String,
-    #[serde(rename = "resourceType")]
-    pub resource_type: String,
+    #[serde(
+        rename = "resourceType",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub resource_type: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -281,6 +397,12 @@ pub struct IndexedFile {
     pub supplements: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub content: Option<String>,
+    /// CodeSystem `valueSet` (index-version 2).
+    #[serde(rename = "valueSet", default, skip_serializing_if = "Option::is_none")]
+    pub value_set: Option<String>,
+    /// StructureDefinition `derivation` (index-version 2).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub derivation: Option<String>,
     #[serde(flatten, default, skip_serializing_if = "Map::is_empty")]
     pub extra: Map<String, Value>,
 }
@@ -301,6 +423,36 @@ pub enum PackageError {
 
 pub type PackageResult<T> = Result<T, PackageError>;
 
+/// Options for loading a package archive.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LoadOptions {
+    /// Classify resources by type (via the package index when available) instead of by the
+    /// `package/examples/` path prefix, so misplaced examples are still treated as examples.
+    pub classify_by_index: bool,
+}
+
+/// Resource types that define or support conformance (profiles, terminology, operations).
+const CONFORMANCE_RESOURCE_TYPES: &[&str] = &[
+    "CapabilityStatement",
+    "CodeSystem",
+    "CompartmentDefinition",
+    "ConceptMap",
+    "GraphDefinition",
+    "ImplementationGuide",
+    "MessageDefinition",
+    "NamingSystem",
+    "OperationDefinition",
+    "SearchParameter",
+    "StructureDefinition",
+    "StructureMap",
+    "TerminologyCapabilities",
+    "ValueSet",
+];
+
+fn is_conformance_resource_type(resource_type: &str) -> bool {
+    CONFORMANCE_RESOURCE_TYPES.contains(&resource_type)
+}
+
 /// Loaded FHIR package with manifest, optional index, and resources.
 ///
 /// Resources are automatically indexed by ID, canonical URL, and type for fast lookups.
@@ -308,13 +460,22 @@ pub type PackageResult<T> = Result<T, PackageError>;
 pub struct FhirPackage {
     pub manifest: PackageManifest,
     pub index: Option<PackageIndex>,
-    pub resources: Vec<Value>,
-    pub examples: Vec<Value>,
+    // Private so that every mutation goes through `add_resource`/`add_example` and keeps the
+    // indices below in sync.
+    resources: Vec<Value>,
+    examples: Vec<Value>,
+
+    // Indices into `resources`/`examples` for fast lookups
+    resources_by_id: HashMap<String, ResourceSlot>,
+    resources_by_url: HashMap<String, ResourceSlot>,
+    resources_by_type: HashMap<String, Vec<ResourceSlot>>,
+}
 
-    // Indexed resources for fast lookups
-    resources_by_id: HashMap<String, Value>,
-    resources_by_url: HashMap<String, Value>,
-    resources_by_type: HashMap<String, Vec<Value>>,
+/// Position of an indexed resource within a package.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ResourceSlot {
+    Conformance(usize),
+    Example(usize),
 }
 
 impl FhirPackage {
@@ -337,7 +498,15 @@ impl FhirPackage {
     }
 
     /// Load package from tar.gz reader.
-    pub fn from_tar_gz<R: Read>(mut reader: R) -> PackageResult<Self> {
+    pub fn from_tar_gz<R: Read>(reader: R) -> PackageResult<Self> {
+        Self::from_tar_gz_with_options(reader, LoadOptions::default())
+    }
+
+    /// Load package from tar.gz reader with explicit [`LoadOptions`].
+    pub fn from_tar_gz_with_options<R: Read>(
+        mut reader: R,
+        options: LoadOptions,
+    ) -> PackageResult<Self> {
         let mut decoder = GzDecoder::new(&mut reader);
         let mut decompressed = Vec::new();
         decoder.read_to_end(&mut decompressed)?;
@@ -361,14 +530,22 @@ impl FhirPackage {
 
         let index = file_map
             .get("package/.index.json")
-            .and_then(|bytes| Self::parse_json::<PackageIndex>(bytes).ok());
+            .map(|bytes| Self::parse_index(bytes))
+            .transpose()?
+            .flatten();
 
-        let resources = Self::load_resources_from_map(
-            &file_map,
-            "package/",
-            &[manifest_path, "package/.index.json"],
-        )?;
-        let examples = Self::load_resources_from_map(&file_map, "package/examples/", &[])?;
+        let (resources, examples) = if options.classify_by_index {
+            Self::classify_resources_from_map(&file_map, index.as_ref())?
+        } else {
+            (
+                Self::load_resources_from_map(
+                    &file_map,
+                    "package/",
+                    &[manifest_path, "package/.index.json"],
+                )?,
+                Self::load_resources_from_map(&file_map, "package/examples/", &[])?,
+            )
+        };
 
         let mut package = Self {
             manifest,
@@ -405,7 +582,9 @@ impl FhirPackage {
             .exists()
             .then(|| package_dir.join(".index.json"))
             .and_then(|p| fs::read(p).ok())
-            .and_then(|bytes| Self::parse_json::<PackageIndex>(&bytes).ok());
+            .map(|bytes| Self::parse_index(&bytes))
+            .transpose()?
+            .flatten();
 
         let resources =
             Self::load_resources_from_dir(package_dir, &["package.json", ".index.json"])?;
@@ -446,61 +625,134 @@ impl FhirPackage {
         self.resources.iter().chain(self.examples.iter()).collect()
     }
 
+    /// Append a conformance resource and index it.
+    pub fn add_resource(&mut self, resource: Value) {
+        self.resources.push(resource);
+        self.index_resource(ResourceSlot::Conformance(self.resources.len() - 1));
+    }
+
+    /// Append an example and index it.
+    pub fn add_example(&mut self, example: Value) {
+        self.examples.push(example);
+        self.index_resource(ResourceSlot::Example(self.examples.len() - 1));
+    }
+
     pub fn resources_by_type(&self, resource_type: &str) -> (Vec<&Value>, Vec<&Value>) {
-        let filter =
-            |r: &&Value| r.get("resourceType").and_then(Value::as_str) == Some(resource_type);
-        (
-            self.resources.iter().filter(filter).collect(),
-            self.examples.iter().filter(filter).collect(),
-        )
+        let mut conformance = Vec::new();
+        let mut examples = Vec::new();
+        for slot in self
+            .resources_by_type
+            .get(resource_type)
+            .into_iter()
+            .flatten()
+        {
+            match (slot, self.resolve(*slot)) {
+                (ResourceSlot::Conformance(_), Some(r)) => conformance.push(r),
+                (ResourceSlot::Example(_), Some(r)) => examples.push(r),
+                (_, None) => {}
+            }
+        }
+        (conformance, examples)
     }
 
     pub fn resource_by_id(&self, id: &str) -> Option<&Value> {
-        self.resources_by_id.get(id)
+        self.resources_by_id
+            .get(id)
+            .and_then(|slot| self.resolve(*slot))
     }
 
     pub fn resource_by_url(&self, url: &str) -> Option<&Value> {
-        self.resources_by_url.get(url)
+        self.resources_by_url
+            .get(url)
+            .and_then(|slot| self.resolve(*slot))
     }
 
-    pub fn resources_of_type(&self, resource_type: &str) -> Option<&[Value]> {
+    /// Conformance resources and examples of a type, in package order.
+    pub fn resources_of_type<'a>(
+        &'a self,
+        resource_type: &str,
+    ) -> impl Iterator<Item = &'a Value> + 'a {
         self.resources_by_type
             .get(resource_type)
-            .map(|v| v.as_slice())
+            .into_iter()
+            .flatten()
+            .filter_map(|slot| self.resolve(*slot))
+    }
+
+    /// Deterministic SHA-256 (lowercase hex) of the package's logical content.
+    ///
+    /// Hashes the manifest and every conformance resource and example as [`canonical_json`],
+    /// with resources in sorted order, so file order, archive layout and formatting do not
+    /// affect the result. The derived `.index.json` is not included.
+    pub fn content_hash(&self) -> String {
+        let mut hasher = Sha256::new();
+        // A derived `Serialize` with string map keys cannot fail.
+        let manifest = serde_json::to_value(&self.manifest).unwrap_or(Value::Null);
+        hasher.update(b"manifest\n");
+        hasher.update(canonical_json(&manifest));
+
+        for (section, resources) in [("resources", &self.resources), ("examples", &self.examples)] {
+            let mut rendered: Vec<String> = resources.iter().map(canonical_json).collect();
+            rendered.sort();
+            hasher.update(format!("\n{} {}", section, rendered.len()));
+            for resource in rendered {
+                hasher.update(b"\n");
+                hasher.update(resource);
+            }
+        }
+
+        hex::encode(hasher.finalize())
+    }
+
+    fn resolve(&self, slot: ResourceSlot) -> Option<&Value> {
+        match slot {
+            ResourceSlot::Conformance(i) => self.resources.get(i),
+            ResourceSlot::Example(i) => self.examples.get(i),
+        }
     }
 
     /// Build indices from resources for fast lookups
     fn build_indices(&mut self) {
-        let resources: Vec<Value> = self.resources.clone();
-        let examples: Vec<Value> = self.examples.clone();
-
-        for resource in resources {
-            self.index_resource(resource);
+        for i in 0..self.resources.len() {
+            self.index_resource(ResourceSlot::Conformance(i));
         }
-        for resource in examples {
-            self.index_resource(resource);
+        for i in 0..self.examples.len() {
+            self.index_resource(ResourceSlot::Example(i));
         }
     }
 
     /// Index a single resource by ID, URL, and type
-    fn index_resource(&mut self, resource: Value) {
-        if let Some(resource_type) = resource.get("resourceType").and_then(Value::as_str) {
-            // Index by type
-            self.resources_by_type
-                .entry(resource_type.to_string())
-                .or_default()
-                .push(resource.clone());
-
-            // Index by ID
-            if let Some(id) = resource.get("id").and_then(Value::as_str) {
-                self.resources_by_id
-                    .insert(id.to_string(), resource.clone());
-            }
+    fn index_resource(&mut self, slot: ResourceSlot) {
+        let resource = match slot {
+            ResourceSlot::Conformance(i) => &self.resources[i],
+            ResourceSlot::Example(i) => &self.examples[i],
+        };
+        let Some(resource_type) = resource.get("resourceType").and_then(Value::as_str) else {
+            return;
+        };
 
-            // Index by canonical URL
-            if let Some(url) = resource.get("url").and_then(Value::as_str) {
-                self.resources_by_url.insert(url.to_string(), resource);
-            }
+        // Index by type, keeping conformance resources ahead of examples
+        let slots = self
+            .resources_by_type
+            .entry(resource_type.to_string())
+            .or_default();
+        let position = match slot {
+            ResourceSlot::Conformance(_) => slots
+                .iter()
+                .position(|s| matches!(s, ResourceSlot::Example(_)))
+                .unwrap_or(slots.len()),
+            ResourceSlot::Example(_) => slots.len(),
+        };
+        slots.insert(position, slot);
+
+        // Index by ID
+        if let Some(id) = resource.get("id").and_then(Value::as_str) {
+            self.resources_by_id.insert(id.to_string(), slot);
+        }
+
+        // Index by canonical URL
+        if let Some(url) = resource.get("url").and_then(Value::as_str) {
+            self.resources_by_url.insert(url.to_string(), slot);
         }
     }
 
@@ -509,6 +761,17 @@ impl FhirPackage {
         Ok(serde_json::from_str(&cleaned)?)
     }
 
+    /// Parse `.index.json`, which is derived data: an index that does not parse is skipped, but
+    /// one with an unknown `index-version` is an error rather than being dropped.
+    fn parse_index(bytes: &[u8]) -> PackageResult<Option<PackageIndex>> {
+        let Ok(index) = Self::parse_json::<PackageIndex>(bytes) else {
+            return Ok(None);
+        };
+        index.validate()?;
+        Ok(Some(index))
+    }
+
+    /// JSON files directly under `prefix` (not in subfolders, matching directory loading).
     fn load_resources_from_map(
         file_map: &HashMap<String, Vec<u8>>,
         prefix: &str,
@@ -517,7 +780,8 @@ impl FhirPackage {
         file_map
             .iter()
             .filter(|(path, _)| {
-                path.starts_with(prefix)
+                path.strip_prefix(prefix)
+                    .is_some_and(|name| !name.contains('/'))
                     && path.ends_with(".json")
                     && !exclude.contains(&path.as_str())
             })
@@ -525,6 +789,60 @@ impl FhirPackage {
             .collect()
     }
 
+    /// Split the JSON files directly under `package/` and `package/examples/` into conformance
+    /// resources and examples, in path order.
+    ///
+    /// The resource type is taken from the package index entry when listed, otherwise from the
+    /// resource itself. Known conformance types are conformance resources wherever they are
+    /// placed; anything else is an example. Other subfolders are ignored, as in path-based
+    /// loading.
+    fn classify_resources_from_map(
+        file_map: &HashMap<String, Vec<u8>>,
+        index: Option<&PackageIndex>,
+    ) -> PackageResult<(Vec<Value>, Vec<Value>)> {
+        let indexed_types: HashMap<&str, &str> = index
+            .map(|index| {
+                index
+                    .files
+                    .iter()
+                    .filter_map(|f| Some((f.filename.as_str(), f.resource_type.as_deref()?)))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut paths: Vec<&String> = file_map.keys().collect();
+        paths.sort();
+
+        let mut resources = Vec::new();
+        let mut examples = Vec::new();
+        for path in paths {
+            let Some(relative) = path.strip_prefix("package/") else {
+                continue;
+            };
+            let name = relative.strip_prefix("examples/").unwrap_or(relative);
+            if name.contains('/')
+                || !name.ends_with(".json")
+                || relative == "package.json"
+                || name == ".index.json"
+            {
+                continue;
+            }
+            let contents = &file_map[path];
+
+            let resource: Value = Self::parse_json(contents)?;
+            let resource_type = indexed_types
+                .get(relative)
+                .copied()
+                .or_else(|| resource.get("resourceType").and_then(Value::as_str));
+            if resource_type.is_some_and(is_conformance_resource_type) {
+                resources.push(resource);
+            } else {
+                examples.push(resource);
+            }
+        }
+        Ok((resources, examples))
+    }
+
     fn load_resources_from_dir(dir: &Path, exclude: &[&str]) -> PackageResult<Vec<Value>> {
         let mut resources = Vec::new();
         for entry in fs::read_dir(dir)? {
@@ -559,11 +877,278 @@ impl FhirPackage {
     }
 }
 
+/// Serialize JSON canonically: object keys sorted, no insignificant whitespace.
+///
+/// Equal values always serialize to the same string, whatever their key order or original
+/// formatting.
+pub fn canonical_json(value: &Value) -> String {
+    let mut out = String::new();
+    write_canonical_json(value, &mut out);
+    out
+}
+
+fn write_canonical_json(value: &Value, out: &mut String) {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(&String, &Value)> = map.iter().collect();
+            entries.sort_by_key(|(key, _)| *key);
+            out.push('{');
+            for (i, (key, value)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push(':');
+                write_canonical_json(value, out);
+            }
+            out.push('}');
+        }
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical_json(item, out);
+            }
+            out.push(']');
+        }
+        scalar => out.push_str(&scalar.to_string()),
+    }
+}
+
+/// FHIR package whose resources are kept as raw JSON until they are requested.
+///
+/// Loading only reads a [`ResourceHeader`] from each resource, so the cost of building full
+/// JSON trees is paid per resource by [`RawResource::parse`]. Resources are split into
+/// conformance resources and examples by path, like [`FhirPackage::from_directory`].
+#[derive(Debug, Clone)]
+pub struct RawFhirPackage {
+    pub manifest: PackageManifest,
+    resources: Vec<RawResource>,
+    examples: Vec<RawResource>,
+}
+
+/// A package resource as unparsed JSON, with the header fields needed to index it.
+#[derive(Debug, Clone)]
+pub struct RawResource {
+    header: ResourceHeader,
+    json: Box<str>,
+}
+
+/// Top-level fields of a resource that identify and version it.
+///
+/// Deserializing a header skips every other element without building it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct ResourceHeader {
+    #[serde(rename = "resourceType")]
+    pub resource_type: Option<String>,
+    pub id: Option<String>,
+    pub url: Option<String>,
+    pub version: Option<String>,
+    #[serde(rename = "versionAlgorithmString")]
+    pub version_algorithm_string: Option<String>,
+    #[serde(rename = "versionAlgorithmCoding")]
+    pub version_algorithm_coding: Option<HeaderCoding>,
+}
+
+/// The `code` of a header `Coding`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct HeaderCoding {
+    pub code: Option<String>,
+}
+
+impl ResourceHeader {
+    /// `versionAlgorithmString`, or else the code of `versionAlgorithmCoding`.
+    pub fn version_algorithm(&self) -> Option<&str> {
+        self.version_algorithm_string.as_deref().or_else(|| {
+            self.version_algorithm_coding
+                .as_ref()
+                .and_then(|coding| coding.code.as_deref())
+        })
+    }
+}
+
+impl RawResource {
+    /// Wrap resource JSON, reading its header. Fails when `bytes` is not valid JSON.
+    pub fn from_bytes(bytes: &[u8]) -> PackageResult<Self> {
+        let json = FhirPackage::clean_bytes(bytes)?;
+        let header = serde_json::from_str(&json)?;
+        Ok(Self {
+            header,
+            json: json.into_boxed_str(),
+        })
+    }
+
+    pub fn header(&self) -> &ResourceHeader {
+        &self.header
+    }
+
+    pub fn json(&self) -> &str {
+        &self.json
+    }
+
+    /// Parse the full resource.
+    pub fn parse(&self) -> PackageResult<Value> {
+        Ok(serde_json::from_str(&self.json)?)
+    }
+}
+
+impl RawFhirPackage {
+    /// Load package from tar.gz bytes, keeping resources unparsed.
+    pub fn from_tar_gz_bytes(bytes: &[u8]) -> PackageResult<Self> {
+        let mut archive = Archive::new(GzDecoder::new(bytes));
+        let mut manifest = None;
+        let mut resources = Vec::new();
+        let mut examples = Vec::new();
+
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            let path = entry.path()?.to_string_lossy().to_string();
+            let Some(relative) = path.strip_prefix("package/") else {
+                continue;
+            };
+            let (target, name) = match relative.strip_prefix("examples/") {
+                Some(name) => (&mut examples, name),
+                None => (&mut resources, relative),
+            };
+            if name.contains('/') || !name.ends_with(".json") || name == ".index.json" {
+                continue;
+            }
+            let mut contents = Vec::new();
+            entry.read_to_end(&mut contents)?;
+            if relative == "package.json" {
+                manifest = Some(FhirPackage::parse_json::<PackageManifest>(&contents)?);
+                continue;
+            }
+            target.push((path, RawResource::from_bytes(&contents)?));
+        }
+
+        let manifest =
+            manifest.ok_or_else(|| PackageError::MissingFile("package/package.json".into()))?;
+        Ok(Self::new_sorted(manifest, resources, examples))
+    }
+
+    /// Load package from directory, keeping resources unparsed.
+    pub fn from_directory(package_dir: &Path) -> PackageResult<Self> {
+        let manifest_path = package_dir.join("package.json");
+        if !manifest_path.exists() {
+            return Err(PackageError::MissingFile(
+                manifest_path.to_string_lossy().into(),
+            ));
+        }
+        let manifest = FhirPackage::parse_json::<PackageManifest>(&fs::read(manifest_path)?)?;
+
+        let resources = Self::read_dir(package_dir, &["package.json", ".index.json"])?;
+        let examples_dir = package_dir.join("examples");
+        let examples = if examples_dir.exists() {
+            Self::read_dir(&examples_dir, &[])?
+        } else {
+            Vec::new()
+        };
+        Ok(Self::new_sorted(manifest, resources, examples))
+    }
+
+    /// Serialize the resources of an already parsed package.
+    pub fn from_package(package: &FhirPackage) -> Self {
+        let raw = |resource: &Value| RawResource {
+            header: ResourceHeader::deserialize(resource).unwrap_or_default(),
+            json: resource.to_string().into_boxed_str(),
+        };
+        Self {
+            manifest: package.manifest.clone(),
+            resources: package.conformance_resources().iter().map(raw).collect(),
+            examples: package.example_resources().iter().map(raw).collect(),
+        }
+    }
+
+    pub fn conformance_resources(&self) -> &[RawResource] {
+        &self.resources
+    }
+
+    pub fn example_resources(&self) -> &[RawResource] {
+        &self.examples
+    }
+
+    fn read_dir(dir: &Path, exclude: &[&str]) -> PackageResult<Vec<(String, RawResource)>> {
+        let mut resources = Vec::new();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            if path.extension() == Some("json".as_ref()) && !exclude.contains(&name) {
+                let resource = RawResource::from_bytes(&fs::read(&path)?)?;
+                resources.push((name.to_string(), resource));
+            }
+        }
+        Ok(resources)
+    }
+
+    /// Order resources by path so loading is deterministic.
+    fn new_sorted(
+        manifest: PackageManifest,
+        mut resources: Vec<(String, RawResource)>,
+        mut examples: Vec<(String, RawResource)>,
+    ) -> Self {
+        resources.sort_by(|a, b| a.0.cmp(&b.0));
+        examples.sort_by(|a, b| a.0.cmp(&b.0));
+        Self {
+            manifest,
+            resources: resources.into_iter().map(|(_, r)| r).collect(),
+            examples: examples.into_iter().map(|(_, r)| r).collect(),
+        }
+    }
+}
+
+/// Read a single resource `package/{filename}` from a tar.gz package.
+///
+/// Entries are streamed and only the requested file is parsed, so the rest of the package is
+/// never loaded. Returns `None` when the archive has no such file.
+pub fn read_resource_from_tar_gz_bytes(
+    bytes: &[u8],
+    filename: &str,
+) -> PackageResult<Option<Value>> {
+    let target = format!("package/{}", filename);
+    let mut archive = Archive::new(GzDecoder::new(bytes));
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if entry.path()?.to_string_lossy() != target {
+            continue;
+        }
+        let mut contents = Vec::new();
+        entry.read_to_end(&mut contents)?;
+        return FhirPackage::parse_json(&contents).map(Some);
+    }
+
+    Ok(None)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use serde_json::json;
 
+    fn tar_gz(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        let mut builder = tar::Builder::new(encoder);
+        for (path, contents) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, path, *contents)
+                .expect("append entry");
+        }
+        builder
+            .into_inner()
+            .expect("finish tar")
+            .finish()
+            .expect("finish gzip")
+    }
+
     #[test]
     fn manifest_matches_spec_example() {
         let manifest_json = json!({
@@ -607,6 +1192,131 @@ mod tests {
         assert_eq!(round_trip["dependencies"], manifest_json["dependencies"]);
     }
 
+    #[test]
+    fn strict_validation_requires_absolute_urls() {
+        let manifest = |canonical: &str| -> PackageManifest {
+            serde_json::from_value(json!({
+                "name": "example.ig",
+                "version": "1.0.0",
+                "canonical": canonical,
+                "homepage": "https://example.org/ig/index.html",
+                "author": "example"
+            }))
+            .expect("deserializes")
+        };
+
+        assert!(manifest("http://example.org/fhir/ig")
+            .validate(true)
+            .is_ok());
+
+        let relative = manifest("example.org/fhir/ig");
+        assert!(relative.validate(false).is_ok());
+        match relative.validate(true) {
+            Err(PackageError::ValidationError(msg)) => {
+                assert!(msg.contains("canonical"), "{msg}");
+                assert!(msg.contains("example.org/fhir/ig"), "{msg}");
+            }
+            other => panic!("expected validation error, got {other:?}"),
+        }
+
+        assert!(manifest("http:///fhir/ig").validate(true).is_err());
+    }
+
+    #[test]
+    fn strict_validation_rejects_unsupported_dependency_ranges() {
+        let manifest = |reference: &str| -> PackageManifest {
+            serde_json::from_value(json!({
+                "name": "example.ig",
+                "version": "1.0.0",
+                "author": "example",
+                "dependencies": {
+                    "hl7.fhir.r4.core": "4.0.1",
+                    "example.base": reference
+                }
+            }))
+            .expect("deserializes")
+        };
+
+        assert!(manifest("1.2.x").validate(true).is_ok());
+
+        for reference in [">=1.0.0", "^1.2", "~1.2.3", "1.x || 2.x"] {
+            let ranged = manifest(reference);
+            assert!(ranged.validate(false).is_ok());
+            match ranged.validate(true) {
+                Err(PackageError::ValidationError(msg)) => {
+                    assert!(msg.contains("example.base"), "{msg}");
+                    assert!(msg.contains("unsupported version range"), "{msg}");
+                }
+                other => panic!("expected validation error for {reference}, got {other:?}"),
+            }
+        }
+
+        match manifest("1.2.3$").validate(true) {
+            Err(PackageError::ValidationError(msg)) => {
+                assert!(msg.starts_with("Dependency 'example.base'"), "{msg}");
+            }
+            other => panic!("expected validation error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn effective_fhir_versions_prefers_explicit_versions() {
+        let manifest: PackageManifest = serde_json::from_value(json!({
+            "name": "example.ig",
+            "version": "1.0.0",
+            "fhirVersions": ["4.0.1"],
+            "dependencies": { "hl7.fhir.r5.core": "5.0.0" },
+            "author": "example"
+        }))
+        .expect("deserializes");
+
+        assert_eq!(
+            manifest.effective_fhir_versions(),
+            vec!["4.0.1".to_string()]
+        );
+    }
+
+    #[test]
+    fn effective_fhir_versions_infers_from_core_dependency() {
+        let manifest: PackageManifest = serde_json::from_value(json!({
+            "name": "example.ig",
+            "version": "1.0.0",
+            "dependencies": {
+                "hl7.fhir.r4.core": "4.0.1",
+                "hl7.fhir.us.core": "6.1.0",
+                "hl7.terminology.r4": "5.0.0"
+            },
+            "author": "example"
+        }))
+        .expect("deserializes");
+
+        assert_eq!(
+            manifest.effective_fhir_versions(),
+            vec!["4.0.1".to_string()]
+        );
+
+        let legacy: PackageManifest = serde_json::from_value(json!({
+            "name": "example.legacy",
+            "version": "0.1.0",
+            "dependencies": { "hl7.fhir.core": "3.0.1", "hl7.fhir.r3.core": "3.0.2" },
+            "author": "example"
+        }))
+        .expect("deserializes");
+
+        assert_eq!(
+            legacy.effective_fhir_versions(),
+            vec!["3.0.1".to_string(), "3.0.2".to_string()]
+        );
+
+        let none: PackageManifest = serde_json::from_value(json!({
+            "name": "example.none",
+            "version": "0.1.0",
+            "author": "example"
+        }))
+        .expect("deserializes");
+        assert!(none.effective_fhir_versions().is_empty());
+    }
+
     #[test]
     fn index_round_trips() {
         let index_json = json!({
@@ -628,12 +1338,265 @@ mod tests {
 
         assert_eq!(index.index_version, 1);
         assert_eq!(index.files.len(), 1);
-        assert_eq!(index.files[0].resource_type, "StructureDefinition");
+        assert_eq!(
+            index.files[0].resource_type.as_deref(),
+            Some("StructureDefinition")
+        );
 
         let round_trip = serde_json::to_value(&index).expect("serializes");
         assert_eq!(round_trip, index_json);
     }
 
+    #[test]
+    fn index_v1_tolerates_entries_without_resource_type() {
+        let index: PackageIndex = serde_json::from_value(json!({
+            "index-version": 1,
+            "files": [
+                { "filename": "ig-r4.json" },
+                {
+                    "filename": "ValueSet-colors.json",
+                    "resourceType": "ValueSet",
+                    "id": "colors"
+                }
+            ]
+        }))
+        .expect("deserializes");
+
+        assert!(index.validate().is_ok());
+        assert_eq!(index.files[0].resource_type, None);
+        assert_eq!(index.files[1].resource_type.as_deref(), Some("ValueSet"));
+
+        let round_trip = serde_json::to_value(&index).expect("serializes");
+        assert!(round_trip["files"][0].get("resourceType").is_none());
+    }
+
+    #[test]
+    fn index_v2_exposes_new_fields() {
+        let index_json = json!({
+            "index-version": 2,
+            "files": [
+                {
+                    "filename": "CodeSystem-colors.json",
+                    "resourceType": "CodeSystem",
+                    "id": "colors",
+                    "url": "http://example.org/CodeSystem/colors",
+                    "version": "1.0.0",
+                    "content": "complete",
+                    "valueSet": "http://example.org/ValueSet/colors"
+                },
+                {
+                    "filename": "StructureDefinition-my-patient.json",
+                    "resourceType": "StructureDefinition",
+                    "id": "my-patient",
+                    "url": "http://example.org/StructureDefinition/my-patient",
+                    "kind": "resource",
+                    "type": "Patient",
+                    "derivation": "constraint"
+                }
+            ]
+        });
+
+        let index: PackageIndex = serde_json::from_value(index_json.clone()).expect("deserializes");
+
+        assert!(index.validate().is_ok());
+        assert_eq!(
+            index.files[0].value_set.as_deref(),
+            Some("http://example.org/ValueSet/colors")
+        );
+        assert_eq!(index.files[1].derivation.as_deref(), Some("constraint"));
+        assert!(index.files.iter().all(|f| f.extra.is_empty()));
+
+        let round_trip = serde_json::to_value(&index).expect("serializes");
+        assert_eq!(round_trip, index_json);
+    }
+
+    #[test]
+    fn index_rejects_unknown_version() {
+        let index: PackageIndex = serde_json::from_value(json!({
+            "index-version": 99,
+            "files": []
+        }))
+        .expect("deserializes");
+
+        match index.validate() {
+            Err(PackageError::InvalidStructure(msg)) => assert!(msg.contains("99"), "{msg}"),
+            other => panic!("expected invalid structure, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn tar_gz_with_unknown_index_version_is_rejected() {
+        let archive = tar_gz(&[
+            (
+                "package/package.json",
+                br#"{"name": "example.ig", "version": "1.0.0", "author": "example"}"#,
+            ),
+            (
+                "package/.index.json",
+                br#"{"index-version": 99, "files": []}"#,
+            ),
+        ]);
+
+        match FhirPackage::from_tar_gz_bytes(&archive) {
+            Err(PackageError::InvalidStructure(msg)) => assert!(msg.contains("99"), "{msg}"),
+            other => panic!("expected invalid structure, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn read_single_resource_from_tar_gz() {
+        let bytes = tar_gz(&[
+            (
+                "package/package.json",
+                br#"{"name": "example.ig", "version": "1.0.0", "author": "example"}"#,
+            ),
+            // Never parsed: only the requested entry is read.
+            ("package/Broken.json", b"{ not json"),
+            (
+                "package/ValueSet-colors.json",
+                "\u{feff}{\"resourceType\": \"ValueSet\", \"id\": \"colors\"}".as_bytes(),
+            ),
+        ]);
+
+        let value_set = read_resource_from_tar_gz_bytes(&bytes, "ValueSet-colors.json")
+            .expect("reads archive")
+            .expect("file present");
+        assert_eq!(value_set["resourceType"], "ValueSet");
+        assert_eq!(value_set["id"], "colors");
+
+        assert!(read_resource_from_tar_gz_bytes(&bytes, "Missing.json")
+            .expect("reads archive")
+            .is_none());
+        assert!(read_resource_from_tar_gz_bytes(&bytes, "Broken.json").is_err());
+    }
+
+    #[test]
+    fn raw_package_reads_headers_without_parsing_resources() {
+        let bytes = tar_gz(&[
+            (
+                "package/package.json",
+                br#"{"name": "example.ig", "version": "1.0.0", "author": "example"}"#,
+            ),
+            (
+                "package/.index.json",
+                br#"{"index-version": 2, "files": []}"#,
+            ),
+            (
+                "package/ValueSet-b.json",
+                br#"{"resourceType": "ValueSet", "id": "b", "url": "http://example.org/ValueSet/b",
+                     "versionAlgorithmCoding": {"code": "semver"}, "compose": {"include": []}}"#,
+            ),
+            (
+                "package/CodeSystem-a.json",
+                br#"{"resourceType": "CodeSystem", "id": "a", "version": "2.0.0"}"#,
+            ),
+            (
+                "package/examples/Patient-p.json",
+                br#"{"resourceType": "Patient", "id": "p"}"#,
+            ),
+            (
+                "package/other/Patient-q.json",
+                br#"{"resourceType": "Patient"}"#,
+            ),
+        ]);
+
+        let package = RawFhirPackage::from_tar_gz_bytes(&bytes).expect("loads");
+        assert_eq!(package.manifest.name, "example.ig");
+
+        let headers: Vec<&ResourceHeader> = package
+            .conformance_resources()
+            .iter()
+            .map(RawResource::header)
+            .collect();
+        assert_eq!(headers[0].id.as_deref(), Some("a"));
+        assert_eq!(headers[0].version.as_deref(), Some("2.0.0"));
+        assert_eq!(
+            headers[1].url.as_deref(),
+            Some("http://example.org/ValueSet/b")
+        );
+        assert_eq!(headers[1].version_algorithm(), Some("semver"));
+        assert_eq!(package.example_resources().len(), 1);
+
+        let value_set = package.conformance_resources()[1].parse().expect("parses");
+        assert_eq!(value_set["compose"], json!({"include": []}));
+
+        let parsed = FhirPackage::from_tar_gz_bytes(&bytes).expect("loads");
+        let reserialized = RawFhirPackage::from_package(&parsed);
+        assert_eq!(reserialized.conformance_resources().len(), 2);
+        assert!(reserialized
+            .conformance_resources()
+            .iter()
+            .any(|r| r.header().id.as_deref() == Some("b")));
+    }
+
+    #[test]
+    fn index_driven_classification_finds_misplaced_examples() {
+        let bytes = tar_gz(&[
+            (
+                "package/package.json",
+                br#"{"name": "example.ig", "version": "1.0.0", "author": "example"}"#,
+            ),
+            (
+                "package/.index.json",
+                br#"{"index-version": 2, "files": [
+                    {"filename": "StructureDefinition-my-patient.json", "resourceType": "StructureDefinition", "id": "my-patient"},
+                    {"filename": "Patient-example.json", "resourceType": "Patient", "id": "example"}
+                ]}"#,
+            ),
+            (
+                "package/StructureDefinition-my-patient.json",
+                br#"{"resourceType": "StructureDefinition", "id": "my-patient"}"#,
+            ),
+            (
+                "package/Patient-example.json",
+                br#"{"resourceType": "Patient", "id": "example"}"#,
+            ),
+            (
+                "package/examples/StructureDefinition-other.json",
+                br#"{"resourceType": "StructureDefinition", "id": "other"}"#,
+            ),
+            (
+                "package/examples/Patient-another.json",
+                br#"{"resourceType": "Patient", "id": "another"}"#,
+            ),
+            ("package/other/broken.json", b"{ not json"),
+            (
+                "package/examples/nested/Patient-nested.json",
+                br#"{"resourceType": "Patient", "id": "nested"}"#,
+            ),
+        ]);
+
+        let ids = |resources: &[Value]| {
+            let mut ids: Vec<String> = resources
+                .iter()
+                .map(|r| r["id"].as_str().unwrap().to_string())
+                .collect();
+            ids.sort();
+            ids
+        };
+
+        let by_path = FhirPackage::from_tar_gz_bytes(&bytes).expect("loads");
+        assert!(ids(by_path.conformance_resources()).contains(&"example".to_string()));
+
+        let options = LoadOptions {
+            classify_by_index: true,
+        };
+        let by_index =
+            FhirPackage::from_tar_gz_with_options(bytes.as_slice(), options).expect("loads");
+        assert_eq!(
+            ids(by_index.conformance_resources()),
+            vec!["my-patient", "other"]
+        );
+        // Only direct children are loaded, in path order
+        let example_ids: Vec<&str> = by_index
+            .example_resources()
+            .iter()
+            .filter_map(|r| r["id"].as_str())
+            .collect();
+        assert_eq!(example_ids, vec!["example", "another"]);
+        assert!(by_index.resource_by_id("example").is_some());
+    }
+
     #[test]
     fn manifest_from_submodule_case_new_format() {
         let raw = include_str!(concat!(
@@ -699,6 +1662,168 @@ mod tests {
         assert_eq!(examples.len(), 0);
     }
 
+    #[test]
+    fn indices_borrow_package_resources() {
+        let manifest: PackageManifest = serde_json::from_value(json!({
+            "name": "example.ig",
+            "version": "1.0.0",
+            "author": "example"
+        }))
+        .expect("deserializes");
+        let package = FhirPackage::new(
+            manifest,
+            vec![
+                json!({
+                    "resourceType": "StructureDefinition",
+                    "id": "sd-a",
+                    "url": "http://example.org/StructureDefinition/a"
+                }),
+                json!({"resourceType": "ValueSet", "id": "vs-a"}),
+            ],
+            vec![json!({"resourceType": "StructureDefinition", "id": "sd-example"})],
+        );
+
+        let by_id = package.resource_by_id("sd-a").expect("indexed by id");
+        let by_url = package
+            .resource_by_url("http://example.org/StructureDefinition/a")
+            .expect("indexed by url");
+        // Lookups point into the package's own storage rather than at copies.
+        assert!(std::ptr::eq(by_id, &package.resources[0]));
+        assert!(std::ptr::eq(by_url, &package.resources[0]));
+        assert!(std::ptr::eq(
+            package.resource_by_id("sd-example").unwrap(),
+            &package.examples[0]
+        ));
+
+        let of_type: Vec<&Value> = package.resources_of_type("StructureDefinition").collect();
+        assert_eq!(of_type.len(), 2);
+        assert!(std::ptr::eq(of_type[0], &package.resources[0]));
+        assert!(std::ptr::eq(of_type[1], &package.examples[0]));
+        assert_eq!(package.resources_of_type("Patient").count(), 0);
+
+        let (conformance, examples) = package.resources_by_type("StructureDefinition");
+        assert_eq!((conformance.len(), examples.len()), (1, 1));
+    }
+
+    #[test]
+    fn tar_gz_loads_only_direct_package_children() {
+        let manifest = br#"{"name": "example.ig", "version": "1.0.0", "author": "example"}"#;
+        let profile = br#"{"resourceType": "StructureDefinition", "id": "sd-a"}"#;
+        let example = br#"{"resourceType": "Patient", "id": "example"}"#;
+        let other = br#"{"resourceType": "Patient", "id": "other"}"#;
+
+        let archive = tar_gz(&[
+            ("package/package.json", manifest),
+            ("package/StructureDefinition-sd-a.json", profile),
+            ("package/examples/Patient-example.json", example),
+            ("package/other/Patient-other.json", other),
+            ("package/examples/nested/Patient-other.json", other),
+        ]);
+        let package = FhirPackage::from_tar_gz_bytes(&archive).expect("loads archive");
+
+        let ids = |resources: &[Value]| -> Vec<String> {
+            resources
+                .iter()
+                .filter_map(|r| r.get("id").and_then(Value::as_str).map(str::to_string))
+                .collect()
+        };
+        assert_eq!(ids(&package.resources), vec!["sd-a"]);
+        assert_eq!(ids(&package.examples), vec!["example"]);
+    }
+
+    #[test]
+    fn content_hash_ignores_layout_and_formatting() {
+        let manifest = br#"{"name": "example.ig", "version": "1.0.0", "author": "example",
+            "dependencies": {"hl7.fhir.r4.core": "4.0.1", "example.base": "1.0.0"}}"#;
+        let profile = br#"{"resourceType": "StructureDefinition", "id": "sd-a",
+            "url": "http://example.org/StructureDefinition/a"}"#;
+        let value_set = br#"{"resourceType":"ValueSet","id":"vs-a","status":"active"}"#;
+        let archive = tar_gz(&[
+            ("package/ValueSet-vs-a.json", value_set),
+            ("package/package.json", manifest),
+            ("package/StructureDefinition-sd-a.json", profile),
+        ]);
+        let from_archive = FhirPackage::from_tar_gz_bytes(&archive).expect("loads archive");
+
+        let dir = std::env::temp_dir().join(format!(
+            "ferrum-package-content-hash-{}",
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir).expect("create package dir");
+        let reformatted = |bytes: &[u8]| {
+            let value: Value = serde_json::from_slice(bytes).unwrap();
+            serde_json::to_vec_pretty(&value).unwrap()
+        };
+        fs::write(
+            dir.join("package.json"),
+            br#"{"author": "example", "dependencies": {"example.base": "1.0.0",
+                "hl7.fhir.r4.core": "4.0.1"}, "version": "1.0.0", "name": "example.ig"}"#,
+        )
+        .unwrap();
+        fs::write(dir.join("a.json"), reformatted(value_set)).unwrap();
+        fs::write(dir.join("b.json"), reformatted(profile)).unwrap();
+        let from_directory = FhirPackage::from_directory(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+        let from_directory = from_directory.expect("loads directory");
+
+        let hash = from_archive.content_hash();
+        assert_eq!(hash.len(), 64);
+        assert_eq!(from_directory.content_hash(), hash);
+
+        // Moving a resource between conformance resources and examples is a content change.
+        let mut resources = from_archive.resources.clone();
+        let moved = resources.pop().unwrap();
+        let changed = FhirPackage::new(from_archive.manifest.clone(), resources, vec![moved]);
+        assert_ne!(changed.content_hash(), hash);
+    }
+
+    #[test]
+    fn added_resources_are_indexed() {
+        let manifest: PackageManifest = serde_json::from_value(json!({
+            "name": "example.ig",
+            "version": "1.0.0",
+            "author": "example"
+        }))
+        .expect("deserializes");
+        let mut package = FhirPackage::new(
+            manifest,
+            vec![],
+            vec![json!({"resourceType": "StructureDefinition", "id": "sd-example"})],
+        );
+        package.add_resource(json!({
+            "resourceType": "StructureDefinition",
+            "id": "sd-a",
+            "url": "http://example.org/StructureDefinition/a"
+        }));
+        package.add_example(json!({"resourceType": "Patient", "id": "example"}));
+
+        assert!(std::ptr::eq(
+            package
+                .resource_by_url("http://example.org/StructureDefinition/a")
+                .unwrap(),
+            &package.conformance_resources()[0]
+        ));
+        assert!(std::ptr::eq(
+            package.resource_by_id("example").unwrap(),
+            &package.example_resources()[1]
+        ));
+        let ids: Vec<&str> = package
+            .resources_of_type("StructureDefinition")
+            .filter_map(|r| r.get("id").and_then(Value::as_str))
+            .collect();
+        assert_eq!(ids, vec!["sd-a", "sd-example"]);
+    }
+
+    #[test]
+    fn canonical_json_sorts_keys() {
+        let value: Value =
+            serde_json::from_str(r#"{ "b": [ {"y": 1, "x": "\"q\""} ], "a": null }"#).unwrap();
+        assert_eq!(
+            canonical_json(&value),
+            r#"{"a":null,"b":[{"x":"\"q\"","y":1}]}"#
+        );
+    }
+
     #[test]
     fn test_validate_version_format() {
         // Valid versions
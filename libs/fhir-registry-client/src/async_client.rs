@@ -5,12 +5,12 @@
 use crate::async_simplifier::SimplifierClient;
 use crate::cache::{FileSystemCache, PackageCache};
 use crate::error::{Error, Result};
-use crate::models::SimplifierSearchParams;
+use crate::models::{ProgressCallback, SimplifierSearchParams};
 use crate::version_resolver::select_version;
 use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::Arc;
-use ferrum_package::FhirPackage;
+use ferrum_package::{FhirPackage, RawFhirPackage};
 
 /// Registry client for loading FHIR packages.
 ///
@@ -30,6 +30,16 @@ impl RegistryClient<FileSystemCache> {
         }
     }
 
+    /// Keep partial downloads in `<cache_root>/.downloads` so interrupted transfers can be
+    /// resumed. Disabled by default; the server enables it with
+    /// `fhir.resumable_package_downloads`.
+    pub fn set_resumable_downloads(&mut self, enabled: bool) {
+        let download_dir = enabled.then(|| self.cache.cache_root().join(".downloads"));
+        if let Some(simplifier) = &mut self.simplifier {
+            simplifier.set_download_dir(download_dir);
+        }
+    }
+
     /// Create a registry client without remote registry access (cache-only).
     pub fn cache_only(cache_dir: Option<PathBuf>) -> Self {
         Self {
@@ -56,6 +66,16 @@ impl<C: PackageCache + 'static> RegistryClient<C> {
         }
     }
 
+    /// Report download progress for packages fetched from the registry.
+    ///
+    /// The callback is invoked off the download task, so it may block (e.g. to redraw a
+    /// progress bar) without slowing the transfer.
+    pub fn set_progress_callback(&mut self, callback: ProgressCallback) {
+        if let Some(simplifier) = &mut self.simplifier {
+            simplifier.set_progress_callback(callback);
+        }
+    }
+
     async fn cache_has_package(&self, name: &str, version: &str) -> Result<bool> {
         let cache = self.cache.clone();
         let name = name.to_string();
@@ -84,6 +104,15 @@ impl<C: PackageCache + 'static> RegistryClient<C> {
             .map_err(|e| Error::Registry(format!("Cache task failed: {e}")))?
     }
 
+    async fn cache_get_raw_package(&self, name: &str, version: &str) -> Result<RawFhirPackage> {
+        let cache = self.cache.clone();
+        let name = name.to_string();
+        let version = version.to_string();
+        tokio::task::spawn_blocking(move || cache.get_raw_package(&name, &version))
+            .await
+            .map_err(|e| Error::Registry(format!("Cache task failed: {e}")))?
+    }
+
     async fn cache_store_package(&self, package: FhirPackage) -> Result<()> {
         let cache = self.cache.clone();
         let name = package.manifest.name.clone();
@@ -261,6 +290,39 @@ impl<C: PackageCache + 'static> RegistryClient<C> {
         Ok(loaded_packages.into_values().collect())
     }
 
+    /// Load a package with all transitive dependencies, keeping resources unparsed.
+    ///
+    /// Cached packages are read without parsing their resources; packages that have to be
+    /// downloaded are parsed once while being stored in the cache.
+    pub async fn load_raw_package_with_dependencies(
+        &self,
+        name: &str,
+        version: Option<&str>,
+    ) -> Result<Vec<RawFhirPackage>> {
+        let mut loaded_packages: HashMap<String, RawFhirPackage> = HashMap::new();
+        let mut pending = vec![(name.to_string(), version.map(|v| v.to_string()))];
+
+        while let Some((name, version)) = pending.pop() {
+            let version = self.resolve_version(&name, version.as_deref()).await?;
+            let package_key = format!("{}#{}", name, version);
+            if loaded_packages.contains_key(&package_key) {
+                continue;
+            }
+
+            let package = if self.cache_has_package(&name, &version).await? {
+                self.cache_get_raw_package(&name, &version).await?
+            } else {
+                RawFhirPackage::from_package(&self.load_or_download_package(&name, &version).await?)
+            };
+            for (dep_name, dep_version_range) in &package.manifest.dependencies {
+                pending.push((dep_name.clone(), Some(dep_version_range.clone())));
+            }
+            loaded_packages.insert(package_key, package);
+        }
+
+        Ok(loaded_packages.into_values().collect())
+    }
+
     /// Load package from cache or download from Simplifier if not cached.
     pub async fn load_or_download_package(&self, name: &str, version: &str) -> Result<FhirPackage> {
         if self.cache_has_package(name, version).await? {
@@ -294,6 +356,21 @@ impl<C: PackageCache + 'static> RegistryClient<C> {
         simplifier.search(params).await
     }
 
+    /// Fetch one page of search results from the Simplifier registry.
+    pub async fn search_packages_paged(
+        &self,
+        params: &SimplifierSearchParams,
+        page: usize,
+        page_size: usize,
+    ) -> Result<crate::models::SearchPage> {
+        let simplifier = self
+            .simplifier
+            .as_ref()
+            .ok_or_else(|| Error::Registry("Simplifier client not available".to_string()))?;
+
+        simplifier.search_paged(params, page, page_size).await
+    }
+
     /// Get available versions for a package from Simplifier.
     pub async fn get_package_versions(&self, package_name: &str) -> Result<Vec<String>> {
         let simplifier = self
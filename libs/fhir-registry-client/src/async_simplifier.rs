@@ -1,17 +1,39 @@
 //! Simplifier registry API client
 
 use crate::error::{Error, Result};
-use crate::models::{SimplifierSearchParams, SimplifierSearchResult};
-use reqwest::Client;
+use crate::models::{
+    DownloadProgress, ProgressCallback, SearchPage, SimplifierSearchParams, SimplifierSearchResult,
+};
+use reqwest::header::{CONTENT_RANGE, RANGE};
+use reqwest::{Client, StatusCode};
+use sha1::{Digest, Sha1};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
+use tokio::io::AsyncWriteExt;
 use ferrum_package::FhirPackage;
 
 const SIMPLIFIER_BASE_URL: &str = "https://packages.simplifier.net";
 
+/// Number of attempts for a resumable download before giving up.
+const MAX_DOWNLOAD_ATTEMPTS: usize = 3;
+
+/// Time allowed to establish a connection to the registry.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Time allowed between two reads of a response, so large tarballs can take as long as they
+/// need while a stalled transfer still fails.
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Distinguishes the partial files of concurrent downloads within one process.
+static DOWNLOAD_COUNTER: AtomicU64 = AtomicU64::new(0);
+
 /// Client for the Simplifier package registry.
 pub struct SimplifierClient {
     client: Client,
     base_url: String,
+    download_dir: Option<PathBuf>,
+    progress: Option<ProgressCallback>,
 }
 
 impl SimplifierClient {
@@ -22,8 +44,36 @@ impl SimplifierClient {
 
     /// Create a Simplifier client with a custom base URL.
     pub fn with_base_url(base_url: String) -> Result<Self> {
-        let client = Client::builder().timeout(Duration::from_secs(30)).build()?;
-        Ok(Self { client, base_url })
+        let client = Client::builder()
+            .connect_timeout(CONNECT_TIMEOUT)
+            .read_timeout(READ_TIMEOUT)
+            .build()?;
+        Ok(Self {
+            client,
+            base_url,
+            download_dir: None,
+            progress: None,
+        })
+    }
+
+    /// Stream package downloads through a partial file in `dir`.
+    ///
+    /// Each download writes its own partial file. One that still fails after retrying is
+    /// left behind as `<dir>/<name>#<version>.tgz.part`, and the next download of that
+    /// package resumes from its length with an HTTP range request.
+    pub fn with_download_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.download_dir = Some(dir.into());
+        self
+    }
+
+    /// Set or clear the directory for resumable downloads (see [`Self::with_download_dir`]).
+    pub fn set_download_dir(&mut self, dir: Option<PathBuf>) {
+        self.download_dir = dir;
+    }
+
+    /// Report [`DownloadProgress`] to `callback` while package tarballs are transferred.
+    pub fn set_progress_callback(&mut self, callback: ProgressCallback) {
+        self.progress = Some(callback);
     }
 
     /// Search for packages in the Simplifier registry.
@@ -32,20 +82,7 @@ impl SimplifierClient {
         params: &SimplifierSearchParams,
     ) -> Result<Vec<SimplifierSearchResult>> {
         let mut url = format!("{}/catalog", self.base_url);
-        let mut query_params = Vec::new();
-
-        if let Some(name) = &params.name {
-            query_params.push(format!("name={}", urlencoding::encode(name)));
-        }
-        if let Some(canonical) = &params.canonical {
-            query_params.push(format!("canonical={}", urlencoding::encode(canonical)));
-        }
-        if let Some(fhir_version) = &params.fhir_version {
-            query_params.push(format!("fhirversion={}", urlencoding::encode(fhir_version)));
-        }
-        if let Some(prerelease) = params.prerelease {
-            query_params.push(format!("prerelease={}", prerelease));
-        }
+        let query_params = Self::search_query(params);
 
         if !query_params.is_empty() {
             url.push('?');
@@ -65,6 +102,56 @@ impl SimplifierClient {
         Ok(results)
     }
 
+    /// Fetch one page of search results.
+    ///
+    /// `page` is zero-based. The Simplifier catalog has no paging parameters and answers with
+    /// every match in one response, so the page is cut from that list: `total` is the number
+    /// of matches and `next_page` is set only while results remain after this page.
+    pub async fn search_paged(
+        &self,
+        params: &SimplifierSearchParams,
+        page: usize,
+        page_size: usize,
+    ) -> Result<SearchPage> {
+        if page_size == 0 {
+            return Err(Error::Registry(
+                "Search page size must be positive".to_string(),
+            ));
+        }
+
+        let mut results = self.search(params).await?;
+        let total = results.len();
+        let start = page.saturating_mul(page_size).min(total);
+        let end = start.saturating_add(page_size).min(total);
+        results.truncate(end);
+        results.drain(..start);
+
+        Ok(SearchPage {
+            results,
+            total: Some(total as u64),
+            page,
+            next_page: (end < total).then(|| page + 1),
+        })
+    }
+
+    fn search_query(params: &SimplifierSearchParams) -> Vec<String> {
+        let mut query_params = Vec::new();
+
+        if let Some(name) = &params.name {
+            query_params.push(format!("name={}", urlencoding::encode(name)));
+        }
+        if let Some(canonical) = &params.canonical {
+            query_params.push(format!("canonical={}", urlencoding::encode(canonical)));
+        }
+        if let Some(fhir_version) = &params.fhir_version {
+            query_params.push(format!("fhirversion={}", urlencoding::encode(fhir_version)));
+        }
+        if let Some(prerelease) = params.prerelease {
+            query_params.push(format!("prerelease={}", prerelease));
+        }
+        query_params
+    }
+
     /// Get all versions for a package.
     pub async fn get_versions(&self, package_name: &str) -> Result<Vec<String>> {
         let url = format!("{}/{}", self.base_url, package_name);
@@ -95,10 +182,42 @@ impl SimplifierClient {
         Ok(versions)
     }
 
+    /// Get the SHA-1 checksum (`dist.shasum`) of a package tarball, if the registry lists one.
+    pub async fn get_tarball_shasum(
+        &self,
+        package_name: &str,
+        version: &str,
+    ) -> Result<Option<String>> {
+        let url = format!("{}/{}", self.base_url, package_name);
+        let response = self.client.get(&url).send().await?;
+
+        if !response.status().is_success() {
+            return Ok(None);
+        }
+
+        let package_metadata: serde_json::Value = response.json().await?;
+        Ok(package_metadata
+            .get("versions")
+            .and_then(|v| v.get(version))
+            .and_then(|v| v.get("dist"))
+            .and_then(|d| d.get("shasum"))
+            .and_then(|s| s.as_str())
+            .map(|s| s.to_ascii_lowercase()))
+    }
+
     /// Download a package from the Simplifier registry.
+    ///
+    /// With a download directory configured, the tarball is streamed to a `.part` file and
+    /// transport failures are retried by resuming from the bytes already on disk.
     pub async fn download_package(&self, package_name: &str, version: &str) -> Result<FhirPackage> {
+        if let Some(dir) = &self.download_dir {
+            return self
+                .download_package_resumable(dir, package_name, version)
+                .await;
+        }
+
         let url = format!("{}/{}/{}", self.base_url, package_name, version);
-        let response = self.client.get(&url).send().await?;
+        let mut response = self.client.get(&url).send().await?;
 
         if !response.status().is_success() {
             return Err(Error::PackageNotFound {
@@ -107,10 +226,276 @@ impl SimplifierClient {
             });
         }
 
-        let bytes = response.bytes().await?;
+        let total = response.content_length();
+        let mut progress = self.progress_reporter(package_name, version, 0, total);
+        let mut bytes = Vec::with_capacity(total.unwrap_or(0) as usize);
+        while let Some(chunk) = response.chunk().await? {
+            bytes.extend_from_slice(&chunk);
+            progress.advance(chunk.len());
+        }
+        progress.finish().await;
+
         let package = FhirPackage::from_tar_gz_bytes(&bytes)?;
         Ok(package)
     }
+
+    async fn download_package_resumable(
+        &self,
+        dir: &Path,
+        package_name: &str,
+        version: &str,
+    ) -> Result<FhirPackage> {
+        tokio::fs::create_dir_all(dir).await?;
+        let shared_path = dir.join(format!("{}#{}.tgz.part", package_name, version));
+        let part_path = dir.join(format!(
+            "{}#{}.{}-{}.tgz.part",
+            package_name,
+            version,
+            std::process::id(),
+            DOWNLOAD_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+
+        // Claim a partial file left by an earlier download. Renames are atomic, so at most
+        // one concurrent download resumes it and no two ever write the same file.
+        match tokio::fs::rename(&shared_path, &part_path).await {
+            Ok(()) => tracing::debug!("Resuming download of {}#{}", package_name, version),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+
+        let mut attempt = 1;
+        let total = loop {
+            match self
+                .fetch_into_part_file(package_name, version, &part_path)
+                .await
+            {
+                Ok(total) => break total,
+                Err(Error::Http(e)) if attempt < MAX_DOWNLOAD_ATTEMPTS => {
+                    tracing::warn!(
+                        "Download of {}#{} interrupted (attempt {}): {}",
+                        package_name,
+                        version,
+                        attempt,
+                        e
+                    );
+                    attempt += 1;
+                }
+                Err(e @ Error::Http(_)) => {
+                    // Hand the partial file over to the next download of this package.
+                    let _ = tokio::fs::rename(&part_path, &shared_path).await;
+                    return Err(e);
+                }
+                Err(e) => {
+                    let _ = tokio::fs::remove_file(&part_path).await;
+                    return Err(e);
+                }
+            }
+        };
+
+        let bytes = tokio::fs::read(&part_path).await?;
+        if let Some(total) = total {
+            if bytes.len() as u64 != total {
+                tokio::fs::remove_file(&part_path).await?;
+                return Err(Error::Registry(format!(
+                    "Download of {}#{} is incomplete: got {} of {} bytes",
+                    package_name,
+                    version,
+                    bytes.len(),
+                    total
+                )));
+            }
+        }
+
+        let expected_shasum = self
+            .get_tarball_shasum(package_name, version)
+            .await
+            .unwrap_or(None);
+        if let Some(expected) = expected_shasum {
+            let actual = sha1_hex(&bytes);
+            if actual != expected {
+                tokio::fs::remove_file(&part_path).await?;
+                return Err(Error::Registry(format!(
+                    "Checksum mismatch for {}#{}: expected {}, got {}",
+                    package_name, version, expected, actual
+                )));
+            }
+        }
+
+        let package = FhirPackage::from_tar_gz_bytes(&bytes);
+        tokio::fs::remove_file(&part_path).await?;
+        Ok(package?)
+    }
+
+    /// Append the rest of a package tarball to `part_path`, returning the full size if known.
+    ///
+    /// Resumes with `Range: bytes=N-` when a partial file exists. A server that ignores the
+    /// range (200 instead of 206) restarts the file from scratch.
+    async fn fetch_into_part_file(
+        &self,
+        package_name: &str,
+        version: &str,
+        part_path: &Path,
+    ) -> Result<Option<u64>> {
+        let url = format!("{}/{}/{}", self.base_url, package_name, version);
+        let existing = match tokio::fs::metadata(part_path).await {
+            Ok(metadata) => metadata.len(),
+            Err(_) => 0,
+        };
+
+        let mut request = self.client.get(&url);
+        if existing > 0 {
+            request = request.header(RANGE, format!("bytes={}-", existing));
+        }
+        let mut response = request.send().await?;
+
+        let mut options = tokio::fs::OpenOptions::new();
+        options.create(true);
+        let mut resumed_from = 0;
+        let total = match response.status() {
+            StatusCode::PARTIAL_CONTENT if existing > 0 => {
+                let (start, total) = response
+                    .headers()
+                    .get(CONTENT_RANGE)
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(parse_content_range)
+                    .ok_or_else(|| {
+                        Error::Registry(format!("Invalid Content-Range in response from {}", url))
+                    })?;
+                if start != existing {
+                    tokio::fs::remove_file(part_path).await?;
+                    return Err(Error::Registry(format!(
+                        "Server resumed {} at byte {} instead of {}",
+                        url, start, existing
+                    )));
+                }
+                options.append(true);
+                resumed_from = existing;
+                total
+            }
+            StatusCode::RANGE_NOT_SATISFIABLE if existing > 0 => {
+                // The partial file is stale (e.g. the tarball changed); start over
+                tokio::fs::remove_file(part_path).await?;
+                return Box::pin(self.fetch_into_part_file(package_name, version, part_path)).await;
+            }
+            status if status.is_success() => {
+                if existing > 0 {
+                    tracing::debug!("{} ignored range request, downloading in full", url);
+                }
+                options.write(true).truncate(true);
+                response.content_length()
+            }
+            _ => {
+                return Err(Error::PackageNotFound {
+                    name: package_name.to_string(),
+                    version: version.to_string(),
+                });
+            }
+        };
+
+        let mut file = options.open(part_path).await?;
+        let mut progress = self.progress_reporter(package_name, version, resumed_from, total);
+        while let Some(chunk) = response.chunk().await? {
+            file.write_all(&chunk).await?;
+            progress.advance(chunk.len());
+        }
+        file.flush().await?;
+        progress.finish().await;
+
+        Ok(total)
+    }
+
+    fn progress_reporter(
+        &self,
+        package_name: &str,
+        version: &str,
+        downloaded: u64,
+        total: Option<u64>,
+    ) -> ProgressReporter {
+        ProgressReporter::new(
+            self.progress.clone(),
+            format!("{}#{}", package_name, version),
+            downloaded,
+            total,
+        )
+    }
+}
+
+/// Forwards download progress to a [`ProgressCallback`].
+///
+/// The callback runs on a blocking worker fed through a channel, so a slow callback never
+/// stalls the transfer itself.
+struct ProgressReporter {
+    package: String,
+    downloaded: u64,
+    total: Option<u64>,
+    sender: Option<std::sync::mpsc::Sender<DownloadProgress>>,
+    worker: Option<tokio::task::JoinHandle<()>>,
+}
+
+impl ProgressReporter {
+    fn new(
+        callback: Option<ProgressCallback>,
+        package: String,
+        downloaded: u64,
+        total: Option<u64>,
+    ) -> Self {
+        let (sender, worker) = match callback {
+            Some(callback) => {
+                let (sender, receiver) = std::sync::mpsc::channel::<DownloadProgress>();
+                let worker = tokio::task::spawn_blocking(move || {
+                    for progress in receiver {
+                        callback(progress);
+                    }
+                });
+                (Some(sender), Some(worker))
+            }
+            None => (None, None),
+        };
+        Self {
+            package,
+            downloaded,
+            total,
+            sender,
+            worker,
+        }
+    }
+
+    fn advance(&mut self, bytes: usize) {
+        self.downloaded += bytes as u64;
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(DownloadProgress {
+                package: self.package.clone(),
+                downloaded: self.downloaded,
+                total: self.total,
+            });
+        }
+    }
+
+    /// Wait until every reported update has been delivered to the callback.
+    async fn finish(mut self) {
+        self.sender = None;
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.await;
+        }
+    }
+}
+
+/// Parse `Content-Range: bytes <start>-<end>/<total>` into the start offset and total size.
+fn parse_content_range(value: &str) -> Option<(u64, Option<u64>)> {
+    let (range, total) = value.strip_prefix("bytes ")?.split_once('/')?;
+    let (start, _end) = range.split_once('-')?;
+    let total = match total {
+        "*" => None,
+        t => Some(t.parse().ok()?),
+    };
+    Some((start.parse().ok()?, total))
+}
+
+fn sha1_hex(bytes: &[u8]) -> String {
+    Sha1::digest(bytes)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
 }
 
 impl Default for SimplifierClient {
@@ -121,6 +506,390 @@ impl Default for SimplifierClient {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+
+    fn package_tarball() -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+        let mut add = |path: &str, body: &str| {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(body.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, path, body.as_bytes())
+                .unwrap();
+        };
+        add(
+            "package/package.json",
+            r#"{"name": "example.fhir.test", "version": "1.0.0", "author": "test"}"#,
+        );
+        // Padding so the tarball is big enough to split meaningfully
+        let padding = "x".repeat(4096);
+        add(
+            "package/StructureDefinition-example.json",
+            &format!(
+                r#"{{"resourceType": "StructureDefinition", "id": "example", "description": "{}"}}"#,
+                padding
+            ),
+        );
+        let tar = builder.into_inner().unwrap();
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::none());
+        encoder.write_all(&tar).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    /// Read an HTTP request head (requests in these tests carry no body).
+    fn read_request(stream: &mut std::net::TcpStream) -> String {
+        let mut request = Vec::new();
+        let mut buf = [0u8; 1024];
+        while !request.ends_with(b"\r\n\r\n") {
+            let n = stream.read(&mut buf).unwrap();
+            if n == 0 {
+                break;
+            }
+            request.extend_from_slice(&buf[..n]);
+        }
+        String::from_utf8_lossy(&request).to_string()
+    }
+
+    /// Serve `tarball` at `/example.fhir.test/1.0.0`, honouring `Range` if `ranges` is set.
+    /// Returns the base URL and the `Range` header (if any) of each tarball request.
+    fn serve_tarball(tarball: Vec<u8>, ranges: bool) -> (String, Arc<Mutex<Vec<Option<String>>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let seen_by_server = seen.clone();
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let request = read_request(&mut stream);
+                let path = request.split_whitespace().nth(1).unwrap_or("").to_string();
+                let range = request.lines().find_map(|line| {
+                    let (name, value) = line.split_once(':')?;
+                    name.eq_ignore_ascii_case("range")
+                        .then(|| value.trim().to_string())
+                });
+
+                let (status, extra, body) = if path != "/example.fhir.test/1.0.0" {
+                    ("404 Not Found", String::new(), Vec::new())
+                } else {
+                    seen_by_server.lock().unwrap().push(range.clone());
+                    let start = range
+                        .filter(|_| ranges)
+                        .and_then(|r| r.strip_prefix("bytes=")?.strip_suffix('-')?.parse().ok());
+                    match start {
+                        Some(start) => (
+                            "206 Partial Content",
+                            format!(
+                                "Content-Range: bytes {}-{}/{}\r\n",
+                                start,
+                                tarball.len() - 1,
+                                tarball.len()
+                            ),
+                            tarball[start..].to_vec(),
+                        ),
+                        None => ("200 OK", String::new(), tarball.clone()),
+                    }
+                };
+                let head = format!(
+                    "HTTP/1.1 {}\r\nContent-Length: {}\r\n{}Connection: close\r\n\r\n",
+                    status,
+                    body.len(),
+                    extra
+                );
+                stream.write_all(head.as_bytes()).unwrap();
+                stream.write_all(&body).unwrap();
+            }
+        });
+
+        (base_url, seen)
+    }
+
+    /// Serve `body` once, written in `chunks` separate pieces with a pause in between.
+    fn serve_in_chunks(body: Vec<u8>, chunks: usize, content_length: bool) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            read_request(&mut stream);
+
+            let length = if content_length {
+                format!("Content-Length: {}\r\n", body.len())
+            } else {
+                String::new()
+            };
+            let head = format!("HTTP/1.1 200 OK\r\n{}Connection: close\r\n\r\n", length);
+            stream.write_all(head.as_bytes()).unwrap();
+            for piece in body.chunks(body.len().div_ceil(chunks)) {
+                stream.write_all(piece).unwrap();
+                stream.flush().unwrap();
+                std::thread::sleep(Duration::from_millis(20));
+            }
+        });
+
+        base_url
+    }
+
+    fn recording_callback() -> (ProgressCallback, Arc<Mutex<Vec<DownloadProgress>>>) {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let recorded = events.clone();
+        let callback: ProgressCallback = Arc::new(move |progress| {
+            recorded.lock().unwrap().push(progress);
+        });
+        (callback, events)
+    }
+
+    fn assert_increasing(events: &[DownloadProgress], size: usize) {
+        assert!(events.len() > 1, "expected several updates: {:?}", events);
+        assert!(events.windows(2).all(|w| w[0].downloaded < w[1].downloaded));
+        assert_eq!(events.last().unwrap().downloaded, size as u64);
+        assert!(events
+            .iter()
+            .all(|e| e.package == "example.fhir.test#1.0.0"));
+    }
+
+    #[tokio::test]
+    async fn reports_download_progress() {
+        let tarball = package_tarball();
+        let size = tarball.len();
+        let base_url = serve_in_chunks(tarball, 4, true);
+        let (callback, events) = recording_callback();
+
+        let mut client = SimplifierClient::with_base_url(base_url).unwrap();
+        client.set_progress_callback(callback);
+        client
+            .download_package("example.fhir.test", "1.0.0")
+            .await
+            .unwrap();
+
+        let events = events.lock().unwrap();
+        assert_increasing(&events, size);
+        assert!(events.iter().all(|e| e.total == Some(size as u64)));
+    }
+
+    #[tokio::test]
+    async fn reports_unknown_total_without_content_length() {
+        let tarball = package_tarball();
+        let size = tarball.len();
+        let base_url = serve_in_chunks(tarball, 4, false);
+        let (callback, events) = recording_callback();
+        let dir = temp_download_dir("progress");
+
+        let mut client = SimplifierClient::with_base_url(base_url)
+            .unwrap()
+            .with_download_dir(&dir);
+        client.set_progress_callback(callback);
+        client
+            .download_package("example.fhir.test", "1.0.0")
+            .await
+            .unwrap();
+
+        let events = events.lock().unwrap();
+        assert_increasing(&events, size);
+        assert!(events.iter().all(|e| e.total.is_none()));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    /// Serve a catalog of `total` packages, returning the full list to every request as the
+    /// Simplifier catalog does. Returns the base URL and the number of requests served.
+    fn serve_catalog(total: usize) -> (String, Arc<Mutex<usize>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(0));
+        let requests_seen = requests.clone();
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                read_request(&mut stream);
+                *requests_seen.lock().unwrap() += 1;
+
+                let results: Vec<serde_json::Value> = (0..total)
+                    .map(|i| {
+                        serde_json::json!({
+                            "Name": format!("example.package.{}", i),
+                            "Description": "Example package",
+                            "FHIRVersion": "4.0.1",
+                            "Version": "1.0.0"
+                        })
+                    })
+                    .collect();
+                let body = serde_json::to_vec(&results).unwrap();
+                let head = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    body.len()
+                );
+                stream.write_all(head.as_bytes()).unwrap();
+                stream.write_all(&body).unwrap();
+            }
+        });
+
+        (base_url, requests)
+    }
+
+    #[tokio::test]
+    async fn search_pages_stop_after_the_last() {
+        let (base_url, requests) = serve_catalog(3);
+        let client = SimplifierClient::with_base_url(base_url).unwrap();
+        let params = SimplifierSearchParams {
+            name: Some("example".to_string()),
+            ..Default::default()
+        };
+
+        let first = client.search_paged(&params, 0, 2).await.unwrap();
+        assert_eq!(first.results.len(), 2);
+        assert_eq!(first.total, Some(3));
+        assert_eq!(first.next_page, Some(1));
+
+        let last = client.search_paged(&params, 1, 2).await.unwrap();
+        assert_eq!(last.results.len(), 1);
+        assert_eq!(last.results[0].name, "example.package.2");
+        assert_eq!(last.total, Some(3));
+        assert_eq!(last.next_page, None);
+
+        // Following `next_page` visits each page once and stops after the last.
+        *requests.lock().unwrap() = 0;
+        let mut names = Vec::new();
+        let mut page = Some(0);
+        while let Some(number) = page {
+            let current = client.search_paged(&params, number, 2).await.unwrap();
+            names.extend(current.results.into_iter().map(|r| r.name));
+            page = current.next_page;
+        }
+        assert_eq!(
+            names,
+            vec![
+                "example.package.0",
+                "example.package.1",
+                "example.package.2"
+            ]
+        );
+        assert_eq!(*requests.lock().unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn search_page_past_the_end_is_empty() {
+        let (base_url, _requests) = serve_catalog(2);
+        let client = SimplifierClient::with_base_url(base_url).unwrap();
+        let params = SimplifierSearchParams::default();
+
+        let exact = client.search_paged(&params, 0, 2).await.unwrap();
+        assert_eq!(exact.results.len(), 2);
+        assert_eq!(exact.next_page, None);
+
+        let beyond = client.search_paged(&params, usize::MAX, 2).await.unwrap();
+        assert!(beyond.results.is_empty());
+        assert_eq!(beyond.total, Some(2));
+        assert_eq!(beyond.next_page, None);
+    }
+
+    fn temp_download_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "ferrum-registry-{}-{}-{}",
+            name,
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[tokio::test]
+    async fn resumes_partial_download_with_range_request() {
+        let tarball = package_tarball();
+        let half = tarball.len() / 2;
+        let dir = temp_download_dir("resume");
+        let part_path = dir.join("example.fhir.test#1.0.0.tgz.part");
+        std::fs::write(&part_path, &tarball[..half]).unwrap();
+
+        let (base_url, seen) = serve_tarball(tarball, true);
+        let client = SimplifierClient::with_base_url(base_url)
+            .unwrap()
+            .with_download_dir(&dir);
+        let package = client
+            .download_package("example.fhir.test", "1.0.0")
+            .await
+            .unwrap();
+
+        assert_eq!(package.manifest.name, "example.fhir.test");
+        assert_eq!(package.conformance_resources().len(), 1);
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec![Some(format!("bytes={}-", half))]
+        );
+        assert!(!part_path.exists(), "partial file is promoted and removed");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn restarts_download_when_server_ignores_ranges() {
+        let tarball = package_tarball();
+        let dir = temp_download_dir("no-ranges");
+        let part_path = dir.join("example.fhir.test#1.0.0.tgz.part");
+        std::fs::write(&part_path, &tarball[..tarball.len() / 2]).unwrap();
+
+        let (base_url, _seen) = serve_tarball(tarball, false);
+        let client = SimplifierClient::with_base_url(base_url)
+            .unwrap()
+            .with_download_dir(&dir);
+        let package = client
+            .download_package("example.fhir.test", "1.0.0")
+            .await
+            .unwrap();
+
+        assert_eq!(package.manifest.name, "example.fhir.test");
+        assert!(!part_path.exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn concurrent_downloads_do_not_share_a_partial_file() {
+        let tarball = package_tarball();
+        let half = tarball.len() / 2;
+        let dir = temp_download_dir("concurrent");
+        let part_path = dir.join("example.fhir.test#1.0.0.tgz.part");
+        std::fs::write(&part_path, &tarball[..half]).unwrap();
+
+        let (base_url, seen) = serve_tarball(tarball, true);
+        let client = SimplifierClient::with_base_url(base_url)
+            .unwrap()
+            .with_download_dir(&dir);
+        let (first, second) = tokio::join!(
+            client.download_package("example.fhir.test", "1.0.0"),
+            client.download_package("example.fhir.test", "1.0.0")
+        );
+
+        assert_eq!(first.unwrap().manifest.name, "example.fhir.test");
+        assert_eq!(second.unwrap().manifest.name, "example.fhir.test");
+        // Only one download resumed the partial file; the other started from scratch.
+        let mut ranges = seen.lock().unwrap().clone();
+        ranges.sort();
+        assert_eq!(ranges, vec![None, Some(format!("bytes={}-", half))]);
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 0);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn parses_content_range() {
+        assert_eq!(
+            parse_content_range("bytes 100-199/200"),
+            Some((100, Some(200)))
+        );
+        assert_eq!(parse_content_range("bytes 5-9/*"), Some((5, None)));
+        assert_eq!(parse_content_range("items 0-1/2"), None);
+    }
 
     #[test]
     fn test_parse_package_metadata_versions() {
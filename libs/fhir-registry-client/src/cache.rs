@@ -2,7 +2,7 @@
 
 use crate::error::{Error, Result};
 use std::path::{Path, PathBuf};
-use ferrum_package::FhirPackage;
+use ferrum_package::{FhirPackage, RawFhirPackage};
 
 /// Trait for FHIR package cache implementations.
 ///
@@ -14,6 +14,15 @@ pub trait PackageCache: Send + Sync {
     /// Load a package from cache
     fn get_package(&self, name: &str, version: &str) -> Result<FhirPackage>;
 
+    /// Load a package from cache without parsing its resources
+    ///
+    /// The default implementation re-serializes the parsed package; caches that keep the
+    /// package files should read them directly.
+    fn get_raw_package(&self, name: &str, version: &str) -> Result<RawFhirPackage> {
+        self.get_package(name, version)
+            .map(|package| RawFhirPackage::from_package(&package))
+    }
+
     /// Store a package in cache
     fn store_package(&self, package: &FhirPackage) -> Result<()>;
 
@@ -75,6 +84,19 @@ impl PackageCache for FileSystemCache {
         FhirPackage::from_directory(&package_path).map_err(Into::into)
     }
 
+    fn get_raw_package(&self, name: &str, version: &str) -> Result<RawFhirPackage> {
+        let package_path = self.get_package_directory(name, version).join("package");
+
+        if !package_path.exists() {
+            return Err(Error::PackageNotFound {
+                name: name.to_string(),
+                version: version.to_string(),
+            });
+        }
+
+        RawFhirPackage::from_directory(&package_path).map_err(Into::into)
+    }
+
     fn list_packages(&self) -> Vec<(String, String)> {
         let mut packages = Vec::new();
 
@@ -124,7 +146,7 @@ impl PackageCache for FileSystemCache {
         }
 
         // Write resources
-        for (i, resource) in package.resources.iter().enumerate() {
+        for (i, resource) in package.conformance_resources().iter().enumerate() {
             let resource_type = resource
                 .get("resourceType")
                 .and_then(|v| v.as_str())
@@ -143,11 +165,11 @@ impl PackageCache for FileSystemCache {
         }
 
         // Write examples
-        if !package.examples.is_empty() {
+        if !package.example_resources().is_empty() {
             let examples_dir = package_path.join("examples");
             fs::create_dir_all(&examples_dir)?;
 
-            for (i, example) in package.examples.iter().enumerate() {
+            for (i, example) in package.example_resources().iter().enumerate() {
                 let resource_type = example
                     .get("resourceType")
                     .and_then(|v| v.as_str())
@@ -41,7 +41,9 @@ pub use async_client::RegistryClient;
 pub use async_simplifier::SimplifierClient;
 pub use cache::{FileSystemCache, PackageCache};
 pub use error::{Error, Result};
-pub use models::{SimplifierSearchParams, SimplifierSearchResult};
+pub use models::{
+    DownloadProgress, ProgressCallback, SearchPage, SimplifierSearchParams, SimplifierSearchResult,
+};
 pub use version_resolver::select_version;
 
 // Re-export fhir_package types for convenience
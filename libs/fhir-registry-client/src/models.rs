@@ -1,6 +1,7 @@
 //! Data models for FHIR packages
 
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
 // Re-export fhir-package types
 pub use ferrum_package::{IndexedFile, PackageIndex, PackageManifest, PackageType};
@@ -16,6 +17,18 @@ pub struct SimplifierSearchResult {
     pub version: String,
 }
 
+/// One page of Simplifier search results
+#[derive(Debug, Clone)]
+pub struct SearchPage {
+    pub results: Vec<SimplifierSearchResult>,
+    /// Total number of matches across all pages
+    pub total: Option<u64>,
+    /// Zero-based number of this page
+    pub page: usize,
+    /// Page to request next, or `None` if this is the last page
+    pub next_page: Option<usize>,
+}
+
 /// Search parameters for Simplifier registry
 #[derive(Debug, Clone, Default)]
 pub struct SimplifierSearchParams {
@@ -24,3 +37,17 @@ pub struct SimplifierSearchParams {
     pub fhir_version: Option<String>,
     pub prerelease: Option<bool>,
 }
+
+/// Progress of a package download, reported while the tarball is transferred
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DownloadProgress {
+    /// Package being downloaded, as `name#version`
+    pub package: String,
+    /// Bytes received so far (including any resumed partial download)
+    pub downloaded: u64,
+    /// Total size, if the server reported it
+    pub total: Option<u64>,
+}
+
+/// Callback receiving [`DownloadProgress`] updates
+pub type ProgressCallback = Arc<dyn Fn(DownloadProgress) + Send + Sync>;
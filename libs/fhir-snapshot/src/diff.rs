@@ -0,0 +1,250 @@
+//! Comparison of two arbitrary snapshots
+//!
+//! Unlike [`generate_differential`](crate::generate_differential), which rebases a snapshot
+//! against its base definition, [`diff_snapshots`] reports how one snapshot changed into
+//! another (e.g. the same profile expanded before and after a dependency upgrade).
+//!
+//! Elements are matched by id, which equals the path for elements outside slices.
+
+use ferrum_models::{ElementDefinition, ElementDefinitionType, Snapshot};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Differences between two snapshots
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotDiff {
+    /// Elements only present in the new snapshot
+    pub added: Vec<String>,
+    /// Elements only present in the old snapshot
+    pub removed: Vec<String>,
+    /// Elements present in both snapshots whose compared fields differ
+    pub modified: Vec<ElementChange>,
+}
+
+impl SnapshotDiff {
+    /// Whether the two snapshots are equivalent for the compared fields
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+
+    /// Changes recorded for an element, if it was modified
+    pub fn modification(&self, path: &str) -> Option<&ElementChange> {
+        self.modified.iter().find(|change| change.path == path)
+    }
+}
+
+/// Field-level changes of a single element
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ElementChange {
+    /// Element id (the path for elements outside slices)
+    pub path: String,
+    pub changes: Vec<FieldChange>,
+}
+
+/// Element field compared by [`diff_snapshots`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ElementField {
+    Cardinality,
+    Types,
+    Binding,
+}
+
+/// A changed field, rendered for reporting (e.g. `0..1` → `1..1`)
+///
+/// `None` means the field is absent on that side.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldChange {
+    pub field: ElementField,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+/// Compare two snapshots element by element
+///
+/// Added and modified elements are reported in the order of `new`, removed elements in the
+/// order of `old`.
+pub fn diff_snapshots(old: &Snapshot, new: &Snapshot) -> SnapshotDiff {
+    let old_by_key: HashMap<&str, &ElementDefinition> =
+        old.element.iter().map(|e| (element_key(e), e)).collect();
+    let new_by_key: HashMap<&str, &ElementDefinition> =
+        new.element.iter().map(|e| (element_key(e), e)).collect();
+
+    let mut diff = SnapshotDiff::default();
+
+    for element in &new.element {
+        let key = element_key(element);
+        match old_by_key.get(key) {
+            None => diff.added.push(key.to_string()),
+            Some(old_element) => {
+                let changes = diff_element(old_element, element);
+                if !changes.is_empty() {
+                    diff.modified.push(ElementChange {
+                        path: key.to_string(),
+                        changes,
+                    });
+                }
+            }
+        }
+    }
+
+    diff.removed = old
+        .element
+        .iter()
+        .map(element_key)
+        .filter(|key| !new_by_key.contains_key(key))
+        .map(str::to_string)
+        .collect();
+
+    diff
+}
+
+fn element_key(element: &ElementDefinition) -> &str {
+    element.id.as_deref().unwrap_or(&element.path)
+}
+
+fn diff_element(old: &ElementDefinition, new: &ElementDefinition) -> Vec<FieldChange> {
+    let compared = [
+        (
+            ElementField::Cardinality,
+            cardinality(old),
+            cardinality(new),
+        ),
+        (ElementField::Types, types(old), types(new)),
+        (ElementField::Binding, binding(old), binding(new)),
+    ];
+
+    compared
+        .into_iter()
+        .filter(|(_, old, new)| old != new)
+        .map(|(field, old, new)| FieldChange { field, old, new })
+        .collect()
+}
+
+fn cardinality(element: &ElementDefinition) -> Option<String> {
+    if element.min.is_none() && element.max.is_none() {
+        return None;
+    }
+    Some(format!(
+        "{}..{}",
+        element.min.map(|m| m.to_string()).as_deref().unwrap_or("?"),
+        element.max.as_deref().unwrap_or("?")
+    ))
+}
+
+fn types(element: &ElementDefinition) -> Option<String> {
+    let types = element.types.as_ref().filter(|types| !types.is_empty())?;
+    Some(types.iter().map(render_type).collect::<Vec<_>>().join(", "))
+}
+
+/// `code`, then `<profiles>` and `(target profiles)` when constrained
+fn render_type(type_: &ElementDefinitionType) -> String {
+    let mut rendered = type_.code.clone();
+    if let Some(profiles) = type_.profile.as_ref().filter(|p| !p.is_empty()) {
+        rendered.push_str(&format!("<{}>", profiles.join("|")));
+    }
+    if let Some(targets) = type_.target_profile.as_ref().filter(|t| !t.is_empty()) {
+        rendered.push_str(&format!("({})", targets.join("|")));
+    }
+    rendered
+}
+
+fn binding(element: &ElementDefinition) -> Option<String> {
+    let binding = element.binding.as_ref()?;
+    let strength = serde_json::to_value(binding.strength)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default();
+    Some(match &binding.value_set {
+        Some(value_set) => format!("{} {}", strength, value_set),
+        None => strength,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn snapshot(elements: serde_json::Value) -> Snapshot {
+        serde_json::from_value(json!({ "element": elements })).unwrap()
+    }
+
+    #[test]
+    fn reports_cardinality_tightening_as_modification() {
+        let old = snapshot(json!([
+            {"id": "Patient", "path": "Patient", "min": 0, "max": "*"},
+            {"id": "Patient.birthDate", "path": "Patient.birthDate", "min": 0, "max": "1",
+             "type": [{"code": "date"}]}
+        ]));
+        let new = snapshot(json!([
+            {"id": "Patient", "path": "Patient", "min": 0, "max": "*"},
+            {"id": "Patient.birthDate", "path": "Patient.birthDate", "min": 1, "max": "1",
+             "type": [{"code": "date"}]}
+        ]));
+
+        let diff = diff_snapshots(&old, &new);
+
+        assert!(diff.added.is_empty());
+        assert!(diff.removed.is_empty());
+        assert_eq!(diff.modified.len(), 1);
+        assert_eq!(
+            diff.modification("Patient.birthDate").unwrap().changes,
+            vec![FieldChange {
+                field: ElementField::Cardinality,
+                old: Some("0..1".to_string()),
+                new: Some("1..1".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn reports_added_removed_and_type_and_binding_changes() {
+        let old = snapshot(json!([
+            {"id": "Observation", "path": "Observation"},
+            {"id": "Observation.status", "path": "Observation.status", "type": [{"code": "code"}],
+             "binding": {"strength": "preferred", "valueSet": "http://example.org/vs-old"}},
+            {"id": "Observation.valueQuantity", "path": "Observation.valueQuantity",
+             "type": [{"code": "Quantity"}]},
+            {"id": "Observation.issued", "path": "Observation.issued"}
+        ]));
+        let new = snapshot(json!([
+            {"id": "Observation", "path": "Observation"},
+            {"id": "Observation.status", "path": "Observation.status", "type": [{"code": "code"}],
+             "binding": {"strength": "required", "valueSet": "http://example.org/vs-new"}},
+            {"id": "Observation.valueQuantity", "path": "Observation.valueQuantity",
+             "type": [{"code": "Quantity",
+                       "profile": ["http://hl7.org/fhir/StructureDefinition/SimpleQuantity"]}]},
+            {"id": "Observation.focus", "path": "Observation.focus"}
+        ]));
+
+        let diff = diff_snapshots(&old, &new);
+
+        assert_eq!(diff.added, vec!["Observation.focus"]);
+        assert_eq!(diff.removed, vec!["Observation.issued"]);
+        assert_eq!(
+            diff.modification("Observation.status").unwrap().changes,
+            vec![FieldChange {
+                field: ElementField::Binding,
+                old: Some("preferred http://example.org/vs-old".to_string()),
+                new: Some("required http://example.org/vs-new".to_string()),
+            }]
+        );
+        assert_eq!(
+            diff.modification("Observation.valueQuantity")
+                .unwrap()
+                .changes,
+            vec![FieldChange {
+                field: ElementField::Types,
+                old: Some("Quantity".to_string()),
+                new: Some(
+                    "Quantity<http://hl7.org/fhir/StructureDefinition/SimpleQuantity>".to_string()
+                ),
+            }]
+        );
+        assert!(diff_snapshots(&new, &new).is_empty());
+    }
+}
@@ -7,8 +7,9 @@
 
 use crate::error::{Error, Result};
 use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
 use ferrum_context::FhirContext;
-use ferrum_models::{ElementDefinition, Snapshot};
+use ferrum_models::{ElementDefinition, Snapshot, StructureDefinition};
 
 /// Context tracking for recursion prevention
 #[derive(Debug, Clone)]
@@ -19,12 +20,29 @@ struct ResolutionContext {
     depth: usize,
 }
 
+/// A complex type expanded in isolation: its elements (and their nested expansions) with
+/// paths and ids still rooted at the type name, ready to be rebased onto an element
+#[derive(Debug)]
+struct ExpandedType {
+    type_name: String,
+    elements: Vec<ElementDefinition>,
+}
+
+/// Expanded complex types keyed by `(canonical_url, version)`
+type TypeCache = HashMap<(String, Option<String>), Arc<ExpandedType>>;
+
 /// Snapshot expander for StructureDefinitions
+///
+/// Complex types expanded directly under a profile's elements are memoized, so a type shared
+/// by many profiles is resolved and expanded once per expander. Types that cannot be resolved
+/// are not cached. The cache lives as long as the expander; call
+/// [`SnapshotExpander::clear_cache`] to release it during long bulk runs.
 pub struct SnapshotExpander {
     max_recursion_depth: HashMap<String, usize>,
     circular_prone_types: HashSet<String>,
     never_resolve_types: HashSet<String>,
     content_reference_max_depth: usize,
+    type_cache: Mutex<TypeCache>,
 }
 
 impl SnapshotExpander {
@@ -45,9 +63,20 @@ impl SnapshotExpander {
             ]),
             never_resolve_types: HashSet::from(["Unknown".into()]),
             content_reference_max_depth: 10,
+            type_cache: Mutex::new(HashMap::new()),
         }
     }
 
+    /// Drop all memoized type expansions
+    pub fn clear_cache(&self) {
+        self.type_cache.lock().unwrap().clear();
+    }
+
+    /// Number of memoized type expansions
+    pub fn cached_type_count(&self) -> usize {
+        self.type_cache.lock().unwrap().len()
+    }
+
     /// Expand a snapshot's elements
     pub fn expand_snapshot(
         &self,
@@ -487,6 +516,89 @@ impl SnapshotExpander {
             .cloned()
     }
 
+    /// Resolve the StructureDefinition for a complex type, logging why it can't be expanded
+    fn resolve_type_definition(
+        &self,
+        canonical_url: &str,
+        context: &dyn FhirContext,
+    ) -> Option<Arc<StructureDefinition>> {
+        // If the profile/base type cannot be resolved, skip expanding further instead of failing
+        let structure_def = match context.get_structure_definition(canonical_url) {
+            Ok(Some(sd)) => sd,
+            Ok(None) => {
+                eprintln!(
+                    "warn: StructureDefinition not found for {}, skipping expansion",
+                    canonical_url
+                );
+                return None;
+            }
+            Err(e) => {
+                eprintln!(
+                    "warn: failed to resolve StructureDefinition {}: {}, skipping expansion",
+                    canonical_url, e
+                );
+                return None;
+            }
+        };
+
+        if structure_def.snapshot.is_none() {
+            eprintln!(
+                "warn: StructureDefinition {} missing snapshot, skipping expansion",
+                canonical_url
+            );
+            return None;
+        }
+
+        Some(structure_def)
+    }
+
+    /// Expand a type in isolation through the type cache
+    ///
+    /// Only successful expansions are cached, so a type that is missing now can still be
+    /// expanded once it becomes resolvable.
+    fn cached_type_expansion(
+        &self,
+        canonical_url: &str,
+        normalized_type: &str,
+        context: &dyn FhirContext,
+    ) -> Result<Option<Arc<ExpandedType>>> {
+        let key = match canonical_url.split_once('|') {
+            Some((url, version)) => (url.to_string(), Some(version.to_string())),
+            None => (canonical_url.to_string(), None),
+        };
+
+        if let Some(cached) = self.type_cache.lock().unwrap().get(&key) {
+            return Ok(Some(cached.clone()));
+        }
+
+        let Some(structure_def) = self.resolve_type_definition(canonical_url, context) else {
+            return Ok(None);
+        };
+        let type_name = structure_def.type_.clone();
+        // The element being expanded is always already seen, which drops the type's root
+        let elements = self.expand_type_elements(
+            &structure_def,
+            normalized_type,
+            &type_name,
+            &type_name,
+            0,
+            &mut HashSet::from([type_name.clone()]),
+            &mut Vec::new(),
+            &mut Vec::new(),
+            context,
+        )?;
+
+        let expanded = Arc::new(ExpandedType {
+            type_name,
+            elements,
+        });
+        self.type_cache
+            .lock()
+            .unwrap()
+            .insert(key, expanded.clone());
+        Ok(Some(expanded))
+    }
+
     /// Expand complex element
     fn expand_complex_element(
         &self,
@@ -536,35 +648,50 @@ impl SnapshotExpander {
             )
         };
 
-        // If the profile/base type cannot be resolved, skip expanding further instead of failing
-        let structure_def = match context.get_structure_definition(&canonical_url) {
-            Ok(Some(sd)) => sd,
-            Ok(None) => {
-                eprintln!(
-                    "warn: StructureDefinition not found for {}, skipping expansion",
-                    canonical_url
-                );
+        // Outside of any other type expansion the result only depends on the type, so it can
+        // be shared; nested expansions depend on the recursion limits of the enclosing types.
+        if resolution_stack.is_empty() {
+            let Some(expanded) =
+                self.cached_type_expansion(&canonical_url, &normalized_type, context)?
+            else {
                 return Ok(Vec::new());
-            }
-            Err(e) => {
-                eprintln!(
-                    "warn: failed to resolve StructureDefinition {}: {}, skipping expansion",
-                    canonical_url, e
-                );
-                return Ok(Vec::new());
-            }
+            };
+            return Ok(self.rebase_type_expansion(&expanded, &element_path, &element_id, seen));
+        }
+
+        let Some(structure_def) = self.resolve_type_definition(&canonical_url, context) else {
+            return Ok(Vec::new());
         };
+        self.expand_type_elements(
+            &structure_def,
+            &normalized_type,
+            &element_path,
+            &element_id,
+            current_depth,
+            seen,
+            resolution_stack,
+            _content_reference_stack,
+            context,
+        )
+    }
 
-        // Get snapshot elements
-        let snapshot = match structure_def.snapshot.as_ref() {
-            Some(snap) => snap,
-            None => {
-                eprintln!(
-                    "warn: StructureDefinition {} missing snapshot, skipping expansion",
-                    canonical_url
-                );
-                return Ok(Vec::new());
-            }
+    /// Copy a type's snapshot elements under `element_path`/`element_id`, expanding nested
+    /// complex types
+    #[allow(clippy::too_many_arguments)]
+    fn expand_type_elements(
+        &self,
+        structure_def: &StructureDefinition,
+        normalized_type: &str,
+        element_path: &str,
+        element_id: &str,
+        current_depth: usize,
+        seen: &mut HashSet<String>,
+        resolution_stack: &mut Vec<ResolutionContext>,
+        content_reference_stack: &mut Vec<String>,
+        context: &dyn FhirContext,
+    ) -> Result<Vec<ElementDefinition>> {
+        let Some(snapshot) = structure_def.snapshot.as_ref() else {
+            return Ok(Vec::new());
         };
 
         let struct_type = &structure_def.type_;
@@ -574,8 +701,8 @@ impl SnapshotExpander {
         let imported_all_elements: Vec<&ElementDefinition> = snapshot.element.iter().collect();
 
         resolution_stack.push(ResolutionContext {
-            type_code: normalized_type.clone(),
-            path: element_path.clone(),
+            type_code: normalized_type.to_string(),
+            path: element_path.to_string(),
             depth: current_depth,
         });
 
@@ -587,9 +714,9 @@ impl SnapshotExpander {
             }
 
             // Replace struct type with element path (only first occurrence)
-            let new_path = child_path.replacen(struct_type, &element_path, 1);
+            let new_path = child_path.replacen(struct_type, element_path, 1);
             let child_id = child_element.id.as_deref().unwrap_or(child_path);
-            let new_id = child_id.replacen(struct_type, &element_id, 1);
+            let new_id = child_id.replacen(struct_type, element_id, 1);
 
             if seen.contains(&new_id) {
                 continue;
@@ -613,7 +740,7 @@ impl SnapshotExpander {
                     &resolved_child,
                     seen,
                     resolution_stack,
-                    _content_reference_stack,
+                    content_reference_stack,
                     &imported_all_elements,
                     context,
                 )?;
@@ -625,6 +752,48 @@ impl SnapshotExpander {
 
         Ok(complex_elements)
     }
+
+    /// Move a cached type expansion under `element_path`/`element_id`
+    ///
+    /// Elements already in `seen` are skipped together with their descendants, as a direct
+    /// expansion would have done.
+    fn rebase_type_expansion(
+        &self,
+        expanded: &ExpandedType,
+        element_path: &str,
+        element_id: &str,
+        seen: &mut HashSet<String>,
+    ) -> Vec<ElementDefinition> {
+        let rebase = |value: &str, root: &str| match value.strip_prefix(&expanded.type_name) {
+            Some(rest) => format!("{}{}", root, rest),
+            None => value.to_string(),
+        };
+
+        let mut skipped: Vec<String> = Vec::new();
+        let mut elements = Vec::new();
+        for cached in &expanded.elements {
+            let new_id = rebase(cached.id.as_deref().unwrap_or(&cached.path), element_id);
+            if skipped
+                .iter()
+                .any(|prefix| new_id.starts_with(&format!("{}.", prefix)))
+            {
+                continue;
+            }
+            if !seen.insert(new_id.clone()) {
+                skipped.push(new_id);
+                continue;
+            }
+
+            let mut element = cached.clone();
+            element.path = rebase(&cached.path, element_path);
+            element.id = Some(new_id);
+            if let Some(ref mut base) = element.base {
+                base.path = element.path.clone();
+            }
+            elements.push(element);
+        }
+        elements
+    }
 }
 
 impl Default for SnapshotExpander {
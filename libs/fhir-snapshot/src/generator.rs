@@ -13,7 +13,7 @@ use crate::inheritance::{
 use crate::merge::{cleanup_fixed_field, merge_element};
 use crate::normalization::{normalize_differential, normalize_snapshot};
 use crate::slicing::SlicingContext;
-use crate::validation::{validate_differential, validate_snapshot};
+use crate::validation::{validate_differential_structure, validate_snapshot};
 use std::collections::HashMap;
 use ferrum_context::FhirContext;
 use ferrum_models::StructureDefinition;
@@ -59,6 +59,30 @@ fn find_insertion_position(elements: &[ElementDefinition], new_elem: &ElementDef
     last_descendant + 1
 }
 
+/// Find the insertion position for a child of a slice, identified by a slice-scoped id
+/// (e.g. `Observation.value[x]:valueQuantity.unit`): after the slice and its children.
+fn find_slice_child_position(
+    elements: &[ElementDefinition],
+    new_elem: &ElementDefinition,
+) -> Option<usize> {
+    let id = new_elem.id.as_deref()?;
+    let (slice_id, _) = id.rsplit_once('.')?;
+    if !slice_id.contains(':') {
+        return None;
+    }
+
+    let slice_idx = elements.iter().position(|e| e.key() == slice_id)?;
+    let scope = format!("{}.", slice_id);
+    let last_child = elements
+        .iter()
+        .enumerate()
+        .skip(slice_idx + 1)
+        .take_while(|(_, e)| e.id.as_deref().is_some_and(|i| i.starts_with(&scope)))
+        .last()
+        .map_or(slice_idx, |(i, _)| i);
+    Some(last_child + 1)
+}
+
 /// Check if `ancestor` is an ancestor of `descendant` in FHIR path terms.
 /// Handles both normal paths and choice-type expansions.
 fn is_ancestor_of(ancestor: &str, descendant: &str) -> bool {
@@ -84,6 +108,92 @@ fn is_ancestor_of(ancestor: &str, descendant: &str) -> bool {
     false
 }
 
+/// Rewrite type slices of choice elements into their canonical form.
+///
+/// A differential can constrain one type of a choice element through its type-specific
+/// name (`Observation.valueQuantity`); this is a type slice of `Observation.value[x]`
+/// named `valueQuantity`. Such elements are rewritten to the `[x]` path with that slice
+/// name, and their children (`Observation.valueQuantity.unit`) to paths under the `[x]`
+/// element with ids scoped to the slice. Type slices that don't list types are restricted
+/// to the type their name refers to.
+fn normalize_type_slices(differential: &Differential, base: &Snapshot) -> Differential {
+    let choices: Vec<&ElementDefinition> = base
+        .element
+        .iter()
+        .filter(|e| e.is_choice_type() && !e.is_slice())
+        .collect();
+
+    let element = differential
+        .element
+        .iter()
+        .map(|elem| {
+            let mut elem = elem.clone();
+            for choice in &choices {
+                let prefix = &choice.path[..choice.path.len() - 3];
+                let choice_name = prefix.rsplit('.').next().unwrap_or(prefix);
+
+                if elem.path == choice.path {
+                    // Explicit form: `value[x]` with slice name `valueQuantity`
+                    if let Some(type_name) = elem
+                        .slice_name
+                        .as_deref()
+                        .and_then(|s| s.strip_prefix(choice_name))
+                        .map(str::to_string)
+                    {
+                        restrict_to_choice_type(&mut elem, choice, &type_name);
+                    }
+                    break;
+                }
+
+                let Some(rest) = elem.path.strip_prefix(prefix) else {
+                    continue;
+                };
+                if !rest.starts_with(|c: char| c.is_ascii_uppercase()) {
+                    continue;
+                }
+                let (type_name, child) = rest.split_at(rest.find('.').unwrap_or(rest.len()));
+                let (type_name, child) = (type_name.to_string(), child.to_string());
+                let slice_name = format!("{}{}", choice_name, type_name);
+
+                if child.is_empty() {
+                    elem.path = choice.path.clone();
+                    elem.slice_name.get_or_insert(slice_name);
+                    elem.id = None;
+                    restrict_to_choice_type(&mut elem, choice, &type_name);
+                } else {
+                    elem.path = format!("{}{}", choice.path, child);
+                    elem.id = Some(format!("{}:{}{}", choice.path, slice_name, child));
+                }
+                break;
+            }
+            elem
+        })
+        .collect();
+
+    Differential { element }
+}
+
+/// Restrict a type slice to the choice type named `type_name` (e.g. "Quantity", "String")
+/// unless the differential already constrains its types.
+fn restrict_to_choice_type(
+    elem: &mut ElementDefinition,
+    choice: &ElementDefinition,
+    type_name: &str,
+) {
+    if elem.types.is_some() {
+        return;
+    }
+    let matching = choice.types.iter().flatten().find(|t| {
+        let mut code = t.code.chars();
+        code.next().is_some_and(|first| {
+            first.to_ascii_uppercase().to_string() + code.as_str() == type_name
+        })
+    });
+    if let Some(t) = matching {
+        elem.types = Some(vec![t.clone()]);
+    }
+}
+
 /// Find base element for a differential element using FHIR's inheritance chain.
 ///
 /// This implements a 4-step lookup:
@@ -317,9 +427,13 @@ pub(crate) fn generate_snapshot_internal(
     base_structure_definition: Option<&StructureDefinition>,
     context: &dyn FhirContext,
 ) -> Result<Snapshot> {
+    // Type slices given by their type-specific name are rewritten onto the choice element
+    let type_sliced = normalize_type_slices(differential, base);
+    let differential = &type_sliced;
+
     // Validate inputs; use base as-is (shallow) to preserve original snapshot ordering/structure
     validate_snapshot(base)?;
-    validate_differential(differential, base)?;
+    validate_differential_structure(differential, base)?;
 
     // Sort differential into canonical order before merging
     let sorted_diff = sort_differential(differential, base);
@@ -453,7 +567,8 @@ pub(crate) fn generate_snapshot_internal(
             let position = if is_new_slice {
                 slicing_ctx.get_slice_position(&merged_elements, &merged)
             } else {
-                find_insertion_position(&merged_elements, &merged)
+                find_slice_child_position(&merged_elements, &merged)
+                    .unwrap_or_else(|| find_insertion_position(&merged_elements, &merged))
             };
 
             merged_elements.insert(position, merged);
@@ -770,6 +885,7 @@ mod tests {
     use super::*;
     use std::collections::HashMap;
     use ferrum_context::DefaultFhirContext;
+    use ferrum_models::ElementDefinitionType;
 
     /// Create an R4 context for testing
     async fn create_test_context() -> DefaultFhirContext {
@@ -817,6 +933,166 @@ mod tests {
         }
     }
 
+    struct EmptyContext;
+
+    impl FhirContext for EmptyContext {
+        fn get_resource_by_url(
+            &self,
+            _canonical_url: &str,
+            _version: Option<&str>,
+        ) -> ferrum_context::Result<Option<std::sync::Arc<serde_json::Value>>> {
+            Ok(None)
+        }
+    }
+
+    fn element_type(code: &str) -> ElementDefinitionType {
+        ElementDefinitionType {
+            code: code.to_string(),
+            profile: None,
+            target_profile: None,
+            aggregation: None,
+            versioning: None,
+        }
+    }
+
+    #[test]
+    fn expands_type_slices_on_choice_elements() {
+        let mut value = make_element("Observation.value[x]", Some(0), Some("1"));
+        value.types = Some(vec![
+            element_type("Quantity"),
+            element_type("CodeableConcept"),
+            element_type("string"),
+        ]);
+        let base = Snapshot {
+            element: vec![
+                make_element("Observation", None, None),
+                make_element("Observation.status", Some(1), Some("1")),
+                value,
+            ],
+        };
+
+        // Shorthand form for Quantity, explicit slice for CodeableConcept
+        let mut concept = make_element("Observation.value[x]", None, None);
+        concept.id = Some("Observation.value[x]:valueCodeableConcept".to_string());
+        concept.slice_name = Some("valueCodeableConcept".to_string());
+        let differential = Differential {
+            element: vec![
+                make_element("Observation.valueQuantity", Some(1), None),
+                make_element("Observation.valueQuantity.unit", Some(1), None),
+                concept,
+            ],
+        };
+
+        let snapshot = generate_snapshot(&base, &differential, &EmptyContext).unwrap();
+        let ids: Vec<&str> = snapshot
+            .element
+            .iter()
+            .map(|e| e.id.as_deref().unwrap())
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                "Observation",
+                "Observation.status",
+                "Observation.value[x]",
+                "Observation.value[x]:valueQuantity",
+                "Observation.value[x]:valueQuantity.unit",
+                "Observation.value[x]:valueCodeableConcept",
+            ]
+        );
+
+        let slice = |name: &str| {
+            snapshot
+                .element
+                .iter()
+                .find(|e| e.slice_name.as_deref() == Some(name))
+                .unwrap()
+        };
+        let quantity = slice("valueQuantity");
+        assert_eq!(quantity.path, "Observation.value[x]");
+        assert_eq!(quantity.min, Some(1));
+        assert_eq!(quantity.type_codes(), vec!["Quantity"]);
+        let concept = slice("valueCodeableConcept");
+        assert_eq!(concept.path, "Observation.value[x]");
+        assert_eq!(concept.type_codes(), vec!["CodeableConcept"]);
+
+        let entry = snapshot
+            .element
+            .iter()
+            .find(|e| e.path == "Observation.value[x]" && !e.is_slice())
+            .unwrap();
+        let discriminator = &entry
+            .slicing
+            .as_ref()
+            .unwrap()
+            .discriminator
+            .as_ref()
+            .unwrap()[0];
+        assert_eq!(
+            discriminator.discriminator_type,
+            ferrum_models::DiscriminatorType::Type
+        );
+        assert_eq!(discriminator.path, "$this");
+    }
+
+    #[test]
+    fn propagates_must_support_and_binding_from_differential() {
+        use ferrum_models::{BindingStrength, ElementDefinitionBinding};
+
+        let mut status = make_element("Observation.status", Some(1), Some("1"));
+        status.binding = Some(ElementDefinitionBinding {
+            strength: BindingStrength::Preferred,
+            description: Some("Codes providing the status of an observation.".to_string()),
+            value_set: Some("http://hl7.org/fhir/ValueSet/observation-status".to_string()),
+        });
+        let mut code = make_element("Observation.code", Some(1), Some("1"));
+        code.binding = Some(ElementDefinitionBinding {
+            strength: BindingStrength::Example,
+            description: None,
+            value_set: Some("http://hl7.org/fhir/ValueSet/observation-codes".to_string()),
+        });
+        let base = Snapshot {
+            element: vec![make_element("Observation", None, None), status, code],
+        };
+
+        let mut diff_status = make_element("Observation.status", None, None);
+        diff_status.must_support = Some(true);
+        diff_status.binding = Some(ElementDefinitionBinding {
+            strength: BindingStrength::Required,
+            description: None,
+            value_set: Some("http://example.org/ValueSet/final-only".to_string()),
+        });
+        let mut diff_code = make_element("Observation.code", None, None);
+        diff_code.must_support = Some(true);
+        let differential = Differential {
+            element: vec![diff_status, diff_code],
+        };
+
+        let snapshot = generate_snapshot(&base, &differential, &EmptyContext).unwrap();
+        let find = |path: &str| snapshot.element.iter().find(|e| e.path == path).unwrap();
+
+        let status = find("Observation.status");
+        assert_eq!(status.must_support, Some(true));
+        let binding = status.binding.as_ref().unwrap();
+        assert_eq!(binding.strength, BindingStrength::Required);
+        assert_eq!(
+            binding.value_set.as_deref(),
+            Some("http://example.org/ValueSet/final-only")
+        );
+        assert_eq!(
+            binding.description.as_deref(),
+            Some("Codes providing the status of an observation.")
+        );
+
+        // Base binding survives when the differential doesn't restate it
+        let code = find("Observation.code");
+        assert_eq!(code.must_support, Some(true));
+        assert_eq!(
+            code.binding.as_ref().map(|b| &b.strength),
+            Some(&BindingStrength::Example)
+        );
+    }
+
     #[tokio::test]
     async fn merges_differential_into_base_snapshot() {
         let ctx = create_test_context().await;
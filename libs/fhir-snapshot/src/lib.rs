@@ -29,6 +29,7 @@
 //! # }
 //! ```
 
+pub mod diff;
 pub mod error;
 pub mod expanded_context;
 pub mod expander;
@@ -40,6 +41,7 @@ pub mod slicing;
 pub mod snapshot_generation;
 pub mod validation;
 
+pub use diff::{diff_snapshots, ElementChange, ElementField, FieldChange, SnapshotDiff};
 pub use error::{Error, Result};
 pub use expanded_context::{BorrowedFhirContext, ExpandedFhirContext};
 pub use expander::SnapshotExpander;
@@ -432,6 +432,7 @@ fn merge_types(
 /// - Differential can make binding more restrictive
 /// - Can change from example -> preferred -> extensible -> required
 /// - Can change the ValueSet
+/// - Base binding is kept as-is when the differential doesn't declare one
 fn merge_binding(
     base: Option<&ElementDefinitionBinding>,
     diff: Option<&ElementDefinitionBinding>,
@@ -469,12 +470,12 @@ fn more_restrictive_binding_strength(
     use ferrum_models::BindingStrength;
     // Order: Example < Preferred < Extensible < Required
     match (base, diff) {
-        (BindingStrength::Example, _) => diff.clone(),
+        (BindingStrength::Example, _) => *diff,
         (_, BindingStrength::Required) => BindingStrength::Required,
         (BindingStrength::Preferred, BindingStrength::Extensible) => BindingStrength::Extensible,
         (BindingStrength::Preferred, BindingStrength::Preferred) => BindingStrength::Preferred,
         (BindingStrength::Extensible, BindingStrength::Extensible) => BindingStrength::Extensible,
-        _ => base.clone(),
+        _ => *base,
     }
 }
 
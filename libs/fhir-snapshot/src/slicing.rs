@@ -168,8 +168,21 @@ impl SlicingContext {
     }
 
     /// Create a default slicing entry for implicit slicing
-    pub fn create_default_slicing_entry(&self, _path: &str) -> ElementDefinitionSlicing {
+    ///
+    /// Slices of a choice element (`value[x]`) are type slices, discriminated by `type` on `$this`.
+    pub fn create_default_slicing_entry(&self, path: &str) -> ElementDefinitionSlicing {
         use ferrum_models::{DiscriminatorType, SlicingRules};
+        if path.ends_with("[x]") {
+            return ElementDefinitionSlicing {
+                discriminator: Some(vec![ElementDefinitionDiscriminator {
+                    discriminator_type: DiscriminatorType::Type,
+                    path: "$this".to_string(),
+                }]),
+                description: Some("Slicing on type".to_string()),
+                ordered: Some(false),
+                rules: SlicingRules::Open,
+            };
+        }
         ElementDefinitionSlicing {
             discriminator: Some(vec![ElementDefinitionDiscriminator {
                 discriminator_type: DiscriminatorType::Value,
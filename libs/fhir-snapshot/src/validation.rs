@@ -6,7 +6,24 @@ use crate::error::{Error, Result};
 use ferrum_models::{Differential, ElementDefinition, Snapshot};
 
 /// Validate a differential according to FHIR rules
+///
+/// On top of the structural checks applied during snapshot generation, this rejects
+/// differential elements whose path does not exist in the base snapshot (e.g. a typo
+/// like `Patient.nmae`), which would otherwise be silently dropped or appended. All
+/// offending paths are reported in a single error.
 pub fn validate_differential(differential: &Differential, base: &Snapshot) -> Result<()> {
+    validate_differential_structure(differential, base)?;
+    validate_paths_exist_in_base(&differential.element, base)
+}
+
+/// Structural checks on a differential (ordering, ancestors and hierarchy).
+///
+/// Snapshot generation only applies these, so that elements can be added to partial
+/// base snapshots.
+pub(crate) fn validate_differential_structure(
+    differential: &Differential,
+    base: &Snapshot,
+) -> Result<()> {
     if differential.element.is_empty() {
         return Ok(());
     }
@@ -92,6 +109,72 @@ fn validate_paths_against_base(diff_elements: &[ElementDefinition], base: &Snaps
     Ok(())
 }
 
+/// Validate that each differential path corresponds to an element of the base snapshot.
+///
+/// A path missing from the base is only accepted when the base does not list the
+/// children of its parent (they come from the parent's type, e.g. `Patient.name.family`),
+/// when it is a renamed choice element (`Observation.valueQuantity` for
+/// `Observation.value[x]`), or when it introduces an extension. Slices share the path
+/// of their base element, so introducing a new slice is always accepted.
+fn validate_paths_exist_in_base(
+    diff_elements: &[ElementDefinition],
+    base: &Snapshot,
+) -> Result<()> {
+    let base_paths: std::collections::HashSet<&str> =
+        base.element.iter().map(|e| e.path.as_str()).collect();
+
+    let mut unknown: Vec<&str> = Vec::new();
+    for elem in diff_elements {
+        let path = elem.path.as_str();
+        if base_paths.contains(path) || unknown.contains(&path) {
+            continue;
+        }
+        let Some((parent, name)) = path.rsplit_once('.') else {
+            continue;
+        };
+        if name == "extension" || name == "modifierExtension" {
+            continue;
+        }
+
+        let parent_children_listed = base_paths.iter().any(|p| {
+            p.len() > parent.len() + 1
+                && p.starts_with(parent)
+                && p.as_bytes()[parent.len()] == b'.'
+        });
+        if parent_children_listed && !is_renamed_choice(path, &base_paths) {
+            unknown.push(path);
+        }
+    }
+
+    if unknown.is_empty() {
+        return Ok(());
+    }
+    Err(Error::Differential(
+        unknown
+            .iter()
+            .map(|path| {
+                format!(
+                    "Differential element '{}' has no matching element in base snapshot",
+                    path
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("; "),
+    ))
+}
+
+/// Check if `path` is a type-specific rename of a choice element in the base
+/// (e.g. `Observation.valueQuantity` for `Observation.value[x]`).
+fn is_renamed_choice(path: &str, base_paths: &std::collections::HashSet<&str>) -> bool {
+    base_paths.iter().any(|p| {
+        p.strip_suffix("[x]").is_some_and(|prefix| {
+            path.strip_prefix(prefix).is_some_and(|rest| {
+                rest.starts_with(|c: char| c.is_ascii_uppercase()) && !rest.contains('.')
+            })
+        })
+    })
+}
+
 /// Check if a parent path is reachable through a choice-type ancestor in the base.
 ///
 /// For example, `CarePlan.activity.detail.scheduled[x].repeat` is valid if
@@ -354,6 +437,61 @@ mod tests {
         assert!(validate_differential(&diff, &base).is_err());
     }
 
+    #[test]
+    fn reports_differential_paths_missing_from_base() {
+        let base = Snapshot {
+            element: vec![
+                make_element("Patient", None),
+                make_element("Patient.name", None),
+                make_element("Patient.birthDate", None),
+            ],
+        };
+
+        let diff = Differential {
+            element: vec![
+                make_element("Patient.nmae", None),
+                make_element("Patient.name", None),
+                make_element("Patient.name.family", None),
+                make_element("Patient.birthdate", None),
+            ],
+        };
+
+        let err = validate_differential(&diff, &base).unwrap_err().to_string();
+        assert!(err.contains("'Patient.nmae'"), "{err}");
+        assert!(err.contains("'Patient.birthdate'"), "{err}");
+        assert!(!err.contains("'Patient.name.family'"), "{err}");
+
+        // Generation only applies the structural checks
+        assert!(validate_differential_structure(&diff, &base).is_ok());
+    }
+
+    #[test]
+    fn allows_new_slices_extensions_and_choice_renames() {
+        let base = Snapshot {
+            element: vec![
+                make_element("Observation", None),
+                make_element("Observation.extension", None),
+                make_element("Observation.component", None),
+                make_element("Observation.component.code", None),
+                make_element("Observation.value[x]", None),
+            ],
+        };
+
+        let diff = Differential {
+            element: vec![
+                make_element("Observation.extension", Some("birthPlace")),
+                make_element("Observation.extension.value[x]", None),
+                make_element("Observation.component", Some("systolic")),
+                make_element("Observation.component.code", None),
+                make_element("Observation.component.extension", None),
+                make_element("Observation.valueQuantity", None),
+                make_element("Observation.valueQuantity.unit", None),
+            ],
+        };
+
+        assert!(validate_differential(&diff, &base).is_ok());
+    }
+
     #[test]
     fn validates_snapshot() {
         let snapshot = Snapshot {
@@ -115,7 +115,7 @@ impl MockContext {
         self.structure_definitions.insert(
             "http://hl7.org/fhir/StructureDefinition/Coding".to_string(),
             json!({
-                "type": "Coding",
+                "resourceType": "StructureDefinition", "url": "http://hl7.org/fhir/StructureDefinition/Coding", "name": "Coding", "status": "active", "kind": "complex-type", "abstract": false, "type": "Coding",
                 "snapshot": {
                     "element": [
                         { "id": "Coding", "path": "Coding" },
@@ -798,3 +798,118 @@ fn test_root_level_element_not_expanded() {
     assert_eq!(paths.len(), 1);
     assert_eq!(paths[0], "Patient");
 }
+
+/// Wraps `MockContext` and counts StructureDefinition lookups per canonical URL
+struct CountingContext {
+    inner: MockContext,
+    lookups: std::sync::Mutex<HashMap<String, usize>>,
+}
+
+impl CountingContext {
+    fn new() -> Self {
+        Self {
+            inner: MockContext::new(),
+            lookups: std::sync::Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn lookups(&self, canonical_url: &str) -> usize {
+        self.lookups
+            .lock()
+            .unwrap()
+            .get(canonical_url)
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+impl FhirContext for CountingContext {
+    fn get_resource_by_url(
+        &self,
+        canonical_url: &str,
+        version: Option<&str>,
+    ) -> ferrum_context::Result<Option<Arc<Value>>> {
+        self.inner.get_resource_by_url(canonical_url, version)
+    }
+
+    fn get_structure_definition(
+        &self,
+        canonical_url: &str,
+    ) -> ferrum_context::Result<Option<Arc<StructureDefinition>>> {
+        *self
+            .lookups
+            .lock()
+            .unwrap()
+            .entry(canonical_url.to_string())
+            .or_insert(0) += 1;
+        self.inner.get_structure_definition(canonical_url)
+    }
+}
+
+#[test]
+fn test_shared_complex_type_is_expanded_once() {
+    let ctx = CountingContext::new();
+    let expander = SnapshotExpander::new();
+    let codeable_concept = "http://hl7.org/fhir/StructureDefinition/CodeableConcept";
+    let coding = "http://hl7.org/fhir/StructureDefinition/Coding";
+
+    let observation = snapshot_from_json(&json!({
+        "element": [
+            {"id": "Observation", "path": "Observation"},
+            {"id": "Observation.code", "path": "Observation.code", "type": [{"code": "CodeableConcept"}]}
+        ]
+    }));
+    let condition = snapshot_from_json(&json!({
+        "element": [
+            {"id": "Condition", "path": "Condition"},
+            {"id": "Condition.code:primary", "path": "Condition.code", "type": [{"code": "CodeableConcept"}]}
+        ]
+    }));
+
+    let expanded_observation = expander.expand_snapshot(&observation, &ctx).unwrap();
+    let expanded_condition = expander.expand_snapshot(&condition, &ctx).unwrap();
+
+    // The cached expansion, including the nested Coding, is rebased onto each element
+    let system = expanded_observation
+        .iter()
+        .find(|e| e.path == "Observation.code.coding.system")
+        .expect("nested type expanded");
+    assert_eq!(system.id.as_deref(), Some("Observation.code.coding.system"));
+    let system = expanded_condition
+        .iter()
+        .find(|e| e.path == "Condition.code.coding.system")
+        .expect("nested type expanded");
+    assert_eq!(
+        system.id.as_deref(),
+        Some("Condition.code:primary.coding.system")
+    );
+    assert_eq!(ctx.lookups(codeable_concept), 1);
+    assert_eq!(ctx.lookups(coding), 1);
+    assert_eq!(expander.cached_type_count(), 1);
+
+    expander.clear_cache();
+    assert_eq!(expander.cached_type_count(), 0);
+    expander.expand_snapshot(&observation, &ctx).unwrap();
+    assert_eq!(ctx.lookups(codeable_concept), 2);
+}
+
+#[test]
+fn test_unresolvable_types_are_not_cached() {
+    let ctx = CountingContext::new();
+    let expander = SnapshotExpander::new();
+    let snapshot = snapshot_from_json(&json!({
+        "element": [
+            {"id": "Patient", "path": "Patient"},
+            {"id": "Patient.other", "path": "Patient.other", "type": [{"code": "Missing"}]}
+        ]
+    }));
+
+    expander.expand_snapshot(&snapshot, &ctx).unwrap();
+    expander.expand_snapshot(&snapshot, &ctx).unwrap();
+
+    assert_eq!(
+        ctx.lookups("http://hl7.org/fhir/StructureDefinition/Missing"),
+        2
+    );
+    assert_eq!(expander.cached_type_count(), 0);
+}
@@ -1,5 +1,5 @@
 use serde_json::json;
-use ferrum_context::DefaultFhirContext;
+use ferrum_context::{ConflictPolicy, DefaultFhirContext};
 use ferrum_validator::*;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -26,7 +26,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Create FHIR context (normally would load FHIR packages)
     // For demo, using empty context - in production, use from_fhir_version() or from_packages()
-    let context = DefaultFhirContext::from_packages(vec![]);
+    let context = DefaultFhirContext::from_packages(vec![], ConflictPolicy::default());
 
     // Create validator (expensive, done once)
     let validator = Validator::new(plan, context);
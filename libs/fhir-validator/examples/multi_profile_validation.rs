@@ -1,5 +1,5 @@
 use serde_json::json;
-use ferrum_context::DefaultFhirContext;
+use ferrum_context::{ConflictPolicy, DefaultFhirContext};
 use ferrum_validator::*;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -24,7 +24,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     ]);
 
     let plan = config.compile()?;
-    let context1 = DefaultFhirContext::from_packages(vec![]);
+    let context1 = DefaultFhirContext::from_packages(vec![], ConflictPolicy::default());
     let validator = Validator::new(plan, context1);
 
     let patient = json!({
@@ -47,7 +47,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .build();
 
     let plan2 = config2.compile()?;
-    let context2 = DefaultFhirContext::from_packages(vec![]);
+    let context2 = DefaultFhirContext::from_packages(vec![], ConflictPolicy::default());
     let validator2 = Validator::new(plan2, context2);
 
     let patient_with_profile = json!({
@@ -76,7 +76,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .build();
 
     let plan3 = config3.compile()?;
-    let context3 = DefaultFhirContext::from_packages(vec![]);
+    let context3 = DefaultFhirContext::from_packages(vec![], ConflictPolicy::default());
     let validator3 = Validator::new(plan3, context3);
 
     let simple_patient = json!({
@@ -1,5 +1,5 @@
 use serde_json::json;
-use ferrum_context::DefaultFhirContext;
+use ferrum_context::{ConflictPolicy, DefaultFhirContext};
 use ferrum_validator::*;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -18,7 +18,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Note: In production, load actual FHIR packages with StructureDefinitions
     // For this demo, using empty context - schema validation will report SD not found
-    let context = DefaultFhirContext::from_packages(vec![]);
+    let context = DefaultFhirContext::from_packages(vec![], ConflictPolicy::default());
     let validator = Validator::new(plan, context);
 
     // Test 1: Valid Patient resource
@@ -65,7 +65,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     cfg.schema.allow_modifier_extensions = false;
 
     let plan_strict = cfg.compile()?;
-    let validator_strict = Validator::new(plan_strict, DefaultFhirContext::from_packages(vec![]));
+    let validator_strict = Validator::new(
+        plan_strict,
+        DefaultFhirContext::from_packages(vec![], ConflictPolicy::default()),
+    );
     let outcome = validator_strict.validate(&with_modifier);
     print_outcome(&outcome);
 
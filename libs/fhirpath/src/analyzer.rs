@@ -3,7 +3,7 @@ use crate::error::{Error, Result};
 use crate::functions::FunctionRegistry;
 use crate::hir::{HirBinaryOperator, HirNode, HirTypeOperator, HirUnaryOperator, PathSegmentHir};
 use crate::types::{Cardinality, ExprType, TypeId, TypeRegistry};
-use crate::value::Value;
+use crate::value::{Collection, Value};
 use crate::variables::VariableRegistry;
 use rust_decimal::prelude::ToPrimitive;
 use std::sync::{Arc, Mutex};
@@ -34,7 +34,7 @@ impl Analyzer {
     pub fn analyze(&self, ast: AstNode) -> Result<HirNode> {
         // For top-level analysis, we don't know the base type yet
         // It will be determined at runtime from the context
-        self.analyze_node(ast, None, None, 0)
+        self.analyze_node(ast, None, None, 0).map(fold_constants)
     }
 
     /// Semantic analysis with known base type: AST → HIR
@@ -52,6 +52,7 @@ impl Analyzer {
             .and_then(|name| self.type_registry.get_type_id_by_name(name));
 
         self.analyze_node(ast, base_type_id, base_type_name, 0)
+            .map(fold_constants)
     }
 
     /// Analyze an AST node recursively
@@ -1045,6 +1046,189 @@ impl Analyzer {
     }
 }
 
+/// Constant folding: evaluate binary operators whose operands are both literals.
+///
+/// Only operator nodes are folded; function calls (e.g. `now()`, `trace()`) are never
+/// evaluated at compile time. Operations that fail or produce more than one item are
+/// left for the VM so runtime semantics (including errors) are unchanged.
+fn fold_constants(node: HirNode) -> HirNode {
+    let fold = |node: Box<HirNode>| Box::new(fold_constants(*node));
+    let fold_all = |nodes: Vec<HirNode>| nodes.into_iter().map(fold_constants).collect();
+
+    match node {
+        HirNode::BinaryOp {
+            op,
+            left,
+            right,
+            impl_id,
+            result_ty,
+        } => {
+            let left = fold(left);
+            let right = fold(right);
+            if let (HirNode::Literal { value: l, .. }, HirNode::Literal { value: r, .. }) =
+                (left.as_ref(), right.as_ref())
+            {
+                if let Some(value) = evaluate_constant_binary_op(op, l, r) {
+                    // The type pass re-infers literal types from the value.
+                    return HirNode::Literal {
+                        value,
+                        ty: result_ty,
+                    };
+                }
+            }
+            HirNode::BinaryOp {
+                op,
+                left,
+                right,
+                impl_id,
+                result_ty,
+            }
+        }
+        HirNode::UnaryOp {
+            op,
+            expr,
+            result_ty,
+        } => HirNode::UnaryOp {
+            op,
+            expr: fold(expr),
+            result_ty,
+        },
+        HirNode::Path {
+            base,
+            segments,
+            result_ty,
+        } => HirNode::Path {
+            base: fold(base),
+            segments,
+            result_ty,
+        },
+        HirNode::FunctionCall {
+            func_id,
+            args,
+            result_ty,
+        } => HirNode::FunctionCall {
+            func_id,
+            args: fold_all(args),
+            result_ty,
+        },
+        HirNode::MethodCall {
+            base,
+            func_id,
+            args,
+            result_ty,
+        } => HirNode::MethodCall {
+            base: fold(base),
+            func_id,
+            args: fold_all(args),
+            result_ty,
+        },
+        HirNode::TypeOp {
+            op,
+            expr,
+            type_specifier,
+            result_ty,
+        } => HirNode::TypeOp {
+            op,
+            expr: fold(expr),
+            type_specifier,
+            result_ty,
+        },
+        HirNode::Where {
+            collection,
+            predicate_hir,
+            predicate_plan_id,
+            result_ty,
+        } => HirNode::Where {
+            collection: fold(collection),
+            predicate_hir: fold(predicate_hir),
+            predicate_plan_id,
+            result_ty,
+        },
+        HirNode::Select {
+            collection,
+            projection_hir,
+            projection_plan_id,
+            result_ty,
+        } => HirNode::Select {
+            collection: fold(collection),
+            projection_hir: fold(projection_hir),
+            projection_plan_id,
+            result_ty,
+        },
+        HirNode::Repeat {
+            collection,
+            projection_hir,
+            projection_plan_id,
+            result_ty,
+        } => HirNode::Repeat {
+            collection: fold(collection),
+            projection_hir: fold(projection_hir),
+            projection_plan_id,
+            result_ty,
+        },
+        HirNode::Aggregate {
+            collection,
+            aggregator_hir,
+            init_value_hir,
+            aggregator_plan_id,
+            result_ty,
+        } => HirNode::Aggregate {
+            collection: fold(collection),
+            aggregator_hir: fold(aggregator_hir),
+            init_value_hir: init_value_hir.map(fold),
+            aggregator_plan_id,
+            result_ty,
+        },
+        HirNode::Exists {
+            collection,
+            predicate_hir,
+            predicate_plan_id,
+            result_ty,
+        } => HirNode::Exists {
+            collection: fold(collection),
+            predicate_hir: predicate_hir.map(fold),
+            predicate_plan_id,
+            result_ty,
+        },
+        HirNode::All {
+            collection,
+            predicate_hir,
+            predicate_plan_id,
+            result_ty,
+        } => HirNode::All {
+            collection: fold(collection),
+            predicate_hir: fold(predicate_hir),
+            predicate_plan_id,
+            result_ty,
+        },
+        node @ (HirNode::Literal { .. } | HirNode::Variable { .. }) => node,
+    }
+}
+
+/// Evaluate `left op right` on literal values, returning `None` when the result
+/// cannot be represented as a single literal.
+fn evaluate_constant_binary_op(
+    op: HirBinaryOperator,
+    left: &Value,
+    right: &Value,
+) -> Option<Value> {
+    let as_collection = |value: &Value| {
+        if matches!(value.data(), crate::value::ValueData::Empty) {
+            Collection::empty()
+        } else {
+            Collection::singleton(value.clone())
+        }
+    };
+
+    let result =
+        crate::vm::execute_binary_op(op, as_collection(left), as_collection(right)).ok()?;
+    match result.len() {
+        0 => Some(Value::empty()),
+        1 => result.iter().next().cloned(),
+        _ => None,
+    }
+}
+
 fn is_negative_numeric_literal(ast: &AstNode) -> bool {
     match ast {
         AstNode::LiteralTerm { literal } => is_negative_numeric_literal(literal),
@@ -1061,3 +1245,74 @@ pub fn is_fhir_type(context: &Arc<dyn FhirContext>, type_name: &str) -> bool {
         Ok(Some(_))
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    fn analyze(expr: &str) -> HirNode {
+        let ast = Parser::new(expr.to_string()).parse().unwrap();
+        let analyzer = Analyzer::new(
+            Arc::new(TypeRegistry::new()),
+            Arc::new(FunctionRegistry::new()),
+            Arc::new(Mutex::new(VariableRegistry::new())),
+        );
+        analyzer.analyze(ast).unwrap()
+    }
+
+    fn literal(node: &HirNode) -> &Value {
+        match node {
+            HirNode::Literal { value, .. } => value,
+            other => panic!("Expected folded literal, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn folds_literal_subexpressions() {
+        let hir = analyze("(1 + 2) = 3");
+        assert!(matches!(
+            literal(&hir).data(),
+            crate::value::ValueData::Boolean(true)
+        ));
+
+        let hir = analyze("'a' + 'b'");
+        assert!(matches!(
+            literal(&hir).data(),
+            crate::value::ValueData::String(s) if s.as_ref() == "ab"
+        ));
+
+        let hir = analyze("true and false");
+        assert!(matches!(
+            literal(&hir).data(),
+            crate::value::ValueData::Boolean(false)
+        ));
+    }
+
+    #[test]
+    fn does_not_fold_function_calls_or_paths() {
+        let hir = analyze("now() > @2020-01-01T00:00:00");
+        match hir {
+            HirNode::BinaryOp { left, .. } => {
+                assert!(matches!(*left, HirNode::FunctionCall { .. }))
+            }
+            other => panic!("Expected BinaryOp, got {:?}", other),
+        }
+
+        // Only the literal operand of a mixed expression is folded.
+        let hir = analyze("name.count() > (1 + 1)");
+        match hir {
+            HirNode::BinaryOp { right, .. } => assert!(matches!(
+                literal(&right).data(),
+                crate::value::ValueData::Integer(2)
+            )),
+            other => panic!("Expected BinaryOp, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn leaves_multi_item_results_unfolded() {
+        let hir = analyze("1 | 2");
+        assert!(matches!(hir, HirNode::BinaryOp { .. }));
+    }
+}
@@ -2,6 +2,7 @@
 //!
 //! Context provides access to variables, the current item ($this), and iteration state.
 
+use crate::resolver::ResourceResolver;
 use crate::value::Value;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -22,6 +23,9 @@ pub struct Context {
     pub resource: Value,
     /// Root container resource (usually same as `resource`)
     pub root: Value,
+    /// Resolver for `resolve()` references outside the evaluated resource; overrides the
+    /// engine-level resolver when set
+    pub resource_resolver: Option<Arc<dyn ResourceResolver>>,
 }
 
 impl Context {
@@ -85,6 +89,7 @@ impl Context {
             variables: Arc::new(variables),
             resource,
             root: root_resource,
+            resource_resolver: None,
         }
     }
 
@@ -99,6 +104,12 @@ impl Context {
         self
     }
 
+    /// Use `resolver` for `resolve()` in this evaluation instead of the engine's resolver
+    pub fn with_resource_resolver(mut self, resolver: Arc<dyn ResourceResolver>) -> Self {
+        self.resource_resolver = Some(resolver);
+        self
+    }
+
     /// Push a new iteration context with $this and $index
     pub fn push_this(mut self, this: Value) -> Self {
         self.this = Some(this.clone());
@@ -21,11 +21,12 @@ pub struct CompileOptions {
     /// Optional base type name used for semantic type annotation and (when `strict`)
     /// StructureDefinition-based path validation.
     pub base_type: Option<String>,
-    /// If `true`, invalid path navigation on resolvable FHIR types errors at compile time.
+    /// If `true`, invalid path navigation on resolvable FHIR types (including fields other than
+    /// `id`/`extension` on primitives) errors at compile time. Lenient mode yields empty instead.
     pub strict: bool,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone)]
 pub struct EvalOptions {
     /// Optional base type name used for compile-time typing/validation.
     pub base_type: Option<String>,
@@ -34,6 +35,9 @@ pub struct EvalOptions {
     /// If `true` and `base_type` is not provided, attempt to infer a base type from the
     /// runtime resource (`resourceType`) for relative paths (e.g., `name.given`).
     pub infer_base_type: bool,
+    /// Resolver used by `resolve()` for references that are neither contained nor entries of
+    /// the enclosing Bundle. Overrides the engine-level resolver for this evaluation.
+    pub resource_resolver: Option<Arc<dyn ResourceResolver>>,
 }
 
 impl Default for EvalOptions {
@@ -42,10 +46,22 @@ impl Default for EvalOptions {
             base_type: None,
             strict: false,
             infer_base_type: true,
+            resource_resolver: None,
         }
     }
 }
 
+impl std::fmt::Debug for EvalOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EvalOptions")
+            .field("base_type", &self.base_type)
+            .field("strict", &self.strict)
+            .field("infer_base_type", &self.infer_base_type)
+            .field("resource_resolver", &self.resource_resolver.is_some())
+            .finish()
+    }
+}
+
 /// Main FHIRPath engine
 ///
 /// Requires a FHIR context for runtime type resolution from StructureDefinitions.
@@ -421,6 +437,7 @@ impl Engine {
                 base_type: base_type.map(|s| s.to_string()),
                 strict: base_type.is_some(),
                 infer_base_type: true,
+                resource_resolver: None,
             },
         )
     }
@@ -440,7 +457,10 @@ impl Engine {
                 strict: options.strict,
             },
         )?;
-        self.evaluate(&plan, ctx)
+        match options.resource_resolver {
+            Some(resolver) => self.evaluate(&plan, &ctx.clone().with_resource_resolver(resolver)),
+            None => self.evaluate(&plan, ctx),
+        }
     }
 
     /// Evaluate an expression against a JSON resource.
@@ -479,7 +499,8 @@ impl Engine {
     /// Evaluate an expression against an XML resource string.
     ///
     /// This method converts the XML resource to JSON internally before evaluation.
-    /// The XML must be a valid FHIR resource in XML format.
+    /// The XML must be a valid FHIR resource in XML format; malformed XML is reported
+    /// as [`Error::ParseError`].
     ///
     /// Optionally accepts a base type name for strict validation during compilation.
     ///
@@ -510,10 +531,13 @@ impl Engine {
         xml_resource: &str,
         base_type: Option<&str>,
     ) -> Result<Collection> {
-        let json_str = fhir_format::xml_to_json(xml_resource)
-            .map_err(|e| Error::EvaluationError(format!("XML parse error: {}", e)))?;
+        let json_str = ferrum_format::xml_to_json_with(
+            xml_resource,
+            ferrum_format::XmlToJsonOptions::default(),
+        )
+        .map_err(|e| Error::ParseError(format!("Invalid FHIR XML: {}", e)))?;
         let resource: serde_json::Value = serde_json::from_str(&json_str)
-            .map_err(|e| Error::EvaluationError(format!("JSON parse error: {}", e)))?;
+            .map_err(|e| Error::ParseError(format!("Invalid FHIR XML: {}", e)))?;
         self.evaluate_json(expr, resource, base_type)
     }
 
@@ -57,7 +57,7 @@ impl TypePass {
     ///
     /// * `context_type`: Optional root type name (e.g., "Patient") used for
     ///   semantic type annotation and (optionally) StructureDefinition navigation.
-    /// * `strict`: If `true`, unknown fields on resolvable FHIR types become errors.
+    /// * `strict`: If `true`, unknown fields on resolvable FHIR types and on primitives become errors.
     pub fn resolve(
         &self,
         node: HirNode,
@@ -580,6 +580,30 @@ impl TypePass {
             return Ok((TypeSet::unknown(), Cardinality::ZERO_TO_MANY));
         }
 
+        // Primitive values only carry `id` and `extension`; anything else cannot exist.
+        if base_types.iter().all(|base| self.is_primitive_leaf(base)) {
+            return match field {
+                "extension" => Ok((
+                    TypeSet::singleton(self.type_registry.fhir_named("Extension")),
+                    Cardinality::ZERO_TO_MANY,
+                )),
+                "id" => Ok((
+                    self.type_registry.system_set(TypeId::String),
+                    Cardinality::ZERO_TO_ONE,
+                )),
+                _ if strict => Err(Error::TypeError(format!(
+                    "Path segment '{}' cannot be navigated on primitive type {}",
+                    field,
+                    base_types
+                        .iter()
+                        .map(|t| t.name.as_ref())
+                        .collect::<Vec<_>>()
+                        .join(" | ")
+                ))),
+                _ => Ok((TypeSet::unknown(), Cardinality::ZERO_TO_MANY)),
+            };
+        }
+
         let mut resolved_types: Vec<NamedType> = Vec::new();
         let mut resolved_any = false;
         let mut saw_resolvable_base = false;
@@ -656,6 +680,15 @@ impl TypePass {
         ))
     }
 
+    /// System primitives that have no child elements (Quantity has `value`/`unit`).
+    fn is_primitive_leaf(&self, ty: &NamedType) -> bool {
+        ty.namespace == TypeNamespace::System
+            && self
+                .type_registry
+                .get_type_id_by_name(ty.name.as_ref())
+                .is_some_and(|id| !matches!(id, TypeId::Unknown | TypeId::Quantity))
+    }
+
     fn named_type_from_code(&self, code: &str) -> Option<NamedType> {
         // Common canonical prefixes
         let code = code
@@ -17,7 +17,7 @@ pub enum JsonPathToken {
     Index(usize),
 }
 
-fn resolve_json_at<'a>(
+pub(crate) fn resolve_json_at<'a>(
     mut current: &'a JsonValue,
     path: &[JsonPathToken],
 ) -> Option<&'a JsonValue> {
@@ -11,7 +11,7 @@
 //! - ASCII tree (for terminal viewing)
 
 use crate::ast::AstNode;
-use crate::hir::HirNode;
+use crate::hir::{HirNode, VariableId};
 use crate::types::{ExprType, TypeNamespace};
 use crate::vm::{Opcode, Plan};
 use std::fmt::Write as FmtWrite;
@@ -153,7 +153,7 @@ fn visit_ast_dot(
     let current_id = *counter;
     *counter += 1;
 
-    let label = ast_node_label(node);
+    let label = escape_dot_label(&ast_node_label(node));
     let _ = writeln!(output, "    n{} [label=\"{}\"];", current_id, label);
 
     if let Some(parent) = parent_id {
@@ -505,70 +505,92 @@ fn visualize_hir_dot(node: &HirNode) -> String {
     output
 }
 
+/// Emit `node` and its operands. `parent` carries the parent id and an optional edge label;
+/// lambda arguments are labelled with the variables they are evaluated with (`$this`, ...).
 fn visit_hir_dot(
     node: &HirNode,
     counter: &mut usize,
-    parent_id: Option<usize>,
+    parent: Option<(usize, Option<&str>)>,
     output: &mut String,
 ) {
     let current_id = *counter;
     *counter += 1;
 
     let label = hir_node_label_with_types(node);
-    // Escape special characters for DOT format
-    let escaped_label = label.replace('"', "\\\"").replace('\n', "\\n");
-    let _ = writeln!(output, "    n{} [label=\"{}\"];", current_id, escaped_label);
-
-    if let Some(parent) = parent_id {
-        let _ = writeln!(output, "    n{} -> n{};", parent, current_id);
+    let _ = writeln!(
+        output,
+        "    n{} [label=\"{}\"];",
+        current_id,
+        escape_dot_label(&label)
+    );
+
+    match parent {
+        Some((parent_id, Some(edge_label))) => {
+            let _ = writeln!(
+                output,
+                "    n{} -> n{} [label=\"{}\"];",
+                parent_id,
+                current_id,
+                escape_dot_label(edge_label)
+            );
+        }
+        Some((parent_id, None)) => {
+            let _ = writeln!(output, "    n{} -> n{};", parent_id, current_id);
+        }
+        None => {}
     }
 
-    // Visit children (same as mermaid)
+    let operand = Some((current_id, None));
+    let input = Some((current_id, Some("input")));
+    let this = Some((current_id, Some("$this")));
+
     match node {
         HirNode::Path { base, .. } => {
-            visit_hir_dot(base, counter, Some(current_id), output);
+            visit_hir_dot(base, counter, operand, output);
         }
         HirNode::BinaryOp { left, right, .. } => {
-            visit_hir_dot(left, counter, Some(current_id), output);
-            visit_hir_dot(right, counter, Some(current_id), output);
+            visit_hir_dot(left, counter, Some((current_id, Some("left"))), output);
+            visit_hir_dot(right, counter, Some((current_id, Some("right"))), output);
         }
         HirNode::UnaryOp { expr, .. } => {
-            visit_hir_dot(expr, counter, Some(current_id), output);
+            visit_hir_dot(expr, counter, operand, output);
         }
         HirNode::FunctionCall { args, .. } => {
             for arg in args {
-                visit_hir_dot(arg, counter, Some(current_id), output);
+                visit_hir_dot(arg, counter, operand, output);
             }
         }
         HirNode::MethodCall { base, args, .. } => {
-            visit_hir_dot(base, counter, Some(current_id), output);
+            visit_hir_dot(base, counter, input, output);
             for arg in args {
-                visit_hir_dot(arg, counter, Some(current_id), output);
+                visit_hir_dot(arg, counter, operand, output);
             }
         }
         HirNode::Where {
             collection,
             predicate_hir,
             ..
+        }
+        | HirNode::All {
+            collection,
+            predicate_hir,
+            ..
         } => {
-            visit_hir_dot(collection, counter, Some(current_id), output);
-            visit_hir_dot(predicate_hir, counter, Some(current_id), output);
+            visit_hir_dot(collection, counter, input, output);
+            visit_hir_dot(predicate_hir, counter, this, output);
         }
         HirNode::Select {
             collection,
             projection_hir,
             ..
-        } => {
-            visit_hir_dot(collection, counter, Some(current_id), output);
-            visit_hir_dot(projection_hir, counter, Some(current_id), output);
         }
-        HirNode::Repeat {
+        | HirNode::Repeat {
             collection,
             projection_hir,
             ..
         } => {
-            visit_hir_dot(collection, counter, Some(current_id), output);
-            visit_hir_dot(projection_hir, counter, Some(current_id), output);
+            visit_hir_dot(collection, counter, input, output);
+            visit_hir_dot(projection_hir, counter, this, output);
         }
         HirNode::Aggregate {
             collection,
@@ -576,10 +598,15 @@ fn visit_hir_dot(
             init_value_hir,
             ..
         } => {
-            visit_hir_dot(collection, counter, Some(current_id), output);
-            visit_hir_dot(aggregator_hir, counter, Some(current_id), output);
+            visit_hir_dot(collection, counter, input, output);
+            visit_hir_dot(
+                aggregator_hir,
+                counter,
+                Some((current_id, Some("$this, $total"))),
+                output,
+            );
             if let Some(init) = init_value_hir {
-                visit_hir_dot(init, counter, Some(current_id), output);
+                visit_hir_dot(init, counter, Some((current_id, Some("init"))), output);
             }
         }
         HirNode::Exists {
@@ -587,18 +614,26 @@ fn visit_hir_dot(
             predicate_hir,
             ..
         } => {
-            visit_hir_dot(collection, counter, Some(current_id), output);
+            visit_hir_dot(collection, counter, input, output);
             if let Some(pred) = predicate_hir {
-                visit_hir_dot(pred, counter, Some(current_id), output);
+                visit_hir_dot(pred, counter, this, output);
             }
         }
         HirNode::TypeOp { expr, .. } => {
-            visit_hir_dot(expr, counter, Some(current_id), output);
+            visit_hir_dot(expr, counter, operand, output);
         }
-        _ => {} // Leaf nodes
+        HirNode::Literal { .. } | HirNode::Variable { .. } => {}
     }
 }
 
+/// Escape text for use inside a double-quoted DOT string.
+fn escape_dot_label(label: &str) -> String {
+    label
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
 fn visualize_hir_ascii(node: &HirNode, depth: usize) -> String {
     let indent = "  ".repeat(depth);
     let label = hir_node_label_with_types(node);
@@ -691,17 +726,22 @@ fn visualize_hir_ascii(node: &HirNode, depth: usize) -> String {
     output
 }
 
+/// Label for a variable reference; ids 0-2 are the implicit lambda variables.
+fn variable_label(var_id: VariableId, name: Option<&str>) -> String {
+    match (name, var_id) {
+        (Some(n), _) => format!("Var: {}", n),
+        (None, 0) => "Var: $this".to_string(),
+        (None, 1) => "Var: $index".to_string(),
+        (None, 2) => "Var: $total".to_string(),
+        (None, id) => format!("Var[{}]", id),
+    }
+}
+
 #[allow(dead_code)]
 fn hir_node_label(node: &HirNode) -> String {
     match node {
         HirNode::Literal { value, .. } => format!("Lit: {:?}", value),
-        HirNode::Variable { var_id, name, .. } => {
-            if let Some(n) = name {
-                format!("Var: {}", n)
-            } else {
-                format!("Var[{}]", var_id)
-            }
-        }
+        HirNode::Variable { var_id, name, .. } => variable_label(*var_id, name.as_deref()),
         HirNode::Path { segments, .. } => {
             let path = segments
                 .iter()
@@ -732,11 +772,7 @@ fn hir_node_label_with_types(node: &HirNode) -> String {
             format!("Lit: {:?}\nType: {}", value, format_expr_type(ty))
         }
         HirNode::Variable { var_id, name, ty } => {
-            let var_name = if let Some(n) = name {
-                format!("Var: {}", n)
-            } else {
-                format!("Var[{}]", var_id)
-            };
+            let var_name = variable_label(*var_id, name.as_deref());
             format!("{}\nType: {}", var_name, format_expr_type(ty))
         }
         HirNode::Path {
@@ -1057,7 +1093,7 @@ fn visualize_plan_dot(plan: &Plan) -> String {
     output.push_str("        color=lightgrey;\n");
 
     for (i, opcode) in plan.opcodes.iter().enumerate() {
-        let label = format_opcode(opcode, plan);
+        let label = escape_dot_label(&format_opcode(opcode, plan));
         let _ = writeln!(output, "        i{} [label=\"{}. {}\"];", i, i, label);
         if i > 0 {
             let _ = writeln!(output, "        i{} -> i{};", i - 1, i);
@@ -1074,7 +1110,7 @@ fn visualize_plan_dot(plan: &Plan) -> String {
         output.push_str("        color=lightblue;\n");
 
         for (i, opcode) in subplan.opcodes.iter().enumerate() {
-            let label = format_opcode(opcode, subplan);
+            let label = escape_dot_label(&format_opcode(opcode, subplan));
             let _ = writeln!(
                 output,
                 "        s{}_i{} [label=\"{}. {}\"];",
@@ -1217,4 +1253,47 @@ mod tests {
 
         assert!(ascii.contains("Integer: 42"));
     }
+
+    #[test]
+    fn test_hir_dot_connects_where_and_path() {
+        use crate::Engine;
+        use ferrum_context::{ConflictPolicy, DefaultFhirContext, FhirContext};
+        use std::sync::Arc;
+
+        let context: Arc<dyn FhirContext> = Arc::new(DefaultFhirContext::from_packages(
+            vec![],
+            ConflictPolicy::default(),
+        ));
+        let engine = Engine::new(context, None);
+        let dot = engine
+            .visualize_hir(
+                "Patient.name.where(use='official').given",
+                VisualizationFormat::Dot,
+            )
+            .unwrap();
+
+        assert!(dot.starts_with("digraph HIR {"));
+        assert!(dot.trim_end().ends_with('}'));
+
+        let node_id = |needle: &str| {
+            dot.lines()
+                .find(|line| line.contains("[label=\"") && line.contains(needle))
+                .and_then(|line| line.split_whitespace().next())
+                .unwrap_or_else(|| panic!("no node labelled {}", needle))
+                .to_string()
+        };
+        let given = node_id("Path: given");
+        let where_node = node_id("where()");
+        assert!(dot.contains(&format!("    {} -> {};", given, where_node)));
+
+        // The predicate is evaluated with $this bound to each input item
+        let predicate = node_id("BinOp: Eq");
+        assert!(dot.contains(&format!(
+            "    {} -> {} [label=\"$this\"];",
+            where_node, predicate
+        )));
+        assert!(dot.contains("Var: $this"));
+        // Quotes inside labels are escaped
+        assert!(dot.contains(r#"String(\"official\")"#));
+    }
 }
@@ -10,8 +10,9 @@ use crate::error::{Error, Result};
 use crate::hir::HirBinaryOperator;
 use crate::value::{Collection, Value, ValueData};
 use functions::{aggregate_with_subplans, execute_function};
-use operations::execute_binary_op;
+pub(crate) use operations::execute_binary_op;
 use serde_json::Value as JsonValue;
+use std::collections::{HashSet, VecDeque};
 use std::sync::Arc;
 
 /// Unary plus operation
@@ -606,7 +607,10 @@ impl<'a> Vm<'a> {
                         self.ctx,
                         path_str.as_deref(),
                         Some(self.engine.fhir_context().as_ref()),
-                        self.engine.resource_resolver(),
+                        self.ctx
+                            .resource_resolver
+                            .as_ref()
+                            .or(self.engine.resource_resolver()),
                     )?;
                     self.stack.push(result);
                     ip += 1;
@@ -674,6 +678,7 @@ impl<'a> Vm<'a> {
                                 variables: self.ctx.variables.clone(),
                                 resource: self.ctx.resource.clone(),
                                 root: self.ctx.root.clone(),
+                                resource_resolver: self.ctx.resource_resolver.clone(),
                             };
 
                             let mut item_vm = Vm::new_for_predicate(&item_context, self.engine);
@@ -724,6 +729,7 @@ impl<'a> Vm<'a> {
                             variables: self.ctx.variables.clone(),
                             resource: self.ctx.resource.clone(),
                             root: self.ctx.root.clone(),
+                            resource_resolver: self.ctx.resource_resolver.clone(),
                         };
 
                         let mut item_vm = Vm::new_for_predicate(&item_context, self.engine);
@@ -796,6 +802,7 @@ impl<'a> Vm<'a> {
                         variables: self.ctx.variables.clone(),
                         resource: self.ctx.resource.clone(),
                         root: self.ctx.root.clone(),
+                        resource_resolver: self.ctx.resource_resolver.clone(),
                     };
 
                     // Evaluate predicate
@@ -1093,6 +1100,7 @@ impl<'a> Vm<'a> {
                 variables: self.ctx.variables.clone(),
                 resource: self.ctx.resource.clone(),
                 root: self.ctx.root.clone(),
+                resource_resolver: self.ctx.resource_resolver.clone(),
             };
 
             // Execute predicate subplan
@@ -1149,6 +1157,7 @@ impl<'a> Vm<'a> {
                 variables: self.ctx.variables.clone(),
                 resource: self.ctx.resource.clone(),
                 root: self.ctx.root.clone(),
+                resource_resolver: self.ctx.resource_resolver.clone(),
             };
 
             // Execute projection subplan
@@ -1174,30 +1183,19 @@ impl<'a> Vm<'a> {
             return Ok(Collection::empty());
         }
 
-        // Input queue: items to process
-        let mut input_queue: Vec<Value> = collection.iter().cloned().collect();
+        // Breadth-first queue of items whose projection is still pending
+        let mut input_queue: VecDeque<Value> = collection.iter().cloned().collect();
         // Output collection: unique items found
         let mut result = Collection::empty();
-        // Seen items: track items already in output (cycle detection)
-        let mut seen_items = Vec::new();
-        // Safety limit to prevent infinite loops
+        // Items already in the output. `Value` equality is node identity for elements
+        // (same JSON node or object), so structurally identical siblings are both kept
+        // while a node reached twice is only emitted once.
+        let mut seen: HashSet<Value> = HashSet::new();
+        // Safety limit for projections that keep producing new values (e.g. `$this + 1`)
         const MAX_ITERATIONS: usize = 10000;
         let mut iterations = 0;
 
-        // Helper to check if item is already seen using FHIRPath equality semantics.
-        let is_seen = |item: &Value, seen: &[Value]| -> bool {
-            seen.iter().any(|seen_item| {
-                let left = Collection::singleton(item.clone());
-                let right = Collection::singleton(seen_item.clone());
-                match execute_binary_op(HirBinaryOperator::Eq, left, right) {
-                    Ok(res) => res.as_boolean().unwrap_or(false),
-                    Err(_) => false,
-                }
-            })
-        };
-
-        // Process queue until empty (with safety limit)
-        while let Some(current_item) = input_queue.pop() {
+        while let Some(current_item) = input_queue.pop_front() {
             iterations += 1;
             if iterations > MAX_ITERATIONS {
                 return Err(Error::EvaluationError(format!(
@@ -1206,14 +1204,15 @@ impl<'a> Vm<'a> {
                 )));
             }
 
-            // Create context with $this = current_item
+            // $this = current_item, $index = its position in processing order
             let item_context = Context {
                 this: Some(current_item.clone()),
-                index: None,
+                index: Some(iterations - 1),
                 strict: self.ctx.strict,
                 variables: self.ctx.variables.clone(),
                 resource: self.ctx.resource.clone(),
                 root: self.ctx.root.clone(),
+                resource_resolver: self.ctx.resource_resolver.clone(),
             };
 
             // Execute projection subplan
@@ -1222,12 +1221,9 @@ impl<'a> Vm<'a> {
 
             // Process all items from projection result
             for new_item in projection_result.iter() {
-                // Check if item is already in output (cycle detection)
-                if !is_seen(new_item, &seen_items) {
-                    // Add to output and queue for further processing
+                if seen.insert(new_item.clone()) {
                     result.push(new_item.clone());
-                    seen_items.push(new_item.clone());
-                    input_queue.push(new_item.clone());
+                    input_queue.push_back(new_item.clone());
                 }
             }
         }
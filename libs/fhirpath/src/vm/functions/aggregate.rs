@@ -66,6 +66,7 @@ pub fn aggregate_with_subplans(
             variables: ctx.variables.clone(),
             resource: ctx.resource.clone(),
             root: ctx.root.clone(),
+            resource_resolver: ctx.resource_resolver.clone(),
         };
 
         let mut item_vm = crate::vm::Vm::new_for_predicate(&item_context, engine);
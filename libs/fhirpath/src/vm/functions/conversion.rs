@@ -32,7 +32,7 @@ fn normalize_quantity_calendar_keyword(unit: &str) -> Option<&'static str> {
     }
 }
 
-fn normalize_quantity_quoted_ucum_unit(unit: &str) -> Option<&str> {
+fn normalize_quantity_ucum_unit(unit: &str) -> Option<&str> {
     let u = unit.trim();
     if u.is_empty() {
         return None;
@@ -41,9 +41,39 @@ fn normalize_quantity_quoted_ucum_unit(unit: &str) -> Option<&str> {
     if u.eq_ignore_ascii_case("mo") || u.eq_ignore_ascii_case("a") {
         return None;
     }
+    ferrum_ucum::validate(u).ok()?;
     Some(u)
 }
 
+/// Parse the string form of a quantity: a number optionally followed by a unit.
+///
+/// The unit is either quoted UCUM (`'mg'`), a calendar duration keyword (`days`), or a bare
+/// UCUM unit (`mg`). UCUM units must pass `ferrum_ucum::validate`; a number without a unit
+/// gets the unit `'1'`. Returns `None` when the string is not a valid quantity.
+fn parse_quantity_string(input: &str) -> Option<Value> {
+    static QUANTITY_RE: OnceLock<Regex> = OnceLock::new();
+    let re = QUANTITY_RE.get_or_init(|| {
+        Regex::new(
+            r"^\s*(?P<value>(\+|-)?\d+(?:\.\d+)?)\s*(?:'(?P<unit>[^']+)'|(?P<word>\S+))?\s*$",
+        )
+        .expect("toQuantity regex must compile")
+    });
+
+    let caps = re.captures(input)?;
+    let value = Decimal::from_str(caps.name("value")?.as_str()).ok()?;
+
+    let unit = if let Some(unit) = caps.name("unit") {
+        normalize_quantity_ucum_unit(unit.as_str())?
+    } else if let Some(word) = caps.name("word") {
+        normalize_quantity_calendar_keyword(word.as_str())
+            .or_else(|| normalize_quantity_ucum_unit(word.as_str()))?
+    } else {
+        "1"
+    };
+
+    Some(Value::quantity(value, Arc::from(unit)))
+}
+
 pub fn iif(
     _collection: Collection,
     arg1: Option<&Collection>,
@@ -681,14 +711,6 @@ pub fn to_quantity(collection: Collection) -> Result<Collection> {
         ));
     }
 
-    static TO_QUANTITY_RE: OnceLock<Regex> = OnceLock::new();
-    let re = TO_QUANTITY_RE.get_or_init(|| {
-        Regex::new(
-            r"^\s*(?P<value>(\+|-)?\d+(?:\.\d+)?)\s*(?:'(?P<unit>[^']+)'|(?P<time>[a-zA-Z]+))?\s*$",
-        )
-        .expect("toQuantity regex must compile")
-    });
-
     let item = collection.iter().next().unwrap();
     match item.data() {
         ValueData::Quantity { .. } => Ok(collection),
@@ -701,33 +723,9 @@ pub fn to_quantity(collection: Collection) -> Result<Collection> {
             if *b { Decimal::ONE } else { Decimal::ZERO },
             Arc::from("1"),
         ))),
-        ValueData::String(s) => {
-            let Some(caps) = re.captures(s.as_ref()) else {
-                return Ok(Collection::empty());
-            };
-            let Some(value_str) = caps.name("value").map(|m| m.as_str()) else {
-                return Ok(Collection::empty());
-            };
-            let Ok(value) = Decimal::from_str(value_str) else {
-                return Ok(Collection::empty());
-            };
-
-            let unit = if let Some(unit) = caps.name("unit").map(|m| m.as_str()) {
-                let Some(unit) = normalize_quantity_quoted_ucum_unit(unit) else {
-                    return Ok(Collection::empty());
-                };
-                Arc::from(unit)
-            } else if let Some(time) = caps.name("time").map(|m| m.as_str()) {
-                let Some(unit) = normalize_quantity_calendar_keyword(time) else {
-                    return Ok(Collection::empty());
-                };
-                Arc::from(unit)
-            } else {
-                Arc::from("1")
-            };
-
-            Ok(Collection::singleton(Value::quantity(value, unit)))
-        }
+        ValueData::String(s) => Ok(parse_quantity_string(s.as_ref())
+            .map(Collection::singleton)
+            .unwrap_or_else(Collection::empty)),
         _ => Ok(Collection::empty()),
     }
 }
@@ -744,31 +742,69 @@ pub fn converts_to_quantity(collection: Collection) -> Result<Collection> {
         ));
     }
 
-    static TO_QUANTITY_RE: OnceLock<Regex> = OnceLock::new();
-    let re = TO_QUANTITY_RE.get_or_init(|| {
-        Regex::new(
-            r"^\s*(?P<value>(\+|-)?\d+(?:\.\d+)?)\s*(?:'(?P<unit>[^']+)'|(?P<time>[a-zA-Z]+))?\s*$",
-        )
-        .expect("toQuantity regex must compile")
-    });
-
     let item = collection.iter().next().unwrap();
     let ok = match item.data() {
         ValueData::Quantity { .. }
         | ValueData::Integer(_)
         | ValueData::Decimal(_)
         | ValueData::Boolean(_) => true,
-        ValueData::String(s) => re.captures(s.as_ref()).is_some_and(|caps| {
-            if let Some(unit) = caps.name("unit").map(|m| m.as_str()) {
-                normalize_quantity_quoted_ucum_unit(unit).is_some()
-            } else if let Some(time) = caps.name("time").map(|m| m.as_str()) {
-                normalize_quantity_calendar_keyword(time).is_some()
-            } else {
-                true
-            }
-        }),
+        ValueData::String(s) => parse_quantity_string(s.as_ref()).is_some(),
         _ => false,
     };
 
     Ok(Collection::singleton(Value::boolean(ok)))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quantity_of(value: Value) -> Option<(Decimal, String)> {
+        let result = to_quantity(Collection::singleton(value)).unwrap();
+        let item = result.iter().next()?.clone();
+        match item.data() {
+            ValueData::Quantity { value, unit } => Some((*value, unit.to_string())),
+            other => panic!("expected quantity, got {:?}", other),
+        }
+    }
+
+    fn converts(value: Value) -> bool {
+        converts_to_quantity(Collection::singleton(value))
+            .unwrap()
+            .as_boolean()
+            .unwrap()
+    }
+
+    #[test]
+    fn numbers_convert_to_unitless_quantities() {
+        assert_eq!(
+            quantity_of(Value::integer(5)),
+            Some((Decimal::from(5), "1".to_string()))
+        );
+        assert_eq!(
+            quantity_of(Value::string("5.4")),
+            Some((Decimal::from_str("5.4").unwrap(), "1".to_string()))
+        );
+        assert!(converts(Value::decimal(Decimal::from_str("5.4").unwrap())));
+    }
+
+    #[test]
+    fn strings_with_valid_ucum_units_convert() {
+        let expected = Some((Decimal::from_str("5.4").unwrap(), "mg".to_string()));
+        assert_eq!(quantity_of(Value::string("5.4 mg")), expected);
+        assert_eq!(quantity_of(Value::string("5.4 'mg'")), expected);
+        assert_eq!(
+            quantity_of(Value::string("2 days")),
+            Some((Decimal::from(2), "day".to_string()))
+        );
+        assert!(converts(Value::string("5.4 mg")));
+    }
+
+    #[test]
+    fn strings_with_invalid_units_do_not_convert() {
+        for input in ["5 'notaunit'", "5 notaunit", "5 mg extra", "mg"] {
+            assert_eq!(quantity_of(Value::string(input)), None, "{}", input);
+            assert!(!converts(Value::string(input)), "{}", input);
+        }
+    }
+}
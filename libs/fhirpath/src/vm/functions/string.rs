@@ -353,6 +353,45 @@ pub fn replace(
     Ok(Collection::singleton(Value::string(result)))
 }
 
+/// Compile a FHIRPath regular expression with the `regex` crate.
+///
+/// Patterns are case-sensitive and use single-line mode (`.` matches newlines), as the
+/// specification requires. Invalid patterns are evaluation errors.
+#[cfg(feature = "regex")]
+fn compile_regex(pattern: &str) -> Result<Regex> {
+    Regex::new(&format!("(?s){}", pattern))
+        .map_err(|e| Error::InvalidOperation(format!("Invalid regular expression: {}", e)))
+}
+
+/// Translate a `replaceMatches()` substitution into a `regex` replacement template.
+///
+/// `$1`..`$n` are group references and end at the last digit, so `$1abc` is group 1
+/// followed by `abc` (the `regex` crate alone would read a group named `1abc`).
+/// `\$` is a literal dollar sign.
+#[cfg(feature = "regex")]
+fn substitution_template(substitution: &str) -> String {
+    let mut out = String::with_capacity(substitution.len());
+    let mut chars = substitution.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'$') => {
+                chars.next();
+                out.push_str("$$");
+            }
+            '$' if chars.peek().is_some_and(|n| n.is_ascii_digit()) => {
+                out.push_str("${");
+                while let Some(d) = chars.peek().copied().filter(|d| d.is_ascii_digit()) {
+                    out.push(d);
+                    chars.next();
+                }
+                out.push('}');
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
 pub fn matches(collection: Collection, pattern_arg: Option<&Collection>) -> Result<Collection> {
     // matches() returns true when the value matches the given regular expression
     // Regular expressions are case-sensitive and use 'single line' mode (DOTALL)
@@ -377,11 +416,8 @@ pub fn matches(collection: Collection, pattern_arg: Option<&Collection>) -> Resu
             .as_string()
             .map_err(|_| Error::TypeError("matches() pattern must be a string".into()))?;
 
-        // Compile regex with DOTALL flag (single line mode) to align with FHIRPath
-        let regex = Regex::new(&format!("(?s){}", pattern_str.as_ref()))
-            .map_err(|e| Error::InvalidOperation(format!("Invalid regular expression: {}", e)))?;
-
-        // Use is_match which is equivalent to re.search() in Python
+        // Unanchored search: the pattern may match anywhere in the input
+        let regex = compile_regex(pattern_str.as_ref())?;
         let matched = regex.is_match(input_str.as_ref());
         Ok(Collection::singleton(Value::boolean(matched)))
     }
@@ -427,10 +463,8 @@ pub fn matches_full(
             .as_string()
             .map_err(|_| Error::TypeError("matchesFull() pattern must be a string".into()))?;
 
-        // Compile regex with DOTALL flag (single line mode) and anchor for full match
-        let anchored = format!("(?s)^(?:{})$", pattern_str.as_ref());
-        let regex = Regex::new(&anchored)
-            .map_err(|e| Error::InvalidOperation(format!("Invalid regular expression: {}", e)))?;
+        // Anchor the whole pattern for a full match
+        let regex = compile_regex(&format!("^(?:{})$", pattern_str.as_ref()))?;
 
         let matched = regex.is_match(input_str.as_ref());
         Ok(Collection::singleton(Value::boolean(matched)))
@@ -489,13 +523,9 @@ pub fn replace_matches(
             Error::TypeError("replaceMatches() replacement must be a string".into())
         })?;
 
-        // Compile regex with DOTALL flag (single line mode)
-        let regex = Regex::new(pattern_str.as_ref())
-            .map_err(|e| Error::InvalidOperation(format!("Invalid regular expression: {}", e)))?;
-
-        // Use replace_all which is equivalent to re.sub() in Python
-        // Note: Rust's regex crate uses $name or ${name} for named captures, which matches FHIRPath spec
-        let result = regex.replace_all(input_str.as_ref(), replacement_str.as_ref());
+        let regex = compile_regex(pattern_str.as_ref())?;
+        let substitution = substitution_template(replacement_str.as_ref());
+        let result = regex.replace_all(input_str.as_ref(), substitution.as_str());
         Ok(Collection::singleton(Value::string(result.to_string())))
     }
 
@@ -921,6 +951,47 @@ mod tests {
         assert!(!result.as_boolean().unwrap());
     }
 
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_matches_searches_anywhere_in_input() {
+        let input = Collection::singleton(Value::string("abc123"));
+        let digits = Collection::singleton(Value::string("[0-9]+"));
+        assert!(matches(input.clone(), Some(&digits))
+            .unwrap()
+            .as_boolean()
+            .unwrap());
+        assert!(!matches_full(input, Some(&digits))
+            .unwrap()
+            .as_boolean()
+            .unwrap());
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_replace_matches_group_references() {
+        let input = Collection::singleton(Value::string("abc123"));
+        let pattern = Collection::singleton(Value::string("([a-z]+)([0-9]+)"));
+
+        let swapped = Collection::singleton(Value::string("$2$1"));
+        let result = replace_matches(input.clone(), Some(&pattern), Some(&swapped)).unwrap();
+        assert_eq!(result.as_string().unwrap().as_ref(), "123abc");
+
+        let suffixed = Collection::singleton(Value::string("$1x \\$2"));
+        let result = replace_matches(input, Some(&pattern), Some(&suffixed)).unwrap();
+        assert_eq!(result.as_string().unwrap().as_ref(), "abcx $2");
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_invalid_regex_is_an_error() {
+        let input = Collection::singleton(Value::string("abc"));
+        let invalid = Collection::singleton(Value::string("(unclosed"));
+        let replacement = Collection::singleton(Value::string(""));
+        assert!(matches(input.clone(), Some(&invalid)).is_err());
+        assert!(matches_full(input.clone(), Some(&invalid)).is_err());
+        assert!(replace_matches(input, Some(&invalid), Some(&replacement)).is_err());
+    }
+
     #[test]
     pub fn matches_fhir_integer_with_path_hint() {
         let val = Value::integer(1);
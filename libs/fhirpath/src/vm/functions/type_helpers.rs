@@ -974,6 +974,7 @@ fn check_fhir_type(
         actual_name == target_name
     } else {
         check_type_inheritance(&actual_name, &target_name)
+            || fhir_context.is_some_and(|fc| fhir_type_is_a(fc, &descriptor.name, &target_name))
     }
 }
 
@@ -987,12 +988,7 @@ fn fhir_type_is_a(fc: &dyn FhirContext, actual_type: &str, target_type_lower: &s
             return true;
         }
 
-        let sd = match fc.get_core_structure_definition_by_type(&current) {
-            Ok(Some(sd)) => sd,
-            _ => return false,
-        };
-
-        let Some(base_def) = sd.base_definition.as_deref() else {
+        let Some(base_def) = core_base_definition(fc, &current) else {
             return false;
         };
 
@@ -1005,6 +1001,27 @@ fn fhir_type_is_a(fc: &dyn FhirContext, actual_type: &str, target_type_lower: &s
     }
 }
 
+/// Resolve the `baseDefinition` of the core StructureDefinition for a FHIR type name.
+///
+/// Path hints capitalize choice suffixes (`valueString` → `String`), so when the name
+/// is not found as given, retry with the case of its first letter flipped.
+fn core_base_definition(fc: &dyn FhirContext, type_name: &str) -> Option<String> {
+    let mut chars = type_name.chars();
+    let first = chars.next()?;
+    let flipped: String = if first.is_uppercase() {
+        first.to_lowercase().chain(chars).collect()
+    } else {
+        first.to_uppercase().chain(chars).collect()
+    };
+
+    for name in [type_name, flipped.as_str()] {
+        if let Ok(Some(sd)) = fc.get_core_structure_definition_by_type(name) {
+            return sd.base_definition.clone();
+        }
+    }
+    None
+}
+
 /// Centralized FHIR primitive type inheritance rules
 /// Returns true if actual_type is-a target_type (including exact match)
 fn check_type_inheritance(actual_type: &str, target_type: &str) -> bool {
@@ -1230,6 +1247,7 @@ fn match_fhir_value_unqualified(
             if hint_lower == spec_name
                 || hint_lower.ends_with(spec_name)
                 || check_type_inheritance(&hint_lower, spec_name)
+                || fhir_context.is_some_and(|fc| fhir_type_is_a(fc, &type_hint, spec_name))
             {
                 return true;
             }
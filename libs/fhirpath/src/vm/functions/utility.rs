@@ -3,7 +3,6 @@
 //! This module implements various utility functions like `trace()`, `now()`, `today()`,
 //! `sort()`, `type()`, `conformsTo()`, etc.
 
-use std::collections::HashMap;
 use std::str::FromStr;
 use std::sync::Arc;
 
@@ -11,12 +10,15 @@ use chrono::TimeZone;
 use rust_decimal::Decimal;
 
 use crate::context::Context;
+use crate::conversion::ToJson;
 use crate::error::{Error, Result};
 use crate::hir::HirBinaryOperator;
 use crate::resolver::ResourceResolver;
-use crate::value::{Collection, Value, ValueData};
+use crate::value::{resolve_json_at, Collection, JsonPathToken, Value, ValueData};
 use crate::vm::operations::execute_binary_op;
 use ferrum_context::FhirContext;
+use serde_json::Value as JsonValue;
+use smallvec::SmallVec;
 
 use super::type_helpers::{
     choose_declared_type_for_value, infer_type_descriptor, normalize_type_code, type_info_value,
@@ -1206,13 +1208,14 @@ pub fn has_value(collection: Collection) -> Result<Collection> {
 
 /// Resolve references to resources
 ///
-/// This function resolves FHIR references in three ways:
-/// 1. Contained resources (references starting with '#')
-/// 2. External resources (via custom ResourceResolver if provided)
-/// 3. Already-resolved resource objects (pass-through)
+/// A reference is looked up, in order, among:
+/// 1. Contained resources (`#id`) of the resource holding the reference
+/// 2. Entries of the enclosing Bundle, by `fullUrl` (relative references are resolved against
+///    the referencing entry's `fullUrl`) and then by resource type and id
+/// 3. The custom ResourceResolver, if one is provided
 ///
-/// If a custom ResourceResolver is provided, it will be used for external references.
-/// Otherwise, only contained references can be resolved.
+/// Items that are resources rather than references pass through unchanged; references that
+/// cannot be resolved are dropped.
 pub fn resolve(
     collection: Collection,
     ctx: &Context,
@@ -1222,25 +1225,6 @@ pub fn resolve(
         return Ok(Collection::empty());
     }
 
-    // Build index of contained resources by local reference (#id)
-    let mut contained_index: HashMap<String, Value> = HashMap::new();
-    if let ValueData::Object(obj) = ctx.resource.data() {
-        if let Some(contained) = obj.get("contained") {
-            for res in contained.iter() {
-                if let ValueData::Object(res_obj) = res.data() {
-                    if let Some(id_col) = res_obj.get("id") {
-                        if let Some(id_val) = id_col.iter().next() {
-                            if let ValueData::String(id_str) = id_val.data() {
-                                contained_index
-                                    .insert(format!("#{}", id_str.as_ref()), res.clone());
-                            }
-                        }
-                    }
-                }
-            }
-        }
-    }
-
     let mut resolved = Collection::empty();
 
     for item in collection.iter() {
@@ -1253,36 +1237,229 @@ pub fn resolve(
                     ValueData::String(s) => Some(s.as_ref().to_string()),
                     _ => None,
                 }),
+            ValueData::LazyJson { .. } => item
+                .data()
+                .resolved_json()
+                .and_then(|json| json.get("reference"))
+                .and_then(|v| v.as_str())
+                .map(str::to_string),
             _ => None,
         };
 
-        if let Some(ref_str) = reference {
-            // Check if it's a contained reference first
-            if ref_str.starts_with('#') {
-                if let Some(target) = contained_index.get(&ref_str) {
-                    resolved.push(target.clone());
-                }
-            } else if let Some(resolver) = resource_resolver {
-                // Use custom resolver for external references
-                match resolver.resolve(&ref_str) {
-                    Ok(Some(resource)) => {
-                        resolved.push(resource);
-                    }
-                    Ok(None) => {
-                        // Reference not found - skip (don't add to result)
-                    }
-                    Err(e) => {
-                        // Resolution error - propagate
-                        return Err(e);
-                    }
+        let Some(reference) = reference else {
+            if matches!(
+                item.data(),
+                ValueData::Object(_) | ValueData::LazyJson { .. }
+            ) {
+                // Already a resource-like object - treat as resolved
+                resolved.push(item.clone());
+            }
+            continue;
+        };
+
+        let bundle = BundleScope::enclosing(item).or_else(|| BundleScope::from_context(ctx));
+
+        if let Some(local_id) = reference.strip_prefix('#') {
+            let container = match &bundle {
+                Some(scope) if scope.source_entry.is_some() => {
+                    scope.source_entry.and_then(|idx| scope.entry_resource(idx))
                 }
+                _ => Some(ctx.resource.clone()),
+            };
+            if let Some(target) = container.and_then(|c| find_contained(&c, local_id)) {
+                resolved.push(target);
+            }
+            continue;
+        }
+
+        if let Some(target) = bundle.as_ref().and_then(|scope| scope.resolve(&reference)) {
+            resolved.push(target);
+        } else if let Some(resolver) = resource_resolver {
+            // Use custom resolver for external references; a miss is skipped
+            if let Some(resource) = resolver.resolve(&reference)? {
+                resolved.push(resource);
             }
-            // else: external reference without resolver - skip
-        } else if matches!(item.data(), ValueData::Object(_)) {
-            // Already a resource-like object - treat as resolved
-            resolved.push(item.clone());
         }
     }
 
     Ok(resolved)
 }
+
+/// Find a contained resource by local id; an empty id refers to the container itself.
+fn find_contained(container: &Value, local_id: &str) -> Option<Value> {
+    if local_id.is_empty() {
+        return Some(container.clone());
+    }
+
+    let has_id = |res: &Value| match res.data() {
+        ValueData::Object(obj) => obj
+            .get("id")
+            .and_then(|col| col.iter().next())
+            .is_some_and(|v| matches!(v.data(), ValueData::String(id) if id.as_ref() == local_id)),
+        ValueData::LazyJson { .. } => {
+            res.data()
+                .resolved_json()
+                .and_then(|json| json.get("id"))
+                .and_then(|v| v.as_str())
+                == Some(local_id)
+        }
+        _ => false,
+    };
+
+    match container.data() {
+        ValueData::Object(obj) => obj.get("contained")?.iter().find(|r| has_id(r)).cloned(),
+        ValueData::LazyJson { root, path } => {
+            let contained = container
+                .data()
+                .resolved_json()?
+                .get("contained")?
+                .as_array()?;
+            contained.iter().enumerate().find_map(|(idx, node)| {
+                let mut child_path = path.clone();
+                child_path.push(JsonPathToken::Key(Arc::from("contained")));
+                child_path.push(JsonPathToken::Index(idx));
+                let res = Value::from_json_node(root.clone(), child_path, node);
+                has_id(&res).then_some(res)
+            })
+        }
+        _ => None,
+    }
+}
+
+/// A Bundle whose entries references may resolve against
+struct BundleScope {
+    root: Arc<JsonValue>,
+    /// Path of the Bundle node within `root`
+    path: SmallVec<[JsonPathToken; 4]>,
+    /// Entry holding the reference being resolved, when known
+    source_entry: Option<usize>,
+}
+
+impl BundleScope {
+    /// The innermost Bundle containing `item`, found from its position in the JSON tree.
+    fn enclosing(item: &Value) -> Option<Self> {
+        let ValueData::LazyJson { root, path } = item.data() else {
+            return None;
+        };
+        (0..path.len().saturating_sub(1)).rev().find_map(|at| {
+            let (JsonPathToken::Key(key), JsonPathToken::Index(entry)) = (&path[at], &path[at + 1])
+            else {
+                return None;
+            };
+            if key.as_ref() != "entry" {
+                return None;
+            }
+            let node = resolve_json_at(root.as_ref(), &path[..at])?;
+            is_bundle(node).then(|| Self {
+                root: root.clone(),
+                path: path[..at].iter().cloned().collect(),
+                source_entry: Some(*entry),
+            })
+        })
+    }
+
+    /// The evaluation root (or resource) when it is a Bundle.
+    fn from_context(ctx: &Context) -> Option<Self> {
+        [&ctx.root, &ctx.resource]
+            .into_iter()
+            .find_map(|value| match value.data() {
+                ValueData::LazyJson { root, path } => {
+                    let node = resolve_json_at(root.as_ref(), path)?;
+                    is_bundle(node).then(|| Self {
+                        root: root.clone(),
+                        path: path.clone(),
+                        source_entry: None,
+                    })
+                }
+                ValueData::Object(_) => {
+                    let json = value.to_json()?;
+                    is_bundle(&json).then(|| Self {
+                        root: Arc::new(json),
+                        path: SmallVec::new(),
+                        source_entry: None,
+                    })
+                }
+                _ => None,
+            })
+    }
+
+    fn entries(&self) -> &[JsonValue] {
+        resolve_json_at(self.root.as_ref(), &self.path)
+            .and_then(|bundle| bundle.get("entry"))
+            .and_then(|entry| entry.as_array())
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    fn entry_resource(&self, index: usize) -> Option<Value> {
+        let mut path = self.path.clone();
+        path.push(JsonPathToken::Key(Arc::from("entry")));
+        path.push(JsonPathToken::Index(index));
+        path.push(JsonPathToken::Key(Arc::from("resource")));
+        let node = resolve_json_at(self.root.as_ref(), &path)?;
+        Some(Value::from_json_node(self.root.clone(), path, node))
+    }
+
+    fn resolve(&self, reference: &str) -> Option<Value> {
+        let entries = self.entries();
+        // Entries are identified by fullUrl, which never carries a version
+        let reference = reference
+            .split_once("/_history/")
+            .map_or(reference, |(unversioned, _)| unversioned);
+        let absolute = is_absolute_reference(reference);
+
+        let mut candidates = vec![reference.to_string()];
+        if !absolute {
+            // Relative references are relative to the base of the referencing entry
+            if let Some(base) = self
+                .source_entry
+                .and_then(|idx| entries.get(idx))
+                .and_then(entry_full_url)
+                .and_then(restful_base)
+            {
+                candidates.insert(0, format!("{}/{}", base, reference));
+            }
+        }
+        for candidate in &candidates {
+            if let Some(idx) = entries
+                .iter()
+                .position(|entry| entry_full_url(entry) == Some(candidate.as_str()))
+            {
+                return self.entry_resource(idx);
+            }
+        }
+
+        if absolute {
+            return None;
+        }
+        let (resource_type, id) = reference.split_once('/')?;
+        let idx = entries.iter().position(|entry| {
+            let resource = entry.get("resource");
+            let field = |name: &str| resource.and_then(|r| r.get(name)).and_then(|v| v.as_str());
+            field("resourceType") == Some(resource_type) && field("id") == Some(id)
+        })?;
+        self.entry_resource(idx)
+    }
+}
+
+fn entry_full_url(entry: &JsonValue) -> Option<&str> {
+    entry.get("fullUrl").and_then(|v| v.as_str())
+}
+
+fn is_bundle(node: &JsonValue) -> bool {
+    node.get("resourceType").and_then(|v| v.as_str()) == Some("Bundle")
+}
+
+fn is_absolute_reference(reference: &str) -> bool {
+    reference.contains("://") || reference.starts_with("urn:")
+}
+
+/// Service base of a RESTful `fullUrl` (`[base]/[type]/[id]`); `urn:` URLs have none.
+fn restful_base(full_url: &str) -> Option<&str> {
+    if !full_url.contains("://") {
+        return None;
+    }
+    let (rest, _id) = full_url.rsplit_once('/')?;
+    let (base, _type) = rest.rsplit_once('/')?;
+    Some(base)
+}
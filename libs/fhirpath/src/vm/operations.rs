@@ -4,7 +4,7 @@
 
 use crate::error::{Error, Result};
 use crate::hir::HirBinaryOperator;
-use crate::value::{Collection, Value, ValueData};
+use crate::value::{Collection, DatePrecision, Value, ValueData};
 use chrono::{Duration, Months};
 use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
@@ -258,6 +258,41 @@ fn quantity_to_duration(value: &Decimal, unit: &str) -> Result<DurationOrMonths>
     }
 }
 
+/// Whole months to shift a year- or month-precision date by
+///
+/// Per spec the quantity is first converted to the precision of the date and any
+/// fractional part is dropped, so `@2014 + 18 months` is `@2015` and `@2014 + 10 days`
+/// stays `@2014`. Days convert at 365 per year and 30 per month.
+fn partial_date_months(value: &Decimal, unit: UnitKind, precision: DatePrecision) -> Result<i32> {
+    let days = match unit {
+        UnitKind::Days => Some(*value),
+        UnitKind::Weeks => Some(*value * Decimal::from(7)),
+        _ => None,
+    };
+
+    let months = match (precision, unit, days) {
+        (DatePrecision::Year, UnitKind::Years, _) => value.trunc() * Decimal::from(12),
+        (DatePrecision::Year, UnitKind::Months, _) => {
+            (*value / Decimal::from(12)).trunc() * Decimal::from(12)
+        }
+        (DatePrecision::Year, _, Some(days)) => {
+            (days / Decimal::from(365)).trunc() * Decimal::from(12)
+        }
+        (_, UnitKind::Years, _) => (*value * Decimal::from(12)).trunc(),
+        (_, UnitKind::Months, _) => value.trunc(),
+        (_, _, Some(days)) => (days / Decimal::from(30)).trunc(),
+        _ => {
+            return Err(Error::InvalidOperation(
+                "Date arithmetic requires day/week/month/year units".into(),
+            ))
+        }
+    };
+
+    months.to_i32().ok_or_else(|| {
+        Error::InvalidOperation("Date arithmetic resulted in out of range date".into())
+    })
+}
+
 /// Execute a binary operation
 pub fn execute_binary_op(
     op: HirBinaryOperator,
@@ -443,6 +478,25 @@ fn add(left: Collection, right: Collection) -> Result<Collection> {
             }
 
             let unit_norm = normalize_unit(unit.as_ref());
+
+            // Partial dates only move in whole units of their own precision
+            if *date_prec != DatePrecision::Day {
+                let months = partial_date_months(value, unit_norm, *date_prec)?;
+                let shifted = if months >= 0 {
+                    d.checked_add_months(Months::new(months as u32))
+                } else {
+                    d.checked_sub_months(Months::new(months.unsigned_abs()))
+                };
+                return match shifted {
+                    Some(date) => Ok(Collection::singleton(Value::date_with_precision(
+                        date, *date_prec,
+                    ))),
+                    None => Err(Error::InvalidOperation(
+                        "Date arithmetic resulted in out of range date".into(),
+                    )),
+                };
+            }
+
             match unit_norm {
                 UnitKind::Days => {
                     let days = value.to_i64().unwrap_or(0);
@@ -2603,25 +2657,15 @@ where
             match (l_cal, r_cal) {
                 (Some(lc), Some(rc)) => Ok(try_ucum_compare(lv, lc, rv, rc).map(op)),
                 (Some(lc), None) => {
+                    // Calendar durations without an exact UCUM equivalent are incomparable
                     if !calendar_is_strict_equal_to_ucum(lu) {
-                        let Ok(other) = ferrum_ucum::Unit::parse(ru) else {
-                            return Ok(None);
-                        };
-                        if is_pure_time_dimension(&other.dimensions) {
-                            return Ok(None);
-                        }
                         return Ok(None);
                     }
                     Ok(try_ucum_compare(lv, lc, rv, ru).map(op))
                 }
                 (None, Some(rc)) => {
+                    // Calendar durations without an exact UCUM equivalent are incomparable
                     if !calendar_is_strict_equal_to_ucum(ru) {
-                        let Ok(other) = ferrum_ucum::Unit::parse(lu) else {
-                            return Ok(None);
-                        };
-                        if is_pure_time_dimension(&other.dimensions) {
-                            return Ok(None);
-                        }
                         return Ok(None);
                     }
                     Ok(try_ucum_compare(lv, lu, rv, rc).map(op))
@@ -2905,123 +2949,58 @@ where
 // Boolean Operations
 // ============================================
 
-fn boolean_and(left: Collection, right: Collection) -> Result<Collection> {
-    // Short-circuit: if left is false, return false without evaluating right
-    if !left.is_empty() && left.len() == 1 {
-        if let Ok(false) = left.as_boolean() {
-            return Ok(Collection::singleton(Value::boolean(false)));
-        }
-    }
-
-    // Get boolean values (None for empty, Some(bool) for non-empty)
-    let left_bool = if left.is_empty() {
+/// Operand of a boolean operator in three-valued logic
+///
+/// `None` stands for the empty collection (unknown). A non-empty, non-boolean operand
+/// counts as `true`, following singleton evaluation of collections.
+fn logic_operand(collection: &Collection) -> Option<bool> {
+    if collection.is_empty() {
         None
     } else {
-        Some(left.as_boolean().unwrap_or(true)) // Non-empty non-boolean = true
-    };
-
-    let right_bool = if right.is_empty() {
-        None
-    } else {
-        Some(right.as_boolean().unwrap_or(true)) // Non-empty non-boolean = true
-    };
-
-    // Apply FHIRPath and logic per spec:
-    // {} and true = {}
-    // {} and false = false
-    // {} and {} = {}
-    // true and {} = {}
-    // false and {} = false
-    match (left_bool, right_bool) {
-        (None, Some(false)) => Ok(Collection::singleton(Value::boolean(false))), // {} and false = false
-        (None, Some(true)) => Ok(Collection::empty()),                           // {} and true = {}
-        (None, None) => Ok(Collection::empty()),                                 // {} and {} = {}
-        (Some(false), None) => Ok(Collection::singleton(Value::boolean(false))), // false and {} = false
-        (Some(true), None) => Ok(Collection::empty()),                           // true and {} = {}
-        (Some(l), Some(r)) => Ok(Collection::singleton(Value::boolean(l && r))),
+        Some(collection.as_boolean().unwrap_or(true))
     }
 }
 
-fn boolean_or(left: Collection, right: Collection) -> Result<Collection> {
-    // Short-circuit: if left is true, return true
-    if !left.is_empty() && left.len() == 1 {
-        if let Ok(true) = left.as_boolean() {
-            return Ok(Collection::singleton(Value::boolean(true)));
-        }
-    }
-
-    // Get boolean values (None for empty, Some(bool) for non-empty)
-    let left_bool = if left.is_empty() {
-        None
-    } else {
-        Some(left.as_boolean().unwrap_or(true)) // Non-empty non-boolean = true
-    };
+fn logic_result(value: Option<bool>) -> Result<Collection> {
+    Ok(match value {
+        Some(b) => Collection::singleton(Value::boolean(b)),
+        None => Collection::empty(),
+    })
+}
 
-    let right_bool = if right.is_empty() {
-        None
-    } else {
-        Some(right.as_boolean().unwrap_or(true)) // Non-empty non-boolean = true
-    };
+fn boolean_and(left: Collection, right: Collection) -> Result<Collection> {
+    // false dominates; otherwise any empty operand makes the result empty
+    logic_result(match (logic_operand(&left), logic_operand(&right)) {
+        (Some(false), _) | (_, Some(false)) => Some(false),
+        (Some(true), Some(true)) => Some(true),
+        _ => None,
+    })
+}
 
-    // Apply FHIRPath or logic per spec:
-    // {} or true = true
-    // {} or false = {}
-    // {} or {} = {}
-    // false or {} = {}
-    // true or {} = true
-    match (left_bool, right_bool) {
-        (None, Some(true)) => Ok(Collection::singleton(Value::boolean(true))), // {} or true = true
-        (None, Some(false)) => Ok(Collection::empty()),                        // {} or false = {}
-        (None, None) => Ok(Collection::empty()),                               // {} or {} = {}
-        (Some(false), None) => Ok(Collection::empty()),                        // false or {} = {}
-        (Some(true), None) => Ok(Collection::singleton(Value::boolean(true))), // true or {} = true
-        (Some(l), Some(r)) => Ok(Collection::singleton(Value::boolean(l || r))),
-    }
+fn boolean_or(left: Collection, right: Collection) -> Result<Collection> {
+    // true dominates; otherwise any empty operand makes the result empty
+    logic_result(match (logic_operand(&left), logic_operand(&right)) {
+        (Some(true), _) | (_, Some(true)) => Some(true),
+        (Some(false), Some(false)) => Some(false),
+        _ => None,
+    })
 }
 
 fn boolean_xor(left: Collection, right: Collection) -> Result<Collection> {
-    if left.is_empty() || right.is_empty() {
-        return Ok(Collection::empty());
-    }
-
-    let left_bool = left.as_boolean().unwrap_or(false);
-    let right_bool = right.as_boolean().unwrap_or(false);
-
-    Ok(Collection::singleton(Value::boolean(
-        left_bool ^ right_bool,
-    )))
+    // Both operands are needed; an empty operand always yields empty
+    logic_result(match (logic_operand(&left), logic_operand(&right)) {
+        (Some(l), Some(r)) => Some(l ^ r),
+        _ => None,
+    })
 }
 
 fn boolean_implies(left: Collection, right: Collection) -> Result<Collection> {
-    // Get boolean values (None for empty, Some(bool) for non-empty)
-    let left_bool = if left.is_empty() {
-        None
-    } else {
-        Some(left.as_boolean().unwrap_or(true)) // Non-empty non-boolean = true
-    };
-
-    let right_bool = if right.is_empty() {
-        None
-    } else {
-        Some(right.as_boolean().unwrap_or(true)) // Non-empty non-boolean = true
-    };
-
-    // Apply FHIRPath implies logic per spec:
-    // {} implies true = true
-    // {} implies false = {}
-    // {} implies {} = {}
-    // false implies X = true
-    // true implies {} = {}
-    // true implies true = true
-    // true implies false = false
-    match (left_bool, right_bool) {
-        (None, Some(true)) => Ok(Collection::singleton(Value::boolean(true))), // {} implies true = true
-        (None, Some(false)) => Ok(Collection::empty()), // {} implies false = {}
-        (None, None) => Ok(Collection::empty()),        // {} implies {} = {}
-        (Some(false), _) => Ok(Collection::singleton(Value::boolean(true))), // false implies X = true
-        (Some(true), None) => Ok(Collection::empty()),                       // true implies {} = {}
-        (Some(true), Some(r)) => Ok(Collection::singleton(Value::boolean(r))), // true implies r = r
-    }
+    // false implies anything and anything implies true; true implies r is r
+    logic_result(match (logic_operand(&left), logic_operand(&right)) {
+        (Some(false), _) | (_, Some(true)) => Some(true),
+        (Some(true), right) => right,
+        (None, _) => None,
+    })
 }
 
 // ============================================
@@ -3144,4 +3123,253 @@ mod tests {
             "date vs datetime with time precision should be incomparable"
         );
     }
+
+    /// Operands of the truth tables: true, false, empty
+    const T: Option<bool> = Some(true);
+    const F: Option<bool> = Some(false);
+    const E: Option<bool> = None;
+
+    fn operand(value: Option<bool>) -> Collection {
+        match value {
+            Some(b) => Collection::singleton(Value::boolean(b)),
+            None => Collection::empty(),
+        }
+    }
+
+    fn assert_truth_table(
+        op: HirBinaryOperator,
+        table: [(Option<bool>, Option<bool>, Option<bool>); 9],
+    ) {
+        for (left, right, expected) in table {
+            let result = execute_binary_op(op, operand(left), operand(right)).unwrap();
+            let actual = if result.is_empty() {
+                None
+            } else {
+                Some(result.as_boolean().unwrap())
+            };
+            assert_eq!(
+                actual, expected,
+                "{:?} {:?} {:?} should be {:?}",
+                left, op, right, expected
+            );
+        }
+    }
+
+    fn ymd(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn date(y: i32, m: u32, d: u32, precision: DatePrecision) -> Collection {
+        Collection::singleton(Value::date_with_precision(ymd(y, m, d), precision))
+    }
+
+    fn quantity(value: i64, unit: &str) -> Collection {
+        Collection::singleton(Value::quantity(Decimal::from(value), Arc::from(unit)))
+    }
+
+    fn date_result(result: Collection) -> (NaiveDate, DatePrecision) {
+        match result.get(0).unwrap().data() {
+            ValueData::Date { value, precision } => (*value, *precision),
+            other => panic!("expected date, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn adding_months_clamps_to_month_end() {
+        let result = execute_binary_op(
+            HirBinaryOperator::Add,
+            date(2013, 1, 31, DatePrecision::Day),
+            quantity(1, "month"),
+        )
+        .unwrap();
+        assert_eq!(date_result(result), (ymd(2013, 2, 28), DatePrecision::Day));
+
+        let result = execute_binary_op(
+            HirBinaryOperator::Sub,
+            date(2013, 3, 31, DatePrecision::Day),
+            quantity(1, "month"),
+        )
+        .unwrap();
+        assert_eq!(date_result(result).0, ymd(2013, 2, 28));
+    }
+
+    #[test]
+    fn calendar_arithmetic_respects_leap_years() {
+        let result = execute_binary_op(
+            HirBinaryOperator::Add,
+            date(2012, 1, 31, DatePrecision::Day),
+            quantity(1, "month"),
+        )
+        .unwrap();
+        assert_eq!(date_result(result).0, ymd(2012, 2, 29));
+
+        let result = execute_binary_op(
+            HirBinaryOperator::Add,
+            date(2012, 2, 29, DatePrecision::Day),
+            quantity(1, "year"),
+        )
+        .unwrap();
+        assert_eq!(date_result(result).0, ymd(2013, 2, 28));
+    }
+
+    #[test]
+    fn partial_dates_keep_their_precision() {
+        let result = execute_binary_op(
+            HirBinaryOperator::Add,
+            date(2013, 1, 1, DatePrecision::Year),
+            quantity(10, "days"),
+        )
+        .unwrap();
+        assert_eq!(date_result(result), (ymd(2013, 1, 1), DatePrecision::Year));
+
+        let result = execute_binary_op(
+            HirBinaryOperator::Add,
+            date(2013, 1, 1, DatePrecision::Year),
+            quantity(400, "days"),
+        )
+        .unwrap();
+        assert_eq!(date_result(result), (ymd(2014, 1, 1), DatePrecision::Year));
+
+        let result = execute_binary_op(
+            HirBinaryOperator::Add,
+            date(2014, 1, 1, DatePrecision::Year),
+            quantity(18, "months"),
+        )
+        .unwrap();
+        assert_eq!(date_result(result).0, ymd(2015, 1, 1));
+
+        let result = execute_binary_op(
+            HirBinaryOperator::Sub,
+            date(2013, 3, 1, DatePrecision::Month),
+            quantity(45, "days"),
+        )
+        .unwrap();
+        assert_eq!(date_result(result), (ymd(2013, 2, 1), DatePrecision::Month));
+    }
+
+    #[test]
+    fn ucum_calendar_units_are_rejected_for_dates() {
+        for unit in ["mo", "a"] {
+            assert!(execute_binary_op(
+                HirBinaryOperator::Add,
+                date(2013, 1, 31, DatePrecision::Day),
+                quantity(1, unit),
+            )
+            .is_err());
+        }
+    }
+
+    #[test]
+    fn and_truth_table() {
+        assert_truth_table(
+            HirBinaryOperator::And,
+            [
+                (T, T, T),
+                (T, F, F),
+                (T, E, E),
+                (F, T, F),
+                (F, F, F),
+                (F, E, F),
+                (E, T, E),
+                (E, F, F),
+                (E, E, E),
+            ],
+        );
+    }
+
+    #[test]
+    fn or_truth_table() {
+        assert_truth_table(
+            HirBinaryOperator::Or,
+            [
+                (T, T, T),
+                (T, F, T),
+                (T, E, T),
+                (F, T, T),
+                (F, F, F),
+                (F, E, E),
+                (E, T, T),
+                (E, F, E),
+                (E, E, E),
+            ],
+        );
+    }
+
+    #[test]
+    fn xor_truth_table() {
+        assert_truth_table(
+            HirBinaryOperator::Xor,
+            [
+                (T, T, F),
+                (T, F, T),
+                (T, E, E),
+                (F, T, T),
+                (F, F, F),
+                (F, E, E),
+                (E, T, E),
+                (E, F, E),
+                (E, E, E),
+            ],
+        );
+    }
+
+    #[test]
+    fn implies_truth_table() {
+        assert_truth_table(
+            HirBinaryOperator::Implies,
+            [
+                (T, T, T),
+                (T, F, F),
+                (T, E, E),
+                (F, T, T),
+                (F, F, T),
+                (F, E, T),
+                (E, T, T),
+                (E, F, E),
+                (E, E, E),
+            ],
+        );
+    }
+
+    #[test]
+    fn non_boolean_singleton_operand_counts_as_true() {
+        let one = || Collection::singleton(Value::integer(1));
+
+        for op in [HirBinaryOperator::And, HirBinaryOperator::Xor] {
+            let result = execute_binary_op(op, one(), operand(T)).unwrap();
+            let expected = op == HirBinaryOperator::And;
+            assert_eq!(result.as_boolean().unwrap(), expected, "{:?}", op);
+        }
+    }
+
+    fn quantity_op(op: HirBinaryOperator, left: Collection, right: Collection) -> Option<bool> {
+        let result = execute_binary_op(op, left, right).unwrap();
+        (!result.is_empty()).then(|| result.as_boolean().unwrap())
+    }
+
+    #[test]
+    fn commensurable_quantities_compare_after_ucum_conversion() {
+        use HirBinaryOperator::*;
+
+        assert_eq!(quantity_op(Eq, quantity(1, "m"), quantity(100, "cm")), T);
+        assert_eq!(quantity_op(Ne, quantity(1, "m"), quantity(100, "cm")), F);
+        assert_eq!(quantity_op(Gt, quantity(1, "m"), quantity(90, "cm")), T);
+        assert_eq!(quantity_op(Lt, quantity(1, "m"), quantity(90, "cm")), F);
+        assert_eq!(quantity_op(Ge, quantity(1000, "g"), quantity(1, "kg")), T);
+        assert_eq!(quantity_op(Le, quantity(999, "g"), quantity(1, "kg")), T);
+    }
+
+    #[test]
+    fn incommensurable_quantities_are_incomparable() {
+        use HirBinaryOperator::*;
+
+        for op in [Eq, Lt, Le, Gt, Ge] {
+            assert_eq!(
+                quantity_op(op, quantity(1, "m"), quantity(1, "kg")),
+                E,
+                "{:?}",
+                op
+            );
+        }
+    }
 }
@@ -416,6 +416,172 @@ fn test_repeat() {
     assert!(values.contains(&3));
 }
 
+#[test]
+fn test_repeat_walks_nested_questionnaire_items() {
+    use serde_json::json;
+
+    // Two structurally identical display items in different groups must both be returned
+    let questionnaire = Value::from_json(json!({
+        "resourceType": "Questionnaire",
+        "status": "active",
+        "item": [
+            {
+                "linkId": "1",
+                "type": "group",
+                "item": [
+                    {"linkId": "1.1", "type": "string"},
+                    {
+                        "linkId": "1.2",
+                        "type": "group",
+                        "item": [{"linkId": "1.2.1", "type": "boolean"}]
+                    },
+                    {"text": "See notes", "type": "display"}
+                ]
+            },
+            {
+                "linkId": "2",
+                "type": "group",
+                "item": [{"text": "See notes", "type": "display"}]
+            }
+        ]
+    }));
+
+    let result = eval("Questionnaire.repeat(item)", questionnaire.clone());
+    assert_eq!(result.len(), 7);
+
+    let link_ids = eval("Questionnaire.repeat(item).linkId", questionnaire.clone());
+    let mut link_ids: Vec<String> = link_ids
+        .iter()
+        .map(|v| v.data().as_string().unwrap().to_string())
+        .collect();
+    link_ids.sort();
+    assert_eq!(link_ids, vec!["1", "1.1", "1.2", "1.2.1", "2"]);
+
+    // Projections that lead back to nodes already in the output terminate
+    let result = eval("Questionnaire.repeat(item | %resource.item)", questionnaire);
+    assert_eq!(result.len(), 7);
+}
+
+#[test]
+fn test_index_in_where_and_select() {
+    use serde_json::json;
+
+    let patient = Value::from_json(json!({
+        "resourceType": "Patient",
+        "name": [
+            {"family": "Chalmers", "given": ["Peter", "James"]},
+            {"family": "Windsor", "given": ["Jim"]},
+            {"family": "Nuclear", "given": ["Jimmy", "J"]}
+        ]
+    }));
+
+    let result = eval("Patient.name.where($index = 0).family", patient.clone());
+    assert_eq!(result.len(), 1);
+    assert_eq!(result.as_string().unwrap().as_ref(), "Chalmers");
+
+    let result = eval("Patient.name.where($index > 0).family", patient.clone());
+    assert_eq!(result.len(), 2);
+
+    let indexes: Vec<i64> = eval("Patient.name.select($index)", patient.clone())
+        .iter()
+        .map(|v| match v.data() {
+            ferrum_fhirpath::value::ValueData::Integer(i) => *i,
+            _ => panic!("Expected integer"),
+        })
+        .collect();
+    assert_eq!(indexes, vec![0, 1, 2]);
+
+    // The inner iteration shadows $index; the outer one is restored afterwards
+    let result = eval(
+        "Patient.name.select(given.where($index = 1))",
+        patient.clone(),
+    );
+    let given: Vec<String> = result
+        .iter()
+        .map(|v| v.data().as_string().unwrap().to_string())
+        .collect();
+    assert_eq!(given, vec!["James", "J"]);
+
+    let result = eval(
+        "Patient.name.where(given.where($index = 0).exists() and $index = 2).family",
+        patient.clone(),
+    );
+    assert_eq!(result.as_string().unwrap().as_ref(), "Nuclear");
+
+    // repeat() numbers items in the order they are processed
+    let result = eval_empty("(10 | 20 | 30).repeat(iif($index = 0, $this + 1, {}))");
+    assert_eq!(result.as_integer().unwrap(), 11);
+}
+
+// ============================================
+// Tree Navigation Functions
+// ============================================
+
+fn nested_observation() -> Value {
+    use serde_json::json;
+
+    Value::from_json(json!({
+        "resourceType": "Observation",
+        "status": "final",
+        "_status": {"extension": [{"url": "http://example.org/status-note", "valueString": "x"}]},
+        "code": {"text": "Blood pressure"},
+        "component": [{
+            "code": {"coding": [{"system": "http://loinc.org", "code": "8480-6"}]},
+            "valueQuantity": {"value": 120, "unit": "mmHg"}
+        }]
+    }))
+}
+
+#[test]
+fn test_children_returns_top_level_members() {
+    let observation = nested_observation();
+
+    // status, code and component; not resourceType or the `_status` companion
+    let result = eval("Observation.children()", observation.clone());
+    assert_eq!(result.len(), 3);
+    let result = eval(
+        "Observation.children().where($this = 'final')",
+        observation.clone(),
+    );
+    assert_eq!(result.len(), 1);
+    assert!(eval(
+        "Observation.children().where($this = 'Observation')",
+        observation.clone()
+    )
+    .is_empty());
+    assert!(eval(
+        "Observation.children().where($this = '8480-6')",
+        observation.clone()
+    )
+    .is_empty());
+
+    let result = eval("Observation.children().text", observation);
+    assert_eq!(result.as_string().unwrap().as_ref(), "Blood pressure");
+}
+
+#[test]
+fn test_descendants_reaches_nested_fields() {
+    let observation = nested_observation();
+
+    let result = eval(
+        "Observation.descendants().where($this = '8480-6')",
+        observation.clone(),
+    );
+    assert_eq!(result.len(), 1);
+
+    // status, code, code.text, component, its code and valueQuantity, the coding,
+    // its system and code, and the quantity's value and unit
+    let result = eval("Observation.descendants()", observation.clone());
+    assert_eq!(result.len(), 11);
+
+    // Primitive extensions are not reached through their `_status` companion
+    assert!(eval(
+        "Observation.descendants().where($this = 'http://example.org/status-note')",
+        observation,
+    )
+    .is_empty());
+}
+
 // ============================================
 // Conversion Functions
 // ============================================
@@ -733,6 +899,167 @@ fn test_filtering_functions() {
     assert_eq!(result.len(), 0);
 }
 
+#[test]
+fn test_resolve_follows_references_across_bundle_entries() {
+    use ferrum_fhirpath::{EvalOptions, ResourceResolver};
+    use serde_json::json;
+    use std::sync::Arc;
+
+    struct ServerResolver;
+
+    impl ResourceResolver for ServerResolver {
+        fn resolve(&self, reference: &str) -> ferrum_fhirpath::Result<Option<Value>> {
+            Ok((reference == "Practitioner/pr1").then(|| {
+                Value::from_json(json!({
+                    "resourceType": "Practitioner",
+                    "id": "pr1",
+                    "name": [{"family": "Careful"}]
+                }))
+            }))
+        }
+    }
+
+    let bundle = Value::from_json(json!({
+        "resourceType": "Bundle",
+        "type": "collection",
+        "entry": [
+            {
+                "fullUrl": "http://example.org/fhir/Observation/o1",
+                "resource": {
+                    "resourceType": "Observation",
+                    "id": "o1",
+                    "status": "final",
+                    "code": {"text": "heart rate"},
+                    "subject": {"reference": "Patient/p1"},
+                    "performer": [
+                        {"reference": "urn:uuid:2b4e0a4c-5d43-4a52-9d4b-0f7e5c3b1a11"},
+                        {"reference": "Practitioner/pr1"}
+                    ]
+                }
+            },
+            {
+                "fullUrl": "http://example.org/fhir/Patient/p1",
+                "resource": {
+                    "resourceType": "Patient",
+                    "id": "p1",
+                    "name": [{"family": "Chalmers"}]
+                }
+            },
+            {
+                "fullUrl": "urn:uuid:2b4e0a4c-5d43-4a52-9d4b-0f7e5c3b1a11",
+                "resource": {
+                    "resourceType": "Organization",
+                    "name": "Acme Lab"
+                }
+            }
+        ]
+    }));
+
+    let result = eval(
+        "Bundle.entry.resource.ofType(Observation).subject.resolve().name.family",
+        bundle.clone(),
+    );
+    assert_eq!(result.as_string().unwrap().as_ref(), "Chalmers");
+
+    // Absolute (urn:uuid) references match fullUrl; references outside the Bundle are
+    // dropped unless a resolver can supply them
+    let expr = "Bundle.entry.resource.ofType(Observation).performer.resolve()";
+    let result = eval(expr, bundle.clone());
+    assert_eq!(result.len(), 1);
+    assert_eq!(
+        eval(&format!("{}.name", expr), bundle.clone())
+            .as_string()
+            .unwrap()
+            .as_ref(),
+        "Acme Lab"
+    );
+
+    let ctx = Context::new(bundle);
+    let options = EvalOptions {
+        resource_resolver: Some(Arc::new(ServerResolver)),
+        ..EvalOptions::default()
+    };
+    let result = get_test_engine()
+        .evaluate_expr_with_options(&format!("{}.name.family", expr), &ctx, options)
+        .unwrap();
+    assert_eq!(result.as_string().unwrap().as_ref(), "Careful");
+}
+
+#[test]
+fn test_navigating_into_primitive_is_empty_unless_strict() {
+    use ferrum_fhirpath::CompileOptions;
+    use serde_json::json;
+
+    let engine = get_test_engine();
+    let patient = Value::from_json(json!({
+        "resourceType": "Patient",
+        "birthDate": "1974-12-25"
+    }));
+
+    let result = eval("Patient.birthDate.family", patient);
+    assert_eq!(result.len(), 0);
+
+    let strict = CompileOptions {
+        base_type: Some("Patient".to_string()),
+        strict: true,
+    };
+    let err = engine
+        .compile_with_options("Patient.birthDate.family", strict.clone())
+        .unwrap_err();
+    assert!(err.to_string().contains("family"));
+
+    // Primitive elements still expose `id` and `extension`
+    assert!(engine
+        .compile_with_options("Patient.birthDate.extension.url", strict)
+        .is_ok());
+}
+
+#[test]
+fn test_extension_selects_by_url() {
+    use serde_json::json;
+
+    let race = "http://hl7.org/fhir/us/core/StructureDefinition/us-core-race";
+    let ethnicity = "http://hl7.org/fhir/us/core/StructureDefinition/us-core-ethnicity";
+    let patient = Value::from_json(json!({
+        "resourceType": "Patient",
+        "extension": [
+            {"url": race, "valueString": "2106-3"},
+            {"url": ethnicity, "valueString": "2186-5"}
+        ],
+        "name": [{
+            "family": "Chalmers",
+            "extension": [{"url": "http://example.org/name-note", "valueString": "legal"}]
+        }],
+        "birthDate": "1974-12-25",
+        "_birthDate": {
+            "extension": [{"url": "http://example.org/birth-time", "valueDateTime": "1974-12-25T14:35:45-05:00"}]
+        }
+    }));
+
+    let result = eval(&format!("Patient.extension('{}')", race), patient.clone());
+    assert_eq!(result.len(), 1);
+    let value = eval(
+        &format!("Patient.extension('{}').value", race),
+        patient.clone(),
+    );
+    assert_eq!(value.as_string().unwrap().as_ref(), "2106-3");
+
+    // Works on any element carrying extensions, including primitives via `_field`
+    let result = eval(
+        "Patient.name.extension('http://example.org/name-note')",
+        patient.clone(),
+    );
+    assert_eq!(result.len(), 1);
+    let result = eval(
+        "Patient.birthDate.extension('http://example.org/birth-time')",
+        patient.clone(),
+    );
+    assert_eq!(result.len(), 1);
+
+    let result = eval("Patient.extension('http://example.org/unknown')", patient);
+    assert_eq!(result.len(), 0);
+}
+
 #[test]
 fn test_combine() {
     // combine() - merge collections without deduplication
@@ -1,8 +1,8 @@
 #![allow(dead_code)]
 
-use std::sync::{Arc, OnceLock};
 use ferrum_context::{DefaultFhirContext, FhirContext};
 use ferrum_fhirpath::Engine;
+use std::sync::{Arc, OnceLock};
 use tokio::runtime::Runtime;
 
 static RUNTIME: OnceLock<Runtime> = OnceLock::new();
@@ -524,3 +524,106 @@ fn test_priority_runtime_over_declared() {
         "Should not match other declared types"
     );
 }
+
+#[test]
+fn test_choice_value_base_types() {
+    // Choice values satisfy their concrete type and the bases from the FHIR type hierarchy
+    let engine = test_support::engine_r5();
+
+    let obs_json = json!({
+        "resourceType": "Observation",
+        "status": "final",
+        "code": {
+            "coding": [{
+                "system": "http://loinc.org",
+                "code": "15074-8"
+            }]
+        },
+        "valueQuantity": {
+            "value": 140,
+            "unit": "mg/dL"
+        }
+    });
+    let obs = Value::from_json(obs_json);
+    let ctx = Context::new(obs);
+
+    let result = engine
+        .evaluate_expr("Observation.value.ofType(Quantity).count()", &ctx, None)
+        .unwrap();
+    assert_eq!(
+        result.as_integer().unwrap(),
+        1,
+        "ofType(Quantity) should keep the Quantity value"
+    );
+
+    let result = engine
+        .evaluate_expr("Observation.value.ofType(Period).count()", &ctx, None)
+        .unwrap();
+    assert_eq!(
+        result.as_integer().unwrap(),
+        0,
+        "ofType(Period) should drop the Quantity value"
+    );
+
+    let result = engine
+        .evaluate_expr("Observation.value is Element", &ctx, None)
+        .unwrap();
+    assert!(
+        result.as_boolean().unwrap(),
+        "Quantity should inherit from Element"
+    );
+
+    let result = engine
+        .evaluate_expr("Observation.status is Element", &ctx, None)
+        .unwrap();
+    assert!(
+        result.as_boolean().unwrap(),
+        "code should inherit from Element"
+    );
+
+    let result = engine
+        .evaluate_expr("Observation.value is Resource", &ctx, None)
+        .unwrap();
+    assert!(
+        !result.as_boolean().unwrap(),
+        "Quantity should not match Resource"
+    );
+}
+
+#[test]
+fn test_resource_base_types() {
+    // Resources satisfy their base types through the baseDefinition chain
+    let engine = test_support::engine_r5();
+
+    let patient_json = json!({
+        "resourceType": "Patient",
+        "id": "example",
+        "active": true
+    });
+    let patient = Value::from_json(patient_json);
+    let ctx = Context::new(patient);
+
+    let result = engine
+        .evaluate_expr("Patient is DomainResource", &ctx, None)
+        .unwrap();
+    assert!(
+        result.as_boolean().unwrap(),
+        "Patient should inherit from DomainResource"
+    );
+
+    let result = engine
+        .evaluate_expr("Patient.is(Resource)", &ctx, None)
+        .unwrap();
+    assert!(
+        result.as_boolean().unwrap(),
+        "Patient should inherit from Resource"
+    );
+
+    let result = engine
+        .evaluate_expr("Patient is Element", &ctx, None)
+        .unwrap();
+    assert!(
+        !result.as_boolean().unwrap(),
+        "Patient should not match datatype bases"
+    );
+}
@@ -107,7 +107,39 @@ fn test_evaluate_xml_invalid() {
 
     // Should return an error for invalid XML
     let result = engine.evaluate_xml("Patient.id", invalid_xml, None);
-    assert!(result.is_err());
+    assert!(matches!(result, Err(ferrum_fhirpath::Error::ParseError(_))));
+}
+
+#[cfg(feature = "xml-support")]
+#[test]
+fn test_evaluate_xml_name_family() {
+    use ferrum_fhirpath::value::ValueData;
+    use ferrum_fhirpath::Engine;
+
+    let context = test_support::context_r4().clone();
+    let engine = Engine::new(context, None);
+
+    let xml = r#"<Patient xmlns="http://hl7.org/fhir">
+        <name>
+            <family value="Doe"/>
+            <given value="John"/>
+        </name>
+        <name>
+            <family value="Smith"/>
+        </name>
+    </Patient>"#;
+
+    let result = engine
+        .evaluate_xml("Patient.name.family", xml, None)
+        .unwrap();
+    let families: Vec<String> = result
+        .iter()
+        .map(|v| match v.data() {
+            ValueData::String(s) => s.to_string(),
+            other => panic!("Expected string, got {:?}", other),
+        })
+        .collect();
+    assert_eq!(families, vec!["Doe", "Smith"]);
 }
 
 #[cfg(not(feature = "xml-support"))]
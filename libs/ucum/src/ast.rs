@@ -1,3 +1,5 @@
+use std::fmt;
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct UnitExpr {
     pub numerator: Vec<(Term, i32)>,
@@ -13,10 +15,61 @@ impl UnitExpr {
     }
 }
 
+/// Canonical string of the expression, e.g. `kg.m.s-2` renders as `kg.m/s2`.
+///
+/// Annotations are kept as written; parsing the output yields an equal expression.
+impl fmt::Display for UnitExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.numerator.is_empty() && self.denominator.is_empty() {
+            return f.write_str("1");
+        }
+        for (i, (term, exp)) in self.numerator.iter().enumerate() {
+            if i > 0 {
+                f.write_str(".")?;
+            }
+            write_factor(f, term, *exp)?;
+        }
+        for (term, exp) in &self.denominator {
+            f.write_str("/")?;
+            write_factor(f, term, *exp)?;
+        }
+        Ok(())
+    }
+}
+
+fn write_factor(f: &mut fmt::Formatter<'_>, term: &Term, exp: i32) -> fmt::Result {
+    let (term, annotation) = match term {
+        Term::Annotated(inner, annotation) => (inner.as_ref(), Some(annotation)),
+        other => (other, None),
+    };
+    match term {
+        Term::Atom(atom) => write!(f, "{atom}")?,
+        Term::Group(group) => write!(f, "({group})")?,
+        Term::Annotated(..) => write!(f, "{term}")?,
+    }
+    if exp != 1 {
+        write!(f, "{exp}")?;
+    }
+    if let Some(annotation) = annotation {
+        write!(f, "{{{annotation}}}")?;
+    }
+    Ok(())
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Term {
     Atom(Atom),
     Group(Box<UnitExpr>),
+    /// A unit symbol followed by an annotation (e.g. `mg{creat}`, `m2{area}`).
+    ///
+    /// The annotation carries no dimension; the exponent of the factor applies to the symbol.
+    Annotated(Box<Term>, String),
+}
+
+impl fmt::Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_factor(f, self, 1)
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -25,4 +78,16 @@ pub enum Atom {
     Symbol(String),
     /// A positive integer scalar (e.g. `12` in `[ligne]/12`).
     Integer(u64),
+    /// A standalone annotation (e.g. `{RBC}`), equivalent to the unity `1`.
+    Annotation(String),
+}
+
+impl fmt::Display for Atom {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Atom::Symbol(s) => f.write_str(s),
+            Atom::Integer(n) => write!(f, "{n}"),
+            Atom::Annotation(s) => write!(f, "{{{s}}}"),
+        }
+    }
 }
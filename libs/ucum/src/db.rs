@@ -12,6 +12,10 @@ pub struct UcumDb {
     pub prefixes: HashMap<String, BigRational>,
     pub base_units: HashMap<String, DimensionVector>,
     pub units: HashMap<String, UnitDef>,
+    /// First `<name>` of each prefix (e.g. `m` -> "milli").
+    pub prefix_names: HashMap<String, String>,
+    /// First `<name>` of each base unit and unit (e.g. `m[Hg]` -> "meter of mercury column").
+    pub unit_names: HashMap<String, String>,
 }
 
 #[derive(Clone, Debug)]
@@ -51,6 +55,8 @@ impl UcumDb {
         let mut prefixes: HashMap<String, BigRational> = HashMap::new();
         let mut base_units: HashMap<String, DimensionVector> = HashMap::new();
         let mut units: HashMap<String, UnitDef> = HashMap::new();
+        let mut prefix_names: HashMap<String, String> = HashMap::new();
+        let mut unit_names: HashMap<String, String> = HashMap::new();
 
         #[derive(Default)]
         struct PrefixBuilder {
@@ -72,6 +78,8 @@ impl UcumDb {
 
         let mut cur_prefix: Option<PrefixBuilder> = None;
         let mut cur_unit: Option<UnitBuilder> = None;
+        let mut cur_base_unit: Option<String> = None;
+        let mut in_name = false;
 
         let mut buf = Vec::new();
         loop {
@@ -93,8 +101,10 @@ impl UcumDb {
                         let dv = DimensionVector::from_ucum_dim(&dim).ok_or_else(|| {
                             Error::Db(format!("unknown UCUM base dimension '{dim}'"))
                         })?;
-                        base_units.insert(code, dv);
+                        base_units.insert(code.clone(), dv);
+                        cur_base_unit = Some(code);
                     }
+                    b"name" => in_name = true,
                     b"unit" => {
                         let code = attr(&e, b"Code")?
                             .ok_or_else(|| Error::Db("unit without Code".into()))?;
@@ -186,7 +196,26 @@ impl UcumDb {
                         _ => {}
                     }
                 }
+                Event::Text(t) if in_name => {
+                    // Units can carry several names; the first one is used.
+                    let name = t.unescape().map_err(|e| Error::Db(e.to_string()))?;
+                    if let Some(p) = cur_prefix.as_ref() {
+                        prefix_names
+                            .entry(p.code.clone())
+                            .or_insert_with(|| name.into_owned());
+                    } else if let Some(code) = cur_unit
+                        .as_ref()
+                        .map(|u| &u.code)
+                        .or(cur_base_unit.as_ref())
+                    {
+                        unit_names
+                            .entry(code.clone())
+                            .or_insert_with(|| name.into_owned());
+                    }
+                }
                 Event::End(e) => match e.name().as_ref() {
+                    b"name" => in_name = false,
+                    b"base-unit" => cur_base_unit = None,
                     b"prefix" => {
                         if let Some(p) = cur_prefix.take() {
                             let value = p.value.ok_or_else(|| {
@@ -243,6 +272,8 @@ impl UcumDb {
             prefixes,
             base_units,
             units,
+            prefix_names,
+            unit_names,
         })
     }
 }
@@ -0,0 +1,104 @@
+use crate::ast::{Atom, Term, UnitExpr};
+
+/// Describe a parsed unit expression in words, e.g. `mg/dL` -> "milligram per deciliter".
+///
+/// Names come from the embedded UCUM essence; symbols it does not know are kept as written.
+pub fn describe(expr: &UnitExpr) -> String {
+    let mut numerator = Vec::new();
+    let mut denominator = Vec::new();
+
+    // A negative exponent moves a factor to the other side (`s-1` reads "per second").
+    for (term, exp) in &expr.numerator {
+        let side = if *exp < 0 {
+            &mut denominator
+        } else {
+            &mut numerator
+        };
+        side.push(describe_factor(term, exp.abs()));
+    }
+    for (term, exp) in &expr.denominator {
+        let side = if *exp < 0 {
+            &mut numerator
+        } else {
+            &mut denominator
+        };
+        side.push(describe_factor(term, exp.abs()));
+    }
+
+    // `1/min` reads "per minute", but a lone `1` stays.
+    if numerator.len() > 1 || !denominator.is_empty() {
+        numerator.retain(|part| part != "1");
+    }
+
+    let mut out = numerator.join(" ");
+    for part in denominator {
+        if !out.is_empty() {
+            out.push(' ');
+        }
+        out.push_str("per ");
+        out.push_str(&part);
+    }
+    out
+}
+
+fn describe_factor(term: &Term, exp: i32) -> String {
+    let base = match term {
+        Term::Atom(Atom::Symbol(s)) if s == "10*" || s == "10^" => {
+            return describe_power_of_ten(exp);
+        }
+        Term::Atom(Atom::Symbol(s)) => describe_symbol(s),
+        Term::Atom(Atom::Integer(n)) => n.to_string(),
+        Term::Atom(Atom::Annotation(a)) => format!("{{{a}}}"),
+        Term::Annotated(term, a) => return format!("{} {{{a}}}", describe_factor(term, exp)),
+        Term::Group(group) => {
+            let inner = describe(group);
+            if group.numerator.len() + group.denominator.len() > 1 {
+                format!("({inner})")
+            } else {
+                inner
+            }
+        }
+    };
+
+    match exp {
+        1 => base,
+        2 => format!("{base} squared"),
+        3 => format!("{base} cubed"),
+        n => format!("{base} to the power of {n}"),
+    }
+}
+
+fn describe_power_of_ten(exp: i32) -> String {
+    match exp {
+        1 => "tens".to_string(),
+        2 => "hundreds".to_string(),
+        3 => "thousands".to_string(),
+        6 => "millions".to_string(),
+        9 => "billions".to_string(),
+        12 => "trillions".to_string(),
+        n => format!("ten to the power of {n}"),
+    }
+}
+
+fn describe_symbol(symbol: &str) -> String {
+    let db = crate::db();
+    if let Some(name) = db.unit_names.get(symbol) {
+        return name.clone();
+    }
+
+    // Longest prefix first, mirroring unit resolution.
+    let mut prefixes: Vec<&String> = db.prefix_names.keys().collect();
+    prefixes.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+    for prefix in prefixes {
+        let Some(rest) = symbol.strip_prefix(prefix.as_str()) else {
+            continue;
+        };
+        let prefixable =
+            db.base_units.contains_key(rest) || db.units.get(rest).is_some_and(|u| u.is_metric);
+        if let (true, Some(name)) = (prefixable, db.unit_names.get(rest)) {
+            return format!("{}{}", db.prefix_names[prefix], name);
+        }
+    }
+
+    symbol.to_string()
+}
@@ -1,5 +1,7 @@
 use crate::error::{Error, Result};
+use crate::unit::Unit;
 use rust_decimal::Decimal;
+use serde_json::Value;
 use std::cmp::Ordering;
 
 pub const UCUM_SYSTEM: &str = "http://unitsofmeasure.org";
@@ -30,3 +32,22 @@ pub fn compare(lhs: FhirQuantity<'_>, rhs: FhirQuantity<'_>) -> Result<Ordering>
         .ok_or_else(|| Error::Db("rhs is not UCUM".into()))?;
     crate::unit::compare_decimal_quantities(&lhs.value, lc, &rhs.value, rc)
 }
+
+/// Check whether a FHIR `Quantity` (as JSON) is commensurable with `expected`.
+///
+/// The quantity must be coded in UCUM: `system` is [`UCUM_SYSTEM`] and `code` is a valid
+/// UCUM expression. Other systems are an error rather than `false`.
+pub fn quantity_has_dimension(q: &Value, expected: &Unit) -> Result<bool> {
+    let system = q.get("system").and_then(Value::as_str);
+    if system != Some(UCUM_SYSTEM) {
+        return Err(Error::Db(format!(
+            "quantity system {:?} is not UCUM",
+            system.unwrap_or("<missing>")
+        )));
+    }
+    let code = q
+        .get("code")
+        .and_then(Value::as_str)
+        .ok_or_else(|| Error::Db("UCUM quantity has no code".into()))?;
+    Ok(Unit::parse(code)?.is_convertible_to(expected))
+}
@@ -2,6 +2,7 @@
 
 mod ast;
 mod db;
+mod describe;
 mod error;
 mod parser;
 mod quantity;
@@ -13,6 +14,7 @@ pub mod fhir;
 use once_cell::sync::Lazy;
 
 pub use ast::{Atom, Term, UnitExpr};
+pub use describe::describe;
 pub use error::{Error, Result};
 pub use parser::{parse, validate};
 pub use quantity::{normalize, NormalizedQuantity, Quantity};
@@ -106,6 +106,12 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_factor(&mut self) -> Result<(Term, i32)> {
+        // A standalone annotation (`{RBC}`) is the unity and takes no exponent.
+        if self.peek() == Some(b'{') {
+            let annotation = self.parse_annotation()?;
+            return Ok((Term::Atom(Atom::Annotation(annotation)), 1));
+        }
+
         let term = self.parse_term()?;
         let exp = self.parse_exponent()?.unwrap_or(1);
         if exp == 0 {
@@ -114,9 +120,45 @@ impl<'a> Parser<'a> {
                 message: "zero exponent is not allowed",
             });
         }
+
+        if self.peek() == Some(b'{') {
+            if !matches!(term, Term::Atom(Atom::Symbol(_))) {
+                return Err(Error::Syntax {
+                    pos: self.pos,
+                    message: "annotation must follow a unit symbol",
+                });
+            }
+            let annotation = self.parse_annotation()?;
+            return Ok((Term::Annotated(Box::new(term), annotation), exp));
+        }
         Ok((term, exp))
     }
 
+    /// `{...}`: any printable ASCII except curly braces; returns the text between the braces.
+    fn parse_annotation(&mut self) -> Result<String> {
+        let start = self.pos;
+        self.expect(b'{')?;
+        while let Some(b) = self.peek() {
+            match b {
+                b'}' => {
+                    self.pos += 1;
+                    return Ok(self.input[start + 1..self.pos - 1].to_string());
+                }
+                b'{' => {
+                    return Err(Error::Syntax {
+                        pos: self.pos,
+                        message: "nested annotation",
+                    });
+                }
+                _ => self.pos += 1,
+            }
+        }
+        Err(Error::Syntax {
+            pos: start,
+            message: "unclosed annotation",
+        })
+    }
+
     fn parse_term(&mut self) -> Result<Term> {
         if self.eat(b'(') {
             let expr = self.parse_expr()?;
@@ -209,7 +251,7 @@ impl<'a> Parser<'a> {
 
         while let Some(b) = self.peek() {
             match b {
-                b'(' | b')' | b'.' | b'/' => break,
+                b'(' | b')' | b'.' | b'/' | b'{' => break,
                 b'0'..=b'9' => break, // exponent begins
                 b'+' | b'-' => break, // exponent begins
                 b'[' => out.push_str(&self.parse_bracket_segment()?),
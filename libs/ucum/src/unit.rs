@@ -91,6 +91,13 @@ impl Unit {
             UnitKind::NonLinear => Err(Error::NonLinear("<non-linear>".into())),
         }
     }
+
+    /// Same dimensions and neither unit is non-linear.
+    pub fn is_convertible_to(&self, other: &Unit) -> bool {
+        self.dimensions == other.dimensions
+            && !matches!(self.kind, UnitKind::NonLinear)
+            && !matches!(other.kind, UnitKind::NonLinear)
+    }
 }
 
 pub fn equivalent(a: &str, b: &str) -> Result<bool> {
@@ -108,13 +115,7 @@ pub fn equivalent(a: &str, b: &str) -> Result<bool> {
 pub fn convertible(a: &str, b: &str) -> Result<bool> {
     let ua = Unit::parse(a)?;
     let ub = Unit::parse(b)?;
-    if ua.dimensions != ub.dimensions {
-        return Ok(false);
-    }
-    match (&ua.kind, &ub.kind) {
-        (UnitKind::NonLinear, _) | (_, UnitKind::NonLinear) => Ok(false),
-        _ => Ok(true),
-    }
+    Ok(ua.is_convertible_to(&ub))
 }
 
 pub fn compare_decimal_quantities(
@@ -255,6 +256,14 @@ fn resolve_term(state: &mut ResolveState, term: &Term) -> Result<Unit> {
             },
         }),
         Term::Atom(Atom::Symbol(s)) => resolve_symbol(state, s),
+        // Annotations carry no dimension: `{RBC}` is the unity, `mg{creat}` is `mg`.
+        Term::Atom(Atom::Annotation(_)) => Ok(Unit {
+            dimensions: DimensionVector::ZERO,
+            kind: UnitKind::Multiplicative {
+                factor: BigRational::one(),
+            },
+        }),
+        Term::Annotated(term, _) => resolve_term(state, term),
         Term::Group(g) => resolve_expr(g),
     }
 }
@@ -45,3 +45,111 @@ fn normalizes_pressure_to_pa() {
     assert_eq!(n.unit, "Pa");
     assert_eq!(n.value, Decimal::from_str("15998.64").unwrap());
 }
+
+#[test]
+fn quantity_dimension_matches_expected_unit() {
+    use ferrum_ucum::fhir::{quantity_has_dimension, UCUM_SYSTEM};
+    use serde_json::json;
+
+    let mass = ferrum_ucum::Unit::parse("g").unwrap();
+    assert_eq!(mass.dimensions, ferrum_ucum::DimensionVector::MASS);
+
+    let dose = json!({"value": 5, "system": UCUM_SYSTEM, "code": "mg"});
+    assert!(quantity_has_dimension(&dose, &mass).unwrap());
+
+    let rate = json!({"value": 72, "system": UCUM_SYSTEM, "code": "/min"});
+    assert!(!quantity_has_dimension(&rate, &mass).unwrap());
+
+    let local = json!({"value": 5, "system": "http://example.org/units", "code": "mg"});
+    assert!(quantity_has_dimension(&local, &mass).is_err());
+    let uncoded = json!({"value": 5, "unit": "mg"});
+    assert!(quantity_has_dimension(&uncoded, &mass).is_err());
+}
+
+#[test]
+fn describes_units_in_words() {
+    let describe = |code: &str| ferrum_ucum::describe(&ferrum_ucum::parse(code).unwrap());
+
+    assert_eq!(describe("mm[Hg]"), "millimeter of mercury column");
+    assert_eq!(describe("m2"), "meter squared");
+    assert_eq!(describe("mg/dL"), "milligram per deciliter");
+    assert_eq!(describe("10*3/uL"), "thousands per microliter");
+    assert_eq!(describe("/min"), "per minute");
+    assert_eq!(describe("kg.m.s-2"), "kilogram meter per second squared");
+    assert_eq!(describe("[foo]"), "[foo]");
+}
+
+#[test]
+fn converts_celsius_to_kelvin() {
+    let v = ferrum_ucum::convert_decimal(Decimal::from(37), "Cel", "K").unwrap();
+    assert_eq!(v, Decimal::from_str("310.15").unwrap());
+    let v = ferrum_ucum::convert_decimal(Decimal::ZERO, "K", "Cel").unwrap();
+    assert_eq!(v, Decimal::from_str("-273.15").unwrap());
+
+    let n = ferrum_ucum::normalize(Decimal::from(37), "Cel").unwrap();
+    assert_eq!(n.unit, "K");
+    assert_eq!(n.value, Decimal::from_str("310.15").unwrap());
+}
+
+#[test]
+fn converts_celsius_to_fahrenheit() {
+    let v = ferrum_ucum::convert_decimal(Decimal::from(37), "Cel", "[degF]").unwrap();
+    assert_eq!(v, Decimal::from_str("98.6").unwrap());
+    let v =
+        ferrum_ucum::convert_decimal(Decimal::from_str("98.6").unwrap(), "[degF]", "Cel").unwrap();
+    assert_eq!(v, Decimal::from(37));
+    let v = ferrum_ucum::convert_decimal(Decimal::from(-40), "Cel", "[degF]").unwrap();
+    assert_eq!(v, Decimal::from(-40));
+
+    assert_eq!(
+        ferrum_ucum::compare_decimal_quantities(
+            &Decimal::from(37),
+            "Cel",
+            &Decimal::from_str("98.6").unwrap(),
+            "[degF]"
+        )
+        .unwrap(),
+        std::cmp::Ordering::Equal
+    );
+}
+
+#[test]
+fn special_units_do_not_combine() {
+    assert!(ferrum_ucum::convert_decimal(Decimal::ONE, "Cel/h", "K/h").is_err());
+}
+
+#[test]
+fn validates_annotations() {
+    assert!(ferrum_ucum::validate("{RBC}").is_ok());
+    assert!(ferrum_ucum::validate("{beats}/min").is_ok());
+    assert!(ferrum_ucum::validate("mg{creat}/dL").is_ok());
+    assert!(ferrum_ucum::validate("{a{b}}").is_err());
+    assert!(ferrum_ucum::validate("{RBC").is_err());
+
+    assert!(ferrum_ucum::equivalent("{beats}/min", "/min").unwrap());
+    let v = ferrum_ucum::convert_decimal(Decimal::ONE, "mg{creat}/dL", "g/L").unwrap();
+    assert_eq!(v, Decimal::from_str("0.01").unwrap());
+}
+
+#[test]
+fn parses_power_of_ten_factor() {
+    let v = ferrum_ucum::convert_decimal(Decimal::ONE, "10*6/uL", "/L").unwrap();
+    assert_eq!(v, Decimal::from(1_000_000_000_000i64));
+    let v = ferrum_ucum::convert_decimal(Decimal::from(5), "10*6{RBC}/uL", "10*12/L").unwrap();
+    assert_eq!(v, Decimal::from(5));
+}
+
+#[test]
+fn reconstructs_canonical_string() {
+    let canonical = |s: &str| ferrum_ucum::parse(s).unwrap().to_string();
+    assert_eq!(canonical("{RBC}"), "{RBC}");
+    assert_eq!(canonical("{beats}/min"), "{beats}/min");
+    assert_eq!(canonical("10*6/uL"), "10*6/uL");
+    assert_eq!(canonical("m2{area}"), "m2{area}");
+    assert_eq!(canonical("kg.m.s-2"), "kg.m/s2");
+    assert_eq!(canonical("/min"), "/min");
+    assert_eq!(canonical("(m.s)2/[IU]"), "(m.s)2/[IU]");
+
+    let expr = ferrum_ucum::parse("10*6{cells}/uL").unwrap();
+    assert_eq!(ferrum_ucum::parse(&expr.to_string()).unwrap(), expr);
+}